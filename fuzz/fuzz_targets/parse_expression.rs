@@ -0,0 +1,10 @@
+#![no_main]
+
+use ae_script_validator::validation::expression::validate_expression;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        let _ = validate_expression(source);
+    }
+});
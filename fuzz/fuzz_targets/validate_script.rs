@@ -0,0 +1,12 @@
+#![no_main]
+
+use ae_script_validator::validation::validate_script;
+use libfuzzer_sys::fuzz_target;
+
+// Calls the unguarded entry point so libFuzzer sees panics as crashes;
+// validate_script_safe is what editor integrations should call.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(script) = std::str::from_utf8(data) {
+        let _ = validate_script(script, "fuzz.jsx");
+    }
+});
@@ -0,0 +1,211 @@
+//! Consistency checks over the API model itself, to drive cleanup of the
+//! model rather than of scripts.
+
+use std::collections::BTreeSet;
+use serde::Serialize;
+
+use super::{ApiObject, UnifiedApi};
+use crate::validation::rules::{MethodValidation, PropertyValueType, ValidationRule};
+
+/// Lower-case keys whose class is not just the key capitalized
+const ALIASES: &[(&str, &str)] = &[
+    ("app", "Application"),
+    ("items", "ItemCollection"),
+    ("layers", "LayerCollection"),
+];
+
+/// `Custom` names that duplicate a dedicated value type
+const PRIMITIVE_CUSTOM_TYPES: &[(&str, PropertyValueType)] = &[
+    ("Number", PropertyValueType::OneD),
+    ("Color", PropertyValueType::Color),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditKind {
+    /// A lower-case alias and its class have different members
+    AliasDivergence,
+    /// A property typed `Custom("Number")` where a value type exists
+    CustomPrimitiveType,
+    /// A method taking arguments with no parameter types
+    MissingParamTypes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditFinding {
+    pub kind: AuditKind,
+    pub object: String,
+    pub member: Option<String>,
+    pub detail: String,
+}
+
+/// Every inconsistency found in a model, ordered by object, kind and member
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ModelAudit {
+    pub findings: Vec<AuditFinding>,
+}
+
+impl ModelAudit {
+    pub fn count(&self, kind: AuditKind) -> usize {
+        self.findings.iter().filter(|f| f.kind == kind).count()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+        for finding in &self.findings {
+            let target = match &finding.member {
+                Some(member) => format!("{}.{}", finding.object, member),
+                None => finding.object.clone(),
+            };
+            let kind = serde_json::to_value(finding.kind).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default();
+            output.push_str(&format!("{}: {} ({})\n", target, finding.detail, kind));
+        }
+        output.push_str(&format!(
+            "{} findings: {} alias divergences, {} custom primitive types, {} methods without parameter types\n",
+            self.findings.len(),
+            self.count(AuditKind::AliasDivergence),
+            self.count(AuditKind::CustomPrimitiveType),
+            self.count(AuditKind::MissingParamTypes),
+        ));
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    fn push(&mut self, kind: AuditKind, object: &str, member: Option<&str>, detail: String) {
+        self.findings.push(AuditFinding { kind, object: object.to_string(), member: member.map(String::from), detail });
+    }
+}
+
+/// Cross-checks `api` for members that disagree between aliases, loosely
+/// typed properties and unchecked parameters
+pub fn audit_model(api: &UnifiedApi) -> ModelAudit {
+    let mut audit = ModelAudit::default();
+    let names: BTreeSet<&str> = api.objects.keys().map(String::as_str).collect();
+
+    for &name in &names {
+        let object = &api.objects[name];
+        if let Some(class) = class_of_alias(name).filter(|class| names.contains(class.as_str())) {
+            compare_alias(&mut audit, name, object, &class, &api.objects[&class]);
+            // The class itself is audited; an alias would repeat its findings
+            continue;
+        }
+
+        for (property, rule) in sorted(&object.properties) {
+            if let PropertyValueType::Custom(custom) = &rule.value_type {
+                if let Some((_, value_type)) = PRIMITIVE_CUSTOM_TYPES.iter().find(|(c, _)| c == custom) {
+                    audit.push(AuditKind::CustomPrimitiveType, name, Some(property),
+                        format!("typed Custom(\"{}\"), use {:?}", custom, value_type));
+                }
+            }
+        }
+        for (method, validation) in sorted(&object.methods) {
+            if validation.param_count > 0 && validation.param_types.is_empty() {
+                audit.push(AuditKind::MissingParamTypes, name, Some(method),
+                    format!("takes {} arguments but declares no parameter types", validation.param_count));
+            }
+        }
+    }
+
+    audit.findings.sort_by(|a, b| (&a.object, a.kind, &a.member).cmp(&(&b.object, b.kind, &b.member)));
+    audit
+}
+
+/// Class a lower-case key is an alias of
+fn class_of_alias(name: &str) -> Option<String> {
+    if let Some((_, class)) = ALIASES.iter().find(|(alias, _)| *alias == name) {
+        return Some(class.to_string());
+    }
+    let first = name.chars().next().filter(char::is_ascii_lowercase)?;
+    Some(format!("{}{}", first.to_ascii_uppercase(), &name[1..]))
+}
+
+fn compare_alias(audit: &mut ModelAudit, alias: &str, aliased: &ApiObject, class: &str, object: &ApiObject) {
+    let mut members: BTreeSet<(&str, bool)> = BTreeSet::new();
+    members.extend(aliased.methods.keys().chain(object.methods.keys()).map(|m| (m.as_str(), true)));
+    members.extend(aliased.properties.keys().chain(object.properties.keys()).map(|p| (p.as_str(), false)));
+
+    for (member, is_method) in members {
+        let detail = if is_method {
+            match (aliased.methods.get(member), object.methods.get(member)) {
+                (Some(_), None) => Some(format!("method only on '{}', missing from {}", alias, class)),
+                (None, Some(_)) => Some(format!("method only on {}, missing from '{}'", class, alias)),
+                (Some(a), Some(b)) => method_difference(a, b).map(|d| format!("'{}' and {} differ: {}", alias, class, d)),
+                (None, None) => None,
+            }
+        } else {
+            match (aliased.properties.get(member), object.properties.get(member)) {
+                (Some(_), None) => Some(format!("property only on '{}', missing from {}", alias, class)),
+                (None, Some(_)) => Some(format!("property only on {}, missing from '{}'", class, alias)),
+                (Some(a), Some(b)) => property_difference(a, b).map(|d| format!("'{}' and {} differ: {}", alias, class, d)),
+                (None, None) => None,
+            }
+        };
+        if let Some(detail) = detail {
+            audit.push(AuditKind::AliasDivergence, class, Some(member), detail);
+        }
+    }
+}
+
+fn method_difference(a: &MethodValidation, b: &MethodValidation) -> Option<String> {
+    if a.param_count != b.param_count {
+        Some(format!("{} vs {} required parameters", a.param_count, b.param_count))
+    } else if a.param_types != b.param_types || a.optional_params != b.optional_params {
+        Some(format!("parameter types {:?} vs {:?}", a.param_types, b.param_types))
+    } else if a.return_type != b.return_type {
+        Some(format!("returns {:?} vs {:?}", a.return_type, b.return_type))
+    } else {
+        None
+    }
+}
+
+fn property_difference(a: &ValidationRule, b: &ValidationRule) -> Option<String> {
+    if a.value_type != b.value_type {
+        Some(format!("typed {:?} vs {:?}", a.value_type, b.value_type))
+    } else if (a.range_min, a.range_max) != (b.range_min, b.range_max) {
+        Some(format!("range {:?}..{:?} vs {:?}..{:?}", a.range_min, a.range_max, b.range_min, b.range_max))
+    } else {
+        None
+    }
+}
+
+fn sorted<T>(members: &std::collections::HashMap<String, T>) -> Vec<(&str, &T)> {
+    let mut members: Vec<_> = members.iter().map(|(name, value)| (name.as_str(), value)).collect();
+    members.sort_by(|a, b| a.0.cmp(b.0));
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::context::ObjectContext;
+
+    #[test]
+    fn test_audit_reports_model_inconsistencies() {
+        let mut api = UnifiedApi::new();
+        let mut widget = ApiObject::new(ObjectContext::Custom("Widget".to_string()));
+        widget.properties.insert("size".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        widget.methods.insert("resize".to_string(), MethodValidation::new(2));
+        let mut alias = widget.clone();
+        alias.methods.insert("refresh".to_string(), MethodValidation::new(0));
+        api.objects.insert("Widget".to_string(), widget);
+        api.objects.insert("widget".to_string(), alias);
+
+        let audit = audit_model(&api);
+        let widget: Vec<(AuditKind, Option<&str>)> = audit.findings.iter()
+            .filter(|f| f.object == "Widget")
+            .map(|f| (f.kind, f.member.as_deref()))
+            .collect();
+        assert_eq!(widget, vec![
+            (AuditKind::AliasDivergence, Some("refresh")),
+            (AuditKind::CustomPrimitiveType, Some("size")),
+            (AuditKind::MissingParamTypes, Some("resize")),
+        ]);
+        assert!(!audit.findings.iter().any(|f| f.object == "widget"));
+        // Standard aliases resolve to their class rather than being stored
+        assert_eq!(audit.count(AuditKind::AliasDivergence), 1);
+        assert!(audit.to_json().contains("\"kind\": \"alias-divergence\""));
+    }
+}
@@ -0,0 +1,309 @@
+//! Assembles a [`UnifiedApi`] from the object modules, so every class has
+//! one definition that its aliases resolve to.
+
+use std::collections::HashMap;
+
+use super::{ApiObject, MethodValidation, UnifiedApi, ValidationRule};
+use super::objects::app::AppObject;
+use super::objects::compitem::CompItem;
+use super::objects::folderitem::FolderItem;
+use super::objects::item::{Item, ItemType};
+use super::objects::itemcollection::{ItemCollection, ItemCollectionType};
+use super::objects::layer::{Layer, LayerType};
+use super::objects::layercollection::LayerCollection;
+use super::objects::project::ProjectObject;
+use super::objects::property::Property;
+use crate::data::enums::get_enum_definitions;
+use crate::validation::context::ObjectContext;
+use crate::validation::rules::{PropertyValueType, RangeValidation};
+
+/// Related classes, selected together to build a model holding only part of
+/// the API, e.g. a lightweight validator embedded in a WASM build that
+/// only checks project, comp and layer code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiFamily {
+    /// `Application` (`app`)
+    Application,
+    /// `Project` and the `ImportOptions` its `importFile` takes
+    Project,
+    /// `Item`, `CompItem`, `ItemCollection` and the guide objects of `item.guides`
+    Items,
+    /// `Layer` and `LayerCollection`
+    Layers,
+    /// `Property`
+    Properties,
+}
+
+impl ApiFamily {
+    pub const ALL: &'static [ApiFamily] = &[
+        ApiFamily::Application,
+        ApiFamily::Project,
+        ApiFamily::Items,
+        ApiFamily::Layers,
+        ApiFamily::Properties,
+    ];
+}
+
+/// Classes of an API model and the aliases scripts reach them through.
+/// Start from [`ApiBuilder::standard`] and `extend` it to test against a
+/// changed model, or from [`ApiBuilder::with_families`] for a subset.
+pub struct ApiBuilder {
+    classes: HashMap<String, ApiObject>,
+    /// `(alias, class)`, resolved on lookup like class names
+    aliases: Vec<(String, String)>,
+    /// Load the effect, layer and property match-name tables
+    match_names: bool,
+}
+
+/// Name under which [`ApiBuilder`] is exported for building [`UnifiedApi`] subsets
+pub type UnifiedApiBuilder = ApiBuilder;
+
+impl ApiBuilder {
+    /// A builder with no classes
+    pub fn new() -> Self {
+        ApiBuilder { classes: HashMap::new(), aliases: Vec::new(), match_names: true }
+    }
+
+    /// The After Effects classes, built by their `api/objects` modules
+    pub fn standard() -> Self {
+        Self::with_families(ApiFamily::ALL)
+    }
+
+    /// Only the classes of `families`. Only the selected classes are built,
+    /// which is where a subset saves memory and startup time. Members typed
+    /// as a class that was left out are kept but resolve no further.
+    pub fn with_families(families: &[ApiFamily]) -> Self {
+        let mut builder = Self::new();
+        for &family in families {
+            builder = builder.family(family);
+        }
+        builder.guides_and_templates().alternate_sources()
+    }
+
+    fn family(self, family: ApiFamily) -> Self {
+        match family {
+            ApiFamily::Application => self
+                .class("Application", AppObject::new().api_object)
+                .alias("app", "Application"),
+            ApiFamily::Project => self
+                .class("Project", ProjectObject::new().property_group.base.api_object)
+                .alias("project", "Project")
+                .import_options(),
+            // Collections are also reached through the property that holds them
+            ApiFamily::Items => self
+                .class("Item", Item::new(ItemType::Composition).api_object)
+                .alias("item", "Item")
+                .class("CompItem", CompItem::new().base.base.api_object)
+                .alias("compItem", "CompItem")
+                .class("FolderItem", FolderItem::new().base.api_object)
+                .class("ItemCollection", ItemCollection::new(ItemCollectionType::ProjectItems).api_object)
+                .alias("items", "ItemCollection"),
+            ApiFamily::Layers => self
+                .class("Layer", Layer::new(LayerType::AV).base.base.api_object)
+                .alias("layer", "Layer")
+                .class("LayerCollection", LayerCollection::new().api_object)
+                .alias("layers", "LayerCollection"),
+            ApiFamily::Properties => self
+                .class("Property", Property::new().base.api_object)
+                .alias("property", "Property"),
+        }
+    }
+
+    /// Leaves out the match-name tables; match names are then all unknown
+    pub fn without_match_names(mut self) -> Self {
+        self.match_names = false;
+        self
+    }
+
+    /// Adds a class, replacing any class of the same name
+    pub fn class(mut self, name: &str, object: ApiObject) -> Self {
+        self.classes.insert(name.to_string(), object);
+        self
+    }
+
+    /// Changes a registered class; its aliases see the change. Unknown
+    /// classes are left alone.
+    pub fn extend(mut self, name: &str, change: impl FnOnce(&mut ApiObject)) -> Self {
+        if let Some(object) = self.classes.get_mut(name) {
+            change(object);
+        }
+        self
+    }
+
+    /// Makes `class` reachable as `alias`, e.g. `app` for `Application`
+    pub fn alias(mut self, alias: &str, class: &str) -> Self {
+        self.aliases.push((alias.to_string(), class.to_string()));
+        self
+    }
+
+    /// The model; class names resolve in any case, and aliases of unknown
+    /// classes are dropped
+    pub fn build(self) -> UnifiedApi {
+        UnifiedApi::from_objects(self.classes, self.aliases, self.match_names)
+    }
+
+    /// Guides (AE 16.1+) and Motion Graphics template members, added to the
+    /// classes that declare them
+    fn guides_and_templates(mut self) -> Self {
+        let guide_methods = [
+            ("addGuide", MethodValidation::new(2)
+                .with_param_types(vec![PropertyValueType::OneD, PropertyValueType::OneD]) // orientationType, position
+                .with_param_ranges(vec![RangeValidation::between("orientationType", 0.0, 1.0), RangeValidation::unbounded("position")])
+                .with_return_type("Number")),
+            ("removeGuide", MethodValidation::new(1)
+                .with_param_types(vec![PropertyValueType::OneD]) // guideIndex
+                .with_param_ranges(vec![RangeValidation::at_least("guideIndex", 0.0)])),
+            ("setGuide", MethodValidation::new(2)
+                .with_param_types(vec![PropertyValueType::OneD, PropertyValueType::OneD]) // position, guideIndex
+                .with_param_ranges(vec![RangeValidation::unbounded("position"), RangeValidation::at_least("guideIndex", 0.0)])),
+        ];
+        for name in ["Item", "CompItem"] {
+            self = self.extend(name, |object| {
+                for (method, validation) in &guide_methods {
+                    object.methods.insert(method.to_string(), validation.clone());
+                }
+                object.properties.insert("guides".to_string(), ValidationRule::simple(PropertyValueType::Custom("Array".to_string())));
+            });
+        }
+
+        self = self.extend("CompItem", |comp| {
+            comp.methods.insert("exportAsMotionGraphicsTemplate".to_string(), MethodValidation::new(1)
+                .with_param_types(vec![PropertyValueType::Custom("Boolean".to_string())]) // doOverWriteFileIfExisting
+                .with_optional_params(vec![PropertyValueType::ArbText]) // file_path
+                .with_return_type("Boolean"));
+            comp.methods.insert("openInEssentialGraphics".to_string(), MethodValidation::new(0));
+            comp.methods.insert("getMotionGraphicsTemplateControllerName".to_string(), MethodValidation::new(1)
+                .with_param_types(vec![PropertyValueType::OneD]) // index
+                .with_param_ranges(vec![RangeValidation::at_least("index", 1.0)])
+                .with_return_type("String"));
+            comp.methods.insert("setMotionGraphicsControllerName".to_string(), MethodValidation::new(2)
+                .with_param_types(vec![PropertyValueType::OneD, PropertyValueType::ArbText]) // index, newName
+                .with_param_ranges(vec![RangeValidation::at_least("index", 1.0)]));
+            comp.properties.insert("motionGraphicsTemplateName".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+            comp.properties.insert("motionGraphicsTemplateControllerCount".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        });
+
+        self = self.extend("Layer", |layer| {
+            // Essential Properties of a precomp layer, e.g. layer.essentialProperty.property("Title")
+            layer.properties.insert("essentialProperty".to_string(), ValidationRule::simple(PropertyValueType::Custom("PropertyGroup".to_string())));
+        });
+
+        self = self.extend("Property", |property| {
+            property.methods.insert("addToMotionGraphicsTemplate".to_string(), MethodValidation::new(1)
+                .with_param_types(vec![PropertyValueType::Custom("CompItem".to_string())])
+                .with_return_type("Boolean"));
+            property.methods.insert("addToMotionGraphicsTemplateAs".to_string(), MethodValidation::new(2)
+                .with_param_types(vec![PropertyValueType::Custom("CompItem".to_string()), PropertyValueType::ArbText])
+                .with_return_type("Boolean"));
+            property.methods.insert("canAddToMotionGraphicsTemplate".to_string(), MethodValidation::new(1)
+                .with_param_types(vec![PropertyValueType::Custom("CompItem".to_string())])
+                .with_return_type("Boolean"));
+            property.properties.insert("essentialPropertySource".to_string(), ValidationRule::simple(PropertyValueType::Custom("Property".to_string())));
+        });
+
+        // Guide objects returned by item.guides
+        if !self.classes.contains_key("Item") && !self.classes.contains_key("CompItem") {
+            return self;
+        }
+        let mut guide = ApiObject::new(ObjectContext::Item);
+        guide.properties.insert("orientationType".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, 1.0));
+        guide.properties.insert("position".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        guide.properties.insert("positionType".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        self.class("GuideObject", guide)
+    }
+
+    /// Media replacement (AE 18.0+): Essential Properties added to a template
+    /// as Media Replacement take a comp or footage item as their alternate source
+    fn alternate_sources(self) -> Self {
+        self.extend("Property", |property| {
+            property.can_set_alternate_source = true;
+            property.alternate_source_type = Some("AVItem".to_string());
+            property.methods.insert("setAlternateSource".to_string(), MethodValidation::new(1)
+                .with_param_types(vec![PropertyValueType::Custom("AVItem".to_string())]));
+            property.properties.insert("alternateSource".to_string(), ValidationRule::simple(PropertyValueType::Custom("AVItem".to_string())));
+            property.properties.insert("canSetAlternateSource".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        })
+    }
+
+    /// `new ImportOptions(file)`, the argument `project.importFile()` expects
+    fn import_options(self) -> Self {
+        let import_as: Vec<String> = get_enum_definitions().into_iter()
+            .filter(|(name, _)| *name == "ImportAsType")
+            .flat_map(|(_, members)| members)
+            .map(|member| format!("ImportAsType.{}", member))
+            .collect();
+
+        let mut options = ApiObject::new(ObjectContext::Custom("ImportOptions".to_string()));
+        options.methods.insert("canImportAs".to_string(), MethodValidation::new(1)
+            .with_param_types(vec![PropertyValueType::Custom("ImportAsType".to_string())])
+            .with_return_type("Boolean"));
+        options.methods.insert("isFileNameNumbered".to_string(), MethodValidation::new(1)
+            .with_param_types(vec![PropertyValueType::Custom("File".to_string())])
+            .with_return_type("Object"));
+        options.properties.insert("file".to_string(), ValidationRule::simple(PropertyValueType::Custom("File".to_string())));
+        options.properties.insert("importAs".to_string(), ValidationRule::simple(PropertyValueType::Custom("ImportAsType".to_string())).with_dropdown(import_as));
+        options.properties.insert("sequence".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        options.properties.insert("forceAlphabetical".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        options.properties.insert("rangeStart".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, f64::MAX));
+        options.properties.insert("rangeEnd".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, f64::MAX));
+        self.class("ImportOptions", options)
+    }
+}
+
+impl Default for ApiBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases_resolve_to_classes() {
+        let api = ApiBuilder::standard().build();
+        for (alias, class) in [("app", "Application"), ("project", "Project"), ("compItem", "CompItem"), ("layer", "Layer"), ("items", "ItemCollection")] {
+            assert_eq!(api.canonical_name(alias), Some(class));
+        }
+        assert!(!api.objects.contains_key("layer"));
+        assert_eq!(api.canonical_name("LAYERS"), Some("LayerCollection"));
+        assert_eq!(api.canonical_name("Widget"), None);
+
+        // Members the object modules define are part of the model
+        assert!(api.validate_method("Project", "importFile"));
+        assert!(api.validate_method("project", "saveWithDialog"));
+        assert!(api.validate_method("compItem", "addGuide"));
+        assert!(matches!(api.object("CompItem").unwrap().object_type, ObjectContext::Comp));
+    }
+
+    #[test]
+    fn test_family_subsets() {
+        let api = ApiBuilder::with_families(&[ApiFamily::Project, ApiFamily::Items, ApiFamily::Layers])
+            .without_match_names()
+            .build();
+        let mut classes: Vec<&str> = api.objects.keys().map(String::as_str).collect();
+        classes.sort();
+        assert_eq!(classes, vec!["CompItem", "FolderItem", "GuideObject", "ImportOptions", "Item", "ItemCollection", "Layer", "LayerCollection", "Project"]);
+        assert_eq!(api.canonical_name("app"), None);
+        assert!(api.validate_method("compItem", "addGuide"));
+        assert!(!api.validate_effect_match_name("ADBE Gaussian Blur 2"));
+
+        let layers = ApiBuilder::with_families(&[ApiFamily::Layers]).build();
+        assert!(!layers.objects.contains_key("GuideObject"));
+        assert!(layers.validate_effect_match_name("ADBE Gaussian Blur 2"));
+        assert_eq!(ApiBuilder::standard().build().objects.len(), 11);
+    }
+
+    #[test]
+    fn test_alternate_sources_take_av_items() {
+        let api = ApiBuilder::standard().build();
+        let property = api.object("Property").unwrap();
+        assert!(property.validate_alternate_source(&serde_json::json!("FootageItem")).is_ok());
+        assert_eq!(
+            property.validate_alternate_source(&serde_json::json!("FolderItem")).unwrap_err(),
+            "alternateSource must be AVItem (CompItem or FootageItem), got FolderItem"
+        );
+        assert!(api.object("Layer").unwrap().validate_alternate_source(&serde_json::json!("CompItem")).is_err());
+    }
+}
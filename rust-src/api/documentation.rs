@@ -1,7 +1,10 @@
 use std::collections::HashMap;
+use std::fmt;
 use serde::{Deserialize, Serialize};
 
 use super::types::{DocumentationInfo, VersionInfo, ExampleInfo, ParameterInfo};
+use super::UnifiedApi;
+use crate::validation::rules::PropertyValueType;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiDocumentation {
@@ -60,6 +63,48 @@ pub struct EffectDocumentation {
     pub properties: HashMap<String, PropertyDocumentation>,
 }
 
+/// Whether a described member is called or read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemberKind {
+    Method,
+    Property,
+}
+
+/// A parameter of a described method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterDescription {
+    pub name: String,
+    pub type_name: String,
+    pub optional: bool,
+    pub default_value: Option<String>,
+    /// Accepted numeric values, e.g. "4 to 30000"
+    pub range: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Everything known about one API member, for editor hovers and REPL help
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberDescription {
+    /// Class the member was found on
+    pub class: String,
+    pub name: String,
+    pub kind: MemberKind,
+    pub parameters: Vec<ParameterDescription>,
+    /// Return type of a method, or the value type of a property
+    pub type_name: Option<String>,
+    /// Accepted values of a property, e.g. "0 to 100"
+    pub range: Option<String>,
+    pub description: Option<String>,
+    pub version_info: Option<VersionInfo>,
+    pub docs_url: Option<String>,
+}
+
+impl Default for ApiDocumentation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ApiDocumentation {
     pub fn new() -> Self {
         ApiDocumentation {
@@ -132,4 +177,339 @@ impl ApiDocumentation {
             file_reference: None,
         })
     }
-} 
\ No newline at end of file
+
+    /// Describes the member at the end of a path such as
+    /// `CompItem.layers.addSolid`, using the shared API model
+    pub fn describe(&self, path: &str) -> Option<MemberDescription> {
+        self.describe_in(&UnifiedApi::shared(), path)
+    }
+
+    /// Describes a member using `api` for signatures and types, and this
+    /// documentation for prose, defaults and versions.
+    ///
+    /// The path starts at a class or alias; each member before the last
+    /// must be a property or method whose type is a class of the model.
+    pub fn describe_in(&self, api: &UnifiedApi, path: &str) -> Option<MemberDescription> {
+        let mut segments: Vec<&str> = path.split('.').map(|s| s.trim().trim_end_matches("()")).collect();
+        let name = segments.pop()?;
+        let (first, rest) = segments.split_first()?;
+        let mut class = api.canonical_name(first)?.to_string();
+        for segment in rest {
+            class = member_class(api, &class, segment)?;
+        }
+        let object = api.object(&class)?;
+
+        if let Some(method) = object.methods.get(name) {
+            let docs = self.method_documentation(&class, name);
+            let parameters = (0..method.param_count + method.optional_params.len())
+                .map(|index| {
+                    let documented = docs.and_then(|d| d.parameters.get(index));
+                    let range = method.param_range(index);
+                    ParameterDescription {
+                        // Ranges carry the parameter names where the model declares them
+                        name: range.map(|r| r.parameter_name.clone())
+                            .or_else(|| documented.map(|p| p.name.clone()))
+                            .unwrap_or_else(|| format!("arg{}", index)),
+                        type_name: method.param_type(index).map_or("any".to_string(), type_name),
+                        optional: index >= method.param_count,
+                        default_value: documented.and_then(|p| p.default_value.clone()),
+                        range: range.and_then(|r| describe_range(r.min, r.max)),
+                        description: documented.map(|p| p.description.clone()).filter(|d| !d.is_empty()),
+                    }
+                })
+                .collect();
+            return Some(MemberDescription {
+                docs_url: member_docs_url(&class, Some(name)),
+                type_name: method.return_type.clone().or_else(|| docs.map(|d| d.return_type.clone())),
+                description: docs.map(|d| d.description.clone()).filter(|d| !d.is_empty()),
+                version_info: docs.map(|d| d.version_info.clone()),
+                class,
+                name: name.to_string(),
+                kind: MemberKind::Method,
+                parameters,
+                range: None,
+            });
+        }
+
+        let rule = object.properties.get(name)?;
+        let docs = self.property_documentation(&class, name);
+        Some(MemberDescription {
+            docs_url: member_docs_url(&class, Some(name)),
+            type_name: Some(type_name(&rule.value_type)),
+            range: describe_range(rule.range_min, rule.range_max),
+            description: docs.map(|d| d.description.clone()).filter(|d| !d.is_empty()),
+            version_info: docs.map(|d| d.version_info.clone()),
+            class,
+            name: name.to_string(),
+            kind: MemberKind::Property,
+            parameters: Vec::new(),
+        })
+    }
+
+    /// Documentation of a method on the class or the classes it inherits from
+    fn method_documentation(&self, class: &str, name: &str) -> Option<&MethodDocumentation> {
+        self.ancestors(class).find_map(|object| object.methods.get(name))
+    }
+
+    fn property_documentation(&self, class: &str, name: &str) -> Option<&PropertyDocumentation> {
+        self.ancestors(class).find_map(|object| object.properties.get(name))
+    }
+
+    fn ancestors<'a>(&'a self, class: &str) -> impl Iterator<Item = &'a ObjectDocumentation> + 'a {
+        let first = self.objects.get(class);
+        // Bounded, so a cycle in hand-written parents cannot loop forever
+        std::iter::successors(first, move |object| object.parent.as_ref().and_then(|parent| self.objects.get(parent)))
+            .take(self.objects.len())
+    }
+}
+
+impl fmt::Display for MemberDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            MemberKind::Method => {
+                let parameters: Vec<String> = self.parameters.iter()
+                    .map(|p| {
+                        let default = p.default_value.as_ref().map_or(String::new(), |d| format!(" = {}", d));
+                        format!("{}{}: {}{}", p.name, if p.optional { "?" } else { "" }, p.type_name, default)
+                    })
+                    .collect();
+                write!(f, "{}.{}({})", self.class, self.name, parameters.join(", "))?;
+                if let Some(returns) = &self.type_name {
+                    write!(f, " -> {}", returns)?;
+                }
+            }
+            MemberKind::Property => {
+                write!(f, "{}.{}: {}", self.class, self.name, self.type_name.as_deref().unwrap_or("any"))?;
+                if let Some(range) = &self.range {
+                    write!(f, " ({})", range)?;
+                }
+            }
+        }
+        if let Some(description) = &self.description {
+            write!(f, "\n  {}", description)?;
+        }
+        for parameter in &self.parameters {
+            let details: Vec<&str> = [parameter.range.as_deref(), parameter.description.as_deref()].into_iter().flatten().collect();
+            if !details.is_empty() {
+                write!(f, "\n  {}: {}", parameter.name, details.join("; "))?;
+            }
+        }
+        if let Some(version) = &self.version_info {
+            if let Some(since) = &version.since_version {
+                write!(f, "\n  Since After Effects {}", since)?;
+            }
+            if let Some(deprecated) = &version.deprecated {
+                write!(f, "\n  Deprecated: {}", deprecated)?;
+            }
+        }
+        if let Some(url) = &self.docs_url {
+            write!(f, "\n  {}", url)?;
+        }
+        Ok(())
+    }
+}
+
+/// The class a property holds or a method returns, if the model has it
+fn member_class(api: &UnifiedApi, class: &str, member: &str) -> Option<String> {
+    let object = api.object(class)?;
+    let target = match object.methods.get(member) {
+        Some(method) => method.return_type.clone()?,
+        None => match &object.properties.get(member)?.value_type {
+            PropertyValueType::Custom(name) => name.clone(),
+            PropertyValueType::TextDocument => "TextDocument".to_string(),
+            PropertyValueType::Marker => "MarkerValue".to_string(),
+            PropertyValueType::Shape => "Shape".to_string(),
+            _ => return None,
+        },
+    };
+    api.canonical_name(&target).map(String::from)
+}
+
+/// Value types as scripts see them
+fn type_name(value_type: &PropertyValueType) -> String {
+    match value_type {
+        PropertyValueType::OneD | PropertyValueType::LayerIndex | PropertyValueType::MaskIndex => "Number".to_string(),
+        PropertyValueType::TwoD | PropertyValueType::TwoDSpatial => "[Number, Number]".to_string(),
+        PropertyValueType::ThreeD | PropertyValueType::ThreeDSpatial => "[Number, Number, Number]".to_string(),
+        PropertyValueType::Color => "[r, g, b, a] (0-1)".to_string(),
+        PropertyValueType::ArbText => "String".to_string(),
+        PropertyValueType::Marker => "MarkerValue".to_string(),
+        PropertyValueType::Shape => "Shape".to_string(),
+        PropertyValueType::TextDocument => "TextDocument".to_string(),
+        PropertyValueType::NoValue | PropertyValueType::CustomValue => "any".to_string(),
+        PropertyValueType::Custom(name) => name.clone(),
+    }
+}
+
+fn describe_range(min: Option<f64>, max: Option<f64>) -> Option<String> {
+    match (min, max) {
+        (Some(min), Some(max)) => Some(format!("{} to {}", min, max)),
+        (Some(min), None) => Some(format!("at least {}", min)),
+        (None, Some(max)) => Some(format!("at most {}", max)),
+        (None, None) => None,
+    }
+}
+
+/// Root of the After Effects scripting guide
+pub const SCRIPTING_GUIDE_URL: &str = "https://ae-scripting.docsforadobe.dev";
+
+/// Guide page of each class, relative to the guide root
+const CLASS_PAGES: &[(&str, &str)] = &[
+    ("Application", "general/application"),
+    ("Project", "general/project"),
+    ("System", "general/system"),
+    ("AVItem", "item/avitem"),
+    ("CompItem", "item/compitem"),
+    ("FolderItem", "item/folderitem"),
+    ("FootageItem", "item/footageitem"),
+    ("Item", "item/item"),
+    ("ItemCollection", "item/itemcollection"),
+    ("AVLayer", "layer/avlayer"),
+    ("CameraLayer", "layer/cameralayer"),
+    ("Layer", "layer/layer"),
+    ("LayerCollection", "layer/layercollection"),
+    ("LightLayer", "layer/lightlayer"),
+    ("ShapeLayer", "layer/shapelayer"),
+    ("TextLayer", "layer/textlayer"),
+    ("ThreeDModelLayer", "layer/threedmodellayer"),
+    ("MaskPropertyGroup", "property/maskpropertygroup"),
+    ("Property", "property/property"),
+    ("PropertyBase", "property/propertybase"),
+    ("PropertyGroup", "property/propertygroup"),
+    ("OMCollection", "renderqueue/omcollection"),
+    ("OutputModule", "renderqueue/outputmodule"),
+    ("RenderQueue", "renderqueue/renderqueue"),
+    ("RenderQueueItem", "renderqueue/renderqueueitem"),
+    ("RQItemCollection", "renderqueue/rqitemcollection"),
+    ("FileSource", "sources/filesource"),
+    ("FootageSource", "sources/footagesource"),
+    ("PlaceholderSource", "sources/placeholdersource"),
+    ("SolidSource", "sources/solidsource"),
+    ("CharacterRange", "text/characterrange"),
+    ("ComposedLineRange", "text/composedlinerange"),
+    ("FontObject", "text/fontobject"),
+    ("FontsObject", "text/fontsobject"),
+    ("ParagraphRange", "text/paragraphrange"),
+    ("TextDocument", "text/textdocument"),
+    ("Collection", "other/collection"),
+    ("ImportOptions", "other/importoptions"),
+    ("KeyframeEase", "other/keyframeease"),
+    ("MarkerValue", "other/markervalue"),
+    ("Preferences", "other/preferences"),
+    ("Settings", "other/settings"),
+    ("Shape", "other/shape"),
+    ("View", "other/view"),
+    ("Viewer", "other/viewer"),
+    ("ViewOptions", "other/viewoptions"),
+];
+
+/// Guide sections covering what a rule checks, for rules about one area of
+/// the API. Other rules are about ExtendScript itself and are explained by
+/// `ae-validator explain`.
+const RULE_PAGES: &[(&str, &str)] = &[
+    ("unguarded-active-item", "general/project/#projectactiveitem"),
+    ("loop-undo-entries", "general/application/#appbeginundogroup"),
+    ("suppress-dialogs-leak", "general/application/#appbeginsuppressdialogs"),
+    ("suppress-dialogs-early-exit", "general/application/#appbeginsuppressdialogs"),
+    ("suppress-dialogs-unprotected", "general/application/#appbeginsuppressdialogs"),
+    ("suppress-dialogs-unmatched-end", "general/application/#appbeginsuppressdialogs"),
+    ("watch-folder-dialog", "general/application/#appwatchfolder"),
+    ("watch-folder-target", "general/application/#appwatchfolder"),
+    ("locale-menu-command", "general/application/#appfindmenucommandid"),
+    ("locale-effect-name", "matchnames/effects/firstparty/"),
+    ("missing-footage", "item/footageitem/"),
+    ("legacy-track-matte", "layer/avlayer/#avlayersettrackmatte"),
+    ("use-after-remove", "layer/layer/#layerremove"),
+    ("duplicate-remove", "layer/layer/#layerremove"),
+    ("alternate-source-type", "property/property/#propertyalternatesource"),
+    ("alternate-source-unchecked", "property/property/#propertyalternatesource"),
+    ("alternate-source-layer", "property/property/#propertyalternatesource"),
+    ("render-queue-state", "renderqueue/renderqueueitem/"),
+    ("render-queue-empty", "renderqueue/renderqueue/"),
+    ("effect-bit-depth", "matchnames/effects/firstparty/"),
+    ("effect-gpu", "matchnames/effects/firstparty/"),
+    ("effect-availability", "matchnames/effects/firstparty/"),
+    ("obsolete-effect", "matchnames/effects/firstparty/"),
+    ("property-parent", "property/propertygroup/"),
+    ("layer-capability", "layer/avlayer/"),
+    ("3d-only-property", "matchnames/layer/3dlayer/"),
+    ("per-char-3d", "layer/avlayer/#avlayerthreedperchar"),
+    ("expression-selector-dimension", "matchnames/layer/textlayer/#animators"),
+];
+
+/// Guide URL of a class, or of one of its members
+pub fn member_docs_url(class_name: &str, member: Option<&str>) -> Option<String> {
+    let (_, page) = CLASS_PAGES.iter().find(|(class, _)| *class == class_name)?;
+    // Section anchors are the heading, e.g. `CompItem.layers` or
+    // `app.beginUndoGroup()`, lowercased without punctuation
+    let prefix = if class_name == "Application" { "app" } else { class_name };
+    Some(match member {
+        Some(member) => format!("{}/{}/#{}{}", SCRIPTING_GUIDE_URL, page, prefix, member).to_lowercase(),
+        None => format!("{}/{}/", SCRIPTING_GUIDE_URL, page),
+    })
+}
+
+/// Guide URL for a rule's diagnostics, for rules mapped to a guide section
+pub fn rule_docs_url(rule: &str) -> Option<String> {
+    RULE_PAGES.iter()
+        .find(|(id, _)| *id == rule)
+        .map(|(_, page)| format!("{}/{}", SCRIPTING_GUIDE_URL, page))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docs_urls() {
+        assert_eq!(member_docs_url("CompItem", Some("workAreaDuration")).as_deref(),
+            Some("https://ae-scripting.docsforadobe.dev/item/compitem/#compitemworkareaduration"));
+        assert_eq!(member_docs_url("Application", Some("beginUndoGroup")).as_deref(),
+            Some("https://ae-scripting.docsforadobe.dev/general/application/#appbeginundogroup"));
+        assert_eq!(member_docs_url("TextLayer", None).as_deref(),
+            Some("https://ae-scripting.docsforadobe.dev/layer/textlayer/"));
+        assert_eq!(member_docs_url("Unknown", None), None);
+        assert_eq!(rule_docs_url("3d-only-property").as_deref(),
+            Some("https://ae-scripting.docsforadobe.dev/matchnames/layer/3dlayer/"));
+        assert_eq!(rule_docs_url("implicit-global"), None);
+    }
+
+    #[test]
+    fn test_describe_member() {
+        let mut docs = ApiDocumentation::new();
+        let mut methods = HashMap::new();
+        methods.insert("addSolid".to_string(), MethodDocumentation {
+            name: "addSolid".to_string(),
+            description: "Creates a new solid and adds it to the composition".to_string(),
+            parameters: Vec::new(),
+            return_type: "AVLayer".to_string(),
+            version_info: VersionInfo { since_version: Some("6.0".to_string()), deprecated: None },
+            example_usage: String::new(),
+        });
+        docs.objects.insert("LayerCollection".to_string(), ObjectDocumentation {
+            name: "LayerCollection".to_string(),
+            description: String::new(),
+            properties: HashMap::new(),
+            methods,
+            parent: None,
+            file_reference: String::new(),
+            examples: Vec::new(),
+        });
+
+        let add_solid = docs.describe("CompItem.layers.addSolid()").unwrap();
+        assert_eq!((add_solid.class.as_str(), add_solid.kind), ("LayerCollection", MemberKind::Method));
+        let names: Vec<(&str, bool)> = add_solid.parameters.iter().map(|p| (p.name.as_str(), p.optional)).collect();
+        assert_eq!(names, vec![
+            ("color", false), ("name", false), ("width", false), ("height", false), ("pixelAspect", false), ("duration", true),
+        ]);
+        assert_eq!(add_solid.type_name.as_deref(), Some("AVLayer"));
+        let text = add_solid.to_string();
+        assert!(text.contains("\n  Creates a new solid"), "{}", text);
+        assert!(text.contains("\n  Since After Effects 6.0"), "{}", text);
+
+        let width = docs.describe("CompItem.width").unwrap();
+        assert_eq!((width.kind, width.type_name.as_deref()), (MemberKind::Property, Some("Number")));
+        assert!(docs.describe("CompItem.frobnicate").is_none());
+        assert!(docs.describe("Frobnicator.name").is_none());
+    }
+}
@@ -1,8 +1,12 @@
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::validation::custom::CustomValidator;
 use crate::validation::rules::{ValidationRule, PropertyValueType};
+use crate::validation::schema::ValueSchema;
 
 // Keyframe interpolation types
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum KeyframeInterpolationType {
     Linear,
     Bezier,
@@ -12,7 +16,7 @@ pub enum KeyframeInterpolationType {
 }
 
 // Temporal ease structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyframeTemporal {
     pub speed: f32,
     pub influence: f32,
@@ -65,26 +69,22 @@ impl InterpolationValidator {
         interp_type: &KeyframeInterpolationType,
     ) -> Result<(), String> {
         match property_type {
-            PropertyValueType::Marker => {
-                if *interp_type != KeyframeInterpolationType::Hold {
+            PropertyValueType::Marker
+                if *interp_type != KeyframeInterpolationType::Hold => {
                     return Err("Marker properties only support Hold interpolation".to_string());
                 }
-            }
-            PropertyValueType::Custom(name) if name == "Boolean" => {
-                if *interp_type != KeyframeInterpolationType::Hold {
+            PropertyValueType::Custom(name) if name == "Boolean"
+                && *interp_type != KeyframeInterpolationType::Hold => {
                     return Err("Boolean properties only support Hold interpolation".to_string());
                 }
-            }
-            PropertyValueType::LayerIndex | PropertyValueType::MaskIndex => {
-                if *interp_type != KeyframeInterpolationType::Hold {
+            PropertyValueType::LayerIndex | PropertyValueType::MaskIndex
+                if *interp_type != KeyframeInterpolationType::Hold => {
                     return Err("Index properties only support Hold interpolation".to_string());
                 }
-            }
-            PropertyValueType::TextDocument => {
-                if *interp_type != KeyframeInterpolationType::Hold {
+            PropertyValueType::TextDocument
+                if *interp_type != KeyframeInterpolationType::Hold => {
                     return Err("TextDocument properties only support Hold interpolation".to_string());
                 }
-            }
             _ => {
                 // Other property types support all interpolation types
             }
@@ -104,10 +104,10 @@ impl InterpolationValidator {
             PropertyValueType::LayerIndex |
             PropertyValueType::MaskIndex |
             PropertyValueType::TextDocument => {
-                return Err("This property type does not support temporal easing".to_string());
+                Err("This property type does not support temporal easing".to_string())
             }
             PropertyValueType::Custom(name) if name == "Boolean" => {
-                return Err("Boolean properties do not support temporal easing".to_string());
+                Err("Boolean properties do not support temporal easing".to_string())
             }
             _ => Ok(())
         }
@@ -189,15 +189,15 @@ impl ExpressionInterpolation {
     }
     
     pub fn can_interpolate_expression_result(property_type: &PropertyValueType) -> bool {
-        match property_type {
-            PropertyValueType::OneD |
-            PropertyValueType::TwoD |
-            PropertyValueType::ThreeD |
-            PropertyValueType::TwoDSpatial |
-            PropertyValueType::ThreeDSpatial |
-            PropertyValueType::Color => true,
-            _ => false,
-        }
+        matches!(
+            property_type,
+            PropertyValueType::OneD
+                | PropertyValueType::TwoD
+                | PropertyValueType::ThreeD
+                | PropertyValueType::TwoDSpatial
+                | PropertyValueType::ThreeDSpatial
+                | PropertyValueType::Color
+        )
     }
 }
 
@@ -274,7 +274,7 @@ pub struct GraphEditorHelpers;
 
 impl GraphEditorHelpers {
     pub fn get_value_graph_range(
-        property_type: &PropertyValueType,
+        _property_type: &PropertyValueType,
         values: &[f32],
     ) -> (f32, f32) {
         let min = values.iter().fold(f32::INFINITY, |a, &b| a.min(b));
@@ -351,10 +351,7 @@ pub fn get_interpolation_properties() -> HashMap<String, ValidationRule> {
         dimensions_separated: false,
         is_dropdown: false,
         allowed_values: None,
-        custom_validator: Some(Box::new(|value| {
-            // Validate temporal ease array structure
-            Ok(())
-        })),
+        custom_validator: Some(CustomValidator::Schema(ValueSchema::keyframe_eases())),
     });
     
     props.insert("keyOutTemporalEase".to_string(), ValidationRule {
@@ -367,10 +364,7 @@ pub fn get_interpolation_properties() -> HashMap<String, ValidationRule> {
         dimensions_separated: false,
         is_dropdown: false,
         allowed_values: None,
-        custom_validator: Some(Box::new(|value| {
-            // Validate temporal ease array structure
-            Ok(())
-        })),
+        custom_validator: Some(CustomValidator::Schema(ValueSchema::keyframe_eases())),
     });
     
     // Spatial tangent properties
@@ -384,10 +378,7 @@ pub fn get_interpolation_properties() -> HashMap<String, ValidationRule> {
         dimensions_separated: false,
         is_dropdown: false,
         allowed_values: None,
-        custom_validator: Some(Box::new(|value| {
-            // Validate spatial tangent array structure
-            Ok(())
-        })),
+        custom_validator: Some(CustomValidator::Schema(ValueSchema::array_of(ValueSchema::number(), 2, 3))),
     });
     
     props.insert("keyOutSpatialTangent".to_string(), ValidationRule {
@@ -400,10 +391,7 @@ pub fn get_interpolation_properties() -> HashMap<String, ValidationRule> {
         dimensions_separated: false,
         is_dropdown: false,
         allowed_values: None,
-        custom_validator: Some(Box::new(|value| {
-            // Validate spatial tangent array structure
-            Ok(())
-        })),
+        custom_validator: Some(CustomValidator::Schema(ValueSchema::array_of(ValueSchema::number(), 2, 3))),
     });
     
     // Auto-bezier and continuous properties
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+use lazy_static::lazy_static;
+use serde_json::Value;
+
+use super::{ApiObject, MethodValidation, UnifiedApi};
+use crate::validation::context::ValidationContext;
+use crate::validation::property::validate_property_value;
+use crate::validation::rules::ValidationRule;
+
+lazy_static! {
+    static ref BASE_API: Arc<UnifiedApi> = Arc::new(UnifiedApi::new());
+}
+
+impl UnifiedApi {
+    /// The process-wide base model, built on first use and shared by every
+    /// validator
+    pub fn shared() -> Arc<UnifiedApi> {
+        Arc::clone(&BASE_API)
+    }
+}
+
+/// A shared, immutable [`UnifiedApi`] plus objects private to one validator.
+///
+/// Object lookups check the overlay first. Changing a base object copies
+/// just that object into the overlay, so extensions never clone the full
+/// model and never leak into other validators. Everything not related to
+/// objects (match names, enums, search) is read from the base through
+/// `Deref`.
+pub struct LayeredApi {
+    base: Arc<UnifiedApi>,
+    overlay: HashMap<String, ApiObject>,
+    validation_context: ValidationContext,
+}
+
+impl Default for LayeredApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayeredApi {
+    /// Layers over the shared base model
+    pub fn new() -> Self {
+        Self::with_base(UnifiedApi::shared())
+    }
+
+    pub fn with_base(base: Arc<UnifiedApi>) -> Self {
+        Self {
+            base,
+            overlay: HashMap::new(),
+            validation_context: ValidationContext::new(),
+        }
+    }
+
+    pub fn base(&self) -> &Arc<UnifiedApi> {
+        &self.base
+    }
+
+    /// Overlay objects are matched exactly; base classes by any case or alias
+    pub fn object(&self, name: &str) -> Option<&ApiObject> {
+        let key = self.key(name);
+        self.overlay.get(key).or_else(|| self.base.objects.get(key))
+    }
+
+    /// The name an object is stored under: its own name in the overlay, or
+    /// the canonical name of a base class
+    fn key<'a>(&'a self, name: &'a str) -> &'a str {
+        if self.overlay.contains_key(name) {
+            name
+        } else {
+            self.base.canonical_name(name).unwrap_or(name)
+        }
+    }
+
+    pub fn contains_object(&self, name: &str) -> bool {
+        self.object(name).is_some()
+    }
+
+    /// Mutable access to an object, copying it out of the base on first write
+    pub fn object_mut(&mut self, name: &str) -> Option<&mut ApiObject> {
+        let key = self.key(name).to_string();
+        if !self.overlay.contains_key(&key) {
+            let object = self.base.objects.get(&key)?.clone();
+            self.overlay.insert(key.clone(), object);
+        }
+        self.overlay.get_mut(&key)
+    }
+
+    /// Adds an object, shadowing any base object of the same name
+    pub fn add_object(&mut self, name: &str, object: ApiObject) {
+        self.overlay.insert(name.to_string(), object);
+    }
+
+    /// Adds a method to an existing object; returns false if the object is unknown
+    pub fn add_method(&mut self, class_name: &str, method_name: &str, method: MethodValidation) -> bool {
+        match self.object_mut(class_name) {
+            Some(object) => {
+                object.methods.insert(method_name.to_string(), method);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a property to an existing object; returns false if the object is unknown
+    pub fn add_property(&mut self, class_name: &str, property_name: &str, rule: ValidationRule) -> bool {
+        match self.object_mut(class_name) {
+            Some(object) => {
+                object.properties.insert(property_name.to_string(), rule);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Names from the base and the overlay, sorted and deduplicated
+    pub fn object_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.base.objects.keys()
+            .chain(self.overlay.keys())
+            .map(String::as_str)
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Number of objects owned by this layer rather than shared
+    pub fn overlay_len(&self) -> usize {
+        self.overlay.len()
+    }
+
+    pub fn method(&self, class_name: &str, method_name: &str) -> Option<&MethodValidation> {
+        self.object(class_name)?.methods.get(method_name)
+    }
+
+    pub fn validate_method(&self, class_name: &str, method_name: &str) -> bool {
+        self.object(class_name).is_some_and(|obj| obj.methods.contains_key(method_name))
+    }
+
+    pub fn validate_property(&self, class_name: &str, property_name: &str) -> bool {
+        self.object(class_name).is_some_and(|obj| obj.properties.contains_key(property_name))
+    }
+
+    pub fn validate_property_access(&mut self, class_name: &str, property_name: &str, value: Option<&Value>) -> Result<(), String> {
+        let key = self.key(class_name);
+        let api_obj = match self.overlay.get(key).or_else(|| self.base.objects.get(key)) {
+            Some(api_obj) => api_obj,
+            None => return Err(format!("Class {} not found", class_name)),
+        };
+
+        self.validation_context.enter_context(api_obj.object_type.clone());
+        let result = match (api_obj.properties.get(property_name), value) {
+            (Some(rule), Some(val)) => validate_property_value(val, rule),
+            (Some(_), None) => Ok(()),
+            (None, _) => Err(format!("Property {} not found on {}", property_name, class_name)),
+        };
+        self.validation_context.exit_context();
+        result
+    }
+}
+
+impl Deref for LayeredApi {
+    type Target = UnifiedApi;
+
+    fn deref(&self) -> &UnifiedApi {
+        &self.base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_does_not_touch_shared_base() {
+        let mut extended = LayeredApi::new();
+        let plain = LayeredApi::new();
+        assert!(Arc::ptr_eq(extended.base(), plain.base()));
+
+        // A method the base model does not declare, so only the overlay can provide it
+        assert!(!plain.validate_method("Layer", "applyStudioPreset"));
+        assert!(extended.add_method("Layer", "applyStudioPreset", MethodValidation::new(1)));
+        assert!(!extended.add_method("NoSuchClass", "run", MethodValidation::new(0)));
+
+        assert!(extended.validate_method("Layer", "applyStudioPreset"));
+        assert!(extended.validate_method("Layer", "duplicate"));
+        assert!(!plain.validate_method("Layer", "applyStudioPreset"));
+        assert!(!extended.base().validate_method("Layer", "applyStudioPreset"));
+        assert!(!LayeredApi::new().validate_method("Layer", "applyStudioPreset"));
+        assert_eq!(extended.overlay_len(), 1);
+        assert_eq!(plain.overlay_len(), 0);
+    }
+}
@@ -5,6 +5,9 @@ use crate::validation::context::ValidationContext;
 use crate::validation::rules::PropertyValueType;
 use super::types::{ValidationInfo, ParameterInfo, DocumentationInfo};
 
+/// Extra check run on a call's arguments
+pub type MethodValidator = Arc<dyn Fn(&[Value], &ValidationContext) -> Result<(), String> + Send + Sync>;
+
 pub struct MethodValidation {
     pub param_count: usize,
     pub param_types: Vec<PropertyValueType>,
@@ -14,7 +17,7 @@ pub struct MethodValidation {
     pub temporal_dimensions: Option<Vec<usize>>,
     pub is_spatial: bool,
     pub requires_expression: bool,
-    pub custom_validator: Option<Arc<dyn Fn(&[Value], &ValidationContext) -> Result<(), String> + Send + Sync>>,
+    pub custom_validator: Option<MethodValidator>,
     pub documentation: Option<DocumentationInfo>,
     pub parameters: Vec<ParameterInfo>,
     pub validation_info: Option<ValidationInfo>,
@@ -63,7 +66,7 @@ impl MethodValidation {
         self
     }
 
-    pub fn with_custom_validator(mut self, validator: Arc<dyn Fn(&[Value], &ValidationContext) -> Result<(), String> + Send + Sync>) -> Self {
+    pub fn with_custom_validator(mut self, validator: MethodValidator) -> Self {
         self.custom_validator = Some(validator);
         self
     }
@@ -128,16 +131,16 @@ impl MethodValidation {
     }
 
     fn validate_argument_type(&self, value: &Value, expected_type: &PropertyValueType) -> bool {
-        match (value, expected_type) {
-            (Value::Number(_), PropertyValueType::OneD) => true,
-            (Value::Array(_), PropertyValueType::TwoD) => true,
-            (Value::Array(_), PropertyValueType::ThreeD) => true,
-            (Value::String(_), PropertyValueType::TextDocument) => true,
-            (Value::Object(_), PropertyValueType::Shape) => true,
-            (Value::Number(_), PropertyValueType::LayerIndex) => true,
-            (Value::Number(_), PropertyValueType::MaskIndex) => true,
-            (Value::Array(_), PropertyValueType::Color) => true,
-            _ => false,
-        }
+        matches!(
+            (value, expected_type),
+            (Value::Number(_), PropertyValueType::OneD)
+                | (Value::Array(_), PropertyValueType::TwoD)
+                | (Value::Array(_), PropertyValueType::ThreeD)
+                | (Value::String(_), PropertyValueType::TextDocument)
+                | (Value::Object(_), PropertyValueType::Shape)
+                | (Value::Number(_), PropertyValueType::LayerIndex)
+                | (Value::Number(_), PropertyValueType::MaskIndex)
+                | (Value::Array(_), PropertyValueType::Color)
+        )
     }
 } 
\ No newline at end of file
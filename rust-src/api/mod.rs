@@ -7,60 +7,134 @@ pub mod documentation;
 pub mod methods;
 pub mod properties;
 pub mod types;
+pub mod search;
+pub mod layered;
+pub mod builder;
+pub mod audit;
+pub mod model;
 
 pub use objects::app::ApiObject;
 pub use crate::validation::rules::ValidationRule;
 pub use crate::validation::rules::MethodValidation;
 pub use properties::PropertyValidation;
 pub use types::*;
+pub use search::{SearchIndex, SearchEntry, SearchHit, EntryKind};
+pub use layered::LayeredApi;
+pub use builder::{ApiBuilder, ApiFamily, UnifiedApiBuilder};
+pub use model::ApiModel;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use serde_json::Value;
 use itertools::Itertools;
 
 use crate::data::match_names::{get_effect_match_names, get_layer_match_names, get_property_match_names};
-use crate::validation::context::{ValidationContext, ObjectContext};
+use crate::data::enums::get_enum_definitions;
+use crate::data::globals::get_global_functions;
+use crate::validation::context::ValidationContext;
 use crate::validation::rules::PropertyValueType;
 use crate::validation::property::validate_property_value;
 
 pub struct UnifiedApi {
+    /// Classes by canonical name; look names up through [`UnifiedApi::object`]
     pub objects: HashMap<String, ApiObject>,
-    global_functions: HashSet<String>,
+    /// Lower-cased class names and aliases to canonical class names
+    class_names: HashMap<String, String>,
+    /// Functions called without a receiver, such as `alert` and `isValid`
+    global_functions: BTreeMap<String, MethodValidation>,
     effect_match_names: HashSet<String>,
     layer_match_names: HashSet<String>,
     property_match_names: HashSet<String>,
     validation_context: ValidationContext,
+    search_index: SearchIndex,
+}
+
+impl Default for UnifiedApi {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl UnifiedApi {
+    /// The standard After Effects model; see [`ApiBuilder`] to assemble a
+    /// different one
     pub fn new() -> Self {
+        ApiBuilder::standard().build()
+    }
+
+    /// A model over `objects`, with the standard enums and, if
+    /// `match_names` is set, match names. Class names resolve without regard
+    /// to case, as do `aliases` (`(alias, class)` pairs such as
+    /// `("app", "Application")`).
+    pub(crate) fn from_objects(objects: HashMap<String, ApiObject>, aliases: Vec<(String, String)>, match_names: bool) -> Self {
+        let mut class_names: HashMap<String, String> = objects.keys().map(|name| (name.to_lowercase(), name.clone())).collect();
+        for (alias, class) in aliases {
+            if objects.contains_key(&class) {
+                class_names.entry(alias.to_lowercase()).or_insert(class);
+            }
+        }
         let mut api = UnifiedApi {
-            objects: HashMap::new(),
-            global_functions: HashSet::new(),
-            effect_match_names: get_effect_match_names().into_iter().map(String::from).collect(),
-            layer_match_names: get_layer_match_names().into_iter().map(String::from).collect(),
-            property_match_names: get_property_match_names().into_iter().map(String::from).collect(),
+            objects,
+            class_names,
+            global_functions: get_global_functions().into_iter().map(|(name, f)| (name.to_string(), f)).collect(),
+            effect_match_names: HashSet::new(),
+            layer_match_names: HashSet::new(),
+            property_match_names: HashSet::new(),
             validation_context: ValidationContext::new(),
+            search_index: SearchIndex::new(),
         };
 
-        api.initialize_core_api();
-        api.initialize_effect_match_names();
-        api.initialize_layer_match_names();
-        api.initialize_property_match_names();
+        if match_names {
+            api.effect_match_names.extend(get_effect_match_names().into_iter().map(String::from));
+            api.layer_match_names.extend(get_layer_match_names().into_iter().map(String::from));
+            api.property_match_names.extend(get_property_match_names().into_iter().map(String::from));
+            api.initialize_effect_match_names();
+            api.initialize_layer_match_names();
+            api.initialize_property_match_names();
+        }
+        api.search_index = api.build_search_index();
 
         api
     }
 
+    /// Canonical name of a class, given its name in any case or an alias
+    pub fn canonical_name(&self, name: &str) -> Option<&str> {
+        match self.objects.get_key_value(name) {
+            Some((canonical, _)) => Some(canonical),
+            None => self.class_names.get(&name.to_lowercase()).map(String::as_str),
+        }
+    }
+
+    pub fn object(&self, name: &str) -> Option<&ApiObject> {
+        self.objects.get(self.canonical_name(name)?)
+    }
+
     pub fn validate_method(&self, class_name: &str, method_name: &str) -> bool {
-        if let Some(obj) = self.objects.get(class_name) {
+        if let Some(obj) = self.object(class_name) {
             obj.methods.contains_key(method_name)
         } else {
             false
         }
     }
 
+    /// Signature of a global function such as `alert` or `timeToCurrentFormat`
+    pub fn global_function(&self, name: &str) -> Option<&MethodValidation> {
+        self.global_functions.get(name)
+    }
+
+    /// Names of the global functions, in alphabetical order
+    pub fn global_function_names(&self) -> impl Iterator<Item = &str> {
+        self.global_functions.keys().map(String::as_str)
+    }
+
+    /// Class returned by a method, when the model declares one
+    pub fn method_return_type(&self, class_name: &str, method_name: &str) -> Option<&str> {
+        self.object(class_name)?
+            .methods.get(method_name)?
+            .return_type.as_deref()
+    }
+
     pub fn validate_property(&self, class_name: &str, property_name: &str) -> bool {
-        if let Some(obj) = self.objects.get(class_name) {
+        if let Some(obj) = self.object(class_name) {
             obj.properties.contains_key(property_name)
         } else {
             false
@@ -68,7 +142,8 @@ impl UnifiedApi {
     }
 
     pub fn validate_property_access(&mut self, class_name: &str, property_name: &str, value: Option<&Value>) -> Result<(), String> {
-        if let Some(api_obj) = self.objects.get(class_name) {
+        let key = self.canonical_name(class_name).unwrap_or(class_name).to_string();
+        if let Some(api_obj) = self.objects.get(&key) {
             // Enter the object's context for validation
             self.validation_context.enter_context(api_obj.object_type.clone());
             
@@ -105,31 +180,55 @@ impl UnifiedApi {
     }
 
     pub fn suggest_effect_match_name(&self, match_name: &str) -> Option<String> {
-        self.fuzzy_match_suggestions(&self.effect_match_names, match_name, "effect")
+        self.fuzzy_match_suggestions(EntryKind::EffectMatchName, match_name, "effect")
     }
 
     pub fn suggest_layer_match_name(&self, match_name: &str) -> Option<String> {
-        self.fuzzy_match_suggestions(&self.layer_match_names, match_name, "layer")
+        self.fuzzy_match_suggestions(EntryKind::LayerMatchName, match_name, "layer")
     }
 
     pub fn suggest_property_match_name(&self, match_name: &str) -> Option<String> {
-        self.fuzzy_match_suggestions(&self.property_match_names, match_name, "property")
+        self.fuzzy_match_suggestions(EntryKind::PropertyMatchName, match_name, "property")
     }
 
-    fn fuzzy_match_suggestions(&self, match_names: &HashSet<String>, input: &str, category: &str) -> Option<String> {
-        let mut similar_matches: Vec<(usize, &String)> = match_names.iter()
-            .map(|name| (strsim::levenshtein(input, name), name))
-            .filter(|(distance, _)| *distance <= 8) // Allow more distance for longer match names
-            .collect();
+    /// Searches object names, members, enum members and match names
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        self.search_index.search(query, limit)
+    }
 
-        similar_matches.sort_by_key(|(distance, _)| *distance);
+    pub fn search_index(&self) -> &SearchIndex {
+        &self.search_index
+    }
 
-        if !similar_matches.is_empty() {
-            let suggestions: Vec<&String> = similar_matches.iter()
-                .take(5) // Show top 5 suggestions
-                .map(|(_, name)| *name)
-                .collect();
+    /// "Did you mean" text for an unknown method or property of a class or enum
+    pub fn suggest_member(&self, class_name: &str, member_name: &str) -> Option<String> {
+        let class_name = self.canonical_name(class_name).unwrap_or(class_name);
+        let candidates = self.search_index.suggest_member(class_name, member_name, 3);
+        if candidates.is_empty() {
+            None
+        } else {
+            Some(format!("Did you mean {}?", candidates.iter().map(|c| format!("'{}'", c)).join(", ")))
+        }
+    }
+
+    pub fn is_enum(&self, name: &str) -> bool {
+        get_enum_definitions().iter().any(|(enum_name, _)| *enum_name == name)
+    }
 
+    pub fn validate_enum_member(&self, enum_name: &str, member: &str) -> bool {
+        get_enum_definitions().iter()
+            .any(|(name, members)| *name == enum_name && members.contains(&member))
+    }
+
+    fn fuzzy_match_suggestions(&self, kind: EntryKind, input: &str, category: &str) -> Option<String> {
+        // Allow more distance for longer match names
+        let suggestions: Vec<String> = self.search_index
+            .search_where(input, 5, 8, |entry| entry.kind == kind)
+            .into_iter()
+            .map(|hit| hit.entry.name.clone())
+            .collect();
+
+        if !suggestions.is_empty() {
             Some(format!(
                 "Invalid {} match name: '{}'\n\nDid you mean one of these?\n{}",
                 category,
@@ -141,14 +240,52 @@ impl UnifiedApi {
         }
     }
 
+    fn build_search_index(&self) -> SearchIndex {
+        let mut index = SearchIndex::new();
+        let mut object_names: Vec<&String> = self.objects.keys().collect();
+        object_names.sort();
+        for name in object_names {
+            let object = &self.objects[name];
+            index.add(name, EntryKind::Object, None);
+            for method in object.methods.keys() {
+                index.add(method, EntryKind::Method, Some(name));
+            }
+            for property in object.properties.keys() {
+                index.add(property, EntryKind::Property, Some(name));
+            }
+        }
+        let mut function_names: Vec<&String> = self.global_functions.keys().collect();
+        function_names.sort();
+        for name in function_names {
+            index.add(name, EntryKind::Function, None);
+        }
+        for (enum_name, members) in get_enum_definitions() {
+            index.add(enum_name, EntryKind::Object, None);
+            for member in members {
+                index.add(member, EntryKind::EnumMember, Some(enum_name));
+            }
+        }
+        for name in &self.effect_match_names {
+            index.add(name, EntryKind::EffectMatchName, None);
+        }
+        for name in &self.layer_match_names {
+            index.add(name, EntryKind::LayerMatchName, None);
+        }
+        for name in &self.property_match_names {
+            index.add(name, EntryKind::PropertyMatchName, None);
+        }
+        index
+    }
+
     pub fn validate_method_call(&mut self, class_name: &str, method_name: &str, args: &[Value]) -> Result<(), String> {
-        if let Some(api_obj) = self.objects.get(class_name) {
+        let key = self.canonical_name(class_name).unwrap_or(class_name).to_string();
+        if let Some(api_obj) = self.objects.get(&key) {
             // Enter the object's context for validation
             self.validation_context.enter_context(api_obj.object_type.clone());
             
             let result = if let Some(method) = api_obj.methods.get(method_name) {
-                // Validate method arguments
-                if args.len() != method.param_count {
+                // Validate method arguments against each overload
+                if !method.signatures().any(|signature| args.len() == signature.param_count) {
                     Err(format!(
                         "Method {} expects {} arguments, got {}",
                         method_name,
@@ -178,7 +315,7 @@ impl UnifiedApi {
     }
 
     pub fn validate_property_value(&self, class_name: &str, property_name: &str, value: &Value, _context: &ValidationContext) -> Result<(), String> {
-        let obj = self.objects.get(class_name)
+        let obj = self.object(class_name)
             .ok_or_else(|| format!("Unknown class: {}", class_name))?;
         
         let prop = obj.properties.get(property_name)
@@ -193,231 +330,6 @@ impl UnifiedApi {
         prop.validate(value)
     }
 
-    fn initialize_core_api(&mut self) {
-        // Application object
-        let mut app = ApiObject::new(ObjectContext::App);
-        
-        // App methods based on After Effects documentation
-        app.methods.insert("beginUndoGroup".to_string(), MethodValidation::new(1));
-        app.methods.insert("endUndoGroup".to_string(), MethodValidation::new(0));
-        app.methods.insert("newProject".to_string(), MethodValidation::new(0));
-        app.methods.insert("open".to_string(), MethodValidation::new(1));
-        app.methods.insert("quit".to_string(), MethodValidation::new(0));
-        app.methods.insert("purge".to_string(), MethodValidation::new(1));
-        app.methods.insert("beginSuppressDialogs".to_string(), MethodValidation::new(0));
-        app.methods.insert("endSuppressDialogs".to_string(), MethodValidation::new(1));
-        app.methods.insert("executeCommand".to_string(), MethodValidation::new(1));
-        app.methods.insert("findMenuCommandId".to_string(), MethodValidation::new(1));
-        app.methods.insert("getPrefsValue".to_string(), MethodValidation::new(2));
-        app.methods.insert("setPrefsValue".to_string(), MethodValidation::new(3));
-        app.methods.insert("savePrefsAs".to_string(), MethodValidation::new(1));
-        app.methods.insert("loadPrefsFromFile".to_string(), MethodValidation::new(1));
-        app.methods.insert("watchFolder".to_string(), MethodValidation::new(1));
-        app.methods.insert("cancelWatchFolder".to_string(), MethodValidation::new(0));
-        app.methods.insert("pauseWatchFolder".to_string(), MethodValidation::new(1));
-        
-        // App properties
-        app.properties.insert("project".to_string(), ValidationRule::simple(PropertyValueType::Custom("Project".to_string())));
-        app.properties.insert("version".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        app.properties.insert("buildName".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        app.properties.insert("buildNumber".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        app.properties.insert("effects".to_string(), ValidationRule::simple(PropertyValueType::Custom("Array".to_string())));
-        app.properties.insert("fonts".to_string(), ValidationRule::simple(PropertyValueType::Custom("Object".to_string())));
-        app.properties.insert("activeViewer".to_string(), ValidationRule::simple(PropertyValueType::Custom("Viewer".to_string())));
-        app.properties.insert("disableRendering".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        app.properties.insert("exitAfterLaunchAndEval".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        app.properties.insert("exitCode".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        app.properties.insert("availableGPUAccelTypes".to_string(), ValidationRule::simple(PropertyValueType::Custom("Array".to_string())));
-
-        self.objects.insert("app".to_string(), app.clone());
-        self.objects.insert("Application".to_string(), app);
-
-        // Project object
-        let mut project = ApiObject::new(ObjectContext::Project);
-        
-        // Project methods
-        project.methods.insert("save".to_string(), MethodValidation::new(0));
-        project.methods.insert("saveWithDialog".to_string(), MethodValidation::new(0));
-        project.methods.insert("close".to_string(), MethodValidation::new(1));
-        project.methods.insert("item".to_string(), MethodValidation::new(1));
-        project.methods.insert("importFile".to_string(), MethodValidation::new(1));
-        project.methods.insert("importFileWithDialog".to_string(), MethodValidation::new(0));
-        project.methods.insert("importPlaceholder".to_string(), MethodValidation::new(5));
-        project.methods.insert("consolidateFootage".to_string(), MethodValidation::new(0));
-        project.methods.insert("removeUnusedFootage".to_string(), MethodValidation::new(0));
-        project.methods.insert("reduceProject".to_string(), MethodValidation::new(1));
-        project.methods.insert("showWindow".to_string(), MethodValidation::new(1));
-        project.methods.insert("autoFixExpressions".to_string(), MethodValidation::new(2));
-        
-        // Project properties
-        project.properties.insert("file".to_string(), ValidationRule::simple(PropertyValueType::Custom("File".to_string())));
-        project.properties.insert("rootFolder".to_string(), ValidationRule::simple(PropertyValueType::Custom("FolderItem".to_string())));
-        project.properties.insert("activeItem".to_string(), ValidationRule::simple(PropertyValueType::Custom("Item".to_string())));
-        project.properties.insert("numItems".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, f64::MAX));
-        project.properties.insert("items".to_string(), ValidationRule::simple(PropertyValueType::Custom("ItemCollection".to_string())));
-        project.properties.insert("renderQueue".to_string(), ValidationRule::simple(PropertyValueType::Custom("RenderQueue".to_string())));
-        project.properties.insert("dirty".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        project.properties.insert("bitsPerChannel".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        project.properties.insert("transparencyGridThumbnails".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        project.properties.insert("workingSpace".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        project.properties.insert("workingGamma".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        project.properties.insert("linearizeWorkingSpace".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        project.properties.insert("compensateForSceneReferredProfiles".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        project.properties.insert("gpuAccelType".to_string(), ValidationRule::simple(PropertyValueType::Custom("GpuAccelType".to_string())));
-        project.properties.insert("expressionEngine".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        project.properties.insert("feetFramesFilmType".to_string(), ValidationRule::simple(PropertyValueType::Custom("FeetFramesFilmType".to_string())));
-        project.properties.insert("framesCountType".to_string(), ValidationRule::simple(PropertyValueType::Custom("FramesCountType".to_string())));
-        project.properties.insert("framesUseFeetFrames".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        project.properties.insert("displayStartFrame".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        project.properties.insert("timeDisplayType".to_string(), ValidationRule::simple(PropertyValueType::Custom("TimeDisplayType".to_string())));
-
-        self.objects.insert("project".to_string(), project.clone());
-        self.objects.insert("Project".to_string(), project);
-
-        // Item object (base class)
-        let mut item = ApiObject::new(ObjectContext::Item);
-        
-        // Item methods
-        item.methods.insert("remove".to_string(), MethodValidation::new(0));
-        item.methods.insert("duplicate".to_string(), MethodValidation::new(0));
-        
-        // Item properties
-        item.properties.insert("name".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        item.properties.insert("id".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(1.0, f64::MAX));
-        item.properties.insert("parentFolder".to_string(), ValidationRule::simple(PropertyValueType::Custom("FolderItem".to_string())));
-        item.properties.insert("selected".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        item.properties.insert("typeName".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        item.properties.insert("comment".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        item.properties.insert("label".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, 16.0));
-
-        self.objects.insert("Item".to_string(), item.clone());
-        self.objects.insert("item".to_string(), item);
-
-        // CompItem object
-        let mut comp_item = ApiObject::new(ObjectContext::Comp);
-        
-        // CompItem methods
-        comp_item.methods.insert("layer".to_string(), MethodValidation::new(1));
-        comp_item.methods.insert("duplicate".to_string(), MethodValidation::new(0));
-        comp_item.methods.insert("openInViewer".to_string(), MethodValidation::new(0));
-        comp_item.methods.insert("saveFrameToPng".to_string(), MethodValidation::new(2));
-        
-        // CompItem properties
-        comp_item.properties.insert("width".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(4.0, 30000.0));
-        comp_item.properties.insert("height".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(4.0, 30000.0));
-        comp_item.properties.insert("duration".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, f64::MAX));
-        comp_item.properties.insert("frameRate".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.01, 99.0));
-        comp_item.properties.insert("pixelAspect".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.01, 99.0));
-        comp_item.properties.insert("layers".to_string(), ValidationRule::simple(PropertyValueType::Custom("LayerCollection".to_string())));
-        comp_item.properties.insert("numLayers".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        comp_item.properties.insert("activeCamera".to_string(), ValidationRule::simple(PropertyValueType::Custom("CameraLayer".to_string())));
-        comp_item.properties.insert("bgColor".to_string(), ValidationRule::simple(PropertyValueType::Custom("Color".to_string())));
-        comp_item.properties.insert("shutterAngle".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        comp_item.properties.insert("shutterPhase".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        comp_item.properties.insert("motionBlur".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        comp_item.properties.insert("draft3d".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        comp_item.properties.insert("frameBlending".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        comp_item.properties.insert("preserveNestedFrameRate".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        comp_item.properties.insert("preserveNestedResolution".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        comp_item.properties.insert("resolutionFactor".to_string(), ValidationRule::simple(PropertyValueType::Custom("TwoD".to_string())));
-        comp_item.properties.insert("workAreaStart".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        comp_item.properties.insert("workAreaDuration".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-
-        self.objects.insert("CompItem".to_string(), comp_item.clone());
-        self.objects.insert("compItem".to_string(), comp_item);
-
-        // Layer object (base layer)
-        let mut layer = ApiObject::new(ObjectContext::Layer);
-        
-        // Layer methods
-        layer.methods.insert("property".to_string(), MethodValidation::new(1));
-        layer.methods.insert("duplicate".to_string(), MethodValidation::new(0));
-        layer.methods.insert("copyToComp".to_string(), MethodValidation::new(1));
-        layer.methods.insert("moveToBeginning".to_string(), MethodValidation::new(0));
-        layer.methods.insert("moveToEnd".to_string(), MethodValidation::new(0));
-        layer.methods.insert("moveAfter".to_string(), MethodValidation::new(1));
-        layer.methods.insert("moveBefore".to_string(), MethodValidation::new(1));
-        layer.methods.insert("remove".to_string(), MethodValidation::new(0));
-        layer.methods.insert("applyPreset".to_string(), MethodValidation::new(1));
-        layer.methods.insert("openInViewer".to_string(), MethodValidation::new(0));
-        
-        // Layer properties
-        layer.properties.insert("name".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        layer.properties.insert("index".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        layer.properties.insert("enabled".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("locked".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("shy".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("solo".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("threeDLayer".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("motionBlur".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("adjustmentLayer".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("guide".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("label".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, 16.0));
-        layer.properties.insert("blendingMode".to_string(), ValidationRule::simple(PropertyValueType::Custom("BlendingMode".to_string())));
-        layer.properties.insert("quality".to_string(), ValidationRule::simple(PropertyValueType::Custom("LayerQuality".to_string())));
-        layer.properties.insert("parent".to_string(), ValidationRule::simple(PropertyValueType::Custom("Layer".to_string())));
-        layer.properties.insert("inPoint".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        layer.properties.insert("outPoint".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        layer.properties.insert("startTime".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        layer.properties.insert("stretch".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        layer.properties.insert("width".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        layer.properties.insert("height".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        layer.properties.insert("containingComp".to_string(), ValidationRule::simple(PropertyValueType::Custom("CompItem".to_string())));
-        layer.properties.insert("isNameSet".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        layer.properties.insert("comment".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-
-        self.objects.insert("Layer".to_string(), layer.clone());
-        self.objects.insert("layer".to_string(), layer);
-
-        // Property object
-        let mut property = ApiObject::new(ObjectContext::Property("".to_string()));
-        
-        // Property methods
-        property.methods.insert("setValue".to_string(), MethodValidation::new(1));
-        property.methods.insert("setValueAtTime".to_string(), MethodValidation::new(2));
-        property.methods.insert("setValueAtKey".to_string(), MethodValidation::new(2));
-        property.methods.insert("valueAtTime".to_string(), MethodValidation::new(2));
-        property.methods.insert("velocityAtTime".to_string(), MethodValidation::new(2));
-        property.methods.insert("speedAtTime".to_string(), MethodValidation::new(1));
-        property.methods.insert("keyTime".to_string(), MethodValidation::new(1));
-        property.methods.insert("keyValue".to_string(), MethodValidation::new(1));
-        property.methods.insert("addKey".to_string(), MethodValidation::new(1));
-        property.methods.insert("removeKey".to_string(), MethodValidation::new(1));
-        property.methods.insert("nearestKeyIndex".to_string(), MethodValidation::new(1));
-        property.methods.insert("setInterpolationTypeAtKey".to_string(), MethodValidation::new(3));
-        property.methods.insert("setTemporalEaseAtKey".to_string(), MethodValidation::new(3));
-        property.methods.insert("setTemporalContinuousAtKey".to_string(), MethodValidation::new(2));
-        property.methods.insert("setTemporalAutoBezierAtKey".to_string(), MethodValidation::new(2));
-        property.methods.insert("setSpatialTangentsAtKey".to_string(), MethodValidation::new(3));
-        property.methods.insert("setSpatialContinuousAtKey".to_string(), MethodValidation::new(2));
-        property.methods.insert("setSpatialAutoBezierAtKey".to_string(), MethodValidation::new(2));
-        property.methods.insert("setRovingAtKey".to_string(), MethodValidation::new(2));
-        property.methods.insert("setSelectedAtKey".to_string(), MethodValidation::new(2));
-        
-        // Property properties
-        property.properties.insert("value".to_string(), ValidationRule::simple(PropertyValueType::Custom("Any".to_string())));
-        property.properties.insert("hasMin".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        property.properties.insert("hasMax".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        property.properties.insert("minValue".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        property.properties.insert("maxValue".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        property.properties.insert("isTimeVarying".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        property.properties.insert("numKeys".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        property.properties.insert("propertyIndex".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
-        property.properties.insert("propertyType".to_string(), ValidationRule::simple(PropertyValueType::Custom("PropertyType".to_string())));
-        property.properties.insert("propertyValueType".to_string(), ValidationRule::simple(PropertyValueType::Custom("PropertyValueType".to_string())));
-        property.properties.insert("unitsText".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        property.properties.insert("expression".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        property.properties.insert("expressionEnabled".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        property.properties.insert("expressionError".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
-        property.properties.insert("canSetExpression".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        property.properties.insert("dimensionsSeparated".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        property.properties.insert("isSeparationFollower".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-        property.properties.insert("isSeparationLeader".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
-
-        self.objects.insert("Property".to_string(), property.clone());
-        self.objects.insert("property".to_string(), property);
-    }
-
     fn initialize_effect_match_names(&mut self) {
         // Based on the After Effects documentation, add comprehensive effect match names
         let effect_names = vec![
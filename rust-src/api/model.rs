@@ -0,0 +1,100 @@
+//! Serialized form of a [`UnifiedApi`], written by `ae-validator dump-model
+//! [--out model.json]` so models can be diffed between versions, inspected by
+//! external tools and loaded back.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use super::{ApiObject, UnifiedApi};
+
+/// Every class, alias and match name of a model. Maps and sets are ordered
+/// so that dumps of the same model are identical.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiModel {
+    /// Classes by canonical name
+    pub classes: BTreeMap<String, ApiObject>,
+    /// Lower-cased aliases to the class they name, e.g. `app` to `Application`
+    pub aliases: BTreeMap<String, String>,
+    pub effect_match_names: BTreeSet<String>,
+    pub layer_match_names: BTreeSet<String>,
+    pub property_match_names: BTreeSet<String>,
+}
+
+impl ApiModel {
+    pub fn from_api(api: &UnifiedApi) -> Self {
+        let aliases = api.class_names.iter()
+            .filter(|(name, class)| **name != class.to_lowercase())
+            .map(|(alias, class)| (alias.clone(), class.clone()))
+            .collect();
+        ApiModel {
+            classes: api.objects.iter().map(|(name, object)| (name.clone(), object.clone())).collect(),
+            aliases,
+            effect_match_names: api.effect_match_names.iter().cloned().collect(),
+            layer_match_names: api.layer_match_names.iter().cloned().collect(),
+            property_match_names: api.property_match_names.iter().cloned().collect(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid API model: {}", e))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    /// Pretty JSON with the members of every class sorted by name
+    pub fn to_json(&self) -> String {
+        // Going through a Value sorts the classes' member maps
+        serde_json::to_value(self)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+            .unwrap_or_default()
+    }
+
+    /// The model this dump describes. Rules loaded from a dump have no
+    /// custom validators.
+    pub fn into_api(self) -> UnifiedApi {
+        let mut api = UnifiedApi::from_objects(
+            self.classes.into_iter().collect(),
+            self.aliases.into_iter().collect(),
+            false,
+        );
+        api.effect_match_names = self.effect_match_names.into_iter().collect();
+        api.layer_match_names = self.layer_match_names.into_iter().collect();
+        api.property_match_names = self.property_match_names.into_iter().collect();
+        api.search_index = api.build_search_index();
+        api
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::rules::PropertyValueType;
+
+    #[test]
+    fn test_model_round_trip() {
+        let model = ApiModel::from_api(&UnifiedApi::new());
+        assert_eq!(model.aliases.get("app").map(String::as_str), Some("Application"));
+        assert!(!model.aliases.contains_key("application"));
+
+        let json = model.to_json();
+        let loaded = ApiModel::from_json(&json).unwrap();
+        assert_eq!(loaded.to_json(), json);
+
+        let api = loaded.into_api();
+        assert_eq!(api.canonical_name("LAYERS"), Some("LayerCollection"));
+        assert!(api.validate_method("compItem", "addGuide"));
+        assert!(api.validate_effect_match_name("ADBE Gaussian Blur 2"));
+        assert_eq!(
+            api.object("ImportOptions").unwrap().properties["importAs"].value_type,
+            PropertyValueType::Custom("ImportAsType".to_string())
+        );
+        assert!(!api.search("addGuide", 5).is_empty());
+        assert!(ApiModel::from_json("{\"classes\": 1}").unwrap_err().starts_with("Invalid API model: "));
+    }
+}
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use crate::validation::context::{ObjectContext, TextValidationContext};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// Adobe After Effects Application - Complete comprehensive implementation
 /// Represents the main AE application object with all documented features
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Application {
     pub app_object: AppObject,
     pub project: Option<String>, // Project reference
@@ -105,7 +106,7 @@ pub struct LocaleSettings {
 }
 
 /// Effect information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppEffectInfo {
     pub display_name: String,
     pub category: String,
@@ -151,7 +152,7 @@ pub enum ApplicationMode {
     Headless,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiObject {
     pub methods: HashMap<String, MethodValidation>,
     pub properties: HashMap<String, ValidationRule>,
@@ -175,16 +176,32 @@ impl ApiObject {
         }
     }
 
+    /// Checks a new alternate source, given as the class name of the item
+    /// passed to `setAlternateSource`
     pub fn validate_alternate_source(&self, value: &Value) -> Result<(), String> {
         if !self.can_set_alternate_source {
             return Err("alternateSource is not available for this object type".to_string());
         }
-        
-        // Basic validation for alternate source
-        Ok(())
+
+        let class = value.as_str()
+            .ok_or_else(|| format!("alternateSource expects an item class name, got {}", value))?;
+        match &self.alternate_source_type {
+            Some(expected) if !is_alternate_source_class(expected, class) => Err(format!(
+                "alternateSource must be {} ({}), got {}", expected, AV_ITEM_CLASSES.join(" or "), class
+            )),
+            _ => Ok(()),
+        }
     }
 }
 
+/// Items that can stand in as media: what an `AVItem` source accepts
+pub const AV_ITEM_CLASSES: &[&str] = &["CompItem", "FootageItem"];
+
+/// Whether an item of `class` can replace a source of type `expected`
+pub fn is_alternate_source_class(expected: &str, class: &str) -> bool {
+    class == expected || (expected == "AVItem" && AV_ITEM_CLASSES.contains(&class))
+}
+
 #[derive(Debug, Clone)]
 pub struct AppObject {
     pub api_object: ApiObject,
@@ -220,6 +237,12 @@ pub struct PerformanceStats {
     pub gpu_utilization: f64,
 }
 
+impl Default for AppObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AppObject {
     pub fn new() -> Self {
         let mut app_object = Self {
@@ -240,8 +263,49 @@ impl AppObject {
         app_object.initialize_preferences();
         app_object.initialize_effects_info();
         app_object.initialize_fonts_info();
+        app_object.initialize_validated_members();
         app_object
     }
+
+    /// Members as scripts use them, checked by the validator; these replace any
+    /// entry above for the same member
+    fn initialize_validated_members(&mut self) {
+        let object = &mut self.api_object;
+
+        // App methods based on After Effects documentation
+        object.methods.insert("beginUndoGroup".to_string(), MethodValidation::new(1));
+        object.methods.insert("endUndoGroup".to_string(), MethodValidation::new(0));
+        object.methods.insert("newProject".to_string(), MethodValidation::new(0));
+        object.methods.insert("open".to_string(), MethodValidation::new(1));
+        object.methods.insert("quit".to_string(), MethodValidation::new(0));
+        object.methods.insert("purge".to_string(), MethodValidation::new(1));
+        object.methods.insert("beginSuppressDialogs".to_string(), MethodValidation::new(0));
+        object.methods.insert("endSuppressDialogs".to_string(), MethodValidation::new(1));
+        object.methods.insert("executeCommand".to_string(), MethodValidation::new(1));
+        object.methods.insert("findMenuCommandId".to_string(), MethodValidation::new(1));
+        object.methods.insert("getPrefsValue".to_string(), MethodValidation::new(2));
+        object.methods.insert("setPrefsValue".to_string(), MethodValidation::new(3));
+        object.methods.insert("savePrefsAs".to_string(), MethodValidation::new(1));
+        object.methods.insert("loadPrefsFromFile".to_string(), MethodValidation::new(1));
+        object.methods.insert("watchFolder".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::Custom("Folder".to_string())]));
+        object.methods.insert("endWatchFolder".to_string(), MethodValidation::new(0));
+        object.methods.insert("cancelWatchFolder".to_string(), MethodValidation::new(0));
+        object.methods.insert("pauseWatchFolder".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::Custom("Boolean".to_string())]));
+
+        // App properties
+        object.properties.insert("project".to_string(), ValidationRule::simple(PropertyValueType::Custom("Project".to_string())));
+        object.properties.insert("version".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("buildName".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("buildNumber".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("effects".to_string(), ValidationRule::simple(PropertyValueType::Custom("Array".to_string())));
+        object.properties.insert("fonts".to_string(), ValidationRule::simple(PropertyValueType::Custom("Object".to_string())));
+        object.properties.insert("activeViewer".to_string(), ValidationRule::simple(PropertyValueType::Custom("Viewer".to_string())));
+        object.properties.insert("disableRendering".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("exitAfterLaunchAndEval".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("isWatchFolder".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("exitCode".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("availableGPUAccelTypes".to_string(), ValidationRule::simple(PropertyValueType::Custom("Array".to_string())));
+    }
     
     fn initialize_methods(&mut self) {
         // Core application control methods
@@ -281,7 +345,7 @@ impl AppObject {
         ]));
         
         // Watch folder management
-        self.api_object.methods.insert("watchFolder".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::Custom("File".to_string())]));
+        self.api_object.methods.insert("watchFolder".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::Custom("Folder".to_string())]));
         self.api_object.methods.insert("endWatchFolder".to_string(), MethodValidation::new(0));
         self.api_object.methods.insert("pauseWatchFolder".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::Custom("Boolean".to_string())]));
         
@@ -621,17 +685,6 @@ impl Default for MemoryLimits {
     }
 }
 
-impl Default for Application {
-    fn default() -> Self {
-        Application {
-            app_object: AppObject::new(),
-            project: None,
-            preferences: ApplicationPreferences::default(),
-            system_info: SystemInfo::default(),
-        }
-    }
-}
-
 impl Default for SystemInfo {
     fn default() -> Self {
         SystemInfo {
@@ -1,4 +1,3 @@
-use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::item::{Item, ItemType};
 
@@ -224,18 +223,7 @@ impl AVItem {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: Some(Box::new(|value| {
-                // Common pixel aspect ratios with tolerance for floating point precision
-                if let Some(par) = value.as_f64() {
-                    let common_pars = vec![0.909091, 1.0, 1.5, 1.09402, 1.21212, 1.33333, 1.45869, 2.0];
-                    for common_par in common_pars {
-                        if (par - common_par).abs() < 0.001 {
-                            return Ok(());
-                        }
-                    }
-                }
-                Ok(()) // Allow any value in range
-            })),
+            custom_validator: None,
         });
         
         // Proxy properties
@@ -290,10 +278,7 @@ impl AVItem {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: Some(Box::new(|_value| {
-                // Array of CompItem objects - validation would check each element
-                Ok(())
-            })),
+            custom_validator: None,
         });
         
         // Media replacement compatibility (After Effects 18.0+)
@@ -1,6 +1,5 @@
-use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
-use super::layer::{Layer, LayerType, BlendingMode, TrackMatteType, LayerQuality};
+use super::layer::{Layer, LayerType, BlendingMode, LayerQuality};
 
 /// AVLayer object - represents audio/video layers
 /// Inherits from Layer → PropertyGroup → PropertyBase
@@ -9,13 +8,17 @@ pub struct AVLayer {
     pub base: Layer,
 }
 
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum FeatherFalloff {
     FFO_LINEAR = 1,
     FFO_SMOOTH = 2,
 }
 
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum PostRenderAction {
     NONE = 0,
     IMPORT = 1,
@@ -31,6 +34,12 @@ pub enum LightType {
     AMBIENT = 4,
 }
 
+impl Default for AVLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AVLayer {
     pub fn new() -> Self {
         let mut av_layer = Self {
@@ -627,7 +636,7 @@ impl AVLayer {
     }
     
     /// Check if audio is active at the specified time
-    pub fn is_audio_active_at_time(&self, time: f64) -> bool {
+    pub fn is_audio_active_at_time(&self, _time: f64) -> bool {
         // This would check audio enabled state, solo states, and in/out points
         // For now, return false as default
         false
@@ -708,21 +717,21 @@ pub mod avlayer_factory {
     
     /// Create an adjustment layer
     pub fn create_adjustment_layer() -> AVLayer {
-        let mut layer = AVLayer::new();
+        let layer = AVLayer::new();
         // Adjustment layer setup would go here
         layer
     }
     
     /// Create a 3D layer
     pub fn create_3d_layer() -> AVLayer {
-        let mut layer = AVLayer::new();
+        let layer = AVLayer::new();
         // 3D layer setup would go here
         layer
     }
     
     /// Create a guide layer
     pub fn create_guide_layer() -> AVLayer {
-        let mut layer = AVLayer::new();
+        let layer = AVLayer::new();
         // Guide layer setup would go here
         layer
     }
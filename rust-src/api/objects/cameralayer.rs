@@ -1,4 +1,3 @@
-use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::layer::{Layer, LayerType};
 
@@ -23,7 +22,9 @@ pub enum CameraFilmSize {
     Custom = 3,
 }
 
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum DepthOfFieldBlur {
     None = 0,
     Fast = 1,
@@ -41,6 +42,12 @@ pub enum IrisShape {
     Octagon = 6,
 }
 
+impl Default for CameraLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CameraLayer {
     pub fn new() -> Self {
         let mut camera_layer = Self {
@@ -827,28 +834,28 @@ pub mod cameralayer_factory {
     
     /// Create a standard two-node camera
     pub fn create_two_node_camera() -> CameraLayer {
-        let mut camera = CameraLayer::new();
+        let camera = CameraLayer::new();
         // Two-node camera setup would go here
         camera
     }
     
     /// Create a one-node camera
     pub fn create_one_node_camera() -> CameraLayer {
-        let mut camera = CameraLayer::new();
+        let camera = CameraLayer::new();
         // One-node camera setup would go here
         camera
     }
     
     /// Create a camera with depth of field enabled
     pub fn create_dof_camera() -> CameraLayer {
-        let mut camera = CameraLayer::new();
+        let camera = CameraLayer::new();
         // Depth of field setup would go here
         camera
     }
     
     /// Create a tracking camera
     pub fn create_tracking_camera() -> CameraLayer {
-        let mut camera = CameraLayer::new();
+        let camera = CameraLayer::new();
         // Tracking camera setup would go here
         camera
     }
@@ -2,9 +2,16 @@ use crate::validation::context::ObjectContext;
 use super::app::ApiObject;
 
 pub struct CollectionObject {
+    #[allow(dead_code)]
     api_object: ApiObject,
 }
 
+impl Default for CollectionObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CollectionObject {
     pub fn new() -> Self {
         Self {
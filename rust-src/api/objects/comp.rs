@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
@@ -7,6 +6,12 @@ pub struct CompItem {
     api_object: ApiObject,
 }
 
+impl Default for CompItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CompItem {
     pub fn new() -> Self {
         let mut comp_item = Self {
@@ -397,6 +402,12 @@ pub struct EssentialProperty {
     api_object: ApiObject,
 }
 
+impl Default for EssentialProperty {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EssentialProperty {
     pub fn new() -> Self {
         let mut essential_property = Self {
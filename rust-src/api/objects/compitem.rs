@@ -8,6 +8,12 @@ pub struct CompItem {
     pub base: AVItem,
 }
 
+impl Default for CompItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CompItem {
     pub fn new() -> Self {
         let mut comp_item = Self {
@@ -16,8 +22,43 @@ impl CompItem {
         
         comp_item.initialize_comp_methods();
         comp_item.initialize_comp_properties();
+        comp_item.initialize_validated_members();
         comp_item
     }
+
+    /// Signatures the validator checks comp calls against, replacing the
+    /// inherited and descriptive entries for the same members
+    fn initialize_validated_members(&mut self) {
+        let object = &mut self.base.base.api_object;
+        object.object_type = ObjectContext::Comp;
+
+        // CompItem methods
+        object.methods.insert("layer".to_string(), MethodValidation::new(1));
+        object.methods.insert("duplicate".to_string(), MethodValidation::new(0));
+        object.methods.insert("openInViewer".to_string(), MethodValidation::new(0));
+        object.methods.insert("saveFrameToPng".to_string(), MethodValidation::new(2));
+
+        // CompItem properties
+        object.properties.insert("width".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(4.0, 30000.0));
+        object.properties.insert("height".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(4.0, 30000.0));
+        object.properties.insert("duration".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, f64::MAX));
+        object.properties.insert("frameRate".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.01, 99.0));
+        object.properties.insert("pixelAspect".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.01, 99.0));
+        object.properties.insert("layers".to_string(), ValidationRule::simple(PropertyValueType::Custom("LayerCollection".to_string())));
+        object.properties.insert("numLayers".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("activeCamera".to_string(), ValidationRule::simple(PropertyValueType::Custom("CameraLayer".to_string())));
+        object.properties.insert("bgColor".to_string(), ValidationRule::simple(PropertyValueType::Custom("Color".to_string())));
+        object.properties.insert("shutterAngle".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("shutterPhase".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("motionBlur".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("draft3d".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("frameBlending".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("preserveNestedFrameRate".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("preserveNestedResolution".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("resolutionFactor".to_string(), ValidationRule::simple(PropertyValueType::Custom("TwoD".to_string())));
+        object.properties.insert("workAreaStart".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("workAreaDuration".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+    }
     
     fn initialize_comp_methods(&mut self) {
         // CompItem-specific methods (in addition to AVItem/Item methods)
@@ -500,7 +541,7 @@ pub mod compitem_factory {
     
     /// Create a composition with Motion Graphics Template capabilities
     pub fn create_mgt_composition() -> CompItem {
-        let mut comp = CompItem::new();
+        let comp = CompItem::new();
         // Additional MGT-specific setup would go here
         comp
     }
@@ -3,6 +3,7 @@ use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::propertygroup::PropertyGroup;
 use super::propertybase::PropertyType;
+use crate::data::match_names::get_effect_group;
 
 /// Effect object - represents an individual effect applied to a layer
 /// Inherits from PropertyGroup → PropertyBase
@@ -54,6 +55,68 @@ pub enum EffectCategory {
     ImmersiveVideo,     // VR/360 effects (subset of various categories)
 }
 
+impl EffectCategory {
+    /// Category of an Effect menu group from the match name data
+    pub fn from_group(group: &str) -> Option<Self> {
+        match group {
+            "3D Channel" => Some(EffectCategory::ThreeDChannel),
+            "Audio" => Some(EffectCategory::Audio),
+            "Blur & Sharpen" => Some(EffectCategory::BlurSharpen),
+            "Channel" => Some(EffectCategory::Channel),
+            "Cinema 4D" => Some(EffectCategory::Cinema4D),
+            "Color Correction" => Some(EffectCategory::ColorCorrection),
+            "Distort" => Some(EffectCategory::Distort),
+            "Expression Controls" => Some(EffectCategory::ExpressionControls),
+            "Generate" => Some(EffectCategory::Generate),
+            "Keying" => Some(EffectCategory::Keying),
+            "Matte" => Some(EffectCategory::Matte),
+            "Noise & Grain" => Some(EffectCategory::NoiseGrain),
+            "Obsolete" => Some(EffectCategory::Obsolete),
+            "Perspective" => Some(EffectCategory::Perspective),
+            "Simulation" => Some(EffectCategory::Simulation),
+            "Stylize" => Some(EffectCategory::Stylize),
+            "Synthetic Aperture" => Some(EffectCategory::SyntheticAperture),
+            "Text" => Some(EffectCategory::Text),
+            "Time" => Some(EffectCategory::Time),
+            "Transition" => Some(EffectCategory::Transition),
+            "Utility" => Some(EffectCategory::Utility),
+            _ => None,
+        }
+    }
+}
+
+/// Whether an effect is part of every After Effects install
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectAvailability {
+    /// Made by Adobe and always installed
+    Standard,
+    /// Third-party plug-in installed alongside After Effects, such as the
+    /// Cycore `CC` effects. It is missing when it was left out of the
+    /// install, removed, or not yet licensed on a render node.
+    Bundled { vendor: &'static str },
+}
+
+impl EffectAvailability {
+    /// Match name prefixes of the bundled third-party plug-ins
+    const VENDORS: &'static [(&'static str, &'static str)] = &[
+        ("CC ", "Cycore"),
+        ("CS", "Cycore"),
+        ("Keylight", "The Foundry"),
+        ("SYNTHAP", "Synthetic Aperture"),
+        ("CINEMA 4D", "Maxon"),
+        ("EXtractoR", "fnord"),
+        ("IDentifier", "fnord"),
+        ("ISL ", "Boris FX (Mocha)"),
+        ("VISINF", "Visual Infinity"),
+    ];
+
+    pub fn of(match_name: &str) -> Self {
+        Self::VENDORS.iter()
+            .find(|(prefix, _)| match_name.starts_with(prefix))
+            .map_or(EffectAvailability::Standard, |(_, vendor)| EffectAvailability::Bundled { vendor })
+    }
+}
+
 /// Effect bit depth support levels
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BitDepthSupport {
@@ -500,6 +563,12 @@ impl Effect {
     }
 }
 
+impl Default for EffectCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EffectCollection {
     /// Create a new EffectCollection (Effect Parade)
     pub fn new() -> Self {
@@ -804,13 +873,24 @@ impl EffectMatchNames {
         effects.insert("ADBE Geometry2", EffectMetadata::new("Transform", EffectCategory::Distort, vec![32], true, Some("15.0".to_string())));
         effects.insert("ADBE Offset", EffectMetadata::new("Offset", EffectCategory::Distort, vec![16], true, Some("14.2".to_string())));
         
+        for (match_name, metadata) in effects.iter_mut() {
+            metadata.availability = EffectAvailability::of(match_name);
+        }
         effects
     }
     
-    /// Get effect category by match name
+    /// Get effect category by match name, from the Effect menu group for
+    /// effects without detailed metadata
     pub fn get_effect_category(match_name: &str) -> Option<EffectCategory> {
         let effects = Self::get_all_effects_with_metadata();
-        effects.get(match_name).map(|metadata| metadata.category)
+        effects.get(match_name)
+            .map(|metadata| metadata.category)
+            .or_else(|| get_effect_group(match_name).and_then(EffectCategory::from_group))
+    }
+    
+    /// Whether a built-in effect ships with every install
+    pub fn get_availability(match_name: &str) -> EffectAvailability {
+        EffectAvailability::of(match_name)
     }
     
     /// Get all effects in a specific category
@@ -847,6 +927,7 @@ pub struct EffectMetadata {
     pub bit_depth_support: Vec<u8>,
     pub gpu_accelerated: bool,
     pub gpu_version: Option<String>,
+    pub availability: EffectAvailability,
 }
 
 impl EffectMetadata {
@@ -863,6 +944,7 @@ impl EffectMetadata {
             bit_depth_support,
             gpu_accelerated,
             gpu_version,
+            availability: EffectAvailability::Standard,
         }
     }
 }
@@ -1151,7 +1233,7 @@ impl EffectOptimization {
     
     /// Get recommended render order for optimal performance
     pub fn get_optimal_render_order<'a>(effects: &'a [&'a str]) -> Vec<&'a str> {
-        let mut sorted_effects: Vec<&str> = effects.iter().copied().collect();
+        let mut sorted_effects: Vec<&str> = effects.to_vec();
         
         // Sort by performance impact (low impact first)
         sorted_effects.sort_by_key(|effect| Self::get_performance_impact(effect));
@@ -1257,6 +1339,14 @@ mod tests {
         assert!(EffectOptimization::can_batch_gpu_acceleration(&gpu_effects));
     }
     
+    #[test]
+    fn test_effect_availability() {
+        assert_eq!(EffectMatchNames::get_availability("ADBE Gaussian Blur 2"), EffectAvailability::Standard);
+        assert_eq!(EffectMatchNames::get_availability("CC Radial Blur"), EffectAvailability::Bundled { vendor: "Cycore" });
+        assert_eq!(EffectMatchNames::get_all_effects_with_metadata()["CS CrossBlur"].availability, EffectAvailability::Bundled { vendor: "Cycore" });
+        assert_eq!(EffectMatchNames::get_effect_category("CC Bubbles"), Some(EffectCategory::Simulation));
+    }
+    
     #[test]
     fn test_bit_depth_support() {
         let bit_depths = EffectMatchNames::get_supported_bit_depths("ADBE Gaussian Blur 2");
@@ -52,6 +52,12 @@ pub struct Point3DControl {
     base: ExpressionControl,
 }
 
+impl Default for Point3DControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Point3DControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -84,6 +90,12 @@ pub struct AngleControl {
     base: ExpressionControl,
 }
 
+impl Default for AngleControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AngleControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -116,6 +128,12 @@ pub struct CheckboxControl {
     base: ExpressionControl,
 }
 
+impl Default for CheckboxControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CheckboxControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -148,6 +166,12 @@ pub struct ColorControl {
     base: ExpressionControl,
 }
 
+impl Default for ColorControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ColorControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -180,6 +204,12 @@ pub struct DropdownControl {
     base: ExpressionControl,
 }
 
+impl Default for DropdownControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DropdownControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -221,6 +251,12 @@ pub struct LayerControl {
     base: ExpressionControl,
 }
 
+impl Default for LayerControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LayerControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -253,6 +289,12 @@ pub struct PointControl {
     base: ExpressionControl,
 }
 
+impl Default for PointControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PointControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -285,6 +327,12 @@ pub struct SliderControl {
     base: ExpressionControl,
 }
 
+impl Default for SliderControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SliderControl {
     pub fn new() -> Self {
         let mut control = Self {
@@ -1,4 +1,3 @@
-use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::item::{Item, ItemType};
 
@@ -8,6 +7,12 @@ pub struct FolderItem {
     pub base: Item,
 }
 
+impl Default for FolderItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FolderItem {
     pub fn new() -> Self {
         let mut folder_item = Self {
@@ -400,14 +405,14 @@ pub mod folderitem_factory {
     
     /// Create a root folder (top-level project folder)
     pub fn create_root_folder() -> FolderItem {
-        let mut folder = FolderItem::new();
+        let folder = FolderItem::new();
         // Additional root folder setup would go here
         folder
     }
     
     /// Create a colored folder with specific organization
-    pub fn create_colored_folder(color_index: u8) -> FolderItem {
-        let mut folder = FolderItem::new();
+    pub fn create_colored_folder(_color_index: u8) -> FolderItem {
+        let folder = FolderItem::new();
         // Color setup would go here
         folder
     }
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
@@ -8,6 +7,12 @@ pub struct FontObject {
     api_object: ApiObject,
 }
 
+impl Default for FontObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FontObject {
     pub fn new() -> Self {
         let mut font_object = Self {
@@ -201,6 +206,12 @@ pub struct FontsObject {
     api_object: ApiObject,
 }
 
+impl Default for FontsObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FontsObject {
     pub fn new() -> Self {
         let mut fonts_object = Self {
@@ -274,6 +285,12 @@ pub struct CharacterRange {
     api_object: ApiObject,
 }
 
+impl Default for CharacterRange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CharacterRange {
     pub fn new() -> Self {
         let mut character_range = Self {
@@ -392,6 +409,12 @@ pub struct ParagraphRange {
     api_object: ApiObject,
 }
 
+impl Default for ParagraphRange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ParagraphRange {
     pub fn new() -> Self {
         let mut paragraph_range = Self {
@@ -1,4 +1,3 @@
-use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::avitem::{AVItem, AVItemType};
 
@@ -8,6 +7,12 @@ pub struct FootageItem {
     pub base: AVItem,
 }
 
+impl Default for FootageItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FootageItem {
     pub fn new() -> Self {
         let mut footage_item = Self {
@@ -560,21 +565,21 @@ pub mod footageitem_factory {
     
     /// Create a still image footage item
     pub fn create_still_footage() -> FootageItem {
-        let mut footage = FootageItem::new();
+        let footage = FootageItem::new();
         // Still image setup would go here
         footage
     }
     
     /// Create an image sequence footage item
     pub fn create_sequence_footage() -> FootageItem {
-        let mut footage = FootageItem::new();
+        let footage = FootageItem::new();
         // Sequence setup would go here
         footage
     }
     
     /// Create a video footage item
     pub fn create_video_footage() -> FootageItem {
-        let mut footage = FootageItem::new();
+        let footage = FootageItem::new();
         // Video footage setup would go here
         footage
     }
@@ -1,7 +1,6 @@
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
-use std::collections::HashMap;
 
 /// ImportOptions object for importing files into After Effects
 /// Comprehensive implementation supporting all Adobe AE import functionality
@@ -93,6 +92,7 @@ pub struct Cinema4DImportOptions {
 
 /// Audio import options
 #[derive(Debug, Clone)]
+#[derive(Default)]
 pub struct AudioImportOptions {
     pub sample_rate: Option<f32>,
     pub bit_depth: Option<i32>,
@@ -149,6 +149,12 @@ pub struct ImportValidationResult {
     pub errors: Vec<String>,
 }
 
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ImportOptions {
     /// Create new import options
     pub fn new() -> Self {
@@ -591,16 +597,6 @@ impl Default for Cinema4DImportOptions {
     }
 }
 
-impl Default for AudioImportOptions {
-    fn default() -> Self {
-        AudioImportOptions {
-            sample_rate: None,
-            bit_depth: None,
-            interpret_timecode: false,
-            conform_frame_rate: None,
-        }
-    }
-}
 
 impl Default for VideoImportOptions {
     fn default() -> Self {
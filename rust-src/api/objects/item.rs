@@ -1,4 +1,5 @@
 use crate::validation::context::ObjectContext;
+use crate::validation::custom::CustomValidator;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
 
@@ -41,8 +42,27 @@ impl Item {
         
         item.initialize_methods();
         item.initialize_properties();
+        item.initialize_validated_members();
         item
     }
+
+    /// Signatures the validator checks; shared by every item subclass
+    fn initialize_validated_members(&mut self) {
+        let object = &mut self.api_object;
+
+        // Item methods
+        object.methods.insert("remove".to_string(), MethodValidation::new(0));
+        object.methods.insert("duplicate".to_string(), MethodValidation::new(0));
+
+        // Item properties
+        object.properties.insert("name".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("id".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(1.0, f64::MAX));
+        object.properties.insert("parentFolder".to_string(), ValidationRule::simple(PropertyValueType::Custom("FolderItem".to_string())));
+        object.properties.insert("selected".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("typeName".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("comment".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("label".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, 16.0));
+    }
     
     fn initialize_methods(&mut self) {
         // Core Item methods
@@ -108,14 +128,7 @@ impl Item {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: Some(Box::new(|value| {
-                if let Some(text) = value.as_str() {
-                    if text.len() > 15999 {
-                        return Err("Comment cannot exceed 15,999 characters".to_string());
-                    }
-                }
-                Ok(())
-            })),
+            custom_validator: Some(CustomValidator::LengthRange { min: None, max: Some(15999) }),
         });
         
         // dynamicLinkGUID - Unique persistent identification for dynamic link
@@ -129,16 +142,9 @@ impl Item {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: Some(Box::new(|value| {
-                if let Some(guid) = value.as_str() {
-                    // Validate GUID format: 00000000-0000-0000-0000-000000000000
-                    let guid_pattern = regex::Regex::new(r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$").unwrap();
-                    if !guid_pattern.is_match(guid) {
-                        return Err("Invalid GUID format".to_string());
-                    }
-                }
-                Ok(())
-            })),
+            custom_validator: Some(CustomValidator::Regex(
+                r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$".to_string(),
+            )),
         });
         
         // guides - Array of guide objects (After Effects 16.1+)
@@ -1,5 +1,5 @@
 use crate::validation::context::ObjectContext;
-use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
+use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType, RangeValidation};
 use super::app::ApiObject;
 
 /// ItemCollection object - represents a collection of items in the Project panel
@@ -45,16 +45,23 @@ impl ItemCollection {
             PropertyValueType::OneD,     // pixelAspect (0.01-100.0)
             PropertyValueType::OneD,     // duration (0.0-10800.0)
             PropertyValueType::OneD      // frameRate (1.0-99.0)
-        ]));
+        ]).with_param_ranges(vec![
+            RangeValidation::unbounded("name"),
+            RangeValidation::between("width", 4.0, 30000.0),
+            RangeValidation::between("height", 4.0, 30000.0),
+            RangeValidation::between("pixelAspect", 0.01, 100.0),
+            RangeValidation::between("duration", 0.0, 10800.0),
+            RangeValidation::between("frameRate", 1.0, 99.0),
+        ]).with_return_type("CompItem"));
         
         self.api_object.methods.insert("addFolder".to_string(), MethodValidation::new(1).with_param_types(vec![
             PropertyValueType::ArbText  // name
-        ]));
+        ]).with_return_type("FolderItem"));
         
         // Import methods
         self.api_object.methods.insert("addFootage".to_string(), MethodValidation::new(1).with_param_types(vec![
             PropertyValueType::Custom("File".to_string())  // ExtendScript File object
-        ]));
+        ]).with_return_type("FootageItem"));
         
         self.api_object.methods.insert("addSolid".to_string(), MethodValidation::new(5).with_param_types(vec![
             PropertyValueType::Color,    // color [R,G,B] in range [0.0..1.0]
@@ -62,7 +69,13 @@ impl ItemCollection {
             PropertyValueType::OneD,     // width (4-30000)
             PropertyValueType::OneD,     // height (4-30000)
             PropertyValueType::OneD      // pixelAspect (0.01-100.0)
-        ]));
+        ]).with_param_ranges(vec![
+            RangeValidation::unbounded("color"),
+            RangeValidation::unbounded("name"),
+            RangeValidation::between("width", 4.0, 30000.0),
+            RangeValidation::between("height", 4.0, 30000.0),
+            RangeValidation::between("pixelAspect", 0.01, 100.0),
+        ]).with_return_type("FootageItem"));
         
         self.api_object.methods.insert("addPlaceholder".to_string(), MethodValidation::new(5).with_param_types(vec![
             PropertyValueType::ArbText,  // name
@@ -70,7 +83,7 @@ impl ItemCollection {
             PropertyValueType::OneD,     // height (4-30000)
             PropertyValueType::OneD,     // frameRate (1.0-99.0)
             PropertyValueType::OneD      // duration (0.0-10800.0)
-        ]));
+        ]).with_return_type("FootageItem"));
         
         // Batch import methods
         self.api_object.methods.insert("importFiles".to_string(), MethodValidation::new(1).with_param_types(vec![
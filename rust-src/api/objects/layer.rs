@@ -22,6 +22,66 @@ pub enum LayerType {
     Adjustment, // Adjustment layer
 }
 
+/// Features only some layer types have. Scripts see every layer through the
+/// same Layer API, so using a feature the layer lacks fails at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerCapability {
+    Effects,
+    Masks,
+    LayerStyles,
+    Materials,
+    /// `replaceSource()`: text and shape layers have no source item
+    ReplaceSource,
+    /// AVLayer switches such as blending mode, track mattes and motion blur
+    AvSwitches,
+    TextProperties,
+    ShapeContents,
+    CameraOptions,
+    LightOptions,
+}
+
+/// Layer members that need a capability, by attribute or method name
+pub const LAYER_MEMBER_CAPABILITIES: &[(&str, LayerCapability)] = &[
+    ("effect", LayerCapability::Effects),
+    ("effectsActive", LayerCapability::Effects),
+    ("mask", LayerCapability::Masks),
+    ("layerStyle", LayerCapability::LayerStyles),
+    ("castsShadows", LayerCapability::Materials),
+    ("lightTransmission", LayerCapability::Materials),
+    ("acceptsShadows", LayerCapability::Materials),
+    ("acceptsLights", LayerCapability::Materials),
+    ("replaceSource", LayerCapability::ReplaceSource),
+    ("blendingMode", LayerCapability::AvSwitches),
+    ("trackMatteType", LayerCapability::AvSwitches),
+    ("setTrackMatte", LayerCapability::AvSwitches),
+    ("preserveTransparency", LayerCapability::AvSwitches),
+    ("motionBlur", LayerCapability::AvSwitches),
+    ("threeDLayer", LayerCapability::AvSwitches),
+    ("adjustmentLayer", LayerCapability::AvSwitches),
+    ("quality", LayerCapability::AvSwitches),
+    ("frameBlendingType", LayerCapability::AvSwitches),
+    ("timeRemapEnabled", LayerCapability::AvSwitches),
+    ("audioEnabled", LayerCapability::AvSwitches),
+    ("sourceRectAtTime", LayerCapability::AvSwitches),
+    ("sourceText", LayerCapability::TextProperties),
+    ("text", LayerCapability::TextProperties),
+    ("content", LayerCapability::ShapeContents),
+];
+
+/// Top-level property groups of layers that need a capability, by display
+/// name. Match names are checked against the property tree instead.
+pub const LAYER_GROUP_CAPABILITIES: &[(&str, LayerCapability)] = &[
+    ("Effects", LayerCapability::Effects),
+    ("Masks", LayerCapability::Masks),
+    ("Layer Styles", LayerCapability::LayerStyles),
+    ("Material Options", LayerCapability::Materials),
+    ("Geometry Options", LayerCapability::Materials),
+    ("Text", LayerCapability::TextProperties),
+    ("Contents", LayerCapability::ShapeContents),
+    ("Camera Options", LayerCapability::CameraOptions),
+    ("Light Options", LayerCapability::LightOptions),
+];
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AutoOrientType {
     NoAutoOrient = 0,
@@ -104,8 +164,52 @@ impl Layer {
         
         layer.initialize_layer_methods();
         layer.initialize_layer_properties();
+        layer.initialize_validated_members();
         layer
     }
+
+    /// Signatures the validator checks layer calls against; every layer
+    /// subclass inherits them
+    fn initialize_validated_members(&mut self) {
+        let object = &mut self.base.base.api_object;
+
+        // Layer methods
+        object.methods.insert("property".to_string(), MethodValidation::new(1));
+        object.methods.insert("duplicate".to_string(), MethodValidation::new(0));
+        object.methods.insert("copyToComp".to_string(), MethodValidation::new(1));
+        object.methods.insert("moveToBeginning".to_string(), MethodValidation::new(0));
+        object.methods.insert("moveToEnd".to_string(), MethodValidation::new(0));
+        object.methods.insert("moveAfter".to_string(), MethodValidation::new(1));
+        object.methods.insert("moveBefore".to_string(), MethodValidation::new(1));
+        object.methods.insert("remove".to_string(), MethodValidation::new(0));
+        object.methods.insert("applyPreset".to_string(), MethodValidation::new(1));
+        object.methods.insert("openInViewer".to_string(), MethodValidation::new(0));
+
+        // Layer properties
+        object.properties.insert("name".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("index".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("enabled".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("locked".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("shy".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("solo".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("threeDLayer".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("motionBlur".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("adjustmentLayer".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("guide".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("label".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, 16.0));
+        object.properties.insert("blendingMode".to_string(), ValidationRule::simple(PropertyValueType::Custom("BlendingMode".to_string())));
+        object.properties.insert("quality".to_string(), ValidationRule::simple(PropertyValueType::Custom("LayerQuality".to_string())));
+        object.properties.insert("parent".to_string(), ValidationRule::simple(PropertyValueType::Custom("Layer".to_string())));
+        object.properties.insert("inPoint".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("outPoint".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("startTime".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("stretch".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("width".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("height".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("containingComp".to_string(), ValidationRule::simple(PropertyValueType::Custom("CompItem".to_string())));
+        object.properties.insert("isNameSet".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("comment".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+    }
     
     fn initialize_layer_methods(&mut self) {
         // Layer base methods (in addition to PropertyGroup methods)
@@ -644,7 +748,7 @@ impl Layer {
     }
     
     /// Check if this layer is active at the specified time
-    pub fn is_active_at_time(&self, time: f64) -> bool {
+    pub fn is_active_at_time(&self, _time: f64) -> bool {
         // This would check if layer is enabled, within in/out points, and not overridden by solo
         // For now, return true as default
         true
@@ -715,6 +819,86 @@ impl LayerType {
             _ => None,
         }
     }
+
+    /// The layer type created by a `LayerCollection.add*` method
+    pub fn from_constructor(method: &str) -> Option<LayerType> {
+        match method {
+            "add" | "addSolid" => Some(LayerType::AV),
+            "addNull" => Some(LayerType::Null),
+            "addText" | "addBoxText" | "addVerticalText" | "addVerticalBoxText" => Some(LayerType::Text),
+            "addShape" => Some(LayerType::Shape),
+            "addCamera" => Some(LayerType::Camera),
+            "addLight" => Some(LayerType::Light),
+            _ => None,
+        }
+    }
+
+    /// The layer type of an API class name, as produced by type narrowing
+    pub fn from_class_name(class: &str) -> Option<LayerType> {
+        match class {
+            "AVLayer" => Some(LayerType::AV),
+            "TextLayer" => Some(LayerType::Text),
+            "ShapeLayer" => Some(LayerType::Shape),
+            "CameraLayer" => Some(LayerType::Camera),
+            "LightLayer" => Some(LayerType::Light),
+            _ => None,
+        }
+    }
+
+    /// How the layer type reads in messages: "a text layer"
+    pub fn describe(&self) -> &'static str {
+        match self {
+            LayerType::AV => "an AV layer",
+            LayerType::Text => "a text layer",
+            LayerType::Shape => "a shape layer",
+            LayerType::Camera => "a camera layer",
+            LayerType::Light => "a light layer",
+            LayerType::Null => "a null layer",
+            LayerType::Adjustment => "an adjustment layer",
+        }
+    }
+
+    /// The capability matrix: which features each layer type has
+    pub fn supports(&self, capability: LayerCapability) -> bool {
+        let visual = !matches!(self, LayerType::Camera | LayerType::Light);
+        match capability {
+            LayerCapability::Effects
+            | LayerCapability::Masks
+            | LayerCapability::LayerStyles
+            | LayerCapability::Materials
+            | LayerCapability::AvSwitches => visual,
+            LayerCapability::ReplaceSource => visual && !matches!(self, LayerType::Text | LayerType::Shape),
+            LayerCapability::TextProperties => *self == LayerType::Text,
+            LayerCapability::ShapeContents => *self == LayerType::Shape,
+            LayerCapability::CameraOptions => *self == LayerType::Camera,
+            LayerCapability::LightOptions => *self == LayerType::Light,
+        }
+    }
+}
+
+impl LayerCapability {
+    pub fn description(&self) -> &'static str {
+        match self {
+            LayerCapability::Effects => "effects",
+            LayerCapability::Masks => "masks",
+            LayerCapability::LayerStyles => "layer styles",
+            LayerCapability::Materials => "material options",
+            LayerCapability::ReplaceSource => "source to replace",
+            LayerCapability::AvSwitches => "AV layer switches",
+            LayerCapability::TextProperties => "text properties",
+            LayerCapability::ShapeContents => "shape contents",
+            LayerCapability::CameraOptions => "camera options",
+            LayerCapability::LightOptions => "light options",
+        }
+    }
+
+    /// The layer types that have this capability
+    pub fn layer_types(&self) -> Vec<LayerType> {
+        [LayerType::AV, LayerType::Text, LayerType::Shape, LayerType::Camera, LayerType::Light, LayerType::Null, LayerType::Adjustment]
+            .into_iter()
+            .filter(|layer_type| layer_type.supports(*self))
+            .collect()
+    }
 }
 
 impl AutoOrientType {
@@ -824,6 +1008,19 @@ mod tests {
         assert_eq!(AutoOrientType::from_value(99), None);
     }
     
+    #[test]
+    fn test_capability_matrix() {
+        assert!(!LayerType::Camera.supports(LayerCapability::Effects));
+        assert!(!LayerType::Light.supports(LayerCapability::Masks));
+        assert!(LayerType::Text.supports(LayerCapability::Masks));
+        assert!(!LayerType::Text.supports(LayerCapability::ReplaceSource));
+        assert!(!LayerType::Shape.supports(LayerCapability::ReplaceSource));
+        assert!(LayerType::Null.supports(LayerCapability::ReplaceSource));
+        assert_eq!(LayerCapability::TextProperties.layer_types(), vec![LayerType::Text]);
+        assert_eq!(LayerType::from_constructor("addCamera"), Some(LayerType::Camera));
+        assert_eq!(LayerType::from_class_name("ShapeLayer"), Some(LayerType::Shape));
+    }
+
     #[test]
     fn test_layer_type_conversion() {
         assert_eq!(LayerType::Text.to_string(), "Text");
@@ -1,5 +1,5 @@
 use crate::validation::context::ObjectContext;
-use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
+use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType, RangeValidation};
 use super::app::ApiObject;
 use super::layer::{Layer, LayerType};
 use super::avlayer::AVLayer;
@@ -15,7 +15,9 @@ pub struct LayerCollection {
     pub api_object: ApiObject,
 }
 
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum LayerBlendingMode {
     Normal = 0,
     Dissolve = 1,
@@ -62,6 +64,12 @@ pub enum PrecomposeOptions {
     LeaveAllAttributes = 1,
 }
 
+impl Default for LayerCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LayerCollection {
     pub fn new() -> Self {
         let mut collection = Self {
@@ -79,23 +87,30 @@ impl LayerCollection {
         // Layer creation methods - add layers from footage/sources
         self.api_object.methods.insert("add".to_string(), MethodValidation::new(1)
             .with_param_types(vec![PropertyValueType::Custom("AVItem".to_string())])
-            .with_optional_params(vec![PropertyValueType::OneD])); // duration
+            .with_optional_params(vec![PropertyValueType::OneD]) // duration
+            .with_return_type("AVLayer"));
             
         // Text layer creation methods
         self.api_object.methods.insert("addText".to_string(), MethodValidation::new(0)
-            .with_optional_params(vec![PropertyValueType::ArbText])); // sourceText
+            .with_optional_params(vec![PropertyValueType::ArbText]) // sourceText
+            .with_return_type("TextLayer"));
             
-        self.api_object.methods.insert("addBoxText".to_string(), MethodValidation::new(0)
-            .with_optional_params(vec![PropertyValueType::TwoD])); // [width, height]
+        self.api_object.methods.insert("addBoxText".to_string(), MethodValidation::new(1)
+            .with_param_types(vec![PropertyValueType::TwoD]) // [width, height]
+            .with_optional_params(vec![PropertyValueType::ArbText]) // sourceText
+            .with_return_type("TextLayer"));
             
         self.api_object.methods.insert("addVerticalText".to_string(), MethodValidation::new(0)
-            .with_optional_params(vec![PropertyValueType::ArbText])); // sourceText
+            .with_optional_params(vec![PropertyValueType::ArbText]) // sourceText
+            .with_return_type("TextLayer"));
             
-        self.api_object.methods.insert("addVerticalBoxText".to_string(), MethodValidation::new(0)
-            .with_optional_params(vec![PropertyValueType::TwoD])); // [width, height]
+        self.api_object.methods.insert("addVerticalBoxText".to_string(), MethodValidation::new(1)
+            .with_param_types(vec![PropertyValueType::TwoD]) // [width, height]
+            .with_optional_params(vec![PropertyValueType::ArbText]) // sourceText
+            .with_return_type("TextLayer"));
             
         // Shape layer creation
-        self.api_object.methods.insert("addShape".to_string(), MethodValidation::new(0));
+        self.api_object.methods.insert("addShape".to_string(), MethodValidation::new(0).with_return_type("ShapeLayer"));
         
         // Solid layer creation
         self.api_object.methods.insert("addSolid".to_string(), MethodValidation::new(5)
@@ -106,30 +121,50 @@ impl LayerCollection {
                 PropertyValueType::OneD,         // height
                 PropertyValueType::OneD          // pixelAspect
             ])
-            .with_optional_params(vec![PropertyValueType::OneD])); // duration
+            .with_optional_params(vec![PropertyValueType::OneD]) // duration
+            .with_param_ranges(vec![
+                RangeValidation::unbounded("color"),
+                RangeValidation::unbounded("name"),
+                RangeValidation::between("width", 4.0, 30000.0),
+                RangeValidation::between("height", 4.0, 30000.0),
+                RangeValidation::between("pixelAspect", 0.01, 100.0),
+                RangeValidation::at_least("duration", 0.0),
+            ])
+            .with_return_type("AVLayer"));
             
         // 3D layer creation methods
         self.api_object.methods.insert("addCamera".to_string(), MethodValidation::new(2)
             .with_param_types(vec![
                 PropertyValueType::ArbText,  // name
                 PropertyValueType::TwoD      // centerPoint
-            ]));
+            ])
+            .with_return_type("CameraLayer"));
             
         self.api_object.methods.insert("addLight".to_string(), MethodValidation::new(2)
             .with_param_types(vec![
                 PropertyValueType::ArbText,  // name
                 PropertyValueType::TwoD      // centerPoint
-            ]));
+            ])
+            .with_return_type("LightLayer")
+            .with_overload(MethodValidation::new(2)
+                .with_param_types(vec![
+                    PropertyValueType::ArbText,  // name
+                    PropertyValueType::ThreeD    // centerPoint with z
+                ])
+                .with_return_type("LightLayer")));
             
         // Null layer creation
         self.api_object.methods.insert("addNull".to_string(), MethodValidation::new(0)
-            .with_optional_params(vec![PropertyValueType::OneD])); // duration
+            .with_optional_params(vec![PropertyValueType::OneD]) // duration
+            .with_param_ranges(vec![RangeValidation::at_least("duration", 0.0)])
+            .with_return_type("AVLayer"));
     }
     
     fn initialize_layer_management_methods(&mut self) {
         // Layer access and retrieval
         self.api_object.methods.insert("byName".to_string(), MethodValidation::new(1)
-            .with_param_types(vec![PropertyValueType::ArbText]));
+            .with_param_types(vec![PropertyValueType::ArbText])
+            .with_return_type("Layer"));
             
         // Layer duplication and copying
         self.api_object.methods.insert("duplicate".to_string(), MethodValidation::new(1)
@@ -447,17 +482,17 @@ impl LayerCollection {
     }
     
     // Advanced layer management methods
-    pub fn validate_layer_index(&self, index: usize) -> bool {
+    pub fn validate_layer_index(&self, _index: usize) -> bool {
         // Validation logic for layer indices would go here
         true
     }
     
-    pub fn get_layer_count_by_type(&self, layer_type: LayerType) -> usize {
+    pub fn get_layer_count_by_type(&self, _layer_type: LayerType) -> usize {
         // Count layers by type logic would go here
         0
     }
     
-    pub fn find_layers_by_name(&self, name: &str) -> Vec<usize> {
+    pub fn find_layers_by_name(&self, _name: &str) -> Vec<usize> {
         // Find layers by name logic would go here
         Vec::new()
     }
@@ -467,12 +502,12 @@ impl LayerCollection {
         Vec::new()
     }
     
-    pub fn validate_precompose_selection(&self, layer_indices: &[usize]) -> bool {
+    pub fn validate_precompose_selection(&self, _layer_indices: &[usize]) -> bool {
         // Validate that selected layers can be precomposed
         true
     }
     
-    pub fn calculate_precomp_duration(&self, layer_indices: &[usize]) -> f64 {
+    pub fn calculate_precomp_duration(&self, _layer_indices: &[usize]) -> f64 {
         // Calculate optimal duration for precomposition
         0.0
     }
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
@@ -7,6 +6,12 @@ pub struct LightLayer {
     api_object: ApiObject,
 }
 
+impl Default for LightLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LightLayer {
     pub fn new() -> Self {
         let mut light_object = Self {
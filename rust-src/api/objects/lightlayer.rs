@@ -1,4 +1,3 @@
-use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::layer::{Layer, LayerType};
 
@@ -39,6 +38,12 @@ pub enum FalloffType {
     InverseSquareUnclamped = 3,
 }
 
+impl Default for LightLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LightLayer {
     pub fn new() -> Self {
         let mut light_layer = Self {
@@ -811,35 +816,35 @@ pub mod lightlayer_factory {
     
     /// Create a point light
     pub fn create_point_light() -> LightLayer {
-        let mut light = LightLayer::new();
+        let light = LightLayer::new();
         // Point light setup would go here
         light
     }
     
     /// Create a spot light
     pub fn create_spot_light() -> LightLayer {
-        let mut light = LightLayer::new();
+        let light = LightLayer::new();
         // Spot light setup would go here
         light
     }
     
     /// Create a parallel (directional) light
     pub fn create_parallel_light() -> LightLayer {
-        let mut light = LightLayer::new();
+        let light = LightLayer::new();
         // Parallel light setup would go here
         light
     }
     
     /// Create an ambient light
     pub fn create_ambient_light() -> LightLayer {
-        let mut light = LightLayer::new();
+        let light = LightLayer::new();
         // Ambient light setup would go here
         light
     }
     
     /// Create a light with shadows enabled
     pub fn create_shadow_casting_light() -> LightLayer {
-        let mut light = LightLayer::new();
+        let light = LightLayer::new();
         // Shadow casting setup would go here
         light
     }
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::propertygroup::PropertyGroup;
@@ -105,6 +104,12 @@ pub struct MaskShape {
     pub feather_falloff_type: i32,
 }
 
+impl Default for MaskShape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MaskShape {
     pub fn new() -> Self {
         Self {
@@ -198,6 +203,12 @@ pub struct MaskPath {
     pub api_object: ApiObject,
 }
 
+impl Default for MaskPath {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MaskPath {
     pub fn new() -> Self {
         let mut mask_path = Self {
@@ -360,6 +371,12 @@ pub struct Mask {
     pub base: PropertyGroup,
 }
 
+impl Default for Mask {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Mask {
     pub fn new() -> Self {
         let mut mask = Self {
@@ -693,6 +710,12 @@ pub struct MaskCollection {
     pub base: PropertyGroup,
 }
 
+impl Default for MaskCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MaskCollection {
     pub fn new() -> Self {
         let mut mask_collection = Self {
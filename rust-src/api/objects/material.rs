@@ -1,12 +1,17 @@
-use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
-use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
+use crate::validation::rules::{ValidationRule, PropertyValueType};
 use super::app::ApiObject;
 
 pub struct MaterialOptionsObject {
     api_object: ApiObject,
 }
 
+impl Default for MaterialOptionsObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MaterialOptionsObject {
     pub fn new() -> Self {
         let mut material_object = Self {
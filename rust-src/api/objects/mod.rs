@@ -1,3 +1,8 @@
+// Several objects define enums of the same name (`AlphaMode`, `FieldOrder`,
+// ...); those are reached through their own modules. Enum `to_string`
+// methods return ExtendScript's constant names.
+#![allow(ambiguous_glob_reexports, clippy::inherent_to_string)]
+
 pub mod app;
 pub mod project;
 pub mod comp;
@@ -105,6 +105,7 @@ pub struct OutputModuleSettings {
 
 /// Output file information
 #[derive(Debug, Clone)]
+#[derive(Default)]
 pub struct OutputFileInfo {
     pub full_flat_path: String,
     pub base_path: String,
@@ -186,6 +187,7 @@ pub enum ResizeQuality {
 
 /// Crop settings
 #[derive(Debug, Clone)]
+#[derive(Default)]
 pub struct CropSettings {
     pub top: i32,
     pub bottom: i32,
@@ -273,7 +275,9 @@ pub enum ColorDepth {
 }
 
 /// Color profile options
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum ColorProfile {
     WorkingSpace,
     sRGB,
@@ -568,18 +572,6 @@ impl Default for OutputModuleSettings {
     }
 }
 
-impl Default for OutputFileInfo {
-    fn default() -> Self {
-        OutputFileInfo {
-            full_flat_path: String::new(),
-            base_path: String::new(),
-            file_name: String::new(),
-            extension: String::new(),
-            sequence_start_frame: None,
-            is_sequence: false,
-        }
-    }
-}
 
 impl Default for VideoOutputSettings {
     fn default() -> Self {
@@ -599,17 +591,6 @@ impl Default for VideoOutputSettings {
     }
 }
 
-impl Default for CropSettings {
-    fn default() -> Self {
-        CropSettings {
-            top: 0,
-            bottom: 0,
-            left: 0,
-            right: 0,
-            enabled: false,
-        }
-    }
-}
 
 impl Default for TimeSpanSettings {
     fn default() -> Self {
@@ -661,6 +642,12 @@ impl Default for MetadataSettings {
     }
 }
 
+impl Default for OutputModuleCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OutputModuleCollection {
     /// Create a new output module collection
     pub fn new() -> Self {
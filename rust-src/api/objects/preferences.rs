@@ -1,5 +1,5 @@
 use crate::validation::context::ObjectContext;
-use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
+use crate::validation::rules::{MethodValidation, PropertyValueType};
 use super::app::ApiObject;
 
 /// Preferences object for managing After Effects preferences
@@ -7,6 +7,12 @@ pub struct Preferences {
     api_object: ApiObject,
 }
 
+impl Default for Preferences {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Preferences {
     pub fn new() -> Self {
         let mut preferences = Self {
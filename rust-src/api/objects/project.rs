@@ -53,7 +53,9 @@ pub enum ProjectExpressionEngine {
 }
 
 /// Additional enums for comprehensive Adobe After Effects support
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum FramesCountType {
     FC_START_1,
     FC_START_0,
@@ -66,13 +68,17 @@ pub enum FeetFramesFilmType {
     MM35,
 }
 
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum FootageTimecodeDisplayStartType {
     FTCS_START_0,
     FTCS_USE_SOURCE_MEDIA,
 }
 
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum ToolType {
     Tool_Arrow,              // Selection Tool
     Tool_Rotate,             // Rotation Tool
@@ -105,13 +111,21 @@ pub enum ToolType {
     Tool_Hairbrush,          // Refine Edge Tool
 }
 
+// Variants keep After Effects' own enum names
 #[derive(Debug, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
 pub enum ResolveType {
     ACCEPT_THEIRS,           // Take the shared version
     ACCEPT_YOURS,            // Keep your version
     ACCEPT_THEIRS_AND_COPY,  // Copy and rename your version, then take shared
 }
 
+impl Default for ProjectObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ProjectObject {
     pub fn new() -> Self {
         let mut project = Self {
@@ -128,8 +142,53 @@ impl ProjectObject {
         project.initialize_templates_presets();
         project.initialize_team_projects();
         project.initialize_missing_properties();
+        project.initialize_validated_members();
         project
     }
+
+    /// Members with the signatures the validator checks calls against. Runs
+    /// last so these win over the descriptive entries above
+    fn initialize_validated_members(&mut self) {
+        let object = &mut self.property_group.base.api_object;
+
+        // Project methods
+        object.methods.insert("save".to_string(), MethodValidation::new(0));
+        object.methods.insert("saveWithDialog".to_string(), MethodValidation::new(0));
+        object.methods.insert("close".to_string(), MethodValidation::new(1));
+        object.methods.insert("item".to_string(), MethodValidation::new(1));
+        object.methods.insert("importFile".to_string(), MethodValidation::new(1)
+            .with_param_types(vec![PropertyValueType::Custom("ImportOptions".to_string())])
+            .with_return_type("Item"));
+        object.methods.insert("importFileWithDialog".to_string(), MethodValidation::new(0));
+        object.methods.insert("importPlaceholder".to_string(), MethodValidation::new(5));
+        object.methods.insert("consolidateFootage".to_string(), MethodValidation::new(0));
+        object.methods.insert("removeUnusedFootage".to_string(), MethodValidation::new(0));
+        object.methods.insert("reduceProject".to_string(), MethodValidation::new(1));
+        object.methods.insert("showWindow".to_string(), MethodValidation::new(1));
+        object.methods.insert("autoFixExpressions".to_string(), MethodValidation::new(2));
+
+        // Project properties
+        object.properties.insert("file".to_string(), ValidationRule::simple(PropertyValueType::Custom("File".to_string())));
+        object.properties.insert("rootFolder".to_string(), ValidationRule::simple(PropertyValueType::Custom("FolderItem".to_string())));
+        object.properties.insert("activeItem".to_string(), ValidationRule::simple(PropertyValueType::Custom("Item".to_string())));
+        object.properties.insert("numItems".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())).with_range(0.0, f64::MAX));
+        object.properties.insert("items".to_string(), ValidationRule::simple(PropertyValueType::Custom("ItemCollection".to_string())));
+        object.properties.insert("renderQueue".to_string(), ValidationRule::simple(PropertyValueType::Custom("RenderQueue".to_string())));
+        object.properties.insert("dirty".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("bitsPerChannel".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("transparencyGridThumbnails".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("workingSpace".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("workingGamma".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("linearizeWorkingSpace".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("compensateForSceneReferredProfiles".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("gpuAccelType".to_string(), ValidationRule::simple(PropertyValueType::Custom("GpuAccelType".to_string())));
+        object.properties.insert("expressionEngine".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("feetFramesFilmType".to_string(), ValidationRule::simple(PropertyValueType::Custom("FeetFramesFilmType".to_string())));
+        object.properties.insert("framesCountType".to_string(), ValidationRule::simple(PropertyValueType::Custom("FramesCountType".to_string())));
+        object.properties.insert("framesUseFeetFrames".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("displayStartFrame".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("timeDisplayType".to_string(), ValidationRule::simple(PropertyValueType::Custom("TimeDisplayType".to_string())));
+    }
     
     /// Initialize core project properties
     fn initialize_project_properties(&mut self) {
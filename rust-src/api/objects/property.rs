@@ -1,5 +1,6 @@
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
+use crate::validation::schema::ValueSchema;
 use super::propertybase::{PropertyBase, PropertyType};
 
 /// Property object - represents a single property such as position or zoom
@@ -8,6 +9,12 @@ pub struct Property {
     pub base: PropertyBase,
 }
 
+impl Default for Property {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Property {
     pub fn new() -> Self {
         let mut property = Self {
@@ -16,14 +23,64 @@ impl Property {
         
         property.initialize_property_methods();
         property.initialize_property_properties();
+        property.initialize_validated_members();
         property
     }
+
+    /// Signatures the validator checks property calls against
+    fn initialize_validated_members(&mut self) {
+        let object = &mut self.base.api_object;
+
+        // Property methods
+        object.methods.insert("setValueAtTime".to_string(), MethodValidation::new(2));
+        object.methods.insert("setValueAtKey".to_string(), MethodValidation::new(2));
+        object.methods.insert("valueAtTime".to_string(), MethodValidation::new(2));
+        object.methods.insert("velocityAtTime".to_string(), MethodValidation::new(2));
+        object.methods.insert("speedAtTime".to_string(), MethodValidation::new(1));
+        object.methods.insert("keyTime".to_string(), MethodValidation::new(1));
+        object.methods.insert("keyValue".to_string(), MethodValidation::new(1));
+        object.methods.insert("addKey".to_string(), MethodValidation::new(1));
+        object.methods.insert("removeKey".to_string(), MethodValidation::new(1));
+        object.methods.insert("nearestKeyIndex".to_string(), MethodValidation::new(1));
+        object.methods.insert("setInterpolationTypeAtKey".to_string(), MethodValidation::new(3));
+        object.methods.insert("setTemporalContinuousAtKey".to_string(), MethodValidation::new(2));
+        object.methods.insert("setTemporalAutoBezierAtKey".to_string(), MethodValidation::new(2));
+        object.methods.insert("setSpatialContinuousAtKey".to_string(), MethodValidation::new(2));
+        object.methods.insert("setSpatialAutoBezierAtKey".to_string(), MethodValidation::new(2));
+        object.methods.insert("setRovingAtKey".to_string(), MethodValidation::new(2));
+        object.methods.insert("setSelectedAtKey".to_string(), MethodValidation::new(2));
+
+        // Property properties
+        object.properties.insert("value".to_string(), ValidationRule::simple(PropertyValueType::Custom("Any".to_string())));
+        object.properties.insert("hasMin".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("hasMax".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("minValue".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("maxValue".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("isTimeVarying".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("numKeys".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("propertyIndex".to_string(), ValidationRule::simple(PropertyValueType::Custom("Number".to_string())));
+        object.properties.insert("propertyType".to_string(), ValidationRule::simple(PropertyValueType::Custom("PropertyType".to_string())));
+        object.properties.insert("propertyValueType".to_string(), ValidationRule::simple(PropertyValueType::Custom("PropertyValueType".to_string())));
+        object.properties.insert("unitsText".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("expression".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("expressionEnabled".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("expressionError".to_string(), ValidationRule::simple(PropertyValueType::Custom("String".to_string())));
+        object.properties.insert("canSetExpression".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("dimensionsSeparated".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("isSeparationFollower".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+        object.properties.insert("isSeparationLeader".to_string(), ValidationRule::simple(PropertyValueType::Custom("Boolean".to_string())));
+    }
     
     fn initialize_property_methods(&mut self) {
         // Property-specific methods (in addition to PropertyBase methods)
         
         // Core value manipulation methods
-        self.base.api_object.methods.insert("setValue".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::CustomValue]));
+        // The value's shape follows the property's value type
+        self.base.api_object.methods.insert("setValue".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::OneD])
+            .with_overload(MethodValidation::new(1).with_param_types(vec![PropertyValueType::TwoD]))
+            .with_overload(MethodValidation::new(1).with_param_types(vec![PropertyValueType::ThreeD]))
+            .with_overload(MethodValidation::new(1).with_param_types(vec![PropertyValueType::Color]))
+            .with_overload(MethodValidation::new(1).with_param_types(vec![PropertyValueType::ArbText])));
         self.base.api_object.methods.insert("setValueAtTime".to_string(), MethodValidation::new(2).with_param_types(vec![
             PropertyValueType::OneD,          // time
             PropertyValueType::CustomValue    // value
@@ -67,11 +124,12 @@ impl Property {
         // Temporal ease methods
         self.base.api_object.methods.insert("keyInTemporalEase".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::OneD]));
         self.base.api_object.methods.insert("keyOutTemporalEase".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::OneD]));
-        self.base.api_object.methods.insert("setTemporalEaseAtKey".to_string(), MethodValidation::new(3).with_param_types(vec![
+        self.base.api_object.methods.insert("setTemporalEaseAtKey".to_string(), MethodValidation::new(2).with_param_types(vec![
             PropertyValueType::OneD,                           // key index
             PropertyValueType::Custom("Array".to_string()),   // in temporal ease
-            PropertyValueType::Custom("Array".to_string())    // out temporal ease
-        ]));
+        ]).with_optional_params(vec![
+            PropertyValueType::Custom("Array".to_string())    // out temporal ease, defaults to the in value
+        ]).with_param_schemas(vec![None, Some(ValueSchema::keyframe_eases()), Some(ValueSchema::keyframe_eases())]));
         self.base.api_object.methods.insert("setTemporalContinuousAtKey".to_string(), MethodValidation::new(2).with_param_types(vec![
             PropertyValueType::OneD,                           // key index
             PropertyValueType::Custom("Boolean".to_string())  // continuous
@@ -84,11 +142,12 @@ impl Property {
         // Spatial tangent methods (for spatial properties)
         self.base.api_object.methods.insert("keyInSpatialTangent".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::OneD]));
         self.base.api_object.methods.insert("keyOutSpatialTangent".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::OneD]));
-        self.base.api_object.methods.insert("setSpatialTangentsAtKey".to_string(), MethodValidation::new(3).with_param_types(vec![
+        self.base.api_object.methods.insert("setSpatialTangentsAtKey".to_string(), MethodValidation::new(2).with_param_types(vec![
             PropertyValueType::OneD,                           // key index
             PropertyValueType::Custom("Array".to_string()),   // in spatial tangent
-            PropertyValueType::Custom("Array".to_string())    // out spatial tangent
-        ]));
+        ]).with_optional_params(vec![
+            PropertyValueType::Custom("Array".to_string())    // out spatial tangent, defaults to the in value
+        ]).with_param_schemas(vec![None, Some(ValueSchema::array_of(ValueSchema::number(), 2, 3)), Some(ValueSchema::array_of(ValueSchema::number(), 2, 3))]));
         self.base.api_object.methods.insert("setSpatialContinuousAtKey".to_string(), MethodValidation::new(2).with_param_types(vec![
             PropertyValueType::OneD,                           // key index
             PropertyValueType::Custom("Boolean".to_string())  // continuous
@@ -153,11 +212,6 @@ impl Property {
         // Advanced keyframe methods
         self.base.api_object.methods.insert("keyInSpatialTangent".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::OneD]));
         self.base.api_object.methods.insert("keyOutSpatialTangent".to_string(), MethodValidation::new(1).with_param_types(vec![PropertyValueType::OneD]));
-        self.base.api_object.methods.insert("setSpatialTangentsAtKey".to_string(), MethodValidation::new(3).with_param_types(vec![
-            PropertyValueType::OneD,                           // key index
-            PropertyValueType::Custom("Array".to_string()),   // in spatial tangent
-            PropertyValueType::Custom("Array".to_string())    // out spatial tangent
-        ]));
         
         // Enhanced separation dimension methods
         self.base.api_object.methods.insert("separateDimensions".to_string(), MethodValidation::new(0));
@@ -1,7 +1,6 @@
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
-use serde::{Deserialize, Serialize};
 
 /// PropertyBase object - base class for Property and PropertyGroup
 /// Represents the common functionality shared by all property objects in After Effects
@@ -1,7 +1,6 @@
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 pub use super::propertybase::{PropertyBase, PropertyType};
-use serde::{Deserialize, Serialize};
 
 /// PropertyGroup object - represents a group of properties
 /// Can contain Property objects and other PropertyGroup objects
@@ -160,7 +160,7 @@ impl PropertySeparation {
     }
     
     /// Initialize properties specific to follower dimensions
-    fn initialize_follower_properties(&self, follower: &mut PropertyBase, dimension_index: usize) {
+    fn initialize_follower_properties(&self, follower: &mut PropertyBase, _dimension_index: usize) {
         // Follower properties inherit most behavior from Property but with dimension-specific constraints
         
         // Dimension identification
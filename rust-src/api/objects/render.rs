@@ -2,9 +2,16 @@ use crate::validation::context::ObjectContext;
 use super::app::ApiObject;
 
 pub struct RenderQueueObject {
+    #[allow(dead_code)]
     api_object: ApiObject,
 }
 
+impl Default for RenderQueueObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RenderQueueObject {
     pub fn new() -> Self {
         Self {
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
@@ -7,6 +6,12 @@ pub struct RenderQueue {
     api_object: ApiObject,
 }
 
+impl Default for RenderQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RenderQueue {
     pub fn new() -> Self {
         let mut render_queue = Self {
@@ -106,6 +111,12 @@ pub struct RenderQueueItem {
     api_object: ApiObject,
 }
 
+impl Default for RenderQueueItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RenderQueueItem {
     pub fn new() -> Self {
         let mut render_queue_item = Self {
@@ -323,6 +334,12 @@ pub struct OutputModule {
     api_object: ApiObject,
 }
 
+impl Default for OutputModule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OutputModule {
     pub fn new() -> Self {
         let mut output_module = Self {
@@ -447,6 +464,12 @@ pub struct RQItemCollection {
     api_object: ApiObject,
 }
 
+impl Default for RQItemCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RQItemCollection {
     pub fn new() -> Self {
         let mut collection = Self {
@@ -488,6 +511,12 @@ pub struct OMCollection {
     api_object: ApiObject,
 }
 
+impl Default for OMCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OMCollection {
     pub fn new() -> Self {
         let mut collection = Self {
@@ -1,12 +1,18 @@
 use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
-use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
+use crate::validation::rules::{ValidationRule, PropertyValueType};
 use super::app::ApiObject;
 
 pub struct ShapeObject {
     api_object: ApiObject,
 }
 
+impl Default for ShapeObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ShapeObject {
     pub fn new() -> Self {
         let mut shape_object = Self {
@@ -29,10 +35,7 @@ impl ShapeObject {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: Some(Box::new(|value| {
-                // Validate that vertices is an array of [x,y] coordinates
-                Ok(())
-            })),
+            custom_validator: None,
         });
         
         self.api_object.properties.insert("inTangents".to_string(), ValidationRule {
@@ -45,10 +48,7 @@ impl ShapeObject {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: Some(Box::new(|value| {
-                // Validate that inTangents is an array of [x,y] coordinates
-                Ok(())
-            })),
+            custom_validator: None,
         });
         
         self.api_object.properties.insert("outTangents".to_string(), ValidationRule {
@@ -61,10 +61,7 @@ impl ShapeObject {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: Some(Box::new(|value| {
-                // Validate that outTangents is an array of [x,y] coordinates
-                Ok(())
-            })),
+            custom_validator: None,
         });
         
         self.api_object.properties.insert("closed".to_string(), ValidationRule {
@@ -182,6 +179,12 @@ pub struct ShapeLayerObject {
     api_object: ApiObject,
 }
 
+impl Default for ShapeLayerObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ShapeLayerObject {
     pub fn new() -> Self {
         let mut shape_layer_object = Self {
@@ -1,5 +1,6 @@
-use crate::validation::context::ObjectContext;
+use crate::validation::custom::CustomValidator;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
+use crate::validation::schema::ValueSchema;
 use super::avlayer::AVLayer;
 use super::layer::LayerType;
 
@@ -78,6 +79,12 @@ pub enum RepeaterComposite {
     Below = 1,
 }
 
+impl Default for ShapeLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ShapeLayer {
     pub fn new() -> Self {
         let mut shape_layer = Self {
@@ -780,7 +787,7 @@ impl ShapeLayer {
             dimensions_separated: false,
             is_dropdown: false,
             allowed_values: None,
-            custom_validator: None,
+            custom_validator: Some(CustomValidator::Schema(ValueSchema::gradient_stops())),
         });
         
         // Trim modifier properties (ADBE Vector Filter - Trim)
@@ -980,42 +987,42 @@ impl ShapeLayer {
     }
     
     /// Get the type of a specific shape
-    pub fn get_shape_type(&self, index: usize) -> Option<ShapeType> {
+    pub fn get_shape_type(&self, _index: usize) -> Option<ShapeType> {
         // This would determine the type of the shape at the given index
         // For now, return None as default
         None
     }
     
     /// Check if a shape has fill
-    pub fn shape_has_fill(&self, index: usize) -> bool {
+    pub fn shape_has_fill(&self, _index: usize) -> bool {
         // This would check if the shape has any fill properties
         // For now, return false as default
         false
     }
     
     /// Check if a shape has stroke
-    pub fn shape_has_stroke(&self, index: usize) -> bool {
+    pub fn shape_has_stroke(&self, _index: usize) -> bool {
         // This would check if the shape has any stroke properties
         // For now, return false as default
         false
     }
     
     /// Get shape bounds at specific time
-    pub fn get_shape_bounds(&self, index: usize, time: f64) -> Option<[f64; 4]> {
+    pub fn get_shape_bounds(&self, _index: usize, _time: f64) -> Option<[f64; 4]> {
         // This would calculate the bounding box [left, top, right, bottom] of the shape
         // For now, return None as default
         None
     }
     
     /// Check if shape is closed (for paths)
-    pub fn is_shape_closed(&self, index: usize) -> bool {
+    pub fn is_shape_closed(&self, _index: usize) -> bool {
         // This would check if a path shape is closed
         // For now, return true as default
         true
     }
     
     /// Get vertex count for a path shape
-    pub fn get_shape_vertex_count(&self, index: usize) -> usize {
+    pub fn get_shape_vertex_count(&self, _index: usize) -> usize {
         // This would count vertices in a path shape
         // For now, return 0 as default
         0
@@ -1040,28 +1047,28 @@ pub mod shapelayer_factory {
     
     /// Create a shape layer with a rectangle
     pub fn create_rectangle_shape_layer() -> ShapeLayer {
-        let mut layer = ShapeLayer::new();
+        let layer = ShapeLayer::new();
         // Rectangle shape setup would go here
         layer
     }
     
     /// Create a shape layer with an ellipse
     pub fn create_ellipse_shape_layer() -> ShapeLayer {
-        let mut layer = ShapeLayer::new();
+        let layer = ShapeLayer::new();
         // Ellipse shape setup would go here
         layer
     }
     
     /// Create a shape layer with a star
     pub fn create_star_shape_layer() -> ShapeLayer {
-        let mut layer = ShapeLayer::new();
+        let layer = ShapeLayer::new();
         // Star shape setup would go here
         layer
     }
     
     /// Create a shape layer with a custom path
     pub fn create_path_shape_layer() -> ShapeLayer {
-        let mut layer = ShapeLayer::new();
+        let layer = ShapeLayer::new();
         // Path shape setup would go here
         layer
     }
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::app::ApiObject;
@@ -7,6 +6,12 @@ pub struct TextDocument {
     api_object: ApiObject,
 }
 
+impl Default for TextDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TextDocument {
     pub fn new() -> Self {
         let mut text_doc_object = Self {
@@ -720,6 +725,12 @@ pub struct TextLayerObject {
     api_object: ApiObject,
 }
 
+impl Default for TextLayerObject {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TextLayerObject {
     pub fn new() -> Self {
         let mut text_layer_object = Self {
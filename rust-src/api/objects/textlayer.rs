@@ -1,4 +1,3 @@
-use crate::validation::context::ObjectContext;
 use crate::validation::rules::{ValidationRule, MethodValidation, PropertyValueType};
 use super::avlayer::AVLayer;
 use super::layer::LayerType;
@@ -59,6 +58,12 @@ pub enum CloseDocumentSaving {
     SaveChanges = 2,
 }
 
+impl Default for TextLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TextLayer {
     pub fn new() -> Self {
         let mut text_layer = Self {
@@ -797,28 +802,28 @@ pub mod textlayer_factory {
     
     /// Create a standard point text layer
     pub fn create_point_text_layer() -> TextLayer {
-        let mut layer = TextLayer::new();
+        let layer = TextLayer::new();
         // Point text setup would go here
         layer
     }
     
     /// Create a box text layer
     pub fn create_box_text_layer() -> TextLayer {
-        let mut layer = TextLayer::new();
+        let layer = TextLayer::new();
         // Box text setup would go here
         layer
     }
     
     /// Create a text layer with 3D per-character enabled
     pub fn create_3d_text_layer() -> TextLayer {
-        let mut layer = TextLayer::new();
+        let layer = TextLayer::new();
         // 3D text setup would go here
         layer
     }
     
     /// Create a text layer with path text
     pub fn create_path_text_layer() -> TextLayer {
-        let mut layer = TextLayer::new();
+        let layer = TextLayer::new();
         // Path text setup would go here
         layer
     }
@@ -5,6 +5,9 @@ use crate::validation::context::ValidationContext;
 use crate::validation::rules::PropertyValueType;
 use super::types::{DocumentationInfo, ValidationInfo};
 
+/// Extra check run on a value assigned to the property
+pub type PropertyValidator = Arc<dyn Fn(&Value, &ValidationContext) -> Result<(), String> + Send + Sync>;
+
 pub struct PropertyValidation {
     pub value_type: PropertyValueType,
     pub range: Option<(f64, f64)>,
@@ -12,7 +15,7 @@ pub struct PropertyValidation {
     pub temporal_dimensions: Option<Vec<usize>>,
     pub is_spatial: bool,
     pub requires_expression: bool,
-    pub custom_validator: Option<Arc<dyn Fn(&Value, &ValidationContext) -> Result<(), String> + Send + Sync>>,
+    pub custom_validator: Option<PropertyValidator>,
     pub documentation: Option<DocumentationInfo>,
     pub validation_info: Option<ValidationInfo>,
 }
@@ -47,7 +50,7 @@ impl PropertyValidation {
         self
     }
 
-    pub fn with_custom_validator(mut self, validator: Arc<dyn Fn(&Value, &ValidationContext) -> Result<(), String> + Send + Sync>) -> Self {
+    pub fn with_custom_validator(mut self, validator: PropertyValidator) -> Self {
         self.custom_validator = Some(validator);
         self
     }
@@ -104,8 +107,8 @@ impl PropertyValidation {
             (PropertyValueType::Color, Value::Array(arr)) => arr.len() == 4,
             (PropertyValueType::TextDocument, Value::String(_)) => true,
             (PropertyValueType::Shape, Value::Object(_)) => true,
-            (PropertyValueType::LayerIndex, Value::Number(n)) => n.as_f64().map_or(false, |x| x >= 0.0),
-            (PropertyValueType::MaskIndex, Value::Number(n)) => n.as_f64().map_or(false, |x| x >= 0.0),
+            (PropertyValueType::LayerIndex, Value::Number(n)) => n.as_f64().is_some_and(|x| x >= 0.0),
+            (PropertyValueType::MaskIndex, Value::Number(n)) => n.as_f64().is_some_and(|x| x >= 0.0),
             (PropertyValueType::Custom(_), _) => true, // Custom types need their own validation
             _ => false,
         }
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+/// What a search entry refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    Object,
+    Method,
+    Property,
+    EnumMember,
+    /// A global function such as `alert`
+    Function,
+    EffectMatchName,
+    LayerMatchName,
+    PropertyMatchName,
+}
+
+/// A searchable name from the API model
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchEntry {
+    pub name: String,
+    pub kind: EntryKind,
+    /// Class or enum the member belongs to
+    pub owner: Option<String>,
+}
+
+impl SearchEntry {
+    /// Qualified name such as `CompItem.layer` or `BlendingMode.MULTIPLY`
+    pub fn qualified_name(&self) -> String {
+        match &self.owner {
+            Some(owner) => format!("{}.{}", owner, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// A ranked search result
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit<'a> {
+    pub entry: &'a SearchEntry,
+    /// Case-insensitive edit distance between the query and the entry name
+    pub distance: usize,
+}
+
+/// Trigram index over every name in the API model
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    entries: Vec<SearchEntry>,
+    trigrams: HashMap<String, Vec<usize>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, name: &str, kind: EntryKind, owner: Option<&str>) {
+        let index = self.entries.len();
+        for trigram in trigrams(name) {
+            self.trigrams.entry(trigram).or_default().push(index);
+        }
+        self.entries.push(SearchEntry {
+            name: name.to_string(),
+            kind,
+            owner: owner.map(String::from),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Best matches for `query` across all entries
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        self.search_where(query, limit, default_max_distance(query), |_| true)
+    }
+
+    /// Best matches for `query` among entries accepted by `filter`, closest first
+    pub fn search_where<F>(&self, query: &str, limit: usize, max_distance: usize, filter: F) -> Vec<SearchHit<'_>>
+    where
+        F: Fn(&SearchEntry) -> bool,
+    {
+        let query_lower = query.to_lowercase();
+        let mut shared: HashMap<usize, usize> = HashMap::new();
+        for trigram in trigrams(query) {
+            if let Some(indices) = self.trigrams.get(&trigram) {
+                for &index in indices {
+                    *shared.entry(index).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut hits: Vec<(SearchHit, usize)> = shared.into_iter()
+            .filter(|(index, _)| filter(&self.entries[*index]))
+            .filter_map(|(index, count)| {
+                let entry = &self.entries[index];
+                let distance = strsim::levenshtein(&query_lower, &entry.name.to_lowercase());
+                if distance <= max_distance {
+                    Some((SearchHit { entry, distance }, count))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        hits.sort_by(|(a, a_shared), (b, b_shared)| {
+            a.distance.cmp(&b.distance)
+                .then(b_shared.cmp(a_shared))
+                .then_with(|| a.entry.name.cmp(&b.entry.name))
+                .then_with(|| a.entry.owner.cmp(&b.entry.owner))
+        });
+
+        let mut seen = HashSet::new();
+        hits.into_iter()
+            .map(|(hit, _)| hit)
+            .filter(|hit| seen.insert(hit.entry.qualified_name()))
+            .take(limit)
+            .collect()
+    }
+
+    /// "Did you mean" candidates for an unknown member of `owner`
+    pub fn suggest_member(&self, owner: &str, name: &str, limit: usize) -> Vec<String> {
+        self.search_where(name, limit, default_max_distance(name), |entry| entry.owner.as_deref() == Some(owner))
+            .into_iter()
+            .map(|hit| hit.entry.name.clone())
+            .collect()
+    }
+
+    /// Classes and enums that declare a member with exactly this name
+    pub fn owners_of(&self, name: &str, kind: EntryKind) -> Vec<&str> {
+        let mut owners: Vec<&str> = self.entries.iter()
+            .filter(|entry| entry.kind == kind && entry.name == name)
+            .filter_map(|entry| entry.owner.as_deref())
+            .collect();
+        owners.sort();
+        owners.dedup();
+        owners
+    }
+}
+
+/// Allowed edit distance for a query; short names tolerate fewer edits
+pub fn default_max_distance(query: &str) -> usize {
+    (query.chars().count() / 3).max(2)
+}
+
+/// Lowercased, space-padded character trigrams of `text`
+fn trigrams(text: &str) -> HashSet<String> {
+    let padded: Vec<char> = format!("  {} ", text.to_lowercase()).chars().collect();
+    padded.windows(3).map(|window| window.iter().collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SearchIndex {
+        let mut index = SearchIndex::new();
+        index.add("CompItem", EntryKind::Object, None);
+        index.add("layer", EntryKind::Method, Some("CompItem"));
+        index.add("layers", EntryKind::Property, Some("CompItem"));
+        index.add("addNull", EntryKind::Method, Some("LayerCollection"));
+        index.add("MULTIPLY", EntryKind::EnumMember, Some("BlendingMode"));
+        index.add("ADBE Gaussian Blur 2", EntryKind::EffectMatchName, None);
+        index
+    }
+
+    #[test]
+    fn test_search_ranks_by_distance() {
+        let index = index();
+        let hits = index.search("layr", 5);
+        assert_eq!(hits[0].entry.name, "layer");
+        assert_eq!(hits[0].distance, 1);
+        assert!(index.search("zzzz", 5).is_empty());
+    }
+
+    #[test]
+    fn test_search_is_case_insensitive() {
+        let index = index();
+        let hits = index.search("multipy", 5);
+        assert_eq!(hits[0].entry.qualified_name(), "BlendingMode.MULTIPLY");
+    }
+
+    #[test]
+    fn test_member_suggestions_and_owners() {
+        let index = index();
+        assert_eq!(index.suggest_member("LayerCollection", "addNul", 3), vec!["addNull"]);
+        assert!(index.suggest_member("CompItem", "addNul", 3).is_empty());
+        assert_eq!(index.owners_of("addNull", EntryKind::Method), vec!["LayerCollection"]);
+    }
+}
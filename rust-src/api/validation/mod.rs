@@ -2,6 +2,5 @@ mod method;
 mod property;
 mod match_names;
 
-pub use self::method::*;
 pub use self::property::*;
 pub use self::match_names::*;
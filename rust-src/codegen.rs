@@ -0,0 +1,627 @@
+//! Typed builders that emit ExtendScript.
+//!
+//! Every argument is checked against the API model while the script is
+//! generated, and [`ScriptBuilder::build`] runs the finished script through
+//! [`validate_script`], so a script is only returned if it validates
+//! without errors.
+//!
+//! ```ignore
+//! let script = ScriptBuilder::new()
+//!     .undo_group("Build lower third")
+//!     .comp(CompBuilder::new("comp", "Lower Third").size(1920, 1080).layer(
+//!         LayerBuilder::text("title", "Breaking News")
+//!             .position(vec![960.0, 900.0])
+//!             .keyframes(KeyframeBuilder::new("transform.opacity", PropertyValueType::OneD)
+//!                 .key(Keyframe::new(0.0, json!(0)))
+//!                 .key(Keyframe::new(0.5, json!(100)))),
+//!     ))
+//!     .build()?;
+//! ```
+
+use std::fmt;
+use serde_json::{json, Value};
+
+use crate::api::UnifiedApi;
+use crate::api::interpolation::{KeyframeInterpolationType, KeyframeTemporal};
+use crate::validation::easing::EasingPreset;
+use crate::validation::keyframes::{ease_dimensions, validate_keyframe_data, Keyframe, KeyframeIssue};
+use crate::validation::property::validate_property_value;
+use crate::validation::rules::{MethodValidation, PropertyValueType, ValidationRule};
+use crate::validation::{validate_script, ValidatorError};
+
+/// Why a script could not be generated
+#[derive(Debug)]
+pub enum CodegenError {
+    /// An argument does not fit the API, e.g. a comp width outside 4-30000
+    InvalidArgument { call: String, message: String },
+    /// Keyframe data rejected by [`validate_keyframe_data`]
+    InvalidKeyframes { property: String, issues: Vec<KeyframeIssue> },
+    /// The generated script did not pass validation
+    Validation(Vec<ValidatorError>),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodegenError::InvalidArgument { call, message } => write!(f, "{}: {}", call, message),
+            CodegenError::InvalidKeyframes { property, issues } => {
+                write!(f, "Invalid keyframes for {}", property)?;
+                for issue in issues {
+                    write!(f, "; key {}: {}", issue.index + 1, issue.message)?;
+                }
+                Ok(())
+            }
+            CodegenError::Validation(errors) => {
+                write!(f, "Generated script failed validation")?;
+                for error in errors {
+                    write!(f, "; {}", error.get_message())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {}
+
+/// Quotes a string as a double-quoted JavaScript literal
+pub fn js_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\u{2028}' => quoted.push_str("\\u2028"),
+            '\u{2029}' => quoted.push_str("\\u2029"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// JavaScript source for a JSON value; strings go through [`js_string`]
+fn js_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => js_string(s),
+        Value::Array(items) => format!("[{}]", items.iter().map(js_value).collect::<Vec<_>>().join(", ")),
+        other => other.to_string(),
+    }
+}
+
+/// Checks call arguments against the method's declared parameter rules,
+/// trying each overload in turn
+fn check_call(class_name: &str, method_name: &str, args: &[Value]) -> Result<(), CodegenError> {
+    let api = UnifiedApi::shared();
+    let method = match api.object(class_name).and_then(|object| object.methods.get(method_name)) {
+        Some(method) => method,
+        None => return Ok(()),
+    };
+    match method.resolve(args.len(), |signature| call_problems(signature, args)) {
+        Ok(_) => Ok(()),
+        Err((_, mut problems)) => Err(CodegenError::InvalidArgument {
+            call: format!("{}.{}", class_name, method_name),
+            message: problems.remove(0),
+        }),
+    }
+}
+
+/// What is wrong with `args` for one signature of a method
+fn call_problems(method: &MethodValidation, args: &[Value]) -> Vec<String> {
+    if !method.accepts_arg_count(args.len()) {
+        return vec![format!("{} argument(s) given", args.len())];
+    }
+    args.iter()
+        .enumerate()
+        .filter_map(|(index, arg)| {
+            let rule = method.param_rule(index)?;
+            validate_property_value(arg, &rule).err().map(|e| format!("argument {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+fn check_value(call: &str, value: &Value, rule: &ValidationRule) -> Result<(), CodegenError> {
+    validate_property_value(value, rule).map_err(|message| CodegenError::InvalidArgument {
+        call: call.to_string(),
+        message,
+    })
+}
+
+fn call_args(args: &[Value]) -> String {
+    args.iter().map(js_value).collect::<Vec<_>>().join(", ")
+}
+
+/// A composition created with `app.project.items.addComp`
+#[derive(Debug, Clone)]
+pub struct CompBuilder {
+    var: String,
+    name: String,
+    width: u32,
+    height: u32,
+    pixel_aspect: f64,
+    duration: f64,
+    frame_rate: f64,
+    layers: Vec<LayerBuilder>,
+}
+
+impl CompBuilder {
+    /// A 1920x1080, 10 second, 30 fps comp assigned to `var`
+    pub fn new(var: &str, name: &str) -> Self {
+        Self {
+            var: var.to_string(),
+            name: name.to_string(),
+            width: 1920,
+            height: 1080,
+            pixel_aspect: 1.0,
+            duration: 10.0,
+            frame_rate: 30.0,
+            layers: Vec::new(),
+        }
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn pixel_aspect(mut self, pixel_aspect: f64) -> Self {
+        self.pixel_aspect = pixel_aspect;
+        self
+    }
+
+    pub fn duration(mut self, duration: f64) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn frame_rate(mut self, frame_rate: f64) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    pub fn layer(mut self, layer: LayerBuilder) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Statements creating the comp and its layers, in order
+    pub fn emit(&self) -> Result<Vec<String>, CodegenError> {
+        let args = vec![
+            json!(self.name),
+            json!(self.width),
+            json!(self.height),
+            json!(self.pixel_aspect),
+            json!(self.duration),
+            json!(self.frame_rate),
+        ];
+        check_call("ItemCollection", "addComp", &args)?;
+
+        let mut statements = vec![format!("var {} = app.project.items.addComp({});", self.var, call_args(&args))];
+        for layer in &self.layers {
+            statements.extend(layer.emit(self)?);
+        }
+        Ok(statements)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum LayerKind {
+    Solid { name: String, color: [f64; 3] },
+    Text(String),
+    Null,
+    Shape,
+    Camera(String),
+    Light(String),
+}
+
+/// A layer added to the comp it is attached to with [`CompBuilder::layer`]
+#[derive(Debug, Clone)]
+pub struct LayerBuilder {
+    var: String,
+    kind: LayerKind,
+    name: Option<String>,
+    position: Option<Vec<f64>>,
+    opacity: Option<f64>,
+    keyframes: Vec<KeyframeBuilder>,
+}
+
+impl LayerBuilder {
+    fn with_kind(var: &str, kind: LayerKind) -> Self {
+        Self {
+            var: var.to_string(),
+            kind,
+            name: None,
+            position: None,
+            opacity: None,
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// A comp-sized solid; `color` is `[r, g, b]` in 0-1
+    pub fn solid(var: &str, name: &str, color: [f64; 3]) -> Self {
+        Self::with_kind(var, LayerKind::Solid { name: name.to_string(), color })
+    }
+
+    pub fn text(var: &str, source_text: &str) -> Self {
+        Self::with_kind(var, LayerKind::Text(source_text.to_string()))
+    }
+
+    pub fn null(var: &str) -> Self {
+        Self::with_kind(var, LayerKind::Null)
+    }
+
+    pub fn shape(var: &str) -> Self {
+        Self::with_kind(var, LayerKind::Shape)
+    }
+
+    /// A camera centered on the comp
+    pub fn camera(var: &str, name: &str) -> Self {
+        Self::with_kind(var, LayerKind::Camera(name.to_string()))
+    }
+
+    /// A light centered on the comp
+    pub fn light(var: &str, name: &str) -> Self {
+        Self::with_kind(var, LayerKind::Light(name.to_string()))
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// `[x, y]`, or `[x, y, z]` for 3D layers
+    pub fn position(mut self, position: Vec<f64>) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn opacity(mut self, opacity: f64) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    pub fn keyframes(mut self, keyframes: KeyframeBuilder) -> Self {
+        self.keyframes.push(keyframes);
+        self
+    }
+
+    fn emit(&self, comp: &CompBuilder) -> Result<Vec<String>, CodegenError> {
+        let (method, args) = match &self.kind {
+            LayerKind::Solid { name, color } => ("addSolid", vec![
+                json!(color),
+                json!(name),
+                json!(comp.width),
+                json!(comp.height),
+                json!(comp.pixel_aspect),
+            ]),
+            LayerKind::Text(source_text) => ("addText", vec![json!(source_text)]),
+            LayerKind::Null => ("addNull", vec![]),
+            LayerKind::Shape => ("addShape", vec![]),
+            LayerKind::Camera(name) => ("addCamera", vec![json!(name), json!([comp.width as f64 / 2.0, comp.height as f64 / 2.0])]),
+            LayerKind::Light(name) => ("addLight", vec![json!(name), json!([comp.width as f64 / 2.0, comp.height as f64 / 2.0])]),
+        };
+        check_call("LayerCollection", method, &args)?;
+
+        let mut statements = vec![format!("var {} = {}.layers.{}({});", self.var, comp.var, method, call_args(&args))];
+        if let Some(name) = &self.name {
+            statements.push(format!("{}.name = {};", self.var, js_string(name)));
+        }
+        if let Some(position) = &self.position {
+            let value_type = if position.len() == 3 { PropertyValueType::ThreeDSpatial } else { PropertyValueType::TwoDSpatial };
+            let value = json!(position);
+            check_value("transform.position", &value, &ValidationRule::simple(value_type))?;
+            statements.push(format!("{}.transform.position.setValue({});", self.var, js_value(&value)));
+        }
+        if let Some(opacity) = self.opacity {
+            let value = json!(opacity);
+            check_value("transform.opacity", &value, &ValidationRule::simple(PropertyValueType::OneD).with_range(0.0, 100.0))?;
+            statements.push(format!("{}.transform.opacity.setValue({});", self.var, value));
+        }
+        for keyframes in &self.keyframes {
+            statements.extend(keyframes.emit(&self.var)?);
+        }
+        Ok(statements)
+    }
+}
+
+/// Keyframes for one property, reached from the layer by `property_path`
+/// (e.g. `transform.position`)
+#[derive(Debug, Clone)]
+pub struct KeyframeBuilder {
+    property_path: String,
+    value_type: PropertyValueType,
+    keys: Vec<Keyframe>,
+}
+
+impl KeyframeBuilder {
+    pub fn new(property_path: &str, value_type: PropertyValueType) -> Self {
+        Self {
+            property_path: property_path.to_string(),
+            value_type,
+            keys: Vec::new(),
+        }
+    }
+
+    pub fn key(mut self, keyframe: Keyframe) -> Self {
+        self.keys.push(keyframe);
+        self
+    }
+
+    pub fn keys(mut self, keyframes: Vec<Keyframe>) -> Self {
+        self.keys.extend(keyframes);
+        self
+    }
+
+    /// Moves from `from` to `to` over `duration`, easing both keys with
+    /// `preset`
+    pub fn eased(self, from: &[f64], to: &[f64], start: f64, duration: f64, preset: &EasingPreset) -> Self {
+        let keys = vec![self.eased_key(start, from, preset), self.eased_key(start + duration, to, preset)];
+        self.keys(keys)
+    }
+
+    /// Like [`KeyframeBuilder::eased`] with zero-speed eases of `influence`
+    /// (0.1 to 100) on both keys
+    pub fn ease_in_out(self, from: &[f64], to: &[f64], start: f64, duration: f64, influence: f32) -> Self {
+        self.eased(from, to, start, duration, &EasingPreset::ease_in_out(influence))
+    }
+
+    /// Moves past `to` by `amount` of the distance travelled, 60% of the way
+    /// through `duration`, then settles back on `to`
+    pub fn overshoot(self, from: &[f64], to: &[f64], start: f64, duration: f64, amount: f64) -> Self {
+        let preset = EasingPreset::named("easy-ease").unwrap();
+        let past = lerp(from, to, 1.0 + amount);
+        let keys = vec![
+            self.eased_key(start, from, preset),
+            self.eased_key(start + duration * 0.6, &past, preset),
+            self.eased_key(start + duration, to, preset),
+        ];
+        self.keys(keys)
+    }
+
+    /// Falls from `from` to `to` and bounces `bounces` times, each bounce a
+    /// quarter of the height and half the time of the one before, as under
+    /// gravity. Peaks are eased, impacts are linear so the motion reverses
+    /// sharply.
+    pub fn bounce(self, from: &[f64], to: &[f64], start: f64, duration: f64, bounces: usize) -> Self {
+        let preset = EasingPreset::named("easy-ease").unwrap();
+        // Time units: 1 for the fall, 2 * 0.5^n for the rise and fall of bounce n
+        let units = 1.0 + (1..=bounces).map(|n| 2.0 * 0.5f64.powi(n as i32)).sum::<f64>();
+        let unit = duration / units;
+        let impact = |time: f64| Keyframe::new(round_time(time), value_json(to))
+            .with_interpolation(KeyframeInterpolationType::Linear, KeyframeInterpolationType::Linear);
+
+        let mut keys = vec![self.eased_key(start, from, preset)];
+        let mut time = start + unit;
+        keys.push(impact(time));
+        for n in 1..=bounces {
+            let half = unit * 0.5f64.powi(n as i32);
+            let peak = lerp(to, from, 0.25f64.powi(n as i32));
+            keys.push(self.eased_key(time + half, &peak, preset));
+            time += 2.0 * half;
+            keys.push(impact(time));
+        }
+        self.keys(keys)
+    }
+
+    fn eased_key(&self, time: f64, value: &[f64], preset: &EasingPreset) -> Keyframe {
+        let (in_ease, out_ease) = preset.eases(ease_dimensions(&self.value_type));
+        Keyframe::new(round_time(time), value_json(value))
+            .with_interpolation(KeyframeInterpolationType::Bezier, KeyframeInterpolationType::Bezier)
+            .with_ease(in_ease, out_ease)
+    }
+
+    /// `setValueAtTime` for every key, then interpolation and ease for the
+    /// keys that set them. Key indices follow the times, which must be
+    /// strictly increasing on a property without existing keys.
+    pub fn emit(&self, layer_var: &str) -> Result<Vec<String>, CodegenError> {
+        validate_keyframe_data(&self.value_type, &self.keys).map_err(|issues| CodegenError::InvalidKeyframes {
+            property: self.property_path.clone(),
+            issues,
+        })?;
+
+        let property = format!("{}.{}", layer_var, self.property_path);
+        let mut statements: Vec<String> = self.keys.iter()
+            .map(|key| format!("{}.setValueAtTime({}, {});", property, key.time, js_value(&key.value)))
+            .collect();
+
+        for (index, key) in self.keys.iter().enumerate() {
+            if key.in_interpolation.is_some() || key.out_interpolation.is_some() {
+                let in_type = key.in_interpolation.as_ref().or(key.out_interpolation.as_ref()).unwrap();
+                let out_type = key.out_interpolation.as_ref().unwrap_or(in_type);
+                statements.push(format!(
+                    "{}.setInterpolationTypeAtKey({}, {}, {});",
+                    property,
+                    index + 1,
+                    interpolation_name(in_type),
+                    interpolation_name(out_type)
+                ));
+            }
+            if !key.in_ease.is_empty() || !key.out_ease.is_empty() {
+                statements.push(format!(
+                    "{}.setTemporalEaseAtKey({}, {}, {});",
+                    property,
+                    index + 1,
+                    ease_array(&key.in_ease, &key.out_ease),
+                    ease_array(&key.out_ease, &key.in_ease)
+                ));
+            }
+        }
+        Ok(statements)
+    }
+}
+
+/// The value `t` of the way from `from` to `to`, per component
+fn lerp(from: &[f64], to: &[f64], t: f64) -> Vec<f64> {
+    from.iter().zip(to).map(|(a, b)| a + (b - a) * t).collect()
+}
+
+/// A number for one component, an array otherwise
+fn value_json(value: &[f64]) -> Value {
+    match value {
+        [single] => json!(single),
+        _ => json!(value),
+    }
+}
+
+/// Keeps generated times readable: 0.6 rather than 0.6000000000000001
+fn round_time(time: f64) -> f64 {
+    (time * 10000.0).round() / 10000.0
+}
+
+fn interpolation_name(interpolation: &KeyframeInterpolationType) -> &'static str {
+    match interpolation {
+        KeyframeInterpolationType::Linear => "KeyframeInterpolationType.LINEAR",
+        KeyframeInterpolationType::Bezier => "KeyframeInterpolationType.BEZIER",
+        KeyframeInterpolationType::Hold => "KeyframeInterpolationType.HOLD",
+        // Continuous and auto bezier are flags on a bezier keyframe
+        KeyframeInterpolationType::Continuous | KeyframeInterpolationType::Auto => "KeyframeInterpolationType.BEZIER",
+    }
+}
+
+/// `setTemporalEaseAtKey` needs both sides; a side left empty gets the
+/// default ease with the other side's dimension count
+fn ease_array(eases: &[KeyframeTemporal], other_side: &[KeyframeTemporal]) -> String {
+    let items: Vec<String> = if eases.is_empty() {
+        other_side.iter().map(|_| KeyframeTemporal::default_ease()).map(|e| ease_literal(&e)).collect()
+    } else {
+        eases.iter().map(ease_literal).collect()
+    };
+    format!("[{}]", items.join(", "))
+}
+
+fn ease_literal(ease: &KeyframeTemporal) -> String {
+    format!("new KeyframeEase({}, {})", ease.speed, ease.influence)
+}
+
+/// A complete script: comps and their layers, plus any raw statements,
+/// optionally wrapped in an undo group
+#[derive(Debug, Clone, Default)]
+pub struct ScriptBuilder {
+    undo_group: Option<String>,
+    comps: Vec<CompBuilder>,
+    statements: Vec<String>,
+}
+
+impl ScriptBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn undo_group(mut self, name: &str) -> Self {
+        self.undo_group = Some(name.to_string());
+        self
+    }
+
+    pub fn comp(mut self, comp: CompBuilder) -> Self {
+        self.comps.push(comp);
+        self
+    }
+
+    /// A statement emitted after the comps; it is covered by the final
+    /// validation but not by the argument checks
+    pub fn statement(mut self, statement: &str) -> Self {
+        self.statements.push(statement.to_string());
+        self
+    }
+
+    /// Generates the script and validates it. Warnings are allowed, errors
+    /// are returned as [`CodegenError::Validation`].
+    pub fn build(&self) -> Result<String, CodegenError> {
+        let mut body = Vec::new();
+        for comp in &self.comps {
+            body.extend(comp.emit()?);
+        }
+        body.extend(self.statements.iter().cloned());
+
+        let script = match &self.undo_group {
+            Some(name) => {
+                let mut lines = vec![format!("app.beginUndoGroup({});", js_string(name))];
+                lines.extend(body.iter().map(|statement| format!("    {}", statement)));
+                lines.push("app.endUndoGroup();".to_string());
+                lines.join("\n") + "\n"
+            }
+            None => body.join("\n") + "\n",
+        };
+
+        let result = validate_script(&script, "<codegen>");
+        if result.errors.is_empty() {
+            Ok(script)
+        } else {
+            Err(CodegenError::Validation(result.errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builds_validated_script() {
+        let script = ScriptBuilder::new()
+            .undo_group("Build \"Lower Third\"")
+            .comp(CompBuilder::new("comp", "Lower Third").duration(5.0)
+                .layer(LayerBuilder::solid("bg", "Background", [0.1, 0.1, 0.1]))
+                .layer(LayerBuilder::text("title", "Breaking News")
+                    .position(vec![960.0, 900.0])
+                    .keyframes(KeyframeBuilder::new("transform.opacity", PropertyValueType::OneD)
+                        .key(Keyframe::new(0.0, json!(0)))
+                        .key(Keyframe::new(0.5, json!(100))
+                            .with_interpolation(KeyframeInterpolationType::Bezier, KeyframeInterpolationType::Bezier)
+                            .with_ease(vec![KeyframeTemporal::new(0.0, 75.0)], vec![])))))
+            .build()
+            .unwrap();
+
+        assert!(script.starts_with("app.beginUndoGroup(\"Build \\\"Lower Third\\\"\");\n"));
+        assert!(script.contains("var comp = app.project.items.addComp(\"Lower Third\", 1920, 1080, 1.0, 5.0, 30.0);"));
+        assert!(script.contains("var bg = comp.layers.addSolid([0.1, 0.1, 0.1], \"Background\", 1920, 1080, 1.0);"));
+        assert!(script.contains("title.transform.opacity.setTemporalEaseAtKey(2, [new KeyframeEase(0, 75)], [new KeyframeEase(0, 16.66667)]);"));
+    }
+
+    #[test]
+    fn test_easing_helpers() {
+        let keys = KeyframeBuilder::new("transform.position", PropertyValueType::TwoDSpatial)
+            .overshoot(&[0.0, 540.0], &[960.0, 540.0], 0.0, 1.0, 0.1)
+            .emit("logo")
+            .unwrap();
+        assert_eq!(keys[1], "logo.transform.position.setValueAtTime(0.6, [1056.0, 540.0]);");
+        assert!(keys.contains(&"logo.transform.position.setTemporalEaseAtKey(2, [new KeyframeEase(0, 33.33333)], [new KeyframeEase(0, 33.33333)]);".to_string()));
+
+        let keys = KeyframeBuilder::new("transform.scale", PropertyValueType::ThreeD)
+            .ease_in_out(&[0.0, 0.0, 100.0], &[100.0, 100.0, 100.0], 0.0, 0.5, 75.0)
+            .emit("logo")
+            .unwrap();
+        assert!(keys[3].ends_with("setTemporalEaseAtKey(1, [new KeyframeEase(0, 75), new KeyframeEase(0, 75), new KeyframeEase(0, 75)], [new KeyframeEase(0, 75), new KeyframeEase(0, 75), new KeyframeEase(0, 75)]);"));
+
+        let bounce = KeyframeBuilder::new("transform.opacity", PropertyValueType::OneD)
+            .bounce(&[100.0], &[0.0], 0.0, 2.5, 2);
+        let times: Vec<f64> = bounce.keys.iter().map(|k| k.time).collect();
+        assert_eq!(times, vec![0.0, 1.0, 1.5, 2.0, 2.25, 2.5]);
+        assert_eq!(bounce.keys[2].value, json!(25.0));
+        assert!(bounce.emit("fade").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_invalid_arguments() {
+        let err = ScriptBuilder::new()
+            .comp(CompBuilder::new("comp", "Huge").size(40000, 1080))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, CodegenError::InvalidArgument { ref call, .. } if call == "ItemCollection.addComp"));
+
+        let err = ScriptBuilder::new()
+            .comp(CompBuilder::new("comp", "Main").layer(LayerBuilder::null("ctrl")
+                .keyframes(KeyframeBuilder::new("transform.rotation", PropertyValueType::OneD)
+                    .key(Keyframe::new(1.0, json!(0)))
+                    .key(Keyframe::new(0.0, json!(90))))))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, CodegenError::InvalidKeyframes { ref issues, .. } if issues[0].index == 1));
+    }
+}
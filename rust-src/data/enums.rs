@@ -0,0 +1,42 @@
+/// After Effects scripting enumerations and their members
+pub fn get_enum_definitions() -> Vec<(&'static str, Vec<&'static str>)> {
+    vec![
+        ("AlphaMode", vec!["IGNORE", "STRAIGHT", "PREMULTIPLIED"]),
+        ("AutoOrientType", vec![
+            "ALONG_PATH", "CAMERA_OR_POINT_OF_INTEREST", "CHARACTERS_TOWARD_CAMERA", "NO_AUTO_ORIENT",
+        ]),
+        ("BlendingMode", vec![
+            "ADD", "ALPHA_ADD", "CLASSIC_COLOR_BURN", "CLASSIC_COLOR_DODGE", "CLASSIC_DIFFERENCE",
+            "COLOR", "COLOR_BURN", "COLOR_DODGE", "DANCING_DISSOLVE", "DARKEN", "DARKER_COLOR",
+            "DIFFERENCE", "DISSOLVE", "DIVIDE", "EXCLUSION", "HARD_LIGHT", "HARD_MIX", "HUE",
+            "LIGHTEN", "LIGHTER_COLOR", "LINEAR_BURN", "LINEAR_DODGE", "LINEAR_LIGHT",
+            "LUMINESCENT_PREMUL", "LUMINOSITY", "MULTIPLY", "NORMAL", "OVERLAY", "PIN_LIGHT",
+            "SATURATION", "SCREEN", "SILHOUETE_ALPHA", "SILHOUETTE_LUMA", "SOFT_LIGHT",
+            "STENCIL_ALPHA", "STENCIL_LUMA", "SUBTRACT", "VIVID_LIGHT",
+        ]),
+        ("CloseOptions", vec!["DO_NOT_SAVE_CHANGES", "PROMPT_TO_SAVE_CHANGES", "SAVE_CHANGES"]),
+        ("FrameBlendingType", vec!["FRAME_MIX", "NO_FRAME_BLEND", "PIXEL_MOTION"]),
+        ("ImportAsType", vec!["COMP_CROPPED_LAYERS", "FOOTAGE", "COMP", "PROJECT"]),
+        ("KeyframeInterpolationType", vec!["LINEAR", "BEZIER", "HOLD"]),
+        ("LayerQuality", vec!["BEST", "DRAFT", "WIREFRAME"]),
+        ("LayerSamplingQuality", vec!["BICUBIC", "BILINEAR"]),
+        ("LightType", vec!["PARALLEL", "SPOT", "POINT", "AMBIENT"]),
+        ("MaskMode", vec!["NONE", "ADD", "SUBTRACT", "INTERSECT", "LIGHTEN", "DARKEN", "DIFFERENCE"]),
+        ("MaskMotionBlur", vec!["SAME_AS_LAYER", "ON", "OFF"]),
+        ("ParagraphJustification", vec![
+            "LEFT_JUSTIFY", "CENTER_JUSTIFY", "RIGHT_JUSTIFY", "FULL_JUSTIFY_LASTLINE_LEFT",
+            "FULL_JUSTIFY_LASTLINE_RIGHT", "FULL_JUSTIFY_LASTLINE_CENTER", "FULL_JUSTIFY_LASTLINE_FULL",
+        ]),
+        ("PropertyType", vec!["PROPERTY", "INDEXED_GROUP", "NAMED_GROUP"]),
+        ("PropertyValueType", vec![
+            "NO_VALUE", "ThreeD_SPATIAL", "ThreeD", "TwoD_SPATIAL", "TwoD", "OneD", "COLOR",
+            "CUSTOM_VALUE", "MARKER", "LAYER_INDEX", "MASK_INDEX", "SHAPE", "TEXT_DOCUMENT",
+        ]),
+        ("RQItemStatus", vec![
+            "WILL_CONTINUE", "NEEDS_OUTPUT", "UNQUEUED", "QUEUED", "RENDERING", "USER_STOPPED",
+            "ERR_STOPPED", "DONE",
+        ]),
+        ("TimeDisplayType", vec!["FRAMES", "TIMECODE"]),
+        ("TrackMatteType", vec!["ALPHA", "ALPHA_INVERTED", "LUMA", "LUMA_INVERTED", "NO_TRACK_MATTE"]),
+    ]
+}
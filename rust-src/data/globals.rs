@@ -0,0 +1,45 @@
+use crate::validation::rules::{MethodValidation, PropertyValueType};
+
+/// Functions ExtendScript and After Effects define globally, callable
+/// without a receiver. Signatures are checked where the scripting guide
+/// documents parameters; the others, such as the ECMAScript built-ins and
+/// the variadic `localize`, accept any arguments.
+pub fn get_global_functions() -> Vec<(&'static str, MethodValidation)> {
+    let text = || PropertyValueType::ArbText;
+    let number = || PropertyValueType::OneD;
+    let boolean = || PropertyValueType::Custom("Boolean".to_string());
+
+    let mut functions = vec![
+        // Dialogs
+        ("alert", MethodValidation::new(1)
+            .with_param_types(vec![text()])                // message
+            .with_optional_params(vec![text(), boolean()])), // title, errorIcon
+        ("confirm", MethodValidation::new(1)
+            .with_param_types(vec![text()])                // message
+            .with_optional_params(vec![boolean(), text()])), // noAsDefault, title
+        ("prompt", MethodValidation::new(1)
+            .with_param_types(vec![text()])                // message
+            .with_optional_params(vec![text(), text()])),  // preset, title
+        // Info panel output
+        ("clearOutput", MethodValidation::new(0)),
+        ("write", MethodValidation::new(1).with_param_types(vec![PropertyValueType::CustomValue])),
+        ("writeLn", MethodValidation::new(1).with_param_types(vec![PropertyValueType::CustomValue])),
+        // Time formatting in the project's display style
+        ("timeToCurrentFormat", MethodValidation::new(2)
+            .with_param_types(vec![number(), number()])    // time, fps
+            .with_optional_params(vec![boolean()])),       // isDuration
+        ("currentFormatToTime", MethodValidation::new(2)
+            .with_param_types(vec![text(), number()])      // formattedTime, fps
+            .with_optional_params(vec![boolean()])),       // isDuration
+        ("generateRandomNumber", MethodValidation::new(0)),
+        ("isValid", MethodValidation::new(1).with_param_types(vec![PropertyValueType::CustomValue])),
+    ];
+
+    for builtin in [
+        "parseInt", "parseFloat", "isNaN", "isFinite", "eval", "escape", "unescape",
+        "encodeURI", "encodeURIComponent", "decodeURI", "decodeURIComponent", "uneval", "localize",
+    ] {
+        functions.push((builtin, MethodValidation::new(0)));
+    }
+    functions
+}
@@ -1,6 +1,7 @@
-pub fn get_effect_match_names() -> Vec<&'static str> {
-    vec![
-        // 3D Channel Effects
+/// Built-in effect match names, grouped under the Effect menu category
+/// each is listed in
+pub const EFFECT_GROUPS: &[(&str, &[&str])] = &[
+    ("3D Channel", &[
         "ADBE AUX CHANNEL EXTRACT",
         "ADBE DEPTH MATTE",
         "ADBE DEPTH FIELD",
@@ -8,8 +9,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE FOG_3D",
         "ADBE ID MATTE",
         "IDentifier",
-
-        // Audio Effects
+    ]),
+    ("Audio", &[
         "ADBE Aud Reverse",
         "ADBE Aud BT",
         "ADBE Aud Delay",
@@ -20,8 +21,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Aud Reverb",
         "ADBE Aud Stereo Mixer",
         "ADBE Aud Tone",
-
-        // Blur & Sharpen Effects
+    ]),
+    ("Blur & Sharpen", &[
         "ADBE Bilateral",
         "ADBE Camera Lens Blur",
         "ADBE CameraShakeDeblur",
@@ -38,8 +39,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Sharpen",
         "ADBE Smart Blur",
         "ADBE Unsharp Mask2",
-
-        // Channel Effects
+    ]),
+    ("Channel", &[
         "ADBE Arithmetic",
         "ADBE Blend",
         "ADBE Calculations",
@@ -53,8 +54,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Set Matte3",
         "ADBE Shift Channels",
         "ADBE Solid Composite",
-
-        // Color Correction Effects
+    ]),
+    ("Color Correction", &[
         "ADBE AutoColor",
         "ADBE AutoContrast",
         "ADBE AutoLevels",
@@ -89,8 +90,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Tint",
         "ADBE Tritone",
         "ADBE Vibrance",
-
-        // Distort Effects
+    ]),
+    ("Distort", &[
         "ADBE BEZMESH",
         "ADBE Bulge",
         "CC Bend It",
@@ -128,8 +129,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE WRPMESH",
         "ADBE SubspaceStabilizer",
         "ADBE Wave Warp",
-
-        // Expression Controls
+    ]),
+    ("Expression Controls", &[
         "ADBE Point3D Control",
         "ADBE Angle Control",
         "ADBE Checkbox Control",
@@ -138,8 +139,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Layer Control",
         "ADBE Point Control",
         "ADBE Slider Control",
-
-        // Generate Effects
+    ]),
+    ("Generate", &[
         "ADBE 4ColorGradient",
         "ADBE Lightning 2",
         "ADBE AudSpect",
@@ -166,8 +167,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Stroke",
         "APC Vegas",
         "ADBE Write-on",
-
-        // Keying Effects
+    ]),
+    ("Keying", &[
         "ADBE Spill2",
         "CC Simple Wire Removal",
         "ADBE Color Difference Key",
@@ -178,15 +179,15 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE KeyCleaner",
         "Keylight 906",
         "ADBE Linear Color Key2",
-
-        // Matte Effects
+    ]),
+    ("Matte", &[
         "ADBE Matte Choker",
         "ISL MochaShapeImporter",
         "ADBE RefineRBMatte",
         "ADBE RefineMatte2",
         "ADBE Simple Choker",
-
-        // Noise & Grain Effects
+    ]),
+    ("Noise & Grain", &[
         "VISINF Grain Implant",
         "ADBE Dust & Scratches",
         "ADBE Fractal Noise",
@@ -198,8 +199,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Noise HLS Auto2",
         "VISINF Grain Removal",
         "ADBE AIF Perlin Noise 3D",
-
-        // Perspective Effects
+    ]),
+    ("Perspective", &[
         "ADBE 3D Tracker",
         "ADBE 3D Glasses2",
         "ADBE Bevel Alpha",
@@ -210,8 +211,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "CC Spotlight",
         "ADBE Drop Shadow",
         "ADBE Radial Shadow",
-
-        // Simulation Effects
+    ]),
+    ("Simulation", &[
         "APC CardDanceCam",
         "APC Caustics",
         "CC Ball Action",
@@ -230,8 +231,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Playgnd",
         "APC Shatter",
         "APC Wave World",
-
-        // Stylize Effects
+    ]),
+    ("Stylize", &[
         "ADBE Brush Strokes",
         "ADBE Cartoonify",
         "CS BlockLoad",
@@ -257,12 +258,12 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Strobe",
         "ADBE Texturize",
         "ADBE Threshold2",
-
-        // Text Effects
+    ]),
+    ("Text", &[
         "ADBE Numbers2",
         "ADBE Timecode",
-
-        // Time Effects
+    ]),
+    ("Time", &[
         "CC Force Motion Blur",
         "CC Wide Time",
         "ADBE Echo",
@@ -271,8 +272,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Difference",
         "ADBE Time Displacement",
         "ADBE Timewarp",
-
-        // Transition Effects
+    ]),
+    ("Transition", &[
         "ADBE Block Dissolve",
         "APC CardWipeCam",
         "CC Glass Wipe",
@@ -290,8 +291,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Linear Wipe",
         "ADBE Radial Wipe",
         "ADBE Venetian Blinds",
-
-        // Utility Effects
+    ]),
+    ("Utility", &[
         "ADBE Apply Color LUT2",
         "CC Overbrights",
         "ADBE Cineon Converter2",
@@ -299,8 +300,8 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE GROW BOUNDS",
         "ADBE Compander",
         "ADBE HDR ToneMap",
-
-        // Obsolete Effects
+    ]),
+    ("Obsolete", &[
         "ADBE Basic 3D",
         "ADBE Basic Text2",
         "ADBE Color Key",
@@ -348,14 +349,23 @@ pub fn get_effect_match_names() -> Vec<&'static str> {
         "ADBE Geometry",
         "ADBE Unsharp Mask",
         "ADBE Vector Paint",
-
-        // Paint Effects
+    ]),
+    ("Paint", &[
         "ADBE Paint",
-
-        // Cinema 4D Effects
+    ]),
+    ("Cinema 4D", &[
         "CINEMA 4D Effect",
-
-        // Synthetic Aperture Effects
+    ]),
+    ("Synthetic Aperture", &[
         "SYNTHAP CF Color Finesse 2",
-    ]
-} 
\ No newline at end of file
+    ]),
+];
+
+pub fn get_effect_match_names() -> Vec<&'static str> {
+    EFFECT_GROUPS.iter().flat_map(|(_, names)| names.iter().copied()).collect()
+}
+
+/// Effect menu category of a built-in effect, e.g. `Blur & Sharpen`
+pub fn get_effect_group(match_name: &str) -> Option<&'static str> {
+    EFFECT_GROUPS.iter().find(|(_, names)| names.contains(&match_name)).map(|(group, _)| *group)
+}
@@ -0,0 +1,42 @@
+/// An effect superseded by a newer one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObsoleteEffect {
+    pub match_name: &'static str,
+    pub display_name: &'static str,
+    /// Match name of the effect to use instead; `None` when the feature
+    /// moved out of effects, e.g. into text layers
+    pub replacement: Option<&'static str>,
+    /// What to use instead, as shown to the user
+    pub advice: &'static str,
+    /// Whether the replacement has the same parameters under the same
+    /// names, so swapping the match name keeps the script working
+    pub compatible: bool,
+}
+
+pub const OBSOLETE_EFFECTS: &[ObsoleteEffect] = &[
+    ObsoleteEffect { match_name: "ADBE Gaussian Blur", display_name: "Gaussian Blur (Legacy)", replacement: Some("ADBE Gaussian Blur 2"), advice: "Gaussian Blur", compatible: true },
+    ObsoleteEffect { match_name: "ADBE Box Blur", display_name: "Box Blur", replacement: Some("ADBE Box Blur2"), advice: "Fast Box Blur", compatible: true },
+    ObsoleteEffect { match_name: "ADBE Fast Blur", display_name: "Fast Blur (Legacy)", replacement: Some("ADBE Box Blur2"), advice: "Fast Box Blur, whose Blur Radius replaces Blurriness", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Brightness & Contrast", display_name: "Brightness & Contrast (Legacy)", replacement: Some("ADBE Brightness & Contrast 2"), advice: "Brightness & Contrast", compatible: true },
+    ObsoleteEffect { match_name: "ADBE Color Balance", display_name: "Color Balance (Legacy)", replacement: Some("ADBE Color Balance 2"), advice: "Color Balance", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Color Balance (HLS)", display_name: "Color Balance (HLS)", replacement: Some("ADBE HUE SATURATION"), advice: "Hue/Saturation", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Exposure", display_name: "Exposure (Legacy)", replacement: Some("ADBE Exposure2"), advice: "Exposure", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Easy Levels", display_name: "Levels (Legacy)", replacement: Some("ADBE Easy Levels2"), advice: "Levels", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Pro Levels", display_name: "Levels (Individual Controls, Legacy)", replacement: Some("ADBE Pro Levels2"), advice: "Levels (Individual Controls)", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Lightning", display_name: "Lightning", replacement: Some("ADBE Lightning 2"), advice: "Advanced Lightning", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Color Key", display_name: "Color Key", replacement: Some("Keylight 906"), advice: "Keylight", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Luma Key", display_name: "Luma Key", replacement: Some("ADBE Extract"), advice: "Extract", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Spill Suppressor", display_name: "Spill Suppressor", replacement: Some("ADBE Spill2"), advice: "Advanced Spill Suppressor", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Geometry", display_name: "Transform (Legacy)", replacement: Some("ADBE Geometry2"), advice: "Transform", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Unsharp Mask", display_name: "Unsharp Mask (Legacy)", replacement: Some("ADBE Unsharp Mask2"), advice: "Unsharp Mask", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Set Matte2", display_name: "Set Matte (Legacy)", replacement: Some("ADBE Set Matte3"), advice: "Set Matte", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Threshold", display_name: "Threshold (Legacy)", replacement: Some("ADBE Threshold2"), advice: "Threshold", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Three-Way Color Corrector", display_name: "Three-Way Color Corrector", replacement: Some("ADBE Lumetri"), advice: "Lumetri Color", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Basic Text2", display_name: "Basic Text", replacement: None, advice: "a text layer (comp.layers.addText)", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Path Text", display_name: "Path Text", replacement: None, advice: "a text layer with a path under Path Options", compatible: false },
+    ObsoleteEffect { match_name: "ADBE Basic 3D", display_name: "Basic 3D", replacement: None, advice: "a 3D layer (layer.threeDLayer = true)", compatible: false },
+];
+
+pub fn find_obsolete_effect(match_name: &str) -> Option<&'static ObsoleteEffect> {
+    OBSOLETE_EFFECTS.iter().find(|effect| effect.match_name == match_name)
+}
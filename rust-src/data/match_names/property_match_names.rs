@@ -1,5 +1,7 @@
+use super::property_tree::PROPERTY_TREE;
+
 pub fn get_property_match_names() -> Vec<&'static str> {
-    vec![
+    let mut names = vec![
         // Text Layer Properties
         "ADBE Text Properties",
         "ADBE Text Document",
@@ -584,5 +586,15 @@ pub fn get_property_match_names() -> Vec<&'static str> {
         "ADBE Expression Enabled",
         "ADBE Expression Language",
         "ADBE Expression Debug",
-    ]
-} 
\ No newline at end of file
+    ];
+
+    // Groups and properties only known from where they sit in the tree
+    for (_, children) in PROPERTY_TREE {
+        for child in children.iter() {
+            if !names.contains(child) {
+                names.push(child);
+            }
+        }
+    }
+    names
+}
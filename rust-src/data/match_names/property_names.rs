@@ -0,0 +1,249 @@
+use super::effect_match_names::get_effect_match_names;
+use super::property_tree::{get_property_children, PROPERTY_TREE};
+
+/// Names a property can be reached by other than its match name: the
+/// English display name, the attribute scripts use, and display names in
+/// German, French, Spanish and Japanese installs.
+///
+/// Several properties share a display name ("Position", "Opacity"); which
+/// one a name means depends on the group it is looked up in.
+pub const PROPERTY_NAMES: &[(&str, &[&str])] = &[
+    // Layer groups
+    ("ADBE Marker", &["Marker", "marker", "Marke", "Marqueur", "Marcador", "マーカー"]),
+    ("ADBE Time Remapping", &["Time Remap", "timeRemap", "Zeitverzerrung", "Remappage temporel", "Reasignación de tiempo", "タイムリマップ"]),
+    ("ADBE Mask Parade", &["Masks", "mask", "Masken", "Masques", "Máscaras", "マスク"]),
+    ("ADBE Effect Parade", &["Effects", "effect", "Effekte", "Effets", "Efectos", "エフェクト"]),
+    ("ADBE Transform Group", &["Transform", "transform", "Transformieren", "Transformer", "Transformar", "トランスフォーム"]),
+    ("ADBE Layer Styles", &["Layer Styles", "layerStyle", "Ebenenstile", "Styles de calque", "Estilos de capa", "レイヤースタイル"]),
+    ("ADBE Material Options Group", &["Material Options", "Materialoptionen", "Options de matière", "Opciones de material", "マテリアルオプション"]),
+    ("ADBE Audio Group", &["Audio", "audio", "オーディオ"]),
+    ("ADBE Text Properties", &["Text", "text", "Texte", "Texto", "テキスト"]),
+    ("ADBE Root Vectors Group", &["Contents", "content", "Inhalt", "Contenu", "Contenido", "コンテンツ"]),
+    ("ADBE Camera Options Group", &["Camera Options", "cameraOption", "Kameraoptionen", "Options de la caméra", "Opciones de cámara", "カメラオプション"]),
+    ("ADBE Light Options Group", &["Light Options", "lightOption", "Lichtoptionen", "Options d'éclairage", "Opciones de luz", "ライトオプション"]),
+
+    // Transform
+    ("ADBE Anchor Point", &["Anchor Point", "anchorPoint", "Ankerpunkt", "Point d'ancrage", "Punto de anclaje", "アンカーポイント"]),
+    ("ADBE Position", &["Position", "position", "Posición", "位置"]),
+    ("ADBE Position_0", &["X Position", "xPosition", "X-Position", "Position X", "Posición X", "X 位置"]),
+    ("ADBE Position_1", &["Y Position", "yPosition", "Y-Position", "Position Y", "Posición Y", "Y 位置"]),
+    ("ADBE Position_2", &["Z Position", "zPosition", "Z-Position", "Position Z", "Posición Z", "Z 位置"]),
+    ("ADBE Scale", &["Scale", "scale", "Skalierung", "Échelle", "Escala", "スケール"]),
+    ("ADBE Orientation", &["Orientation", "orientation", "Ausrichtung", "Orientación", "方向"]),
+    ("ADBE Rotate X", &["X Rotation", "xRotation", "X-Drehung", "Rotation X", "Rotación X", "X 回転"]),
+    ("ADBE Rotate Y", &["Y Rotation", "yRotation", "Y-Drehung", "Rotation Y", "Rotación Y", "Y 回転"]),
+    ("ADBE Rotate Z", &["Rotation", "rotation", "Z Rotation", "zRotation", "Drehung", "Z-Drehung", "Rotation Z", "Rotación", "Rotación Z", "回転", "Z 回転"]),
+    ("ADBE Opacity", &["Opacity", "opacity", "Deckkraft", "Opacité", "Opacidad", "不透明度"]),
+
+    // Masks
+    ("ADBE Mask Shape", &["Mask Path", "maskPath", "maskShape", "Maskenpfad", "Tracé du masque", "Trazado de máscara", "マスクパス"]),
+    ("ADBE Mask Feather", &["Mask Feather", "maskFeather", "Weiche Maskenkante", "Contour progressif du masque", "Desvanecimiento de máscara", "マスクの境界のぼかし"]),
+    ("ADBE Mask Opacity", &["Mask Opacity", "maskOpacity", "Maskendeckkraft", "Opacité du masque", "Opacidad de máscara", "マスクの不透明度"]),
+    ("ADBE Mask Offset", &["Mask Expansion", "maskExpansion", "Maskenausweitung", "Expansion du masque", "Expansión de máscara", "マスクの拡張"]),
+
+    // Text
+    ("ADBE Text Document", &["Source Text", "sourceText", "Quelltext", "Texte source", "Texto de origen", "ソーステキスト"]),
+    ("ADBE Text Path Options", &["Path Options", "pathOption"]),
+    ("ADBE Text More Options", &["More Options", "moreOption"]),
+    ("ADBE Text Animators", &["Animators", "animator"]),
+    ("ADBE Text Selectors", &["Selectors", "selector"]),
+    ("ADBE Text Animator Properties", &["Properties", "property"]),
+
+    // Shapes
+    ("ADBE Vectors Group", &["Contents", "content", "Inhalt", "Contenu", "Contenido", "コンテンツ"]),
+    ("ADBE Vector Group", &["Group", "Gruppe", "Groupe", "Grupo", "グループ"]),
+    ("ADBE Vector Transform Group", &["Transform", "transform", "Transformieren", "Transformer", "Transformar", "トランスフォーム"]),
+    ("ADBE Vector Anchor", &["Anchor Point", "anchorPoint", "Ankerpunkt", "Point d'ancrage", "Punto de anclaje", "アンカーポイント"]),
+    ("ADBE Vector Position", &["Position", "position", "Posición", "位置"]),
+    ("ADBE Vector Scale", &["Scale", "scale", "Skalierung", "Échelle", "Escala", "スケール"]),
+    ("ADBE Vector Skew", &["Skew", "skew", "Neigung", "Inclinaison", "Sesgar", "歪曲"]),
+    ("ADBE Vector Skew Axis", &["Skew Axis", "skewAxis", "Neigungsachse", "Axe d'inclinaison", "Eje de sesgo", "歪曲軸"]),
+    ("ADBE Vector Rotation", &["Rotation", "rotation", "Drehung", "Rotación", "回転"]),
+    ("ADBE Vector Group Opacity", &["Opacity", "opacity", "Deckkraft", "Opacité", "Opacidad", "不透明度"]),
+    ("ADBE Vector Rect Size", &["Size", "size", "Größe", "Taille", "Tamaño", "サイズ"]),
+    ("ADBE Vector Rect Position", &["Position", "position", "Posición", "位置"]),
+    ("ADBE Vector Rect Roundness", &["Roundness", "roundness", "Rundheit", "Arrondi", "Redondez", "角丸の半径"]),
+    ("ADBE Vector Ellipse Size", &["Size", "size", "Größe", "Taille", "Tamaño", "サイズ"]),
+    ("ADBE Vector Ellipse Position", &["Position", "position", "Posición", "位置"]),
+    ("ADBE Vector Shape", &["Path", "path", "Pfad", "Tracé", "Trazado", "パス"]),
+    ("ADBE Vector Graphic - Fill", &["Fill", "Fläche", "Fond", "Relleno", "塗り"]),
+    ("ADBE Vector Graphic - Stroke", &["Stroke", "Kontur", "Contour", "Trazo", "線"]),
+    ("ADBE Vector Fill Color", &["Color", "color", "Farbe", "Couleur", "カラー"]),
+    ("ADBE Vector Fill Opacity", &["Opacity", "opacity", "Deckkraft", "Opacité", "Opacidad", "不透明度"]),
+    ("ADBE Vector Stroke Color", &["Color", "color", "Farbe", "Couleur", "カラー"]),
+    ("ADBE Vector Stroke Opacity", &["Opacity", "opacity", "Deckkraft", "Opacité", "Opacidad", "不透明度"]),
+    ("ADBE Vector Stroke Width", &["Stroke Width", "strokeWidth", "Konturbreite", "Largeur du contour", "Anchura del trazo", "線幅"]),
+    ("ADBE Vector Trim Start", &["Start", "start", "Anfang", "Début", "Inicio", "開始点"]),
+    ("ADBE Vector Trim End", &["End", "end", "Ende", "Fin", "終了点"]),
+    ("ADBE Vector Trim Offset", &["Offset", "offset", "Versatz", "Décalage", "Desplazamiento", "オフセット"]),
+
+    // Cameras and lights
+    ("ADBE Camera Zoom", &["Zoom", "zoom", "ズーム"]),
+    ("ADBE Camera Depth of Field", &["Depth of Field", "depthOfField", "Tiefenschärfe", "Profondeur de champ", "Profundidad de campo", "被写界深度"]),
+    ("ADBE Camera Focus Distance", &["Focus Distance", "focusDistance", "Fokusentfernung", "Distance de mise au point", "Distancia de enfoque", "フォーカス距離"]),
+    ("ADBE Camera Aperture", &["Aperture", "aperture", "Blende", "Ouverture", "Apertura", "絞り"]),
+    ("ADBE Camera Blur Level", &["Blur Level", "blurLevel", "Weichzeichnungsstärke", "Niveau de flou", "Nivel de desenfoque", "ブラーレベル"]),
+    ("ADBE Light Intensity", &["Intensity", "intensity", "Intensität", "Intensité", "Intensidad", "強度"]),
+    ("ADBE Light Color", &["Color", "color", "Farbe", "Couleur", "カラー"]),
+    ("ADBE Light Cone Angle", &["Cone Angle", "coneAngle", "Lichtkegel", "Angle du cône", "Ángulo de cono", "円錐角"]),
+    ("ADBE Light Cone Feather", &["Cone Feather", "coneFeather", "Weiche Kegelkante", "Contour progressif du cône", "Desvanecimiento de cono", "円錐のぼかし"]),
+    ("ADBE Casts Shadows", &["Casts Shadows", "castsShadows", "Wirft Schatten", "Projette des ombres", "Proyecta sombras", "シャドウを落とす"]),
+    ("ADBE Light Shadow Darkness", &["Shadow Darkness", "shadowDarkness", "Schattendunkelheit", "Obscurité de l'ombre", "Oscuridad de sombra", "シャドウの暗さ"]),
+    ("ADBE Light Shadow Diffusion", &["Shadow Diffusion", "shadowDiffusion", "Schattenstreuung", "Diffusion de l'ombre", "Difusión de sombra", "シャドウの拡散"]),
+];
+
+/// Layer match names, the owners of the groups at the top of a layer
+const LAYERS: &[&str] = &["ADBE AV Layer", "ADBE Text Layer", "ADBE Vector Layer", "ADBE Camera Layer", "ADBE Light Layer"];
+
+/// The match name `name` stands for in the group `parent`, or at the top of
+/// a layer when `parent` is `None`. `name` may be a match name, a display
+/// name in any listed language, or a script attribute; properties of the
+/// transform group can also be named directly on a layer.
+pub fn canonical_match_name(name: &str, parent: Option<&str>) -> Option<&'static str> {
+    let owners = match parent {
+        Some(parent) => vec![parent],
+        None => LAYERS.to_vec(),
+    };
+    let resolved = resolve(name, &owners)?;
+    resolved.last().copied()
+}
+
+/// Maps a property path to its match names joined by dots, e.g.
+/// `layer.Transform.Position` and `thisLayer.property("Transformieren").position`
+/// to `ADBE Transform Group.ADBE Position`.
+///
+/// Segments are attributes, `property("name")` calls or bare names. Leading
+/// segments that are not properties, such as `layer` or `thisComp.layer(1)`,
+/// are the path's root and are dropped. Names below an effect are kept as
+/// written, since effect parameters are not in the property tree.
+pub fn canonicalize_property_path(path: &str) -> Result<String, String> {
+    let mut owners: Vec<&str> = LAYERS.to_vec();
+    let mut canonical: Vec<String> = Vec::new();
+    let mut in_effect = false;
+
+    for segment in split_path(path) {
+        let name = property_name(segment);
+        if in_effect {
+            canonical.push(name.to_string());
+            continue;
+        }
+        match resolve(name, &owners) {
+            Some(resolved) => {
+                let last = *resolved.last().unwrap();
+                in_effect = owners.contains(&"ADBE Effect Parade");
+                canonical.extend(resolved.iter().map(|m| m.to_string()));
+                owners = vec![last];
+            }
+            // Roots are variables and calls, which start in lower case
+            None if canonical.is_empty() && !name.starts_with(char::is_uppercase) && name == segment => continue,
+            None => {
+                let owner = canonical.last().map_or("a layer", String::as_str);
+                return Err(format!("'{}' is not a property of {}", name, owner));
+            }
+        }
+    }
+
+    if canonical.is_empty() {
+        return Err(format!("'{}' names no property", path));
+    }
+    Ok(canonical.join("."))
+}
+
+/// The match names `name` reaches from one of `owners`: usually one, two
+/// for a transform property named directly on a layer
+fn resolve(name: &str, owners: &[&str]) -> Option<Vec<&'static str>> {
+    let candidates = candidates(name);
+    let contains = |owner: &str, child: &str| get_property_children(owner).is_some_and(|children| children.contains(&child));
+
+    if let Some(found) = candidates.iter().find(|c| owners.iter().any(|owner| contains(owner, c))) {
+        return Some(vec![found]);
+    }
+    // Any effect goes in the effects group
+    if owners.contains(&"ADBE Effect Parade") {
+        if let Some(effect) = get_effect_match_names().into_iter().find(|effect| *effect == name) {
+            return Some(vec![effect]);
+        }
+    }
+    // `layer.position` for `layer.transform.position`
+    if owners.iter().any(|owner| LAYERS.contains(owner)) {
+        if let Some(found) = candidates.iter().find(|c| contains("ADBE Transform Group", c)) {
+            return Some(vec!["ADBE Transform Group", found]);
+        }
+    }
+    None
+}
+
+/// Match names `name` may stand for, in table order; exact names first,
+/// then names differing only in case
+fn candidates(name: &str) -> Vec<&'static str> {
+    let mut found: Vec<&'static str> = PROPERTY_TREE.iter()
+        .flat_map(|(owner, children)| std::iter::once(*owner).chain(children.iter().copied()))
+        .filter(|match_name| *match_name == name)
+        .take(1)
+        .collect();
+    found.extend(PROPERTY_NAMES.iter().filter(|(_, names)| names.contains(&name)).map(|(match_name, _)| *match_name));
+    found.extend(PROPERTY_NAMES.iter()
+        .filter(|(_, names)| names.iter().any(|n| n.to_lowercase() == name.to_lowercase()))
+        .map(|(match_name, _)| *match_name));
+    found
+}
+
+/// Splits on dots outside parentheses and quotes
+fn split_path(path: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in path.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, '.') if depth == 0 => {
+                segments.push(path[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(path[start..].trim());
+    segments.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+/// The name in `property("name")`, or the segment itself
+fn property_name(segment: &str) -> &str {
+    let argument = segment.strip_prefix("property").unwrap_or(segment).trim();
+    argument.strip_prefix('(')
+        .and_then(|a| a.strip_suffix(')'))
+        .map(str::trim)
+        .filter(|a| a.len() >= 2 && (a.starts_with('"') && a.ends_with('"') || a.starts_with('\'') && a.ends_with('\'')))
+        .map_or(segment, |a| &a[1..a.len() - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_property_path() {
+        let transform_position = Ok("ADBE Transform Group.ADBE Position".to_string());
+        assert_eq!(canonicalize_property_path("layer.Transform.Position"), transform_position);
+        assert_eq!(canonicalize_property_path("thisComp.layer(\"Title\").transform.position"), transform_position);
+        assert_eq!(canonicalize_property_path("layer.position"), transform_position);
+        assert_eq!(canonicalize_property_path("layer.property(\"ADBE Transform Group\").property('Position')"), transform_position);
+        assert_eq!(canonicalize_property_path("layer.property(\"Transformieren\").property(\"Deckkraft\")").as_deref(),
+            Ok("ADBE Transform Group.ADBE Opacity"));
+        assert_eq!(canonicalize_property_path("shape.content.property(\"ADBE Vector Group\").transform.position").as_deref(),
+            Ok("ADBE Root Vectors Group.ADBE Vector Group.ADBE Vector Transform Group.ADBE Vector Position"));
+        assert_eq!(canonicalize_property_path("layer.Effects.property(\"ADBE Gaussian Blur 2\").Blurriness").as_deref(),
+            Ok("ADBE Effect Parade.ADBE Gaussian Blur 2.Blurriness"));
+        assert_eq!(canonicalize_property_path("layer.Transform.Blurriness"),
+            Err("'Blurriness' is not a property of ADBE Transform Group".to_string()));
+        assert!(canonicalize_property_path("layer.Transfrom.Position").is_err());
+
+        assert_eq!(canonical_match_name("Opacity", Some("ADBE Vector Graphic - Fill")), Some("ADBE Vector Fill Opacity"));
+        assert_eq!(canonical_match_name("xRotation", None), Some("ADBE Rotate X"));
+    }
+}
@@ -0,0 +1,309 @@
+/// The property hierarchy: each owner with the match names it may contain.
+///
+/// Owners are layer match names, for the groups at the top of a layer, or
+/// property group match names. Groups whose contents are open-ended, such
+/// as "ADBE Effect Parade" which takes any effect, are not listed.
+pub const PROPERTY_TREE: &[(&str, &[&str])] = &[
+    // Layer Types
+    ("ADBE AV Layer", &[
+        "ADBE Marker",
+        "ADBE Time Remapping",
+        "ADBE MTrackers",
+        "ADBE Mask Parade",
+        "ADBE Effect Parade",
+        "ADBE Transform Group",
+        "ADBE Layer Styles",
+        "ADBE Plane Options Group",
+        "ADBE Extrsn Options Group",
+        "ADBE Material Options Group",
+        "ADBE Audio Group",
+    ]),
+    ("ADBE Text Layer", &[
+        "ADBE Marker",
+        "ADBE Text Properties",
+        "ADBE Mask Parade",
+        "ADBE Effect Parade",
+        "ADBE Transform Group",
+        "ADBE Layer Styles",
+        "ADBE Extrsn Options Group",
+        "ADBE Material Options Group",
+        "ADBE Audio Group",
+    ]),
+    ("ADBE Vector Layer", &[
+        "ADBE Marker",
+        "ADBE Root Vectors Group",
+        "ADBE Mask Parade",
+        "ADBE Effect Parade",
+        "ADBE Transform Group",
+        "ADBE Layer Styles",
+        "ADBE Extrsn Options Group",
+        "ADBE Material Options Group",
+        "ADBE Audio Group",
+    ]),
+    ("ADBE Camera Layer", &[
+        "ADBE Marker",
+        "ADBE Transform Group",
+        "ADBE Camera Options Group",
+    ]),
+    ("ADBE Light Layer", &[
+        "ADBE Marker",
+        "ADBE Transform Group",
+        "ADBE Light Options Group",
+    ]),
+
+    // Common Layer Properties
+    ("ADBE Transform Group", &[
+        "ADBE Anchor Point",
+        "ADBE Position",
+        "ADBE Position_0",
+        "ADBE Position_1",
+        "ADBE Position_2",
+        "ADBE Scale",
+        "ADBE Orientation",
+        "ADBE Rotate X",
+        "ADBE Rotate Y",
+        "ADBE Rotate Z",
+        "ADBE Opacity",
+    ]),
+    ("ADBE Mask Parade", &["ADBE Mask Atom"]),
+    ("ADBE Mask Atom", &[
+        "ADBE Mask Shape",
+        "ADBE Mask Feather",
+        "ADBE Mask Opacity",
+        "ADBE Mask Offset",
+    ]),
+
+    // Text Layer Properties
+    ("ADBE Text Properties", &[
+        "ADBE Text Document",
+        "ADBE Text Path Options",
+        "ADBE Text More Options",
+        "ADBE Text Animators",
+    ]),
+    ("ADBE Text Path Options", &[
+        "ADBE Text Path",
+        "ADBE Text Reverse Path",
+        "ADBE Text Perpendicular To Path",
+        "ADBE Text Force Align Path",
+        "ADBE Text First Margin",
+        "ADBE Text Last Margin",
+    ]),
+    ("ADBE Text More Options", &[
+        "ADBE Text Anchor Point Option",
+        "ADBE Text Anchor Point Align",
+        "ADBE Text Render Order",
+        "ADBE Text Character Blend Mode",
+    ]),
+    ("ADBE Text Animators", &["ADBE Text Animator"]),
+    ("ADBE Text Animator", &["ADBE Text Selectors", "ADBE Text Animator Properties"]),
+    ("ADBE Text Selectors", &[
+        "ADBE Text Selector",
+        "ADBE Text Wiggly Selector",
+        "ADBE Text Expressible Selector",
+    ]),
+    ("ADBE Text Selector", &[
+        "ADBE Text Percent Start",
+        "ADBE Text Percent End",
+        "ADBE Text Percent Offset",
+        "ADBE Text Index Start",
+        "ADBE Text Index End",
+        "ADBE Text Index Offset",
+        "ADBE Text Range Advanced",
+    ]),
+    ("ADBE Text Expressible Selector", &[
+        "ADBE Text Range Type2",
+        "ADBE Text Range Units",
+        "ADBE Text Expressible Amount",
+    ]),
+    ("ADBE Text Range Advanced", &[
+        "ADBE Text Range Units",
+        "ADBE Text Range Type2",
+        "ADBE Text Selector Mode",
+        "ADBE Text Selector Max Amount",
+        "ADBE Text Range Shape",
+        "ADBE Text Selector Smoothness",
+        "ADBE Text Levels Max Ease",
+        "ADBE Text Levels Min Ease",
+        "ADBE Text Randomize Order",
+        "ADBE Text Random Seed",
+    ]),
+    ("ADBE Text Animator Properties", &[
+        "ADBE Text Anchor Point 3D",
+        "ADBE Text Position 3D",
+        "ADBE Text Scale 3D",
+        "ADBE Text Skew",
+        "ADBE Text Skew Axis",
+        "ADBE Text Rotation X",
+        "ADBE Text Rotation Y",
+        "ADBE Text Rotation",
+        "ADBE Text Opacity",
+        "ADBE Text Fill Opacity",
+        "ADBE Text Stroke Opacity",
+        "ADBE Text Fill Color",
+        "ADBE Text Stroke Color",
+        "ADBE Text Fill Hue",
+        "ADBE Text Stroke Hue",
+        "ADBE Text Fill Saturation",
+        "ADBE Text Stroke Saturation",
+        "ADBE Text Fill Brightness",
+        "ADBE Text Stroke Brightness",
+        "ADBE Text Stroke Width",
+        "ADBE Text Line Anchor",
+        "ADBE Text Track Type",
+        "ADBE Text Tracking Amount",
+        "ADBE Text Character Replace",
+        "ADBE Text Character Offset",
+        "ADBE Text Line Spacing",
+        "ADBE Text Blur",
+    ]),
+
+    // Shape Layer Properties
+    ("ADBE Root Vectors Group", SHAPE_CONTENTS),
+    ("ADBE Vectors Group", SHAPE_CONTENTS),
+    ("ADBE Vector Group", &[
+        "ADBE Vector Blend Mode",
+        "ADBE Vectors Group",
+        "ADBE Vector Transform Group",
+        "ADBE Vector Materials Group",
+    ]),
+    ("ADBE Vector Transform Group", &[
+        "ADBE Vector Anchor",
+        "ADBE Vector Position",
+        "ADBE Vector Scale",
+        "ADBE Vector Skew",
+        "ADBE Vector Skew Axis",
+        "ADBE Vector Rotation",
+        "ADBE Vector Group Opacity",
+    ]),
+    ("ADBE Vector Shape - Rect", &[
+        "ADBE Vector Shape Direction",
+        "ADBE Vector Rect Size",
+        "ADBE Vector Rect Position",
+        "ADBE Vector Rect Roundness",
+    ]),
+    ("ADBE Vector Shape - Ellipse", &[
+        "ADBE Vector Shape Direction",
+        "ADBE Vector Ellipse Size",
+        "ADBE Vector Ellipse Position",
+    ]),
+    ("ADBE Vector Shape - Star", &[
+        "ADBE Vector Shape Direction",
+        "ADBE Vector Star Type",
+        "ADBE Vector Star Points",
+        "ADBE Vector Star Position",
+        "ADBE Vector Star Rotation",
+        "ADBE Vector Star Inner Radius",
+        "ADBE Vector Star Outer Radius",
+        "ADBE Vector Star Inner Roundess",
+        "ADBE Vector Star Outer Roundess",
+    ]),
+    ("ADBE Vector Shape - Group", &["ADBE Vector Shape Direction", "ADBE Vector Shape"]),
+    ("ADBE Vector Graphic - Fill", &[
+        "ADBE Vector Blend Mode",
+        "ADBE Vector Composite Order",
+        "ADBE Vector Fill Rule",
+        "ADBE Vector Fill Color",
+        "ADBE Vector Fill Opacity",
+    ]),
+    ("ADBE Vector Graphic - Stroke", &[
+        "ADBE Vector Blend Mode",
+        "ADBE Vector Composite Order",
+        "ADBE Vector Stroke Color",
+        "ADBE Vector Stroke Opacity",
+        "ADBE Vector Stroke Width",
+        "ADBE Vector Stroke Line Cap",
+        "ADBE Vector Stroke Line Join",
+        "ADBE Vector Stroke Miter Limit",
+        "ADBE Vector Stroke Dashes",
+    ]),
+    ("ADBE Vector Stroke Dashes", &[
+        "ADBE Vector Stroke Dash 1",
+        "ADBE Vector Stroke Gap 1",
+        "ADBE Vector Stroke Dash 2",
+        "ADBE Vector Stroke Gap 2",
+        "ADBE Vector Stroke Dash 3",
+        "ADBE Vector Stroke Gap 3",
+        "ADBE Vector Stroke Offset",
+    ]),
+    ("ADBE Vector Filter - Trim", &[
+        "ADBE Vector Trim Start",
+        "ADBE Vector Trim End",
+        "ADBE Vector Trim Offset",
+        "ADBE Vector Trim Type",
+    ]),
+    ("ADBE Vector Filter - Repeater", &[
+        "ADBE Vector Repeater Copies",
+        "ADBE Vector Repeater Offset",
+        "ADBE Vector Repeater Order",
+        "ADBE Vector Repeater Transform",
+    ]),
+
+    // Camera Properties
+    ("ADBE Camera Options Group", &[
+        "ADBE Camera Zoom",
+        "ADBE Camera Depth of Field",
+        "ADBE Camera Focus Distance",
+        "ADBE Camera Aperture",
+        "ADBE Camera Blur Level",
+        "ADBE Camera Iris Shape",
+        "ADBE Camera Iris Rotation",
+        "ADBE Camera Iris Roundness",
+        "ADBE Camera Iris Aspect Ratio",
+        "ADBE Camera Iris Diffraction Fringe",
+        "ADBE Camera Highlight Gain",
+        "ADBE Camera Highlight Threshold",
+        "ADBE Camera Highlight Saturation",
+    ]),
+
+    // Light Properties
+    ("ADBE Light Options Group", &[
+        "ADBE Light Intensity",
+        "ADBE Light Color",
+        "ADBE Light Cone Angle",
+        "ADBE Light Cone Feather",
+        "ADBE Light Falloff Type",
+        "ADBE Light Falloff Start",
+        "ADBE Light Falloff Distance",
+        "ADBE Casts Shadows",
+        "ADBE Light Shadow Darkness",
+        "ADBE Light Shadow Diffusion",
+        "ADBE Light Radius",
+    ]),
+];
+
+/// What may go in the contents of a shape layer or shape group
+const SHAPE_CONTENTS: &[&str] = &[
+    "ADBE Vector Group",
+    "ADBE Vector Shape - Rect",
+    "ADBE Vector Shape - Ellipse",
+    "ADBE Vector Shape - Star",
+    "ADBE Vector Shape - Group",
+    "ADBE Vector Graphic - Fill",
+    "ADBE Vector Graphic - Stroke",
+    "ADBE Vector Graphic - G-Fill",
+    "ADBE Vector Graphic - G-Stroke",
+    "ADBE Vector Filter - Merge",
+    "ADBE Vector Filter - Offset",
+    "ADBE Vector Filter - PB",
+    "ADBE Vector Filter - Repeater",
+    "ADBE Vector Filter - RC",
+    "ADBE Vector Filter - Trim",
+    "ADBE Vector Filter - Twist",
+    "ADBE Vector Filter - Roughen",
+    "ADBE Vector Filter - Wiggler",
+    "ADBE Vector Filter - Zigzag",
+];
+
+/// The match names an owner may contain, or `None` when its contents are
+/// not listed
+pub fn get_property_children(owner: &str) -> Option<&'static [&'static str]> {
+    PROPERTY_TREE.iter().find(|(name, _)| *name == owner).map(|(_, children)| *children)
+}
+
+/// The owners a match name may be found in, empty for names not in the tree
+pub fn get_property_parents(match_name: &str) -> Vec<&'static str> {
+    PROPERTY_TREE.iter()
+        .filter(|(_, children)| children.contains(&match_name))
+        .map(|(owner, _)| *owner)
+        .collect()
+}
@@ -0,0 +1,55 @@
+/// An English menu command name scripts commonly look up by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MenuCommand {
+    /// Name as shown in the English menus, without a trailing ellipsis
+    pub name: &'static str,
+    /// Command ID for `app.executeCommand`, where it has been stable across versions
+    pub id: Option<u32>,
+    /// Scripting API doing the same job, where one exists
+    pub api: Option<&'static str>,
+}
+
+const fn command(name: &'static str, id: Option<u32>, api: Option<&'static str>) -> MenuCommand {
+    MenuCommand { name, id, api }
+}
+
+/// Menu commands whose English names are passed to `app.findMenuCommandId`
+pub fn get_menu_commands() -> Vec<MenuCommand> {
+    vec![
+        // Edit
+        command("Undo", Some(16), None),
+        command("Cut", Some(18), None),
+        command("Copy", Some(19), None),
+        command("Paste", Some(20), None),
+        command("Clear", Some(21), None),
+        command("Select All", Some(23), None),
+        command("Deselect All", Some(2004), None),
+        command("Duplicate", Some(2080), Some("layer.duplicate()")),
+        command("Split Layer", Some(2158), None),
+
+        // Composition
+        command("New Composition", Some(2000), Some("app.project.items.addComp(name, width, height, pixelAspect, duration, frameRate)")),
+        command("Composition Settings", Some(2007), None),
+        command("Add to Render Queue", Some(2161), Some("app.project.renderQueue.items.add(comp)")),
+
+        // Layer
+        command("Pre-compose", Some(2071), Some("comp.layers.precompose(layerIndices, name, moveAllAttributes)")),
+        command("Create Shapes from Vector Layer", Some(3973), None),
+
+        // Animation
+        command("Convert Audio to Keyframes", Some(5015), None),
+        command("Sequence Layers", None, None),
+
+        // File
+        command("Save", None, Some("app.project.save()")),
+        command("Remove Unused Footage", None, Some("app.project.removeUnusedFootage()")),
+        command("Consolidate All Footage", None, Some("app.project.consolidateFootage()")),
+        command("Reduce Project", None, Some("app.project.reduceProject(items)")),
+    ]
+}
+
+/// Looks up a command by its English name, ignoring a trailing ellipsis
+pub fn find_menu_command(name: &str) -> Option<MenuCommand> {
+    let name = name.trim().trim_end_matches("...").trim_end_matches('…');
+    get_menu_commands().into_iter().find(|c| c.name == name)
+}
@@ -2,14 +2,24 @@ pub mod match_names {
     mod effect_match_names;
     mod layer_match_names;
     mod property_match_names;
+    mod obsolete_effects;
+    mod property_tree;
+    mod property_names;
 
-    pub use effect_match_names::get_effect_match_names;
+    pub use effect_match_names::{get_effect_group, get_effect_match_names, EFFECT_GROUPS};
     pub use layer_match_names::get_layer_match_names;
     pub use property_match_names::get_property_match_names;
+    pub use obsolete_effects::{find_obsolete_effect, ObsoleteEffect, OBSOLETE_EFFECTS};
+    pub use property_tree::{get_property_children, get_property_parents, PROPERTY_TREE};
+    pub use property_names::{canonical_match_name, canonicalize_property_path, PROPERTY_NAMES};
 }
 
 pub mod api_definitions {
     mod core_api;
     
     pub use core_api::get_core_api;
-} 
\ No newline at end of file
+}
+
+pub mod enums;
+pub mod globals;
+pub mod menu_commands;
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
 use std::fs;
 use std::path::Path;
 use serde::{Deserialize, Serialize};
 
+use crate::validation::errors::ValidatorError;
+use crate::validation::validate_script;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MethodParameter {
     pub name: String,
@@ -37,6 +39,18 @@ pub struct Class {
     pub parent_class: Option<String>,
     pub methods: HashMap<String, Method>,
     pub properties: HashMap<String, Property>,
+    /// Example snippets for the class as a whole
+    #[serde(default)]
+    pub examples: Vec<String>,
+}
+
+/// A stored example snippet the validator reports errors for
+#[derive(Debug)]
+pub struct ExampleFailure {
+    /// The class, or `Class.method()`, the example documents
+    pub location: String,
+    pub code: String,
+    pub errors: Vec<ValidatorError>,
 }
 
 #[derive(Debug)]
@@ -47,6 +61,12 @@ pub struct Documentation {
     global_functions: HashMap<String, Method>,
 }
 
+impl Default for Documentation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Documentation {
     pub fn new() -> Self {
         Documentation {
@@ -97,17 +117,14 @@ impl Documentation {
     fn load_classes<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), Box<dyn std::error::Error>> {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
-            if entry.path().extension().map_or(false, |ext| ext == "md") {
+            if entry.path().extension().is_some_and(|ext| ext == "md") {
                 let content = fs::read_to_string(entry.path())?;
                 if let Some(class) = self.parse_class_doc(&content) {
                     let class_name = class.name.clone();
                     
                     // Also add common aliases
-                    match class_name.as_str() {
-                        "Application" => {
-                            self.classes.insert("app".to_string(), class.clone());
-                        }
-                        _ => {}
+                    if class_name.as_str() == "Application" {
+                        self.classes.insert("app".to_string(), class.clone());
                     }
                     
                     self.classes.insert(class_name, class);
@@ -135,7 +152,7 @@ impl Documentation {
     fn load_effect_match_names<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), Box<dyn std::error::Error>> {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
-            if entry.path().extension().map_or(false, |ext| ext == "md") {
+            if entry.path().extension().is_some_and(|ext| ext == "md") {
                 let content = fs::read_to_string(entry.path())?;
                 self.parse_match_names(&content, "effect");
             }
@@ -146,7 +163,7 @@ impl Documentation {
     fn load_layer_match_names<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), Box<dyn std::error::Error>> {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
-            if entry.path().extension().map_or(false, |ext| ext == "md") {
+            if entry.path().extension().is_some_and(|ext| ext == "md") {
                 let content = fs::read_to_string(entry.path())?;
                 self.parse_match_names(&content, "layer");
             }
@@ -187,6 +204,7 @@ impl Documentation {
             parent_class: None,
             methods: HashMap::new(),
             properties: HashMap::new(),
+            examples: Vec::new(),
         };
 
         // Parse description
@@ -221,6 +239,13 @@ impl Documentation {
                         class.properties.insert(property.name.clone(), property);
                     }
                 }
+            } else if line.starts_with("#### Example") {
+                line_idx += 1;
+                if let Some(code) = code_block(&lines, &mut line_idx) {
+                    class.examples.push(code);
+                } else {
+                    continue;
+                }
             }
             line_idx += 1;
         }
@@ -291,6 +316,14 @@ impl Documentation {
                     method.return_type = lines[*line_idx].trim().to_string();
                 }
             }
+
+            if line.starts_with("#### Example") {
+                *line_idx += 1;
+                method.example = code_block(lines, line_idx);
+                if method.example.is_none() {
+                    continue;
+                }
+            }
             
             *line_idx += 1;
         }
@@ -492,12 +525,12 @@ impl Documentation {
                         args.len()
                     ));
                 }
-                return Ok(());
+                Ok(())
             } else {
-                return Err(format!("Method {} not found in class {}", method_name, class_name));
+                Err(format!("Method {} not found in class {}", method_name, class_name))
             }
         } else {
-            return Err(format!("Class {} not found", class_name));
+            Err(format!("Class {} not found", class_name))
         }
     }
 
@@ -557,6 +590,37 @@ impl Documentation {
         self.classes.keys().collect()
     }
 
+    /// Every stored example snippet with the class or method it documents,
+    /// sorted by location
+    pub fn examples(&self) -> Vec<(String, &str)> {
+        let mut examples = Vec::new();
+        // Aliases such as "app" share their class's examples
+        for (name, class) in self.classes.iter().filter(|(name, class)| **name == class.name) {
+            examples.extend(class.examples.iter().map(|code| (name.clone(), code.as_str())));
+            examples.extend(class.methods.values()
+                .filter_map(|method| Some((format!("{}.{}()", name, method.name), method.example.as_deref()?))));
+        }
+        examples.extend(self.global_functions.values()
+            .filter_map(|function| Some((format!("{}()", function.name), function.example.as_deref()?))));
+        examples.sort();
+        examples
+    }
+
+    /// Runs every stored example through the validator, so the documentation
+    /// cannot drift from the rules. Warnings are allowed, since examples are
+    /// often fragments of a larger script.
+    pub fn check_examples(&self) -> Vec<ExampleFailure> {
+        self.examples().into_iter()
+            .filter_map(|(location, code)| {
+                let result = validate_script(code, &location);
+                if result.errors.is_empty() {
+                    return None;
+                }
+                Some(ExampleFailure { location, code: code.to_string(), errors: result.errors })
+            })
+            .collect()
+    }
+
     pub fn get_stats(&self) -> (usize, usize, usize, usize) {
         (
             self.classes.len(),
@@ -567,9 +631,28 @@ impl Documentation {
     }
 }
 
+/// The fenced code block following `lines[*line_idx]`, leaving `line_idx`
+/// on its closing fence. Stops at the next heading when there is none.
+fn code_block(lines: &[&str], line_idx: &mut usize) -> Option<String> {
+    while *line_idx < lines.len() && !lines[*line_idx].trim().starts_with("```") {
+        if lines[*line_idx].trim().starts_with('#') {
+            return None;
+        }
+        *line_idx += 1;
+    }
+    *line_idx += 1;
+    let start = *line_idx;
+    while *line_idx < lines.len() && !lines[*line_idx].trim().starts_with("```") {
+        *line_idx += 1;
+    }
+    let code = lines.get(start..*line_idx)?.join("\n");
+    Some(code).filter(|code| !code.trim().is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn test_load_documentation() {
@@ -599,6 +682,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_check_examples() {
+        let markdown = r#"# LayerCollection object
+
+Holds the layers of a composition.
+
+#### Example
+
+```javascript
+var layers = app.project.activeItem.layers;
+alert(layers.length);
+```
+
+## Methods
+
+### LayerCollection.addSolid()
+
+#### Description
+
+Creates a new solid.
+
+#### Example
+
+```javascript
+app.project.activeItem.layers.addSolid([1, 1, 1], "BG", 1920, 1080, 1;
+```
+
+### LayerCollection.addNull()
+
+#### Returns
+
+AVLayer object.
+"#;
+        let mut doc = Documentation::new();
+        let class = doc.parse_class_doc(markdown).unwrap();
+        doc.classes.insert(class.name.clone(), class);
+
+        let locations: Vec<String> = doc.examples().into_iter().map(|(location, _)| location).collect();
+        assert_eq!(locations, vec!["LayerCollection", "LayerCollection.addSolid()"]);
+        assert!(doc.get_method_info("LayerCollection", "addNull").unwrap().example.is_none());
+
+        let failures = doc.check_examples();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].location, "LayerCollection.addSolid()");
+    }
+
     #[test]
     fn test_method_validation() {
         let doc_path = PathBuf::from("after-effects-scripting-guide/docs");
@@ -1,12 +1,17 @@
 use std::fmt;
 
+use crate::validation::errors::Suggestion;
+
 #[derive(Debug, Clone)]
 pub struct ValidationError {
     pub message: String,
     pub line: usize,
     pub column: usize,
     pub level: ErrorLevel,
-    pub suggestion: Option<String>,
+    /// Fixes for the error, most confident first
+    pub suggestions: Vec<Suggestion>,
+    /// Reference page for the API member the error is about
+    pub docs_url: Option<String>,
 }
 
 impl ValidationError {
@@ -16,9 +21,27 @@ impl ValidationError {
             line,
             column,
             level,
-            suggestion,
+            suggestions: suggestion.map(Suggestion::new).into_iter().collect(),
+            docs_url: None,
         }
     }
+
+    /// Replaces the suggestions with ranked alternatives
+    pub fn with_suggestions(mut self, mut suggestions: Vec<Suggestion>) -> Self {
+        suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        self.suggestions = suggestions;
+        self
+    }
+
+    pub fn with_docs_url(mut self, url: Option<String>) -> Self {
+        self.docs_url = url;
+        self
+    }
+
+    /// Message of the most confident suggestion
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestions.first().map(|s| s.message.as_str())
+    }
 }
 
 impl fmt::Display for ValidationError {
@@ -0,0 +1,106 @@
+//! Git pre-commit integration: `ae-validator install-hook` writes a hook that
+//! validates the staged version of every added or modified script, reporting
+//! only diagnostics on the lines being committed.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::validation::ScriptSource;
+
+/// Line identifying hooks written by `install-hook`, which may be replaced
+const HOOK_MARKER: &str = "# Installed by ae-validator install-hook";
+
+/// Extensions of the staged files the hook validates
+pub const HOOK_EXTENSIONS: &[&str] = &["jsx", "js"];
+
+/// Runs git with `args` in the current directory and returns its stdout
+fn git(args: &[&str]) -> io::Result<Vec<u8>> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("git {}: {}", args.join(" "), stderr.trim())));
+    }
+    Ok(output.stdout)
+}
+
+/// `:./path`, the index entry for a path relative to the current directory
+fn index_path(path: &str) -> String {
+    format!(":./{}", path.strip_prefix("./").unwrap_or(path))
+}
+
+/// The staged contents of `path`, decoded like a file on disk
+pub fn read_staged(path: &str) -> io::Result<ScriptSource> {
+    git(&["show", &index_path(path)]).map(|bytes| ScriptSource::decode(&bytes))
+}
+
+/// The pre-commit hook running `validator` on the staged scripts. Each file
+/// is validated as staged, against its staged diff, and the commit is
+/// rejected when any of them has errors on a changed line.
+pub fn pre_commit_hook(validator: &Path) -> String {
+    let patterns: Vec<String> = HOOK_EXTENSIONS.iter().map(|ext| format!("'*.{}'", ext)).collect();
+    format!(
+        "#!/bin/sh
+{marker}
+# Validates staged scripts; bypass with `git commit --no-verify`.
+validator={validator}
+status=0
+IFS='
+'
+for file in $(git diff --cached --name-only --diff-filter=ACMR -- {patterns}); do
+    git diff --cached --no-color --no-ext-diff -- \"$file\" |
+        \"$validator\" validate --staged --diff - --errors-only \"$file\" || status=1
+done
+exit $status
+",
+        marker = HOOK_MARKER,
+        validator = shell_quote(&validator.to_string_lossy()),
+        patterns = patterns.join(" "),
+    )
+}
+
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Writes the pre-commit hook into the current repository's hooks
+/// directory, honouring `core.hooksPath`. An existing hook is only replaced
+/// when `install-hook` wrote it or `force` is set.
+pub fn install_pre_commit_hook(validator: &Path, force: bool) -> Result<PathBuf, String> {
+    let hooks_dir = git(&["rev-parse", "--git-path", "hooks"])
+        .map(|out| PathBuf::from(String::from_utf8_lossy(&out).trim()))
+        .map_err(|e| format!("Not in a git repository: {}", e))?;
+    let hook = hooks_dir.join("pre-commit");
+
+    if let Ok(existing) = fs::read_to_string(&hook) {
+        if !force && !existing.contains(HOOK_MARKER) {
+            return Err(format!("{} already exists; use --force to replace it", hook.display()));
+        }
+    }
+    let write = fs::create_dir_all(&hooks_dir).and_then(|_| fs::write(&hook, pre_commit_hook(validator)));
+    write.map_err(|e| format!("Failed to write {}: {}", hook.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to make {} executable: {}", hook.display(), e))?;
+    }
+    Ok(hook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pre_commit_hook() {
+        let hook = pre_commit_hook(Path::new("/opt/it's here/ae-validator"));
+        assert!(hook.starts_with("#!/bin/sh\n# Installed by ae-validator install-hook\n"));
+        assert!(hook.contains("validator='/opt/it'\\''s here/ae-validator'\n"));
+        assert!(hook.contains("--diff-filter=ACMR -- '*.jsx' '*.js'); do"));
+        assert!(hook.contains("\"$validator\" validate --staged --diff - --errors-only \"$file\" || status=1"));
+
+        assert_eq!(index_path("./src/ui.jsx"), ":./src/ui.jsx");
+    }
+}
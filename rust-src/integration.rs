@@ -0,0 +1,390 @@
+//! End-to-end runner that executes validated scripts in a locally installed
+//! After Effects. Enabled with the `integration` feature.
+//!
+//! Scripts are validated first, then wrapped so that `$.writeln` output and
+//! uncaught errors go to a log file, and launched with `AfterFX.exe -r` on
+//! Windows or `osascript ... DoScriptFile` on macOS. The runner waits for the
+//! wrapper's completion marker and returns the captured lines, which
+//! [`IntegrationHarness`] compares with `<name>.expected.txt` next to each
+//! `.jsx` fixture.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+use crate::codegen::js_string;
+use crate::validation::{validate_script, ValidatorError};
+
+/// Path to `AfterFX.exe` or the After Effects application; overrides detection
+pub const AFTERFX_ENV_VAR: &str = "AE_VALIDATOR_AFTERFX";
+
+/// Last line the wrapper writes, after the script finished or threw
+const DONE_MARKER: &str = "__AE_VALIDATOR_DONE__";
+const ERROR_PREFIX: &str = "__AE_VALIDATOR_ERROR__ ";
+
+/// How a script is handed to After Effects
+#[derive(Debug, Clone, PartialEq)]
+pub enum AeLauncher {
+    /// `AfterFX.exe -r <script>` (Windows)
+    AfterFx(PathBuf),
+    /// `osascript -e 'tell application "<name>" to DoScriptFile "<script>"'` (macOS)
+    Osascript(String),
+}
+
+impl AeLauncher {
+    /// The launcher named by `AE_VALIDATOR_AFTERFX`, or the newest installed
+    /// After Effects in the platform's default location
+    pub fn detect() -> Option<Self> {
+        if let Ok(path) = std::env::var(AFTERFX_ENV_VAR) {
+            return Some(Self::from_path(Path::new(&path)));
+        }
+        if cfg!(target_os = "macos") {
+            newest_install(Path::new("/Applications")).map(|app| Self::from_path(&app))
+        } else if cfg!(windows) {
+            newest_install(Path::new(r"C:\Program Files\Adobe"))
+                .map(|dir| Self::AfterFx(dir.join("Support Files").join("AfterFX.exe")))
+                .filter(|launcher| matches!(launcher, Self::AfterFx(exe) if exe.exists()))
+        } else {
+            None
+        }
+    }
+
+    /// `.exe` paths launch directly, anything else is treated as a macOS
+    /// application bundle and addressed by name
+    pub fn from_path(path: &Path) -> Self {
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("exe")) {
+            return Self::AfterFx(path.to_path_buf());
+        }
+        let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        Self::Osascript(name)
+    }
+
+    fn launch(&self, script: &Path) -> std::io::Result<()> {
+        let status = match self {
+            Self::AfterFx(exe) => Command::new(exe).arg("-r").arg(script).status()?,
+            Self::Osascript(app) => Command::new("osascript")
+                .arg("-e")
+                .arg(format!(
+                    "tell application {} to DoScriptFile {}",
+                    js_string(app),
+                    js_string(&script.to_string_lossy())
+                ))
+                .status()?,
+        };
+        if status.success() {
+            Ok(())
+        } else {
+            Err(std::io::Error::other(format!("launcher exited with {}", status)))
+        }
+    }
+}
+
+/// `Adobe After Effects <year>` directory with the highest year
+fn newest_install(parent: &Path) -> Option<PathBuf> {
+    let mut installs: Vec<PathBuf> = fs::read_dir(parent).ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with("Adobe After Effects")))
+        .collect();
+    installs.sort();
+    let install = installs.pop()?;
+    // macOS installs are folders holding the application bundle of the same name
+    let bundle = install.join(format!("{}.app", install.file_name()?.to_string_lossy()));
+    Some(if bundle.exists() { bundle } else { install })
+}
+
+/// Why a script could not be run
+#[derive(Debug)]
+pub enum IntegrationError {
+    /// The script has validation errors and was not run
+    Invalid(Vec<ValidatorError>),
+    /// After Effects could not be launched
+    Launch(std::io::Error),
+    /// No completion marker was written before the timeout
+    Timeout(Duration),
+}
+
+impl fmt::Display for IntegrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegrationError::Invalid(errors) => {
+                write!(f, "Script failed validation")?;
+                for error in errors {
+                    write!(f, "; {}", error.get_message())?;
+                }
+                Ok(())
+            }
+            IntegrationError::Launch(e) => write!(f, "Could not launch After Effects: {}", e),
+            IntegrationError::Timeout(timeout) => write!(f, "Script did not finish within {:?}", timeout),
+        }
+    }
+}
+
+impl std::error::Error for IntegrationError {}
+
+/// What a script wrote while running in After Effects
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunOutput {
+    /// `$.writeln` and `$.write` output, one entry per line
+    pub lines: Vec<String>,
+    /// Message of an uncaught error, if the script threw
+    pub error: Option<String>,
+}
+
+/// Runs scripts in After Effects and collects their output
+pub struct IntegrationRunner {
+    launcher: AeLauncher,
+    timeout: Duration,
+    work_dir: PathBuf,
+}
+
+impl IntegrationRunner {
+    pub fn new(launcher: AeLauncher) -> Self {
+        Self {
+            launcher,
+            timeout: Duration::from_secs(120),
+            work_dir: std::env::temp_dir().join(format!("ae-validator-integration-{}", std::process::id())),
+        }
+    }
+
+    /// Runner for the detected install, or `None` when After Effects is not
+    /// available, in which case integration tests should be skipped
+    pub fn detect() -> Option<Self> {
+        AeLauncher::detect().map(Self::new)
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_work_dir<P: AsRef<Path>>(mut self, work_dir: P) -> Self {
+        self.work_dir = work_dir.as_ref().to_path_buf();
+        self
+    }
+
+    /// Validates `script` and, if it has no errors, runs it in After Effects
+    pub fn run(&self, name: &str, script: &str) -> Result<RunOutput, IntegrationError> {
+        let result = validate_script(script, name);
+        if !result.errors.is_empty() {
+            return Err(IntegrationError::Invalid(result.errors));
+        }
+
+        fs::create_dir_all(&self.work_dir).map_err(IntegrationError::Launch)?;
+        let stem = Path::new(name).file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_else(|| "script".to_string());
+        let script_path = self.work_dir.join(format!("{}.run.jsx", stem));
+        let log_path = self.work_dir.join(format!("{}.log", stem));
+        let _ = fs::remove_file(&log_path);
+        fs::write(&script_path, wrap_script(script, &log_path)).map_err(IntegrationError::Launch)?;
+
+        self.launcher.launch(&script_path).map_err(IntegrationError::Launch)?;
+
+        let start = Instant::now();
+        loop {
+            if let Some(output) = fs::read_to_string(&log_path).ok().and_then(|log| parse_log(&log)) {
+                return Ok(output);
+            }
+            if start.elapsed() >= self.timeout {
+                return Err(IntegrationError::Timeout(self.timeout));
+            }
+            thread::sleep(Duration::from_millis(250));
+        }
+    }
+}
+
+/// Wraps a script so its console output and any uncaught error are written
+/// to `log_path`, followed by the completion marker
+pub fn wrap_script(script: &str, log_path: &Path) -> String {
+    let log = js_string(&log_path.to_string_lossy().replace('\\', "/"));
+    format!(
+        r#"(function () {{
+    var __log = new File({log});
+    __log.encoding = "UTF-8";
+    __log.open("w");
+    var __writeln = $.writeln;
+    var __write = $.write;
+    $.writeln = function () {{ __log.writeln(Array.prototype.join.call(arguments, "")); }};
+    $.write = function () {{ __log.write(Array.prototype.join.call(arguments, "")); }};
+    try {{
+        (function () {{
+{script}
+        }})();
+    }} catch (e) {{
+        __log.writeln("");
+        __log.writeln({error_prefix} + e.toString().replace(/\n/g, " "));
+    }}
+    $.writeln = __writeln;
+    $.write = __write;
+    __log.writeln({done});
+    __log.close();
+}})();
+"#,
+        log = log,
+        script = script,
+        error_prefix = js_string(ERROR_PREFIX),
+        done = js_string(DONE_MARKER),
+    )
+}
+
+/// Output of a finished run, or `None` while the marker is missing
+fn parse_log(log: &str) -> Option<RunOutput> {
+    let mut lines: Vec<&str> = log.lines().collect();
+    if lines.last().map(|l| l.trim_end()) != Some(DONE_MARKER) {
+        return None;
+    }
+    lines.pop();
+
+    let mut output = RunOutput::default();
+    if let Some(message) = lines.last().and_then(|l| l.strip_prefix(ERROR_PREFIX)) {
+        output.error = Some(message.to_string());
+        lines.pop();
+        // The wrapper starts the error on a fresh line
+        if lines.last() == Some(&"") {
+            lines.pop();
+        }
+    }
+    output.lines = lines.into_iter().map(str::to_string).collect();
+    Some(output)
+}
+
+/// A fixture whose output did not match what was expected
+#[derive(Debug)]
+pub struct IntegrationFailure {
+    pub fixture: PathBuf,
+    pub expected: Vec<String>,
+    /// Captured output, or the error that stopped the run
+    pub actual: Result<RunOutput, IntegrationError>,
+}
+
+/// Outcome of running a harness over a fixture directory
+#[derive(Debug, Default)]
+pub struct IntegrationReport {
+    pub passed: Vec<PathBuf>,
+    pub failures: Vec<IntegrationFailure>,
+}
+
+impl IntegrationReport {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panics listing expected and actual output for every failing fixture
+    pub fn assert_ok(&self) {
+        if self.is_ok() {
+            return;
+        }
+        let mut message = format!("{} integration fixture(s) failed:\n", self.failures.len());
+        for failure in &self.failures {
+            message.push_str(&format!("\n{}\nexpected:\n", failure.fixture.display()));
+            for line in &failure.expected {
+                message.push_str(&format!("  {}\n", line));
+            }
+            match &failure.actual {
+                Ok(output) => {
+                    message.push_str("actual:\n");
+                    for line in &output.lines {
+                        message.push_str(&format!("  {}\n", line));
+                    }
+                    if let Some(error) = &output.error {
+                        message.push_str(&format!("  uncaught error: {}\n", error));
+                    }
+                }
+                Err(e) => message.push_str(&format!("error: {}\n", e)),
+            }
+        }
+        panic!("{}", message);
+    }
+}
+
+/// Runs `.jsx` fixtures in After Effects and compares their output with the
+/// `<name>.expected.txt` file next to each one. A script passes when it
+/// finishes without an uncaught error and writes exactly the expected lines.
+pub struct IntegrationHarness {
+    fixtures_dir: PathBuf,
+    runner: IntegrationRunner,
+}
+
+impl IntegrationHarness {
+    pub fn new<P: AsRef<Path>>(fixtures_dir: P, runner: IntegrationRunner) -> Self {
+        Self {
+            fixtures_dir: fixtures_dir.as_ref().to_path_buf(),
+            runner,
+        }
+    }
+
+    /// Fixtures that have an expected output file, sorted by path
+    pub fn fixtures(&self) -> Vec<PathBuf> {
+        let mut fixtures: Vec<PathBuf> = WalkDir::new(&self.fixtures_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "jsx"))
+            .filter(|path| expected_path(path).exists())
+            .collect();
+        fixtures.sort();
+        fixtures
+    }
+
+    pub fn run(&self) -> std::io::Result<IntegrationReport> {
+        let mut report = IntegrationReport::default();
+
+        for fixture in self.fixtures() {
+            let script = fs::read_to_string(&fixture)?;
+            let expected: Vec<String> = fs::read_to_string(expected_path(&fixture))?
+                .lines()
+                .map(str::to_string)
+                .collect();
+            let name = fixture.strip_prefix(&self.fixtures_dir).unwrap_or(&fixture).to_string_lossy().replace('\\', "/");
+            let actual = self.runner.run(&name, &script);
+            match &actual {
+                Ok(output) if output.error.is_none() && output.lines == expected => report.passed.push(fixture),
+                _ => report.failures.push(IntegrationFailure { fixture, expected, actual }),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// `dir/name.jsx` -> `dir/name.expected.txt`
+pub fn expected_path(fixture: &Path) -> PathBuf {
+    let stem = fixture.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    fixture.with_file_name(format!("{}.expected.txt", stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapper_and_log_parsing() {
+        let wrapped = wrap_script("$.writeln(app.project.numItems);", Path::new("C:\\tmp\\out.log"));
+        assert!(wrapped.contains("new File(\"C:/tmp/out.log\")"));
+        assert!(wrapped.contains("$.writeln(app.project.numItems);"));
+
+        assert_eq!(parse_log("3\n"), None);
+        let finished = parse_log("3\nComp 1\n__AE_VALIDATOR_DONE__\n").unwrap();
+        assert_eq!(finished.lines, vec!["3", "Comp 1"]);
+        assert_eq!(finished.error, None);
+
+        let threw = parse_log("3\n\n__AE_VALIDATOR_ERROR__ Error: null is not an object\n__AE_VALIDATOR_DONE__\n").unwrap();
+        assert_eq!(threw.lines, vec!["3"]);
+        assert_eq!(threw.error.as_deref(), Some("Error: null is not an object"));
+    }
+
+    #[test]
+    fn test_launcher_from_path() {
+        assert_eq!(
+            AeLauncher::from_path(Path::new(r"C:\Program Files\Adobe\Adobe After Effects 2024\Support Files\AfterFX.exe")),
+            AeLauncher::AfterFx(PathBuf::from(r"C:\Program Files\Adobe\Adobe After Effects 2024\Support Files\AfterFX.exe"))
+        );
+        assert_eq!(
+            AeLauncher::from_path(Path::new("/Applications/Adobe After Effects 2024/Adobe After Effects 2024.app")),
+            AeLauncher::Osascript("Adobe After Effects 2024".to_string())
+        );
+    }
+}
@@ -4,6 +4,16 @@ pub mod errors;
 pub mod documentation;
 pub mod validator;
 pub mod validation;
+pub mod repl;
+pub mod testing;
+pub mod report;
+pub mod report_diff;
+pub mod codegen;
+pub mod typegen;
+pub mod typeimport;
+pub mod hooks;
+#[cfg(feature = "integration")]
+pub mod integration;
 
 #[cfg(test)]
 pub mod tests;
@@ -15,6 +25,14 @@ pub use api::{
     methods::MethodValidation,
     properties::PropertyValidation,
     documentation::ApiDocumentation,
+    ApiBuilder,
+    ApiFamily,
+    UnifiedApiBuilder,
 };
-pub use validator::ScriptValidator;
+pub use validator::{CancellationToken, ScriptValidator, ValidateOptions};
+pub use report::ValidationReport;
+pub use validation::{ValidationSession, Workspace};
+pub use repl::Repl;
+pub use codegen::{ScriptBuilder, CompBuilder, LayerBuilder, KeyframeBuilder, CodegenError};
+pub use testing::{SnapshotHarness, SnapshotMode, SnapshotReport};
 pub use errors::{ValidationError, ErrorLevel}; 
\ No newline at end of file
@@ -1,42 +1,1036 @@
 use std::fs;
-use ae_script_validator::ScriptValidator;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use ae_script_validator::{Repl, ScriptValidator, UnifiedApi, ValidateOptions, ValidationReport, Workspace};
+use ae_script_validator::api::audit::audit_model;
+use ae_script_validator::api::ApiModel;
+use ae_script_validator::documentation::Documentation;
+use ae_script_validator::hooks::{install_pre_commit_hook, read_staged};
+use ae_script_validator::typegen::generate_declarations;
+use ae_script_validator::typeimport::{merge_declarations, parse_declarations, DeclaredClass};
+use ae_script_validator::report::{RuleStats, DEFAULT_STATS_FILE};
+use ae_script_validator::report_diff::{diagnostics_from_json, ReportDiff};
+use ae_script_validator::validation::errors::TerminalRenderer;
+use ae_script_validator::validation::{
+    format_script, is_jsxbin, validate_duplicate_globals, IncludeGraph, read_script, write_script, validate_against_manifest, collect_script_diagnostics, apply_config, ApiUsage, CallGraph, ChangedLines, Environment, ExpressionReport, FormatOptions,
+    migrate_track_matte, fix_color_ranges, fix_suppressed_dialogs, fix_obsolete_effects, validate_footage_paths, HeadlessReport, PathResolver, PeakAllocator, Platform, Locale, MessageCatalog, ProjectManifest, Profiler, QuoteStyle, ScriptMetrics, ScriptSource, SourceMap, ValidatorConfig,
+};
+
+// Lets --profile report each pass's peak memory
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Validates After Effects ExtendScript. Without a command, arguments are
+/// passed to `validate`, so `ae-validator script.jsx` keeps working.
+#[derive(Parser)]
+#[command(name = "ae-validator", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
+enum Command {
+    /// Validate a script
+    Validate(ValidateArgs),
+    /// Analyze an expression file, or an expression given inline
+    Expr {
+        /// Expression file, or the expression source itself
+        input: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show the API model's entry for a class or `Class.member`
+    Docs {
+        #[arg(required_unless_present = "check_examples")]
+        name: Option<String>,
+        /// Validate every example snippet in the scripting guide instead
+        #[arg(long)]
+        check_examples: bool,
+        /// Scripting guide docs directory, for --check-examples
+        #[arg(long, default_value = "after-effects-scripting-guide/docs")]
+        docs_dir: PathBuf,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Search classes, members, enum members and match names
+    Search {
+        query: String,
+        /// Maximum number of results
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Format a script
+    Fmt(FmtArgs),
+    /// Explain what a rule checks and why
+    Explain {
+        rule: String,
+        #[arg(long, default_value = "en")]
+        locale: Locale,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Print the script's call graph
+    Graph {
+        script: String,
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+    /// List the API members a script uses
+    ApiUsage {
+        script: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// List dialogs that block headless runs
+    Dialogs {
+        script: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Explore the API model interactively
+    Repl,
+    /// Print size and complexity metrics
+    Metrics {
+        script: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Rewrite trackMatteType assignments for the AE 2023 track matte API
+    Migrate {
+        script: String,
+        /// Rewrite the file instead of printing the result
+        #[arg(long)]
+        write: bool,
+    },
+    /// Apply safe autofixes: 0-255 color literals are divided by 255 and
+    /// suppressed dialogs are ended in a finally block
+    Fix {
+        script: String,
+        /// Rewrite the file instead of printing the result
+        #[arg(long)]
+        write: bool,
+    },
+    /// Compare two JSON reports
+    DiffResults {
+        old: String,
+        new: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Generate TypeScript declarations from the API model
+    Types {
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Merge .d.ts declarations into the model and report what changed
+    ImportTypes {
+        #[arg(required = true)]
+        files: Vec<String>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Write the API model as JSON, for diffing between versions and external tools
+    DumpModel {
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Report inconsistencies in the API model
+    AuditModel {
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Show or reset the rule statistics recorded with `validate --record-stats`
+    Stats {
+        #[command(subcommand)]
+        action: StatsAction,
+    },
+    /// Show the configuration that applies to a script
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Install a git pre-commit hook validating the changed lines of staged scripts
+    InstallHook {
+        /// Replace an existing pre-commit hook
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsAction {
+    /// Print how often each rule fired, most frequent first
+    Show {
+        #[arg(long, default_value = DEFAULT_STATS_FILE)]
+        file: PathBuf,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Delete the recorded statistics
+    Reset {
+        #[arg(long, default_value = DEFAULT_STATS_FILE)]
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the effective configuration for a script and the .auteurrc files it merges
+    Show {
+        script: String,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+#[derive(Args)]
+struct ValidateArgs {
+    script: String,
+    /// Use the strict rule profile
+    #[arg(long)]
+    strict: bool,
+    /// Collapse related diagnostics
+    #[arg(long)]
+    group: bool,
+    /// Configuration file; .auteurrc is discovered from the script's directory otherwise
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Project manifest to check comp and layer references against
+    #[arg(long)]
+    manifest: Option<String>,
+    /// interactive, aerender or panel
+    #[arg(long)]
+    environment: Option<Environment>,
+    /// en, ja, de or fr
+    #[arg(long)]
+    locale: Option<Locale>,
+    /// windows or macos; repeat for scripts run on both
+    #[arg(long = "platform")]
+    platforms: Vec<Platform>,
+    /// Check that footage paths exist
+    #[arg(long)]
+    check_paths: bool,
+    /// Resolve paths starting with <prefix> under <dir>
+    #[arg(long = "path-root", value_name = "PREFIX=DIR", value_parser = parse_path_root)]
+    path_roots: Vec<(String, String)>,
+    /// Skip compiled .jsxbin files instead of failing
+    #[arg(long)]
+    allow_jsxbin: bool,
+    /// Source map for bundled scripts
+    #[arg(long)]
+    source_map: Option<String>,
+    /// Library script whose namespaces are loaded alongside this one
+    #[arg(long = "lib")]
+    libraries: Vec<String>,
+    /// .d.ts file adding members the model lacks
+    #[arg(long = "types")]
+    declaration_files: Vec<String>,
+    /// Print per-rule statistics
+    #[arg(long)]
+    stats: bool,
+    /// Print per-pass timings
+    #[arg(long)]
+    profile: bool,
+    /// Stop starting API model passes after this many seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    #[command(flatten)]
+    output: OutputArgs,
+    /// Exit with 0 even when there are errors
+    #[arg(long)]
+    ignore_errors: bool,
+    /// Leave out warnings and infos
+    #[arg(long)]
+    errors_only: bool,
+    /// Report every diagnostic, ignoring max_reports_per_rule from the config
+    #[arg(long)]
+    all_findings: bool,
+    /// Print diagnostic counts per rule instead of each diagnostic
+    #[arg(long, conflicts_with = "quiet")]
+    by_rule: bool,
+    /// Print only a one-line verdict per file
+    #[arg(long, short = 'q')]
+    quiet: bool,
+    /// Report only diagnostics on lines this unified diff adds (`-` for stdin)
+    #[arg(long, value_name = "DIFF")]
+    diff: Option<String>,
+    /// Report only diagnostics on these lines, such as `src/ui.jsx:10-24,31`
+    #[arg(long = "changed-lines", value_name = "FILE:RANGES")]
+    changed_lines: Vec<String>,
+    /// Validate the version of the script staged in the git index
+    #[arg(long)]
+    staged: bool,
+    /// Add this run's rule hits to a local statistics file
+    #[arg(long, value_name = "FILE", num_args = 0..=1, require_equals = true, default_missing_value = DEFAULT_STATS_FILE)]
+    record_stats: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct FmtArgs {
+    script: String,
+    /// Spaces per indentation level
+    #[arg(long, default_value_t = FormatOptions::default().indent_width)]
+    indent: usize,
+    /// Indent with tabs
+    #[arg(long)]
+    tabs: bool,
+    #[arg(long, value_enum, default_value_t = Quotes::Preserve)]
+    quotes: Quotes,
+    /// Rewrite the file instead of printing the result
+    #[arg(long)]
+    write: bool,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// `--format text|json`, shared by every command with structured output
+#[derive(Args)]
+struct OutputArgs {
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Same as --format json
+    #[arg(long, hide = true)]
+    json: bool,
+}
+
+impl OutputArgs {
+    fn is_json(&self) -> bool {
+        self.json || self.format == OutputFormat::Json
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum GraphFormat {
+    Dot,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Quotes {
+    Single,
+    Double,
+    Preserve,
+}
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Usage: {} <script_file>", args[0]);
-        std::process::exit(1);
+    let cli = Cli::parse_from(with_default_command(std::env::args().collect()));
+    match cli.command {
+        Command::Validate(args) => run_validate(&args),
+        Command::Expr { input, output } => run_expr(&input, &output),
+        Command::Docs { check_examples: true, docs_dir, output, .. } => run_check_examples(&docs_dir, &output),
+        Command::Docs { name, output, .. } => run_docs(name.as_deref().unwrap_or_default(), &output),
+        Command::Search { query, limit, output } => run_search(&query, limit, &output),
+        Command::Fmt(args) => run_fmt(&args),
+        Command::Explain { rule, locale, output } => run_explain(&rule, locale, &output),
+        Command::Graph { script, format } => run_graph(&script, format),
+        Command::ApiUsage { script, output } => run_api_usage(&script, &output),
+        Command::Dialogs { script, output } => run_dialogs(&script, &output),
+        Command::Repl => {
+            let stdin = std::io::stdin();
+            if let Err(e) = Repl::new().run(stdin.lock(), std::io::stdout()) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Metrics { script, output } => run_metrics(&script, &output),
+        Command::Migrate { script, write } => run_migrate(&script, write),
+        Command::Fix { script, write } => run_fix(&script, write),
+        Command::DiffResults { old, new, output } => run_diff_results(&old, &new, &output),
+        Command::Types { out } => run_types(out.as_deref()),
+        Command::ImportTypes { files, output } => run_import_types(&files, &output),
+        Command::DumpModel { out } => run_dump_model(out.as_deref()),
+        Command::AuditModel { output } => run_audit_model(&output),
+        Command::Stats { action: StatsAction::Show { file, output } } => run_stats_show(&file, &output),
+        Command::Stats { action: StatsAction::Reset { file } } => run_stats_reset(&file),
+        Command::Config { action: ConfigAction::Show { script, output } } => run_config_show(&script, &output),
+        Command::InstallHook { force } => run_install_hook(force),
     }
+}
 
-    let ignore_errors = args.contains(&"--ignore-errors".to_string());
-    let script_path = if ignore_errors {
-        &args[2]
-    } else {
-        &args[1]
+/// Inserts `validate` when the first argument is not a command, so flags
+/// and the script path can come in any order without naming the command
+fn with_default_command(mut args: Vec<String>) -> Vec<String> {
+    let first = match args.get(1) {
+        Some(first) => first.as_str(),
+        None => return args,
     };
+    let is_command = Cli::command().get_subcommands().any(|c| c.get_name() == first)
+        || matches!(first, "help" | "-h" | "--help" | "-V" | "--version");
+    if !is_command {
+        args.insert(1, "validate".to_string());
+    }
+    args
+}
+
+fn parse_path_root(value: &str) -> Result<(String, String), String> {
+    value.split_once('=')
+        .map(|(prefix, dir)| (prefix.to_string(), dir.to_string()))
+        .ok_or_else(|| "expected <prefix>=<dir>".to_string())
+}
 
-    let script = match fs::read_to_string(script_path) {
-        Ok(content) => content,
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    value.parse::<f64>().ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| "expected a number of seconds".to_string())
+}
+
+/// Folder whose `.auteurrc` files apply to `script`
+fn script_dir(script: &str) -> &Path {
+    match Path::new(script).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// Reads a script or exits with the error
+fn read_script_or_exit(path: &str) -> String {
+    read_source_or_exit(path).text
+}
+
+/// Reads a script, keeping how it was stored for writing it back, or exits
+/// with the error
+fn read_source_or_exit(path: &str) -> ScriptSource {
+    match read_script(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Error reading file: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_validate(args: &ValidateArgs) {
+    let source = if args.staged { read_staged(&args.script) } else { read_script(&args.script) };
+    let source = match source {
+        Ok(source) => source,
         Err(e) => {
             eprintln!("Error reading file: {}", e);
             std::process::exit(1);
         }
     };
+    let script = source.text.clone();
 
+    let loaded = match &args.config {
+        Some(file) => ValidatorConfig::load(file),
+        None => Workspace::new(script_dir(&args.script)).config_for(&args.script).map(|config| (*config).clone()),
+    };
+    let mut config = match loaded {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if args.strict {
+        config.profile = Some("strict".to_string());
+    }
+    if let Some(environment) = args.environment {
+        config.environment = environment;
+    }
+    if args.group {
+        config.group_related = true;
+    }
+    if let Some(locale) = args.locale {
+        config.locale = locale;
+    }
+    if !args.platforms.is_empty() {
+        config.platforms = args.platforms.clone();
+    }
+    config.path_roots.extend(args.path_roots.iter().cloned());
+    if args.allow_jsxbin {
+        config.allow_jsxbin = true;
+    }
+
+    // Compiled files only get the pipeline's jsxbin diagnostic
+    let compiled = is_jsxbin(&script);
     let mut validator = ScriptValidator::new();
-    match validator.validate_script(&script) {
-        Ok(_) => println!("Script validation successful!"),
-        Err(errors) => {
-            for error in errors {
-                eprintln!("Validation error at line {}, column {}: {}", error.line, error.column, error.message);
-                if let Some(suggestion) = error.suggestion {
-                    eprintln!("\nSuggestion: {}\n", suggestion);
-                }
+    // Members the model lacks, from .d.ts files; conflicts keep the model's view
+    if !args.declaration_files.is_empty() {
+        merge_declarations(validator.api_mut(), &read_declarations(&args.declaration_files));
+    }
+    // Namespaces from --lib scripts loaded alongside this one and from
+    // included files; includes that do not resolve locally are skipped
+    for library in &args.libraries {
+        match read_script(library) {
+            Ok(library) => validator.register_modules(&library.text),
+            Err(e) => {
+                eprintln!("Error reading library {}: {}", library, e);
+                std::process::exit(1);
             }
-            if !ignore_errors {
+        }
+    }
+    let includes = IncludeGraph::resolve(&args.script, &script);
+    for included in includes.files.iter().filter(|file| file.name != args.script) {
+        validator.register_modules(&included.text);
+    }
+    let mut report = if compiled {
+        ValidationReport::new(None)
+    } else {
+        let mut options = ValidateOptions::default();
+        if let Some(timeout) = args.timeout {
+            options = options.with_timeout(timeout);
+        }
+        validator.validate_script_with(&script, &options)
+    };
+
+    let mut profiler = Profiler::new();
+    for pass in &report.pass_timings {
+        profiler.record(&format!("legacy/{}", pass.name), pass.duration);
+    }
+    let start = Instant::now();
+    let mut result = collect_script_diagnostics(&script, &args.script, &config, &mut profiler);
+    report.record_pass("pipeline", start.elapsed());
+    if let Some(path) = args.manifest.as_ref().filter(|_| !compiled) {
+        let manifest = match ProjectManifest::load(path) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                eprintln!("{}", e);
                 std::process::exit(1);
             }
+        };
+        let start = Instant::now();
+        for error in validate_against_manifest(&script, &args.script, &manifest) {
+            result.add_error(error);
+        }
+        report.record_pass("manifest", start.elapsed());
+        profiler.record("manifest", start.elapsed());
+    }
+    if args.check_paths && !compiled {
+        let start = Instant::now();
+        let resolver = PathResolver::new(script_dir(&args.script)).with_roots(&config.path_roots);
+        for error in validate_footage_paths(&script, &args.script, &resolver) {
+            result.add_error(error);
+        }
+        report.record_pass("paths", start.elapsed());
+        profiler.record("paths", start.elapsed());
+    }
+    // Profile, grouping and localization cover the manifest and path passes too
+    if !compiled {
+        if let Err(e) = apply_config(&mut result, &config) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+    if includes.files.len() > 1 {
+        let start = Instant::now();
+        for error in validate_duplicate_globals(&includes) {
+            result.add_error(error);
+        }
+        report.record_pass("includes", start.elapsed());
+        profiler.record("includes", start.elapsed());
+    }
+    report.merge_result(result);
+    report.extend(source.diagnostics(&args.script));
+    report.set_file(&args.script);
+
+    // Bundled scripts: report against the original modules, from an explicit
+    // map or from the `//# sourceURL=` markers a concatenating build leaves
+    let source_map = match &args.source_map {
+        Some(path) => match SourceMap::load(path) {
+            Ok(map) => Some(map),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        None => SourceMap::from_source_urls(&script),
+    };
+    if let Some(map) = &source_map {
+        report.apply_source_map(map, &script);
+    }
+    // Statistics cover every diagnostic, before output filters narrow the report
+    if let Some(file) = &args.record_stats {
+        let recorded = RuleStats::load(file).and_then(|mut stats| {
+            stats.record(&report);
+            stats.save(file)
+        });
+        if let Err(e) = recorded {
+            eprintln!("Failed to record rule statistics: {}", e);
+        }
+    }
+    // Whole files are still validated so that changed lines are checked in context
+    if args.diff.is_some() || !args.changed_lines.is_empty() {
+        let changed = args.diff.iter()
+            .map(|path| ChangedLines::load_diff(path))
+            .chain(args.changed_lines.iter().map(|spec| ChangedLines::parse_ranges(spec)))
+            .try_fold(ChangedLines::new(), |mut all, changed| {
+                all.merge(changed?);
+                Ok::<_, String>(all)
+            });
+        match changed {
+            Ok(changed) => report.retain_changed(&changed),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if args.errors_only {
+        report.retain_errors();
+    }
+    if let Some(max) = config.max_reports_per_rule.filter(|_| !args.all_findings) {
+        report.limit_per_rule(max);
+    }
+
+    if args.output.is_json() {
+        println!("{}", report.to_json());
+    } else if args.quiet {
+        println!("{}", report.verdict());
+    } else if args.by_rule {
+        eprint!("{}", report.rule_summary());
+        eprintln!("{}", report.summary());
+    } else {
+        let renderer = TerminalRenderer::for_stderr();
+        // Remapped lines and included files do not index into the script; render their snippets instead
+        for diagnostic in report.diagnostics() {
+            let in_script = diagnostic.get_context().file.as_deref() == Some(args.script.as_str());
+            let rendered_source = if source_map.is_none() && in_script { script.as_str() } else { "" };
+            eprintln!("{}", renderer.render(diagnostic, rendered_source));
+        }
+        for note in report.truncation_notes() {
+            eprintln!("{}", note);
+        }
+        eprintln!("{}", report.summary());
+        if args.stats {
+            eprint!("{}", report.stats_table());
+        }
+    }
+    if args.profile {
+        eprint!("{}", profiler.to_table());
+    }
+
+    if report.is_valid() {
+        if !args.output.is_json() && !args.quiet {
+            println!("Script validation successful!");
         }
+    } else if !args.ignore_errors {
+        std::process::exit(1);
+    }
+}
+
+
+fn run_fmt(args: &FmtArgs) {
+    let options = FormatOptions {
+        indent_width: args.indent,
+        use_tabs: args.tabs,
+        quote_style: match args.quotes {
+            Quotes::Single => QuoteStyle::Single,
+            Quotes::Double => QuoteStyle::Double,
+            Quotes::Preserve => QuoteStyle::Preserve,
+        },
+        ..FormatOptions::default()
+    };
+
+    let source = read_source_or_exit(&args.script);
+    let script = &source.text;
+
+    let formatted = format_script(script, &options);
+    if args.write {
+        if let Err(e) = write_script(&args.script, &source, &formatted) {
+            eprintln!("Error writing file: {}", e);
+            std::process::exit(1);
+        }
+    }
+    if args.output.is_json() {
+        let printed = if args.write { None } else { Some(&formatted) };
+        let summary = serde_json::json!({
+            "file": args.script,
+            "changed": &formatted != script,
+            "formatted": printed,
+        });
+        println!("{}", serde_json::to_string_pretty(&summary).unwrap_or_default());
+    } else if !args.write {
+        print!("{}", formatted);
+    }
+}
+
+fn run_migrate(script_path: &str, write_in_place: bool) {
+    let source = read_source_or_exit(script_path);
+    let script = &source.text;
+
+    let (migrated, manual) = migrate_track_matte(script);
+    if write_in_place {
+        if let Err(e) = write_script(script_path, &source, &migrated) {
+            eprintln!("Error writing file: {}", e);
+            std::process::exit(1);
+        }
+    } else {
+        print!("{}", migrated);
+    }
+    for migration in &manual {
+        eprintln!(
+            "{}:{}:{}: manual review needed for '{}.trackMatteType': {}",
+            script_path, migration.line, migration.column, migration.receiver,
+            migration.reason.as_deref().unwrap_or("matte layer unknown")
+        );
+    }
+    if !manual.is_empty() {
+        std::process::exit(2);
+    }
+}
+
+fn run_fix(script_path: &str, write_in_place: bool) {
+    let source = read_source_or_exit(script_path);
+    let script = &source.text;
+
+    let (fixed, colors) = fix_color_ranges(script);
+    let (fixed, suppressions) = fix_suppressed_dialogs(&fixed);
+    let (fixed, effects) = fix_obsolete_effects(&fixed);
+    if write_in_place {
+        if let Err(e) = write_script(script_path, &source, &fixed) {
+            eprintln!("Error writing file: {}", e);
+            std::process::exit(1);
+        }
+    } else {
+        print!("{}", fixed);
+    }
+    eprintln!(
+        "{}: fixed {} color literal(s), wrapped {} dialog suppression(s) in try/finally, replaced {} obsolete effect(s)",
+        script_path, colors, suppressions, effects
+    );
+}
+
+fn run_dialogs(script_path: &str, output: &OutputArgs) {
+    let script = read_script_or_exit(script_path);
+    let report = HeadlessReport::analyze(&script, script_path);
+    if output.is_json() {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.to_text());
+    }
+    if !report.is_headless_safe() {
+        std::process::exit(2);
+    }
+}
+
+fn run_expr(input: &str, output: &OutputArgs) {
+    // A path to a snippet file, or the expression itself
+    let source = if Path::new(input).is_file() {
+        read_script_or_exit(input)
+    } else {
+        input.to_string()
+    };
+
+    let report = ExpressionReport::analyze(&source);
+    if output.is_json() {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.to_text());
+    }
+    if !report.is_valid() {
+        std::process::exit(1);
+    }
+}
+
+/// Prints a class's members, or one member, from the API model
+fn run_docs(name: &str, output: &OutputArgs) {
+    let api = UnifiedApi::shared();
+    let (class, member) = match name.split_once('.') {
+        Some((class, member)) => (class, Some(member)),
+        None => (name, None),
+    };
+    let object = match api.object(class) {
+        Some(object) => object,
+        None => {
+            eprintln!("Unknown class '{}'", class);
+            std::process::exit(1);
+        }
+    };
+    let class = api.canonical_name(class).unwrap_or(class);
+    if let Some(member) = member.filter(|m| !object.methods.contains_key(*m) && !object.properties.contains_key(*m)) {
+        eprintln!("{} has no member '{}'", class, member);
+        std::process::exit(1);
+    }
+
+    if output.is_json() {
+        let wanted = |name: &str| member.is_none_or(|m| m == name);
+        let mut methods: Vec<_> = object.methods.iter()
+            .filter(|(name, _)| wanted(name))
+            .map(|(name, method)| serde_json::json!({
+                "name": name,
+                "params": method.param_count,
+                "param_types": method.param_types.iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>(),
+                "returns": api.method_return_type(class, name),
+            }))
+            .collect();
+        methods.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        let mut properties: Vec<_> = object.properties.iter()
+            .filter(|(name, _)| wanted(name))
+            .map(|(name, rule)| serde_json::json!({
+                "name": name,
+                "type": format!("{:?}", rule.value_type),
+                "min": rule.range_min,
+                "max": rule.range_max,
+            }))
+            .collect();
+        properties.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        let docs = serde_json::json!({ "class": class, "methods": methods, "properties": properties });
+        println!("{}", serde_json::to_string_pretty(&docs).unwrap_or_default());
+        return;
+    }
+
+    let mut repl = Repl::new();
+    match member {
+        Some(member) => println!("{}", repl.eval(&format!("{}.{}", class, member))),
+        None => {
+            println!("{}\n\nMethods:\n{}", class, repl.eval(&format!("{}.methods", class)));
+            println!("\nProperties:\n{}", repl.eval(&format!("{}.properties", class)));
+        }
+    }
+}
+
+fn run_check_examples(docs_dir: &Path, output: &OutputArgs) {
+    let docs = match Documentation::load_from_directory(docs_dir) {
+        Ok(docs) => docs,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", docs_dir.display(), e);
+            std::process::exit(1);
+        }
+    };
+    let checked = docs.examples().len();
+    let failures = docs.check_examples();
+    if output.is_json() {
+        let failures: Vec<_> = failures.iter()
+            .map(|failure| serde_json::json!({
+                "location": failure.location,
+                "code": failure.code,
+                "errors": failure.errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+            }))
+            .collect();
+        let report = serde_json::json!({ "checked": checked, "failures": failures });
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    } else {
+        for failure in &failures {
+            println!("{}:", failure.location);
+            for error in &failure.errors {
+                println!("  {}", error);
+            }
+        }
+        println!("{} of {} examples failed validation", failures.len(), checked);
+    }
+    if !failures.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn run_search(query: &str, limit: usize, output: &OutputArgs) {
+    let api = UnifiedApi::shared();
+    let hits = api.search(query, limit);
+    if output.is_json() {
+        let hits: Vec<_> = hits.iter()
+            .map(|hit| serde_json::json!({
+                "name": hit.entry.qualified_name(),
+                "kind": format!("{:?}", hit.entry.kind),
+                "distance": hit.distance,
+            }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&hits).unwrap_or_default());
+    } else if hits.is_empty() {
+        println!("No matches for '{}'", query);
+    } else {
+        for hit in &hits {
+            println!("{:<40} {:?}", hit.entry.qualified_name(), hit.entry.kind);
+        }
+    }
+}
+
+fn run_api_usage(script_path: &str, output: &OutputArgs) {
+    let script = read_script_or_exit(script_path);
+    let usage = ApiUsage::analyze(&script, &UnifiedApi::shared());
+    if output.is_json() {
+        println!("{}", usage.to_json());
+    } else {
+        print!("{}", usage.to_table());
+    }
+}
+
+fn run_diff_results(old: &str, new: &str, output: &OutputArgs) {
+    let read = |path: &str| {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Error reading {}: {}", path, e);
+                std::process::exit(1);
+            }
+        };
+        match diagnostics_from_json(&content) {
+            Ok(diagnostics) => diagnostics,
+            Err(e) => {
+                eprintln!("Error parsing {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let diff = ReportDiff::compare(&read(old), &read(new));
+    if output.is_json() {
+        println!("{}", diff.to_json());
+    } else {
+        print!("{}", diff.to_text());
+    }
+    if !diff.introduced.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn run_types(out: Option<&Path>) {
+    let declarations = generate_declarations(&UnifiedApi::shared());
+    match out {
+        None => print!("{}", declarations),
+        Some(path) => {
+            if let Err(e) = fs::write(path, declarations) {
+                eprintln!("Error writing {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn run_dump_model(out: Option<&Path>) {
+    let json = ApiModel::from_api(&UnifiedApi::shared()).to_json();
+    match out {
+        None => println!("{}", json),
+        Some(path) => {
+            if let Err(e) = fs::write(path, json + "\n") {
+                eprintln!("Error writing {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn run_audit_model(output: &OutputArgs) {
+    let audit = audit_model(&UnifiedApi::shared());
+    if output.is_json() {
+        println!("{}", audit.to_json());
+    } else {
+        print!("{}", audit.to_text());
+    }
+}
+
+fn run_stats_show(file: &Path, output: &OutputArgs) {
+    let stats = match RuleStats::load(file) {
+        Ok(stats) => stats,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if output.is_json() {
+        println!("{}", stats.to_json());
+    } else {
+        print!("{}", stats.to_table());
+    }
+}
+
+fn run_stats_reset(file: &Path) {
+    if let Err(e) = RuleStats::reset(file) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_config_show(script: &str, output: &OutputArgs) {
+    let (config, sources) = match ValidatorConfig::cascade(script_dir(script)) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let config = serde_json::to_value(&config).unwrap_or_default();
+    if output.is_json() {
+        let sources: Vec<String> = sources.iter().map(|path| path.display().to_string()).collect();
+        println!("{}", serde_json::json!({ "sources": sources, "config": config }));
+        return;
+    }
+    if sources.is_empty() {
+        println!("# No .auteurrc applies; showing the defaults");
+    }
+    for source in &sources {
+        println!("# {}", source.display());
+    }
+    println!("{}", serde_json::to_string_pretty(&config).unwrap_or_default());
+}
+
+fn run_install_hook(force: bool) {
+    let validator = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("ae-validator"));
+    match install_pre_commit_hook(&validator, force) {
+        Ok(hook) => println!("Installed {}", hook.display()),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_import_types(files: &[String], output: &OutputArgs) {
+    let mut validator = ScriptValidator::new();
+    let report = merge_declarations(validator.api_mut(), &read_declarations(files));
+    if output.is_json() {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.to_text());
+    }
+}
+
+fn read_declarations(files: &[String]) -> Vec<DeclaredClass> {
+    let mut classes = Vec::new();
+    for file in files {
+        match fs::read_to_string(file) {
+            Ok(source) => classes.extend(parse_declarations(&source)),
+            Err(e) => {
+                eprintln!("Error reading {}: {}", file, e);
+                std::process::exit(1);
+            }
+        }
+    }
+    classes
+}
+
+fn run_graph(script_path: &str, format: GraphFormat) {
+    let script = read_script_or_exit(script_path);
+    let graph = CallGraph::build(&script);
+    match format {
+        GraphFormat::Json => println!("{}", graph.to_json()),
+        GraphFormat::Dot => print!("{}", graph.to_dot()),
+    }
+}
+
+fn run_metrics(script_path: &str, output: &OutputArgs) {
+    let script = read_script_or_exit(script_path);
+    let metrics = ScriptMetrics::analyze(&script);
+    if output.is_json() {
+        println!("{}", metrics.to_json());
+    } else {
+        print!("{}", metrics.to_table());
+    }
+}
+
+fn run_explain(rule: &str, locale: Locale, output: &OutputArgs) {
+    let explanation = match MessageCatalog::for_locale(locale).explanation(rule) {
+        Some(explanation) => explanation,
+        None => {
+            eprintln!("No explanation available for rule '{}'", rule);
+            std::process::exit(1);
+        }
+    };
+    if output.is_json() {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "rule": rule, "explanation": explanation })).unwrap_or_default());
+    } else {
+        println!("{}: {}", rule, explanation);
     }
 }
 
@@ -71,4 +1065,28 @@ mod tests {
         "#;
         assert!(validator.validate_script(invalid_script).is_err());
     }
+
+    #[test]
+    fn test_flags_parse_in_any_position() {
+        let args = |line: &str| with_default_command(line.split(' ').map(String::from).collect());
+        for line in ["ae-validator --ignore-errors script.jsx", "ae-validator script.jsx --ignore-errors", "ae-validator validate script.jsx --ignore-errors"] {
+            match Cli::try_parse_from(args(line)).map(|cli| cli.command) {
+                Ok(Command::Validate(validate)) => assert!(validate.ignore_errors && validate.script == "script.jsx", "{}", line),
+                _ => panic!("'{}' did not parse as validate", line),
+            }
+        }
+        assert!(matches!(Cli::try_parse_from(args("ae-validator graph --format json a.jsx")).map(|cli| cli.command), Ok(Command::Graph { format: GraphFormat::Json, .. })));
+    }
+
+    #[test]
+    fn test_output_files_are_flags() {
+        let args = |line: &str| with_default_command(line.split(' ').map(String::from).collect());
+        for line in ["ae-validator dump-model --out model.json", "ae-validator types --out ae.d.ts"] {
+            match Cli::try_parse_from(args(line)).map(|cli| cli.command) {
+                Ok(Command::DumpModel { out }) | Ok(Command::Types { out }) => assert!(out.is_some(), "{}", line),
+                _ => panic!("'{}' did not parse", line),
+            }
+        }
+        assert!(Cli::try_parse_from(args("ae-validator dump-model model.json")).is_err());
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,230 @@
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+use crate::api::UnifiedApi;
+use crate::api::documentation::ApiDocumentation;
+use crate::api::objects::ApiObject;
+use crate::data::match_names::get_effect_match_names;
+use crate::validation::validate_script;
+
+const HELP: &str = "\
+Commands:
+  objects                 list API classes
+  <Class>.methods         list methods of a class (e.g. CompItem.methods)
+  <Class>.properties      list properties of a class
+  <Class>.<member>        show details for a method or property
+  <Class>.<path>.<member> follow members to another class (e.g. CompItem.layers.addSolid)
+  effect \"<query>\"        fuzzy search effect match names
+  validate <snippet>      validate a script snippet (or paste code directly)
+  help                    show this message
+  quit                    exit the REPL";
+
+/// Interactive shell for exploring the API model and validating snippets
+pub struct Repl {
+    api: Arc<UnifiedApi>,
+    docs: ApiDocumentation,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Self { api: UnifiedApi::shared(), docs: ApiDocumentation::new() }
+    }
+
+    /// Reads commands from `input` until EOF or `quit`
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+        writeln!(output, "ae-validator REPL - type 'help' for commands")?;
+        write!(output, "> ")?;
+        output.flush()?;
+
+        for line in input.lines() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed == "quit" || trimmed == "exit" {
+                break;
+            }
+            if !trimmed.is_empty() {
+                writeln!(output, "{}", self.eval(trimmed))?;
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Evaluates a single command and returns its output
+    pub fn eval(&mut self, line: &str) -> String {
+        let line = line.trim();
+
+        if line == "help" {
+            return HELP.to_string();
+        }
+        if line == "objects" {
+            let mut names: Vec<&String> = self.api.objects.keys().collect();
+            names.sort();
+            return names.iter().map(|n| n.as_str()).collect::<Vec<_>>().join("\n");
+        }
+        if let Some(query) = line.strip_prefix("effect ") {
+            return search_effects(query.trim().trim_matches(|c| c == '"' || c == '\''));
+        }
+        if let Some(snippet) = line.strip_prefix("validate ") {
+            return validate_snippet(snippet);
+        }
+        if let Some((class, member)) = split_member_query(line) {
+            if self.find_object(class).is_some() || member == "methods" || member == "properties" {
+                return self.describe(class, member);
+            }
+        }
+
+        validate_snippet(line)
+    }
+
+    fn find_object(&self, class: &str) -> Option<&ApiObject> {
+        self.api.object(class)
+    }
+
+    fn describe(&self, class: &str, member: &str) -> String {
+        let object = match self.find_object(class) {
+            Some(object) => object,
+            None => return format!("Unknown class '{}'. Type 'objects' to list classes.", class),
+        };
+
+        match member {
+            "methods" => {
+                let mut methods: Vec<String> = object.methods.iter()
+                    .map(|(name, method)| format!("{}({} params)", name, method.param_count))
+                    .collect();
+                methods.sort();
+                if methods.is_empty() {
+                    format!("{} has no methods", class)
+                } else {
+                    methods.join("\n")
+                }
+            }
+            "properties" => {
+                let mut properties: Vec<String> = object.properties.iter()
+                    .map(|(name, rule)| format!("{}: {:?}", name, rule.value_type))
+                    .collect();
+                properties.sort();
+                if properties.is_empty() {
+                    format!("{} has no properties", class)
+                } else {
+                    properties.join("\n")
+                }
+            }
+            name => {
+                if let Some(description) = self.docs.describe_in(&self.api, &format!("{}.{}", class, name)) {
+                    description.to_string()
+                } else if name.contains('.') {
+                    format!("Cannot follow '{}.{}' through the API model", class, name)
+                } else {
+                    let members: Vec<&String> = object.methods.keys().chain(object.properties.keys()).collect();
+                    let closest = members.iter()
+                        .map(|m| (strsim::levenshtein(name, m), *m))
+                        .filter(|(distance, _)| *distance <= 3)
+                        .min_by_key(|(distance, m)| (*distance, *m));
+                    match closest {
+                        Some((_, suggestion)) => format!("{} has no member '{}'. Did you mean '{}'?", class, name, suggestion),
+                        None => format!("{} has no member '{}'", class, name),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits `Class.member` and `Class.path.member` queries; anything with
+/// spaces, calls or operators is a snippet
+fn split_member_query(line: &str) -> Option<(&str, &str)> {
+    let (class, member) = line.split_once('.')?;
+    let is_word = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if is_word(class) && member.split('.').all(is_word) {
+        Some((class, member))
+    } else {
+        None
+    }
+}
+
+/// Ranks effect match names by substring match, then edit distance
+fn search_effects(query: &str) -> String {
+    let query = query.to_lowercase();
+    let mut matches: Vec<(usize, &str)> = get_effect_match_names().into_iter()
+        .map(|name| {
+            let lower = name.to_lowercase();
+            let short = lower.strip_prefix("adbe ").unwrap_or(&lower);
+            let score = if short.contains(&query) {
+                short.len() - query.len()
+            } else {
+                100 + strsim::levenshtein(&query, short)
+            };
+            (score, name)
+        })
+        .filter(|(score, _)| *score < 100 + query.len() / 2 + 1)
+        .collect();
+    matches.sort();
+    matches.dedup_by(|a, b| a.1 == b.1);
+
+    if matches.is_empty() {
+        format!("No effects match '{}'", query)
+    } else {
+        matches.iter().take(10).map(|(_, name)| *name).collect::<Vec<_>>().join("\n")
+    }
+}
+
+fn validate_snippet(snippet: &str) -> String {
+    let result = validate_script(snippet, "<repl>");
+    if result.errors.is_empty() && result.warnings.is_empty() {
+        return "OK - no issues found".to_string();
+    }
+    result.errors.iter().chain(result.warnings.iter())
+        .map(|diagnostic| match diagnostic.get_context().suggestion() {
+            Some(suggestion) => format!("{}\n  Suggestion: {}", diagnostic, suggestion),
+            None => diagnostic.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_queries() {
+        let mut repl = Repl::new();
+        assert!(repl.eval("CompItem.methods").contains("layer("));
+        assert!(repl.eval("Frobnicator.methods").starts_with("Unknown class"));
+        assert!(repl.eval("CompItem.laye").contains("Did you mean"));
+        assert!(repl.eval("CompItem.layers.addSolid").starts_with("LayerCollection.addSolid(color: "));
+    }
+
+    #[test]
+    fn test_effect_search() {
+        let mut repl = Repl::new();
+        let output = repl.eval("effect \"gaussian\"");
+        assert!(output.lines().next().unwrap().contains("Gaussian Blur"));
+    }
+
+    #[test]
+    fn test_snippet_validation() {
+        let mut repl = Repl::new();
+        assert!(repl.eval("leaked = 1;").contains("implicit global"));
+        assert_eq!(split_member_query("comp.layer(1)"), None);
+    }
+
+    #[test]
+    fn test_run_loop() {
+        let mut repl = Repl::new();
+        let mut output = Vec::new();
+        repl.run("help\nquit\nobjects\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Commands:"));
+        assert!(!output.contains("CompItem\n"));
+    }
+}
@@ -0,0 +1,536 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::errors::{ErrorLevel, ValidationError};
+use crate::validation::errors::{suggestions_json, ErrorContextBuilder, ErrorSeverity, ValidatorError};
+use crate::validation::changes::ChangedLines;
+use crate::validation::sourcemap::SourceMap;
+use crate::validation::ScriptValidationResult;
+
+/// Wall-clock time spent in one validation pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassTiming {
+    pub name: String,
+    pub duration: Duration,
+}
+
+/// Outcome of validating a script: diagnostics split by severity, timings
+/// and how often each rule fired
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub file: Option<String>,
+    /// Error and fatal diagnostics
+    pub errors: Vec<ValidatorError>,
+    pub warnings: Vec<ValidatorError>,
+    pub infos: Vec<ValidatorError>,
+    pub pass_timings: Vec<PassTiming>,
+    /// Diagnostics per rule id; diagnostics without a rule count as `unknown`
+    pub rule_hits: BTreeMap<String, usize>,
+    /// Diagnostics per rule left out by [`ValidationReport::limit_per_rule`]
+    pub truncated: BTreeMap<String, usize>,
+}
+
+impl ValidationReport {
+    pub fn new(file: Option<String>) -> Self {
+        Self { file, ..Self::default() }
+    }
+
+    /// Attributes the report, and every diagnostic without a file, to `file`
+    pub fn set_file(&mut self, file: &str) {
+        self.file = Some(file.to_string());
+        for diagnostic in self.errors.iter_mut().chain(&mut self.warnings).chain(&mut self.infos) {
+            let context = diagnostic.get_context_mut();
+            if context.file.is_none() {
+                context.file = Some(file.to_string());
+            }
+        }
+    }
+
+    /// Reports diagnostics in a bundled script against the modules it was
+    /// built from. Call after `set_file`, which fills in the bundle's name.
+    pub fn apply_source_map(&mut self, map: &SourceMap, bundle: &str) {
+        for diagnostic in self.errors.iter_mut().chain(&mut self.warnings).chain(&mut self.infos) {
+            map.apply(diagnostic, bundle);
+        }
+    }
+
+    /// Files a diagnostic under its severity and counts its rule
+    pub fn push(&mut self, diagnostic: ValidatorError) {
+        *self.rule_hits.entry(diagnostic.rule_id().unwrap_or("unknown").to_string()).or_insert(0) += 1;
+        self.file_by_severity(diagnostic);
+    }
+
+    fn file_by_severity(&mut self, diagnostic: ValidatorError) {
+        match diagnostic.severity() {
+            ErrorSeverity::Error | ErrorSeverity::Fatal => self.errors.push(diagnostic),
+            ErrorSeverity::Warning => self.warnings.push(diagnostic),
+            ErrorSeverity::Info => self.infos.push(diagnostic),
+        }
+    }
+
+    pub fn extend<I: IntoIterator<Item = ValidatorError>>(&mut self, diagnostics: I) {
+        for diagnostic in diagnostics {
+            self.push(diagnostic);
+        }
+    }
+
+    /// Adds the errors and warnings of a pipeline validation run
+    pub fn merge_result(&mut self, result: ScriptValidationResult) {
+        self.extend(result.errors);
+        self.extend(result.warnings);
+    }
+
+    pub fn record_pass(&mut self, name: &str, duration: Duration) {
+        self.pass_timings.push(PassTiming { name: name.to_string(), duration });
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Total time of all recorded passes
+    pub fn duration(&self) -> Duration {
+        self.pass_timings.iter().map(|p| p.duration).sum()
+    }
+
+    /// All diagnostics ordered by line, errors before warnings before infos
+    /// on the same line, then by column, rule and message
+    pub fn diagnostics(&self) -> Vec<&ValidatorError> {
+        sorted(self.errors.iter().chain(&self.warnings).chain(&self.infos))
+    }
+
+    /// One-line summary such as `2 errors, 1 warning, 0 infos in 4.2 ms`
+    pub fn summary(&self) -> String {
+        let summary = format!(
+            "{}, {}, {} in {:.1} ms",
+            plural(self.errors.len(), "error"),
+            plural(self.warnings.len(), "warning"),
+            plural(self.infos.len(), "info"),
+            self.duration().as_secs_f64() * 1000.0
+        );
+        match self.truncated.values().sum::<usize>() {
+            0 => summary,
+            omitted => format!("{} ({} more not shown)", summary, omitted),
+        }
+    }
+
+    /// Drops warnings and infos, for output that only shows errors
+    pub fn retain_errors(&mut self) {
+        self.warnings.clear();
+        self.infos.clear();
+        self.recount_rules();
+    }
+
+    /// Keeps the diagnostics on changed lines, for reporting only what a
+    /// change introduced while still validating whole files
+    pub fn retain_changed(&mut self, changed: &ChangedLines) {
+        let file = self.file.clone().unwrap_or_default();
+        self.errors.retain(|d| changed.touches(d, &file));
+        self.warnings.retain(|d| changed.touches(d, &file));
+        self.infos.retain(|d| changed.touches(d, &file));
+        self.recount_rules();
+    }
+
+    /// Keeps the first `max` diagnostics of each rule, by line, counting
+    /// the rest in `truncated`. Rule hits still count every diagnostic. A
+    /// `max` of 0 keeps one per rule so the verdict is unchanged; configs
+    /// reject 0 when loaded.
+    pub fn limit_per_rule(&mut self, max: usize) {
+        let mut all: Vec<ValidatorError> = std::mem::take(&mut self.errors).into_iter()
+            .chain(std::mem::take(&mut self.warnings))
+            .chain(std::mem::take(&mut self.infos))
+            .collect();
+        all.sort_by(diagnostic_order);
+
+        let mut shown: BTreeMap<String, usize> = BTreeMap::new();
+        for diagnostic in all {
+            let rule = diagnostic.rule_id().unwrap_or("unknown").to_string();
+            let count = shown.entry(rule.clone()).or_insert(0);
+            if *count < max.max(1) {
+                *count += 1;
+                self.file_by_severity(diagnostic);
+            } else {
+                *self.truncated.entry(rule).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// One line per truncated rule, such as `implicit-global: …and 213 more`
+    pub fn truncation_notes(&self) -> Vec<String> {
+        self.truncated.iter().map(|(rule, count)| format!("{}: …and {} more", rule, count)).collect()
+    }
+
+    fn recount_rules(&mut self) {
+        self.rule_hits.clear();
+        for diagnostic in self.errors.iter().chain(&self.warnings).chain(&self.infos) {
+            *self.rule_hits.entry(diagnostic.rule_id().unwrap_or("unknown").to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// One line per file, such as `ui.jsx: FAIL (2 errors, 1 warning)`
+    pub fn verdict(&self) -> String {
+        let mut counts = vec![plural(self.errors.len(), "error"), plural(self.warnings.len(), "warning")];
+        if !self.infos.is_empty() {
+            counts.push(plural(self.infos.len(), "info"));
+        }
+        format!(
+            "{}: {} ({})",
+            self.file.as_deref().unwrap_or("<script>"),
+            if self.is_valid() { "OK" } else { "FAIL" },
+            counts.join(", ")
+        )
+    }
+
+    /// Diagnostics counted per rule, most frequent first, with the first
+    /// occurrence of each as an example
+    pub fn rule_summary(&self) -> String {
+        let mut rules: Vec<(&str, Vec<&ValidatorError>)> = Vec::new();
+        for diagnostic in self.diagnostics() {
+            let rule = diagnostic.rule_id().unwrap_or("unknown");
+            match rules.iter_mut().find(|(name, _)| *name == rule) {
+                Some((_, diagnostics)) => diagnostics.push(diagnostic),
+                None => rules.push((rule, vec![diagnostic])),
+            }
+        }
+        let total = |rule: &str, shown: usize| shown + self.truncated.get(rule).copied().unwrap_or(0);
+        rules.sort_by(|a, b| total(b.0, b.1.len()).cmp(&total(a.0, a.1.len())).then(a.0.cmp(b.0)));
+
+        let mut output = String::new();
+        for (rule, diagnostics) in &rules {
+            let first = diagnostics[0];
+            let severity = format!("{:?}", first.severity()).to_lowercase();
+            let location = first.get_context().line.map_or(String::new(), |line| format!("line {}: ", line));
+            output.push_str(&format!(
+                "  {:<28} {:>5} {:<8} {}{}\n",
+                rule,
+                total(rule, diagnostics.len()),
+                severity,
+                location,
+                first.get_message()
+            ));
+        }
+        output
+    }
+
+    /// Pass timings and rule hit counts as aligned text
+    pub fn stats_table(&self) -> String {
+        let mut output = String::from("Pass timings:\n");
+        for pass in &self.pass_timings {
+            output.push_str(&format!("  {:<24} {:>8.2} ms\n", pass.name, pass.duration.as_secs_f64() * 1000.0));
+        }
+        output.push_str("Rule hits:\n");
+        for (rule, hits) in &self.rule_hits {
+            output.push_str(&format!("  {:<24} {:>8}\n", rule, hits));
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        let diagnostic = |d: &ValidatorError| {
+            let context = d.get_context();
+            json!({
+                "rule": context.rule,
+                "severity": format!("{:?}", d.severity()),
+                "message": d.get_message(),
+                "line": context.line,
+                "column": context.column,
+                "suggestion": context.suggestion(),
+                "suggestions": suggestions_json(&context.suggestions),
+                "docs_url": context.docs_url,
+            })
+        };
+        let report = json!({
+            "file": self.file,
+            "valid": self.is_valid(),
+            "errors": sorted(&self.errors).into_iter().map(diagnostic).collect::<Vec<_>>(),
+            "warnings": sorted(&self.warnings).into_iter().map(diagnostic).collect::<Vec<_>>(),
+            "infos": sorted(&self.infos).into_iter().map(diagnostic).collect::<Vec<_>>(),
+            "timings_ms": self.pass_timings.iter()
+                .map(|p| (p.name.clone(), json!(p.duration.as_secs_f64() * 1000.0)))
+                .collect::<serde_json::Map<_, _>>(),
+            "rule_hits": self.rule_hits,
+            "truncated": self.truncated,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+}
+
+/// File `--record-stats` accumulates rule statistics in unless given one
+pub const DEFAULT_STATS_FILE: &str = ".auteur-stats.json";
+
+/// How often one rule fired over the recorded runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleCount {
+    pub hits: usize,
+    /// Runs in which the rule fired at least once
+    pub runs: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+/// Rule hits accumulated over many runs in a local JSON file, so teams can
+/// see which rules fire most and tune their severities. Nothing is sent
+/// anywhere; recording is opt-in with `--record-stats`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleStats {
+    pub runs: usize,
+    pub rules: BTreeMap<String, RuleCount>,
+}
+
+impl RuleStats {
+    /// Reads recorded statistics; a missing file is an empty record
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Invalid statistics file {}: {}", path.display(), e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Deletes the statistics file, if there is one
+    pub fn reset<P: AsRef<Path>>(path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(format!("Failed to remove {}: {}", path.display(), e)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds one run's diagnostics
+    pub fn record(&mut self, report: &ValidationReport) {
+        self.runs += 1;
+        let mut fired: Vec<&str> = Vec::new();
+        for diagnostic in report.errors.iter().chain(&report.warnings).chain(&report.infos) {
+            let rule = diagnostic.rule_id().unwrap_or("unknown");
+            let count = self.rules.entry(rule.to_string()).or_default();
+            count.hits += 1;
+            match diagnostic.severity() {
+                ErrorSeverity::Error | ErrorSeverity::Fatal => count.errors += 1,
+                ErrorSeverity::Warning => count.warnings += 1,
+                ErrorSeverity::Info => count.infos += 1,
+            }
+            if !fired.contains(&rule) {
+                fired.push(rule);
+                count.runs += 1;
+            }
+        }
+    }
+
+    /// Rules ordered by hits, most frequent first
+    pub fn ranked(&self) -> Vec<(&str, &RuleCount)> {
+        let mut rules: Vec<(&str, &RuleCount)> = self.rules.iter().map(|(rule, count)| (rule.as_str(), count)).collect();
+        rules.sort_by(|a, b| b.1.hits.cmp(&a.1.hits).then(a.0.cmp(b.0)));
+        rules
+    }
+
+    /// Hits, runs fired in and severity split per rule, as aligned text
+    pub fn to_table(&self) -> String {
+        let mut output = format!("Rule hits over {}:\n", plural(self.runs, "run"));
+        output.push_str(&format!(
+            "  {:<28} {:>7} {:>11} {:>7} {:>8} {:>6}\n",
+            "rule", "hits", "runs", "errors", "warnings", "infos"
+        ));
+        for (rule, count) in self.ranked() {
+            let share = if self.runs == 0 { 0.0 } else { count.runs as f64 * 100.0 / self.runs as f64 };
+            output.push_str(&format!(
+                "  {:<28} {:>7} {:>5} {:>4.0}% {:>7} {:>8} {:>6}\n",
+                rule, count.hits, count.runs, share, count.errors, count.warnings, count.infos
+            ));
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Converts a `ScriptValidator` error into a pipeline diagnostic attributed to `rule`
+pub fn legacy_diagnostic(error: ValidationError, file: Option<&str>, rule: &str) -> ValidatorError {
+    let mut context = ErrorContextBuilder::new()
+        .suggestions(error.suggestions)
+        .docs_url(error.docs_url)
+        .rule(rule);
+    if let Some(file) = file {
+        context = context.file(file.to_string());
+    }
+    if error.line > 0 {
+        context = context.line(error.line).column(error.column.max(1));
+    }
+    ValidatorError::Method {
+        message: error.message,
+        context: context.build(),
+        severity: match error.level {
+            ErrorLevel::Error => ErrorSeverity::Error,
+            ErrorLevel::Warning => ErrorSeverity::Warning,
+        },
+    }
+}
+
+/// Order diagnostics are reported in: by line, severity and column, then
+/// file, rule and message, so identical input gives identical output
+/// whatever order passes and maps produced them in
+fn diagnostic_order(a: &ValidatorError, b: &ValidatorError) -> Ordering {
+    let rank = |d: &ValidatorError| match d.severity() {
+        ErrorSeverity::Error | ErrorSeverity::Fatal => 0,
+        ErrorSeverity::Warning => 1,
+        ErrorSeverity::Info => 2,
+    };
+    let (first, second) = (a.get_context(), b.get_context());
+    (first.line.unwrap_or(0), rank(a), first.column.unwrap_or(0))
+        .cmp(&(second.line.unwrap_or(0), rank(b), second.column.unwrap_or(0)))
+        .then_with(|| first.file.cmp(&second.file))
+        .then_with(|| a.rule_id().cmp(&b.rule_id()))
+        .then_with(|| a.get_message().cmp(b.get_message()))
+}
+
+fn sorted<'a, I: IntoIterator<Item = &'a ValidatorError>>(diagnostics: I) -> Vec<&'a ValidatorError> {
+    let mut all: Vec<&ValidatorError> = diagnostics.into_iter().collect();
+    all.sort_by(|a, b| diagnostic_order(a, b));
+    all
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(line: usize, rule: &str, severity: ErrorSeverity) -> ValidatorError {
+        ValidatorError::Script {
+            message: format!("{} on line {}", rule, line),
+            context: ErrorContextBuilder::new().line(line).rule(rule).build(),
+            severity,
+        }
+    }
+
+    #[test]
+    fn test_condensed_output() {
+        let mut report = ValidationReport::new(Some("ui.jsx".to_string()));
+        report.extend(vec![
+            diagnostic(3, "implicit-global", ErrorSeverity::Warning),
+            diagnostic(5, "invalid-method", ErrorSeverity::Error),
+            diagnostic(9, "implicit-global", ErrorSeverity::Warning),
+        ]);
+        assert_eq!(report.verdict(), "ui.jsx: FAIL (1 error, 2 warnings)");
+        let summary = report.rule_summary();
+        let rules: Vec<&str> = summary.lines().filter_map(|line| line.split_whitespace().next()).collect();
+        assert_eq!(rules, vec!["implicit-global", "invalid-method"]);
+        assert!(report.rule_summary().starts_with("  implicit-global                  2 warning  line 3: implicit-global on line 3\n"));
+
+        report.retain_errors();
+        assert_eq!(report.verdict(), "ui.jsx: FAIL (1 error, 0 warnings)");
+        assert_eq!(report.rule_hits.len(), 1);
+    }
+
+    #[test]
+    fn test_limit_per_rule() {
+        let mut report = ValidationReport::new(Some("ui.jsx".to_string()));
+        for line in (1..=25).rev() {
+            report.push(diagnostic(line, "missing-semicolon", ErrorSeverity::Warning));
+        }
+        report.push(diagnostic(30, "invalid-method", ErrorSeverity::Error));
+        report.push(diagnostic(2, "invalid-method", ErrorSeverity::Error));
+
+        report.limit_per_rule(20);
+        assert_eq!(report.warnings.len(), 20);
+        assert_eq!(report.warnings.last().unwrap().get_context().line, Some(20));
+        assert_eq!(report.errors.len(), 2);
+        assert_eq!(report.truncation_notes(), vec!["missing-semicolon: …and 5 more"]);
+        assert!(report.summary().starts_with("2 errors, 20 warnings, 0 infos in "));
+        assert!(report.summary().ends_with(" ms (5 more not shown)"));
+        assert_eq!(report.rule_hits["missing-semicolon"], 25);
+        assert!(report.rule_summary().starts_with("  missing-semicolon               25 warning  line 1: "));
+
+        report.limit_per_rule(0);
+        assert_eq!(report.errors.len(), 1);
+        assert!(!report.is_valid());
+        assert_eq!(report.truncated["invalid-method"], 1);
+    }
+
+    #[test]
+    fn test_output_is_deterministic() {
+        let at = |line: usize, column: usize, rule: &str, severity: ErrorSeverity| ValidatorError::Script {
+            message: format!("{} at {}:{}", rule, line, column),
+            context: ErrorContextBuilder::new().line(line).column(column).rule(rule).build(),
+            severity,
+        };
+        let diagnostics = || vec![
+            at(4, 9, "unknown-function", ErrorSeverity::Warning),
+            at(4, 1, "implicit-global", ErrorSeverity::Warning),
+            at(4, 9, "invalid-method", ErrorSeverity::Warning),
+            at(4, 12, "invalid-method", ErrorSeverity::Error),
+            at(2, 5, "implicit-global", ErrorSeverity::Warning),
+        ];
+        let mut forward = ValidationReport::new(Some("ui.jsx".to_string()));
+        forward.extend(diagnostics());
+        let mut backward = ValidationReport::new(Some("ui.jsx".to_string()));
+        backward.extend(diagnostics().into_iter().rev());
+
+        assert_eq!(forward.to_json(), backward.to_json());
+        assert_eq!(forward.rule_summary(), backward.rule_summary());
+        let order: Vec<&str> = backward.diagnostics().iter().map(|d| d.get_message()).collect();
+        assert_eq!(order, vec![
+            "implicit-global at 2:5",
+            "invalid-method at 4:12",
+            "implicit-global at 4:1",
+            "invalid-method at 4:9",
+            "unknown-function at 4:9",
+        ]);
+
+        let script = "counter = 0;\nalret(total); total = counter + lenght;\nvar comp = app.project.activeItem;\ncomp.layer(1).sourceText = undefined;\n";
+        let validate = || {
+            let mut report = ValidationReport::new(Some("ui.jsx".to_string()));
+            report.merge_result(crate::validation::validate_script(script, "ui.jsx"));
+            report.to_json()
+        };
+        assert_eq!(validate(), validate());
+    }
+
+    #[test]
+    fn test_rule_stats() {
+        let mut stats = RuleStats::default();
+        let mut first = ValidationReport::new(Some("a.jsx".to_string()));
+        first.extend(vec![
+            diagnostic(1, "implicit-global", ErrorSeverity::Warning),
+            diagnostic(2, "implicit-global", ErrorSeverity::Warning),
+            diagnostic(4, "invalid-method", ErrorSeverity::Error),
+        ]);
+        let mut second = ValidationReport::new(Some("b.jsx".to_string()));
+        second.push(diagnostic(7, "implicit-global", ErrorSeverity::Info));
+        stats.record(&first);
+        stats.record(&second);
+
+        assert_eq!(stats.runs, 2);
+        assert_eq!(stats.rules["implicit-global"], RuleCount { hits: 3, runs: 2, errors: 0, warnings: 2, infos: 1 });
+        assert_eq!(stats.ranked()[1], ("invalid-method", &RuleCount { hits: 1, runs: 1, errors: 1, warnings: 0, infos: 0 }));
+        assert!(stats.to_table().contains("\n  implicit-global                    3     2  100%       0        2      1\n"));
+
+        let file = std::env::temp_dir().join(format!("auteur-stats-{}.json", std::process::id()));
+        stats.save(&file).unwrap();
+        assert_eq!(RuleStats::load(&file).unwrap(), stats);
+        RuleStats::reset(&file).unwrap();
+        assert_eq!(RuleStats::load(&file).unwrap(), RuleStats::default());
+    }
+}
@@ -0,0 +1,196 @@
+//! Compares two sets of JSON reports, as written by `ae-validator --json`,
+//! e.g. before and after upgrading the crate or changing the rule config.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One diagnostic as read back from a JSON report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportDiagnostic {
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub rule: Option<String>,
+    pub severity: String,
+    pub message: String,
+    #[serde(default)]
+    pub line: Option<usize>,
+    #[serde(default)]
+    pub column: Option<usize>,
+}
+
+impl ReportDiagnostic {
+    fn location(&self) -> String {
+        let file = self.file.as_deref().unwrap_or("<unknown>");
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", file, line, column),
+            (Some(line), None) => format!("{}:{}", file, line),
+            _ => file.to_string(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} [{}] {}: {}",
+            self.location(),
+            self.severity,
+            self.rule.as_deref().unwrap_or("unknown"),
+            self.message
+        )
+    }
+}
+
+/// A diagnostic reported by both versions with a different message or severity
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangedDiagnostic {
+    pub old: ReportDiagnostic,
+    pub new: ReportDiagnostic,
+}
+
+/// Diagnostics introduced, fixed and changed between two report sets
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReportDiff {
+    pub introduced: Vec<ReportDiagnostic>,
+    pub fixed: Vec<ReportDiagnostic>,
+    pub changed: Vec<ChangedDiagnostic>,
+    pub unchanged: usize,
+}
+
+/// Reads every diagnostic from a JSON report, or an array of reports
+pub fn diagnostics_from_json(content: &str) -> Result<Vec<ReportDiagnostic>, String> {
+    let value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let reports = match value {
+        Value::Array(reports) => reports,
+        report => vec![report],
+    };
+
+    let mut diagnostics = Vec::new();
+    for report in &reports {
+        let file = report.get("file").and_then(Value::as_str).map(str::to_string);
+        for key in ["errors", "warnings", "infos"] {
+            for entry in report.get(key).and_then(Value::as_array).into_iter().flatten() {
+                let mut diagnostic: ReportDiagnostic = serde_json::from_value(entry.clone())
+                    .map_err(|e| format!("Invalid diagnostic in {}: {}", key, e))?;
+                if diagnostic.file.is_none() {
+                    diagnostic.file = file.clone();
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+    Ok(diagnostics)
+}
+
+impl ReportDiff {
+    /// Pairs diagnostics from the two sets. Identical diagnostics, and ones
+    /// with the same rule and message that only moved to another line, are
+    /// unchanged; the same rule on the same line with a different message or
+    /// severity is changed. Everything else was introduced or fixed.
+    pub fn compare(old: &[ReportDiagnostic], new: &[ReportDiagnostic]) -> Self {
+        let mut old_left: Vec<Option<&ReportDiagnostic>> = old.iter().map(Some).collect();
+        let mut new_left: Vec<Option<&ReportDiagnostic>> = new.iter().map(Some).collect();
+        let mut diff = ReportDiff::default();
+
+        let passes: [fn(&ReportDiagnostic, &ReportDiagnostic) -> bool; 3] = [
+            |a, b| a == b,
+            |a, b| a.file == b.file && a.rule == b.rule && a.message == b.message && a.severity == b.severity,
+            |a, b| a.file == b.file && a.rule == b.rule && a.line == b.line,
+        ];
+        for (pass, matches) in passes.iter().enumerate() {
+            for old_slot in old_left.iter_mut() {
+                let old_diagnostic = match old_slot {
+                    Some(diagnostic) => *diagnostic,
+                    None => continue,
+                };
+                let found = new_left.iter_mut()
+                    .find(|slot| slot.is_some_and(|new_diagnostic| matches(old_diagnostic, new_diagnostic)));
+                if let Some(new_slot) = found {
+                    let new_diagnostic = new_slot.take().unwrap();
+                    old_slot.take();
+                    if pass < 2 {
+                        diff.unchanged += 1;
+                    } else {
+                        diff.changed.push(ChangedDiagnostic { old: old_diagnostic.clone(), new: new_diagnostic.clone() });
+                    }
+                }
+            }
+        }
+
+        diff.fixed = old_left.into_iter().flatten().cloned().collect();
+        diff.introduced = new_left.into_iter().flatten().cloned().collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.introduced.is_empty() && self.fixed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+        if !self.introduced.is_empty() {
+            output.push_str(&format!("Introduced ({}):\n", self.introduced.len()));
+            for diagnostic in &self.introduced {
+                output.push_str(&format!("  + {}\n", diagnostic.describe()));
+            }
+        }
+        if !self.fixed.is_empty() {
+            output.push_str(&format!("Fixed ({}):\n", self.fixed.len()));
+            for diagnostic in &self.fixed {
+                output.push_str(&format!("  - {}\n", diagnostic.describe()));
+            }
+        }
+        if !self.changed.is_empty() {
+            output.push_str(&format!("Changed ({}):\n", self.changed.len()));
+            for change in &self.changed {
+                output.push_str(&format!("  ~ {}\n", change.old.describe()));
+                output.push_str(&format!("    -> [{}] {}\n", change.new.severity, change.new.message));
+            }
+        }
+        output.push_str(&format!(
+            "{} introduced, {} fixed, {} changed, {} unchanged\n",
+            self.introduced.len(),
+            self.fixed.len(),
+            self.changed.len(),
+            self.unchanged
+        ));
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_introduced_fixed_changed() {
+        let old = diagnostics_from_json(r#"[{
+            "file": "a.jsx",
+            "errors": [{"rule": "type-mismatch", "severity": "Error", "message": "Expected number", "line": 3, "column": 5}],
+            "warnings": [
+                {"rule": "implicit-global", "severity": "Warning", "message": "Implicit global 'x'", "line": 1, "column": 1},
+                {"rule": "deprecated", "severity": "Warning", "message": "trackMatteType is deprecated", "line": 8, "column": 1}
+            ]
+        }]"#).unwrap();
+        let new = diagnostics_from_json(r#"{
+            "file": "a.jsx",
+            "errors": [{"rule": "type-mismatch", "severity": "Error", "message": "Expected number, got string", "line": 3, "column": 5}],
+            "warnings": [
+                {"rule": "implicit-global", "severity": "Warning", "message": "Implicit global 'x'", "line": 2, "column": 1},
+                {"rule": "platform-path", "severity": "Warning", "message": "Hard-coded Windows path", "line": 4, "column": 10}
+            ]
+        }"#).unwrap();
+
+        let diff = ReportDiff::compare(&old, &new);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].new.message, "Expected number, got string");
+        assert_eq!(diff.fixed.len(), 1);
+        assert_eq!(diff.fixed[0].rule.as_deref(), Some("deprecated"));
+        assert_eq!(diff.introduced.len(), 1);
+        assert!(diff.to_text().contains("  + a.jsx:4:10 [Warning] platform-path: Hard-coded Windows path\n"));
+    }
+}
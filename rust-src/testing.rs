@@ -0,0 +1,329 @@
+//! Snapshot testing utilities for validator output.
+//!
+//! A fixture directory holds `.jsx` scripts; each one has a committed
+//! `<name>.expected.json` snapshot next to it. [`SnapshotHarness`] validates
+//! every fixture and compares the diagnostics with its snapshot, or rewrites
+//! the snapshots in update mode.
+//!
+//! [`assert_valid_script`] and the [`assert_valid_script!`] macro check
+//! generated ExtendScript with [`ScriptValidator`], for code that emits
+//! scripts and wants to self-check its output.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::errors::{ErrorLevel, ValidationError};
+use crate::validation::{validate_script, ScriptValidationResult, ValidatorError};
+use crate::validator::ScriptValidator;
+
+/// Environment variable that switches harnesses into update mode
+pub const UPDATE_ENV_VAR: &str = "AE_VALIDATOR_UPDATE_SNAPSHOTS";
+
+/// Whether snapshots are compared or rewritten
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotMode {
+    Compare,
+    Update,
+}
+
+impl SnapshotMode {
+    /// `Update` when `AE_VALIDATOR_UPDATE_SNAPSHOTS` is set to a non-empty value other than `0`
+    pub fn from_env() -> Self {
+        match std::env::var(UPDATE_ENV_VAR) {
+            Ok(value) if !value.is_empty() && value != "0" => SnapshotMode::Update,
+            _ => SnapshotMode::Compare,
+        }
+    }
+}
+
+/// Stable, serializable form of a single diagnostic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDiagnostic {
+    pub severity: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl From<&ValidatorError> for SnapshotDiagnostic {
+    fn from(error: &ValidatorError) -> Self {
+        let context = error.get_context();
+        Self {
+            severity: format!("{:?}", error.severity()),
+            rule: context.rule.clone(),
+            line: context.line,
+            column: context.column,
+            message: error.get_message().to_string(),
+        }
+    }
+}
+
+/// Validator output for one fixture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub is_valid: bool,
+    pub errors: Vec<SnapshotDiagnostic>,
+    pub warnings: Vec<SnapshotDiagnostic>,
+}
+
+impl Snapshot {
+    pub fn from_result(result: &ScriptValidationResult) -> Self {
+        Self {
+            is_valid: result.is_valid,
+            errors: result.errors.iter().map(SnapshotDiagnostic::from).collect(),
+            warnings: result.warnings.iter().map(SnapshotDiagnostic::from).collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).unwrap_or_default();
+        json.push('\n');
+        json
+    }
+}
+
+/// A fixture whose output did not match its snapshot
+#[derive(Debug, Clone)]
+pub struct SnapshotFailure {
+    pub fixture: PathBuf,
+    /// Committed snapshot, or `None` when it does not exist yet
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+impl SnapshotFailure {
+    /// Line-by-line difference between the snapshot and the actual output
+    pub fn diff(&self) -> String {
+        let expected = match &self.expected {
+            Some(expected) => expected,
+            None => return format!("missing snapshot; actual output:\n{}", self.actual),
+        };
+        let expected_lines: Vec<&str> = expected.lines().collect();
+        let actual_lines: Vec<&str> = self.actual.lines().collect();
+        let mut diff = String::new();
+        for i in 0..expected_lines.len().max(actual_lines.len()) {
+            match (expected_lines.get(i), actual_lines.get(i)) {
+                (Some(e), Some(a)) if e == a => {}
+                (e, a) => {
+                    if let Some(e) = e {
+                        diff.push_str(&format!("-{:>4} {}\n", i + 1, e));
+                    }
+                    if let Some(a) = a {
+                        diff.push_str(&format!("+{:>4} {}\n", i + 1, a));
+                    }
+                }
+            }
+        }
+        diff
+    }
+}
+
+/// Outcome of running a harness over a fixture directory
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotReport {
+    pub passed: Vec<PathBuf>,
+    pub updated: Vec<PathBuf>,
+    pub failures: Vec<SnapshotFailure>,
+}
+
+impl SnapshotReport {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panics with a readable diff for every failing fixture
+    pub fn assert_ok(&self) {
+        if self.is_ok() {
+            return;
+        }
+        let mut message = format!("{} snapshot(s) did not match:\n", self.failures.len());
+        for failure in &self.failures {
+            message.push_str(&format!("\n{}\n{}", failure.fixture.display(), failure.diff()));
+        }
+        message.push_str(&format!("\nRun with {}=1 to update the snapshots.\n", UPDATE_ENV_VAR));
+        panic!("{}", message);
+    }
+}
+
+type Validate = Box<dyn Fn(&str, &str) -> ScriptValidationResult>;
+
+/// Runs validation over `.jsx` fixtures and checks the `.expected.json` snapshots
+pub struct SnapshotHarness {
+    fixtures_dir: PathBuf,
+    mode: SnapshotMode,
+    validate: Validate,
+}
+
+impl SnapshotHarness {
+    /// Harness using `validate_script` and the mode from the environment
+    pub fn new<P: AsRef<Path>>(fixtures_dir: P) -> Self {
+        Self {
+            fixtures_dir: fixtures_dir.as_ref().to_path_buf(),
+            mode: SnapshotMode::from_env(),
+            validate: Box::new(validate_script),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: SnapshotMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Replaces the validation entry point, e.g. to apply a config or custom rule pack
+    pub fn with_validator<F>(mut self, validate: F) -> Self
+    where
+        F: Fn(&str, &str) -> ScriptValidationResult + 'static,
+    {
+        self.validate = Box::new(validate);
+        self
+    }
+
+    /// Fixture scripts in the directory, sorted by path
+    pub fn fixtures(&self) -> Vec<PathBuf> {
+        let mut fixtures: Vec<PathBuf> = WalkDir::new(&self.fixtures_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "jsx"))
+            .collect();
+        fixtures.sort();
+        fixtures
+    }
+
+    pub fn run(&self) -> std::io::Result<SnapshotReport> {
+        let mut report = SnapshotReport::default();
+
+        for fixture in self.fixtures() {
+            let script = fs::read_to_string(&fixture)?;
+            let name = fixture.strip_prefix(&self.fixtures_dir).unwrap_or(&fixture).to_string_lossy().replace('\\', "/");
+            let actual = Snapshot::from_result(&(self.validate)(&script, &name)).to_json();
+            let snapshot_path = snapshot_path(&fixture);
+            let expected = fs::read_to_string(&snapshot_path).ok();
+
+            if expected.as_deref() == Some(actual.as_str()) {
+                report.passed.push(fixture);
+            } else if self.mode == SnapshotMode::Update {
+                fs::write(&snapshot_path, &actual)?;
+                report.updated.push(fixture);
+            } else {
+                report.failures.push(SnapshotFailure { fixture, expected, actual });
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// `dir/name.jsx` -> `dir/name.expected.json`
+pub fn snapshot_path(fixture: &Path) -> PathBuf {
+    let stem = fixture.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    fixture.with_file_name(format!("{}.expected.json", stem))
+}
+
+/// Errors (not warnings) [`ScriptValidator`] reports for a generated script
+pub fn generated_script_errors(script: &str) -> Vec<ValidationError> {
+    match ScriptValidator::new().validate_script(script) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.into_iter().filter(|e| matches!(e.level, ErrorLevel::Error)).collect(),
+    }
+}
+
+/// Panics with every error and the script if `script` does not validate
+#[track_caller]
+pub fn assert_valid_script(script: &str) {
+    let errors = generated_script_errors(script);
+    if errors.is_empty() {
+        return;
+    }
+    let mut message = format!("generated script has {} validation error(s):\n", errors.len());
+    for error in &errors {
+        message.push_str(&format!("  {}:{}: {}\n", error.line, error.column, error.message));
+    }
+    message.push_str(&format!("--- script ---\n{}", script));
+    panic!("{}", message);
+}
+
+/// Returns `script` unchanged, after asserting it validates in debug builds
+#[track_caller]
+pub fn debug_validated(script: String) -> String {
+    if cfg!(debug_assertions) {
+        assert_valid_script(&script);
+    }
+    script
+}
+
+/// Asserts that a generated ExtendScript string passes [`ScriptValidator`].
+///
+/// ```ignore
+/// assert_valid_script!(TestDataFactory::create_test_composition());
+/// ```
+#[macro_export]
+macro_rules! assert_valid_script {
+    ($script:expr $(,)?) => {
+        $crate::testing::assert_valid_script(::std::convert::AsRef::<str>::as_ref(&$script))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ae-validator-snapshots-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("globals.jsx"), "counter = 1;\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_update_then_compare() {
+        let dir = fixture_dir("roundtrip");
+
+        let missing = SnapshotHarness::new(&dir).with_mode(SnapshotMode::Compare).run().unwrap();
+        assert_eq!(missing.failures.len(), 1);
+        assert!(missing.failures[0].expected.is_none());
+
+        let updated = SnapshotHarness::new(&dir).with_mode(SnapshotMode::Update).run().unwrap();
+        assert_eq!(updated.updated.len(), 1);
+        let snapshot = fs::read_to_string(dir.join("globals.expected.json")).unwrap();
+        assert!(snapshot.contains("\"rule\": \"implicit-global\""));
+
+        let compared = SnapshotHarness::new(&dir).with_mode(SnapshotMode::Compare).run().unwrap();
+        compared.assert_ok();
+        assert_eq!(compared.passed.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_custom_validator_and_diff() {
+        let dir = fixture_dir("custom");
+        SnapshotHarness::new(&dir).with_mode(SnapshotMode::Update).run().unwrap();
+
+        let report = SnapshotHarness::new(&dir)
+            .with_mode(SnapshotMode::Compare)
+            .with_validator(|_, _| ScriptValidationResult::new())
+            .run()
+            .unwrap();
+        assert!(!report.is_ok());
+        assert!(report.failures[0].diff().contains("implicit-global"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_assert_valid_script() {
+        assert_valid_script!("app.project.items.addComp('Main', 1920, 1080, 1, 10, 30);");
+        assert_valid_script!(String::from("var comp = app.project.activeItem;"));
+
+        let errors = generated_script_errors("app.project.items.addComp('Main', 1920, 1080)");
+        assert_eq!(errors[0].message, "Missing semicolons");
+        let panicked = std::panic::catch_unwind(|| assert_valid_script("app.project.items.addComp('Main')"));
+        assert!(panicked.is_err());
+    }
+}
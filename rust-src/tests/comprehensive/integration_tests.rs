@@ -1869,4 +1869,20 @@ mod integration_tests {
             assert!(feature_tested, "Data consistency feature '{}' not tested", feature);
         }
     }
-}
\ No newline at end of file
+    /// Runs a factory scene in a real After Effects when one is installed
+    #[cfg(feature = "integration")]
+    #[test]
+    fn test_factory_scene_in_after_effects() {
+        let runner = match crate::integration::IntegrationRunner::detect() {
+            Some(runner) => runner,
+            None => return,
+        };
+        let script = format!(
+            "{}\n$.writeln(app.project.numItems > 0);",
+            TestDataFactory::create_complete_test_scene("E2E Scene")
+        );
+        let output = runner.run("factory_scene.jsx", &script).unwrap();
+        assert_eq!(output.error, None);
+        assert_eq!(output.lines, vec!["true"]);
+    }
+}
@@ -20,6 +20,7 @@ pub mod performance_tests;
 
 use crate::api::objects::*;
 use crate::ScriptValidator;
+use crate::testing::debug_validated;
 use crate::validation::rules::PropertyValueType;
 use std::collections::HashMap;
 use std::time::Instant;
@@ -429,11 +430,11 @@ impl MockAEEnvironment {
         self.created_objects.push(format!("project:{}", name));
         
         // Return ExtendScript to create the project
-        format!("
+        debug_validated(format!("
             // Create new project
             app.newProject();
             app.project.displayStartFrame = 0;
-        ")
+        "))
     }
     
     /// Create a test composition
@@ -451,9 +452,9 @@ impl MockAEEnvironment {
         self.created_objects.push(format!("comp:{}", name));
         
         // Return ExtendScript to create the composition
-        format!("
+        debug_validated(format!("
             var testComp = app.project.items.addComp('{}', {}, {}, 1, {}, {});
-        ", name, width, height, duration, frame_rate)
+        ", name, width, height, duration, frame_rate))
     }
     
     /// Create test layer in active composition
@@ -464,7 +465,7 @@ impl MockAEEnvironment {
         
         self.created_objects.push(format!("layer:{}", name));
         
-        match layer_type {
+        debug_validated(match layer_type {
             "solid" => format!("
                 var testLayer = testComp.layers.addSolid([1, 0, 0], '{}', 100, 100, 1);
             ", name),
@@ -478,12 +479,12 @@ impl MockAEEnvironment {
             _ => format!("
                 var testLayer = testComp.layers.addSolid([0.5, 0.5, 0.5], '{}', 100, 100, 1);
             ", name),
-        }
+        })
     }
     
     /// Generate cleanup script to remove all created objects
     pub fn get_cleanup_script(&self) -> String {
-        format!("
+        debug_validated(format!("
             // Cleanup all test objects
             try {{
                 // Remove all compositions
@@ -501,7 +502,7 @@ impl MockAEEnvironment {
             }} catch (e) {{
                 // Ignore cleanup errors
             }}
-        ")
+        "))
     }
     
     /// Reset environment for next test
@@ -513,7 +514,7 @@ impl MockAEEnvironment {
     
     /// Get a realistic test environment setup script
     pub fn get_environment_setup_script(&self) -> String {
-        format!("
+        debug_validated(format!("
             // Setup mock After Effects environment
             
             // Ensure we have a project
@@ -530,7 +531,7 @@ impl MockAEEnvironment {
             }}
         ", 
         self.preferences.audio_preview_duration,
-        self.preferences.auto_save_enabled)
+        self.preferences.auto_save_enabled))
     }
     
     /// Validate environment state
@@ -550,40 +551,40 @@ pub struct TestDataFactory;
 impl TestDataFactory {
     /// Create a basic composition with standard settings
     pub fn create_test_composition() -> String {
-        "app.project.items.addComp('TestComp', 1920, 1080, 1, 10, 30);".to_string()
+        debug_validated("app.project.items.addComp('TestComp', 1920, 1080, 1, 10, 30);".to_string())
     }
     
     /// Create composition with custom settings
     pub fn create_custom_composition(name: &str, width: u32, height: u32, duration: f64, frame_rate: f64) -> String {
-        format!("app.project.items.addComp('{}', {}, {}, 1, {}, {});", name, width, height, duration, frame_rate)
+        debug_validated(format!("app.project.items.addComp('{}', {}, {}, 1, {}, {});", name, width, height, duration, frame_rate))
     }
     
     /// Create a solid layer with specified color and size
     pub fn create_solid_layer(comp_var: &str, name: &str, color: [f64; 3], width: u32, height: u32) -> String {
-        format!("{}.layers.addSolid([{}, {}, {}], '{}', {}, {}, 1);", 
-                comp_var, color[0], color[1], color[2], name, width, height)
+        debug_validated(format!("{}.layers.addSolid([{}, {}, {}], '{}', {}, {}, 1);", 
+                comp_var, color[0], color[1], color[2], name, width, height))
     }
     
     /// Create a text layer with default properties
     pub fn create_text_layer(comp_var: &str, text: &str) -> String {
-        format!("{}.layers.addText('{}');", comp_var, text)
+        debug_validated(format!("{}.layers.addText('{}');", comp_var, text))
     }
     
     /// Create a null object layer
     pub fn create_null_layer(comp_var: &str, name: &str) -> String {
-        format!("
+        debug_validated(format!("
             var nullLayer = {}.layers.addNull();
             nullLayer.name = '{}';
             nullLayer;
-        ", comp_var, name)
+        ", comp_var, name))
     }
     
     /// Create a camera layer
     pub fn create_camera_layer(comp_var: &str, name: &str) -> String {
-        format!("
+        debug_validated(format!("
             var cameraLayer = {}.layers.addCamera('{}', [0, 0, -500]);
             cameraLayer;
-        ", comp_var, name)
+        ", comp_var, name))
     }
     
     /// Create a light layer
@@ -596,16 +597,16 @@ impl TestDataFactory {
             _ => "LightType.PARALLEL",
         };
         
-        format!("
+        debug_validated(format!("
             var lightLayer = {}.layers.addLight('{}', [0, 0, 0]);
             lightLayer.lightType = {};
             lightLayer;
-        ", comp_var, name, light_type_enum)
+        ", comp_var, name, light_type_enum))
     }
     
     /// Create a shape layer with basic shape
     pub fn create_shape_layer(comp_var: &str, name: &str) -> String {
-        format!("
+        debug_validated(format!("
             var shapeLayer = {}.layers.addShape();
             shapeLayer.name = '{}';
             
@@ -618,47 +619,47 @@ impl TestDataFactory {
             rectFill.color.setValue([1, 0, 0]);
             
             shapeLayer;
-        ", comp_var, name)
+        ", comp_var, name))
     }
     
     /// Create keyframes for position animation
     pub fn create_position_keyframes(layer_var: &str, start_pos: [f64; 2], end_pos: [f64; 2], duration: f64) -> String {
-        format!("
+        debug_validated(format!("
             var positionProp = {}.transform.position;
             positionProp.setValueAtTime(0, [{}, {}]);
             positionProp.setValueAtTime({}, [{}, {}]);
-        ", layer_var, start_pos[0], start_pos[1], duration, end_pos[0], end_pos[1])
+        ", layer_var, start_pos[0], start_pos[1], duration, end_pos[0], end_pos[1]))
     }
     
     /// Create opacity keyframes
     pub fn create_opacity_keyframes(layer_var: &str, start_opacity: f64, end_opacity: f64, duration: f64) -> String {
-        format!("
+        debug_validated(format!("
             var opacityProp = {}.transform.opacity;
             opacityProp.setValueAtTime(0, {});
             opacityProp.setValueAtTime({}, {});
-        ", layer_var, start_opacity, duration, end_opacity)
+        ", layer_var, start_opacity, duration, end_opacity))
     }
     
     /// Create scale keyframes
     pub fn create_scale_keyframes(layer_var: &str, start_scale: [f64; 2], end_scale: [f64; 2], duration: f64) -> String {
-        format!("
+        debug_validated(format!("
             var scaleProp = {}.transform.scale;
             scaleProp.setValueAtTime(0, [{}, {}]);
             scaleProp.setValueAtTime({}, [{}, {}]);
-        ", layer_var, start_scale[0], start_scale[1], duration, end_scale[0], end_scale[1])
+        ", layer_var, start_scale[0], start_scale[1], duration, end_scale[0], end_scale[1]))
     }
     
     /// Add effect to layer
     pub fn add_effect_to_layer(layer_var: &str, effect_name: &str) -> String {
-        format!("
+        debug_validated(format!("
             var effect = {}.effects.addProperty('{}');
             effect;
-        ", layer_var, effect_name)
+        ", layer_var, effect_name))
     }
     
     /// Create text document with formatting
     pub fn create_formatted_text_document(text: &str, font_family: &str, font_size: f64, color: [f64; 3]) -> String {
-        format!("
+        debug_validated(format!("
             var textDoc = new TextDocument('{}');
             textDoc.font = '{}';
             textDoc.fontSize = {};
@@ -666,12 +667,12 @@ impl TestDataFactory {
             textDoc.applyStroke = false;
             textDoc.applyFill = true;
             textDoc;
-        ", text, font_family, font_size, color[0], color[1], color[2])
+        ", text, font_family, font_size, color[0], color[1], color[2]))
     }
     
     /// Create mask on layer
     pub fn create_mask(layer_var: &str, mask_name: &str, shape_type: &str) -> String {
-        match shape_type {
+        debug_validated(match shape_type {
             "rectangle" => format!("
                 var maskGroup = {}.masks.addProperty('Mask');
                 maskGroup.name = '{}';
@@ -701,22 +702,22 @@ impl TestDataFactory {
                 maskGroup.name = '{}';
                 maskGroup;
             ", layer_var, mask_name),
-        }
+        })
     }
     
     /// Create render queue item
     pub fn create_render_queue_item(comp_var: &str) -> String {
-        format!("
+        debug_validated(format!("
             var renderItem = app.project.renderQueue.items.add({});
             var outputModule = renderItem.outputModule(1);
             outputModule.file = new File(Folder.desktop.fsName + '/test_render.mov');
             renderItem;
-        ", comp_var)
+        ", comp_var))
     }
     
     /// Create complete test scene (composition with multiple layers and animation)
     pub fn create_complete_test_scene(scene_name: &str) -> String {
-        format!("
+        debug_validated(format!("
             // Create test scene: {}
             var testComp = app.project.items.addComp('{}', 1920, 1080, 1, 10, 30);
             
@@ -743,12 +744,12 @@ impl TestDataFactory {
             textLayer.transform.opacity.setValueAtTime(10, 0);
             
             testComp;
-        ", scene_name, scene_name)
+        ", scene_name, scene_name))
     }
     
     /// Create performance test scene with many objects
     pub fn create_performance_test_scene(num_layers: u32) -> String {
-        format!("
+        debug_validated(format!("
             // Create performance test scene with {} layers
             var perfComp = app.project.items.addComp('PerformanceTest', 1920, 1080, 1, 30, 30);
             
@@ -774,12 +775,12 @@ impl TestDataFactory {
             }}
             
             perfComp;
-        ", num_layers, num_layers)
+        ", num_layers, num_layers))
     }
     
     /// Get cleanup script for test objects
     pub fn get_cleanup_script() -> String {
-        "
+        debug_validated("
             // Cleanup test objects
             try {
                 for (var i = app.project.items.length; i >= 1; i--) {
@@ -795,7 +796,7 @@ impl TestDataFactory {
             } catch (e) {
                 // Ignore cleanup errors
             }
-        ".to_string()
+        ".to_string())
     }
 }
 
@@ -0,0 +1,152 @@
+//! TypeScript declarations generated from the API model.
+//!
+//! The output follows the layout of `types-for-adobe` (ambient `declare
+//! class` and `declare enum` blocks plus the `app` global), so it can replace
+//! the After Effects declarations of a TS-to-ExtendScript toolchain. Only
+//! what the validator models is declared: a script that type-checks against
+//! these declarations uses no member the validator would reject.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::api::{ApiObject, MethodValidation, UnifiedApi};
+use crate::data::enums::get_enum_definitions;
+use crate::validation::rules::PropertyValueType;
+
+/// ExtendScript globals declared by the shared `types-for-adobe` files
+const SHARED_TYPES: &[&str] = &["File", "Folder", "Date"];
+
+/// Renders declarations for every class and enum in `api`
+pub fn generate_declarations(api: &UnifiedApi) -> String {
+    let enums: BTreeSet<&str> = get_enum_definitions().iter().map(|(name, _)| *name).collect();
+    // Lower-case names are aliases the validator uses for receivers (`layer`, `items`)
+    let classes: BTreeSet<&str> = api.objects.keys()
+        .map(String::as_str)
+        .filter(|name| name.starts_with(|c: char| c.is_ascii_uppercase()))
+        .collect();
+    let types = TypeNames { classes: &classes, enums: &enums };
+
+    let mut output = String::from("// Generated by ae-validator from its API model. Do not edit.\n\n");
+    if classes.contains("Application") {
+        output.push_str("declare const app: Application;\n\n");
+    }
+    for (name, members) in get_enum_definitions() {
+        writeln!(output, "declare enum {} {{", name).unwrap();
+        for member in members {
+            writeln!(output, "  {},", member).unwrap();
+        }
+        output.push_str("}\n\n");
+    }
+    for name in &classes {
+        output.push_str(&class_declaration(name, &api.objects[*name], &types));
+        output.push('\n');
+    }
+    output
+}
+
+struct TypeNames<'a> {
+    classes: &'a BTreeSet<&'a str>,
+    enums: &'a BTreeSet<&'a str>,
+}
+
+impl TypeNames<'_> {
+    fn ts_type(&self, value_type: &PropertyValueType) -> String {
+        match value_type {
+            PropertyValueType::OneD | PropertyValueType::LayerIndex | PropertyValueType::MaskIndex => "number".to_string(),
+            PropertyValueType::TwoD | PropertyValueType::TwoDSpatial => "[number, number]".to_string(),
+            PropertyValueType::ThreeD | PropertyValueType::ThreeDSpatial => "[number, number, number]".to_string(),
+            PropertyValueType::Color => "[number, number, number, number]".to_string(),
+            PropertyValueType::ArbText => "string".to_string(),
+            PropertyValueType::Marker => self.named("MarkerValue"),
+            PropertyValueType::Shape => self.named("Shape"),
+            PropertyValueType::TextDocument => self.named("TextDocument"),
+            PropertyValueType::NoValue | PropertyValueType::CustomValue => "any".to_string(),
+            PropertyValueType::Custom(name) => match name.as_str() {
+                "Boolean" => "boolean".to_string(),
+                "Number" => "number".to_string(),
+                "String" => "string".to_string(),
+                "Array" => "any[]".to_string(),
+                _ => match name.strip_prefix("Array<").and_then(|inner| inner.strip_suffix('>')) {
+                    Some(inner) => format!("{}[]", self.named(inner)),
+                    None => self.named(name),
+                },
+            },
+        }
+    }
+
+    /// A class or enum name, or `any` when the declarations would not define it
+    fn named(&self, name: &str) -> String {
+        if self.classes.contains(name) || self.enums.contains(name) || SHARED_TYPES.contains(&name) {
+            name.to_string()
+        } else {
+            "any".to_string()
+        }
+    }
+}
+
+fn class_declaration(name: &str, object: &ApiObject, types: &TypeNames) -> String {
+    let mut output = String::new();
+    writeln!(output, "declare class {} {{", name).unwrap();
+
+    let mut properties: Vec<_> = object.properties.iter().collect();
+    properties.sort_by(|a, b| a.0.cmp(b.0));
+    for (property, rule) in properties {
+        writeln!(output, "  {}: {};", property, types.ts_type(&rule.value_type)).unwrap();
+    }
+
+    let mut methods: Vec<_> = object.methods.iter().collect();
+    methods.sort_by(|a, b| a.0.cmp(b.0));
+    for (method, validation) in methods {
+        // Overloads become TypeScript overload declarations
+        for signature in validation.signatures() {
+            writeln!(output, "  {}({}): {};", method, parameters(signature, types), return_type(signature, types)).unwrap();
+        }
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+/// Parameters the validator checks, named `argN` unless the model names
+/// them; methods without a modeled signature accept anything
+fn parameters(method: &MethodValidation, types: &TypeNames) -> String {
+    if !method.has_signature() {
+        return "...args: any[]".to_string();
+    }
+    let optional = method.optional_params.len();
+    (0..method.param_count + optional)
+        .map(|i| {
+            let ts_type = method.param_type(i).map_or("any".to_string(), |t| types.ts_type(t));
+            let marker = if i >= method.param_count { "?" } else { "" };
+            // Ranges carry the parameter names where the model declares them
+            let name = method.param_range(i).map_or(format!("arg{}", i), |range| range.parameter_name.clone());
+            format!("{}{}: {}", name, marker, ts_type)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Classes the model does not declare a return for are left open
+fn return_type(method: &MethodValidation, types: &TypeNames) -> String {
+    method.return_type.as_deref().map_or("any".to_string(), |class| types.named(class))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declarations_match_model() {
+        let api = UnifiedApi::new();
+        let declarations = generate_declarations(&api);
+
+        assert!(declarations.contains("declare const app: Application;"));
+        assert!(declarations.contains("declare class CompItem {"));
+        assert!(declarations.contains("declare enum BlendingMode {"));
+        assert!(!declarations.contains("declare class layer {"));
+
+        assert!(declarations.contains(
+            "  addComp(name: string, width: number, height: number, pixelAspect: number, duration: number, frameRate: number): CompItem;"
+        ));
+    }
+}
@@ -0,0 +1,571 @@
+//! Imports TypeScript declarations, such as the After Effects files of the
+//! community `Types-for-Adobe` project, into a validator's API model.
+//!
+//! Members missing from the model are added to a [`LayeredApi`]; members
+//! the model already has are left untouched, and disagreements between the
+//! two are listed in the [`ImportReport`] for review.
+
+use std::collections::{BTreeMap, HashSet};
+use regex::Regex;
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::api::{ApiObject, LayeredApi, MethodValidation, ValidationRule};
+use crate::validation::context::ObjectContext;
+use crate::validation::rules::PropertyValueType;
+use crate::validation::schema::{FieldSchema, ValueSchema};
+
+lazy_static! {
+    static ref CLASS_RE: Regex = Regex::new(
+        r"(?m)^[ \t]*(?:export\s+)?(?:declare\s+)?(?:abstract\s+)?(?:class|interface)\s+(\w+)(?:<[^>{]*>)?(?:\s+extends\s+(\w+)(?:<[^>{]*>)?)?(?:\s+implements\s+[^{]+)?\s*\{"
+    ).unwrap();
+    static ref METHOD_RE: Regex = Regex::new(r"(?s)^(\w+)\??\s*(?:<[^>]*>)?\s*\((.*)\)\s*(?::\s*(.+))?$").unwrap();
+    static ref PROPERTY_RE: Regex = Regex::new(r"(?s)^(\w+)\??\s*:\s*(.+)$").unwrap();
+}
+
+/// A parameter of a declared method
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclaredParam {
+    pub name: String,
+    pub ts_type: String,
+    pub optional: bool,
+    /// `...args: T[]`
+    pub rest: bool,
+}
+
+/// One signature of a declared method; overloads are listed separately
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclaredMethod {
+    pub name: String,
+    pub params: Vec<DeclaredParam>,
+    pub returns: Option<String>,
+}
+
+/// A class or interface from a declaration file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeclaredClass {
+    pub name: String,
+    pub extends: Option<String>,
+    /// Property names and their TypeScript types
+    pub properties: Vec<(String, String)>,
+    pub methods: Vec<DeclaredMethod>,
+}
+
+/// A member the model and the declarations describe differently
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportConflict {
+    /// `Class.member`
+    pub member: String,
+    pub model: String,
+    pub declared: String,
+}
+
+/// What an import added and where it disagreed with the model
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ImportReport {
+    pub added_classes: Vec<String>,
+    pub added_methods: Vec<String>,
+    pub added_properties: Vec<String>,
+    pub conflicts: Vec<ImportConflict>,
+}
+
+impl ImportReport {
+    pub fn to_text(&self) -> String {
+        let mut output = String::new();
+        for (title, entries) in [
+            ("Added classes", &self.added_classes),
+            ("Added methods", &self.added_methods),
+            ("Added properties", &self.added_properties),
+        ] {
+            if !entries.is_empty() {
+                output.push_str(&format!("{} ({}):\n", title, entries.len()));
+                for entry in entries {
+                    output.push_str(&format!("  + {}\n", entry));
+                }
+            }
+        }
+        if !self.conflicts.is_empty() {
+            output.push_str(&format!("Conflicts, kept from the model ({}):\n", self.conflicts.len()));
+            for conflict in &self.conflicts {
+                output.push_str(&format!("  ! {}: model {}, declared {}\n", conflict.member, conflict.model, conflict.declared));
+            }
+        }
+        output.push_str(&format!(
+            "{} classes, {} methods and {} properties added, {} conflicts\n",
+            self.added_classes.len(),
+            self.added_methods.len(),
+            self.added_properties.len(),
+            self.conflicts.len()
+        ));
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Reads the classes and interfaces of a `.d.ts` file. Static members,
+/// constructors, index signatures and accessors are skipped.
+pub fn parse_declarations(source: &str) -> Vec<DeclaredClass> {
+    let source = strip_comments(source);
+    let mut classes = Vec::new();
+
+    for cap in CLASS_RE.captures_iter(&source) {
+        let open = cap.get(0).unwrap().end();
+        let body = match closing(&source[open..], '{', '}') {
+            Some(end) => &source[open..open + end],
+            None => continue,
+        };
+        let mut class = DeclaredClass {
+            name: cap[1].to_string(),
+            extends: cap.get(2).map(|m| m.as_str().to_string()),
+            ..Default::default()
+        };
+
+        for member in split_top_level(body, &[';', '\n']) {
+            let mut member = member.trim();
+            if member.starts_with("static ") || member.starts_with("constructor")
+                || member.starts_with('[') || member.starts_with("get ") || member.starts_with("set ") {
+                continue;
+            }
+            for modifier in ["readonly ", "public ", "protected ", "private "] {
+                member = member.strip_prefix(modifier).unwrap_or(member).trim_start();
+            }
+            if let Some(method) = METHOD_RE.captures(member) {
+                class.methods.push(DeclaredMethod {
+                    name: method[1].to_string(),
+                    params: split_top_level(&method[2], &[','])
+                        .iter()
+                        .filter_map(|param| parse_param(param))
+                        .collect(),
+                    returns: method.get(3).map(|m| m.as_str().trim().to_string()),
+                });
+            } else if let Some(property) = PROPERTY_RE.captures(member) {
+                class.properties.push((property[1].to_string(), property[2].trim().to_string()));
+            }
+        }
+        classes.push(class);
+    }
+
+    classes
+}
+
+fn parse_param(param: &str) -> Option<DeclaredParam> {
+    let param = param.trim();
+    if param.is_empty() {
+        return None;
+    }
+    let rest = param.starts_with("...");
+    let param = param.trim_start_matches("...");
+    let (name, ts_type) = param.split_once(':').unwrap_or((param, "any"));
+    // A default value follows `=`, but not the `=>` of a function type
+    let default_at = ts_type.match_indices('=').map(|(i, _)| i).find(|&i| !ts_type[i + 1..].starts_with('>'));
+    let ts_type = ts_type[..default_at.unwrap_or(ts_type.len())].trim();
+    Some(DeclaredParam {
+        name: name.trim().trim_end_matches('?').to_string(),
+        ts_type: ts_type.to_string(),
+        optional: name.trim().ends_with('?') || param.contains('='),
+        rest,
+    })
+}
+
+/// Adds the members `classes` declare and the model lacks. Inherited members
+/// are flattened into each class, as the model has no inheritance.
+pub fn merge_declarations(api: &mut LayeredApi, classes: &[DeclaredClass]) -> ImportReport {
+    let mut report = ImportReport::default();
+    let by_name: BTreeMap<&str, &DeclaredClass> = classes.iter().map(|c| (c.name.as_str(), c)).collect();
+
+    for class in classes {
+        if !api.contains_object(&class.name) {
+            // The model folds superclasses such as AVItem into their
+            // subclasses; their members are merged there instead
+            if is_folded_superclass(api, &class.name, &by_name) {
+                continue;
+            }
+            api.add_object(&class.name, ApiObject::new(ObjectContext::Custom(class.name.clone())));
+            report.added_classes.push(class.name.clone());
+        }
+
+        let mut seen = HashSet::new();
+        let mut current = Some(class);
+        let mut properties = Vec::new();
+        let mut methods: BTreeMap<&str, Vec<&DeclaredMethod>> = BTreeMap::new();
+        while let Some(declared) = current.filter(|c| seen.insert(c.name.as_str())) {
+            properties.extend(declared.properties.iter());
+            for method in &declared.methods {
+                methods.entry(method.name.as_str()).or_default().push(method);
+            }
+            current = declared.extends.as_deref().and_then(|parent| by_name.get(parent).copied());
+        }
+
+        for (name, ts_type) in properties {
+            let member = format!("{}.{}", class.name, name);
+            let declared = value_type(ts_type);
+            match api.object(&class.name).and_then(|o| o.properties.get(name)) {
+                Some(rule) => {
+                    if let (Some(model), Some(imported)) = (category(&rule.value_type), category(&declared)) {
+                        if model != imported {
+                            report.conflicts.push(ImportConflict { member, model: format!("{:?}", rule.value_type), declared: ts_type.clone() });
+                        }
+                    }
+                }
+                None => {
+                    api.add_property(&class.name, name, ValidationRule::simple(declared));
+                    report.added_properties.push(member);
+                }
+            }
+        }
+
+        for (name, overloads) in methods {
+            let member = format!("{}.{}", class.name, name);
+            let declared = method_validation(&overloads);
+            match api.method(&class.name, name) {
+                Some(model) => {
+                    if let Some(conflict) = signature_conflict(model, &declared) {
+                        report.conflicts.push(ImportConflict { member, model: conflict.0, declared: conflict.1 });
+                    }
+                }
+                None => {
+                    api.add_method(&class.name, name, declared);
+                    report.added_methods.push(member);
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Whether `name` is a superclass of a class the model has, e.g. `AVItem`
+/// of `CompItem`, so the model already covers it through that subclass
+fn is_folded_superclass(api: &LayeredApi, name: &str, by_name: &BTreeMap<&str, &DeclaredClass>) -> bool {
+    by_name.values().any(|class| {
+        let mut seen = HashSet::new();
+        let mut parent = class.extends.as_deref();
+        while let Some(current) = parent.filter(|p| seen.insert(*p)) {
+            if current == name {
+                return api.contains_object(&class.name);
+            }
+            parent = by_name.get(current).and_then(|c| c.extends.as_deref());
+        }
+        false
+    })
+}
+
+/// Each declared overload becomes a checked signature; rest parameters
+/// leave the argument list unchecked
+fn method_validation(overloads: &[&DeclaredMethod]) -> MethodValidation {
+    if overloads.iter().any(|method| method.params.iter().any(|p| p.rest)) {
+        return MethodValidation::new(0);
+    }
+    let mut signatures = overloads.iter().map(|method| declared_signature(method));
+    match signatures.next() {
+        Some(first) => signatures.fold(first, MethodValidation::with_overload),
+        None => MethodValidation::new(0),
+    }
+}
+
+fn declared_signature(method: &DeclaredMethod) -> MethodValidation {
+    let required: Vec<PropertyValueType> = method.params.iter().filter(|p| !p.optional).map(|p| value_type(&p.ts_type)).collect();
+    let optional: Vec<PropertyValueType> = method.params.iter().filter(|p| p.optional).map(|p| value_type(&p.ts_type)).collect();
+    // Parameters in model order, required first
+    let schemas: Vec<Option<ValueSchema>> = method.params.iter().filter(|p| !p.optional)
+        .chain(method.params.iter().filter(|p| p.optional))
+        .map(|p| options_schema(&p.ts_type))
+        .collect();
+    let mut validation = MethodValidation::new(required.len())
+        .with_param_types(required)
+        .with_optional_params(optional);
+    if schemas.iter().any(Option::is_some) {
+        validation = validation.with_param_schemas(schemas);
+    }
+    if let Some(class) = method.returns.as_deref().filter(|r| is_class_name(r)) {
+        validation = validation.with_return_type(class);
+    }
+    validation
+}
+
+/// How the model's signature differs from the declared one; an overloaded
+/// declaration agrees when any of its signatures does
+fn signature_conflict(model: &MethodValidation, declared: &MethodValidation) -> Option<(String, String)> {
+    if !model.has_signature() || !declared.has_signature() {
+        return None;
+    }
+    let mut differences = declared.signatures()
+        .map(|signature| signature_difference(model, signature))
+        .collect::<Option<Vec<_>>>()?;
+    Some(differences.remove(0))
+}
+
+fn signature_difference(model: &MethodValidation, declared: &MethodValidation) -> Option<(String, String)> {
+    if model.param_count != declared.param_count {
+        return Some((format!("{} required parameters", model.param_count), format!("{} required parameters", declared.param_count)));
+    }
+    match (&model.return_type, &declared.return_type) {
+        (Some(model), Some(declared)) if model != declared => Some((format!("returns {}", model), format!("returns {}", declared))),
+        _ => None,
+    }
+}
+
+/// Maps a TypeScript type onto the model's value types; anything the model
+/// cannot express becomes `CustomValue`
+fn value_type(ts_type: &str) -> PropertyValueType {
+    let ts_type = ts_type.trim().trim_end_matches("| null").trim_end_matches("| undefined").trim();
+    match ts_type {
+        "number" => return PropertyValueType::OneD,
+        "string" => return PropertyValueType::ArbText,
+        "boolean" => return PropertyValueType::Custom("Boolean".to_string()),
+        _ => {}
+    }
+    if let Some(inner) = ts_type.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return match split_top_level(inner, &[',']).iter().filter(|e| e.trim() == "number").count() {
+            2 => PropertyValueType::TwoD,
+            3 => PropertyValueType::ThreeD,
+            4 => PropertyValueType::Color,
+            _ => PropertyValueType::Custom("Array".to_string()),
+        };
+    }
+    if ts_type.ends_with("[]") || ts_type.starts_with("Array<") {
+        return PropertyValueType::Custom("Array".to_string());
+    }
+    if is_class_name(ts_type) {
+        return PropertyValueType::Custom(ts_type.to_string());
+    }
+    PropertyValueType::CustomValue
+}
+
+/// An inline object type such as `{ width: number; label?: string }`
+/// becomes an options schema, so call sites are checked key by key
+fn options_schema(ts_type: &str) -> Option<ValueSchema> {
+    let body = ts_type.trim().strip_prefix('{')?.strip_suffix('}')?;
+    let fields = split_top_level(body, &[',', ';', '\n'])
+        .iter()
+        .filter_map(|field| {
+            let (name, field_type) = field.split_once(':')?;
+            let name = name.trim();
+            let schema = FieldSchema::new(name.trim_end_matches('?'), field_schema(field_type));
+            Some(if name.ends_with('?') { schema.optional() } else { schema })
+        })
+        .collect();
+    Some(ValueSchema::Options(fields))
+}
+
+fn field_schema(ts_type: &str) -> ValueSchema {
+    let ts_type = ts_type.trim().trim_end_matches("| null").trim_end_matches("| undefined").trim();
+    match ts_type {
+        "number" => return ValueSchema::number(),
+        "string" => return ValueSchema::String,
+        "boolean" => return ValueSchema::Boolean,
+        _ => {}
+    }
+    if let Some(options) = options_schema(ts_type) {
+        return options;
+    }
+    if let Some(inner) = ts_type.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        let elements = split_top_level(inner, &[',']);
+        if elements.iter().all(|e| e.trim() == "number") {
+            return ValueSchema::array_of(ValueSchema::number(), elements.len(), elements.len());
+        }
+    }
+    match ts_type.strip_suffix("[]") {
+        Some(item) => ValueSchema::Array { items: Box::new(field_schema(item)), min_len: None, max_len: None },
+        None => ValueSchema::Any,
+    }
+}
+
+fn is_class_name(ts_type: &str) -> bool {
+    ts_type.starts_with(|c: char| c.is_ascii_uppercase()) && ts_type.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Coarse kind of a value type, so `OneD` and `Custom("Number")` agree;
+/// `None` when the type says too little to compare
+fn category(value_type: &PropertyValueType) -> Option<String> {
+    match value_type {
+        PropertyValueType::OneD | PropertyValueType::LayerIndex | PropertyValueType::MaskIndex => Some("number".to_string()),
+        PropertyValueType::ArbText => Some("string".to_string()),
+        PropertyValueType::TwoD | PropertyValueType::TwoDSpatial | PropertyValueType::ThreeD
+            | PropertyValueType::ThreeDSpatial | PropertyValueType::Color => Some("array".to_string()),
+        PropertyValueType::Custom(name) => match name.as_str() {
+            "Number" => Some("number".to_string()),
+            "String" => Some("string".to_string()),
+            "Boolean" => Some("boolean".to_string()),
+            "Any" | "Object" => None,
+            name if name == "Array" || name.starts_with("Array<") => Some("array".to_string()),
+            name => Some(name.to_string()),
+        },
+        _ => None,
+    }
+}
+
+/// Removes `//` and `/* */` comments, leaving string literals alone
+fn strip_comments(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote = None;
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                output.push(c);
+                if c == '\\' {
+                    output.extend(chars.next());
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '/' && chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            None if c == '/' && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push('\n');
+                    }
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            None => {
+                if c == '"' || c == '\'' || c == '`' {
+                    quote = Some(c);
+                }
+                output.push(c);
+            }
+        }
+    }
+    output
+}
+
+/// Byte offset of the bracket closing an already opened one
+fn closing(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 1usize;
+    for (i, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// Splits on `separators` outside brackets, braces, parentheses and generics
+fn split_top_level<'a>(text: &'a str, separators: &[char]) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut previous = None;
+    for (i, c) in text.char_indices() {
+        let arrow = c == '>' && previous == Some('=');
+        previous = Some(c);
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            // The `>` of `=>` in a function type closes nothing
+            '>' if arrow => {}
+            ')' | ']' | '}' | '>' => depth -= 1,
+            _ if depth == 0 && separators.contains(&c) => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().filter(|p| !p.trim().is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARATIONS: &str = r#"
+/** An item in a project */
+declare class AVItem extends Item {
+  /** The width of the item, in pixels. */
+  width: string
+  getRenderGUID(): string
+}
+
+declare class CompItem extends AVItem {
+  bgColor: [number, number, number]
+  // Overloaded: by index or relative to another layer
+  layer(index: number): Layer
+  layer(otherLayer: Layer, relIndex: number): Layer
+  duplicate(): CompItem
+  static create(): CompItem
+}
+
+declare class ItemCollection {
+  addComp(name: string, width: number, height: number): CompItem
+}
+
+declare class Viewport {
+  readonly zoom: number
+  setZoom(zoom: number, animate?: boolean): void
+  frame(layer: Layer): void
+  frame(center: [number, number], zoom: number): void
+  capture(options?: { width: number; height: number; format?: string; layers?: number[] }): File
+}
+"#;
+
+    #[test]
+    fn test_arrow_typed_members_keep_later_members() {
+        let classes = parse_declarations("interface RenderOptions {\n  cb: (x: number) => void; other: string\n  run(done: () => void, retries = 2): void\n  last: number\n}\n");
+        let properties: Vec<(&str, &str)> = classes[0].properties.iter().map(|(n, t)| (n.as_str(), t.as_str())).collect();
+        assert_eq!(properties, vec![("cb", "(x: number) => void"), ("other", "string"), ("last", "number")]);
+        assert_eq!(classes[0].methods[0].name, "run");
+        assert_eq!(classes[0].methods[0].params[0].ts_type, "() => void");
+        assert!(classes[0].methods[0].params[1].optional);
+    }
+
+    #[test]
+    fn test_parses_and_merges_declarations() {
+        let classes = parse_declarations(DECLARATIONS);
+        let names: Vec<&str> = classes.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["AVItem", "CompItem", "ItemCollection", "Viewport"]);
+        assert_eq!(classes[1].extends.as_deref(), Some("AVItem"));
+        assert_eq!(classes[1].methods.len(), 3);
+        assert_eq!(classes[3].methods[0].params[1], DeclaredParam {
+            name: "animate".to_string(), ts_type: "boolean".to_string(), optional: true, rest: false,
+        });
+
+        let mut api = LayeredApi::new();
+        let report = merge_declarations(&mut api, &classes);
+        assert_eq!(report.added_classes, vec!["Viewport"]);
+        assert!(!api.contains_object("AVItem"));
+        assert!(report.added_methods.contains(&"CompItem.getRenderGUID".to_string()));
+        assert!(!report.added_methods.contains(&"CompItem.layer".to_string()));
+        assert!(api.validate_method("Viewport", "setZoom"));
+        assert!(api.method("Viewport", "setZoom").unwrap().accepts_arg_count(2));
+        let frame = api.method("Viewport", "frame").unwrap();
+        assert_eq!(frame.overloads.len(), 1);
+        assert_eq!(frame.signatures().map(|s| s.param_count).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(frame.overloads[0].param_types[0], PropertyValueType::TwoD);
+        let capture = api.method("Viewport", "capture").unwrap().param_schema(0).unwrap();
+        assert!(capture.validate(&serde_json::json!({ "width": 1920, "height": 1080, "layers": [1, 2] })).is_ok());
+        assert_eq!(capture.validate(&serde_json::json!({ "width": 1920 })).unwrap_err(), "value.height is missing");
+        assert_eq!(
+            capture.validate(&serde_json::json!({ "width": 1920, "height": 1080, "fromat": "png" })).unwrap_err(),
+            "Value has no option 'fromat'; did you mean 'format'?"
+        );
+        assert!(api.validate_property("Viewport", "zoom"));
+        assert!(!LayeredApi::new().validate_method("CompItem", "getRenderGUID"));
+
+        let conflicts: Vec<&str> = report.conflicts.iter().map(|c| c.member.as_str()).collect();
+        assert!(conflicts.contains(&"CompItem.width"));
+        assert!(conflicts.contains(&"ItemCollection.addComp"));
+        assert_eq!(api.method("ItemCollection", "addComp").unwrap().param_count, 6);
+    }
+}
@@ -0,0 +1,206 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::inference::{infer_expression_type, infer_variable_types};
+use super::script::lexer::{matching_bracket, significant_tokens, Token};
+use crate::api::objects::app::{is_alternate_source_class, AV_ITEM_CLASSES};
+
+lazy_static! {
+    static ref READ_ONLY_RE: Regex = Regex::new(r"\b(\w+)\.(alternateSource|canSetAlternateSource|essentialPropertySource)\s*=[^=]").unwrap();
+    static ref ESSENTIAL_RE: Regex = Regex::new(r"\b(\w+)\.essentialProperty\b").unwrap();
+    static ref ASSIGNMENT_RE: Regex = Regex::new(r"\b(\w+)\s*=\s*([^=;\n][^;\n]*)").unwrap();
+    static ref ADD_LAYER_RE: Regex = Regex::new(r"\blayers\.(add\w+)\s*\(").unwrap();
+}
+
+/// Layers created by these `layers.add*` methods never have a comp source,
+/// so they have no Essential Properties to replace media through
+const SOURCELESS_LAYERS: &[(&str, &str)] = &[
+    ("addText", "text"),
+    ("addBoxText", "text"),
+    ("addShape", "shape"),
+    ("addCamera", "camera"),
+    ("addLight", "light"),
+    ("addNull", "null"),
+    ("addSolid", "solid"),
+];
+
+/// Validates media replacement in template-swapping scripts:
+///
+/// - `alternateSource`, `canSetAlternateSource` and `essentialPropertySource`
+///   are read-only
+/// - `setAlternateSource()` takes a comp or footage item, and should only be
+///   called after checking `canSetAlternateSource`
+/// - `essentialProperty` only exists on precomp layers
+pub fn validate_alternate_sources(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let variable_types = infer_variable_types(script);
+    let mut errors = Vec::new();
+
+    for cap in READ_ONLY_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        let suggestion = match &cap[2] {
+            "alternateSource" => format!("Replace the media with {}.setAlternateSource(item)", &cap[1]),
+            _ => "Read the value instead of assigning it".to_string(),
+        };
+        errors.push(diagnostic(
+            script, file_path, m.start(), m.end() - 1,
+            format!("'{}.{}' is read-only", &cap[1], &cap[2]),
+            suggestion,
+            "alternate-source-read-only",
+            ErrorSeverity::Error,
+        ));
+    }
+
+    let tokens = significant_tokens(script);
+    for (i, window) in tokens.windows(4).enumerate() {
+        if !(window[0].is_identifier() && window[1].is_punct(".") && window[2].text == "setAlternateSource" && window[3].is_punct("(")) {
+            continue;
+        }
+        let close = match matching_bracket(&tokens, i + 3) {
+            Some(close) => close,
+            None => continue,
+        };
+        let (start, end) = (window[0].start, tokens[close].end);
+        let receiver = window[0].text.as_str();
+        let source = script[window[3].end..tokens[close].start].trim();
+        let class = infer_expression_type(source, &variable_types)
+            .filter(|class| class != "Item" && !is_alternate_source_class("AVItem", class));
+        if let Some(class) = class {
+            errors.push(diagnostic(
+                script, file_path, start, end,
+                format!("setAlternateSource() expects {}, but '{}' is a {}", AV_ITEM_CLASSES.join(" or "), source, class),
+                "Pass the comp or footage item that should replace the media".to_string(),
+                "alternate-source-type",
+                ErrorSeverity::Error,
+            ));
+        }
+
+        let checked = tokens[..i].windows(3).enumerate().any(|(j, check)| {
+            check[0].text == receiver && check[1].is_punct(".") && check[2].text == "canSetAlternateSource"
+                && guarded_range(&tokens, j).is_some_and(|(from, to)| from < i && i < to)
+        });
+        if !checked {
+            errors.push(diagnostic(
+                script, file_path, start, end,
+                format!("'{}.setAlternateSource()' is called without checking canSetAlternateSource", receiver),
+                format!("Only Media Replacement properties accept a new source: if ({}.canSetAlternateSource) {{ ... }}", receiver),
+                "alternate-source-unchecked",
+                ErrorSeverity::Warning,
+            ));
+        }
+    }
+
+    for cap in ESSENTIAL_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        let layer = &cap[1];
+        let created_by = ASSIGNMENT_RE.captures_iter(&script[..m.start()])
+            .filter(|assignment| &assignment[1] == layer)
+            .last()
+            .and_then(|assignment| ADD_LAYER_RE.captures(&assignment[2]).map(|add| add[1].to_string()));
+        let kind = created_by.and_then(|method| SOURCELESS_LAYERS.iter().find(|(add, _)| *add == method).map(|(_, kind)| *kind));
+        if let Some(kind) = kind {
+            errors.push(diagnostic(
+                script, file_path, m.start(), m.end(),
+                format!("'{}' is a {} layer; only precomp layers have Essential Properties", layer, kind),
+                "Add the comp as a layer with comp.layers.add(templateComp) and read its essentialProperty".to_string(),
+                "alternate-source-layer",
+                ErrorSeverity::Error,
+            ));
+        }
+    }
+
+    errors.sort_by_key(|e| (e.get_context().line, e.get_context().column));
+    errors
+}
+
+/// Token range a `canSetAlternateSource` check at `check` guards: the branch
+/// of the `if` whose condition it is in, or otherwise the rest of its
+/// enclosing block (e.g. after `if (!media.canSetAlternateSource) return;`)
+fn guarded_range(tokens: &[Token], check: usize) -> Option<(usize, usize)> {
+    let negated = check > 0 && tokens[check - 1].is_punct("!");
+    for k in (0..check).rev() {
+        let token = &tokens[k];
+        // The innermost bracket still open at the check
+        let close = match matching_bracket(tokens, k) {
+            Some(close) if close < check => continue,
+            Some(close) => close,
+            None if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") => tokens.len(),
+            None => continue,
+        };
+        if token.is_punct("(") && !negated && k > 0 && tokens[k - 1].is_keyword("if") {
+            let branch_end = if tokens.get(close + 1).is_some_and(|t| t.is_punct("{")) {
+                matching_bracket(tokens, close + 1)?
+            } else {
+                (close + 1..tokens.len()).find(|&t| tokens[t].is_punct(";"))?
+            };
+            return Some((close, branch_end));
+        }
+        if token.is_punct("{") {
+            return Some((check, close));
+        }
+    }
+    Some((check, tokens.len()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn diagnostic(
+    script: &str,
+    file_path: &str,
+    start: usize,
+    end: usize,
+    message: String,
+    suggestion: String,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let line = script[..start].matches('\n').count() + 1;
+    let line_start = script[..start].rfind('\n').map_or(0, |i| i + 1);
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(script[line_start..start].chars().count() + 1)
+        .length(end - start)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule(rule)
+        .build();
+
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_replacement_checks() {
+        let script = r#"var precomp = comp.layers.add(template);
+var title = comp.layers.addText("Title");
+var media = precomp.essentialProperty.property("Media");
+var caption = title.essentialProperty.property("Caption");
+var folder = Folder.selectDialog("Pick");
+if (media.canSetAlternateSource) {
+    media.setAlternateSource(app.project.activeItem);
+    media.setAlternateSource(folder);
+}
+caption.setAlternateSource(app.project.item(2));
+media.alternateSource = footage;
+if (caption.canSetAlternateSource) {
+    var ok = true;
+}
+caption.setAlternateSource(app.project.item(3));
+function swap(layer) {
+    if (!layer.canSetAlternateSource) return;
+    layer.setAlternateSource(app.project.activeItem);
+}
+"#;
+        let errors = validate_alternate_sources(script, "swap.jsx");
+        let found: Vec<(usize, &str)> = errors.iter().map(|e| (e.get_context().line.unwrap(), e.get_message())).collect();
+        assert_eq!(found, vec![
+            (4, "'title' is a text layer; only precomp layers have Essential Properties"),
+            (8, "setAlternateSource() expects CompItem or FootageItem, but 'folder' is a Folder"),
+            (10, "'caption.setAlternateSource()' is called without checking canSetAlternateSource"),
+            (11, "'media.alternateSource' is read-only"),
+            (15, "'caption.setAlternateSource()' is called without checking canSetAlternateSource"),
+        ]);
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{significant_tokens, Token, TokenKind};
+use crate::api::documentation::SCRIPTING_GUIDE_URL;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Group {
+    Camera,
+    Light,
+}
+
+impl Group {
+    /// The group's match names in the scripting guide
+    fn docs_url(self) -> String {
+        let page = match self {
+            Group::Camera => "matchnames/layer/cameralayer",
+            Group::Light => "matchnames/layer/lightlayer",
+        };
+        format!("{}/{}/", SCRIPTING_GUIDE_URL, page)
+    }
+}
+
+/// What a property depends on, or switches on
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Plain,
+    /// Only rendered while Depth of Field is on
+    DepthOfField,
+    /// Only rendered while the light casts shadows
+    Shadow,
+    DepthOfFieldSwitch,
+    ShadowSwitch,
+}
+
+/// A property of the Camera Options or Light Options group
+struct OptionProperty {
+    group: Group,
+    attribute: &'static str,
+    display_name: &'static str,
+    match_name: &'static str,
+    /// Lowest value, and whether the value must be strictly greater
+    min: Option<(f64, bool)>,
+    max: Option<f64>,
+    role: Role,
+}
+
+const fn option(group: Group, attribute: &'static str, display_name: &'static str, match_name: &'static str, min: Option<(f64, bool)>, max: Option<f64>, role: Role) -> OptionProperty {
+    OptionProperty { group, attribute, display_name, match_name, min, max, role }
+}
+
+const OPTIONS: &[OptionProperty] = &[
+    option(Group::Camera, "zoom", "Zoom", "ADBE Camera Zoom", Some((0.0, true)), None, Role::Plain),
+    option(Group::Camera, "depthOfField", "Depth of Field", "ADBE Camera Depth of Field", None, None, Role::DepthOfFieldSwitch),
+    option(Group::Camera, "focusDistance", "Focus Distance", "ADBE Camera Focus Distance", Some((0.0, true)), None, Role::DepthOfField),
+    option(Group::Camera, "aperture", "Aperture", "ADBE Camera Aperture", Some((0.0, true)), None, Role::DepthOfField),
+    option(Group::Camera, "blurLevel", "Blur Level", "ADBE Camera Blur Level", Some((0.0, false)), None, Role::DepthOfField),
+    option(Group::Camera, "irisShape", "Iris Shape", "ADBE Camera Iris Shape", None, None, Role::DepthOfField),
+    option(Group::Camera, "irisRotation", "Iris Rotation", "ADBE Camera Iris Rotation", None, None, Role::DepthOfField),
+    option(Group::Camera, "irisRoundness", "Iris Roundness", "ADBE Camera Iris Roundness", Some((0.0, false)), Some(100.0), Role::DepthOfField),
+    option(Group::Camera, "irisAspectRatio", "Iris Aspect Ratio", "ADBE Camera Iris Aspect Ratio", Some((0.0, true)), None, Role::DepthOfField),
+    option(Group::Camera, "irisDiffractionFringe", "Iris Diffraction Fringe", "ADBE Camera Iris Diffraction Fringe", Some((0.0, false)), None, Role::DepthOfField),
+    option(Group::Camera, "highlightGain", "Highlight Gain", "ADBE Camera Highlight Gain", Some((0.0, false)), Some(100.0), Role::DepthOfField),
+    option(Group::Camera, "highlightThreshold", "Highlight Threshold", "ADBE Camera Highlight Threshold", Some((0.0, false)), None, Role::DepthOfField),
+    option(Group::Camera, "highlightSaturation", "Highlight Saturation", "ADBE Camera Highlight Saturation", Some((0.0, false)), Some(100.0), Role::DepthOfField),
+    option(Group::Light, "intensity", "Intensity", "ADBE Light Intensity", None, None, Role::Plain),
+    option(Group::Light, "coneAngle", "Cone Angle", "ADBE Light Cone Angle", Some((0.0, false)), Some(180.0), Role::Plain),
+    option(Group::Light, "coneFeather", "Cone Feather", "ADBE Light Cone Feather", Some((0.0, false)), Some(100.0), Role::Plain),
+    option(Group::Light, "radius", "Radius", "ADBE Light Falloff Start", Some((0.0, false)), None, Role::Plain),
+    option(Group::Light, "falloffDistance", "Falloff Distance", "ADBE Light Falloff Distance", Some((0.0, false)), None, Role::Plain),
+    option(Group::Light, "castsShadows", "Casts Shadows", "ADBE Casts Shadows", None, None, Role::ShadowSwitch),
+    option(Group::Light, "shadowDarkness", "Shadow Darkness", "ADBE Light Shadow Darkness", Some((0.0, false)), Some(100.0), Role::Shadow),
+    option(Group::Light, "shadowDiffusion", "Shadow Diffusion", "ADBE Light Shadow Diffusion", Some((0.0, false)), None, Role::Shadow),
+];
+
+/// The options group reached through `.cameraOption`, `.lightOption` or
+/// `.property(...)` at `j`, and the index after it
+fn options_group(tokens: &[Token], j: usize) -> Option<(Group, usize)> {
+    match tokens.get(j..j + 2) {
+        Some([dot, member]) if dot.is_punct(".") && member.text == "cameraOption" => return Some((Group::Camera, j + 2)),
+        Some([dot, member]) if dot.is_punct(".") && member.text == "lightOption" => return Some((Group::Light, j + 2)),
+        _ => {}
+    }
+    let arg = property_call(tokens, j)?;
+    match unquote(&tokens[arg].text) {
+        "Camera Options" | "ADBE Camera Options Group" => Some((Group::Camera, j + 5)),
+        "Light Options" | "ADBE Light Options Group" => Some((Group::Light, j + 5)),
+        _ => None,
+    }
+}
+
+/// What is known about a camera or light a variable holds
+#[derive(Debug, Clone, Copy, Default)]
+struct Switches {
+    depth_of_field: Option<bool>,
+    casts_shadows: Option<bool>,
+}
+
+/// A variable holding a layer, or the options group of the layer in `owner`
+#[derive(Debug, Clone)]
+enum Holder {
+    Layer(Switches),
+    Options { group: Group, owner: String },
+}
+
+/// Checks values set on Camera Options and Light Options properties:
+///
+/// - literal values must be in the property's range, e.g. a zoom above 0
+///   and a cone angle from 0 to 180
+/// - shadow settings do nothing while the light does not cast shadows, and
+///   animating depth-of-field properties does nothing while Depth of Field
+///   is off. Cameras and lights created by the script start with both off.
+///
+/// Like property constraints, values are tracked per variable in source
+/// order; reassigning a variable forgets what is known about it.
+pub fn validate_camera_light_options(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let mut holders: HashMap<String, Holder> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_identifier() || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+
+        // `var cam = comp.layers.addCamera(...)`, `var opts = cam.cameraOption`
+        if tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+            match holder_value(&tokens, i + 2) {
+                Some(holder) => holders.insert(token.text.clone(), holder),
+                None => holders.remove(&token.text),
+            };
+            continue;
+        }
+
+        let (group, owner, j) = match holders.get(&token.text) {
+            Some(Holder::Options { group, owner }) => (*group, owner.clone(), i + 1),
+            _ => match options_group(&tokens, i + 1) {
+                Some((group, j)) => (group, token.text.clone(), j),
+                None => continue,
+            },
+        };
+        let (property, name_token, j) = match option_property(&tokens, group, j) {
+            Some(found) => found,
+            None => continue,
+        };
+        let (method, args) = match tokens.get(j..j + 3) {
+            Some([dot, method, open]) if dot.is_punct(".") && open.is_punct("(") => (method.text.as_str(), j + 3),
+            _ => continue,
+        };
+        let value = match method {
+            "setValue" => number(&tokens, args),
+            "setValueAtTime" => number(&tokens, args).and_then(|(_, next)| {
+                tokens.get(next).filter(|t| t.is_punct(","))?;
+                number(&tokens, next + 1)
+            }),
+            "setValuesAtTimes" | "addKey" => None,
+            _ => continue,
+        };
+        let value = value
+            .filter(|(_, next)| tokens.get(*next).is_some_and(|t| t.is_punct(")")))
+            .map(|(value, _)| value);
+        let animated = method != "setValue";
+
+        if let Some(value) = value {
+            if let Some(message) = out_of_range(property, value) {
+                errors.push(option_error(script, file_path, name_token, property.group, message, range_hint(property), "option-range", ErrorSeverity::Error));
+            }
+        }
+
+        let switches = match holders.entry(owner.clone()).or_insert_with(|| Holder::Layer(Switches::default())) {
+            Holder::Layer(switches) => switches,
+            Holder::Options { .. } => continue,
+        };
+        match property.role {
+            Role::DepthOfFieldSwitch => switches.depth_of_field = if animated { None } else { value.map(|v| v != 0.0) },
+            Role::ShadowSwitch => switches.casts_shadows = if animated { None } else { value.map(|v| v != 0.0) },
+            Role::DepthOfField if animated && switches.depth_of_field == Some(false) => errors.push(option_error(
+                script, file_path, name_token, property.group,
+                format!("{} is animated, but Depth of Field is off on '{}', so the animation has no visible effect", property.display_name, owner),
+                format!("Turn Depth of Field on first: {}.cameraOption.depthOfField.setValue(1)", owner),
+                "option-inactive",
+                ErrorSeverity::Warning,
+            )),
+            Role::Shadow if switches.casts_shadows == Some(false) => errors.push(option_error(
+                script, file_path, name_token, property.group,
+                format!("{} has no effect, since '{}' does not cast shadows", property.display_name, owner),
+                format!("Turn shadows on first: {}.lightOption.castsShadows.setValue(1)", owner),
+                "option-inactive",
+                ErrorSeverity::Warning,
+            )),
+            _ => {}
+        }
+    }
+
+    errors
+}
+
+/// What a variable holds after `= <value at start>`: a new camera or
+/// light, an options group, or an unknown layer when the value is not
+/// recognised
+fn holder_value(tokens: &[Token], start: usize) -> Option<Holder> {
+    let end = tokens[start.min(tokens.len())..].iter()
+        .position(|t| !(t.is_identifier() || t.is_punct(".")))
+        .map_or(tokens.len(), |offset| start + offset);
+    match tokens.get(end.saturating_sub(1)..=end) {
+        Some([method, open]) if open.is_punct("(") && method.text == "addCamera" => {
+            return Some(Holder::Layer(Switches { depth_of_field: Some(false), casts_shadows: None }));
+        }
+        Some([method, open]) if open.is_punct("(") && method.text == "addLight" => {
+            return Some(Holder::Layer(Switches { depth_of_field: None, casts_shadows: Some(false) }));
+        }
+        _ => {}
+    }
+    if !tokens.get(start).is_some_and(|t| t.is_identifier()) {
+        return None;
+    }
+    let (group, next) = options_group(tokens, start + 1)?;
+    let ends = tokens.get(next).is_none_or(|t| t.is_punct(";") || t.newlines_before > 0);
+    ends.then(|| Holder::Options { group, owner: tokens[start].text.clone() })
+}
+
+/// The option property at `j` in `group`: `.zoom` or `.property("Zoom")`,
+/// with the token naming it and the index after it
+fn option_property(tokens: &[Token], group: Group, j: usize) -> Option<(&'static OptionProperty, &Token, usize)> {
+    let in_group = |name: &str| OPTIONS.iter().find(|p| p.group == group && (p.display_name == name || p.match_name == name));
+    if let Some(arg) = property_call(tokens, j) {
+        return in_group(unquote(&tokens[arg].text)).map(|p| (p, &tokens[arg], j + 5));
+    }
+    match tokens.get(j..j + 2) {
+        Some([dot, member]) if dot.is_punct(".") => OPTIONS.iter()
+            .find(|p| p.group == group && p.attribute == member.text)
+            .map(|p| (p, member, j + 2)),
+        _ => None,
+    }
+}
+
+fn out_of_range(property: &OptionProperty, value: f64) -> Option<String> {
+    let too_low = property.min.is_some_and(|(min, exclusive)| if exclusive { value <= min } else { value < min });
+    let too_high = property.max.is_some_and(|max| value > max);
+    (too_low || too_high).then(|| format!("{} is out of range: {}", property.display_name, value))
+}
+
+fn range_hint(property: &OptionProperty) -> String {
+    match (property.min, property.max) {
+        (Some((min, _)), Some(max)) => format!("{} must be from {} to {}", property.display_name, min, max),
+        (Some((min, true)), None) => format!("{} must be greater than {}", property.display_name, min),
+        (Some((min, false)), None) => format!("{} must be at least {}", property.display_name, min),
+        (None, Some(max)) => format!("{} must be at most {}", property.display_name, max),
+        (None, None) => String::new(),
+    }
+}
+
+/// `.property("name")` at `j`, as the index of the string argument
+fn property_call(tokens: &[Token], j: usize) -> Option<usize> {
+    match tokens.get(j..j + 5) {
+        Some([dot, method, open, arg, close])
+            if dot.is_punct(".") && method.text == "property" && open.is_punct("(")
+                && arg.kind == TokenKind::String && close.is_punct(")") => Some(j + 3),
+        _ => None,
+    }
+}
+
+/// A number or boolean literal at `i`, optionally negated, and the index after it
+fn number(tokens: &[Token], i: usize) -> Option<(f64, usize)> {
+    match tokens.get(i)? {
+        t if t.is_keyword("true") => return Some((1.0, i + 1)),
+        t if t.is_keyword("false") => return Some((0.0, i + 1)),
+        _ => {}
+    }
+    let negative = tokens.get(i)?.is_punct("-");
+    let token = tokens.get(if negative { i + 1 } else { i }).filter(|t| t.kind == TokenKind::Number)?;
+    let value: f64 = token.text.parse().ok()?;
+    Some((if negative { -value } else { value }, if negative { i + 2 } else { i + 1 }))
+}
+
+fn unquote(text: &str) -> &str {
+    text.get(1..text.len().saturating_sub(1)).unwrap_or("")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn option_error(
+    script: &str,
+    file_path: &str,
+    token: &Token,
+    group: Group,
+    message: String,
+    suggestion: String,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .docs_url(Some(group.docs_url()))
+        .rule(rule)
+        .build();
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_and_light_options() {
+        let script = r#"var cam = comp.layers.addCamera("Camera", [960, 540]);
+cam.cameraOption.zoom.setValue(0);
+cam.cameraOption.aperture.setValueAtTime(0, 25);
+cam.cameraOption.aperture.setValueAtTime(2, 50);
+cam.cameraOption.depthOfField.setValue(1);
+cam.property("Camera Options").property("Focus Distance").setValuesAtTimes([0, 2], [500, 900]);
+var light = comp.layers.addLight("Key", [960, 540]);
+var opts = light.property("ADBE Light Options Group");
+opts.coneAngle.setValue(200);
+opts.property("Shadow Darkness").setValue(80);
+opts.castsShadows.setValue(true);
+opts.shadowDiffusion.setValue(12);
+var other = comp.layer("Fill");
+other.lightOption.shadowDarkness.setValue(-5);
+"#;
+        let errors = validate_camera_light_options(script, "scene.jsx");
+        let found: Vec<(usize, &str, &str)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.rule_id().unwrap(), e.get_message()))
+            .collect();
+        assert_eq!(found, vec![
+            (2, "option-range", "Zoom is out of range: 0"),
+            (3, "option-inactive", "Aperture is animated, but Depth of Field is off on 'cam', so the animation has no visible effect"),
+            (4, "option-inactive", "Aperture is animated, but Depth of Field is off on 'cam', so the animation has no visible effect"),
+            (9, "option-range", "Cone Angle is out of range: 200"),
+            (10, "option-inactive", "Shadow Darkness has no effect, since 'light' does not cast shadows"),
+            (14, "option-range", "Shadow Darkness is out of range: -5"),
+        ]);
+        assert_eq!(errors[0].get_context().suggestion(), Some("Zoom must be greater than 0"));
+        assert_eq!(errors[0].get_context().docs_url.as_deref(), Some("https://ae-scripting.docsforadobe.dev/matchnames/layer/cameralayer/"));
+    }
+}
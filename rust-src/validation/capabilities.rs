@@ -0,0 +1,156 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::inference::{narrowed_type, type_narrowings, Narrowing};
+use crate::api::objects::layer::{LayerCapability, LayerType, LAYER_GROUP_CAPABILITIES, LAYER_MEMBER_CAPABILITIES};
+
+lazy_static! {
+    static ref ASSIGNMENT_RE: Regex = Regex::new(r"\b(\w+)\s*=\s*([^=;\n][^;\n]*)").unwrap();
+    static ref ADD_LAYER_RE: Regex = Regex::new(r"^[\w.]*\blayers\.(add\w*)\s*\(").unwrap();
+    static ref MEMBER_RE: Regex = Regex::new(r"\b(\w+)\.(\w+)\b").unwrap();
+    static ref GROUP_RE: Regex = Regex::new(r#"\b(\w+)\.property\s*\(\s*(?:"([^"\n]+)"|'([^'\n]+)')\s*\)"#).unwrap();
+}
+
+/// Checks layer members and top-level property groups against the layer
+/// type's capabilities, e.g. effects on a camera layer or `replaceSource()`
+/// on a text layer. A variable's layer type comes from the `layers.add*()`
+/// call last assigned to it, or from a type guard around the use; layers
+/// of unknown type are not checked.
+pub fn validate_layer_capabilities(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let assignments = layer_assignments(script);
+    let narrowings = type_narrowings(script);
+    let mut errors = Vec::new();
+
+    for cap in MEMBER_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        if script[..m.start()].ends_with('.') {
+            continue;
+        }
+        let capability = match LAYER_MEMBER_CAPABILITIES.iter().find(|(member, _)| *member == &cap[2]) {
+            Some((_, capability)) => *capability,
+            None => continue,
+        };
+        if let Some(layer_type) = layer_type_at(&assignments, &narrowings, &cap[1], m.start()) {
+            if !layer_type.supports(capability) {
+                errors.push(capability_error(script, file_path, m.start(), m.end(), &format!("'{}'", m.as_str()), &layer_type, capability));
+            }
+        }
+    }
+
+    for cap in GROUP_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        let name = cap.get(2).or_else(|| cap.get(3)).unwrap().as_str();
+        let capability = match LAYER_GROUP_CAPABILITIES.iter().find(|(group, _)| *group == name) {
+            Some((_, capability)) => *capability,
+            None => continue,
+        };
+        if let Some(layer_type) = layer_type_at(&assignments, &narrowings, &cap[1], m.start()) {
+            if !layer_type.supports(capability) {
+                errors.push(capability_error(script, file_path, m.start(), m.end(), &format!("The \"{}\" group", name), &layer_type, capability));
+            }
+        }
+    }
+
+    errors.sort_by_key(|e| (e.get_context().line, e.get_context().column));
+    errors
+}
+
+/// Every assignment in the script, in order, with the layer type it stores:
+/// `None` for values that are not new layers
+fn layer_assignments(script: &str) -> Vec<(usize, String, Option<LayerType>)> {
+    ASSIGNMENT_RE.captures_iter(script)
+        .map(|cap| {
+            let layer_type = ADD_LAYER_RE.captures(cap[2].trim())
+                .and_then(|add| LayerType::from_constructor(&add[1]));
+            (cap.get(0).unwrap().start(), cap[1].to_string(), layer_type)
+        })
+        .collect()
+}
+
+fn layer_type_at(
+    assignments: &[(usize, String, Option<LayerType>)],
+    narrowings: &[Narrowing],
+    variable: &str,
+    offset: usize,
+) -> Option<LayerType> {
+    if let Some(class) = narrowed_type(narrowings, variable, offset) {
+        return LayerType::from_class_name(class);
+    }
+    assignments.iter()
+        .rfind(|(start, name, _)| *start < offset && name == variable)
+        .and_then(|(_, _, layer_type)| layer_type.clone())
+}
+
+fn capability_error(
+    script: &str,
+    file_path: &str,
+    start: usize,
+    end: usize,
+    used: &str,
+    layer_type: &LayerType,
+    capability: LayerCapability,
+) -> ValidatorError {
+    let line = script[..start].matches('\n').count() + 1;
+    let line_start = script[..start].rfind('\n').map_or(0, |i| i + 1);
+    let layers: Vec<String> = capability.layer_types().iter()
+        .map(|layer_type| match layer_type {
+            LayerType::AV => "AV".to_string(),
+            other => other.to_string().to_lowercase(),
+        })
+        .collect();
+    let layers = match layers.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => layers.join(""),
+    };
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(script[line_start..start].chars().count() + 1)
+        .length(end - start)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(format!("Only {} layers have {}", layers, capability.description())))
+        .rule("layer-capability")
+        .build();
+
+    ValidatorError::Script {
+        message: format!("{} is not available on {}, which has no {}", used, layer_type.describe(), capability.description()),
+        context,
+        severity: ErrorSeverity::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_capabilities() {
+        let script = r#"var cam = comp.layers.addCamera("Camera", [960, 540]);
+cam.property("Effects").addProperty("ADBE Gaussian Blur 2");
+cam.threeDLayer = true;
+var title = comp.layers.addText("Title");
+title.property("Masks").addProperty("ADBE Mask Atom");
+title.replaceSource(footage, false);
+title.sourceText.setValue("Hello");
+var layer = comp.layer(1);
+layer.replaceSource(footage, false);
+if (layer instanceof ShapeLayer) {
+    layer.replaceSource(footage, false);
+}
+title = comp.layers.addSolid([0, 0, 0], "BG", 1920, 1080, 1);
+title.replaceSource(footage, false);
+"#;
+        let errors = validate_layer_capabilities(script, "layers.jsx");
+        let found: Vec<(usize, &str)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.get_message()))
+            .collect();
+        assert_eq!(found, vec![
+            (2, "The \"Effects\" group is not available on a camera layer, which has no effects"),
+            (3, "'cam.threeDLayer' is not available on a camera layer, which has no AV layer switches"),
+            (6, "'title.replaceSource' is not available on a text layer, which has no source to replace"),
+            (11, "'layer.replaceSource' is not available on a shape layer, which has no source to replace"),
+        ]);
+        assert_eq!(errors[0].get_context().suggestion(),
+            Some("Only AV, text, shape, null and adjustment layers have effects"));
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::Token;
+use super::script::scope::analyze_scopes;
+use crate::data::enums::get_enum_definitions;
+
+/// ExtendScript and After Effects classes scripts construct with `new`
+const CONSTRUCTORS: &[&str] = &[
+    "Array", "Date", "File", "Folder", "ImportOptions", "KeyframeEase", "MarkerValue",
+    "Object", "RegExp", "Shape", "TextDocument", "Window", "XML",
+];
+
+/// Flags constructors and enums written in the wrong case, such as
+/// `new importOptions(f)` or `blendingMode.MULTIPLY`. ExtendScript names are
+/// case-sensitive, so these are `undefined` at runtime. Names the script
+/// declares itself are left alone.
+pub fn validate_class_name_case(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let declared: HashSet<&str> = analysis.scopes.iter().flat_map(|s| s.declared.iter().map(String::as_str)).collect();
+    let enums = get_enum_definitions();
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_identifier() || declared.contains(token.text.as_str())
+            || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+
+        let constructed = i > 0 && tokens[i - 1].is_keyword("new");
+        if constructed {
+            let class = CONSTRUCTORS.iter().find(|name| name.eq_ignore_ascii_case(&token.text));
+            if let Some(class) = class.filter(|class| **class != token.text) {
+                errors.push(case_error(script, file_path, token, "constructor", class, format!("new {}(...)", class)));
+            }
+            continue;
+        }
+
+        // `Enum.MEMBER`, only when the member confirms which enum is meant
+        let member = match (tokens.get(i + 1), tokens.get(i + 2)) {
+            (Some(dot), Some(member)) if dot.is_punct(".") && member.is_identifier() => member,
+            _ => continue,
+        };
+        let enum_name = enums.iter()
+            .find(|(name, members)| name.eq_ignore_ascii_case(&token.text) && members.contains(&member.text.as_str()))
+            .map(|(name, _)| *name);
+        if let Some(enum_name) = enum_name.filter(|name| *name != token.text) {
+            errors.push(case_error(script, file_path, token, "enum", enum_name, format!("{}.{}", enum_name, member.text)));
+        }
+    }
+
+    errors
+}
+
+fn case_error(script: &str, file_path: &str, token: &Token, kind: &str, correct: &str, usage: String) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(format!("Names are case-sensitive; write {}", usage)))
+        .rule("class-name-case")
+        .build();
+    ValidatorError::Script {
+        message: format!("'{}' is undefined; the {} is {}", token.text, kind, correct),
+        context,
+        severity: ErrorSeverity::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrong_case_constructors_and_enums() {
+        let script = r#"var opts = new importOptions(File("a.mov"));
+layer.blendingMode = blendingMode.MULTIPLY;
+layer.blendingMode = BlendingMode.SCREEN;
+var ease = new KeyframeEase(0.5, 50);
+var d = new date();
+var layer = comp.layer(1);
+layer.name = "x";
+"#;
+        let errors = validate_class_name_case(script, "a.jsx");
+        let found: Vec<(usize, &str)> = errors.iter().map(|e| (e.get_context().line.unwrap(), e.get_message())).collect();
+        assert_eq!(found, vec![
+            (1, "'importOptions' is undefined; the constructor is ImportOptions"),
+            (2, "'blendingMode' is undefined; the enum is BlendingMode"),
+            (5, "'date' is undefined; the constructor is Date"),
+        ]);
+    }
+}
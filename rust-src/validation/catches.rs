@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{matching_bracket, significant_tokens, tokenize, Token};
+
+/// Share of a script's tokens a try block must run, directly or through the
+/// functions it calls, to count as wrapping the whole script
+const SCRIPT_WIDE_SHARE: f64 = 0.8;
+
+/// How an error handling check reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckLevel {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl CheckLevel {
+    fn severity(self) -> Option<ErrorSeverity> {
+        match self {
+            CheckLevel::Off => None,
+            CheckLevel::Info => Some(ErrorSeverity::Info),
+            CheckLevel::Warning => Some(ErrorSeverity::Warning),
+            CheckLevel::Error => Some(ErrorSeverity::Error),
+        }
+    }
+}
+
+/// Severities of the try/catch checks, from the `error_handling` section of
+/// `.auteurrc`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorHandlingConfig {
+    /// Catch blocks with no code and no comment explaining why
+    pub empty_catch: CheckLevel,
+    /// Catch blocks that never look at the caught error
+    pub unused_error: CheckLevel,
+    /// A try/catch around the whole script that does not rethrow
+    pub script_wide: CheckLevel,
+}
+
+impl Default for ErrorHandlingConfig {
+    fn default() -> Self {
+        Self {
+            empty_catch: CheckLevel::Warning,
+            unused_error: CheckLevel::Info,
+            script_wide: CheckLevel::Warning,
+        }
+    }
+}
+
+/// A `try` statement with a `catch` clause, as token indices
+#[derive(Debug, Clone, Copy)]
+struct TryCatch {
+    try_keyword: usize,
+    /// `{` and `}` of the try block
+    body: (usize, usize),
+    catch_keyword: usize,
+    /// The catch parameter
+    param: usize,
+    /// `{` and `}` of the catch block
+    handler: (usize, usize),
+}
+
+/// Reports catch blocks that hide failures: empty ones, ones that drop the
+/// caught error, and a catch around the whole script. The last hides every
+/// failure on render farms, where nobody sees an `alert`, so it is only
+/// reported when the handler does not rethrow.
+pub fn validate_error_handling(script: &str, file_path: &str, config: &ErrorHandlingConfig) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let try_catches = find_try_catches(&tokens);
+    let mut errors = Vec::new();
+    let mut report = |token: &Token, level: CheckLevel, rule: &str, message: String, suggestion: &str| {
+        if let Some(severity) = level.severity() {
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(token.line)
+                .column(token.column)
+                .length(token.text.chars().count())
+                .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+                .suggestion(Some(suggestion.to_string()))
+                .rule(rule)
+                .build();
+            errors.push(ValidatorError::Script { message, context, severity });
+        }
+    };
+
+    for try_catch in &try_catches {
+        let catch_token = &tokens[try_catch.catch_keyword];
+        let param = &tokens[try_catch.param];
+        let (open, close) = try_catch.handler;
+        if open + 1 == close {
+            if !has_comment(script, &tokens[open], &tokens[close]) {
+                report(
+                    catch_token, config.empty_catch, "empty-catch",
+                    "Empty catch block swallows the error".to_string(),
+                    "Log or rethrow the error, or add a comment saying why it is safe to ignore",
+                );
+            }
+        } else if !tokens[open + 1..close].iter().enumerate().any(|(i, t)| t.text == param.text && !tokens[open + i].is_punct(".")) {
+            report(
+                catch_token, config.unused_error, "catch-unused-error",
+                format!("The caught error '{}' is never used, so the reason for the failure is lost", param.text),
+                "Include the error in what the handler reports, e.g. its message and line",
+            );
+        }
+    }
+
+    let outermost = try_catches.iter().filter(|inner| {
+        !try_catches.iter().any(|outer| outer.body.0 < inner.try_keyword && inner.try_keyword < outer.body.1)
+    });
+    for try_catch in outermost {
+        let (open, close) = try_catch.handler;
+        let rethrows = tokens[open..close].iter().any(|t| t.is_keyword("throw"));
+        if !rethrows && runs_whole_script(&tokens, try_catch.body) {
+            report(
+                &tokens[try_catch.try_keyword], config.script_wide, "script-wide-catch",
+                "This try/catch wraps the whole script, so any failure is caught here and hidden on render farms".to_string(),
+                "Catch around the steps that can fail, or rethrow after reporting the error",
+            );
+        }
+    }
+
+    errors.sort_by_key(|e| e.get_context().line);
+    errors
+}
+
+fn find_try_catches(tokens: &[Token]) -> Vec<TryCatch> {
+    let mut found = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_keyword("try") || !tokens.get(i + 1).is_some_and(|t| t.is_punct("{")) {
+            continue;
+        }
+        let body_end = match matching_bracket(tokens, i + 1) {
+            Some(end) => end,
+            None => continue,
+        };
+        let catch_keyword = body_end + 1;
+        let header = (tokens.get(catch_keyword), tokens.get(catch_keyword + 1), tokens.get(catch_keyword + 2));
+        let param = match header {
+            (Some(catch), Some(paren), Some(param)) if catch.is_keyword("catch") && paren.is_punct("(") && param.is_identifier() => catch_keyword + 2,
+            _ => continue,
+        };
+        // `catch (e if e instanceof IOError)` is a SpiderMonkey extension ExtendScript accepts
+        let handler_open = match tokens[param..].iter().position(|t| t.is_punct("{")) {
+            Some(offset) => param + offset,
+            None => continue,
+        };
+        if let Some(handler_close) = matching_bracket(tokens, handler_open) {
+            found.push(TryCatch {
+                try_keyword: i,
+                body: (i + 1, body_end),
+                catch_keyword,
+                param,
+                handler: (handler_open, handler_close),
+            });
+        }
+    }
+    found
+}
+
+/// Whether a comment sits between the braces of an otherwise empty block
+fn has_comment(script: &str, open: &Token, close: &Token) -> bool {
+    tokenize(&script[open.end..close.start]).iter().any(Token::is_trivia)
+}
+
+/// Whether the try block runs most of the script, counting the bodies of
+/// named functions it calls, so `try { main(); }` counts like a try around
+/// `main`'s code
+fn runs_whole_script(tokens: &[Token], body: (usize, usize)) -> bool {
+    let mut covered = body.1 - body.0;
+    let mut counted: Vec<&str> = Vec::new();
+    for (i, token) in tokens.iter().enumerate().take(body.1).skip(body.0) {
+        let called = token.is_identifier()
+            && tokens.get(i + 1).is_some_and(|t| t.is_punct("("))
+            && !tokens[i - 1].is_punct(".")
+            && !tokens[i - 1].is_keyword("function");
+        if !called || counted.contains(&token.text.as_str()) {
+            continue;
+        }
+        let declaration = tokens.windows(3).position(|w| w[0].is_keyword("function") && w[1].text == token.text && w[2].is_punct("("));
+        let function_body = declaration
+            .and_then(|d| tokens[d..].iter().position(|t| t.is_punct("{")).map(|offset| d + offset))
+            .and_then(|open| matching_bracket(tokens, open).map(|close| (open, close)));
+        if let Some((open, close)) = function_body {
+            covered += close - open;
+            counted.push(&token.text);
+        }
+    }
+    covered as f64 >= tokens.len() as f64 * SCRIPT_WIDE_SHARE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_quality() {
+        let script = r#"function main() {
+    var comp = app.project.activeItem;
+    try { comp.layer(1).remove(); } catch (e) {}
+    try { comp.openInViewer(); } catch (e) { /* no viewer in aerender */ }
+    try { comp.layer(2).name = "x"; } catch (err) { $.writeln("failed"); }
+    try { comp.layer(3).enabled = false; } catch (err) { $.writeln(err.message); }
+    comp.name = "Main";
+    comp.duration = 10;
+}
+try {
+    main();
+} catch (e) {
+    alert("Something went wrong");
+}
+"#;
+        let errors = validate_error_handling(script, "a.jsx", &ErrorHandlingConfig::default());
+        let found: Vec<(usize, &str, ErrorSeverity)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.get_context().rule.as_deref().unwrap(), e.get_severity()))
+            .collect();
+        assert_eq!(found, vec![
+            (3, "empty-catch", ErrorSeverity::Warning),
+            (5, "catch-unused-error", ErrorSeverity::Info),
+            (10, "script-wide-catch", ErrorSeverity::Warning),
+            (12, "catch-unused-error", ErrorSeverity::Info),
+        ]);
+
+        let config = ErrorHandlingConfig { unused_error: CheckLevel::Off, script_wide: CheckLevel::Error, ..ErrorHandlingConfig::default() };
+        let rethrown = script.replace("alert(\"Something went wrong\");", "alert(e.message); throw e;");
+        let found: Vec<usize> = validate_error_handling(&rethrown, "a.jsx", &config).iter().map(|e| e.get_context().line.unwrap()).collect();
+        assert_eq!(found, vec![3]);
+        assert!(validate_error_handling(script, "a.jsx", &config).iter().any(|e| e.get_severity() == ErrorSeverity::Error));
+    }
+}
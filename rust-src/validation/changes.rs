@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use super::errors::ValidatorError;
+
+/// Lines added or changed per file, so a report can be limited to the
+/// diagnostics a change is responsible for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangedLines {
+    /// Inclusive `(first, last)` line ranges per file, sorted and merged
+    files: BTreeMap<String, Vec<(usize, usize)>>,
+}
+
+impl ChangedLines {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a unified diff from a file, or from stdin for `-`
+    pub fn load_diff(path: &str) -> Result<Self, String> {
+        let mut content = String::new();
+        let read = if path == "-" {
+            std::io::stdin().read_to_string(&mut content).map(|_| ())
+        } else {
+            fs::read_to_string(path).map(|text| content = text)
+        };
+        read.map_err(|e| format!("Failed to read diff {}: {}", path, e))?;
+        Ok(Self::from_unified_diff(&content))
+    }
+
+    /// The lines a unified diff (`git diff`, `diff -u`) adds to the new side
+    /// of each file. Deleted files and pure deletions change no lines.
+    pub fn from_unified_diff(diff: &str) -> Self {
+        let mut changed = Self::new();
+        let mut file: Option<String> = None;
+        // Lines left in the current hunk, on the old and new side
+        let mut old_left = 0;
+        let mut new_left = 0;
+        let mut line = 0;
+
+        for text in diff.lines() {
+            if old_left > 0 || new_left > 0 {
+                match text.chars().next() {
+                    Some('+') => {
+                        if let Some(file) = &file {
+                            changed.add(file, line, line);
+                        }
+                        line += 1;
+                        new_left -= 1;
+                    }
+                    Some('-') => old_left -= 1,
+                    // "\ No newline at end of file"
+                    Some('\\') => {}
+                    _ => {
+                        line += 1;
+                        old_left -= 1;
+                        new_left -= 1;
+                    }
+                }
+                continue;
+            }
+            if let Some(path) = text.strip_prefix("+++ ") {
+                let path = path.split('\t').next().unwrap_or("").trim();
+                file = match path {
+                    "/dev/null" => None,
+                    path => Some(path.strip_prefix("b/").unwrap_or(path).to_string()),
+                };
+            } else if let Some((old, new)) = hunk_header(text) {
+                old_left = old;
+                line = new.0;
+                new_left = new.1;
+            }
+        }
+        changed
+    }
+
+    /// Parses `file:ranges`, e.g. `src/ui.jsx:10-24,31`
+    pub fn parse_ranges(spec: &str) -> Result<Self, String> {
+        let (file, ranges) = spec.rsplit_once(':')
+            .ok_or_else(|| format!("Expected FILE:RANGES, got '{}'", spec))?;
+        let mut changed = Self::new();
+        for range in ranges.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            let (first, last) = range.split_once('-').unwrap_or((range, range));
+            let parse = |n: &str| n.trim().parse::<usize>().ok().filter(|&n| n > 0);
+            match (parse(first), parse(last)) {
+                (Some(first), Some(last)) if first <= last => changed.add(file, first, last),
+                _ => return Err(format!("Invalid line range '{}' in '{}'", range, spec)),
+            }
+        }
+        Ok(changed)
+    }
+
+    pub fn merge(&mut self, other: ChangedLines) {
+        for (file, ranges) in other.files {
+            for (first, last) in ranges {
+                self.add(&file, first, last);
+            }
+        }
+    }
+
+    fn add(&mut self, file: &str, first: usize, last: usize) {
+        let ranges = self.files.entry(normalize(file).to_string()).or_default();
+        ranges.push((first, last));
+        ranges.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+        for &(first, last) in ranges.iter() {
+            match merged.last_mut() {
+                Some(previous) if first <= previous.1 + 1 => previous.1 = previous.1.max(last),
+                _ => merged.push((first, last)),
+            }
+        }
+        *ranges = merged;
+    }
+
+    /// Ranges recorded for `file`. Paths match when one ends with the
+    /// other, so repository-relative diff paths match the paths a script
+    /// was validated under.
+    fn ranges(&self, file: &str) -> Option<&[(usize, usize)]> {
+        let file = normalize(file);
+        self.files.iter()
+            .find(|(changed, _)| same_file(changed, file))
+            .map(|(_, ranges)| ranges.as_slice())
+    }
+
+    /// Whether `diagnostic`, reported in `default_file` unless it names its
+    /// own, spans a changed line. Diagnostics without a line are kept for
+    /// files with any change.
+    pub fn touches(&self, diagnostic: &ValidatorError, default_file: &str) -> bool {
+        let context = diagnostic.get_context();
+        let ranges = match self.ranges(context.file.as_deref().unwrap_or(default_file)) {
+            Some(ranges) => ranges,
+            None => return false,
+        };
+        match context.line {
+            Some(line) => ranges.iter().any(|&(first, last)| line >= first && line <= last),
+            None => true,
+        }
+    }
+}
+
+/// `@@ -12,5 +12,7 @@`: the old side's line count, and the new side's
+/// first line and count. Counts default to 1.
+fn hunk_header(text: &str) -> Option<(usize, (usize, usize))> {
+    let rest = text.strip_prefix("@@ -")?;
+    let (old, rest) = rest.split_once(" +")?;
+    let (new, _) = rest.split_once(" @@")?;
+    let side = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    Some((side(old)?.1, side(new)?))
+}
+
+fn normalize(path: &str) -> &str {
+    let path = path.trim();
+    path.strip_prefix("./").unwrap_or(path)
+}
+
+fn same_file(a: &str, b: &str) -> bool {
+    let a = a.replace('\\', "/");
+    let b = b.replace('\\', "/");
+    let (long, short) = if a.len() >= b.len() { (&a, &b) } else { (&b, &a) };
+    long == short || long.ends_with(&format!("/{}", short))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::errors::{ErrorContextBuilder, ErrorSeverity};
+
+    #[test]
+    fn test_changed_lines() {
+        let diff = "diff --git a/src/ui.jsx b/src/ui.jsx
+--- a/src/ui.jsx
++++ b/src/ui.jsx
+@@ -3,3 +3,4 @@ function build() {
+ var a = 1;
+-var b = 2;
++var b = 3;
++var c = 4;
+ var d = 5;
+--- a/src/old.jsx
++++ /dev/null
+@@ -1 +0,0 @@
+-gone();
+--- a/src/loop.jsx
++++ b/src/loop.jsx
+@@ -20 +21,2 @@
++++counter;
++more();
+";
+        let changed = ChangedLines::from_unified_diff(diff);
+        assert_eq!(changed.ranges("/work/repo/src/ui.jsx"), Some(&[(4, 5)][..]));
+        assert_eq!(changed.ranges("src/loop.jsx"), Some(&[(21, 22)][..]));
+        assert_eq!(changed.ranges("old.jsx"), None);
+        assert_eq!(changed.ranges("ui2.jsx"), None);
+
+        let mut changed = ChangedLines::parse_ranges("./lib/tools.jsx:10-12,14").unwrap();
+        changed.merge(ChangedLines::parse_ranges("lib/tools.jsx:13,40-41").unwrap());
+        assert_eq!(changed.ranges("lib/tools.jsx"), Some(&[(10, 14), (40, 41)][..]));
+        assert!(ChangedLines::parse_ranges("lib/tools.jsx:5-2").is_err());
+
+        let at = |line: usize| ValidatorError::Script {
+            message: String::new(),
+            context: ErrorContextBuilder::new().line(line).build(),
+            severity: ErrorSeverity::Error,
+        };
+        assert!(changed.touches(&at(11), "lib/tools.jsx"));
+        assert!(!changed.touches(&at(15), "lib/tools.jsx"));
+        assert!(!changed.touches(&at(11), "lib/other.jsx"));
+    }
+}
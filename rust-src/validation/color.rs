@@ -0,0 +1,188 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lines::LineIndex;
+
+lazy_static! {
+    /// Array literals in color-typed positions: color properties set with
+    /// `setValue`/`setValueAtTime`/`setValueAtKey`, assignments to `*Color`
+    /// members, and the color argument of `addSolid`
+    static ref COLOR_LITERAL_RES: Vec<Regex> = vec![
+        Regex::new(r#"(?:\.property\(\s*["'][^"']*[Cc]olou?r[^"']*["']\s*\)|\.\w*[Cc]olor)\.setValue(?:AtTime|AtKey)?\s*\((?:[^,()\[\]]+,\s*)?(\[[^\[\]\n]*\])"#).unwrap(),
+        Regex::new(r"\.\w*[Cc]olor\s*=\s*(\[[^\[\]\n]*\])").unwrap(),
+        Regex::new(r"\.addSolid\s*\(\s*(\[[^\[\]\n]*\])").unwrap(),
+    ];
+}
+
+/// A color given as numeric components, in AE's 0-1 float space
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorValue {
+    pub components: Vec<f64>,
+}
+
+impl ColorValue {
+    /// Parses an array literal such as `[255, 128, 0]`; `None` unless every
+    /// component is a number and there are 3 (RGB) or 4 (RGBA)
+    pub fn parse(literal: &str) -> Option<Self> {
+        let inner = literal.trim().strip_prefix('[')?.strip_suffix(']')?;
+        let components = inner.split(',')
+            .map(|c| c.trim().parse::<f64>().ok())
+            .collect::<Option<Vec<f64>>>()?;
+        if components.len() == 3 || components.len() == 4 {
+            Some(Self { components })
+        } else {
+            None
+        }
+    }
+
+    pub fn is_in_range(&self) -> bool {
+        self.components.iter().all(|c| (0.0..=1.0).contains(c))
+    }
+
+    /// Components look like 8-bit values: some exceed 1, none exceed 255
+    pub fn looks_8bit(&self) -> bool {
+        self.components.iter().any(|c| *c > 1.0)
+            && self.components.iter().all(|c| (0.0..=255.0).contains(c))
+    }
+
+    /// The color with every component divided by 255
+    pub fn from_8bit(&self) -> Self {
+        Self { components: self.components.iter().map(|c| c / 255.0).collect() }
+    }
+
+    /// Array literal with components rounded to 4 decimals
+    pub fn to_literal(&self) -> String {
+        let parts: Vec<String> = self.components.iter()
+            .map(|c| {
+                let rounded = format!("{:.4}", c);
+                rounded.trim_end_matches('0').trim_end_matches('.').to_string()
+            })
+            .collect();
+        format!("[{}]", parts.join(", "))
+    }
+}
+
+/// A color literal with components outside 0-1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorIssue {
+    pub line: usize,
+    pub column: usize,
+    /// Byte range of the array literal
+    pub start: usize,
+    pub end: usize,
+    pub color: ColorValue,
+    /// Normalized literal when the components look like 0-255 values
+    pub replacement: Option<String>,
+}
+
+/// Finds out-of-range color literals in color-typed positions
+pub fn find_color_issues(script: &str) -> Vec<ColorIssue> {
+    let mut issues: Vec<ColorIssue> = Vec::new();
+    let lines = LineIndex::new(script);
+    for re in COLOR_LITERAL_RES.iter() {
+        for cap in re.captures_iter(script) {
+            let literal = cap.get(1).unwrap();
+            let color = match ColorValue::parse(literal.as_str()) {
+                Some(color) if !color.is_in_range() => color,
+                _ => continue,
+            };
+            if issues.iter().any(|i| i.start == literal.start()) {
+                continue;
+            }
+            let (line, column) = lines.position(script, literal.start());
+            issues.push(ColorIssue {
+                line,
+                column,
+                start: literal.start(),
+                end: literal.end(),
+                replacement: if color.looks_8bit() { Some(color.from_8bit().to_literal()) } else { None },
+                color,
+            });
+        }
+    }
+    issues.sort_by_key(|i| i.start);
+    issues
+}
+
+/// Divides 0-255 color literals by 255 and returns the new script with the
+/// number of literals changed
+pub fn fix_color_ranges(script: &str) -> (String, usize) {
+    let mut output = script.to_string();
+    let mut fixed = 0;
+    for issue in find_color_issues(script).into_iter().rev() {
+        if let Some(replacement) = &issue.replacement {
+            output.replace_range(issue.start..issue.end, replacement);
+            fixed += 1;
+        }
+    }
+    (output, fixed)
+}
+
+/// Flags color components above 1, usually 0-255 values where AE expects 0-1
+pub fn validate_color_ranges(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let lines = LineIndex::new(script);
+    find_color_issues(script).into_iter().map(|issue| {
+        let suggestion = match &issue.replacement {
+            Some(replacement) => format!("Color components are 0-1 floats; divide by 255: {}", replacement),
+            None => "Color components must be between 0 and 1".to_string(),
+        };
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(issue.line)
+            .column(issue.column)
+            .length(issue.end - issue.start)
+            .code_snippet(lines.line_text(script, issue.line).trim().to_string())
+            .suggestion(Some(suggestion))
+            .rule("color-range")
+            .build();
+
+        ValidatorError::Type {
+            message: format!("Color {} has components outside 0-1", issue.color.to_literal()),
+            context,
+            severity: ErrorSeverity::Warning,
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_value_normalizes_8bit() {
+        let color = ColorValue::parse("[255, 128, 0]").unwrap();
+        assert!(color.looks_8bit());
+        assert_eq!(color.from_8bit().to_literal(), "[1, 0.502, 0]");
+        assert!(ColorValue::parse("[1, 0.5, 0]").unwrap().is_in_range());
+        assert_eq!(ColorValue::parse("[a, 1, 2]"), None);
+    }
+
+    #[test]
+    fn test_finds_and_fixes_color_positions() {
+        let script = r#"var fill = layer.property("ADBE Root Vectors Group").property("Color");
+layer.property("Color").setValue([255, 0, 0]);
+textDoc.fillColor = [255, 255, 255];
+var bg = comp.layers.addSolid([0, 0, 300], "BG", 100, 100, 1);
+layer.property("Position").setValue([960, 540]);
+textDoc.strokeColor = [1, 0.5, 0];
+"#;
+        let issues = find_color_issues(script);
+        assert_eq!(issues.iter().map(|i| i.line).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(issues[2].replacement, None);
+
+        let (fixed, count) = fix_color_ranges(script);
+        assert_eq!(count, 2);
+        assert!(fixed.contains(r#"setValue([1, 0, 0]);"#));
+        assert!(fixed.contains("textDoc.fillColor = [1, 1, 1];"));
+        assert!(fixed.contains("addSolid([0, 0, 300]"));
+    }
+
+    #[test]
+    fn test_literal_starting_a_line() {
+        let script = "textDoc.fillColor =\n[255, 0, 0];\n";
+        let issues = find_color_issues(script);
+        assert_eq!((issues[0].line, issues[0].column), (2, 1));
+        let errors = validate_color_ranges(script, "c.jsx");
+        assert_eq!(errors[0].get_context().code_snippet.as_deref(), Some("[255, 0, 0];"));
+    }
+}
@@ -0,0 +1,517 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::i18n::Locale;
+use super::naming::NamingConfig;
+use super::catches::ErrorHandlingConfig;
+
+/// Name of the per-project configuration file
+pub const CONFIG_FILE_NAME: &str = ".auteurrc";
+
+/// Rules escalated to errors by the built-in `strict` profile
+pub const STRICT_RULES: &[&str] = &[
+    "implicit-global",
+    "loose-equality",
+    "missing-semicolon",
+    "unguarded-active-item",
+];
+
+/// Validator configuration, usually loaded from an `.auteurrc` file. Config
+/// files are read as JSON only; TOML and other formats are rejected.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidatorConfig {
+    /// Stop looking for `.auteurrc` files in parent directories
+    pub root: bool,
+    /// Presets (`auteur:recommended`) and config files, relative to this
+    /// one, merged beneath it in order. Resolved when the file is loaded.
+    pub extends: Vec<String>,
+    /// Name of the profile to apply (built-in or from `profiles`)
+    pub profile: Option<String>,
+    /// User-defined profiles keyed by name
+    pub profiles: BTreeMap<String, RuleProfile>,
+    /// Runtime the scripts are deployed to
+    pub environment: Environment,
+    /// Report repeated diagnostics once, with "also at line" notes
+    pub group_related: bool,
+    /// Most diagnostics of one rule reported per file; the rest are
+    /// summarized as "…and N more". Must be at least 1: 0 is rejected on
+    /// load rather than read as "report nothing", which would hide errors.
+    pub max_reports_per_rule: Option<usize>,
+    /// Language diagnostics are reported in
+    pub locale: Locale,
+    /// Polyfills loaded before the scripts run (e.g. `json2`, `es5-shim`),
+    /// in addition to those detected from `#include` lines
+    pub shims: Vec<String>,
+    /// Path prefixes rewritten to local directories when `--check-paths`
+    /// resolves footage, e.g. `{ "/Volumes/Assets": "./assets" }`
+    pub path_roots: BTreeMap<String, String>,
+    /// Operating systems the scripts must run on; empty means every platform
+    pub platforms: Vec<Platform>,
+    /// Pass compiled `.jsxbin` files through with a note instead of failing
+    pub allow_jsxbin: bool,
+    /// Naming conventions for layers, comps, functions, constants and undo groups
+    pub naming: NamingConfig,
+    /// Severities of the empty, error-dropping and script-wide catch checks
+    pub error_handling: ErrorHandlingConfig,
+}
+
+/// Where a script is expected to run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    /// The After Effects UI, where dialogs are allowed
+    #[default]
+    Interactive,
+    /// The headless `aerender` command-line renderer
+    Aerender,
+    /// A dockable panel, loaded from `ScriptUI Panels` when After Effects starts
+    Panel,
+}
+
+impl std::str::FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "interactive" => Ok(Environment::Interactive),
+            "aerender" => Ok(Environment::Aerender),
+            "panel" => Ok(Environment::Panel),
+            _ => Err(format!("Unknown environment '{}' (expected interactive, aerender or panel)", s)),
+        }
+    }
+}
+
+/// Operating system a script is deployed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Windows,
+    Macos,
+}
+
+impl std::str::FromStr for Platform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "windows" => Ok(Platform::Windows),
+            "macos" => Ok(Platform::Macos),
+            _ => Err(format!("Unknown platform '{}' (expected windows or macos)", s)),
+        }
+    }
+}
+
+/// A named rule set adjusting diagnostic severities
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleProfile {
+    /// Profile whose escalations are inherited
+    pub extends: Option<String>,
+    /// Rule ids whose warnings are reported as errors
+    pub escalate: Vec<String>,
+}
+
+impl RuleProfile {
+    pub fn escalates(&self, rule: &str) -> bool {
+        self.escalate.iter().any(|r| r == rule)
+    }
+}
+
+/// Returns a profile bundled with the validator
+pub fn builtin_profile(name: &str) -> Option<RuleProfile> {
+    match name {
+        "strict" => Some(RuleProfile {
+            extends: None,
+            escalate: STRICT_RULES.iter().map(|r| r.to_string()).collect(),
+        }),
+        _ => None,
+    }
+}
+
+/// Presets bundled with the validator, used as `auteur:<name>` in `extends`
+pub const PRESET_NAMES: &[&str] = &["recommended", "strict", "render-farm", "panel-dev"];
+
+/// Returns the configuration fragment of a bundled preset
+pub fn builtin_preset(name: &str) -> Option<&'static str> {
+    match name {
+        "recommended" => Some(r#"{
+            "group_related": true
+        }"#),
+        "strict" => Some(r#"{
+            "extends": ["auteur:recommended"],
+            "profile": "strict",
+            "naming": { "functions": "camelCase", "undo_groups": true },
+            "error_handling": { "empty_catch": "error", "unused_error": "warning", "script_wide": "error" }
+        }"#),
+        // Headless renders on mixed Windows and macOS nodes, whose scripts
+        // are usually wrapped in one try/catch that logs the failure
+        "render-farm" => Some(r#"{
+            "extends": ["auteur:recommended"],
+            "environment": "aerender",
+            "platforms": ["windows", "macos"],
+            "error_handling": { "script_wide": "off" }
+        }"#),
+        "panel-dev" => Some(r#"{
+            "extends": ["auteur:recommended"],
+            "environment": "panel",
+            "naming": { "undo_groups": true },
+            "error_handling": { "empty_catch": "error" }
+        }"#),
+        _ => None,
+    }
+}
+
+impl ValidatorConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a configuration file and the presets and files it extends
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let value = Self::read_layer(path.as_ref())?;
+        Self::from_value(Self::expand_extends(value, path.as_ref(), 0)?)
+            .map_err(|e| format!("Invalid merged config: {}", e))
+    }
+
+    /// Parses a configuration. `extends` is not resolved, since relative
+    /// paths need the file's location; use [`ValidatorConfig::load`].
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        Self::from_value(serde_json::from_str(content).map_err(|e| e.to_string())?)
+    }
+
+    /// Searches `start` and its ancestors for an `.auteurrc` file
+    pub fn discover<P: AsRef<Path>>(start: P) -> Option<PathBuf> {
+        let mut dir = Some(start.as_ref());
+        while let Some(current) = dir {
+            let candidate = current.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            dir = current.parent();
+        }
+        None
+    }
+
+    /// Effective configuration for scripts in `dir`, and the `.auteurrc`
+    /// files it was merged from, outermost first. Every file from `dir` up
+    /// to the filesystem root or the first file with `"root": true` applies;
+    /// nearer files override the keys they set, objects such as `profiles`
+    /// and `naming` are merged key by key and lists are replaced.
+    pub fn cascade<P: AsRef<Path>>(dir: P) -> Result<(Self, Vec<PathBuf>), String> {
+        let mut layers = Vec::new();
+        let mut current = Some(dir.as_ref());
+        while let Some(dir) = current {
+            let candidate = dir.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                let value = Self::read_layer(&candidate)?;
+                let is_root = value.get("root").and_then(Value::as_bool).unwrap_or(false);
+                let value = Self::expand_extends(value, &candidate, 0)?;
+                layers.push((candidate, value));
+                if is_root {
+                    break;
+                }
+            }
+            current = dir.parent();
+        }
+
+        let mut merged = Value::Object(Default::default());
+        let mut sources = Vec::new();
+        for (path, value) in layers.into_iter().rev() {
+            merge_values(&mut merged, value);
+            sources.push(path);
+        }
+        let config = Self::from_value(merged).map_err(|e| format!("Invalid merged config: {}", e))?;
+        Ok((config, sources))
+    }
+
+    /// Deserializes a configuration and checks the values serde cannot
+    fn from_value(value: Value) -> Result<Self, String> {
+        let config: Self = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        config.naming.check()?;
+        if config.max_reports_per_rule == Some(0) {
+            return Err("max_reports_per_rule must be at least 1".to_string());
+        }
+        Ok(config)
+    }
+
+    /// One config file, checked on its own so errors name the file
+    fn read_layer(path: &Path) -> Result<Value, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let value: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid config {}: config files are read as JSON only: {}", path.display(), e))?;
+        Self::from_value(value.clone()).map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+        Ok(value)
+    }
+
+    /// Merges the presets and files `value` extends beneath it, following
+    /// their own `extends`. `path` is the file `value` was read from.
+    fn expand_extends(mut value: Value, path: &Path, depth: usize) -> Result<Value, String> {
+        if depth > 8 {
+            return Err(format!("{} has a cyclic or too deep 'extends' chain", path.display()));
+        }
+        let extends: Vec<String> = match value.as_object_mut().and_then(|object| object.remove("extends")) {
+            Some(extends) => serde_json::from_value(extends)
+                .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?,
+            None => return Ok(value),
+        };
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let mut merged = Value::Object(Default::default());
+        for name in extends {
+            let base = match name.strip_prefix("auteur:") {
+                Some(preset) => {
+                    let fragment = builtin_preset(preset).ok_or_else(|| format!(
+                        "Unknown preset '{}' in {} (expected one of auteur:{})", name, path.display(), PRESET_NAMES.join(", auteur:")
+                    ))?;
+                    let fragment = serde_json::from_str(fragment).map_err(|e| format!("Invalid preset '{}': {}", name, e))?;
+                    Self::expand_extends(fragment, path, depth + 1)?
+                }
+                None => {
+                    let file = dir.join(&name);
+                    let base = Self::read_layer(&file)?;
+                    Self::expand_extends(base, &file, depth + 1)?
+                }
+            };
+            merge_values(&mut merged, base);
+        }
+        merge_values(&mut merged, value);
+        Ok(merged)
+    }
+
+    /// Whether scripts must run on `platform`
+    pub fn targets(&self, platform: Platform) -> bool {
+        self.platforms.is_empty() || self.platforms.contains(&platform)
+    }
+
+    pub fn with_profile(mut self, name: &str) -> Self {
+        self.profile = Some(name.to_string());
+        self
+    }
+
+    /// Resolves the active profile, following `extends` chains
+    pub fn active_profile(&self) -> Result<Option<RuleProfile>, String> {
+        match &self.profile {
+            Some(name) => self.resolve_profile(name, 0).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn resolve_profile(&self, name: &str, depth: usize) -> Result<RuleProfile, String> {
+        if depth > 8 {
+            return Err(format!("Profile '{}' has a cyclic or too deep 'extends' chain", name));
+        }
+        let profile = self.profiles.get(name).cloned()
+            .or_else(|| builtin_profile(name))
+            .ok_or_else(|| format!("Unknown profile '{}'", name))?;
+
+        let mut resolved = match &profile.extends {
+            Some(parent) => self.resolve_profile(parent, depth + 1)?,
+            None => RuleProfile::default(),
+        };
+        for rule in profile.escalate {
+            if !resolved.escalates(&rule) {
+                resolved.escalate.push(rule);
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+/// Overlays `overlay` onto `base`: objects merge key by key, anything else
+/// replaces the base value
+fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::catches::CheckLevel;
+
+    #[test]
+    fn test_builtin_strict_profile() {
+        let config = ValidatorConfig::new().with_profile("strict");
+        let profile = config.active_profile().unwrap().unwrap();
+        assert!(profile.escalates("implicit-global"));
+        assert!(profile.escalates("missing-semicolon"));
+    }
+
+    #[test]
+    fn test_strict_profile_end_to_end() {
+        let script = "var comp = app.project.activeItem;\nexecuteComprehensiveAITool(\"create_layer\", { name: \"Title\" });\nvar done = true\n";
+        let lenient = crate::validation::validate_script_with_config(script, "t.jsx", &ValidatorConfig::new()).unwrap();
+        assert!(lenient.errors.iter().all(|e| e.rule_id() != Some("missing-semicolon")));
+        assert!(lenient.warnings.iter().any(|e| e.rule_id() == Some("missing-semicolon")));
+
+        let strict = crate::validation::validate_script_with_config(script, "t.jsx", &ValidatorConfig::new().with_profile("strict")).unwrap();
+        assert!(strict.errors.iter().any(|e| e.rule_id() == Some("missing-semicolon") && e.get_context().line == Some(3)));
+    }
+
+    #[test]
+    fn test_user_profile_extends_builtin() {
+        let config = ValidatorConfig::from_json(r#"{
+            "profile": "ci",
+            "profiles": { "ci": { "extends": "strict", "escalate": ["custom-rule"] } }
+        }"#).unwrap();
+        let profile = config.active_profile().unwrap().unwrap();
+        assert!(profile.escalates("custom-rule"));
+        assert!(profile.escalates("loose-equality"));
+    }
+
+    #[test]
+    fn test_environment_from_json() {
+        let config = ValidatorConfig::from_json(r#"{ "environment": "aerender" }"#).unwrap();
+        assert_eq!(config.environment, Environment::Aerender);
+        assert_eq!(ValidatorConfig::new().environment, Environment::Interactive);
+        assert_eq!("panel".parse::<Environment>(), Ok(Environment::Panel));
+    }
+
+    #[test]
+    fn test_locale_from_json() {
+        let config = ValidatorConfig::from_json(r#"{ "locale": "ja", "group_related": true }"#).unwrap();
+        assert_eq!(config.locale, Locale::Ja);
+        assert!(config.group_related);
+    }
+
+    #[test]
+    fn test_max_reports_per_rule_from_json() {
+        let config = ValidatorConfig::from_json(r#"{ "max_reports_per_rule": 20 }"#).unwrap();
+        assert_eq!(config.max_reports_per_rule, Some(20));
+        assert_eq!(ValidatorConfig::new().max_reports_per_rule, None);
+        assert_eq!(
+            ValidatorConfig::from_json(r#"{ "max_reports_per_rule": 0 }"#).unwrap_err(),
+            "max_reports_per_rule must be at least 1"
+        );
+    }
+
+    #[test]
+    fn test_platforms_from_json() {
+        let config = ValidatorConfig::from_json(r#"{ "platforms": ["windows"] }"#).unwrap();
+        assert!(config.targets(Platform::Windows));
+        assert!(!config.targets(Platform::Macos));
+        assert!(ValidatorConfig::new().targets(Platform::Macos));
+    }
+
+    #[test]
+    fn test_naming_from_json() {
+        let config = ValidatorConfig::from_json(r#"{ "naming": { "functions": "camelCase", "undo_groups": true } }"#).unwrap();
+        assert_eq!(config.naming.functions.map(|style| style.name()), Some("camelCase"));
+        assert!(ValidatorConfig::from_json(r#"{ "naming": { "layer_names": "([" } }"#).is_err());
+    }
+
+    #[test]
+    fn test_error_handling_from_json() {
+        let config = ValidatorConfig::from_json(r#"{ "error_handling": { "unused_error": "off" } }"#).unwrap();
+        assert_eq!(config.error_handling.unused_error, CheckLevel::Off);
+        assert_eq!(config.error_handling.empty_catch, CheckLevel::Warning);
+    }
+
+    #[test]
+    fn test_cascading_configs() {
+        let top = std::env::temp_dir().join(format!("ae-validator-cascade-{}", std::process::id()));
+        let panels = top.join("repo/panels");
+        fs::create_dir_all(panels.join("ui")).unwrap();
+        fs::write(top.join(CONFIG_FILE_NAME), r#"{ "locale": "ja" }"#).unwrap();
+        fs::write(top.join("repo").join(CONFIG_FILE_NAME), r#"{
+            "root": true,
+            "environment": "aerender",
+            "shims": ["json2"],
+            "platforms": ["windows", "macos"],
+            "profiles": { "ci": { "escalate": ["custom-rule"] } }
+        }"#).unwrap();
+        fs::write(panels.join(CONFIG_FILE_NAME), r#"{
+            "environment": "panel",
+            "platforms": ["macos"],
+            "profiles": { "dev": {} }
+        }"#).unwrap();
+
+        let (config, sources) = ValidatorConfig::cascade(panels.join("ui")).unwrap();
+        assert_eq!(sources, vec![top.join("repo").join(CONFIG_FILE_NAME), panels.join(CONFIG_FILE_NAME)]);
+        assert_eq!(config.environment, Environment::Panel);
+        assert_eq!(config.shims, vec!["json2"]);
+        assert_eq!(config.platforms, vec![Platform::Macos]);
+        assert!(config.profiles.contains_key("ci") && config.profiles.contains_key("dev"));
+        assert_eq!(config.locale, ValidatorConfig::new().locale);
+
+        fs::write(panels.join(CONFIG_FILE_NAME), r#"{ "environment": "web" }"#).unwrap();
+        let error = ValidatorConfig::cascade(&panels).unwrap_err();
+        assert!(error.starts_with(&format!("Invalid config {}", panels.join(CONFIG_FILE_NAME).display())));
+
+        fs::write(panels.join(CONFIG_FILE_NAME), "max_reports_per_rule = 20\n").unwrap();
+        assert!(ValidatorConfig::cascade(&panels).unwrap_err().contains("config files are read as JSON only"));
+
+        fs::remove_dir_all(&top).unwrap();
+    }
+
+    #[test]
+    fn test_presets_and_extended_files() {
+        for name in PRESET_NAMES {
+            let fragment = builtin_preset(name).unwrap();
+            assert!(ValidatorConfig::from_json(fragment).is_ok(), "{}", name);
+        }
+
+        let dir = std::env::temp_dir().join(format!("ae-validator-presets-{}", std::process::id()));
+        fs::create_dir_all(dir.join("shared")).unwrap();
+        fs::write(dir.join("shared/company-rules.json"), r#"{
+            "extends": ["auteur:render-farm"],
+            "shims": ["json2"],
+            "error_handling": { "unused_error": "off" }
+        }"#).unwrap();
+        fs::write(dir.join(CONFIG_FILE_NAME), r#"{
+            "extends": ["auteur:strict", "./shared/company-rules.json"],
+            "platforms": ["windows"]
+        }"#).unwrap();
+
+        let config = ValidatorConfig::load(dir.join(CONFIG_FILE_NAME)).unwrap();
+        assert_eq!(config.environment, Environment::Aerender);
+        assert_eq!(config.profile.as_deref(), Some("strict"));
+        assert!(config.group_related);
+        assert_eq!(config.platforms, vec![Platform::Windows]);
+        assert_eq!(config.shims, vec!["json2"]);
+        assert_eq!(config.error_handling.empty_catch, CheckLevel::Error);
+        assert_eq!(config.error_handling.unused_error, CheckLevel::Off);
+        assert_eq!(config.error_handling.script_wide, CheckLevel::Off);
+        assert!(config.extends.is_empty());
+
+        fs::write(dir.join(CONFIG_FILE_NAME), r#"{ "extends": ["auteur:lenient"] }"#).unwrap();
+        assert!(ValidatorConfig::load(dir.join(CONFIG_FILE_NAME)).unwrap_err().starts_with("Unknown preset 'auteur:lenient'"));
+        fs::write(dir.join("shared/company-rules.json"), r#"{ "extends": ["../.auteurrc"] }"#).unwrap();
+        fs::write(dir.join(CONFIG_FILE_NAME), r#"{ "extends": ["shared/company-rules.json"] }"#).unwrap();
+        assert!(ValidatorConfig::load(dir.join(CONFIG_FILE_NAME)).unwrap_err().contains("cyclic or too deep 'extends' chain"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_serialized_maps_are_sorted() {
+        let config = ValidatorConfig::from_json(r#"{
+            "path_roots": { "/Volumes/B": "./b", "/Volumes/A": "./a" },
+            "profiles": { "release": {}, "ci": {} }
+        }"#).unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""path_roots":{"/Volumes/A":"./a","/Volumes/B":"./b"}"#));
+        assert!(json.find(r#""ci""#).unwrap() < json.find(r#""release""#).unwrap());
+        assert_eq!(json, serde_json::to_string(&ValidatorConfig::from_json(&json).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let config = ValidatorConfig::new().with_profile("missing");
+        assert!(config.active_profile().is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{matching_bracket, significant_tokens, Token, TokenKind};
+
+/// A relationship property values must satisfy, checked when a script
+/// assigns one of its fields
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constraint {
+    /// `first + second <= limit`, e.g. the work area inside the comp
+    SumAtMost { first: &'static str, second: &'static str, limit: &'static str },
+    /// `earlier < later`
+    Before { earlier: &'static str, later: &'static str },
+    /// Every component a whole number of at least `min`
+    ComponentsAtLeast { field: &'static str, min: f64 },
+    /// A whole number in `min..=max`
+    IntegerIn { field: &'static str, min: f64, max: f64 },
+}
+
+pub const CONSTRAINTS: &[Constraint] = &[
+    Constraint::SumAtMost { first: "workAreaStart", second: "workAreaDuration", limit: "duration" },
+    Constraint::Before { earlier: "inPoint", later: "outPoint" },
+    Constraint::ComponentsAtLeast { field: "resolutionFactor", min: 1.0 },
+    Constraint::IntegerIn { field: "label", min: 0.0, max: 16.0 },
+];
+
+impl Constraint {
+    /// Fields whose assignment is checked. Shortening a comp is not: After
+    /// Effects trims the work area to fit.
+    fn triggers(&self) -> Vec<&'static str> {
+        match *self {
+            Constraint::SumAtMost { first, second, .. } => vec![first, second],
+            Constraint::Before { earlier, later } => vec![earlier, later],
+            Constraint::ComponentsAtLeast { field, .. } | Constraint::IntegerIn { field, .. } => vec![field],
+        }
+    }
+
+    /// Message and suggestion for the receiver's known values, if they
+    /// break the constraint
+    fn check(&self, receiver: &str, known: &HashMap<String, Literal>) -> Option<(String, String)> {
+        let number = |field: &str| match known.get(field) {
+            Some(Literal::Number(n)) => Some(*n),
+            _ => None,
+        };
+        match *self {
+            Constraint::SumAtMost { first, second, limit } => {
+                let (a, b, max) = (number(first)?, number(second)?, number(limit)?);
+                (a + b > max).then(|| (
+                    format!("{r}.{} + {r}.{} ({} + {}) exceeds {r}.{} ({})", first, second, a, b, limit, max, r = receiver),
+                    format!("Keep {} at most {} or start earlier", second, (max - a).max(0.0)),
+                ))
+            }
+            Constraint::Before { earlier, later } => {
+                let (start, end) = (number(earlier)?, number(later)?);
+                (start >= end).then(|| (
+                    format!("{r}.{} ({}) must be before {r}.{} ({})", earlier, start, later, end, r = receiver),
+                    format!("Set {} later than {}", later, earlier),
+                ))
+            }
+            Constraint::ComponentsAtLeast { field, min } => {
+                let components = match known.get(field)? {
+                    Literal::Array(components) => components,
+                    Literal::Number(_) => return None,
+                };
+                components.iter().any(|c| *c < min || c.fract() != 0.0).then(|| (
+                    format!("{}.{} components must be whole numbers of at least {}, got [{}]", receiver, field, min,
+                        components.iter().map(f64::to_string).collect::<Vec<_>>().join(", ")),
+                    "Use [1, 1] for full resolution, [2, 2] for half".to_string(),
+                ))
+            }
+            Constraint::IntegerIn { field, min, max } => {
+                let value = number(field)?;
+                (value < min || value > max || value.fract() != 0.0).then(|| (
+                    format!("{}.{} must be a whole number from {} to {}, got {}", receiver, field, min, max, value),
+                    format!("{} is no label; {} to {} are the label colors", min, min + 1.0, max),
+                ))
+            }
+        }
+    }
+}
+
+/// A statically known property value
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Number(f64),
+    Array(Vec<f64>),
+}
+
+/// Checks relationships between property values the script assigns, such as
+/// `workAreaStart + workAreaDuration <= duration` or `inPoint < outPoint`.
+///
+/// Values are tracked per receiver variable in source order and only while
+/// they are literals; assigning anything else, or reassigning the variable,
+/// forgets them. Comps created with `addComp` start with the duration passed.
+pub fn validate_property_constraints(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let mut known: HashMap<String, HashMap<String, Literal>> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_identifier() || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+
+        // `comp = ...` starts over with a new object
+        if tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+            let values = known.entry(token.text.clone()).or_default();
+            values.clear();
+            if let Some(duration) = created_comp_duration(&tokens, i + 2) {
+                values.insert("duration".to_string(), Literal::Number(duration));
+            }
+            continue;
+        }
+
+        // `comp.field = value`
+        let field = match (tokens.get(i + 1), tokens.get(i + 2), tokens.get(i + 3)) {
+            (Some(dot), Some(field), Some(eq)) if dot.is_punct(".") && field.is_identifier() && eq.is_punct("=") => field,
+            _ => continue,
+        };
+        let values = known.entry(token.text.clone()).or_default();
+        match literal(&tokens, i + 4) {
+            Some(value) => values.insert(field.text.clone(), value),
+            None => values.remove(&field.text),
+        };
+
+        for constraint in CONSTRAINTS.iter().filter(|c| c.triggers().contains(&field.text.as_str())) {
+            if let Some((message, suggestion)) = constraint.check(&token.text, values) {
+                errors.push(constraint_error(script, file_path, token, &tokens[i + 2], message, suggestion));
+            }
+        }
+    }
+
+    errors
+}
+
+/// The literal at `start` when it makes up the whole assigned value
+fn literal(tokens: &[Token], start: usize) -> Option<Literal> {
+    let (value, next) = if tokens.get(start)?.is_punct("[") {
+        let mut components = Vec::new();
+        let mut i = start + 1;
+        loop {
+            let (n, after) = number(tokens, i)?;
+            components.push(n);
+            i = after;
+            match tokens.get(i) {
+                Some(t) if t.is_punct(",") => i += 1,
+                Some(t) if t.is_punct("]") => break,
+                _ => return None,
+            }
+        }
+        (Literal::Array(components), i + 1)
+    } else {
+        let (n, next) = number(tokens, start)?;
+        (Literal::Number(n), next)
+    };
+    match tokens.get(next) {
+        None => Some(value),
+        Some(t) if t.is_punct(";") || t.is_punct("}") || t.newlines_before > 0 => Some(value),
+        _ => None,
+    }
+}
+
+/// A number literal at `i`, optionally negated, and the index after it
+fn number(tokens: &[Token], i: usize) -> Option<(f64, usize)> {
+    let negative = tokens.get(i)?.is_punct("-");
+    let token = tokens.get(if negative { i + 1 } else { i }).filter(|t| t.kind == TokenKind::Number)?;
+    let value: f64 = token.text.parse().ok()?;
+    Some((if negative { -value } else { value }, if negative { i + 2 } else { i + 1 }))
+}
+
+/// The duration argument of an `items.addComp(name, w, h, pa, duration, fps)`
+/// call making up the value at `start`
+fn created_comp_duration(tokens: &[Token], start: usize) -> Option<f64> {
+    let mut i = start;
+    while i < tokens.len() && !tokens[i].is_punct(";") && (i == start || tokens[i].newlines_before == 0) {
+        if tokens[i].text == "addComp" && tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+            let close = matching_bracket(tokens, i + 1)?;
+            let mut arg = 0;
+            let mut j = i + 2;
+            while j < close {
+                if tokens[j].is_punct(",") {
+                    arg += 1;
+                    if arg == 4 {
+                        return number(tokens, j + 1)
+                            .filter(|(_, next)| tokens.get(*next).is_some_and(|t| t.is_punct(",") || t.is_punct(")")))
+                            .map(|(duration, _)| duration);
+                    }
+                }
+                j = matching_bracket(tokens, j).unwrap_or(j) + 1;
+            }
+            return None;
+        }
+        i += 1;
+    }
+    None
+}
+
+fn constraint_error(script: &str, file_path: &str, receiver: &Token, field: &Token, message: String, suggestion: String) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(receiver.line)
+        .column(receiver.column)
+        .length(field.end - receiver.start)
+        .code_snippet(script.lines().nth(receiver.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule("property-constraint")
+        .build();
+    ValidatorError::Script {
+        message,
+        context,
+        severity: ErrorSeverity::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_field_constraints() {
+        let script = r#"var comp = app.project.items.addComp("Main", 1920, 1080, 1, 10, 30);
+comp.workAreaStart = 4;
+comp.workAreaDuration = 8;
+comp.resolutionFactor = [0, 1];
+var layer = comp.layer(1);
+layer.inPoint = 5;
+layer.outPoint = 2;
+layer.label = 17;
+layer.outPoint = someTime;
+layer.inPoint = 6;
+comp = app.project.activeItem;
+comp.workAreaDuration = 8;
+"#;
+        let errors = validate_property_constraints(script, "a.jsx");
+        let found: Vec<(usize, &str)> = errors.iter().map(|e| (e.get_context().line.unwrap(), e.get_message())).collect();
+        assert_eq!(found, vec![
+            (3, "comp.workAreaStart + comp.workAreaDuration (4 + 8) exceeds comp.duration (10)"),
+            (4, "comp.resolutionFactor components must be whole numbers of at least 1, got [0, 1]"),
+            (7, "layer.inPoint (5) must be before layer.outPoint (2)"),
+            (8, "layer.label must be a whole number from 0 to 16, got 17"),
+        ]);
+        assert_eq!(errors[0].get_context().suggestion(), Some("Keep workAreaDuration at most 6 or start earlier"));
+    }
+}
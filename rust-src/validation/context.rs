@@ -1,4 +1,5 @@
-use super::rules::{ValidationRule, PropertyValueType};
+use super::rules::PropertyValueType;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -7,22 +8,26 @@ pub struct ValidationContext {
     // Track variable assignments and their inferred types
     variable_types: HashMap<String, PropertyValueType>,
     // Track property assignments to validate against correct types
+    #[allow(dead_code)]
     property_assignments: Vec<PropertyAssignment>,
     // Track the current object context (e.g., inside a layer, property, etc.)
     current_context: Vec<ObjectContext>,
     // Track project settings
     pub project_bpc: u8,
     pub ae_version: String,
+    // Known value of app.isWatchFolder on the current code path, if any
+    pub is_watch_folder: Option<bool>,
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 struct PropertyAssignment {
     target_property: String,  // e.g., "rotation", "position"
     target_object: String,    // e.g., "layer", "camera"
     value: Value,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ObjectContext {
     App,
     Project,
@@ -69,21 +74,21 @@ pub enum ObjectContext {
     AudioOptions,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AutoKernType {
     NoAutoKern,
     MetricKern,
     OpticalKern,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BaselineDirection {
     WithStream,
     VerticalRotated,
     VerticalCrossStream,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FontCapsOption {
     NormalCaps,
     SmallCaps,
@@ -99,7 +104,7 @@ pub struct EffectInfo {
     pub category: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextValidationContext {
     pub auto_kern_type: Option<AutoKernType>,
     pub baseline_direction: Option<BaselineDirection>,
@@ -107,6 +112,12 @@ pub struct TextValidationContext {
     pub mixed_values: bool,
 }
 
+impl Default for ValidationContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ValidationContext {
     pub fn new() -> Self {
         ValidationContext {
@@ -115,6 +126,7 @@ impl ValidationContext {
             current_context: Vec::new(),
             project_bpc: 8,
             ae_version: "2025".to_string(),
+            is_watch_folder: None,
         }
     }
 
@@ -275,6 +287,33 @@ impl ValidationContext {
     pub fn get_variable_type(&self, var_name: &str) -> Option<&PropertyValueType> {
         self.variable_types.get(var_name)
     }
+
+    /// Narrows `is_watch_folder` for a branch header such as
+    /// `if (!app.isWatchFolder)` or `else of if (app.isWatchFolder)`.
+    /// Headers combining several tests with `||` tell us nothing.
+    pub fn enter_branch(&mut self, header: &str) {
+        let (negated, condition) = match header.strip_prefix("else of ") {
+            Some(rest) => (true, rest),
+            None => (false, header),
+        };
+        if !condition.contains("app.isWatchFolder") || condition.contains("||") {
+            return;
+        }
+        if negated && condition.contains("&&") {
+            return;
+        }
+        let compact: String = condition.chars().filter(|c| !c.is_whitespace()).collect();
+        let is_false = compact.contains("!app.isWatchFolder")
+            || compact.contains("app.isWatchFolder==false")
+            || compact.contains("app.isWatchFolder===false");
+        self.is_watch_folder = Some(is_false == negated);
+    }
+}
+
+impl Default for TextValidationContext {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TextValidationContext {
@@ -0,0 +1,430 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use super::schema::ValueSchema;
+
+/// Check a property value must pass beyond its type, range and allowed
+/// values. It is plain data rather than a closure, so it is written to and
+/// loaded from a dumped model like the rest of a [`ValidationRule`].
+///
+/// [`ValidationRule`]: super::rules::ValidationRule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomValidator {
+    /// Strings must match the pattern
+    Regex(String),
+    /// The value must equal one of these
+    OneOf(Vec<Value>),
+    /// Strings, in characters, or arrays, in elements, of a length in `min..=max`
+    LengthRange {
+        #[serde(default)]
+        min: Option<usize>,
+        #[serde(default)]
+        max: Option<usize>,
+    },
+    /// Only valid while the sibling `property` satisfies the expression
+    /// `when`, in which `value` is the sibling's value
+    DependsOn { property: String, when: String },
+    /// An expression that must hold, such as `value[0] < value[1]` or
+    /// `len(value) <= 3 && value != ""`. `value` is the value being set;
+    /// other names are sibling properties. Checks on unknown siblings pass.
+    Expression {
+        check: String,
+        #[serde(default)]
+        message: Option<String>,
+    },
+    /// Structured values such as arrays of `KeyframeEase`, checked field by field
+    Schema(ValueSchema),
+    /// Every check must pass
+    All(Vec<CustomValidator>),
+}
+
+impl CustomValidator {
+    /// Checks `value` on its own; [`CustomValidator::DependsOn`] and
+    /// expressions naming siblings pass
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        self.validate_in(value, &Map::new())
+    }
+
+    /// Checks `value` on an object whose other known property values are
+    /// `siblings`
+    pub fn validate_in(&self, value: &Value, siblings: &Map<String, Value>) -> Result<(), String> {
+        match self {
+            CustomValidator::Regex(pattern) => {
+                let regex = Regex::new(pattern).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+                match value.as_str() {
+                    Some(text) if !regex.is_match(text) => Err(format!("'{}' does not match {}", text, pattern)),
+                    _ => Ok(()),
+                }
+            }
+            CustomValidator::OneOf(allowed) => {
+                if allowed.contains(value) {
+                    Ok(())
+                } else {
+                    let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+                    Err(format!("Expected one of {}, got {}", allowed.join(", "), value))
+                }
+            }
+            CustomValidator::LengthRange { min, max } => {
+                let (length, unit) = match value {
+                    Value::String(text) => (text.chars().count(), "characters"),
+                    Value::Array(items) => (items.len(), "elements"),
+                    _ => return Ok(()),
+                };
+                match (min, max) {
+                    (Some(min), _) if length < *min => Err(format!("Expected at least {} {}, got {}", min, unit, length)),
+                    (_, Some(max)) if length > *max => Err(format!("Expected at most {} {}, got {}", max, unit, length)),
+                    _ => Ok(()),
+                }
+            }
+            CustomValidator::DependsOn { property, when } => {
+                let sibling = match siblings.get(property) {
+                    Some(sibling) => sibling,
+                    None => return Ok(()),
+                };
+                match evaluate(when, sibling, siblings)? {
+                    Value::Bool(false) => Err(format!("Only valid when {} satisfies {} (it is {})", property, when, sibling)),
+                    _ => Ok(()),
+                }
+            }
+            CustomValidator::Expression { check, message } => match evaluate(check, value, siblings)? {
+                Value::Bool(false) => Err(message.clone().unwrap_or_else(|| format!("{} does not satisfy {}", value, check))),
+                _ => Ok(()),
+            },
+            CustomValidator::Schema(schema) => schema.validate(value),
+            CustomValidator::All(validators) => validators.iter().try_for_each(|v| v.validate_in(value, siblings)),
+        }
+    }
+}
+
+/// Evaluates `expression` with `value` bound. Operators and functions
+/// propagate `null` for missing data: `null` results count as passing, and
+/// `false && null` is still `false`.
+pub fn evaluate(expression: &str, value: &Value, siblings: &Map<String, Value>) -> Result<Value, String> {
+    let tokens = tokenize(expression).map_err(|e| format!("Invalid expression '{}': {}", expression, e))?;
+    let mut parser = Parser { tokens: &tokens, pos: 0, value, siblings };
+    let result = parser.or().map_err(|e| format!("Invalid expression '{}': {}", expression, e))?;
+    if parser.pos < tokens.len() {
+        return Err(format!("Invalid expression '{}': unexpected '{}'", expression, tokens[parser.pos].text()));
+    }
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Number(f64),
+    Str(String),
+    Name(String),
+    Op(&'static str),
+}
+
+impl Tok {
+    fn text(&self) -> String {
+        match self {
+            Tok::Number(n) => n.to_string(),
+            Tok::Str(s) => format!("\"{}\"", s),
+            Tok::Name(name) => name.clone(),
+            Tok::Op(op) => op.to_string(),
+        }
+    }
+}
+
+const OPERATORS: &[&str] = &["&&", "||", "==", "!=", "<=", ">=", "<", ">", "+", "-", "*", "/", "%", "!", "(", ")", "[", "]", ","];
+
+fn tokenize(source: &str) -> Result<Vec<Tok>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit)) {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Tok::Number(text.parse().map_err(|_| format!("bad number '{}'", text))?));
+        } else if c == '"' || c == '\'' {
+            let end = chars[i + 1..].iter().position(|&ch| ch == c).ok_or("unterminated string")?;
+            tokens.push(Tok::Str(chars[i + 1..i + 1 + end].iter().collect()));
+            i += end + 2;
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                i += 1;
+            }
+            tokens.push(Tok::Name(chars[start..i].iter().collect()));
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let op = OPERATORS.iter().find(|op| rest.starts_with(**op)).ok_or_else(|| format!("unexpected '{}'", c))?;
+            tokens.push(Tok::Op(op));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over `||`, `&&`, comparisons, `+ -`, `* / %`, unary
+/// `! -`, indexing and calls, evaluating as it parses
+struct Parser<'a> {
+    tokens: &'a [Tok],
+    pos: usize,
+    value: &'a Value,
+    siblings: &'a Map<String, Value>,
+}
+
+impl Parser<'_> {
+    fn eat(&mut self, op: &str) -> bool {
+        if matches!(self.tokens.get(self.pos), Some(Tok::Op(o)) if *o == op) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), String> {
+        if self.eat(op) {
+            Ok(())
+        } else {
+            Err(format!("expected '{}'", op))
+        }
+    }
+
+    fn or(&mut self) -> Result<Value, String> {
+        let mut left = self.and()?;
+        while self.eat("||") {
+            let right = self.and()?;
+            left = match (truth(&left), truth(&right)) {
+                (Some(true), _) | (_, Some(true)) => Value::Bool(true),
+                (Some(false), Some(false)) => Value::Bool(false),
+                _ => Value::Null,
+            };
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Value, String> {
+        let mut left = self.comparison()?;
+        while self.eat("&&") {
+            let right = self.comparison()?;
+            left = match (truth(&left), truth(&right)) {
+                (Some(false), _) | (_, Some(false)) => Value::Bool(false),
+                (Some(true), Some(true)) => Value::Bool(true),
+                _ => Value::Null,
+            };
+        }
+        Ok(left)
+    }
+
+    fn comparison(&mut self) -> Result<Value, String> {
+        let left = self.sum()?;
+        for op in ["==", "!=", "<=", ">=", "<", ">"] {
+            if self.eat(op) {
+                let right = self.sum()?;
+                return Ok(compare(op, &left, &right));
+            }
+        }
+        Ok(left)
+    }
+
+    fn sum(&mut self) -> Result<Value, String> {
+        let mut left = self.product()?;
+        loop {
+            let op = if self.eat("+") { "+" } else if self.eat("-") { "-" } else { return Ok(left) };
+            let right = self.product()?;
+            left = match (op, &left, &right) {
+                ("+", Value::String(a), b) => Value::String(format!("{}{}", a, display(b))),
+                _ => arithmetic(op, &left, &right),
+            };
+        }
+    }
+
+    fn product(&mut self) -> Result<Value, String> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat("*") { "*" } else if self.eat("/") { "/" } else if self.eat("%") { "%" } else { return Ok(left) };
+            let right = self.unary()?;
+            left = arithmetic(op, &left, &right);
+        }
+    }
+
+    fn unary(&mut self) -> Result<Value, String> {
+        if self.eat("!") {
+            return Ok(truth(&self.unary()?).map_or(Value::Null, |b| Value::Bool(!b)));
+        }
+        if self.eat("-") {
+            return Ok(number(&self.unary()?).map_or(Value::Null, |n| json_number(-n)));
+        }
+        let mut operand = self.primary()?;
+        while self.eat("[") {
+            let index = self.or()?;
+            self.expect("]")?;
+            operand = match (&operand, number(&index)) {
+                (Value::Array(items), Some(i)) if i >= 0.0 => items.get(i as usize).cloned().unwrap_or(Value::Null),
+                _ => Value::Null,
+            };
+        }
+        Ok(operand)
+    }
+
+    fn primary(&mut self) -> Result<Value, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("unexpected end")?;
+        self.pos += 1;
+        match token {
+            Tok::Number(n) => Ok(json_number(n)),
+            Tok::Str(s) => Ok(Value::String(s)),
+            Tok::Op("(") => {
+                let inner = self.or()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Tok::Name(name) if self.eat("(") => {
+                let mut args = Vec::new();
+                if !self.eat(")") {
+                    loop {
+                        args.push(self.or()?);
+                        if self.eat(")") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                call(&name, &args)
+            }
+            Tok::Name(name) => Ok(match name.as_str() {
+                "value" => self.value.clone(),
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" => Value::Null,
+                _ => self.siblings.get(&name).cloned().unwrap_or(Value::Null),
+            }),
+            Tok::Op(op) => Err(format!("unexpected '{}'", op)),
+        }
+    }
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value, String> {
+    let numbers: Option<Vec<f64>> = args.iter().map(number).collect();
+    Ok(match (name, args) {
+        ("len", [Value::String(s)]) => json_number(s.chars().count() as f64),
+        ("len", [Value::Array(items)]) => json_number(items.len() as f64),
+        ("len", [_]) => Value::Null,
+        ("abs", [_]) => numbers.map_or(Value::Null, |n| json_number(n[0].abs())),
+        ("floor", [_]) => numbers.map_or(Value::Null, |n| json_number(n[0].floor())),
+        ("min", [_, ..]) => numbers.map_or(Value::Null, |n| json_number(n.into_iter().fold(f64::INFINITY, f64::min))),
+        ("max", [_, ..]) => numbers.map_or(Value::Null, |n| json_number(n.into_iter().fold(f64::NEG_INFINITY, f64::max))),
+        ("len" | "abs" | "floor" | "min" | "max", _) => return Err(format!("wrong number of arguments to {}()", name)),
+        _ => return Err(format!("unknown function {}()", name)),
+    })
+}
+
+fn compare(op: &str, left: &Value, right: &Value) -> Value {
+    if left.is_null() || right.is_null() {
+        return Value::Null;
+    }
+    let ordering = match (number(left), number(right), left, right) {
+        (Some(a), Some(b), _, _) => a.partial_cmp(&b),
+        (_, _, Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    let result = match (op, ordering) {
+        ("==", Some(ordering)) => ordering.is_eq(),
+        ("!=", Some(ordering)) => ordering.is_ne(),
+        ("==", None) => left == right,
+        ("!=", None) => left != right,
+        ("<", Some(ordering)) => ordering.is_lt(),
+        ("<=", Some(ordering)) => ordering.is_le(),
+        (">", Some(ordering)) => ordering.is_gt(),
+        (">=", Some(ordering)) => ordering.is_ge(),
+        _ => return Value::Null,
+    };
+    Value::Bool(result)
+}
+
+fn arithmetic(op: &str, left: &Value, right: &Value) -> Value {
+    let (a, b) = match (number(left), number(right)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Value::Null,
+    };
+    match op {
+        "+" => json_number(a + b),
+        "-" => json_number(a - b),
+        "*" => json_number(a * b),
+        "/" if b != 0.0 => json_number(a / b),
+        "%" if b != 0.0 => json_number(a % b),
+        _ => Value::Null,
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    value.as_f64()
+}
+
+fn truth(value: &Value) -> Option<bool> {
+    match value {
+        Value::Null => None,
+        Value::Bool(b) => Some(*b),
+        Value::Number(n) => Some(n.as_f64() != Some(0.0)),
+        Value::String(s) => Some(!s.is_empty()),
+        Value::Array(_) | Value::Object(_) => Some(true),
+    }
+}
+
+fn json_number(n: f64) -> Value {
+    serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number)
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::validation::property::validate_property_value;
+    use crate::validation::rules::{PropertyValueType, ValidationRule};
+
+    #[test]
+    fn test_custom_validators() {
+        let guid = CustomValidator::Regex(r"^[0-9a-fA-F]{8}(-[0-9a-fA-F]{4}){3}-[0-9a-fA-F]{12}$".to_string());
+        assert!(guid.validate(&json!("0A1B2C3D-0000-4000-8000-00000000000F")).is_ok());
+        assert!(guid.validate(&json!("not-a-guid")).is_err());
+
+        let comment = CustomValidator::LengthRange { min: None, max: Some(5) };
+        assert_eq!(comment.validate(&json!("tooooo long")).unwrap_err(), "Expected at most 5 characters, got 11");
+        assert!(CustomValidator::OneOf(vec![json!(1), json!(2)]).validate(&json!(3)).is_err());
+        let rule = ValidationRule::simple(PropertyValueType::ArbText).with_validator(comment.clone());
+        assert!(validate_property_value(&json!("short"), &rule).is_ok());
+        assert!(validate_property_value(&json!("much too long"), &rule).is_err());
+
+        let work_area = CustomValidator::All(vec![
+            CustomValidator::Expression { check: "value >= 0 && value + workAreaDuration <= duration".to_string(), message: None },
+            CustomValidator::DependsOn { property: "hasVideo".to_string(), when: "value == true".to_string() },
+        ]);
+        let comp = json!({ "workAreaDuration": 5, "duration": 10, "hasVideo": true });
+        let comp = comp.as_object().unwrap();
+        assert!(work_area.validate_in(&json!(4), comp).is_ok());
+        assert_eq!(work_area.validate_in(&json!(6), comp).unwrap_err(), "6 does not satisfy value >= 0 && value + workAreaDuration <= duration");
+        // Siblings that are not known do not fail the check
+        assert!(work_area.validate(&json!(60)).is_ok());
+        let mut audio = comp.clone();
+        audio.insert("hasVideo".to_string(), json!(false));
+        assert_eq!(work_area.validate_in(&json!(1), &audio).unwrap_err(), "Only valid when hasVideo satisfies value == true (it is false)");
+
+        assert_eq!(evaluate("len(value) == 2 && value[0] < value[1]", &json!([1, 3]), &Map::new()), Ok(json!(true)));
+        assert_eq!(evaluate("max(1, -value, 2) % 2", &json!(-7), &Map::new()), Ok(json!(1.0)));
+        assert_eq!(evaluate("'#' + value == \"#a\" || missing > 1", &json!("a"), &Map::new()), Ok(json!(true)));
+        assert!(evaluate("value >", &json!(1), &Map::new()).unwrap_err().starts_with("Invalid expression 'value >': "));
+
+        let round_trip: CustomValidator = serde_json::from_value(serde_json::to_value(&work_area).unwrap()).unwrap();
+        assert_eq!(round_trip, work_area);
+        assert_eq!(serde_json::to_value(&comment).unwrap(), json!({ "length_range": { "min": null, "max": 5 } }));
+    }
+}
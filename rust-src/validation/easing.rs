@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{significant_tokens, Token, TokenKind};
+use crate::api::interpolation::KeyframeTemporal;
+
+/// A named ease: the speed and influence of each side of a key, as given to
+/// `new KeyframeEase(speed, influence)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EasingPreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Speed and influence of the side entering the key
+    pub in_ease: (f32, f32),
+    /// Speed and influence of the side leaving the key
+    pub out_ease: (f32, f32),
+}
+
+/// Influence After Effects gives a side it does not ease
+const DEFAULT_INFLUENCE: f32 = 16.66667;
+
+/// Eases scripts commonly write by hand, so they can be recognized in
+/// `setTemporalEaseAtKey` calls and generated by the codegen helpers
+pub const EASING_PRESETS: &[EasingPreset] = &[
+    EasingPreset {
+        name: "easy-ease",
+        description: "Easy Ease (F9): slows into and out of the key",
+        in_ease: (0.0, 33.33333),
+        out_ease: (0.0, 33.33333),
+    },
+    EasingPreset {
+        name: "easy-ease-in",
+        description: "Easy Ease In: slows into the key",
+        in_ease: (0.0, 33.33333),
+        out_ease: (0.0, DEFAULT_INFLUENCE),
+    },
+    EasingPreset {
+        name: "easy-ease-out",
+        description: "Easy Ease Out: slows out of the key",
+        in_ease: (0.0, DEFAULT_INFLUENCE),
+        out_ease: (0.0, 33.33333),
+    },
+    EasingPreset {
+        name: "strong-ease",
+        description: "A longer ease on both sides, for motion that settles gently",
+        in_ease: (0.0, 66.66667),
+        out_ease: (0.0, 66.66667),
+    },
+    EasingPreset {
+        name: "expo-ease",
+        description: "Nearly all of the move happens mid-way between keys",
+        in_ease: (0.0, 90.0),
+        out_ease: (0.0, 90.0),
+    },
+];
+
+/// How far hand-written numbers may be from a preset's and still match it
+const TOLERANCE: f32 = 0.01;
+
+impl EasingPreset {
+    pub fn named(name: &str) -> Option<&'static EasingPreset> {
+        EASING_PRESETS.iter().find(|preset| preset.name == name)
+    }
+
+    /// The preset with these eases on both sides, if any
+    pub fn matching(in_ease: (f32, f32), out_ease: (f32, f32)) -> Option<&'static EasingPreset> {
+        let close = |a: (f32, f32), b: (f32, f32)| (a.0 - b.0).abs() <= TOLERANCE && (a.1 - b.1).abs() <= TOLERANCE;
+        EASING_PRESETS.iter().find(|preset| close(preset.in_ease, in_ease) && close(preset.out_ease, out_ease))
+    }
+
+    /// Zero-speed eases of `influence` on both sides, a stronger or gentler
+    /// Easy Ease
+    pub fn ease_in_out(influence: f32) -> EasingPreset {
+        EasingPreset {
+            name: "ease-in-out",
+            description: "Slows into and out of the key",
+            in_ease: (0.0, influence),
+            out_ease: (0.0, influence),
+        }
+    }
+
+    /// The in and out eases for a property with `dimensions` ease
+    /// dimensions, as `setTemporalEaseAtKey` takes them
+    pub fn eases(&self, dimensions: usize) -> (Vec<KeyframeTemporal>, Vec<KeyframeTemporal>) {
+        let side = |(speed, influence): (f32, f32)| vec![KeyframeTemporal::new(speed, influence); dimensions];
+        (side(self.in_ease), side(self.out_ease))
+    }
+}
+
+/// A `new KeyframeEase(speed, influence)` with literal numbers, and the
+/// token of its influence
+#[derive(Clone, Copy)]
+struct Ease<'a> {
+    speed: f32,
+    influence: f32,
+    token: &'a Token,
+    /// Index after the closing parenthesis
+    end: usize,
+}
+
+/// Checks hand-written `KeyframeEase` values:
+///
+/// - influence must be between 0.1 and 100
+/// - an influence below 1 that is a preset's influence divided by 100 was
+///   most likely written as a fraction, and makes the ease almost linear
+/// - `setTemporalEaseAtKey` needs as many eases entering the key as leaving it
+///
+/// Eases are read from `new KeyframeEase(...)` literals, directly in the
+/// call or through variables assigned from them.
+pub fn validate_keyframe_eases(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let mut variables: HashMap<&str, Ease> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_keyword("new") {
+            if let Some(ease) = ease_literal(&tokens, i) {
+                check_influence(script, file_path, &ease, &mut errors);
+            }
+            continue;
+        }
+        if !token.is_identifier() || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+
+        // `var ease = new KeyframeEase(0, 33.33);`
+        if tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+            match ease_literal(&tokens, i + 2).filter(|ease| ends_statement(&tokens, ease.end)) {
+                Some(ease) => variables.insert(&token.text, ease),
+                None => variables.remove(token.text.as_str()),
+            };
+            continue;
+        }
+
+        // `prop.setTemporalEaseAtKey(key, [inEases], [outEases])`
+        let call = matches!(tokens.get(i + 1..i + 4),
+            Some([dot, method, open]) if dot.is_punct(".") && method.text == "setTemporalEaseAtKey" && open.is_punct("("));
+        if !call {
+            continue;
+        }
+        let sides = match ease_arrays(&tokens, i + 4) {
+            Some(sides) => sides,
+            None => continue,
+        };
+        let [in_side, out_side] = sides.map(|side| side.iter().map(|&j| {
+            let variable = || variables.get(tokens[j].text.as_str()).copied()
+                .filter(|_| tokens.get(j + 1).is_some_and(|t| t.is_punct(",") || t.is_punct("]")));
+            ease_literal(&tokens, j).or_else(variable)
+        }).collect::<Vec<_>>());
+        if in_side.len() == out_side.len() {
+            continue;
+        }
+        let preset = match (in_side.first(), out_side.first()) {
+            (Some(Some(entering)), Some(Some(leaving))) => {
+                EasingPreset::matching((entering.speed, entering.influence), (leaving.speed, leaving.influence))
+            }
+            _ => None,
+        };
+        let method = &tokens[i + 2];
+        let suggestion = match preset {
+            Some(preset) => format!("Pass the same number of eases on both sides, one per dimension; for {} that is the same KeyframeEase on each", preset.name),
+            None => "Pass the same number of eases on both sides, one per dimension of the property".to_string(),
+        };
+        errors.push(ease_error(
+            script, file_path, method,
+            format!("setTemporalEaseAtKey is given {} ease(s) entering the key but {} leaving it", in_side.len(), out_side.len()),
+            suggestion,
+            "keyframe-ease-dimensions",
+            ErrorSeverity::Error,
+        ));
+    }
+
+    errors.sort_by_key(|e| (e.get_context().line, e.get_context().column));
+    errors
+}
+
+/// `new KeyframeEase(speed, influence)` starting at `start`
+fn ease_literal(tokens: &[Token], start: usize) -> Option<Ease<'_>> {
+    match tokens.get(start..start + 3)? {
+        [new, class, open] if new.is_keyword("new") && class.text == "KeyframeEase" && open.is_punct("(") => {}
+        _ => return None,
+    }
+    let (speed, comma) = signed_number(tokens, start + 3)?;
+    tokens.get(comma).filter(|t| t.is_punct(","))?;
+    let (influence, close) = signed_number(tokens, comma + 1)?;
+    tokens.get(close).filter(|t| t.is_punct(")"))?;
+    Some(Ease { speed, influence, token: &tokens[close - 1], end: close + 1 })
+}
+
+/// A number at `j`, maybe negated, and the index after it
+fn signed_number(tokens: &[Token], j: usize) -> Option<(f32, usize)> {
+    let sign = usize::from(tokens.get(j)?.is_punct("-"));
+    let number = tokens.get(j + sign).filter(|t| t.kind == TokenKind::Number)?;
+    let value: f32 = number.text.parse().ok()?;
+    Some((if sign == 1 { -value } else { value }, j + sign + 1))
+}
+
+/// Token indices of the eases in the two array arguments after the key
+/// index, starting just inside the call's parenthesis
+fn ease_arrays(tokens: &[Token], start: usize) -> Option<[Vec<usize>; 2]> {
+    let mut j = start;
+    let mut depth = 0;
+    // Skip the key index
+    while let Some(token) = tokens.get(j) {
+        match token.text.as_str() {
+            "(" | "[" => depth += 1,
+            ")" | "]" if depth == 0 => return None,
+            ")" | "]" => depth -= 1,
+            "," if depth == 0 => break,
+            _ => {}
+        }
+        j += 1;
+    }
+    let mut sides = [Vec::new(), Vec::new()];
+    for side in sides.iter_mut() {
+        if !tokens.get(j).is_some_and(|t| t.is_punct(",")) || !tokens.get(j + 1).is_some_and(|t| t.is_punct("[")) {
+            return None;
+        }
+        j += 2;
+        let mut depth = 0;
+        let mut element = Some(j);
+        while let Some(token) = tokens.get(j) {
+            match token.text.as_str() {
+                "(" | "[" => depth += 1,
+                "]" if depth == 0 => break,
+                ")" | "]" => depth -= 1,
+                "," if depth == 0 => {
+                    side.extend(element.take());
+                    element = Some(j + 1);
+                }
+                _ => {}
+            }
+            j += 1;
+        }
+        // `[]` has no elements; `[a, b]` ends with one not yet taken
+        side.extend(element.filter(|&e| e < j));
+        j += 1;
+    }
+    tokens.get(j).filter(|t| t.is_punct(")"))?;
+    Some(sides)
+}
+
+fn ends_statement(tokens: &[Token], end: usize) -> bool {
+    tokens.get(end).is_none_or(|t| t.is_punct(";") || t.newlines_before > 0)
+}
+
+fn check_influence(script: &str, file_path: &str, ease: &Ease, errors: &mut Vec<ValidatorError>) {
+    if !(0.1..=100.0).contains(&ease.influence) {
+        errors.push(ease_error(
+            script, file_path, ease.token,
+            format!("KeyframeEase influence {} is outside 0.1 to 100", ease.influence),
+            "Influence is the percentage of the time to the next key the ease extends over".to_string(),
+            "keyframe-ease-influence",
+            ErrorSeverity::Error,
+        ));
+        return;
+    }
+    // 0.33 for the 33.33 of Easy Ease
+    let as_percentage = ease.influence * 100.0;
+    let preset = EASING_PRESETS.iter()
+        .find(|preset| [preset.in_ease.1, preset.out_ease.1].iter().any(|influence| (influence - as_percentage).abs() < 1.0));
+    if let Some(preset) = preset.filter(|_| ease.influence < 1.0) {
+        errors.push(ease_error(
+            script, file_path, ease.token,
+            format!("KeyframeEase influence {} is a percentage, so the ease is almost linear", ease.influence),
+            format!("Write {} for the {} preset", preset.in_ease.1.max(preset.out_ease.1), preset.name),
+            "keyframe-ease-fraction",
+            ErrorSeverity::Warning,
+        ));
+    }
+}
+
+fn ease_error(
+    script: &str,
+    file_path: &str,
+    token: &Token,
+    message: String,
+    suggestion: String,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule(rule)
+        .build();
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyframe_eases() {
+        let script = r#"var ease = new KeyframeEase(0, 33.33333);
+var fraction = new KeyframeEase(0, 0.33);
+var pos = layer.transform.position;
+pos.setTemporalEaseAtKey(1, [ease], [ease]);
+pos.setTemporalEaseAtKey(2, [ease, ease], [ease]);
+layer.transform.scale.setTemporalEaseAtKey(1, [new KeyframeEase(0, 120), ease, ease], [ease, ease, ease]);
+pos.setTemporalEaseAtKey(3, eases, [ease]);
+"#;
+        let errors = validate_keyframe_eases(script, "ease.jsx");
+        let found: Vec<(usize, &str)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.rule_id().unwrap()))
+            .collect();
+        assert_eq!(found, vec![
+            (2, "keyframe-ease-fraction"),
+            (5, "keyframe-ease-dimensions"),
+            (6, "keyframe-ease-influence"),
+        ]);
+        assert_eq!(errors[0].get_context().suggestion(), Some("Write 33.33333 for the easy-ease preset"));
+        assert!(errors[1].get_context().suggestion().unwrap().contains("for easy-ease"));
+
+        assert_eq!(EasingPreset::matching((0.0, 33.333), (0.0, 16.66667)).map(|p| p.name), Some("easy-ease-in"));
+        let (in_ease, out_ease) = EasingPreset::named("strong-ease").unwrap().eases(2);
+        assert_eq!((in_ease.len(), out_ease[1].influence), (2, 66.66667));
+    }
+}
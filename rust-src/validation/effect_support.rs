@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use crate::api::objects::effects::{EffectAvailability, EffectMatchNames, EffectMetadata};
+
+lazy_static! {
+    static ref ADD_EFFECT_RE: Regex = Regex::new(r#"\.addProperty\s*\(\s*(?:"([^"\n]+)"|'([^'\n]+)')\s*\)"#).unwrap();
+    static ref BIT_DEPTH_RE: Regex = Regex::new(r"\bbitsPerChannel\s*=\s*(8|16|32)\b").unwrap();
+    static ref EFFECTS_CHECK_RE: Regex = Regex::new(r"\bapp\.effects\b").unwrap();
+    static ref METADATA: HashMap<&'static str, EffectMetadata> = EffectMatchNames::get_all_effects_with_metadata();
+}
+
+/// Checks effects added with `addProperty` against what is known about them:
+///
+/// - effects that process fewer bits than the project's `bitsPerChannel`,
+///   set earlier in the script, clip the extra precision
+/// - effects without GPU acceleration render on the CPU, which is slowest
+///   in a 32-bpc project
+/// - bundled third-party effects may be missing, unless the script checks
+///   `app.effects` first
+pub fn validate_effect_support(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let checks_installed = EFFECTS_CHECK_RE.is_match(script);
+    let mut errors = Vec::new();
+
+    for cap in ADD_EFFECT_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        let match_name = cap.get(1).or_else(|| cap.get(2)).unwrap().as_str();
+        let metadata = match METADATA.get(match_name) {
+            Some(metadata) => metadata,
+            None => continue,
+        };
+        let project_depth = BIT_DEPTH_RE.captures_iter(&script[..m.start()])
+            .last()
+            .and_then(|depth| depth[1].parse::<u8>().ok());
+        // Audio effects list no bit depths
+        let max_depth = metadata.bit_depth_support.iter().max().copied();
+
+        if let (Some(project), Some(effect)) = (project_depth, max_depth) {
+            if effect < project {
+                errors.push(diagnostic(
+                    script, file_path, m.start(),
+                    format!("{} only processes {} bpc; in this {}-bpc project it clips colors to {} bpc", metadata.display_name, effect, project, effect),
+                    "Use an effect that supports the project's bit depth, or lower app.project.bitsPerChannel".to_string(),
+                    "effect-bit-depth",
+                    ErrorSeverity::Warning,
+                ));
+            }
+            if project == 32 && !metadata.gpu_accelerated {
+                errors.push(diagnostic(
+                    script, file_path, m.start(),
+                    format!("{} is not GPU accelerated and renders on the CPU in this 32-bpc project", metadata.display_name),
+                    "Expect slower renders, or use a GPU-accelerated alternative".to_string(),
+                    "effect-gpu",
+                    ErrorSeverity::Info,
+                ));
+            }
+        }
+
+        if let EffectAvailability::Bundled { vendor } = metadata.availability {
+            if !checks_installed {
+                errors.push(diagnostic(
+                    script, file_path, m.start(),
+                    format!("{} is a {} plug-in bundled with After Effects and can be missing from an install", metadata.display_name, vendor),
+                    format!("Check that it is installed first: look for \"{}\" in app.effects", match_name),
+                    "effect-availability",
+                    ErrorSeverity::Info,
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+fn diagnostic(
+    script: &str,
+    file_path: &str,
+    start: usize,
+    message: String,
+    suggestion: String,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let line = script[..start].matches('\n').count() + 1;
+    let line_start = script[..start].rfind('\n').map_or(0, |i| i + 1);
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(script[line_start..start].chars().count() + 1)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule(rule)
+        .build();
+
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effect_support_in_project() {
+        let script = r#"var effects = layer.property("ADBE Effect Parade");
+effects.addProperty("ADBE Smart Blur");
+app.project.bitsPerChannel = 32;
+effects.addProperty("ADBE Smart Blur");
+effects.addProperty("ADBE Gaussian Blur 2");
+effects.addProperty("CC Radial Blur");
+"#;
+        let errors = validate_effect_support(script, "fx.jsx");
+        let found: Vec<(usize, &str)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.rule_id().unwrap()))
+            .collect();
+        assert_eq!(found, vec![
+            (4, "effect-bit-depth"),
+            (4, "effect-gpu"),
+            (6, "effect-gpu"),
+            (6, "effect-availability"),
+        ]);
+        assert_eq!(errors[0].get_message(), "Smart Blur only processes 16 bpc; in this 32-bpc project it clips colors to 16 bpc");
+
+        let checked = format!("var installed = app.effects;\n{}", script);
+        assert!(!validate_effect_support(&checked, "fx.jsx").iter().any(|e| e.rule_id() == Some("effect-availability")));
+    }
+}
@@ -0,0 +1,296 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use super::encoding::read_script;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity, SourceLocation};
+use super::script::lexer::{Token, TokenKind};
+use super::script::scope::{analyze_scopes, ScopeAnalysis};
+use super::script::validate_script;
+
+/// Inner diagnostics about names the outer script may define, which
+/// evaluated code can see: the caller's variables for `eval`, the outer
+/// script's globals for code run later. Names local to the outer function
+/// are reported as closure pitfalls instead where they are not visible.
+const OUTER_NAME_RULES: &[&str] = &["unknown-function", "implicit-global"];
+
+/// ScriptUI events whose handler may be given as a string of code
+const STRING_HANDLERS: &[&str] = &[
+    "onClick", "onChange", "onChanging", "onDraw", "onShow", "onClose", "onActivate",
+    "onDeactivate", "onEnterKey", "onResize", "onResizing", "onMove", "onMoving",
+];
+
+thread_local! {
+    /// Files being validated through `$.evalFile`, so cycles terminate
+    static EVALUATING: RefCell<HashSet<PathBuf>> = RefCell::new(HashSet::new());
+}
+
+/// The code in a string literal, with the position in the outer file of
+/// each of its characters
+pub struct EmbeddedCode {
+    pub text: String,
+    positions: Vec<(usize, usize)>,
+}
+
+impl EmbeddedCode {
+    /// Unescapes a string literal token. Line continuations are dropped and
+    /// escaped characters map to the position of their backslash.
+    pub fn from_literal(token: &Token) -> Option<Self> {
+        if token.kind != TokenKind::String || token.text.len() < 2 {
+            return None;
+        }
+        let mut chars = token.text.chars().skip(1).peekable();
+        let (mut line, mut column) = (token.line, token.column + 1);
+        let mut text = String::new();
+        let mut positions = Vec::new();
+        let quote = token.text.chars().next();
+
+        while let Some(c) = chars.next() {
+            if chars.peek().is_none() && Some(c) == quote {
+                break;
+            }
+            let start = (line, column);
+            column += 1;
+            let value = match c {
+                '\\' => {
+                    let escaped = chars.next();
+                    column += 1;
+                    match escaped {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('\n') => {
+                            line += 1;
+                            column = 1;
+                            continue;
+                        }
+                        Some(other) => other,
+                        None => break,
+                    }
+                }
+                other => other,
+            };
+            text.push(value);
+            positions.push(start);
+        }
+        Some(EmbeddedCode { text, positions })
+    }
+
+    /// Outer position of the 1-based `line` and `column` of the embedded code
+    pub fn outer_position(&self, line: usize, column: usize) -> (usize, usize) {
+        let (mut current_line, mut current_column) = (1, 1);
+        for (c, position) in self.text.chars().zip(&self.positions) {
+            if current_line == line && current_column == column {
+                return *position;
+            }
+            if c == '\n' {
+                current_line += 1;
+                current_column = 1;
+            } else {
+                current_column += 1;
+            }
+        }
+        self.positions.last().copied().unwrap_or((1, 1))
+    }
+}
+
+/// Where evaluated code runs, which decides the outer names it can see
+#[derive(Debug, Clone, Copy)]
+pub enum EvaluatedIn<'a> {
+    /// `eval`, which runs in the calling function and sees its variables
+    Caller,
+    /// Code run later in the global scope, such as a scheduled task. Names
+    /// local to `scope` of the outer script are reported under `rule`.
+    Global {
+        scope: usize,
+        rule: &'a str,
+        /// What runs the code, e.g. `Scheduled code`
+        code: &'a str,
+        /// When it runs, e.g. `the scheduled task runs`
+        runs: &'a str,
+    },
+}
+
+/// Validates code embedded in a string literal of `script` like a script of
+/// its own, mapping its diagnostics back to the literal in the outer file
+pub fn validate_embedded_code(
+    code: &EmbeddedCode,
+    outer: &ScopeAnalysis,
+    evaluated_in: EvaluatedIn,
+    script: &str,
+    file_path: &str,
+) -> Vec<ValidatorError> {
+    let inner = validate_script(&code.text, file_path);
+    let mut errors = Vec::new();
+
+    for mut error in inner.errors.into_iter().chain(inner.warnings) {
+        if is_about_outer_name(&error, outer) {
+            continue;
+        }
+        let context = error.get_context_mut();
+        let (line, column) = code.outer_position(context.line.unwrap_or(1), context.column.unwrap_or(1));
+        context.line = Some(line);
+        context.column = Some(column);
+        context.code_snippet = Some(script.lines().nth(line - 1).unwrap_or("").trim().to_string());
+        errors.push(error);
+    }
+
+    let (scope, rule, description, runs) = match evaluated_in {
+        EvaluatedIn::Global { scope, rule, code, runs } if scope != 0 => (scope, rule, code, runs),
+        _ => return errors,
+    };
+
+    // Names the code reads that only exist in the function that set it up
+    let analysis = analyze_scopes(&code.text);
+    let mut reported = HashSet::new();
+    for (i, token) in analysis.tokens.iter().enumerate() {
+        let is_member = i > 0 && (analysis.tokens[i - 1].is_punct(".") || analysis.tokens[i - 1].is_punct("?."));
+        if !token.is_identifier() || is_member || analysis.is_declared(&token.text, analysis.token_scopes[i]) {
+            continue;
+        }
+        let is_local = outer.is_declared(&token.text, scope) && !outer.global_declarations().contains(&token.text);
+        if !is_local || !reported.insert(token.text.as_str()) {
+            continue;
+        }
+        let (line, column) = code.outer_position(token.line, token.column);
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(line)
+            .column(column)
+            .length(token.text.chars().count())
+            .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some(format!(
+                "{} runs later in the global scope; store the value in a global such as $.global.{} or write it into the string",
+                description, token.text
+            )))
+            .rule(rule)
+            .arg("name", &token.text)
+            .build();
+        errors.push(ValidatorError::Scope {
+            message: format!("'{}' is local to {} and undefined when {}", token.text, outer.scope_label(scope), runs),
+            context,
+            severity: ErrorSeverity::Error,
+            variable: token.text.clone(),
+        });
+    }
+
+    errors
+}
+
+fn is_about_outer_name(error: &ValidatorError, outer: &ScopeAnalysis) -> bool {
+    let context = error.get_context();
+    let name = context.args.iter().find(|(key, _)| key == "name").map(|(_, value)| value.as_str());
+    context.rule.as_deref().is_some_and(|rule| OUTER_NAME_RULES.contains(&rule))
+        && name.is_some_and(|name| outer.scopes.iter().any(|s| s.declared.contains(name)))
+}
+
+/// Validates strings that are evaluated as code: `eval("...")`, ScriptUI
+/// handlers assigned as strings (`button.onClick = "..."`), which run in the
+/// global scope, and the files loaded with `$.evalFile("...")` when the path
+/// resolves next to the script. Diagnostics in strings point into the
+/// literal; those in an evaluated file point into that file, with the
+/// `evalFile` call as related location.
+pub fn validate_evaluated_strings(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let literal = match tokens.get(i + 2) {
+            Some(literal) if literal.kind == TokenKind::String => literal,
+            _ => continue,
+        };
+        let after_dot = i > 0 && tokens[i - 1].is_punct(".");
+        let single_argument = tokens.get(i + 3).is_some_and(|t| t.is_punct(")"));
+        let opens_call = tokens[i + 1].is_punct("(");
+
+        if token.text == "eval" && !after_dot && opens_call && single_argument {
+            if let Some(code) = EmbeddedCode::from_literal(literal) {
+                errors.extend(validate_embedded_code(&code, &analysis, EvaluatedIn::Caller, script, file_path));
+            }
+        } else if STRING_HANDLERS.contains(&token.text.as_str()) && after_dot && tokens[i + 1].is_punct("=") {
+            if let Some(code) = EmbeddedCode::from_literal(literal) {
+                let evaluated_in = EvaluatedIn::Global {
+                    scope: analysis.token_scopes[i],
+                    rule: "string-handler-closure",
+                    code: "A string handler",
+                    runs: "the handler runs",
+                };
+                errors.extend(validate_embedded_code(&code, &analysis, evaluated_in, script, file_path));
+            }
+        } else if token.text == "evalFile" && i >= 2 && tokens[i - 2].text == "$" && after_dot && opens_call && single_argument {
+            errors.extend(validate_evaluated_file(literal, &analysis, file_path));
+        }
+    }
+
+    errors
+}
+
+/// Validates the file a `$.evalFile` literal names, resolved against the
+/// folder of `file_path`. Paths that do not resolve are left alone.
+fn validate_evaluated_file(literal: &Token, outer: &ScopeAnalysis, file_path: &str) -> Vec<ValidatorError> {
+    let relative = &literal.text[1..literal.text.len() - 1];
+    let dir = Path::new(file_path).parent().unwrap_or(Path::new("."));
+    let path = dir.join(relative);
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+    let source = match read_script(&path) {
+        Ok(source) => source,
+        Err(_) => return Vec::new(),
+    };
+    if !EVALUATING.with(|files| files.borrow_mut().insert(canonical.clone())) {
+        return Vec::new();
+    }
+    let name = path.to_string_lossy().replace('\\', "/");
+    let inner = validate_script(&source.text, &name);
+    EVALUATING.with(|files| files.borrow_mut().remove(&canonical));
+
+    inner.errors.into_iter().chain(inner.warnings)
+        .filter(|error| !is_about_outer_name(error, outer))
+        .map(|mut error| {
+            error.get_context_mut().related.push(SourceLocation {
+                file: file_path.to_string(),
+                line: literal.line,
+                column: literal.column,
+            });
+            error
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_evaluated_strings() {
+        let dir = std::env::temp_dir().join(format!("ae-validator-embedded-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("helpers.jsx"), "function helper() {\n    alret(\"x\");\n}\n").unwrap();
+
+        let script = r#"function build(win) {
+    var count = 0;
+    eval("count++; alret(count);");
+    win.ok.onClick = "win.close(); alret('done');";
+    $.evalFile("helpers.jsx");
+    $.evalFile("missing.jsx");
+}
+"#;
+        let main = dir.join("main.jsx");
+        let errors = validate_evaluated_strings(script, main.to_str().unwrap());
+        let found: Vec<(Option<&str>, usize, usize)> = errors.iter()
+            .filter(|e| matches!(e.rule_id(), Some("unknown-function") | Some("string-handler-closure")))
+            .map(|e| (e.rule_id(), e.get_context().line.unwrap(), e.get_context().column.unwrap()))
+            .collect();
+        assert_eq!(found, vec![
+            (Some("unknown-function"), 3, 20),
+            (Some("unknown-function"), 4, 36),
+            (Some("string-handler-closure"), 4, 23),
+            (Some("unknown-function"), 2, 5),
+        ]);
+        let from_file = errors.iter().find(|e| e.rule_id() == Some("unknown-function") && e.get_context().line == Some(2)).unwrap();
+        assert!(from_file.get_context().file.as_deref().unwrap().ends_with("helpers.jsx"));
+        assert_eq!((from_file.get_context().related[0].line, from_file.get_context().related[0].column), (5, 16));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
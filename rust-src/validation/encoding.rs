@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::Path;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+
+/// How a script file was stored on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Not valid UTF-8; decoded byte by byte as Latin-1
+    Latin1,
+}
+
+impl SourceEncoding {
+    pub fn name(self) -> &'static str {
+        match self {
+            SourceEncoding::Utf8 => "UTF-8",
+            SourceEncoding::Utf8Bom => "UTF-8 with BOM",
+            SourceEncoding::Utf16Le => "UTF-16 LE",
+            SourceEncoding::Utf16Be => "UTF-16 BE",
+            SourceEncoding::Latin1 => "Latin-1",
+        }
+    }
+}
+
+/// A script decoded to text with `\n` line endings, so line and column
+/// numbers match what editors show
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptSource {
+    pub text: String,
+    pub encoding: SourceEncoding,
+    /// The file used `\r\n` (or bare `\r`) line endings
+    pub crlf: bool,
+    /// A `#!` first line was turned into a `//` comment
+    pub shebang: bool,
+}
+
+impl ScriptSource {
+    /// Decodes raw file contents. A BOM decides the encoding; without one,
+    /// UTF-16 is recognized by the NUL bytes ASCII text leaves in every
+    /// other byte, and anything that is not valid UTF-8 is read as Latin-1.
+    pub fn decode(bytes: &[u8]) -> Self {
+        let (text, encoding) = if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+            (String::from_utf8_lossy(rest).into_owned(), SourceEncoding::Utf8Bom)
+        } else if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+            (decode_utf16(rest, u16::from_le_bytes), SourceEncoding::Utf16Le)
+        } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+            (decode_utf16(rest, u16::from_be_bytes), SourceEncoding::Utf16Be)
+        } else if let Some(encoding) = sniff_utf16(bytes) {
+            let decoder = if encoding == SourceEncoding::Utf16Le { u16::from_le_bytes } else { u16::from_be_bytes };
+            (decode_utf16(bytes, decoder), encoding)
+        } else {
+            match std::str::from_utf8(bytes) {
+                Ok(text) => (text.to_string(), SourceEncoding::Utf8),
+                Err(_) => (bytes.iter().map(|&b| b as char).collect(), SourceEncoding::Latin1),
+            }
+        };
+
+        let crlf = text.contains('\r');
+        let mut text = if crlf { text.replace("\r\n", "\n").replace('\r', "\n") } else { text };
+        let shebang = text.starts_with("#!");
+        if shebang {
+            text.replace_range(..2, "//");
+        }
+
+        Self { text, encoding, crlf, shebang }
+    }
+
+    /// Encodes `text`, typically a rewrite of `self.text`, the way the file
+    /// was stored: same encoding, line endings and shebang
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let mut text = text.to_string();
+        if self.shebang && text.starts_with("//") {
+            text.replace_range(..2, "#!");
+        }
+        if self.crlf {
+            text = text.replace('\n', "\r\n");
+        }
+
+        match self.encoding {
+            SourceEncoding::Utf8 => text.into_bytes(),
+            SourceEncoding::Utf8Bom => [&[0xEF, 0xBB, 0xBF][..], text.as_bytes()].concat(),
+            SourceEncoding::Utf16Le => encode_utf16(&text, [0xFF, 0xFE], u16::to_le_bytes),
+            SourceEncoding::Utf16Be => encode_utf16(&text, [0xFE, 0xFF], u16::to_be_bytes),
+            SourceEncoding::Latin1 => text.chars().map(|c| u8::try_from(c).unwrap_or(b'?')).collect(),
+        }
+    }
+
+    /// Informational notes about how the file was read. Plain UTF-8 and a
+    /// UTF-8 BOM, which ExtendScript handles, are not reported.
+    pub fn diagnostics(&self, file_path: &str) -> Vec<ValidatorError> {
+        let mut notes = Vec::new();
+        let note = |message: String, suggestion: &str, severity: ErrorSeverity| ValidatorError::Script {
+            message,
+            context: ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(1)
+                .column(1)
+                .suggestion(Some(suggestion.to_string()))
+                .rule("source-encoding")
+                .build(),
+            severity,
+        };
+
+        match self.encoding {
+            SourceEncoding::Utf16Le | SourceEncoding::Utf16Be => notes.push(note(
+                format!("Script is saved as {}; it was transcoded for validation", self.encoding.name()),
+                "Save scripts as UTF-8 so diffs and other tools read them correctly",
+                ErrorSeverity::Info,
+            )),
+            SourceEncoding::Latin1 => notes.push(note(
+                "Script is not valid UTF-8; non-ASCII characters were read as Latin-1".to_string(),
+                "Re-save the script as UTF-8; ExtendScript may misread accented characters in string literals",
+                ErrorSeverity::Warning,
+            )),
+            SourceEncoding::Utf8 | SourceEncoding::Utf8Bom => {}
+        }
+        if self.shebang {
+            notes.push(note(
+                "Shebang line is not valid ExtendScript; it was ignored for validation".to_string(),
+                "Remove the #! line; After Effects reports a syntax error on it",
+                ErrorSeverity::Warning,
+            ));
+        }
+        notes
+    }
+}
+
+/// Reads and decodes a script file
+pub fn read_script<P: AsRef<Path>>(path: P) -> std::io::Result<ScriptSource> {
+    fs::read(path).map(|bytes| ScriptSource::decode(&bytes))
+}
+
+/// Writes `text` back to the file `source` was read from, in its encoding
+pub fn write_script<P: AsRef<Path>>(path: P, source: &ScriptSource, text: &str) -> std::io::Result<()> {
+    fs::write(path, source.encode(text))
+}
+
+fn decode_utf16(bytes: &[u8], decode: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| decode([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn encode_utf16(text: &str, bom: [u8; 2], encode: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    let mut bytes = bom.to_vec();
+    bytes.extend(text.encode_utf16().flat_map(encode));
+    bytes
+}
+
+/// BOM-less UTF-16: mostly-ASCII text has a NUL in every high byte
+fn sniff_utf16(bytes: &[u8]) -> Option<SourceEncoding> {
+    let sample = &bytes[..bytes.len().min(512) & !1];
+    if sample.len() < 4 {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let even_nuls = sample.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_nuls = sample.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+    if odd_nuls * 10 >= pairs * 9 && even_nuls == 0 {
+        Some(SourceEncoding::Utf16Le)
+    } else if even_nuls * 10 >= pairs * 9 && odd_nuls == 0 {
+        Some(SourceEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str, bom: bool) -> Vec<u8> {
+        let mut bytes = if bom { vec![0xFF, 0xFE] } else { Vec::new() };
+        bytes.extend(text.encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+        bytes
+    }
+
+    #[test]
+    fn test_decodes_encodings_and_line_endings() {
+        let script = "var a = 1;\r\nalert(\"Größe\");\r\n";
+        for bytes in [utf16le(script, true), utf16le(script, false)] {
+            let source = ScriptSource::decode(&bytes);
+            assert_eq!(source.encoding, SourceEncoding::Utf16Le);
+            assert_eq!(source.text, "var a = 1;\nalert(\"Größe\");\n");
+            assert!(source.crlf);
+            assert_eq!(source.diagnostics("a.jsx")[0].severity(), ErrorSeverity::Info);
+        }
+
+        let bom = ScriptSource::decode(b"\xEF\xBB\xBFvar a = 1;\n");
+        assert_eq!((bom.encoding, bom.text.as_str()), (SourceEncoding::Utf8Bom, "var a = 1;\n"));
+        assert!(bom.diagnostics("a.jsx").is_empty());
+
+        let latin1 = ScriptSource::decode(b"alert(\"Gr\xF6\xDFe\");");
+        assert_eq!((latin1.encoding, latin1.text.as_str()), (SourceEncoding::Latin1, "alert(\"Größe\");"));
+
+        let shebang = ScriptSource::decode(b"#!/usr/bin/env osascript\nalert(1);\n");
+        assert_eq!(shebang.text, "///usr/bin/env osascript\nalert(1);\n");
+        assert_eq!(shebang.diagnostics("a.jsx")[0].get_message(), "Shebang line is not valid ExtendScript; it was ignored for validation");
+    }
+
+    #[test]
+    fn test_encode_restores_the_original_form() {
+        let originals: [&[u8]; 4] = [
+            b"#!/usr/bin/env osascript\r\nalert(1);\r\n",
+            b"\xEF\xBB\xBFvar a = 1;\n",
+            b"alert(\"Gr\xF6\xDFe\");",
+            &utf16le("var a = 1;\r\nalert(\"Gr\u{f6}\u{df}e\");\r\n", true),
+        ];
+        for bytes in originals {
+            let source = ScriptSource::decode(bytes);
+            assert_eq!(source.encode(&source.text), bytes);
+        }
+    }
+}
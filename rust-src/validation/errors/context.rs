@@ -1,4 +1,5 @@
-use super::types::{ErrorContext, SourceLocation, ValidatorError, ErrorSeverity};
+use super::types::{ErrorContext, Suggestion, ValidatorError, ErrorSeverity};
+use crate::api::documentation::rule_docs_url;
 
 /// Builder for creating error contexts
 #[derive(Debug, Default)]
@@ -6,8 +7,13 @@ pub struct ErrorContextBuilder {
     file: Option<String>,
     line: Option<usize>,
     column: Option<usize>,
+    length: Option<usize>,
     code_snippet: Option<String>,
-    suggestion: Option<String>,
+    suggestions: Vec<Suggestion>,
+    rule: Option<String>,
+    docs_url: Option<String>,
+    message_key: Option<String>,
+    args: Vec<(String, String)>,
 }
 
 impl ErrorContextBuilder {
@@ -30,23 +36,73 @@ impl ErrorContextBuilder {
         self
     }
 
+    /// Span length in characters; diagnostics without one underline a single character
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self
+    }
+
     pub fn code_snippet(mut self, snippet: String) -> Self {
         self.code_snippet = Some(snippet);
         self
     }
 
+    /// Adds a plain-text suggestion at full confidence
     pub fn suggestion(mut self, suggestion: Option<String>) -> Self {
-        self.suggestion = suggestion;
+        self.suggestions.extend(suggestion.map(Suggestion::new));
+        self
+    }
+
+    /// Adds an alternative fix; alternatives are ranked by confidence on build
+    pub fn alternative(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    pub fn suggestions(mut self, suggestions: Vec<Suggestion>) -> Self {
+        self.suggestions.extend(suggestions);
+        self
+    }
+
+    pub fn rule(mut self, rule: &str) -> Self {
+        self.rule = Some(rule.to_string());
+        self
+    }
+
+    /// Links the API member the diagnostic is about; without one, the
+    /// rule's guide page is linked if it has one
+    pub fn docs_url(mut self, url: Option<String>) -> Self {
+        self.docs_url = url;
+        self
+    }
+
+    /// Catalog key for rules that emit more than one kind of message
+    pub fn message_key(mut self, key: &str) -> Self {
+        self.message_key = Some(key.to_string());
+        self
+    }
+
+    /// Records a value interpolated into the message for translated catalogs
+    pub fn arg<V: ToString>(mut self, name: &str, value: V) -> Self {
+        self.args.push((name.to_string(), value.to_string()));
         self
     }
 
-    pub fn build(self) -> ErrorContext {
+    pub fn build(mut self) -> ErrorContext {
+        // Stable, so equally confident suggestions keep the order they were added in
+        self.suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
         ErrorContext {
             file: self.file,
             line: self.line,
             column: self.column,
+            length: self.length,
             code_snippet: self.code_snippet,
-            suggestion: self.suggestion,
+            suggestions: self.suggestions,
+            docs_url: self.docs_url.or_else(|| self.rule.as_deref().and_then(rule_docs_url)),
+            rule: self.rule,
+            message_key: self.message_key,
+            args: self.args,
+            related: Vec::new(),
         }
     }
 }
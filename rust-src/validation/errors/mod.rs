@@ -5,10 +5,13 @@ mod reporting;
 pub use types::{
     ErrorSeverity,
     SourceLocation,
+    Suggestion,
+    Replacement,
     ErrorContext,
     ValidatorError,
     ValidatorResult,
     ErrorCollection,
+    group_related,
 };
 
 pub use context::{
@@ -20,4 +23,6 @@ pub use reporting::{
     OutputFormat,
     ReportConfig,
     ErrorReporter,
+    TerminalRenderer,
+    suggestions_json,
 }; 
\ No newline at end of file
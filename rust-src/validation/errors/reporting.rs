@@ -1,7 +1,23 @@
 use std::fmt::Write;
-use super::types::{ErrorCollection, ValidatorError, ErrorSeverity, ErrorContext};
+use super::types::{ErrorCollection, ValidatorError, ErrorSeverity, Suggestion};
 use serde_json::json;
 
+/// Suggestions as JSON objects, most confident first
+pub fn suggestions_json(suggestions: &[Suggestion]) -> serde_json::Value {
+    suggestions.iter()
+        .map(|s| json!({
+            "message": s.message,
+            "confidence": s.confidence,
+            "replacement": s.replacement_span.as_ref().map(|r| json!({
+                "line": r.line,
+                "column": r.column,
+                "length": r.length,
+                "text": r.text
+            }))
+        }))
+        .collect()
+}
+
 /// Formats for error output
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputFormat {
@@ -101,11 +117,19 @@ impl ErrorReporter {
             }
 
             if self.config.show_suggestions {
-                if let Some(suggestion) = self.get_suggestion(error) {
-                    writeln!(output, "\nSuggestion: {}", suggestion).unwrap();
+                let suggestions = self.get_suggestions(error);
+                if let Some((best, alternatives)) = suggestions.split_first() {
+                    writeln!(output, "\nSuggestion: {}", best).unwrap();
+                    for alternative in alternatives {
+                        writeln!(output, "Alternatively: {}", alternative).unwrap();
+                    }
                 }
             }
 
+            if let Some(note) = error.related_note() {
+                writeln!(output, "Note: {}", note).unwrap();
+            }
+
             error_count += 1;
         }
 
@@ -145,7 +169,14 @@ impl ErrorReporter {
                             "column": context.column
                         },
                         "snippet": self.config.show_snippets.then(|| context.code_snippet.clone()),
-                        "suggestion": self.config.show_suggestions.then(|| context.suggestion.clone())
+                        "suggestion": self.config.show_suggestions.then(|| context.suggestion()),
+                        "suggestions": self.config.show_suggestions.then(|| suggestions_json(&context.suggestions)),
+                        "docs_url": context.docs_url,
+                        "related": context.related.iter().map(|l| json!({
+                            "file": l.file,
+                            "line": l.line,
+                            "column": l.column
+                        })).collect::<Vec<_>>()
                     })
                 }
                 // Add similar match arms for other error types...
@@ -210,12 +241,20 @@ impl ErrorReporter {
             }
 
             if self.config.show_suggestions {
-                if let Some(suggestion) = self.get_suggestion(error) {
+                for suggestion in self.get_suggestions(error) {
                     writeln!(output, "<p class=\"suggestion\">Suggestion: {}</p>", 
-                        html_escape::encode_text(&suggestion)).unwrap();
+                        html_escape::encode_text(&suggestion.message)).unwrap();
                 }
             }
 
+            if let Some(note) = error.related_note() {
+                writeln!(output, "<p class=\"note\">Note: {}</p>", html_escape::encode_text(&note)).unwrap();
+            }
+
+            if let Some(url) = &error.get_context().docs_url {
+                writeln!(output, "<p class=\"docs\"><a href=\"{}\">Documentation</a></p>", html_escape::encode_double_quoted_attribute(url)).unwrap();
+            }
+
             writeln!(output, "</div>").unwrap();
             error_count += 1;
         }
@@ -265,7 +304,7 @@ impl ErrorReporter {
         }
     }
 
-    fn get_suggestion(&self, error: &ValidatorError) -> Option<String> {
+    fn get_suggestions<'a>(&self, error: &'a ValidatorError) -> &'a [Suggestion] {
         match error {
             ValidatorError::Expression { context, .. } |
             ValidatorError::Type { context, .. } |
@@ -276,10 +315,153 @@ impl ErrorReporter {
             ValidatorError::Temporal { context, .. } |
             ValidatorError::BestPractice { context, .. } |
             ValidatorError::Script { context, .. } => {
-                context.suggestion.clone()
+                &context.suggestions
+            }
+        }
+    }
+}
+
+ /// Renders diagnostics for a terminal in the style of rustc: severity
+/// header, `file:line:column` locator, the source line with a caret
+/// underline, and help/note lines for the suggestion and related locations.
+#[derive(Debug, Clone, Copy)]
+pub struct TerminalRenderer {
+    /// Emit ANSI color escapes
+    pub color: bool,
+}
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const BLUE: &str = "\x1b[1;34m";
+
+impl TerminalRenderer {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+
+    /// Colors on when stderr is a terminal and `NO_COLOR` is unset
+    pub fn for_stderr() -> Self {
+        use std::io::IsTerminal;
+        Self::new(std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none())
+    }
+
+    /// Renders one diagnostic; `source` is the full script the positions refer to
+    pub fn render(&self, error: &ValidatorError, source: &str) -> String {
+        let context = error.get_context();
+        let severity = error.severity();
+        let (label, color) = match severity {
+            ErrorSeverity::Info => ("info", "\x1b[1;36m"),
+            ErrorSeverity::Warning => ("warning", "\x1b[1;33m"),
+            ErrorSeverity::Error => ("error", "\x1b[1;31m"),
+            ErrorSeverity::Fatal => ("fatal", "\x1b[1;35m"),
+        };
+
+        let mut output = String::new();
+        let rule = error.rule_id().map(|r| format!("[{}]", r)).unwrap_or_default();
+        writeln!(output, "{}{}", self.paint(&format!("{}{}", label, rule), color), self.paint(&format!(": {}", error.get_message()), BOLD)).unwrap();
+
+        let line_text = context.line
+            .and_then(|line| source.lines().nth(line.saturating_sub(1)))
+            .map(|text| text.replace('\t', "    "))
+            .or_else(|| context.code_snippet.clone());
+        let gutter = " ".repeat(context.line.map_or(1, |l| l.to_string().len()));
+
+        if let Some(file) = &context.file {
+            let location = match (context.line, context.column) {
+                (Some(line), Some(column)) => format!("{}:{}:{}", file, line, column),
+                (Some(line), None) => format!("{}:{}", file, line),
+                _ => file.clone(),
+            };
+            writeln!(output, "{}{} {}", gutter, self.paint("-->", BLUE), location).unwrap();
+        }
+
+        if let (Some(line), Some(text)) = (context.line, line_text) {
+            let bar = self.paint("|", BLUE);
+            writeln!(output, "{} {}", gutter, bar).unwrap();
+            writeln!(output, "{} {} {}", self.paint(&line.to_string(), BLUE), bar, text).unwrap();
+            if let Some(column) = context.column {
+                // Columns count characters of the original line, where tabs were one character
+                let original = source.lines().nth(line.saturating_sub(1)).unwrap_or("");
+                let offset: usize = original.chars().take(column.saturating_sub(1))
+                    .map(|c| if c == '\t' { 4 } else { 1 })
+                    .sum();
+                let available = text.chars().count().saturating_sub(offset).max(1);
+                let length = context.length.unwrap_or(1).clamp(1, available);
+                writeln!(output, "{} {} {}{}", gutter, bar, " ".repeat(offset), self.paint(&"^".repeat(length), color)).unwrap();
             }
         }
+
+        for suggestion in &context.suggestions {
+            writeln!(output, "{} {} {}: {}", gutter, self.paint("=", BLUE), self.paint("help", BOLD), suggestion).unwrap();
+        }
+        if let Some(note) = error.related_note() {
+            writeln!(output, "{} {} {}: {}", gutter, self.paint("=", BLUE), self.paint("note", BOLD), note).unwrap();
+        }
+        output
+    }
+
+    /// Renders every diagnostic followed by a summary line
+    pub fn render_all<'a, I>(&self, errors: I, source: &str) -> String
+    where
+        I: IntoIterator<Item = &'a ValidatorError>,
+    {
+        let mut output = String::new();
+        let (mut error_count, mut warning_count) = (0, 0);
+        for error in errors {
+            match error.severity() {
+                ErrorSeverity::Error | ErrorSeverity::Fatal => error_count += 1,
+                ErrorSeverity::Warning => warning_count += 1,
+                ErrorSeverity::Info => {}
+            }
+            output.push_str(&self.render(error, source));
+            output.push('\n');
+        }
+        if error_count + warning_count > 0 {
+            writeln!(output, "{}", self.paint(&format!("{} error(s), {} warning(s)", error_count, warning_count), BOLD)).unwrap();
+        }
+        output
+    }
+
+    fn paint(&self, text: &str, style: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
     }
 }
 
- 
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ErrorContextBuilder;
+
+    #[test]
+    fn test_terminal_render_layout() {
+        let source = "var a = 1;\n\tcount = a;\n";
+        let error = ValidatorError::Scope {
+            message: "Assignment to undeclared variable 'count' creates an implicit global".to_string(),
+            context: ErrorContextBuilder::new()
+                .file("a.jsx".to_string())
+                .line(2)
+                .column(2)
+                .length(5)
+                .suggestion(Some("Declare it with 'var count'".to_string()))
+                .rule("implicit-global")
+                .build(),
+            severity: ErrorSeverity::Warning,
+            variable: "count".to_string(),
+        };
+
+        let rendered = TerminalRenderer::new(false).render(&error, source);
+        assert_eq!(rendered, "\
+warning[implicit-global]: Assignment to undeclared variable 'count' creates an implicit global
+ --> a.jsx:2:2
+  |
+2 |     count = a;
+  |     ^^^^^
+  = help: Declare it with 'var count'
+");
+        assert!(TerminalRenderer::new(true).render(&error, source).contains("\x1b[1;33m"));
+    }
+}
@@ -22,14 +22,77 @@ pub struct SourceLocation {
     pub column: usize,
 }
 
+/// A proposed fix for a diagnostic. A diagnostic can carry several,
+/// ranked by confidence, for consumers to list, apply or choose from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub message: String,
+    /// Source text the fix rewrites, for fixes that can be applied mechanically
+    pub replacement_span: Option<Replacement>,
+    /// How likely this is the intended fix, from 0 to 1
+    pub confidence: f32,
+}
+
+/// Replaces `length` characters starting at a 1-based line and column with `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub text: String,
+}
+
+impl Suggestion {
+    /// Advice without an applicable edit, at full confidence
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into(), replacement_span: None, confidence: 1.0 }
+    }
+
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_replacement(mut self, line: usize, column: usize, length: usize, text: impl Into<String>) -> Self {
+        self.replacement_span = Some(Replacement { line, column, length, text: text.into() });
+        self
+    }
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 /// Context information for an error
 #[derive(Debug)]
 pub struct ErrorContext {
     pub file: Option<String>,
     pub line: Option<usize>,
     pub column: Option<usize>,
+    /// Number of characters the diagnostic spans, starting at `column`
+    pub length: Option<usize>,
     pub code_snippet: Option<String>,
-    pub suggestion: Option<String>,
+    /// Fixes for the diagnostic, most confident first
+    pub suggestions: Vec<Suggestion>,
+    /// Identifier of the rule that produced the diagnostic, used by profiles and config
+    pub rule: Option<String>,
+    /// Reference page for the rule or the API member the diagnostic is about
+    pub docs_url: Option<String>,
+    /// Catalog key of the message when it differs from the rule id
+    pub message_key: Option<String>,
+    /// Values interpolated into the message, used to render translations
+    pub args: Vec<(String, String)>,
+    /// Further occurrences of the same problem, filled in when diagnostics are grouped
+    pub related: Vec<SourceLocation>,
+}
+
+impl ErrorContext {
+    /// Message of the most confident suggestion, the one plain-text output shows
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestions.first().map(|s| s.message.as_str())
+    }
 }
 
 /// Main error type for the validator
@@ -225,6 +288,48 @@ impl ErrorCollection {
     pub fn errors(&self) -> impl Iterator<Item = &ValidatorError> {
         self.errors.iter()
     }
+
+    /// Collapses repeated diagnostics into one primary error per root cause
+    pub fn group_related(&mut self) {
+        self.errors = group_related(std::mem::take(&mut self.errors));
+    }
+}
+
+/// Collapses diagnostics of the same kind, rule, message and file into the
+/// first occurrence, recording the others in its `related` locations.
+///
+/// The primary keeps the highest severity of its group. Grouping an already
+/// grouped list is a no-op.
+pub fn group_related(errors: Vec<ValidatorError>) -> Vec<ValidatorError> {
+    let mut grouped: Vec<ValidatorError> = Vec::new();
+
+    for mut error in errors {
+        let primary = grouped.iter_mut().find(|g| g.group_key() == error.group_key());
+        match primary {
+            Some(primary) => {
+                let severity = error.severity();
+                if severity > primary.severity() {
+                    primary.set_severity(severity);
+                }
+                let context = error.get_context();
+                let location = SourceLocation {
+                    file: context.file.clone().unwrap_or_default(),
+                    line: context.line.unwrap_or(0),
+                    column: context.column.unwrap_or(0),
+                };
+                let related = std::mem::take(&mut error.get_context_mut().related);
+                let primary_related = &mut primary.get_context_mut().related;
+                primary_related.push(location);
+                primary_related.extend(related);
+            }
+            None => grouped.push(error),
+        }
+    }
+
+    for error in &mut grouped {
+        error.get_context_mut().related.sort_by_key(|l| (l.line, l.column));
+    }
+    grouped
 }
 
 impl ValidatorError {
@@ -242,6 +347,20 @@ impl ValidatorError {
         }
     }
 
+    pub fn get_context_mut(&mut self) -> &mut ErrorContext {
+        match self {
+            ValidatorError::Expression { context, .. } |
+            ValidatorError::Type { context, .. } |
+            ValidatorError::Scope { context, .. } |
+            ValidatorError::Property { context, .. } |
+            ValidatorError::Method { context, .. } |
+            ValidatorError::Performance { context, .. } |
+            ValidatorError::Temporal { context, .. } |
+            ValidatorError::BestPractice { context, .. } |
+            ValidatorError::Script { context, .. } => context,
+        }
+    }
+
     pub fn get_severity(&self) -> ErrorSeverity {
         match self {
             ValidatorError::Expression { severity, .. } |
@@ -260,6 +379,38 @@ impl ValidatorError {
         self.get_severity()
     }
 
+    pub fn set_severity(&mut self, new_severity: ErrorSeverity) {
+        match self {
+            ValidatorError::Expression { severity, .. } |
+            ValidatorError::Type { severity, .. } |
+            ValidatorError::Scope { severity, .. } |
+            ValidatorError::Property { severity, .. } |
+            ValidatorError::Method { severity, .. } |
+            ValidatorError::Performance { severity, .. } |
+            ValidatorError::Temporal { severity, .. } |
+            ValidatorError::BestPractice { severity, .. } |
+            ValidatorError::Script { severity, .. } => *severity = new_severity,
+        }
+    }
+
+    pub fn get_message_mut(&mut self) -> &mut String {
+        match self {
+            ValidatorError::Expression { message, .. } |
+            ValidatorError::Type { message, .. } |
+            ValidatorError::Scope { message, .. } |
+            ValidatorError::Property { message, .. } |
+            ValidatorError::Method { message, .. } |
+            ValidatorError::Performance { message, .. } |
+            ValidatorError::Temporal { message, .. } |
+            ValidatorError::BestPractice { message, .. } |
+            ValidatorError::Script { message, .. } => message,
+        }
+    }
+
+    pub fn rule_id(&self) -> Option<&str> {
+        self.get_context().rule.as_deref()
+    }
+
     pub fn get_message(&self) -> &str {
         match self {
             ValidatorError::Expression { message, .. } |
@@ -273,4 +424,79 @@ impl ValidatorError {
             ValidatorError::Script { message, .. } => message,
         }
     }
-} 
\ No newline at end of file
+
+    /// Identity used to decide whether two diagnostics share a root cause
+    fn group_key(&self) -> (std::mem::Discriminant<ValidatorError>, Option<&str>, &str, Option<&str>) {
+        (std::mem::discriminant(self), self.rule_id(), self.get_message(), self.get_context().file.as_deref())
+    }
+
+    /// "also at lines 4, 9" note for grouped diagnostics
+    pub fn related_note(&self) -> Option<String> {
+        let related = &self.get_context().related;
+        if related.is_empty() {
+            return None;
+        }
+        // Locations in other files, e.g. a redefinition in an included script, name the file
+        let file = self.get_context().file.as_deref();
+        if related.iter().any(|l| Some(l.file.as_str()) != file) {
+            let locations: Vec<String> = related.iter().map(|l| format!("{}:{}", l.file, l.line)).collect();
+            return Some(format!("also at {}", locations.join(", ")));
+        }
+        let lines: Vec<String> = related.iter().map(|l| l.line.to_string()).collect();
+        let noun = if lines.len() == 1 { "line" } else { "lines" };
+        Some(format!("also at {} {}", noun, lines.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ErrorContextBuilder;
+
+    fn unknown_variable(line: usize, severity: ErrorSeverity) -> ValidatorError {
+        ValidatorError::Scope {
+            message: "Unknown variable 'comp'".to_string(),
+            context: ErrorContextBuilder::new().file("a.jsx".to_string()).line(line).column(1).rule("undefined-variable").build(),
+            severity,
+            variable: "comp".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_group_related_collapses_repeats() {
+        let mut collection = ErrorCollection::new();
+        collection.add(unknown_variable(3, ErrorSeverity::Warning));
+        collection.add(unknown_variable(9, ErrorSeverity::Error));
+        collection.add(unknown_variable(5, ErrorSeverity::Warning));
+        collection.group_related();
+
+        let errors: Vec<&ValidatorError> = collection.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].get_context().line, Some(3));
+        assert_eq!(errors[0].severity(), ErrorSeverity::Error);
+        assert_eq!(errors[0].related_note().as_deref(), Some("also at lines 5, 9"));
+
+        let regrouped = group_related(collection.errors.drain(..).chain([unknown_variable(12, ErrorSeverity::Warning)]).collect());
+        assert_eq!(regrouped.len(), 1);
+        assert_eq!(regrouped[0].get_context().related.len(), 3);
+    }
+
+    #[test]
+    fn test_suggestions_ranked_by_confidence() {
+        let context = ErrorContextBuilder::new()
+            .alternative(Suggestion::new("Did you mean 'DISSOLVE'?").with_confidence(0.5))
+            .suggestion(Some("Check the documentation for BlendingMode".to_string()))
+            .alternative(Suggestion::new("Did you mean 'MULTIPLY'?").with_confidence(0.9).with_replacement(1, 35, 8, "MULTIPLY"))
+            .build();
+
+        let ranked: Vec<String> = context.suggestions.iter().map(|s| s.to_string()).collect();
+        assert_eq!(ranked, vec![
+            "Check the documentation for BlendingMode",
+            "Did you mean 'MULTIPLY'?",
+            "Did you mean 'DISSOLVE'?",
+        ]);
+        assert_eq!(context.suggestion(), Some("Check the documentation for BlendingMode"));
+        assert_eq!(context.suggestions[1].replacement_span.as_ref().map(|r| r.text.as_str()), Some("MULTIPLY"));
+        assert_eq!(Suggestion::new("x").with_confidence(3.0).confidence, 1.0);
+    }
+}
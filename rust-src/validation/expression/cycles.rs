@@ -0,0 +1,260 @@
+use std::collections::{HashMap, HashSet};
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::super::script::lines::LineIndex;
+
+lazy_static! {
+    static ref LAYER_VAR_RE: Regex = Regex::new(r#"\bvar\s+(\w+)\s*=\s*[\w\.]*layers?(?:\.byName)?\(\s*["']([^"']+)["']\s*\)"#).unwrap();
+    static ref EXPRESSION_ASSIGN_RE: Regex = Regex::new(r#"(?P<target>[\w\.]+(?:\(\s*["'][^"']*["']\s*\)[\w\.]*)*)\.expression\s*=\s*(?:"(?P<dq>(?:\\.|[^"\\])*)"|'(?P<sq>(?:\\.|[^'\\])*)')"#).unwrap();
+    static ref TARGET_LAYER_RE: Regex = Regex::new(r#"layers?(?:\.byName)?\(\s*["']([^"']+)["']\s*\)"#).unwrap();
+    static ref PATH_SEGMENT_RE: Regex = Regex::new(r#"\.(\w+)|\(\s*["']([^"']+)["']\s*\)"#).unwrap();
+    static ref LAYER_REF_RE: Regex = Regex::new(r#"thisComp\.layer\(\s*["']([^"']+)["']\s*\)(?P<path>(?:\.\w+|\(\s*["'][^"']+["']\s*\))*)"#).unwrap();
+    static ref SAME_LAYER_REF_RE: Regex = Regex::new(r#"(?:\bthisLayer|(?:^|[^\w\.\)]))(?P<path>\.?transform(?:\.\w+)+)"#).unwrap();
+    static ref SELF_VALUE_AT_TIME_RE: Regex = Regex::new(r"\bthisProperty\.valueAtTime\(\s*time\s*\)").unwrap();
+}
+
+/// A property that carries an expression, identified by layer name and property name
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropertyRef {
+    pub layer: String,
+    pub property: String,
+}
+
+impl std::fmt::Display for PropertyRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "layer(\"{}\").{}", self.layer, self.property)
+    }
+}
+
+/// An expression assignment found in a script
+#[derive(Debug, Clone)]
+pub struct ExpressionAssignment {
+    pub target: PropertyRef,
+    pub expression: String,
+    pub line: usize,
+    pub references: Vec<PropertyRef>,
+}
+
+/// Finds `.expression = "..."` assignments whose target layer can be resolved
+pub fn collect_expression_assignments(script: &str) -> Vec<ExpressionAssignment> {
+    let layer_vars: HashMap<String, String> = LAYER_VAR_RE.captures_iter(script)
+        .map(|cap| (cap[1].to_string(), cap[2].to_string()))
+        .collect();
+
+    let mut assignments = Vec::new();
+    let lines = LineIndex::new(script);
+    for cap in EXPRESSION_ASSIGN_RE.captures_iter(script) {
+        let target_text = &cap["target"];
+        let layer = match TARGET_LAYER_RE.captures(target_text) {
+            Some(layer_cap) => layer_cap[1].to_string(),
+            None => {
+                let root = target_text.split('.').next().unwrap_or("");
+                match layer_vars.get(root) {
+                    Some(name) => name.clone(),
+                    None => continue,
+                }
+            }
+        };
+        let property = match last_property(target_text) {
+            Some(property) => property,
+            None => continue,
+        };
+
+        let expression = assigned_expression(&cap).to_string();
+        let pos = cap.get(0).unwrap().start();
+        let references = expression_references(&expression, &layer);
+        assignments.push(ExpressionAssignment {
+            target: PropertyRef { layer, property },
+            expression,
+            line: lines.line(pos),
+            references,
+        });
+    }
+
+    assignments
+}
+
+fn assigned_expression<'t>(cap: &regex::Captures<'t>) -> &'t str {
+    cap.name("dq").or_else(|| cap.name("sq")).map_or("", |m| m.as_str())
+}
+
+/// Properties read by an expression applied to a property on `own_layer`
+pub fn expression_references(expr: &str, own_layer: &str) -> Vec<PropertyRef> {
+    let mut references = Vec::new();
+
+    for cap in LAYER_REF_RE.captures_iter(expr) {
+        if let Some(property) = last_property(&cap["path"]) {
+            references.push(PropertyRef { layer: cap[1].to_string(), property });
+        }
+    }
+    for cap in SAME_LAYER_REF_RE.captures_iter(expr) {
+        if let Some(property) = last_property(&cap["path"]) {
+            references.push(PropertyRef { layer: own_layer.to_string(), property });
+        }
+    }
+
+    references
+}
+
+/// Normalized name of the last property in an access path such as
+/// `.transform.position` or `.property("Position")`
+fn last_property(path: &str) -> Option<String> {
+    PATH_SEGMENT_RE.captures_iter(path)
+        .filter_map(|cap| cap.get(1).or_else(|| cap.get(2)).map(|m| m.as_str().to_string()))
+        .filter(|segment| !matches!(segment.as_str(), "transform" | "value" | "property" | "layer" | "layers" | "byName"))
+        .last()
+        .map(|segment| normalize_property(&segment))
+}
+
+fn normalize_property(name: &str) -> String {
+    let name = name.strip_prefix("ADBE ").unwrap_or(name);
+    name.chars().filter(|c| !c.is_whitespace() && *c != '_').collect::<String>().to_lowercase()
+}
+
+/// Flags expressions that read their own current value and circular
+/// references between expressions assigned in the same script
+pub fn validate_expression_references(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let mut errors = Vec::new();
+    let assignments = collect_expression_assignments(script);
+    let lines = LineIndex::new(script);
+
+    for cap in EXPRESSION_ASSIGN_RE.captures_iter(script) {
+        let expr = assigned_expression(&cap);
+        if SELF_VALUE_AT_TIME_RE.is_match(expr) {
+            let pos = cap.get(0).unwrap().start();
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(lines.line(pos))
+                .column(1)
+                .code_snippet(expr.to_string())
+                .suggestion(Some("Offset the time (e.g. thisProperty.valueAtTime(time - thisComp.frameDuration)) or use 'value'".to_string()))
+                .rule("expression-self-reference")
+                .build();
+            errors.push(ValidatorError::Expression {
+                message: "Expression reads its own property at the current time".to_string(),
+                context,
+                severity: ErrorSeverity::Warning,
+            });
+        }
+    }
+
+    for assignment in &assignments {
+        if assignment.references.contains(&assignment.target) {
+            errors.push(cycle_error(file_path, assignment, std::slice::from_ref(&assignment.target)));
+        }
+    }
+
+    let graph: HashMap<&PropertyRef, &ExpressionAssignment> = assignments.iter()
+        .map(|a| (&a.target, a))
+        .collect();
+    let mut reported: HashSet<Vec<PropertyRef>> = HashSet::new();
+    for assignment in &assignments {
+        let mut path = vec![assignment.target.clone()];
+        if let Some(cycle) = find_cycle(&graph, &assignment.target, &mut path) {
+            if cycle.len() < 2 {
+                continue;
+            }
+            let mut key = cycle.clone();
+            key.sort_by(|a, b| (&a.layer, &a.property).cmp(&(&b.layer, &b.property)));
+            if reported.insert(key) {
+                errors.push(cycle_error(file_path, graph[&cycle[0]], &cycle));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Depth-first search for a path leading back to `start`
+fn find_cycle(
+    graph: &HashMap<&PropertyRef, &ExpressionAssignment>,
+    start: &PropertyRef,
+    path: &mut Vec<PropertyRef>,
+) -> Option<Vec<PropertyRef>> {
+    let current = path.last()?.clone();
+    let node = graph.get(&current)?;
+    for reference in &node.references {
+        if reference == start {
+            return Some(path.clone());
+        }
+        if path.contains(reference) || !graph.contains_key(reference) {
+            continue;
+        }
+        path.push(reference.clone());
+        if let Some(cycle) = find_cycle(graph, start, path) {
+            return Some(cycle);
+        }
+        path.pop();
+    }
+    None
+}
+
+fn cycle_error(file_path: &str, assignment: &ExpressionAssignment, cycle: &[PropertyRef]) -> ValidatorError {
+    let chain: Vec<String> = cycle.iter().chain(cycle.first()).map(|p| p.to_string()).collect();
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(assignment.line)
+        .column(1)
+        .code_snippet(assignment.expression.clone())
+        .suggestion(Some("Break the cycle by reading a non-expression property or using valueAtTime with a time offset".to_string()))
+        .rule("expression-cycle")
+        .build();
+
+    ValidatorError::Expression {
+        message: format!("Circular expression reference: {}", chain.join(" -> ")),
+        context,
+        severity: ErrorSeverity::Warning,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_value_at_time() {
+        let script = r#"comp.layer("A").transform.opacity.expression = "thisProperty.valueAtTime(time)";"#;
+        let errors = validate_expression_references(script, "test.jsx");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule_id(), Some("expression-self-reference"));
+
+        let second_line = "var a = 1;\ncomp.layer(\"A\").transform.opacity.expression = \"thisProperty.valueAtTime(time)\";";
+        assert_eq!(validate_expression_references(second_line, "test.jsx")[0].get_context().line, Some(2));
+
+        let offset = r#"comp.layer("A").transform.opacity.expression = "thisProperty.valueAtTime(time - 1)";"#;
+        assert!(validate_expression_references(offset, "test.jsx").is_empty());
+    }
+
+    #[test]
+    fn test_cycle_between_layers() {
+        let script = r#"
+            var a = comp.layer("A");
+            var b = comp.layer("B");
+            a.transform.position.expression = "thisComp.layer('B').transform.position";
+            b.transform.position.expression = "thisComp.layer('A').transform.position + [10, 0]";
+        "#;
+        let errors = validate_expression_references(script, "test.jsx");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule_id(), Some("expression-cycle"));
+
+        let unindented = "var a = comp.layer(\"A\");\nvar b = comp.layer(\"B\");\na.transform.position.expression = \"thisComp.layer('B').transform.position\";\n";
+        let lines: Vec<usize> = collect_expression_assignments(unindented).iter().map(|a| a.line).collect();
+        assert_eq!(lines, vec![3]);
+    }
+
+    #[test]
+    fn test_acyclic_references() {
+        let script = r#"
+            comp.layer("A").property("Position").expression = "thisComp.layer('B').transform.position";
+            comp.layer("C").transform.rotation.expression = "thisComp.layer('A').transform.position[0]";
+        "#;
+        assert!(validate_expression_references(script, "test.jsx").is_empty());
+    }
+
+    #[test]
+    fn test_property_normalization() {
+        let refs = expression_references("thisComp.layer(\"Null\").property(\"ADBE Position\")", "Own");
+        assert_eq!(refs, vec![PropertyRef { layer: "Null".to_string(), property: "position".to_string() }]);
+    }
+}
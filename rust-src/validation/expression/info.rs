@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+use regex::Regex;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use super::super::performance::PerformanceMetrics;
+
+lazy_static! {
+    static ref COMP_RE: Regex = Regex::new(r#"\bcomp\(\s*["']([^"']+)["']\s*\)"#).unwrap();
+    static ref LAYER_RE: Regex = Regex::new(r#"\blayer\(\s*(?:["']([^"']+)["']|(\d+))\s*\)"#).unwrap();
+    static ref EFFECT_RE: Regex = Regex::new(r#"\beffect\(\s*(?:["']([^"']+)["']|(\d+))\s*\)"#).unwrap();
+    static ref NAMED_PROPERTY_RE: Regex = Regex::new(r"\.(anchorPoint|position|scale|rotation|xRotation|yRotation|zRotation|orientation|opacity|sourceText|marker|pointOfInterest|zoom)\b").unwrap();
+    /// `.property("Name")` and effect parameters such as `effect("Amount")("Slider")`
+    static ref LOOKUP_PROPERTY_RE: Regex = Regex::new(r#"(?:\.property|\))\(\s*["']([^"']+)["']\s*\)"#).unwrap();
+    static ref CALL_RE: Regex = Regex::new(r"\b((?:Math\.)?[A-Za-z_$][\w$]*)\s*\(").unwrap();
+    static ref FUNCTION_DECL_RE: Regex = Regex::new(r"\bfunction\s+([A-Za-z_$][\w$]*)").unwrap();
+}
+
+/// Calls that select what an expression reads rather than compute anything
+const LOOKUP_CALLS: &[&str] = &["comp", "layer", "effect", "property", "footage", "mask"];
+
+const KEYWORDS: &[&str] = &["if", "for", "while", "switch", "function", "return", "catch", "typeof", "with"];
+
+/// Everything an expression reads, for auditing it before it is injected
+/// into a template
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ExpressionInfo {
+    /// Comps looked up with `comp("Name")`
+    pub referenced_comps: Vec<String>,
+    /// Layers by name, or `#n` by index
+    pub layers: Vec<String>,
+    /// Effects by name, or `#n` by index
+    pub effects: Vec<String>,
+    /// Properties read, by attribute name or the name passed to `property()`
+    pub properties: Vec<String>,
+    /// Functions and methods called, e.g. `wiggle`, `Math.sin`, `valueAtTime`
+    pub functions_used: Vec<String>,
+    /// Relative evaluation cost, see [`PerformanceMetrics::estimated_cost`]
+    pub estimated_cost: u32,
+}
+
+/// Statically extracts what an expression references. Every list is in
+/// order of first use, without duplicates.
+pub fn analyze_expression(expr: &str) -> ExpressionInfo {
+    let mut properties: Vec<(usize, String)> = NAMED_PROPERTY_RE.captures_iter(expr)
+        .map(|cap| (cap.get(0).unwrap().start(), cap[1].to_string()))
+        .chain(LOOKUP_PROPERTY_RE.captures_iter(expr).map(|cap| (cap.get(0).unwrap().start(), cap[1].to_string())))
+        .collect();
+    properties.sort_by_key(|(pos, _)| *pos);
+
+    let declared: HashSet<&str> = FUNCTION_DECL_RE.captures_iter(expr).map(|cap| cap.get(1).unwrap().as_str()).collect();
+    let functions = CALL_RE.captures_iter(expr)
+        .map(|cap| cap[1].to_string())
+        .filter(|name| !LOOKUP_CALLS.contains(&name.as_str()) && !KEYWORDS.contains(&name.as_str()))
+        .filter(|name| !declared.contains(name.as_str()));
+
+    ExpressionInfo {
+        referenced_comps: unique(COMP_RE.captures_iter(expr).map(|cap| cap[1].to_string())),
+        layers: unique(named_or_indexed(&LAYER_RE, expr)),
+        effects: unique(named_or_indexed(&EFFECT_RE, expr)),
+        properties: unique(properties.into_iter().map(|(_, name)| name)),
+        functions_used: unique(functions),
+        estimated_cost: PerformanceMetrics::analyze(expr).estimated_cost(),
+    }
+}
+
+fn named_or_indexed<'a>(re: &'a Regex, expr: &'a str) -> impl Iterator<Item = String> + 'a {
+    re.captures_iter(expr).filter_map(|cap| match (cap.get(1), cap.get(2)) {
+        (Some(name), _) => Some(name.as_str().to_string()),
+        (None, Some(index)) => Some(format!("#{}", index.as_str())),
+        (None, None) => None,
+    })
+}
+
+fn unique(items: impl Iterator<Item = String>) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    for item in items {
+        if !found.contains(&item) {
+            found.push(item);
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_dependencies() {
+        let info = analyze_expression(r#"var src = comp("Assets").layer("Logo");
+var amount = thisComp.layer("Controls").effect("Amount")("Slider");
+function jitter(t) { return wiggle(2, amount, 1, 0.5, t); }
+src.transform.position.valueAtTime(time - 0.1) + jitter(time) * Math.sin(time);"#);
+
+        assert_eq!(info.referenced_comps, vec!["Assets"]);
+        assert_eq!(info.layers, vec!["Logo", "Controls"]);
+        assert_eq!(info.effects, vec!["Amount"]);
+        assert_eq!(info.properties, vec!["Slider", "position"]);
+        assert_eq!(info.functions_used, vec!["wiggle", "valueAtTime", "Math.sin"]);
+        assert!(info.estimated_cost > 0);
+    }
+}
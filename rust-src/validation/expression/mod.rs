@@ -1,10 +1,19 @@
+// Parse and type errors carry the full diagnostic context
+#![allow(clippy::result_large_err)]
+
 pub mod parser;
 pub mod scope;
 pub mod validation;
 pub mod typechecker;
+pub mod cycles;
+pub mod snippet;
+pub mod info;
 
 use super::errors::ValidatorError;
 pub use validation::{ExpressionValidationResult, validate_expression_syntax};
+pub use cycles::validate_expression_references;
+pub use snippet::ExpressionReport;
+pub use info::{analyze_expression, ExpressionInfo};
 
 /// Validates an After Effects expression
 pub fn validate_expression(source: &str) -> Result<(), ValidatorError> {
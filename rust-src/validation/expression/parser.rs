@@ -1,7 +1,9 @@
-use super::super::errors::{ValidatorError, ErrorContext, ErrorContextBuilder, ErrorSeverity};
-use serde_json::Value;
+use super::super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
 use std::collections::HashMap;
 
+/// Deepest nesting of parentheses, brackets and unary chains the parser will follow
+pub const MAX_NESTING_DEPTH: usize = 128;
+
 /// Represents a token in an After Effects expression
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
@@ -64,6 +66,7 @@ pub struct ExpressionParser {
     /// The source code being parsed
     source: String,
     /// Current position in the source code
+    #[allow(dead_code)]
     pos: usize,
     /// Current line number
     line: usize,
@@ -73,6 +76,8 @@ pub struct ExpressionParser {
     tokens: Vec<Token>,
     /// Current index in the tokens
     current: usize,
+    /// Current recursion depth of `parse_expression`
+    depth: usize,
 }
 
 impl ExpressionParser {
@@ -84,6 +89,7 @@ impl ExpressionParser {
             column: 1,
             tokens: Vec::new(),
             current: 0,
+            depth: 0,
         }
     }
 
@@ -129,7 +135,7 @@ impl ExpressionParser {
                 }
                 '"' | '\'' => {
                     let quote = c;
-                    while let Some(ch) = chars.next() {
+                    for ch in chars.by_ref() {
                         if ch == quote {
                             break;
                         }
@@ -210,7 +216,27 @@ impl ExpressionParser {
 
     /// Parse an expression
     fn parse_expression(&mut self) -> Result<(), ValidatorError> {
-        match &self.tokens[self.current] {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(self.error(
+                format!("Expression is nested more than {} levels deep", MAX_NESTING_DEPTH),
+                "Split the expression into intermediate variables",
+            ));
+        }
+        self.depth += 1;
+        let result = self.parse_expression_inner();
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_expression_inner(&mut self) -> Result<(), ValidatorError> {
+        let token = match self.tokens.get(self.current) {
+            Some(token) => token,
+            None => return Err(self.error(
+                "Unexpected end of expression".to_string(),
+                "Complete the expression",
+            )),
+        };
+        match token {
             Token::Identifier(_) => {
                 self.current += 1;
                 if self.current < self.tokens.len() {
@@ -265,7 +291,7 @@ impl ExpressionParser {
         self.expect_token(Token::LParen)?;
         while self.current < self.tokens.len() && !matches!(self.tokens[self.current], Token::RParen) {
             self.parse_expression()?;
-            if matches!(self.tokens[self.current], Token::Comma) {
+            if matches!(self.tokens.get(self.current), Some(Token::Comma)) {
                 self.current += 1;
             }
         }
@@ -284,7 +310,7 @@ impl ExpressionParser {
     fn parse_array(&mut self) -> Result<(), ValidatorError> {
         while self.current < self.tokens.len() && !matches!(self.tokens[self.current], Token::RBracket) {
             self.parse_expression()?;
-            if matches!(self.tokens[self.current], Token::Comma) {
+            if matches!(self.tokens.get(self.current), Some(Token::Comma)) {
                 self.current += 1;
             }
         }
@@ -298,7 +324,7 @@ impl ExpressionParser {
             self.expect_token_type(TokenType::Identifier)?;
             self.expect_token(Token::Colon)?;
             self.parse_expression()?;
-            if matches!(self.tokens[self.current], Token::Comma) {
+            if matches!(self.tokens.get(self.current), Some(Token::Comma)) {
                 self.current += 1;
             }
         }
@@ -306,6 +332,22 @@ impl ExpressionParser {
         Ok(())
     }
 
+    fn error(&self, message: String, suggestion: &str) -> ValidatorError {
+        let context = ErrorContextBuilder::new()
+            .file("expression".to_string())
+            .line(self.line)
+            .column(self.column)
+            .code_snippet(self.source.clone())
+            .suggestion(Some(suggestion.to_string()))
+            .build();
+
+        ValidatorError::Expression {
+            message,
+            context,
+            severity: ErrorSeverity::Error,
+        }
+    }
+
     /// Expect a token
     fn expect_token(&mut self, expected: Token) -> Result<(), ValidatorError> {
         if self.current >= self.tokens.len() {
@@ -447,4 +489,18 @@ mod tests {
             Token::RParen,
         ]);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_malformed_input_is_an_error() {
+        for source in ["a +", "f(a", "[1, 2", "{a: 1", "(((", "-"] {
+            assert!(parse_expression(source).is_err(), "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_deep_nesting_is_bounded() {
+        let source = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+        let error = parse_expression(&source).unwrap_err();
+        assert!(error.get_message().contains("nested"));
+    }
+}
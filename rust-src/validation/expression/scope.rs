@@ -71,6 +71,12 @@ pub struct ScopeValidator {
     source: String,
 }
 
+impl Default for ScopeValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ScopeValidator {
     pub fn new() -> Self {
         let global_scope = Scope::new(ScopeType::Global);
@@ -121,7 +127,7 @@ impl ScopeValidator {
             }
             
             // Check if variable exists in scope
-            if !self.current_scope.lookup_variable(var_name).is_some() {
+            if self.current_scope.lookup_variable(var_name).is_none() {
                 let context = ErrorContextBuilder::new()
                     .code_snippet(self.source.clone())
                     .suggestion(Some(format!("Declare variable '{}' before use", var_name)))
@@ -174,10 +180,12 @@ pub fn validate_scope(source: &str) -> Result<(), ValidatorError> {
 }
 
 /// Context for scope validation
+#[allow(dead_code)]
 struct ValidationContext<'a> {
     expr: &'a Expression,
 }
 
+#[allow(dead_code)]
 impl<'a> ValidationContext<'a> {
     fn new(expr: &'a Expression) -> Self {
         Self { expr }
@@ -0,0 +1,117 @@
+use serde::Serialize;
+use super::info::analyze_expression;
+use super::parser::parse_expression;
+use super::scope::validate_scope;
+use super::typechecker::{Type, TypeChecker};
+use super::validation::validate_expression_syntax;
+
+/// Analysis of a standalone expression, as kept in snippet libraries
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpressionReport {
+    /// Type of the final statement, which becomes the property value
+    pub result_type: Option<String>,
+    /// Layers referenced by name or index, in order of first use
+    pub layers: Vec<String>,
+    /// Effects referenced by name or index, in order of first use
+    pub effects: Vec<String>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ExpressionReport {
+    pub fn analyze(source: &str) -> Self {
+        let syntax = validate_expression_syntax(source);
+        let mut errors: Vec<String> = syntax.syntax_errors.iter()
+            .chain(syntax.type_errors.iter())
+            .cloned()
+            .collect();
+        if let Err(e) = validate_scope(source) {
+            errors.push(e.get_message().to_string());
+        }
+        let warnings = syntax.safety_warnings.iter()
+            .chain(syntax.performance_warnings.iter())
+            .cloned()
+            .collect();
+
+        let info = analyze_expression(source);
+        Self {
+            result_type: if errors.is_empty() { result_type(source) } else { None },
+            layers: info.layers,
+            effects: info.effects,
+            errors,
+            warnings,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut output = format!("result type: {}\n", self.result_type.as_deref().unwrap_or("unknown"));
+        if !self.layers.is_empty() {
+            output.push_str(&format!("layers: {}\n", self.layers.join(", ")));
+        }
+        if !self.effects.is_empty() {
+            output.push_str(&format!("effects: {}\n", self.effects.join(", ")));
+        }
+        for error in &self.errors {
+            output.push_str(&format!("error: {}\n", error));
+        }
+        for warning in &self.warnings {
+            output.push_str(&format!("warning: {}\n", warning));
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Type of the last statement, or `None` if it cannot be inferred
+fn result_type(source: &str) -> Option<String> {
+    let last = source.split([';', '\n'])
+        .map(str::trim)
+        .rfind(|s| !s.is_empty() && !s.starts_with("//"))?;
+    let expression = parse_expression(last).ok()?;
+    match TypeChecker::new().check(&expression).ok()? {
+        Type::Any => None,
+        known => Some(describe(&known)),
+    }
+}
+
+fn describe(value_type: &Type) -> String {
+    match value_type {
+        Type::Number => "number".to_string(),
+        Type::String => "string".to_string(),
+        Type::Boolean => "boolean".to_string(),
+        Type::Array(element) => format!("array of {}", describe(element)),
+        Type::Object(_) => "object".to_string(),
+        Type::Property(name) => format!("property {}", name),
+        Type::Method { .. } => "function".to_string(),
+        Type::Color => "color".to_string(),
+        Type::Vector(size) => format!("{}D vector", size),
+        Type::Temporal(inner) => describe(inner),
+        Type::Controller { value_type, .. } => describe(value_type),
+        Type::Any => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_references() {
+        let report = ExpressionReport::analyze(
+            "var ctrl = thisComp.layer(\"Controls\").effect(\"Amount\")(\"Slider\");\nthisComp.layer(1).transform.opacity * ctrl / 100;"
+        );
+        assert_eq!(report.layers, vec!["Controls", "#1"]);
+        assert_eq!(report.effects, vec!["Amount"]);
+
+        let broken = ExpressionReport::analyze("wiggle(2, 30");
+        assert!(!broken.is_valid());
+        assert_eq!(broken.result_type, None);
+    }
+}
@@ -43,6 +43,12 @@ pub struct TypeChecker {
     env: HashMap<String, Type>,
 }
 
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TypeChecker {
     pub fn new() -> Self {
         let mut env = HashMap::new();
@@ -337,16 +343,19 @@ impl TypeChecker {
     }
 
     /// Check if a type is temporal
+    #[allow(dead_code)]
     fn is_temporal(&self, t: &Type) -> bool {
         matches!(t, Type::Temporal(_))
     }
 
     /// Check if a type is a controller
+    #[allow(dead_code)]
     fn is_controller(&self, t: &Type) -> bool {
         matches!(t, Type::Controller { .. })
     }
 
     /// Get the dimension of a vector type
+    #[allow(dead_code)]
     fn vector_dimension(&self, t: &Type) -> Option<usize> {
         match t {
             Type::Vector(dim) => Some(*dim),
@@ -2,7 +2,6 @@ use regex::Regex;
 use lazy_static::lazy_static;
 use super::super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
 use super::super::performance::PerformanceMetrics;
-use super::typechecker::{Type, TypeChecker};
 
 lazy_static! {
     static ref LAYER_REF_RE: Regex = Regex::new(r"thisLayer|thisComp\.layer\(\s*['\x22]?[\w\s]+['\x22]?\s*\)").unwrap();
@@ -24,6 +23,12 @@ pub struct ExpressionValidationResult {
     pub type_errors: Vec<String>,
 }
 
+impl Default for ExpressionValidationResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ExpressionValidationResult {
     pub fn new() -> Self {
         Self {
@@ -249,11 +254,10 @@ fn validate_color_operations(expr: &str) -> Result<(), String> {
     // Check for color function usage
     if COLOR_FUNC_RE.is_match(expr) {
         // Validate color parameters
-        if expr.contains("rgbToHsl") || expr.contains("hslToRgb") {
-            if !expr.contains("[") || !expr.contains("]") {
+        if (expr.contains("rgbToHsl") || expr.contains("hslToRgb"))
+            && (!expr.contains("[") || !expr.contains("]")) {
                 return Err("Color functions expect array parameters".to_string());
             }
-        }
     }
 
     // Check for color property access
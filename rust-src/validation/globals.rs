@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::rules::MethodValidation;
+use super::script::lexer::{matching_bracket, Token};
+use super::script::scope::{analyze_scopes, EXTENDSCRIPT_GLOBALS};
+use super::shims::include_paths;
+use crate::api::UnifiedApi;
+
+/// Engine constructors that scripts may call without `new`, beyond the
+/// classes of the API model
+const CALLABLE_CLASSES: &[&str] = &[
+    "UnitValue", "BridgeTalk", "Namespace", "QName", "XMLList",
+    "TypeError", "RangeError", "ReferenceError", "SyntaxError", "EvalError", "URIError",
+];
+
+/// Checks calls without a receiver, such as `alert("Done")`. Calls to
+/// global functions are checked against their signatures; calls to names
+/// that are neither global nor declared by the script, such as `alret(...)`
+/// or `setTimeout(...)`, are undefined at runtime. Scripts that pull in
+/// other files with `#include` or `$.evalFile` may call functions defined
+/// there, so unknown names are only reported for self-contained scripts.
+pub fn validate_global_calls(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let api = UnifiedApi::shared();
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let self_contained = include_paths(script).is_empty() && !script.contains("evalFile");
+    let declared: HashSet<&str> = analysis.scopes.iter().flat_map(|s| s.declared.iter().map(String::as_str)).collect();
+    let implicit: HashSet<String> = analysis.implicit_globals().into_iter().map(|g| g.name).collect();
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_identifier() || !tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+            continue;
+        }
+        if i > 0 {
+            let prev = &tokens[i - 1];
+            if prev.is_punct(".") || prev.is_punct("?.") || prev.is_keyword("function") || prev.is_keyword("new") {
+                continue;
+            }
+        }
+        if analysis.is_declared(&token.text, analysis.token_scopes[i]) {
+            continue;
+        }
+        let close = match matching_bracket(tokens, i + 1) {
+            Some(close) => close,
+            None => continue,
+        };
+
+        if let Some(function) = api.global_function(&token.text) {
+            let count = argument_count(tokens, i + 1, close);
+            if let Some(error) = check_arity(script, file_path, token, function, count) {
+                errors.push(error);
+            }
+            continue;
+        }
+
+        // `name() { ... }`, a method in an object literal
+        let is_definition = tokens.get(close + 1).is_some_and(|t| t.is_punct("{"));
+        let known = EXTENDSCRIPT_GLOBALS.contains(&token.text.as_str())
+            || CALLABLE_CLASSES.contains(&token.text.as_str())
+            || api.object(&token.text).is_some()
+            || declared.contains(token.text.as_str())
+            || implicit.contains(&token.text);
+        if self_contained && !known && !is_definition {
+            let candidates = api.global_function_names().chain(declared.iter().copied());
+            errors.push(unknown_function(script, file_path, token, closest(&token.text, candidates)));
+        }
+    }
+
+    errors
+}
+
+/// Number of arguments between the parentheses at `open` and `close`
+fn argument_count(tokens: &[Token], open: usize, close: usize) -> usize {
+    if close == open + 1 {
+        return 0;
+    }
+    let mut depth = 0usize;
+    let mut count = 1;
+    for token in &tokens[open + 1..close] {
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            depth += 1;
+        } else if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0 && token.is_punct(",") {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|c| (strsim::levenshtein(&name.to_lowercase(), &c.to_lowercase()), c))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, c)| (*distance, *c))
+        .map(|(_, c)| c)
+}
+
+fn check_arity(script: &str, file_path: &str, token: &Token, function: &MethodValidation, count: usize) -> Option<ValidatorError> {
+    if !function.has_signature() || function.accepts_arg_count(count) {
+        return None;
+    }
+    let max = function.param_count + function.optional_params.len();
+    let expected = if max == function.param_count {
+        format!("{}", max)
+    } else {
+        format!("{} to {}", function.param_count, max)
+    };
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .rule("global-function-arity")
+        .arg("name", &token.text)
+        .build();
+    Some(ValidatorError::Method {
+        message: format!("{}() expects {} argument(s), got {}", token.text, expected, count),
+        context,
+        severity: ErrorSeverity::Error,
+    })
+}
+
+fn unknown_function(script: &str, file_path: &str, token: &Token, suggestion: Option<&str>) -> ValidatorError {
+    let suggestion = match suggestion {
+        Some(name) => format!("Did you mean '{}'?", name),
+        None => format!("Define '{}' in this script or #include the file that does", token.text),
+    };
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule("unknown-function")
+        .arg("name", &token.text)
+        .build();
+    ValidatorError::Method {
+        message: format!("'{}' is not a global function or a function this script defines", token.text),
+        context,
+        severity: ErrorSeverity::Warning,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_calls() {
+        let script = r#"function pad(n) { return n < 10 ? "0" + n : "" + n; }
+var log = function (m) { $.writeln(m); };
+alert("Done", "Render");
+alret("Done");
+var t = timeToCurrentFormat(comp.time);
+writeLn(pad(3));
+log(parseInt("12", 10));
+setTimeout(log, 100);
+if (isValid(layer) && confirm("Delete?")) { layer.remove(); }
+var f = new File("a.txt");
+function build(callback) { callback(); bulid(); }
+"#;
+        let errors = validate_global_calls(script, "g.jsx");
+        let found: Vec<(usize, Option<&str>, &str)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.rule_id(), e.get_message()))
+            .collect();
+        assert_eq!(found, vec![
+            (4, Some("unknown-function"), "'alret' is not a global function or a function this script defines"),
+            (5, Some("global-function-arity"), "timeToCurrentFormat() expects 2 to 3 argument(s), got 1"),
+            (8, Some("unknown-function"), "'setTimeout' is not a global function or a function this script defines"),
+            (11, Some("unknown-function"), "'bulid' is not a global function or a function this script defines"),
+        ]);
+        assert_eq!(errors[0].get_context().suggestion(), Some("Did you mean 'alert'?"));
+        assert_eq!(errors[3].get_context().suggestion(), Some("Did you mean 'build'?"));
+
+        let included = "#include \"lib.jsx\"\nhelper();\nalert();\n";
+        let included_errors = validate_global_calls(included, "g.jsx");
+        let rules: Vec<Option<&str>> = included_errors.iter().map(|e| e.rule_id()).collect();
+        assert_eq!(rules, vec![Some("global-function-arity")]);
+    }
+}
@@ -0,0 +1,397 @@
+use serde::Serialize;
+use super::script::lexer::{matching_bracket, significant_tokens, Token};
+use super::script::inference::branch_end;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::config::Environment;
+use super::panel::validate_panel_script;
+
+/// Global functions that open a modal dialog
+const DIALOG_FUNCTIONS: &[&str] = &["alert", "confirm", "prompt"];
+
+/// Methods that open a file or folder picker
+const DIALOG_METHODS: &[&str] = &[
+    "saveWithDialog", "importFileWithDialog", "openDialog", "saveDialog", "selectDialog",
+];
+
+/// A call that can display UI and therefore blocks a headless session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DialogCall {
+    /// Called function or method, or `Window` for ScriptUI windows
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+    /// Enclosing functions, branches, loops and handlers, outermost first
+    pub conditions: Vec<String>,
+}
+
+/// Finds every call that may display a dialog or ScriptUI window, together
+/// with the code path leading to it
+pub fn find_dialog_calls(script: &str) -> Vec<DialogCall> {
+    let tokens = significant_tokens(script);
+    let mut calls = Vec::new();
+    // Header of each open block (None for plain blocks and object literals)
+    let mut blocks: Vec<Option<String>> = Vec::new();
+    // Header waiting for its body, and the token index where the header ends
+    let mut pending: Option<String> = None;
+    let mut header: Option<(usize, String)> = None;
+    let mut last_closed: Option<String> = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if header.as_ref().is_some_and(|(end, _)| *end == i) {
+            pending = header.take().map(|(_, text)| text);
+            last_closed = None;
+            continue;
+        }
+
+        if token.is_keyword("if") || token.is_keyword("for") || token.is_keyword("while")
+            || token.is_keyword("switch") || token.is_keyword("catch") || token.is_keyword("with") {
+            if let Some(end) = matching_bracket(&tokens, i + 1) {
+                let mut text = script[token.start..tokens[end].end].to_string();
+                if i > 0 && tokens[i - 1].is_keyword("else") {
+                    text = format!("else {}", text);
+                }
+                header = Some((end, collapse_whitespace(&text)));
+            }
+        } else if token.is_keyword("else") {
+            if !tokens.get(i + 1).is_some_and(|t| t.is_keyword("if")) {
+                pending = Some(match last_closed.take() {
+                    Some(branch) => format!("else of {}", branch),
+                    None => "else".to_string(),
+                });
+            }
+        } else if token.is_keyword("try") || token.is_keyword("finally") || token.is_keyword("do") {
+            pending = Some(token.text.clone());
+        } else if token.is_keyword("function") {
+            pending = Some(match tokens.get(i + 1).filter(|t| t.is_identifier()) {
+                Some(name) => format!("function {}", name.text),
+                None => function_label(&tokens, i),
+            });
+        } else if token.is_punct("{") {
+            blocks.push(pending.take());
+        } else if token.is_punct("}") {
+            last_closed = blocks.pop().flatten().filter(|h| h.starts_with("if") || h.starts_with("else if"));
+            continue;
+        } else if token.is_punct(";") {
+            pending = None;
+        }
+
+        if is_dialog_call(&tokens, i) {
+            let mut conditions: Vec<String> = blocks.iter().flatten().cloned().collect();
+            conditions.extend(pending.iter().cloned());
+            calls.push(DialogCall {
+                name: token.text.clone(),
+                line: token.line,
+                column: token.column,
+                conditions,
+            });
+        }
+        last_closed = None;
+    }
+
+    calls
+}
+
+fn is_dialog_call(tokens: &[Token], i: usize) -> bool {
+    let token = &tokens[i];
+    if !token.is_identifier() || !tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+        return false;
+    }
+    let prev = i.checked_sub(1).map(|p| &tokens[p]);
+    let is_member = prev.is_some_and(|p| p.is_punct("."));
+    if DIALOG_METHODS.contains(&token.text.as_str()) {
+        is_member
+    } else if DIALOG_FUNCTIONS.contains(&token.text.as_str()) {
+        // Bare calls and `Window.alert(...)`; `obj.confirm(...)` is someone else's method
+        !is_member || (i >= 2 && tokens[i - 2].text == "Window")
+    } else {
+        token.text == "Window" && prev.is_some_and(|p| p.is_keyword("new"))
+    }
+}
+
+/// Names anonymous functions after what they are assigned to, e.g. `button.onClick`
+fn function_label(tokens: &[Token], i: usize) -> String {
+    if i >= 2 && (tokens[i - 1].is_punct("=") || tokens[i - 1].is_punct(":")) {
+        let mut start = i - 2;
+        while start >= 2 && tokens[start - 1].is_punct(".") && tokens[start - 2].is_identifier() {
+            start -= 2;
+        }
+        let target: String = tokens[start..i - 1].iter().map(|t| t.text.as_str()).collect();
+        return format!("function {}", target);
+    }
+    "anonymous function".to_string()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Audit of every code path in a script that could open a modal dialog
+#[derive(Debug, Clone, Serialize)]
+pub struct HeadlessReport {
+    pub file: String,
+    pub dialog_calls: Vec<DialogCall>,
+}
+
+impl HeadlessReport {
+    pub fn analyze(script: &str, file_path: &str) -> Self {
+        Self {
+            file: file_path.to_string(),
+            dialog_calls: find_dialog_calls(script),
+        }
+    }
+
+    /// A script is headless-safe when it contains no dialog calls at all
+    pub fn is_headless_safe(&self) -> bool {
+        self.dialog_calls.is_empty()
+    }
+
+    pub fn to_text(&self) -> String {
+        if self.is_headless_safe() {
+            return format!("{}: no dialog calls found\n", self.file);
+        }
+        let mut output = format!("{}: {} dialog call(s)\n", self.file, self.dialog_calls.len());
+        for call in &self.dialog_calls {
+            output.push_str(&format!("  {}:{} {}()\n", call.line, call.column, call.name));
+            if call.conditions.is_empty() {
+                output.push_str("    always reached\n");
+            }
+            for condition in &call.conditions {
+                output.push_str(&format!("    within {}\n", condition));
+            }
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Checks calls that are not allowed in the configured runtime environment
+pub fn validate_environment(script: &str, file_path: &str, environment: Environment) -> Vec<ValidatorError> {
+    if environment == Environment::Panel {
+        return validate_panel_script(script, file_path);
+    }
+    let mut errors = Vec::new();
+    if environment != Environment::Aerender {
+        return errors;
+    }
+
+    let guarded = interactive_branches(script);
+    for call in find_dialog_calls(script) {
+        let position = (call.line, call.column);
+        if guarded.iter().any(|(first, last)| *first <= position && position <= *last) {
+            continue;
+        }
+        errors.push(environment_error(
+            script,
+            file_path,
+            call.line,
+            call.column,
+            format!("'{}' displays UI, which hangs or fails under aerender", call.name),
+            "Write to $.writeln or a log file instead, or guard the call with if (!app.isRenderEngine)",
+            ErrorSeverity::Error,
+        ));
+    }
+
+    let tokens = significant_tokens(script);
+    for (i, token) in tokens.iter().enumerate() {
+        if token.text != "app" || !tokens.get(i + 1).is_some_and(|t| t.is_punct(".")) {
+            continue;
+        }
+        if i > 0 && tokens[i - 1].is_punct(".") {
+            continue;
+        }
+        let member = match tokens.get(i + 2) {
+            Some(member) => member.text.as_str(),
+            None => continue,
+        };
+        let next = tokens.get(i + 3);
+        if member == "watchFolder" && next.is_some_and(|t| t.is_punct("(")) {
+            errors.push(environment_error(
+                script,
+                file_path,
+                token.line,
+                token.column,
+                "app.watchFolder() never returns and blocks the aerender process".to_string(),
+                "Start watch folder rendering from the After Effects UI or a dedicated render node",
+                ErrorSeverity::Error,
+            ));
+        } else if member == "exitAfterLaunchAndEval" && next.is_some_and(|t| t.is_punct("="))
+            && tokens.get(i + 4).is_some_and(|t| t.is_keyword("false")) {
+            errors.push(environment_error(
+                script,
+                file_path,
+                token.line,
+                token.column,
+                "Setting app.exitAfterLaunchAndEval to false keeps aerender running after the script finishes".to_string(),
+                "Leave app.exitAfterLaunchAndEval at its default so the render node exits",
+                ErrorSeverity::Warning,
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Branches that only run outside aerender: the body of
+/// `if (!app.isRenderEngine)` and the `else` of `if (app.isRenderEngine)`, as
+/// the line and column of their first and last tokens
+fn interactive_branches(script: &str) -> Vec<((usize, usize), (usize, usize))> {
+    let tokens = significant_tokens(script);
+    let position = |i: usize| (tokens[i].line, tokens[i].column);
+    let mut branches = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_keyword("if") {
+            continue;
+        }
+        let close = match matching_bracket(&tokens, i + 1) {
+            Some(close) => close,
+            None => continue,
+        };
+        let condition: Vec<&str> = tokens[i + 2..close].iter().map(|t| t.text.as_str()).collect();
+        let negated = match condition.as_slice() {
+            ["!", "app", ".", "isRenderEngine"] => true,
+            ["app", ".", "isRenderEngine"] => false,
+            _ => continue,
+        };
+        let body_end = match branch_end(&tokens, close + 1) {
+            Some(end) => end,
+            None => continue,
+        };
+        if negated {
+            branches.push((position(close + 1), position(body_end)));
+        } else if tokens.get(body_end + 1).is_some_and(|t| t.is_keyword("else")) {
+            if let Some(else_end) = branch_end(&tokens, body_end + 2) {
+                branches.push((position(body_end + 2), position(else_end)));
+            }
+        }
+    }
+
+    branches
+}
+
+fn environment_error(
+    script: &str,
+    file_path: &str,
+    line: usize,
+    column: usize,
+    message: String,
+    suggestion: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(column)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion.to_string()))
+        .rule("headless-ui")
+        .build();
+
+    ValidatorError::Script {
+        message,
+        context,
+        severity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_dialog_calls() {
+        let script = r#"
+            // alert("commented out");
+            var msg = "alert(1)";
+            alert("Done");
+            app.project.saveWithDialog();
+            var f = File.openDialog("Pick");
+            var w = new Window("dialog", "Options");
+            logger.confirm("not a dialog");
+        "#;
+        let names: Vec<String> = find_dialog_calls(script).into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["alert", "saveWithDialog", "openDialog", "Window"]);
+    }
+
+    #[test]
+    fn test_dialog_conditions() {
+        let script = r#"
+            function run(comp) {
+                if (!(comp instanceof CompItem)) {
+                    alert("Select a comp");
+                    return;
+                } else {
+                    for (var i = 1; i <= comp.numLayers; i++) {
+                        if (comp.layer(i).locked) prompt("Unlock?", "yes");
+                    }
+                }
+            }
+            button.onClick = function () { confirm("Go?"); };
+        "#;
+        let calls = find_dialog_calls(script);
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].conditions, vec!["function run", "if (!(comp instanceof CompItem))"]);
+        assert_eq!(calls[1].conditions, vec![
+            "function run",
+            "else of if (!(comp instanceof CompItem))",
+            "for (var i = 1; i <= comp.numLayers; i++)",
+            "if (comp.layer(i).locked)",
+        ]);
+        assert_eq!(calls[2].conditions, vec!["function button.onClick"]);
+    }
+
+    #[test]
+    fn test_headless_report() {
+        assert!(HeadlessReport::analyze("var a = 1;", "a.jsx").is_headless_safe());
+        let report = HeadlessReport::analyze("alert('x');", "b.jsx");
+        assert!(report.to_text().contains("always reached"));
+        assert!(report.to_json().contains("\"name\": \"alert\""));
+    }
+
+    #[test]
+    fn test_aerender_environment() {
+        let script = "confirm(\"Continue?\");\napp.exitAfterLaunchAndEval = false;\n";
+        assert!(validate_environment(script, "test.jsx", Environment::Interactive).is_empty());
+
+        let errors = validate_environment(script, "test.jsx", Environment::Aerender);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].severity(), ErrorSeverity::Error);
+        assert_eq!(errors[1].severity(), ErrorSeverity::Warning);
+    }
+
+    #[test]
+    fn test_aerender_ignores_strings_and_comments() {
+        let script = r#"
+            $.writeln("call app.watchFolder(dir) from the UI");
+            /* app.exitAfterLaunchAndEval = false; */
+            var note = 'app.exitAfterLaunchAndEval = false';
+            /*
+             * app.watchFolder(folder);
+             */
+            app.watchFolder(folder);
+        "#;
+        let errors = validate_environment(script, "test.jsx", Environment::Aerender);
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].get_context().line, errors[0].get_context().column), (Some(8), Some(13)));
+    }
+
+    #[test]
+    fn test_render_engine_guards() {
+        let script = r#"
+            if (!app.isRenderEngine) {
+                alert("Done");
+            }
+            if (app.isRenderEngine) $.writeln("Done"); else confirm("Done?");
+            if (app.isRenderEngine) {
+                alert("Rendering");
+            }
+            prompt("Name?", "");
+        "#;
+        let errors = validate_environment(script, "test.jsx", Environment::Aerender);
+        let lines: Vec<_> = errors.iter().map(|e| e.get_context().line).collect();
+        assert_eq!(lines, vec![Some(7), Some(9)]);
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{matching_bracket, significant_tokens, Token, TokenKind};
+use crate::data::match_names::{get_property_children, get_property_parents, PROPERTY_TREE};
+
+/// `layers.add*` methods and the match name of the layer they create
+const LAYER_CONSTRUCTORS: &[(&str, &str)] = &[
+    ("add", "ADBE AV Layer"),
+    ("addSolid", "ADBE AV Layer"),
+    ("addNull", "ADBE AV Layer"),
+    ("addText", "ADBE Text Layer"),
+    ("addBoxText", "ADBE Text Layer"),
+    ("addVerticalText", "ADBE Text Layer"),
+    ("addVerticalBoxText", "ADBE Text Layer"),
+    ("addShape", "ADBE Vector Layer"),
+    ("addCamera", "ADBE Camera Layer"),
+    ("addLight", "ADBE Light Layer"),
+];
+
+/// Checks `property()` and `addProperty()` calls against the property tree:
+/// a match name the tree places under other owners cannot be found in, or
+/// added to, the receiver, e.g. camera options on a text layer. Receivers
+/// are followed through call chains and variables assigned from
+/// `layers.add*()` or from `property()` calls; others are not checked.
+/// Calls in `catch` blocks are left alone, as scripts use them to try
+/// another match name when the first one fails.
+pub fn validate_property_hierarchy(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let fallbacks = catch_blocks(&tokens);
+    let mut owners: HashMap<&str, &'static str> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for i in 0..tokens.len() {
+        if !tokens[i].is_identifier() || (i > 0 && tokens[i - 1].is_punct(".")) {
+            continue;
+        }
+        let mut found = Vec::new();
+        let owner = walk_chain(script, file_path, &tokens, i, owners.get(tokens[i].text.as_str()).copied(), &mut found);
+        if !fallbacks.iter().any(|(open, close)| (*open..*close).contains(&i)) {
+            errors.append(&mut found);
+        }
+
+        // `var text = comp.layers.addText("Title");`
+        let assigned = i >= 2
+            && tokens[i - 1].is_punct("=")
+            && tokens[i - 2].is_identifier()
+            && !(i >= 3 && tokens[i - 3].is_punct("."));
+        if assigned {
+            let name = tokens[i - 2].text.as_str();
+            match owner {
+                Some(owner) => owners.insert(name, owner),
+                None => owners.remove(name),
+            };
+        }
+    }
+
+    errors
+}
+
+/// Follows the member calls chained onto the identifier at `start`,
+/// reporting children the current owner cannot have, and returns the owner
+/// the chain ends on
+fn walk_chain(
+    script: &str,
+    file_path: &str,
+    tokens: &[Token],
+    start: usize,
+    mut owner: Option<&'static str>,
+    errors: &mut Vec<ValidatorError>,
+) -> Option<&'static str> {
+    let mut previous = tokens[start].text.as_str();
+    let mut i = start + 1;
+
+    while i + 1 < tokens.len() && tokens[i].is_punct(".") && tokens[i + 1].is_identifier() {
+        let member = tokens[i + 1].text.as_str();
+        let called = tokens.get(i + 2).is_some_and(|t| t.is_punct("("));
+        if !called {
+            owner = None;
+            previous = member;
+            i += 2;
+            continue;
+        }
+        let close = matching_bracket(tokens, i + 2)?;
+        let argument = match &tokens[i + 3..close] {
+            [arg] if arg.kind == TokenKind::String => Some(arg),
+            _ => None,
+        };
+
+        owner = match (member, argument) {
+            ("property" | "addProperty", Some(arg)) => {
+                let child = unquote(&arg.text);
+                let parents = get_property_parents(child);
+                if let Some(parent) = owner {
+                    let legal = get_property_children(parent).is_none_or(|children| children.contains(&child));
+                    if !legal && !parents.is_empty() {
+                        errors.push(hierarchy_error(script, file_path, arg, child, parent, &parents));
+                    }
+                }
+                tree_name(child)
+            }
+            _ if previous == "layers" => LAYER_CONSTRUCTORS.iter()
+                .find(|(method, _)| *method == member)
+                .map(|(_, layer)| *layer),
+            _ => None,
+        };
+        previous = member;
+        i = close + 1;
+    }
+
+    owner
+}
+
+/// The tree's own copy of a match name, `None` for names not in the tree
+fn tree_name(match_name: &str) -> Option<&'static str> {
+    PROPERTY_TREE.iter()
+        .flat_map(|(owner, children)| std::iter::once(owner).chain(children.iter()))
+        .find(|name| **name == match_name)
+        .copied()
+}
+
+fn unquote(text: &str) -> &str {
+    text.get(1..text.len().saturating_sub(1)).unwrap_or("")
+}
+
+/// Token ranges of the bodies of `catch` blocks
+fn catch_blocks(tokens: &[Token]) -> Vec<(usize, usize)> {
+    tokens.iter().enumerate()
+        .filter(|(_, token)| token.is_keyword("catch"))
+        .filter_map(|(i, _)| {
+            let open = tokens[i..].iter().position(|t| t.is_punct("{"))? + i;
+            Some((open, matching_bracket(tokens, open)?))
+        })
+        .collect()
+}
+
+/// How an owner reads in messages: layers by their kind, groups by match name
+fn describe(owner: &str) -> String {
+    match owner {
+        "ADBE AV Layer" => "an AV layer".to_string(),
+        "ADBE Text Layer" => "a text layer".to_string(),
+        "ADBE Vector Layer" => "a shape layer".to_string(),
+        "ADBE Camera Layer" => "a camera layer".to_string(),
+        "ADBE Light Layer" => "a light layer".to_string(),
+        group => format!("\"{}\"", group),
+    }
+}
+
+fn hierarchy_error(
+    script: &str,
+    file_path: &str,
+    token: &Token,
+    child: &str,
+    owner: &str,
+    parents: &[&str],
+) -> ValidatorError {
+    let places: Vec<String> = parents.iter().map(|parent| describe(parent)).collect();
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(format!("\"{}\" belongs in {}", child, places.join(" or "))))
+        .rule("property-parent")
+        .build();
+
+    ValidatorError::Script {
+        message: format!("\"{}\" is not a property of {}", child, describe(owner)),
+        context,
+        severity: ErrorSeverity::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_hierarchy() {
+        let script = r#"var title = comp.layers.addText("Title");
+title.property("ADBE Camera Options Group").property("ADBE Camera Zoom").setValue(800);
+var animators = title.property("ADBE Text Properties").property("ADBE Text Animators");
+animators.addProperty("ADBE Text Animator").property("ADBE Text Animator Properties").addProperty("ADBE Text Fill Color");
+animators.addProperty("ADBE Vector Group");
+var cam = comp.layers.addCamera("Camera", [960, 540]);
+cam.property("ADBE Camera Options Group").property("ADBE Camera Zoom").setValue(800);
+cam.property("Camera Options").property("ADBE Light Intensity");
+var shape = comp.layers.addShape();
+shape.property("ADBE Root Vectors Group").addProperty("ADBE Vector Shape - Rect");
+shape.property("ADBE Effect Parade").addProperty("ADBE Gaussian Blur 2");
+title = comp.layer(1);
+title.property("ADBE Root Vectors Group");
+try { title.property("ADBE Text Properties"); } catch (e) { shape.property("ADBE Text Properties"); }
+"#;
+        let errors = validate_property_hierarchy(script, "layers.jsx");
+        let found: Vec<(usize, String)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.get_message().to_string()))
+            .collect();
+        assert_eq!(found, vec![
+            (2, "\"ADBE Camera Options Group\" is not a property of a text layer".to_string()),
+            (5, "\"ADBE Vector Group\" is not a property of \"ADBE Text Animators\"".to_string()),
+        ]);
+        assert_eq!(errors[0].get_context().suggestion(), Some("\"ADBE Camera Options Group\" belongs in a camera layer"));
+    }
+}
@@ -0,0 +1,374 @@
+//! Translated diagnostic messages.
+//!
+//! Rules write their messages in English and record the values they
+//! interpolate as named arguments on the error context. A [`MessageCatalog`]
+//! maps message keys to templates such as `"Fehlendes Semikolon"` or
+//! `"... '{name}' ..."`; diagnostics without a template for the selected
+//! locale keep their English text.
+//!
+//! Keys are the rule id (or an explicit message key for rules with several
+//! messages), with `.suggestion` and `.explanation` suffixes for the
+//! suggestion and the long-form rule explanation.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use super::errors::{Suggestion, ValidatorError};
+
+/// Language diagnostics are reported in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+    De,
+    Fr,
+}
+
+impl std::str::FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Accept region-qualified tags such as `de-DE` or `ja_JP`
+        match s.split(['-', '_']).next().unwrap_or("").to_lowercase().as_str() {
+            "en" => Ok(Locale::En),
+            "ja" => Ok(Locale::Ja),
+            "de" => Ok(Locale::De),
+            "fr" => Ok(Locale::Fr),
+            _ => Err(format!("Unknown locale '{}' (expected en, ja, de or fr)", s)),
+        }
+    }
+}
+
+/// Key to template map for one locale
+#[derive(Debug, Clone, Default)]
+pub struct MessageCatalog {
+    templates: HashMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// The built-in catalog for a locale
+    pub fn for_locale(locale: Locale) -> Self {
+        let entries = match locale {
+            Locale::En => EN,
+            Locale::Ja => JA,
+            Locale::De => DE,
+            Locale::Fr => FR,
+        };
+        let mut catalog = Self::default();
+        for (key, template) in entries {
+            catalog.insert(key, template);
+        }
+        // Explanations fall back to English when a translation is missing
+        for (key, template) in EN {
+            catalog.templates.entry(key.to_string()).or_insert_with(|| template.to_string());
+        }
+        catalog
+    }
+
+    /// Loads a flat JSON object of `key: template` pairs, e.g. a community translation
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let templates: HashMap<String, String> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        Ok(Self { templates })
+    }
+
+    pub fn insert(&mut self, key: &str, template: &str) {
+        self.templates.insert(key.to_string(), template.to_string());
+    }
+
+    /// Overlays `other` on this catalog
+    pub fn merge(&mut self, other: MessageCatalog) {
+        self.templates.extend(other.templates);
+    }
+
+    /// Renders the template for `key`, replacing `{name}` placeholders with `args`
+    pub fn render(&self, key: &str, args: &[(String, String)]) -> Option<String> {
+        let template = self.templates.get(key)?;
+        let mut output = template.clone();
+        for (name, value) in args {
+            output = output.replace(&format!("{{{}}}", name), value);
+        }
+        Some(output)
+    }
+
+    /// Long-form description of a rule
+    pub fn explanation(&self, rule: &str) -> Option<String> {
+        self.render(&format!("{}.explanation", rule), &[])
+    }
+
+    /// Rewrites the message and suggestion of a diagnostic when templates exist
+    pub fn localize(&self, error: &mut ValidatorError) {
+        let context = error.get_context();
+        let key = match context.message_key.as_deref().or(context.rule.as_deref()) {
+            Some(key) => key.to_string(),
+            None => return,
+        };
+        let args = context.args.clone();
+
+        if let Some(message) = self.render(&key, &args) {
+            *error.get_message_mut() = message;
+        }
+        if let Some(suggestion) = self.render(&format!("{}.suggestion", key), &args) {
+            // Templates translate the best suggestion; alternatives keep their source text
+            let suggestions = &mut error.get_context_mut().suggestions;
+            match suggestions.first_mut() {
+                Some(best) => best.message = suggestion,
+                None => suggestions.push(Suggestion::new(suggestion)),
+            }
+        }
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("implicit-global.explanation",
+        "Assigning to a name that was never declared creates a property on the global object. \
+         All scripts in an After Effects session share one ExtendScript engine, so the value leaks \
+         into other scripts and panels and survives between runs."),
+    ("missing-semicolon.explanation",
+        "ExtendScript inserts semicolons automatically, but a line break before '(' or '[' can join \
+         two statements into one. Ending every statement with ';' avoids these surprises."),
+    ("loose-equality.explanation",
+        "'==' and '!=' convert their operands before comparing. When one side has a known type, \
+         '===' and '!==' state the intent and avoid coercion bugs such as '0 == \"\"'."),
+    ("unguarded-active-item.explanation",
+        "app.project.activeItem is null when no item is selected or the Project panel has focus \
+         without a selection. Check it (and its type) before use."),
+    ("syntax.explanation",
+        "Brackets, braces and parentheses must be balanced, and strings and comments must be closed. \
+         The validator reports each problem separately and keeps checking the rest of the file."),
+    ("color-range.explanation",
+        "After Effects color values are floats from 0 to 1, so [255, 0, 0] is not red. \
+         Divide 8-bit components by 255; `ae-validator fix` rewrites such literals."),
+    ("missing-footage.explanation",
+        "With --check-paths, File path literals are resolved relative to the script and must exist. \
+         Map absolute asset locations to local folders with --path-root <prefix>=<dir> or `path_roots` in .auteurrc."),
+    ("platform-path.explanation",
+        "Drive letters and backslashes only work on Windows, and `~` or fsName-built paths differ between platforms. \
+         Set `platforms` in .auteurrc (or --platform) to the systems your scripts actually run on."),
+    ("locale-menu-command.explanation",
+        "Menu names are translated, so app.findMenuCommandId(\"Convert Audio to Keyframes\") returns 0 on a German install. \
+         Pass the numeric command ID to app.executeCommand, or use the scripting API for the same operation."),
+    ("locale-effect-name.explanation",
+        "Effect display names are translated on non-English installs. \
+         Match names such as \"ADBE Gaussian Blur 2\" are the same in every language."),
+    ("loop-undo-entries.explanation",
+        "Outside an undo group every setValue or addProperty call is a separate undo entry, and After Effects slows down \
+         as hundreds of them pile up. Begin one undo group before the loop and end it after the loop."),
+    ("expression-global-in-script.explanation",
+        "thisComp, thisLayer, thisProperty and thisProject are defined by the expression engine only. \
+         A script reaches the same objects through app.project, e.g. app.project.activeItem.layer(1)."),
+    ("script-global-in-expression.explanation",
+        "Expressions run in their own engine without app, $, File or Folder. \
+         Compute such values in the script and write the result into the expression string."),
+    ("jsxbin.explanation",
+        "Files exported as binary (.jsxbin) contain encoded bytecode, not source, so no rule can check them. \
+         Validate the original .jsx, or pass --allow-jsxbin (`allow_jsxbin` in .auteurrc) to skip compiled files."),
+    ("source-encoding.explanation",
+        "Scripts are read as UTF-8 (with or without BOM), UTF-16 or Latin-1 and line endings are normalized to \\n, \
+         so reported lines and columns match the editor. UTF-16 and Latin-1 files are better re-saved as UTF-8."),
+    ("duplicate-global.explanation",
+        "Every included file runs in the same ExtendScript engine, so a global function or variable defined in two \
+         files with different bodies is silently replaced by whichever loads last, for every caller."),
+    ("naming-layer-name.explanation",
+        "Layer names given as string literals must match the `naming.layer_names` pattern from .auteurrc."),
+    ("naming-comp-name.explanation",
+        "Comp names given as string literals must match the `naming.comp_names` pattern from .auteurrc."),
+    ("naming-function.explanation",
+        "Function names must follow the `naming.functions` style from .auteurrc (camelCase, PascalCase, snake_case or UPPER_SNAKE)."),
+    ("naming-constant.explanation",
+        "Global variables initialized to a literal and never reassigned are constants and must follow the `naming.constants` style from .auteurrc."),
+    ("naming-undo-group.explanation",
+        "With `naming.undo_groups` enabled, undo groups need a name that describes the change, since it is shown in Edit > Undo."),
+    ("class-name-case.explanation",
+        "ExtendScript names are case-sensitive: `blendingMode.MULTIPLY` or `new importOptions(f)` refer to undefined globals and throw at runtime. Use the exact spelling, `BlendingMode` or `ImportOptions`."),
+    ("property-constraint.explanation",
+        "Some properties only make sense together: the work area must fit inside the comp and a layer's inPoint must come \
+         before its outPoint. After Effects throws when an assignment breaks such a relationship, so literal values are checked up front."),
+    ("use-after-remove.explanation",
+        "remove() deletes the layer, comp or property from the project, and the variable is left pointing at an invalid object. \
+         Reading or changing it afterwards throws \"Object is invalid\". Read what you need first, or check isValid()."),
+    ("duplicate-remove.explanation",
+        "Calling remove() on an object that was already removed throws at runtime. Remove it once, or guard the call with isValid()."),
+    ("stale-reference.explanation",
+        "Closing or replacing the project, or removing a comp, invalidates the objects fetched from it earlier. \
+         Using such a variable throws \"Object is invalid\". Check isValid() first, or fetch the object again."),
+    ("alternate-source-type.explanation",
+        "Media Replacement properties take a comp or footage item as their new source. Folders, files and layers are rejected at runtime."),
+    ("alternate-source-unchecked.explanation",
+        "Only Essential Properties added to the template as Media Replacement accept setAlternateSource(). \
+         Check canSetAlternateSource first so templates without such a property don't throw."),
+    ("alternate-source-layer.explanation",
+        "essentialProperty is the group of Essential Properties of a precomp layer. Text, shape, camera, light, null and solid layers have no comp source and no Essential Properties."),
+    ("validation-truncated.explanation",
+        "Validation was cancelled or ran past its time budget, so the passes after the one named were skipped. \
+         The other diagnostics are partial; a script without them is not known to be valid."),
+    ("suppress-dialogs-leak.explanation",
+        "app.beginSuppressDialogs() stays in effect until app.endSuppressDialogs() runs, even after the script ends. \
+         Until then After Effects hides its error dialogs, so later failures go unnoticed."),
+    ("suppress-dialogs-early-exit.explanation",
+        "A return between beginSuppressDialogs() and endSuppressDialogs() skips the end and leaves dialogs suppressed. \
+         Ending the suppression in a finally block runs it on every path."),
+    ("suppress-dialogs-unprotected.explanation",
+        "If the code between beginSuppressDialogs() and endSuppressDialogs() throws, the end never runs and dialogs stay suppressed. \
+         Put the end in a finally block; `ae-validator fix` rewrites simple cases."),
+    ("suppress-dialogs-unmatched-end.explanation",
+        "endSuppressDialogs() without an earlier beginSuppressDialogs() in the same function usually means the begin was removed or lives elsewhere."),
+    ("empty-catch.explanation",
+        "An empty catch block hides the failure entirely. Handle or rethrow the error, or leave a comment explaining why ignoring it is safe."),
+    ("catch-unused-error.explanation",
+        "A catch block that never reads the caught error loses its message and line, which are often the only clue when a script fails on another machine."),
+    ("script-wide-catch.explanation",
+        "A try/catch around the whole script turns every failure into one generic message, and on render farms an alert goes unseen. Catch around the steps that can fail, or rethrow."),
+    ("infinite-loop.explanation",
+        "Scripts run on After Effects' only thread, so a loop whose condition never becomes false and that never breaks out freezes the application until it is force quit."),
+    ("panel-entry.explanation",
+        "After Effects runs panel scripts with 'this' set to the dock Panel. The UI must be built onto it, falling back to a palette Window when the script is run from File > Scripts."),
+    ("panel-layout.explanation",
+        "A docked panel does not lay out its controls by itself; without layout.layout(true) it shows up empty."),
+    ("panel-resize.explanation",
+        "Docked panels are resized with the frame around them. An onResizing handler calling layout.resize() keeps the controls fitting the panel."),
+    ("panel-blocking-load.explanation",
+        "Panels load while After Effects starts, so a dialog or other blocking call at load time stalls startup until someone dismisses it."),
+    ("effect-bit-depth.explanation",
+        "Effects that only process 8 or 16 bits per channel convert a higher bit-depth project's colors down, clipping over-bright values and banding gradients."),
+    ("effect-gpu.explanation",
+        "Effects without GPU acceleration render on the CPU. In 32-bpc projects that is the slowest path, so renders take noticeably longer."),
+    ("effect-availability.explanation",
+        "Third-party plug-ins bundled with After Effects, such as the Cycore CC effects, are not on every install. addProperty() fails where they are missing."),
+    ("obsolete-effect.explanation",
+        "Obsolete effects are kept so old projects open, but newer effects replace them with better quality, higher bit depths or GPU rendering. \
+         Where the parameters match, `ae-validator fix` swaps the match name."),
+    ("property-parent.explanation",
+        "Each property group only contains certain properties: camera options exist only on camera layers, shape contents only under a shape group. \
+         property() returns null and addProperty() throws for a match name its receiver cannot hold."),
+    ("layer-capability.explanation",
+        "Every layer is reached through the same Layer API, but layer types differ: cameras and lights have no effects, masks or AV switches, \
+         and text and shape layers have no source to replace. Using a missing feature fails when the script runs."),
+    ("3d-only-property.explanation",
+        "X and Y Rotation, Orientation and Material Options only exist once a layer's 3D switch is on. \
+         New layers start out 2D, so accessing these properties before setting threeDLayer = true fails at runtime."),
+    ("option-range.explanation",
+        "Camera and light options have limits: zoom, aperture and focus distance must be positive, and a light's cone angle is between 0 and 180 degrees. \
+         setValue() throws for values outside them."),
+    ("option-inactive.explanation",
+        "Some options only matter while a switch is on: shadow darkness and diffusion need Casts Shadows, and the blur settings need Depth of Field. \
+         New cameras and lights start with both off, so values set before turning them on have no visible effect."),
+    ("per-char-3d.explanation",
+        "X and Y Rotation in a text animator rotate each character in 3D, which needs the layer's per-character 3D switch. \
+         New text layers start with threeDPerChar off, and adding these properties before turning it on fails."),
+    ("expression-selector-dimension.explanation",
+        "An expression selector's Amount has one component per axis. Its expression must return a single number or a 3-component array; \
+         any other array length makes the expression fail, and the animator silently stops applying."),
+    ("keyframe-ease-influence.explanation",
+        "A KeyframeEase influence is the percentage of the time to the neighbouring key that the ease extends over. \
+         After Effects rejects values outside 0.1 to 100 when the ease is applied."),
+    ("keyframe-ease-fraction.explanation",
+        "Influence is a percentage, not a fraction: 0.33 makes the ease so short that the key is practically linear. \
+         Easy Ease uses 33.33333."),
+    ("keyframe-ease-dimensions.explanation",
+        "setTemporalEaseAtKey takes one KeyframeEase per dimension for each side of the key. \
+         Arrays of different lengths for the entering and leaving side make the call fail."),
+];
+
+const JA: &[(&str, &str)] = &[
+    ("implicit-global", "未宣言の変数 '{name}' への代入により暗黙のグローバル変数が作成されます"),
+    ("implicit-global.suggestion", "外側の関数内で 'var {name}' として宣言するか、名前空間オブジェクトに追加してください（例: MyScript.{name}）"),
+    ("implicit-global.explanation",
+        "宣言されていない名前に代入すると、グローバルオブジェクトのプロパティが作成されます。\
+         After Effects のセッション内のすべてのスクリプトは 1 つの ExtendScript エンジンを共有するため、\
+         値が他のスクリプトやパネルに漏れ、実行後も残ります。"),
+    ("missing-semicolon", "セミコロンがありません"),
+    ("missing-semicolon.suggestion", "文の末尾にセミコロンを追加してください"),
+    ("loose-equality", "型が既知の値に緩い等価演算子 '{operator}' が使われています"),
+    ("loose-equality.suggestion", "型変換なしで比較するには '{strict}' を使用してください"),
+    ("unguarded-active-item", "null チェックなしで app.project.activeItem を使用しています"),
+    ("unguarded-active-item.suggestion", "追加: if (app.project.activeItem) { ... }"),
+    ("syntax.unclosed-parenthesis", "ここで開かれた丸括弧が閉じられていません"),
+    ("syntax.unclosed-bracket", "ここで開かれた角括弧が閉じられていません"),
+    ("syntax.unclosed-brace", "ここで開かれた波括弧が閉じられていません"),
+    ("syntax.unmatched-parenthesis", "対応する開き丸括弧のない閉じ丸括弧があります"),
+    ("syntax.unmatched-bracket", "対応する開き角括弧のない閉じ角括弧があります"),
+    ("syntax.unmatched-brace", "対応する開き波括弧のない閉じ波括弧があります"),
+    ("syntax.unterminated-string", "文字列リテラルが閉じられていません"),
+    ("syntax.unterminated-template", "テンプレートリテラルが閉じられていません"),
+    ("syntax.unterminated-comment", "ブロックコメントが閉じられていません"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("implicit-global", "Zuweisung an die nicht deklarierte Variable '{name}' erzeugt eine implizite globale Variable"),
+    ("implicit-global.suggestion", "Deklarieren Sie sie mit 'var {name}' in der umgebenden Funktion oder hängen Sie sie an ein Namespace-Objekt an (z. B. MyScript.{name})"),
+    ("implicit-global.explanation",
+        "Eine Zuweisung an einen nie deklarierten Namen erzeugt eine Eigenschaft des globalen Objekts. \
+         Alle Skripte einer After-Effects-Sitzung teilen sich eine ExtendScript-Engine, daher gelangt der \
+         Wert in andere Skripte und Panels und bleibt zwischen Ausführungen erhalten."),
+    ("missing-semicolon", "Fehlendes Semikolon"),
+    ("missing-semicolon.suggestion", "Fügen Sie am Ende der Anweisung ein Semikolon hinzu"),
+    ("loose-equality", "Loser Vergleich '{operator}' auf einem Wert mit bekanntem Typ"),
+    ("loose-equality.suggestion", "Verwenden Sie '{strict}', um ohne Typumwandlung zu vergleichen"),
+    ("unguarded-active-item", "app.project.activeItem wird ohne Null-Prüfung verwendet"),
+    ("unguarded-active-item.suggestion", "Ergänzen Sie: if (app.project.activeItem) { ... }"),
+    ("syntax.unclosed-parenthesis", "Hier geöffnete runde Klammer wird nicht geschlossen"),
+    ("syntax.unclosed-bracket", "Hier geöffnete eckige Klammer wird nicht geschlossen"),
+    ("syntax.unclosed-brace", "Hier geöffnete geschweifte Klammer wird nicht geschlossen"),
+    ("syntax.unmatched-parenthesis", "Schließende runde Klammer ohne passende öffnende Klammer"),
+    ("syntax.unmatched-bracket", "Schließende eckige Klammer ohne passende öffnende Klammer"),
+    ("syntax.unmatched-brace", "Schließende geschweifte Klammer ohne passende öffnende Klammer"),
+    ("syntax.unterminated-string", "Nicht abgeschlossenes Zeichenkettenliteral"),
+    ("syntax.unterminated-template", "Nicht abgeschlossenes Template-Literal"),
+    ("syntax.unterminated-comment", "Nicht abgeschlossener Blockkommentar"),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("implicit-global", "L'affectation à la variable non déclarée '{name}' crée une variable globale implicite"),
+    ("implicit-global.suggestion", "Déclarez-la avec 'var {name}' dans la fonction englobante, ou rattachez-la à un objet d'espace de noms (par ex. MyScript.{name})"),
+    ("implicit-global.explanation",
+        "Affecter une valeur à un nom jamais déclaré crée une propriété de l'objet global. \
+         Tous les scripts d'une session After Effects partagent un même moteur ExtendScript : la valeur \
+         se propage aux autres scripts et panneaux et persiste d'une exécution à l'autre."),
+    ("missing-semicolon", "Point-virgule manquant"),
+    ("missing-semicolon.suggestion", "Ajoutez un point-virgule à la fin de l'instruction"),
+    ("loose-equality", "Égalité non stricte '{operator}' utilisée sur une valeur de type connu"),
+    ("loose-equality.suggestion", "Utilisez '{strict}' pour comparer sans conversion de type"),
+    ("unguarded-active-item", "app.project.activeItem est utilisé sans vérification de null"),
+    ("unguarded-active-item.suggestion", "Ajoutez : if (app.project.activeItem) { ... }"),
+    ("syntax.unclosed-parenthesis", "Parenthèse ouverte ici mais jamais fermée"),
+    ("syntax.unclosed-bracket", "Crochet ouvert ici mais jamais fermé"),
+    ("syntax.unclosed-brace", "Accolade ouverte ici mais jamais fermée"),
+    ("syntax.unmatched-parenthesis", "Parenthèse fermante sans parenthèse ouvrante correspondante"),
+    ("syntax.unmatched-bracket", "Crochet fermant sans crochet ouvrant correspondant"),
+    ("syntax.unmatched-brace", "Accolade fermante sans accolade ouvrante correspondante"),
+    ("syntax.unterminated-string", "Chaîne de caractères non terminée"),
+    ("syntax.unterminated-template", "Littéral de gabarit non terminé"),
+    ("syntax.unterminated-comment", "Commentaire de bloc non terminé"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::script::scope::validate_implicit_globals;
+
+    #[test]
+    fn test_localizes_message_and_suggestion() {
+        let mut errors = validate_implicit_globals("total = 1;", "a.jsx");
+        MessageCatalog::for_locale(Locale::De).localize(&mut errors[0]);
+        assert_eq!(errors[0].get_message(), "Zuweisung an die nicht deklarierte Variable 'total' erzeugt eine implizite globale Variable");
+        assert!(errors[0].get_context().suggestion().unwrap().contains("var total"));
+    }
+
+    #[test]
+    fn test_missing_template_keeps_english() {
+        let mut errors = validate_implicit_globals("total = 1;", "a.jsx");
+        let english = errors[0].get_message().to_string();
+        MessageCatalog::for_locale(Locale::En).localize(&mut errors[0]);
+        assert_eq!(errors[0].get_message(), english);
+    }
+
+    #[test]
+    fn test_locale_tags_and_explanations() {
+        assert_eq!("ja-JP".parse::<Locale>(), Ok(Locale::Ja));
+        assert!("xx".parse::<Locale>().is_err());
+        let catalog = MessageCatalog::for_locale(Locale::Fr);
+        assert!(catalog.explanation("implicit-global").unwrap().contains("ExtendScript"));
+        assert!(catalog.explanation("loose-equality").unwrap().contains("coercion"));
+    }
+}
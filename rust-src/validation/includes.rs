@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use super::encoding::read_script;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity, SourceLocation};
+use super::script::lexer::{matching_bracket, Token};
+use super::script::scope::analyze_scopes;
+use super::shims::include_paths;
+
+/// A script reached through `#include` lines
+#[derive(Debug, Clone)]
+pub struct IncludedFile {
+    pub path: PathBuf,
+    /// Name used in diagnostics: the entry script's own name, or the path as
+    /// seen from the entry script's folder
+    pub name: String,
+    pub text: String,
+}
+
+/// The entry script and every file it includes, directly or through other
+/// includes, in the order ExtendScript loads them
+#[derive(Debug, Clone)]
+pub struct IncludeGraph {
+    pub files: Vec<IncludedFile>,
+    /// `(including file, include path)` for includes that do not resolve locally
+    pub unresolved: Vec<(String, String)>,
+}
+
+impl IncludeGraph {
+    /// Follows `#include` and `//@include` lines, resolved against the
+    /// including file's folder. Each file is loaded once, where it is first
+    /// included, so include cycles terminate.
+    pub fn resolve(entry_path: &str, entry_text: &str) -> Self {
+        let entry = Path::new(entry_path);
+        let root = entry.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let mut graph = IncludeGraph { files: Vec::new(), unresolved: Vec::new() };
+        let mut seen = HashSet::new();
+        seen.insert(canonical(entry));
+        graph.visit(IncludedFile { path: entry.to_path_buf(), name: entry_path.to_string(), text: entry_text.to_string() }, &root, &mut seen);
+        graph
+    }
+
+    fn visit(&mut self, file: IncludedFile, root: &Path, seen: &mut HashSet<PathBuf>) {
+        let dir = file.path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let includes = include_paths(&file.text);
+        let name = file.name.clone();
+        for include in includes {
+            let path = dir.join(&include);
+            if !seen.insert(canonical(&path)) {
+                continue;
+            }
+            match read_script(&path) {
+                Ok(source) => {
+                    let name = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+                    self.visit(IncludedFile { path, name, text: source.text }, root, seen);
+                }
+                Err(_) => self.unresolved.push((name.clone(), include)),
+            }
+        }
+        // Included code runs before the rest of the including file
+        self.files.push(file);
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// A global function or initialized global variable
+#[derive(Debug, Clone)]
+struct GlobalDefinition {
+    name: String,
+    is_function: bool,
+    line: usize,
+    column: usize,
+    /// Token texts of the function or initializer, joined by spaces so
+    /// formatting differences do not count as different bodies
+    body: String,
+}
+
+fn global_definitions(script: &str) -> Vec<GlobalDefinition> {
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let join = |range: &[Token]| range.iter().map(|t| t.text.as_str()).collect::<Vec<_>>().join(" ");
+    let mut definitions = Vec::new();
+
+    for scope in analysis.scopes.iter().skip(1).filter(|s| s.parent == Some(0)) {
+        let name = match &scope.name {
+            Some(name) => name,
+            None => continue,
+        };
+        let declaration = scope.start == 0 || ["{", "}", ";"].iter().any(|p| tokens[scope.start - 1].is_punct(p));
+        let body = tokens[scope.start..].iter().position(|t| t.is_punct("{")).map(|open| scope.start + open);
+        if let Some(end) = body.filter(|_| declaration).and_then(|open| matching_bracket(tokens, open)) {
+            definitions.push(GlobalDefinition {
+                name: name.clone(),
+                is_function: true,
+                line: tokens[scope.start + 1].line,
+                column: tokens[scope.start + 1].column,
+                body: join(&tokens[scope.start..=end]),
+            });
+        }
+    }
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_keyword("var") || analysis.token_scopes[i] != 0 {
+            continue;
+        }
+        let mut j = i + 1;
+        while let (Some(name), Some(assign)) = (tokens.get(j), tokens.get(j + 1)) {
+            if !name.is_identifier() {
+                break;
+            }
+            let mut end = j + 1;
+            if assign.is_punct("=") {
+                end = j + 2;
+                while let Some(t) = tokens.get(end) {
+                    if t.is_punct(";") || t.is_punct(",") || (end > j + 2 && t.newlines_before > 0) {
+                        break;
+                    }
+                    // Nested brackets are skipped whole, line breaks inside them included
+                    end = matching_bracket(tokens, end).map_or(end + 1, |close| close + 1);
+                }
+                let value = &tokens[j + 2..end];
+                // `var MyLib = MyLib || {}` deliberately shares one object between files
+                let namespace = value.len() >= 2 && value[0].text == name.text && value[1].is_punct("||");
+                if !namespace {
+                    definitions.push(GlobalDefinition {
+                        name: name.text.clone(),
+                        is_function: false,
+                        line: name.line,
+                        column: name.column,
+                        body: join(value),
+                    });
+                }
+            }
+            if !tokens.get(end).is_some_and(|t| t.is_punct(",")) {
+                break;
+            }
+            j = end + 1;
+        }
+    }
+
+    definitions
+}
+
+/// Reports globals that two files of an include graph define differently.
+/// Every file shares one engine, so whichever definition loads last
+/// silently replaces the other for all callers. The diagnostic points at
+/// the later definition and lists the earlier one as related.
+pub fn validate_duplicate_globals(graph: &IncludeGraph) -> Vec<ValidatorError> {
+    let mut errors = Vec::new();
+    let mut defined: Vec<(&IncludedFile, GlobalDefinition)> = Vec::new();
+
+    for file in &graph.files {
+        let definitions = global_definitions(&file.text);
+        let mut reported = HashSet::new();
+        for definition in &definitions {
+            let earlier = defined.iter()
+                .find(|(other, d)| other.path != file.path && d.name == definition.name && d.body != definition.body);
+            if let Some((other, first)) = earlier.filter(|_| reported.insert(definition.name.clone())) {
+                let kind = if definition.is_function { "Function" } else { "Global variable" };
+                let mut context = ErrorContextBuilder::new()
+                    .file(file.name.clone())
+                    .line(definition.line)
+                    .column(definition.column)
+                    .length(definition.name.chars().count())
+                    .code_snippet(file.text.lines().nth(definition.line - 1).unwrap_or("").trim().to_string())
+                    .suggestion(Some(format!(
+                        "Rename one of them or move the shared definition into a single file; this one replaces {}:{} for every script",
+                        other.name, first.line
+                    )))
+                    .rule("duplicate-global")
+                    .build();
+                context.related.push(SourceLocation { file: other.name.clone(), line: first.line, column: first.column });
+
+                errors.push(ValidatorError::Scope {
+                    message: format!("{} '{}' is also defined in {} with a different body", kind, definition.name, other.name),
+                    context,
+                    severity: ErrorSeverity::Warning,
+                    variable: definition.name.clone(),
+                });
+            }
+        }
+        defined.extend(definitions.into_iter().map(|d| (file, d)));
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_redefinitions_across_includes() {
+        let dir = std::env::temp_dir().join(format!("ae-validator-includes-{}", std::process::id()));
+        fs::create_dir_all(dir.join("lib")).unwrap();
+        fs::write(dir.join("lib/a.jsx"), "#include \"b.jsx\"\nfunction pad(n) { return '0' + n; }\nvar VERSION = 1;\nvar Lib = Lib || {};\n").unwrap();
+        fs::write(dir.join("lib/b.jsx"), "#include \"a.jsx\"\nfunction pad(n) {\n    return \"00\" + n;\n}\nvar VERSION = 1;\nvar Lib = Lib || {};\nfunction log(m) {}\n").unwrap();
+        let main = dir.join("main.jsx");
+        let graph = IncludeGraph::resolve(main.to_str().unwrap(), "#include \"lib/a.jsx\"\n#include \"missing.jsx\"\nfunction log(message) { $.writeln(message); }\n");
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<&str> = graph.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["lib/b.jsx", "lib/a.jsx", main.to_str().unwrap()]);
+        assert_eq!(graph.unresolved.len(), 1);
+
+        let errors = validate_duplicate_globals(&graph);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].get_message(), "Function 'pad' is also defined in lib/b.jsx with a different body");
+        assert_eq!(errors[0].get_context().related[0].line, 2);
+        assert_eq!(errors[1].get_message(), "Function 'log' is also defined in lib/b.jsx with a different body");
+    }
+}
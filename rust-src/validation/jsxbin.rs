@@ -0,0 +1,70 @@
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+
+/// Every compiled ExtendScript file starts with this marker, e.g.
+/// `@JSXBIN@ES@2.0@MyBbyBn0AB...`
+pub const JSXBIN_HEADER: &str = "@JSXBIN@";
+
+/// Whether `content` is compiled `.jsxbin` rather than script source. Also
+/// matches jsxbin embedded with `eval("@JSXBIN@...")`, which is how
+/// compiled code is usually shipped inside a `.jsx`.
+pub fn is_jsxbin(content: &str) -> bool {
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    content.starts_with(JSXBIN_HEADER)
+        || content.starts_with(&format!("eval(\"{}", JSXBIN_HEADER))
+        || content.starts_with(&format!("eval('{}", JSXBIN_HEADER))
+}
+
+/// Format version from the header, e.g. `2.0`
+pub fn jsxbin_version(content: &str) -> Option<&str> {
+    let start = content.find(JSXBIN_HEADER)? + JSXBIN_HEADER.len();
+    let mut fields = content[start..].split('@');
+    match (fields.next(), fields.next()) {
+        (Some("ES"), Some(version)) if !version.is_empty() => Some(version),
+        _ => None,
+    }
+}
+
+/// The single diagnostic reported for a compiled file, whose contents
+/// cannot be validated. An error unless jsxbin is allowed, in which case
+/// the file is passed through with an informational note.
+pub fn jsxbin_diagnostic(content: &str, file_path: &str, allowed: bool) -> ValidatorError {
+    let version = jsxbin_version(content).map_or(String::new(), |v| format!(" (version {})", v));
+    let (message, severity) = if allowed {
+        (format!("Skipped compiled JSXBIN file{}", version), ErrorSeverity::Info)
+    } else {
+        (format!("Compiled JSXBIN file{} cannot be validated", version), ErrorSeverity::Error)
+    };
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(1)
+        .column(1)
+        .suggestion(Some(
+            "Validate the .jsx source the file was exported from, or pass --allow-jsxbin to skip compiled files".to_string(),
+        ))
+        .rule("jsxbin")
+        .build();
+
+    ValidatorError::Script {
+        message,
+        context,
+        severity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_jsxbin() {
+        let compiled = "@JSXBIN@ES@2.0@MyBbyBn0ABJAnABjzFjBjMjFjSjUBfRBFeFjIjFjMjMjPff0DzACByB";
+        assert!(is_jsxbin(compiled));
+        assert!(is_jsxbin(&format!("\u{feff}eval(\"{}\");", compiled)));
+        assert!(!is_jsxbin("// @JSXBIN@ in a comment\nalert(1);"));
+        assert_eq!(jsxbin_version(compiled), Some("2.0"));
+
+        let error = jsxbin_diagnostic(compiled, "tool.jsxbin", false);
+        assert_eq!(error.get_message(), "Compiled JSXBIN file (version 2.0) cannot be validated");
+        assert_eq!(jsxbin_diagnostic(compiled, "tool.jsxbin", true).severity(), ErrorSeverity::Info);
+    }
+}
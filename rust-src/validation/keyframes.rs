@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use super::rules::{ValidationRule, PropertyValueType};
+use super::property::validate_property_value;
+use crate::api::interpolation::{InterpolationValidator, KeyframeInterpolationType, KeyframeTemporal};
+
+/// One keyframe as generated by a pipeline, e.g. from
+/// `{"time": 1.0, "value": [960, 540], "outInterpolation": "BEZIER", "outEase": [{"speed": 0, "influence": 33}]}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Keyframe {
+    pub time: f64,
+    pub value: Value,
+    #[serde(default)]
+    pub in_interpolation: Option<KeyframeInterpolationType>,
+    #[serde(default)]
+    pub out_interpolation: Option<KeyframeInterpolationType>,
+    #[serde(default)]
+    pub in_ease: Vec<KeyframeTemporal>,
+    #[serde(default)]
+    pub out_ease: Vec<KeyframeTemporal>,
+}
+
+/// A problem with one keyframe of a bulk import
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyframeIssue {
+    /// Index into the keyframe slice
+    pub index: usize,
+    pub message: String,
+}
+
+impl Keyframe {
+    pub fn new(time: f64, value: Value) -> Self {
+        Self {
+            time,
+            value,
+            in_interpolation: None,
+            out_interpolation: None,
+            in_ease: Vec::new(),
+            out_ease: Vec::new(),
+        }
+    }
+
+    pub fn with_interpolation(mut self, in_type: KeyframeInterpolationType, out_type: KeyframeInterpolationType) -> Self {
+        self.in_interpolation = Some(in_type);
+        self.out_interpolation = Some(out_type);
+        self
+    }
+
+    pub fn with_ease(mut self, in_ease: Vec<KeyframeTemporal>, out_ease: Vec<KeyframeTemporal>) -> Self {
+        self.in_ease = in_ease;
+        self.out_ease = out_ease;
+        self
+    }
+}
+
+/// Parses a JSON array of keyframes
+pub fn keyframes_from_json(content: &str) -> Result<Vec<Keyframe>, String> {
+    serde_json::from_str(content).map_err(|e| e.to_string())
+}
+
+/// Number of `KeyframeEase` objects `setTemporalEaseAtKey` expects: one per
+/// dimension for 2D and 3D properties, one for spatial and all other types
+pub(crate) fn ease_dimensions(property_type: &PropertyValueType) -> usize {
+    match property_type {
+        PropertyValueType::TwoD => 2,
+        PropertyValueType::ThreeD => 3,
+        _ => 1,
+    }
+}
+
+/// Validates keyframe data before it is turned into `setValueAtTime` /
+/// `setInterpolationTypeAtKey` / `setTemporalEaseAtKey` calls.
+///
+/// Checks that times strictly increase, that every value fits the property
+/// type, that interpolation types are allowed for the property, and that
+/// eases have the right number of dimensions, an influence between 0.1 and
+/// 100, and (on spatial properties, where speed is a magnitude) no negative
+/// speed. All problems are reported, not just the first.
+pub fn validate_keyframe_data(property_type: &PropertyValueType, keyframes: &[Keyframe]) -> Result<(), Vec<KeyframeIssue>> {
+    let mut issues = Vec::new();
+    let rule = ValidationRule::simple(property_type.clone());
+    let spatial = matches!(property_type, PropertyValueType::TwoDSpatial | PropertyValueType::ThreeDSpatial);
+    let mut push = |index: usize, message: String| issues.push(KeyframeIssue { index, message });
+
+    for (index, keyframe) in keyframes.iter().enumerate() {
+        if !keyframe.time.is_finite() {
+            push(index, format!("Time {} is not a finite number", keyframe.time));
+        } else if let Some(previous) = index.checked_sub(1).map(|i| keyframes[i].time) {
+            if keyframe.time <= previous {
+                push(index, format!("Time {} does not come after the previous keyframe at {}", keyframe.time, previous));
+            }
+        }
+
+        if let Err(e) = validate_property_value(&keyframe.value, &rule) {
+            push(index, format!("Value {}: {}", keyframe.value, e));
+        }
+
+        for interpolation in keyframe.in_interpolation.iter().chain(keyframe.out_interpolation.iter()) {
+            if let Err(e) = InterpolationValidator::validate_interpolation_type(property_type, interpolation) {
+                push(index, e);
+            }
+        }
+
+        for (side, eases) in [("In", &keyframe.in_ease), ("Out", &keyframe.out_ease)] {
+            if eases.is_empty() {
+                continue;
+            }
+            let expected = ease_dimensions(property_type);
+            if eases.len() != expected {
+                push(index, format!("{} ease has {} dimension(s), this property needs {}", side, eases.len(), expected));
+            }
+            for ease in eases {
+                if let Err(e) = InterpolationValidator::validate_temporal_ease(property_type, ease) {
+                    push(index, format!("{} ease: {}", side, e));
+                } else if ease.influence < 0.1 {
+                    push(index, format!("{} ease: influence {} is below the minimum of 0.1", side, ease.influence));
+                }
+                if spatial && ease.speed < 0.0 {
+                    push(index, format!("{} ease: speed {} must not be negative on a spatial property", side, ease.speed));
+                }
+            }
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_position_keyframes_from_json() {
+        let keyframes = keyframes_from_json(r#"[
+            {"time": 0, "value": [0, 540], "outInterpolation": "BEZIER", "outEase": [{"speed": 0, "influence": 33}]},
+            {"time": 1, "value": [1920, 540], "inInterpolation": "LINEAR"}
+        ]"#).unwrap();
+        assert!(validate_keyframe_data(&PropertyValueType::TwoDSpatial, &keyframes).is_ok());
+    }
+
+    #[test]
+    fn test_reports_every_issue() {
+        let keyframes = vec![
+            Keyframe::new(1.0, json!(50)),
+            Keyframe::new(0.5, json!([1, 2])),
+            Keyframe::new(2.0, json!(75)).with_ease(
+                vec![KeyframeTemporal::new(-20.0, 150.0), KeyframeTemporal::new(0.0, 33.0)],
+                vec![],
+            ),
+        ];
+        let issues = validate_keyframe_data(&PropertyValueType::OneD, &keyframes).unwrap_err();
+        let indices: Vec<usize> = issues.iter().map(|i| i.index).collect();
+        assert_eq!(indices, vec![1, 1, 2, 2]);
+        assert!(issues[0].message.starts_with("Time 0.5 does not come after"));
+        assert_eq!(issues[2].message, "In ease has 2 dimension(s), this property needs 1");
+    }
+
+    #[test]
+    fn test_spatial_speed_and_hold_only_types() {
+        let position = vec![Keyframe::new(0.0, json!([0, 0, 0])).with_ease(vec![KeyframeTemporal::new(-5.0, 50.0)], vec![])];
+        assert_eq!(validate_keyframe_data(&PropertyValueType::ThreeDSpatial, &position).unwrap_err().len(), 1);
+
+        let source_text = vec![Keyframe::new(0.0, json!({"text": "Hi"}))
+            .with_interpolation(KeyframeInterpolationType::Linear, KeyframeInterpolationType::Hold)];
+        let issues = validate_keyframe_data(&PropertyValueType::TextDocument, &source_text).unwrap_err();
+        assert!(issues.iter().any(|i| i.message == "TextDocument properties only support Hold interpolation"));
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::inference::infer_variable_types;
+use super::script::lexer::{significant_tokens, Token};
+
+/// Classes whose `remove()` deletes something on disk and leaves the object usable
+const REUSABLE_AFTER_REMOVE: &[&str] = &["File", "Folder"];
+
+/// Methods that close the project or delete items from it, invalidating
+/// every project object fetched before the call
+const PROJECT_RESETS: &[&str] = &[
+    "newProject", "open", "close", "reduceProject", "removeUnusedFootage", "consolidateFootage",
+];
+
+/// A `remove()` call and the blocks enclosing it, outermost first
+struct Removal {
+    line: usize,
+    blocks: Vec<usize>,
+    reported: bool,
+}
+
+/// Flags variables used after `x.remove()` and objects removed twice.
+///
+/// A removal only counts for code it always runs before: later statements of
+/// its own block and the blocks nested in them. Removals in a branch or loop
+/// body the use is outside of, or in a brace-less `if`, are ignored, as are
+/// `isValid()` checks. Assigning the variable again forgets the removal.
+pub fn validate_removed_objects(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let variable_types = infer_variable_types(script);
+    let mut removed: HashMap<&str, Removal> = HashMap::new();
+    let mut blocks = vec![0];
+    let mut next_block = 1;
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_punct("{") {
+            blocks.push(next_block);
+            next_block += 1;
+            continue;
+        }
+        if token.is_punct("}") {
+            if blocks.len() > 1 {
+                blocks.pop();
+            }
+            continue;
+        }
+        if !token.is_identifier() || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+        let name = token.text.as_str();
+        if tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+            removed.remove(name);
+            continue;
+        }
+
+        let removes = member_call(&tokens, i, "remove");
+        if let Some(removal) = removed.get_mut(name).filter(|r| blocks.starts_with(&r.blocks)) {
+            if removes {
+                errors.push(lifecycle_error(
+                    script, file_path, token,
+                    format!("'{}' is removed twice; {}.remove() on line {} already removed it", name, name, removal.line),
+                    "Remove the second call, or guard it with isValid()".to_string(),
+                    "duplicate-remove",
+                    ErrorSeverity::Warning,
+                ));
+            } else if !member_call(&tokens, i, "isValid") && !removal.reported {
+                removal.reported = true;
+                errors.push(lifecycle_error(
+                    script, file_path, token,
+                    format!("'{}' is used after {}.remove() on line {}", name, name, removal.line),
+                    "Removed objects are invalid; read what you need before calling remove(), or fetch the object again".to_string(),
+                    "use-after-remove",
+                    ErrorSeverity::Error,
+                ));
+            }
+            continue;
+        }
+
+        let reusable = variable_types.get(name).is_some_and(|class| REUSABLE_AFTER_REMOVE.contains(&class.as_str()));
+        if removes && !reusable && !is_conditional(&tokens, i) {
+            removed.insert(name, Removal { line: token.line, blocks: blocks.clone(), reported: false });
+        }
+    }
+
+    errors
+}
+
+/// A variable holding a project object, and the variable it was fetched
+/// from (`comp` for `var layer = comp.layer(1)`)
+struct Reference<'a> {
+    origin: Option<&'a str>,
+}
+
+/// The operation that invalidated a reference and the blocks enclosing it
+struct Invalidation {
+    cause: String,
+    line: usize,
+    blocks: Vec<usize>,
+    reported: bool,
+}
+
+/// Flags project objects used after an operation that may have invalidated
+/// them: `app.newProject()`, `app.open()`, `app.project.close()` and the
+/// project clean-ups that delete items, or `remove()` on the object they
+/// were fetched from (a layer of a removed comp). Like
+/// [`validate_removed_objects`], an operation only counts for code it always
+/// runs before. Passing the variable to `isValid()` or assigning it again
+/// settles it.
+pub fn validate_stale_references(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let variable_types = infer_variable_types(script);
+    let mut references: HashMap<&str, Reference> = HashMap::new();
+    let mut stale: HashMap<&str, Invalidation> = HashMap::new();
+    let mut blocks = vec![0];
+    let mut next_block = 1;
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_punct("{") {
+            blocks.push(next_block);
+            next_block += 1;
+            continue;
+        }
+        if token.is_punct("}") {
+            if blocks.len() > 1 {
+                blocks.pop();
+            }
+            continue;
+        }
+        if !token.is_identifier() {
+            continue;
+        }
+        if i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?.")) {
+            if let Some(cause) = project_reset(&tokens, i) {
+                let start = chain_start(&tokens, i);
+                if !is_conditional(&tokens, start) {
+                    for name in references.keys() {
+                        stale.insert(*name, Invalidation { cause: cause.clone(), line: token.line, blocks: blocks.clone(), reported: false });
+                    }
+                }
+            }
+            continue;
+        }
+        let name = token.text.as_str();
+        if tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+            stale.remove(name);
+            references.remove(name);
+            let is_project_object = variable_types.get(name)
+                .is_some_and(|class| !REUSABLE_AFTER_REMOVE.contains(&class.as_str()));
+            if is_project_object {
+                let origin = tokens.get(i + 2)
+                    .filter(|t| t.is_identifier() && t.text != "app" && tokens.get(i + 3).is_some_and(|t| t.is_punct(".")))
+                    .map(|t| t.text.as_str());
+                references.insert(name, Reference { origin });
+            }
+            continue;
+        }
+        if i >= 2 && tokens[i - 1].is_punct("(") && tokens[i - 2].text == "isValid" {
+            stale.remove(name);
+            continue;
+        }
+
+        if member_call(&tokens, i, "remove") && !is_conditional(&tokens, i) {
+            let cause = format!("{}.remove()", name);
+            for dependent in dependents(&references, name) {
+                stale.insert(dependent, Invalidation { cause: cause.clone(), line: token.line, blocks: blocks.clone(), reported: false });
+            }
+        }
+        if let Some(invalidation) = stale.get_mut(name).filter(|s| !s.reported && blocks.starts_with(&s.blocks)) {
+            invalidation.reported = true;
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(token.line)
+                .column(token.column)
+                .length(token.text.chars().count())
+                .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+                .suggestion(Some(format!(
+                    "Check isValid({}) before using it, or fetch it again after {}", name, invalidation.cause
+                )))
+                .rule("stale-reference")
+                .arg("name", name)
+                .build();
+            errors.push(ValidatorError::Script {
+                message: format!("'{}' may be invalid after {} on line {}", name, invalidation.cause, invalidation.line),
+                context,
+                severity: ErrorSeverity::Warning,
+            });
+        }
+    }
+
+    errors
+}
+
+/// `app.newProject()` or `app.project.close(...)` when the member at `i` is
+/// a method that resets the project, as written without arguments
+fn project_reset(tokens: &[Token], i: usize) -> Option<String> {
+    let method = tokens[i].text.as_str();
+    if !PROJECT_RESETS.contains(&method) || !tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+        return None;
+    }
+    let receiver: Vec<&str> = tokens[chain_start(tokens, i)..i - 1].iter().map(|t| t.text.as_str()).collect();
+    let on_project = match method {
+        "newProject" | "open" => receiver == ["app"],
+        _ => receiver.last() == Some(&"project"),
+    };
+    on_project.then(|| format!("{}.{}()", receiver.concat(), method))
+}
+
+/// Index of the first identifier of the member chain ending at `i`
+fn chain_start(tokens: &[Token], i: usize) -> usize {
+    let mut start = i;
+    while start >= 2 && (tokens[start - 1].is_punct(".") || tokens[start - 1].is_punct("?.")) && tokens[start - 2].is_identifier() {
+        start -= 2;
+    }
+    start
+}
+
+/// References fetched from `name`, directly or through other references
+fn dependents<'a>(references: &HashMap<&'a str, Reference<'a>>, name: &str) -> Vec<&'a str> {
+    let mut found: Vec<&str> = Vec::new();
+    let mut queue = vec![name.to_string()];
+    while let Some(current) = queue.pop() {
+        for (dependent, reference) in references {
+            if reference.origin == Some(current.as_str()) && !found.contains(dependent) {
+                found.push(*dependent);
+                queue.push(dependent.to_string());
+            }
+        }
+    }
+    found
+}
+
+/// Whether the tokens at `i` are `name.method()`
+fn member_call(tokens: &[Token], i: usize, method: &str) -> bool {
+    matches!(tokens.get(i + 1..i + 5), Some([dot, m, open, close])
+        if dot.is_punct(".") && m.text == method && open.is_punct("(") && close.is_punct(")"))
+}
+
+/// Whether the statement starting at `i` is the brace-less body of an
+/// `if`, `else` or loop
+fn is_conditional(tokens: &[Token], i: usize) -> bool {
+    i > 0 && (tokens[i - 1].is_punct(")") || tokens[i - 1].is_keyword("else") || tokens[i - 1].is_keyword("do"))
+}
+
+fn lifecycle_error(
+    script: &str,
+    file_path: &str,
+    token: &Token,
+    message: String,
+    suggestion: String,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule(rule)
+        .build();
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_use_after_remove() {
+        let script = r#"var layer = comp.layer(1);
+layer.remove();
+layer.name = "gone";
+layer.enabled = false;
+if (layer.isValid()) {}
+layer.remove();
+var solid = comp.layers.addSolid([0, 0, 0], "BG", 100, 100, 1);
+if (cleanup) { solid.remove(); }
+solid.name = "kept";
+if (cleanup) solid.remove();
+solid.opacity = 50;
+layer = comp.layer(2);
+layer.name = "new";
+var log = new File("~/log.txt");
+log.remove();
+log.open("w");
+"#;
+        let errors = validate_removed_objects(script, "a.jsx");
+        let found: Vec<(usize, &str)> = errors.iter().map(|e| (e.get_context().line.unwrap(), e.get_message())).collect();
+        assert_eq!(found, vec![
+            (3, "'layer' is used after layer.remove() on line 2"),
+            (6, "'layer' is removed twice; layer.remove() on line 2 already removed it"),
+        ]);
+        assert_eq!(errors[1].get_severity(), ErrorSeverity::Warning);
+    }
+
+    #[test]
+    fn test_stale_references() {
+        let script = r#"var comp = app.project.activeItem;
+var layer = comp.layer(1);
+var opacity = layer.property("Opacity");
+var log = new File("~/log.txt");
+comp.remove();
+opacity.setValue(50);
+opacity.setValue(60);
+app.newProject();
+if (isValid(layer)) { layer.name = "x"; }
+log.open("w");
+comp = app.project.activeItem;
+comp.name = "fresh";
+var proj = app.project;
+if (closing) app.project.close(CloseOptions.DO_NOT_SAVE_CHANGES);
+proj.save();
+"#;
+        let errors = validate_stale_references(script, "a.jsx");
+        let found: Vec<(usize, &str)> = errors.iter().map(|e| (e.get_context().line.unwrap(), e.get_message())).collect();
+        assert_eq!(found, vec![(6, "'opacity' may be invalid after comp.remove() on line 5")]);
+        assert_eq!(errors[0].get_context().suggestion(), Some("Check isValid(opacity) before using it, or fetch it again after comp.remove()"));
+
+        let reopened = "var item = app.project.item(1);\napp.open(new File(path));\nitem.name = \"x\";\n";
+        let errors = validate_stale_references(reopened, "b.jsx");
+        assert_eq!(errors[0].get_message(), "'item' may be invalid after app.open() on line 2");
+    }
+}
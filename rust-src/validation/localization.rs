@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use crate::api::objects::effects::EffectMatchNames;
+use crate::data::menu_commands::find_menu_command;
+
+lazy_static! {
+    static ref MENU_LOOKUP_RE: Regex = Regex::new(r#"\bfindMenuCommandId\s*\(\s*(?:"([^"\n]+)"|'([^'\n]+)')\s*\)"#).unwrap();
+    static ref EFFECT_NAME_RE: Regex = Regex::new(r#"\.(?:addProperty|effect)\s*\(\s*(?:"([^"\n]+)"|'([^'\n]+)')\s*\)"#).unwrap();
+    /// English display name to match name for built-in effects
+    static ref EFFECT_DISPLAY_NAMES: HashMap<String, &'static str> = EffectMatchNames::get_all_effects_with_metadata()
+        .into_iter()
+        .map(|(match_name, metadata)| (metadata.display_name, match_name))
+        .collect();
+}
+
+/// Flags English UI strings that stop matching on localized installs:
+/// menu command names passed to `app.findMenuCommandId` and effect display
+/// names passed to `addProperty` or `effect`
+pub fn validate_localized_strings(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let mut errors = Vec::new();
+
+    for cap in MENU_LOOKUP_RE.captures_iter(script) {
+        let name = cap.get(1).or_else(|| cap.get(2)).unwrap().as_str();
+        let command = match find_menu_command(name) {
+            Some(command) => command,
+            None => continue,
+        };
+        let suggestion = match (command.id, command.api) {
+            (_, Some(api)) => format!("Call the scripting API instead: {}", api),
+            (Some(id), None) => format!("Use the command ID, which is the same in every language: app.executeCommand({})", id),
+            (None, None) => "Look the command ID up once on an English install and pass it to app.executeCommand(id)".to_string(),
+        };
+        errors.push(localization_error(
+            script,
+            file_path,
+            cap.get(0).unwrap().start(),
+            format!("Menu command '{}' is only found by that name on English installs", name),
+            suggestion,
+            "locale-menu-command",
+        ));
+    }
+
+    for cap in EFFECT_NAME_RE.captures_iter(script) {
+        let name = cap.get(1).or_else(|| cap.get(2)).unwrap().as_str();
+        let match_name = match EFFECT_DISPLAY_NAMES.get(name) {
+            Some(match_name) => *match_name,
+            None => continue,
+        };
+        errors.push(localization_error(
+            script,
+            file_path,
+            cap.get(0).unwrap().start(),
+            format!("Effect display name '{}' is translated on non-English installs", name),
+            format!("Use the match name, which never changes: \"{}\"", match_name),
+            "locale-effect-name",
+        ));
+    }
+
+    errors.sort_by_key(|e| e.get_context().line);
+    errors
+}
+
+fn localization_error(
+    script: &str,
+    file_path: &str,
+    pos: usize,
+    message: String,
+    suggestion: String,
+    rule: &str,
+) -> ValidatorError {
+    let line = script[..pos].matches('\n').count() + 1;
+    let line_start = script[..pos].rfind('\n').map_or(0, |i| i + 1);
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(script[line_start..pos].chars().count() + 1)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule(rule)
+        .build();
+
+    ValidatorError::Script {
+        message,
+        context,
+        severity: ErrorSeverity::Warning,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_english_ui_strings() {
+        let script = r#"var id = app.findMenuCommandId("Convert Audio to Keyframes");
+app.executeCommand(app.findMenuCommandId("Remove Unused Footage"));
+app.findMenuCommandId("My Script Panel.jsx");
+layer.property("ADBE Effect Parade").addProperty("Gaussian Blur");
+layer.property("ADBE Effect Parade").addProperty("ADBE Gaussian Blur 2");
+"#;
+        let errors = validate_localized_strings(script, "menu.jsx");
+        let found: Vec<(Option<&str>, Option<usize>)> = errors.iter()
+            .map(|e| (e.rule_id(), e.get_context().line))
+            .collect();
+        assert_eq!(found, vec![
+            (Some("locale-menu-command"), Some(1)),
+            (Some("locale-menu-command"), Some(2)),
+            (Some("locale-effect-name"), Some(4)),
+        ]);
+        assert_eq!(
+            errors[0].get_context().suggestion(),
+            Some("Use the command ID, which is the same in every language: app.executeCommand(5015)")
+        );
+        assert_eq!(
+            errors[2].get_context().suggestion(),
+            Some("Use the match name, which never changes: \"ADBE Gaussian Blur 2\"")
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::HashSet;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{matching_bracket, Token, TokenKind};
+use super::script::scope::{analyze_scopes, ScopeAnalysis};
+
+const ASSIGNMENT_OPERATORS: &[&str] = &[
+    "=", "+=", "-=", "*=", "/=", "%=", "<<=", ">>=", ">>>=", "&=", "|=", "^=", "++", "--",
+];
+
+/// A `while`, `do ... while` or `for` loop, as token indices
+#[derive(Debug, Clone, Copy)]
+struct Loop {
+    keyword: usize,
+    /// Condition tokens, end exclusive; empty for `for (;;)`
+    condition: (usize, usize),
+    /// Update clause of a `for` loop, end exclusive
+    update: (usize, usize),
+    /// First and last token of the body
+    body: (usize, usize),
+}
+
+/// What a loop condition depends on
+enum Condition<'a> {
+    Always,
+    /// Plain variables, read without calls or member accesses
+    Variables(Vec<&'a str>),
+    Unknown,
+}
+
+/// Reports loops that never end. ExtendScript runs on After Effects' only
+/// thread, so such a loop freezes the application until it is force quit.
+///
+/// A loop is reported when nothing in its body breaks out of it (`break`,
+/// `return` or `throw`) and either its condition is always true, or every
+/// variable the condition reads stays unchanged. A variable counts as
+/// unchanged when the loop never assigns it and nothing the loop calls can:
+/// locals of the enclosing function can only be changed by closures that
+/// assign them, other variables by any function call.
+pub fn validate_infinite_loops(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let loops = find_loops(tokens);
+    let mut breakables: Vec<(usize, usize)> = loops.iter().map(|l| l.body).collect();
+    breakables.extend(switch_bodies(tokens));
+    let mut errors = Vec::new();
+
+    for lp in &loops {
+        let scope = analysis.token_scopes[lp.keyword];
+        if has_exit(&analysis, lp.body, scope, &breakables) {
+            continue;
+        }
+        let message = match classify(&tokens[lp.condition.0..lp.condition.1]) {
+            Condition::Always => "Infinite loop: the condition is always true and nothing in the body breaks out of it".to_string(),
+            Condition::Variables(names) if !names.iter().any(|name| may_change(&analysis, name, lp, scope)) => {
+                let quoted: Vec<String> = names.iter().map(|name| format!("'{}'", name)).collect();
+                format!(
+                    "Infinite loop: {} never {} inside the loop and nothing in the body breaks out of it",
+                    quoted.join(", "),
+                    if names.len() == 1 { "changes" } else { "change" }
+                )
+            }
+            _ => continue,
+        };
+
+        let keyword = &tokens[lp.keyword];
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(keyword.line)
+            .column(keyword.column)
+            .length(keyword.text.chars().count())
+            .code_snippet(script.lines().nth(keyword.line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some("Add a break, return or throw, or update the condition inside the loop; a loop that never ends freezes After Effects".to_string()))
+            .rule("infinite-loop")
+            .build();
+        errors.push(ValidatorError::Script { message, context, severity: ErrorSeverity::Error });
+    }
+
+    errors
+}
+
+fn find_loops(tokens: &[Token]) -> Vec<Loop> {
+    let mut loops = Vec::new();
+    // `while` keywords that end a `do` loop
+    let mut do_tails = HashSet::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let found = if token.is_keyword("do") {
+            do_loop(tokens, i).map(|(lp, tail)| {
+                do_tails.insert(tail);
+                lp
+            })
+        } else if token.is_keyword("while") && !do_tails.contains(&i) {
+            while_loop(tokens, i)
+        } else if token.is_keyword("for") {
+            for_loop(tokens, i)
+        } else {
+            None
+        };
+        loops.extend(found);
+    }
+    loops
+}
+
+/// A `do` loop and the index of its `while`
+fn do_loop(tokens: &[Token], i: usize) -> Option<(Loop, usize)> {
+    let body_end = statement_end(tokens, i + 1)?;
+    let tail = body_end + 1;
+    if !tokens.get(tail)?.is_keyword("while") {
+        return None;
+    }
+    let close = paren_after(tokens, tail)?;
+    Some((Loop { keyword: i, condition: (tail + 2, close), update: (close, close), body: (i + 1, body_end) }, tail))
+}
+
+fn while_loop(tokens: &[Token], i: usize) -> Option<Loop> {
+    let close = paren_after(tokens, i)?;
+    let body_end = statement_end(tokens, close + 1)?;
+    Some(Loop { keyword: i, condition: (i + 2, close), update: (close, close), body: (close + 1, body_end) })
+}
+
+fn for_loop(tokens: &[Token], i: usize) -> Option<Loop> {
+    let close = paren_after(tokens, i)?;
+    let mut semicolons = Vec::new();
+    let mut depth = 0usize;
+    for (j, token) in tokens.iter().enumerate().take(close).skip(i + 2) {
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            depth += 1;
+        } else if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0 && token.is_punct(";") {
+            semicolons.push(j);
+        }
+    }
+    // `for (key in object)` ends when the keys run out
+    let (first, second) = match semicolons.as_slice() {
+        [first, second] => (*first, *second),
+        _ => return None,
+    };
+    let body_end = statement_end(tokens, close + 1)?;
+    Some(Loop { keyword: i, condition: (first + 1, second), update: (second + 1, close), body: (close + 1, body_end) })
+}
+
+fn switch_bodies(tokens: &[Token]) -> Vec<(usize, usize)> {
+    tokens.iter().enumerate()
+        .filter(|(_, token)| token.is_keyword("switch"))
+        .filter_map(|(i, _)| {
+            let open = paren_after(tokens, i)? + 1;
+            tokens.get(open).filter(|t| t.is_punct("{"))?;
+            matching_bracket(tokens, open).map(|close| (open, close))
+        })
+        .collect()
+}
+
+/// Index of the `)` closing the `(` right after the keyword at `i`
+fn paren_after(tokens: &[Token], i: usize) -> Option<usize> {
+    tokens.get(i + 1).filter(|t| t.is_punct("("))?;
+    matching_bracket(tokens, i + 1)
+}
+
+/// Last token of the statement starting at `start`: a block, or everything
+/// up to the next `;`
+fn statement_end(tokens: &[Token], start: usize) -> Option<usize> {
+    if tokens.get(start)?.is_punct("{") {
+        return matching_bracket(tokens, start);
+    }
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            depth += 1;
+        } else if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            if depth == 0 {
+                return i.checked_sub(1).filter(|&end| end >= start);
+            }
+            depth -= 1;
+        } else if depth == 0 && token.is_punct(";") {
+            return Some(i);
+        }
+    }
+    Some(tokens.len() - 1)
+}
+
+/// Whether the body can leave the loop: a `return` or `throw` of the
+/// loop's own function, a labelled `break`, or a `break` not inside a
+/// nested loop or switch
+fn has_exit(analysis: &ScopeAnalysis, body: (usize, usize), scope: usize, breakables: &[(usize, usize)]) -> bool {
+    let tokens = &analysis.tokens;
+    (body.0..=body.1).any(|j| {
+        let token = &tokens[j];
+        if analysis.token_scopes[j] != scope {
+            return false;
+        }
+        if token.is_keyword("return") || token.is_keyword("throw") {
+            return true;
+        }
+        if !token.is_keyword("break") {
+            return false;
+        }
+        let labelled = tokens.get(j + 1).is_some_and(|t| t.is_identifier() && t.newlines_before == 0);
+        labelled || !breakables.iter().any(|&(start, end)| body.0 < start && start <= j && j <= end)
+    })
+}
+
+fn classify(condition: &[Token]) -> Condition<'_> {
+    let texts: Vec<&str> = condition.iter().map(|t| t.text.as_str()).collect();
+    match texts.as_slice() {
+        [] | ["true"] | ["!", "0"] | ["!", "false"] => return Condition::Always,
+        [number] if condition[0].kind == TokenKind::Number && number.parse::<f64>().is_ok_and(|n| n != 0.0) => {
+            return Condition::Always;
+        }
+        _ => {}
+    }
+
+    let mut names = Vec::new();
+    for (k, token) in condition.iter().enumerate() {
+        let call = token.is_punct("(")
+            && k > 0 && (condition[k - 1].is_identifier() || condition[k - 1].is_punct(")") || condition[k - 1].is_punct("]"));
+        let member = token.is_punct(".") || token.is_punct("?.") || token.is_punct("[");
+        let keyword = token.kind == TokenKind::Keyword && !matches!(token.text.as_str(), "true" | "false" | "null" | "undefined");
+        if call || member || keyword || ASSIGNMENT_OPERATORS.iter().any(|op| token.is_punct(op)) {
+            return Condition::Unknown;
+        }
+        if token.is_identifier() && !names.contains(&token.text.as_str()) {
+            names.push(token.text.as_str());
+        }
+    }
+    if names.is_empty() {
+        Condition::Unknown
+    } else {
+        Condition::Variables(names)
+    }
+}
+
+/// Whether `name` can change while the loop runs
+fn may_change(analysis: &ScopeAnalysis, name: &str, lp: &Loop, scope: usize) -> bool {
+    let tokens = &analysis.tokens;
+    let assigns = |j: usize| tokens[j].is_identifier() && tokens[j].text == name && is_assignment(tokens, j);
+    let mut in_loop = (lp.body.0..=lp.body.1).chain(lp.update.0..lp.update.1);
+    if in_loop.any(assigns) {
+        return true;
+    }
+
+    let local = scope != 0 && analysis.scopes[scope].declared.contains(name);
+    if local {
+        // A closure of the enclosing function may assign it when called
+        (0..tokens.len()).any(|j| analysis.token_scopes[j] != scope && is_nested(analysis, analysis.token_scopes[j], scope) && assigns(j))
+    } else {
+        // Any function the loop calls may assign a global or outer variable
+        (lp.body.0..=lp.body.1).chain(lp.update.0..lp.update.1).any(|j| {
+            tokens[j].is_punct("(")
+                && (tokens[j - 1].is_identifier() || tokens[j - 1].is_punct(")") || tokens[j - 1].is_punct("]"))
+        })
+    }
+}
+
+/// Whether the variable at `j` is assigned, incremented or decremented
+fn is_assignment(tokens: &[Token], j: usize) -> bool {
+    if j > 0 && (tokens[j - 1].is_punct(".") || tokens[j - 1].is_punct("?.")) {
+        return false;
+    }
+    let assigned = tokens.get(j + 1).is_some_and(|next| ASSIGNMENT_OPERATORS.iter().any(|op| next.is_punct(op)));
+    let incremented = j > 0 && (tokens[j - 1].is_punct("++") || tokens[j - 1].is_punct("--"));
+    assigned || incremented
+}
+
+fn is_nested(analysis: &ScopeAnalysis, scope: usize, ancestor: usize) -> bool {
+    let mut current = analysis.scopes[scope].parent;
+    while let Some(index) = current {
+        if index == ancestor {
+            return true;
+        }
+        current = analysis.scopes[index].parent;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loops_that_never_end() {
+        let script = r#"while (true) {
+    $.writeln("tick");
+}
+for (;;) {
+    if (isDone()) break;
+}
+function waitForRender(queue) {
+    var finished = false;
+    while (!finished) {
+        queue.render();
+    }
+    var attempts = 0;
+    while (attempts < 3) {
+        attempts++;
+    }
+    do {
+        for (var i = 0; i < 3; i++) { if (i == 1) break; }
+    } while (true);
+    while (true) {
+        switch (queue.status) { case 1: break; }
+    }
+    while (true) {
+        if (queue.numItems == 0) return;
+    }
+}
+var polling = true;
+while (polling) { poll(); }
+while (true) { var cb = function () { return 1; }; }
+"#;
+        let errors = validate_infinite_loops(script, "loops.jsx");
+        let lines: Vec<usize> = errors.iter().map(|e| e.get_context().line.unwrap()).collect();
+        assert_eq!(lines, vec![1, 9, 16, 19, 28]);
+        assert_eq!(errors[1].get_message(), "Infinite loop: 'finished' never changes inside the loop and nothing in the body breaks out of it");
+        assert!(errors.iter().all(|e| e.get_severity() == ErrorSeverity::Error));
+    }
+}
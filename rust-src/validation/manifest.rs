@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lines::LineIndex;
+
+lazy_static! {
+    static ref COMP_VAR_RE: Regex = Regex::new(r#"\bvar\s+(\w+)\s*=\s*[^;\n]*?\(\s*["']([^"']+)["']\s*\)"#).unwrap();
+    static ref LAYER_LOOKUP_RE: Regex = Regex::new(r#"\b(\w+)\.(?:layer|layers\.byName)\(\s*["']([^"']+)["']\s*\)"#).unwrap();
+    static ref ITEM_NAME_RE: Regex = Regex::new(r#"\b\w*[Ii]tem\w*(?:\(\s*\w+\s*\))?\.name\s*===?\s*["']([^"']+)["']"#).unwrap();
+}
+
+/// Declared structure of an .aep template that scripts are expected to run against
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectManifest {
+    pub comps: Vec<ManifestComp>,
+    /// Names of footage items in the project panel
+    pub footage: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ManifestComp {
+    pub name: String,
+    /// Layer names, top to bottom
+    pub layers: Vec<String>,
+}
+
+impl ProjectManifest {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        Self::from_json(&content)
+            .map_err(|e| format!("Invalid manifest {}: {}", path.as_ref().display(), e))
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        serde_json::from_str(content).map_err(|e| e.to_string())
+    }
+
+    pub fn comp(&self, name: &str) -> Option<&ManifestComp> {
+        self.comps.iter().find(|c| c.name == name)
+    }
+
+    pub fn has_item(&self, name: &str) -> bool {
+        self.comp(name).is_some() || self.footage.iter().any(|f| f == name)
+    }
+}
+
+impl ManifestComp {
+    pub fn has_layer(&self, name: &str) -> bool {
+        self.layers.iter().any(|l| l == name)
+    }
+}
+
+/// Checks the comp, layer and item names a script relies on against a project manifest
+pub fn validate_against_manifest(script: &str, file_path: &str, manifest: &ProjectManifest) -> Vec<ValidatorError> {
+    let mut errors = Vec::new();
+
+    // Variables bound to a comp looked up by one of the manifest's comp names
+    let comp_vars: HashMap<String, String> = COMP_VAR_RE.captures_iter(script)
+        .filter(|cap| manifest.comp(&cap[2]).is_some())
+        .map(|cap| (cap[1].to_string(), cap[2].to_string()))
+        .collect();
+
+    for cap in LAYER_LOOKUP_RE.captures_iter(script) {
+        let layer = &cap[2];
+        let pos = cap.get(0).unwrap().start();
+        let (message, suggestion) = match comp_vars.get(&cap[1]).and_then(|name| manifest.comp(name)) {
+            Some(comp) if !comp.has_layer(layer) => (
+                format!("Comp '{}' in the project manifest has no layer named '{}'", comp.name, layer),
+                closest(layer, &comp.layers),
+            ),
+            Some(_) => continue,
+            None if !manifest.comps.iter().any(|c| c.has_layer(layer)) => {
+                let all_layers: Vec<String> = manifest.comps.iter().flat_map(|c| c.layers.iter().cloned()).collect();
+                (
+                    format!("No comp in the project manifest has a layer named '{}'", layer),
+                    closest(layer, &all_layers),
+                )
+            }
+            None => continue,
+        };
+        errors.push(manifest_error(script, file_path, pos, message, suggestion, "manifest-layer"));
+    }
+
+    for cap in ITEM_NAME_RE.captures_iter(script) {
+        let name = &cap[1];
+        if manifest.has_item(name) {
+            continue;
+        }
+        let pos = cap.get(0).unwrap().start();
+        let mut items: Vec<String> = manifest.comps.iter().map(|c| c.name.clone()).collect();
+        items.extend(manifest.footage.iter().cloned());
+        errors.push(manifest_error(
+            script,
+            file_path,
+            pos,
+            format!("The project manifest has no item named '{}'", name),
+            closest(name, &items),
+            "manifest-item",
+        ));
+    }
+
+    errors
+}
+
+fn closest(name: &str, candidates: &[String]) -> Option<String> {
+    candidates.iter()
+        .map(|c| (strsim::levenshtein(&name.to_lowercase(), &c.to_lowercase()), c))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, c)| (*distance, *c))
+        .map(|(_, c)| format!("Did you mean '{}'?", c))
+}
+
+fn manifest_error(
+    script: &str,
+    file_path: &str,
+    pos: usize,
+    message: String,
+    suggestion: Option<String>,
+    rule: &str,
+) -> ValidatorError {
+    let lines = LineIndex::new(script);
+    let (line, column) = lines.position(script, pos);
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(column)
+        .code_snippet(lines.line_text(script, line).trim().to_string())
+        .suggestion(suggestion)
+        .rule(rule)
+        .build();
+
+    ValidatorError::Script {
+        message,
+        context,
+        severity: ErrorSeverity::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> ProjectManifest {
+        ProjectManifest::from_json(r#"{
+            "comps": [
+                { "name": "Main", "layers": ["Title", "Background"] },
+                { "name": "Lower Third", "layers": ["Name", "Role"] }
+            ],
+            "footage": ["logo.png"]
+        }"#).unwrap()
+    }
+
+    #[test]
+    fn test_missing_layer_in_bound_comp() {
+        let script = r#"
+            var comp = findComp("Main");
+            comp.layer("Title").enabled = true;
+            comp.layer("Titel").enabled = false;
+            comp.layer("Name").enabled = false;
+        "#;
+        let errors = validate_against_manifest(script, "test.jsx", &manifest());
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].get_context().suggestion(), Some("Did you mean 'Title'?"));
+        assert_eq!(errors[0].get_context().line, Some(4));
+    }
+
+    #[test]
+    fn test_unbound_comp_checks_all_layers() {
+        let script = r#"
+            var comp = app.project.activeItem;
+            comp.layers.byName("Role").enabled = true;
+            comp.layers.byName("Logo").enabled = true;
+        "#;
+        let errors = validate_against_manifest(script, "test.jsx", &manifest());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule_id(), Some("manifest-layer"));
+    }
+
+    #[test]
+    fn test_match_at_line_start() {
+        let script = "var comp = findComp(\"Main\");\ncomp.layer(\"Titel\").enabled = false;\n";
+        let errors = validate_against_manifest(script, "test.jsx", &manifest());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].get_context().line, Some(2));
+        assert_eq!(errors[0].get_context().column, Some(1));
+        assert_eq!(errors[0].get_context().code_snippet.as_deref(), Some("comp.layer(\"Titel\").enabled = false;"));
+    }
+
+    #[test]
+    fn test_item_names() {
+        let script = r#"
+            for (var i = 1; i <= app.project.numItems; i++) {
+                var item = app.project.item(i);
+                if (item.name == "logo.png" || item.name === "Intro") {}
+            }
+        "#;
+        let errors = validate_against_manifest(script, "test.jsx", &manifest());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule_id(), Some("manifest-item"));
+    }
+}
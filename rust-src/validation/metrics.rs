@@ -0,0 +1,197 @@
+use serde::Serialize;
+use super::script::callgraph::{CallGraph, CallKind};
+use super::script::lexer::{tokenize, TokenKind};
+use super::script::scope::analyze_scopes;
+
+/// Line counts for a script
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LineCounts {
+    pub total: usize,
+    pub code: usize,
+    pub comment: usize,
+    pub blank: usize,
+}
+
+/// Complexity figures for one function (or the top-level code)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunctionMetrics {
+    pub name: String,
+    pub line: usize,
+    /// McCabe complexity: 1 plus the number of branch points
+    pub cyclomatic_complexity: usize,
+    /// Deepest block nesting inside the function body
+    pub max_nesting: usize,
+}
+
+/// Size and complexity report for a script
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScriptMetrics {
+    pub lines: LineCounts,
+    pub functions: Vec<FunctionMetrics>,
+    pub max_nesting_depth: usize,
+    pub api_calls: usize,
+    pub undo_groups: usize,
+    pub expressions: usize,
+}
+
+impl ScriptMetrics {
+    pub fn analyze(script: &str) -> Self {
+        let analysis = analyze_scopes(script);
+        let tokens = &analysis.tokens;
+
+        // Brace depth after each token
+        let mut depth = 0usize;
+        let depths: Vec<usize> = tokens.iter().map(|token| {
+            if token.is_punct("{") {
+                depth += 1;
+            } else if token.is_punct("}") {
+                depth = depth.saturating_sub(1);
+            }
+            depth
+        }).collect();
+
+        let mut functions: Vec<FunctionMetrics> = (0..analysis.scopes.len()).map(|scope| FunctionMetrics {
+            name: analysis.scope_label(scope),
+            line: analysis.scopes[scope].line,
+            cyclomatic_complexity: 1,
+            max_nesting: 0,
+        }).collect();
+        let mut base_depth: Vec<Option<usize>> = vec![None; analysis.scopes.len()];
+        base_depth[0] = Some(0);
+
+        for (i, token) in tokens.iter().enumerate() {
+            let scope = analysis.token_scopes[i];
+            // The first token of a function scope is its body's opening brace
+            let base = *base_depth[scope].get_or_insert(depths[i]);
+            let metrics = &mut functions[scope];
+            metrics.max_nesting = metrics.max_nesting.max(depths[i].saturating_sub(base));
+
+            let is_branch = match token.kind {
+                TokenKind::Keyword => matches!(token.text.as_str(), "if" | "for" | "while" | "case" | "catch"),
+                TokenKind::Punctuator => matches!(token.text.as_str(), "&&" | "||" | "?"),
+                _ => false,
+            };
+            if is_branch {
+                metrics.cyclomatic_complexity += 1;
+            }
+        }
+
+        let undo_groups = tokens.iter().enumerate()
+            .filter(|(i, t)| t.text == "beginUndoGroup" && tokens.get(i + 1).is_some_and(|n| n.is_punct("(")))
+            .count();
+        let expressions = tokens.iter().enumerate()
+            .filter(|(i, t)| {
+                t.text == "expression"
+                    && *i > 0 && tokens[i - 1].is_punct(".")
+                    && tokens.get(i + 1).is_some_and(|n| n.is_punct("="))
+            })
+            .count();
+        let api_calls = CallGraph::build(script).edges.iter()
+            .filter(|edge| edge.kind == CallKind::Api)
+            .map(|edge| edge.lines.len())
+            .sum();
+
+        Self {
+            lines: count_lines(script),
+            max_nesting_depth: depths.iter().copied().max().unwrap_or(0),
+            functions,
+            api_calls,
+            undo_groups,
+            expressions,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Plain-text table for terminals and review comments
+    pub fn to_table(&self) -> String {
+        let mut output = String::new();
+        output.push_str(&format!(
+            "Lines: {} total, {} code, {} comment, {} blank\n",
+            self.lines.total, self.lines.code, self.lines.comment, self.lines.blank
+        ));
+        output.push_str(&format!(
+            "API calls: {}  Undo groups: {}  Expressions: {}  Max nesting: {}\n\n",
+            self.api_calls, self.undo_groups, self.expressions, self.max_nesting_depth
+        ));
+
+        let width = self.functions.iter().map(|f| f.name.len()).max().unwrap_or(0).max("Function".len());
+        output.push_str(&format!("{:<width$}  {:>5}  {:>10}  {:>7}\n", "Function", "Line", "Complexity", "Nesting", width = width));
+        for function in &self.functions {
+            output.push_str(&format!(
+                "{:<width$}  {:>5}  {:>10}  {:>7}\n",
+                function.name, function.line, function.cyclomatic_complexity, function.max_nesting,
+                width = width
+            ));
+        }
+        output
+    }
+}
+
+fn count_lines(script: &str) -> LineCounts {
+    let mut counts = LineCounts { total: script.lines().count(), ..LineCounts::default() };
+    let mut has_code = vec![false; counts.total];
+    let mut has_comment = vec![false; counts.total];
+
+    for token in tokenize(script) {
+        let span = token.text.matches('\n').count();
+        let target = if matches!(token.kind, TokenKind::LineComment | TokenKind::BlockComment) {
+            &mut has_comment
+        } else {
+            &mut has_code
+        };
+        for line in token.line..=token.line + span {
+            if let Some(flag) = target.get_mut(line - 1) {
+                *flag = true;
+            }
+        }
+    }
+
+    for line in 0..counts.total {
+        if has_code[line] {
+            counts.code += 1;
+        } else if has_comment[line] {
+            counts.comment += 1;
+        } else {
+            counts.blank += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_complexity_and_nesting() {
+        let script = r#"
+function process(comp) {
+    for (var i = 1; i <= comp.numLayers; i++) {
+        if (comp.layer(i).enabled && comp.layer(i).hasVideo) {
+            comp.layer(i).label = 2;
+        }
+    }
+}
+process(app.project.activeItem);
+"#;
+        let metrics = ScriptMetrics::analyze(script);
+        let process = metrics.functions.iter().find(|f| f.name == "process").unwrap();
+        assert_eq!(process.cyclomatic_complexity, 4);
+        assert_eq!(process.max_nesting, 2);
+        assert_eq!(metrics.max_nesting_depth, 3);
+    }
+
+    #[test]
+    fn test_counts() {
+        let script = "// header\n\napp.beginUndoGroup(\"x\");\nlayer.transform.opacity.expression = \"50\";\n/* a\n b */\napp.endUndoGroup();\n";
+        let metrics = ScriptMetrics::analyze(script);
+        assert_eq!(metrics.lines, LineCounts { total: 7, code: 3, comment: 3, blank: 1 });
+        assert_eq!(metrics.undo_groups, 1);
+        assert_eq!(metrics.expressions, 1);
+        assert_eq!(metrics.api_calls, 2);
+        assert!(metrics.to_table().contains("<global>"));
+    }
+}
@@ -0,0 +1,223 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lines::LineIndex;
+
+lazy_static! {
+    /// `receiver.trackMatteType = value` where the receiver is a dotted path,
+    /// optionally indexed like `comp.layer(3)`
+    static ref TRACK_MATTE_ASSIGN_RE: Regex = Regex::new(
+        r"([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*|\(\s*\d+\s*\)|\[\s*\d+\s*\])*)\.trackMatteType\s*=\s*([^=;\n][^;\n]*)"
+    ).unwrap();
+    static ref LAYER_INDEX_RE: Regex = Regex::new(r"^(.+)\.layer\(\s*(\d+)\s*\)$").unwrap();
+    static ref LAYER_ADD_RE: Regex = Regex::new(r"^([\w$.]+)\.layers\.add\w*\s*\(").unwrap();
+    static ref MOVE_RE: Regex = Regex::new(r"\.(?:moveBefore|moveAfter|moveToBeginning|moveToEnd|moveTo)\s*\(").unwrap();
+}
+
+/// A legacy `trackMatteType` assignment and its AE 23+ replacement
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackMatteMigration {
+    pub line: usize,
+    pub column: usize,
+    /// Byte range of the assignment, excluding the trailing semicolon
+    pub start: usize,
+    pub end: usize,
+    pub receiver: String,
+    pub matte_type: String,
+    /// Rewritten statement, or `None` when the matte layer cannot be inferred
+    pub replacement: Option<String>,
+    /// Why the assignment needs manual review
+    pub reason: Option<String>,
+}
+
+/// Finds assignments to `trackMatteType`.
+///
+/// Before AE 23 the matte was always the layer directly above. The matte
+/// layer is inferred when the receiver is `comp.layer(n)` (or a variable
+/// holding one), or a variable created with `comp.layers.add...()` followed
+/// by exactly one more layer created in the same comp, which then sits
+/// directly above it.
+pub fn find_track_matte_migrations(script: &str) -> Vec<TrackMatteMigration> {
+    let lines = LineIndex::new(script);
+    TRACK_MATTE_ASSIGN_RE.captures_iter(script).map(|cap| {
+        let whole = cap.get(0).unwrap();
+        let receiver = cap[1].to_string();
+        let matte_type = cap[2].trim().to_string();
+        let (line, column) = lines.position(script, whole.start());
+        let end = whole.start() + whole.as_str().trim_end().len();
+
+        let (replacement, reason) = if is_no_track_matte(&matte_type) {
+            (Some(format!("{}.removeTrackMatte()", receiver)), None)
+        } else {
+            match infer_matte_layer(script, &receiver, whole.start()) {
+                Ok(matte) => (Some(format!("{}.setTrackMatte({}, {})", receiver, matte, matte_type)), None),
+                Err(reason) => (None, Some(reason)),
+            }
+        };
+
+        TrackMatteMigration {
+            line,
+            column,
+            start: whole.start(),
+            end,
+            receiver,
+            matte_type,
+            replacement,
+            reason,
+        }
+    }).collect()
+}
+
+/// Rewrites every migratable assignment and returns the new script together
+/// with the assignments left for manual review
+pub fn migrate_track_matte(script: &str) -> (String, Vec<TrackMatteMigration>) {
+    let mut output = script.to_string();
+    let mut manual = Vec::new();
+
+    for migration in find_track_matte_migrations(script).into_iter().rev() {
+        match &migration.replacement {
+            Some(replacement) => output.replace_range(migration.start..migration.end, replacement),
+            None => manual.push(migration),
+        }
+    }
+
+    manual.reverse();
+    (output, manual)
+}
+
+/// Flags `trackMatteType` assignments, which AE 23+ replaced with `setTrackMatte`
+pub fn validate_track_matte_migration(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let lines = LineIndex::new(script);
+    find_track_matte_migrations(script).into_iter().map(|migration| {
+        let suggestion = match (&migration.replacement, &migration.reason) {
+            (Some(replacement), _) => format!("Replace with: {}", replacement),
+            (None, Some(reason)) => format!(
+                "Manual review needed ({}). The legacy matte was the layer directly above: {}.setTrackMatte({}.containingComp.layer({}.index - 1), {})",
+                reason, migration.receiver, migration.receiver, migration.receiver, migration.matte_type
+            ),
+            (None, None) => "Use setTrackMatte(matteLayer, type)".to_string(),
+        };
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(migration.line)
+            .column(migration.column)
+            .length(migration.end - migration.start)
+            .code_snippet(lines.line_text(script, migration.line).trim().to_string())
+            .suggestion(Some(suggestion))
+            .rule("legacy-track-matte")
+            .build();
+
+        ValidatorError::BestPractice {
+            message: "'trackMatteType' assignment uses the pre-AE 23 adjacent-layer track matte".to_string(),
+            context,
+            severity: ErrorSeverity::Warning,
+        }
+    }).collect()
+}
+
+fn is_no_track_matte(value: &str) -> bool {
+    value == "TrackMatteType.NO_TRACK_MATTE"
+}
+
+fn infer_matte_layer(script: &str, receiver: &str, pos: usize) -> Result<String, String> {
+    if let Some(matte) = layer_above(receiver)? {
+        return Ok(matte);
+    }
+    if !receiver.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+        return Err(format!("cannot tell which layer '{}' is", receiver));
+    }
+
+    let (value, value_end) = last_assignment(script, receiver, pos)
+        .ok_or_else(|| format!("'{}' is not assigned in this script", receiver))?;
+    if let Some(matte) = layer_above(&value)? {
+        return Ok(matte);
+    }
+
+    let comp = LAYER_ADD_RE.captures(&value)
+        .map(|cap| cap[1].to_string())
+        .ok_or_else(|| format!("cannot tell which layer '{}' is", receiver))?;
+    let between = &script[value_end..pos];
+    if MOVE_RE.is_match(between) {
+        return Err("layers are reordered before the matte is set".to_string());
+    }
+
+    let add_re = Regex::new(&format!(r"{}\.layers\.add\w*\s*\(", regex::escape(&comp))).unwrap();
+    let adds: Vec<_> = add_re.find_iter(between).collect();
+    if adds.len() != 1 {
+        return Err(format!("{} layers are added to {} after '{}'", adds.len(), comp, receiver));
+    }
+    let assigned_re = Regex::new(r"(?:\bvar\s+)?([A-Za-z_$][\w$]*)\s*=\s*$").unwrap();
+    assigned_re.captures(&between[..adds[0].start()])
+        .map(|cap| cap[1].to_string())
+        .ok_or_else(|| "the layer above is not stored in a variable".to_string())
+}
+
+/// `comp.layer(n)` -> `comp.layer(n - 1)`
+fn layer_above(expr: &str) -> Result<Option<String>, String> {
+    match LAYER_INDEX_RE.captures(expr.trim()) {
+        Some(cap) => {
+            let index: usize = cap[2].parse().unwrap_or(0);
+            if index <= 1 {
+                return Err(format!("{} has no layer above it", expr.trim()));
+            }
+            Ok(Some(format!("{}.layer({})", &cap[1], index - 1)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Value and end offset of the last `name = value` before `pos`
+fn last_assignment(script: &str, name: &str, pos: usize) -> Option<(String, usize)> {
+    let re = Regex::new(&format!(r"(?:\bvar\s+)?\b{}\s*=\s*([^=;\n][^;\n]*)", regex::escape(name))).unwrap();
+    re.captures_iter(&script[..pos])
+        .last()
+        .map(|cap| {
+            let value = cap.get(1).unwrap();
+            (value.as_str().trim().to_string(), value.end())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrates_indexed_and_created_layers() {
+        let script = r#"var comp = app.project.activeItem;
+comp.layer(3).trackMatteType = TrackMatteType.ALPHA;
+var fill = comp.layers.addSolid([1, 0, 0], "Fill", 100, 100, 1);
+var matte = comp.layers.addShape();
+fill.trackMatteType = TrackMatteType.LUMA_INVERTED;
+comp.layer(2).trackMatteType = TrackMatteType.NO_TRACK_MATTE;
+"#;
+        let (migrated, manual) = migrate_track_matte(script);
+        assert!(manual.is_empty());
+        assert!(migrated.contains("comp.layer(3).setTrackMatte(comp.layer(2), TrackMatteType.ALPHA);"));
+        assert!(migrated.contains("fill.setTrackMatte(matte, TrackMatteType.LUMA_INVERTED);"));
+        assert!(migrated.contains("comp.layer(2).removeTrackMatte();"));
+    }
+
+    #[test]
+    fn test_flags_uninferable_assignments() {
+        let script = r#"var layer = comp.layer(1);
+layer.trackMatteType = TrackMatteType.ALPHA;
+function apply(target) { target.trackMatteType = TrackMatteType.LUMA; }
+"#;
+        let (migrated, manual) = migrate_track_matte(script);
+        assert_eq!(migrated, script);
+        assert_eq!(manual.len(), 2);
+        assert!(manual[0].reason.as_deref().unwrap().contains("no layer above"));
+        assert_eq!((manual[0].line, manual[0].column), (2, 1));
+
+        let errors = validate_track_matte_migration(script, "a.jsx");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].get_context().line, Some(2));
+        assert_eq!(errors[0].get_context().code_snippet.as_deref(), Some("layer.trackMatteType = TrackMatteType.ALPHA;"));
+        assert!(errors[1].get_context().suggestion().unwrap().contains("Manual review"));
+    }
+
+    #[test]
+    fn test_ignores_comparisons() {
+        assert!(find_track_matte_migrations("if (layer.trackMatteType == TrackMatteType.ALPHA) {}").is_empty());
+    }
+}
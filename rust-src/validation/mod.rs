@@ -1,4 +1,6 @@
 pub mod rules;
+pub mod custom;
+pub mod schema;
 pub mod property;
 pub mod temporal;
 pub mod expression;
@@ -10,21 +12,103 @@ pub mod errors;
 pub mod script;
 pub mod workflow;
 pub mod typechecker;
+pub mod config;
+pub mod manifest;
+pub mod headless;
+pub mod metrics;
+pub mod i18n;
+pub mod migration;
+pub mod shims;
+pub mod profile;
+pub mod color;
+pub mod renderqueue;
+pub mod paths;
+pub mod platform;
+pub mod localization;
+pub mod keyframes;
+pub mod jsxbin;
+pub mod encoding;
+pub mod sourcemap;
+pub mod changes;
+pub mod includes;
+pub mod naming;
+pub mod casing;
+pub mod constraints;
+pub mod lifecycle;
+pub mod alternate;
+pub mod usage;
+pub mod suppression;
+pub mod catches;
+pub mod loops;
+pub mod panel;
+pub mod session;
+pub mod effect_support;
+pub mod obsolete;
+pub mod hierarchy;
+pub mod capabilities;
+pub mod three_d;
+pub mod camera_light;
+pub mod text_animators;
+pub mod easing;
+pub mod globals;
+pub mod tasks;
+pub mod embedded;
+pub mod workspace;
 
 #[cfg(test)]
 mod tests;
 
 pub use rules::{ValidationRule, MethodValidation};
+pub use custom::CustomValidator;
+pub use schema::{ValueSchema, FieldSchema};
 pub use property::validate_property_value;
 pub use temporal::validate_temporal_ease;
-pub use expression::{validate_expression_syntax, ExpressionValidationResult};
+pub use keyframes::{validate_keyframe_data, keyframes_from_json, Keyframe, KeyframeIssue};
+pub use easing::{EasingPreset, EASING_PRESETS};
+pub use expression::{validate_expression_syntax, analyze_expression, ExpressionValidationResult, ExpressionReport, ExpressionInfo};
 pub use performance::PerformanceMetrics;
 pub use context::{ValidationContext, ObjectContext, TextValidationContext, EffectInfo};
 pub use text::validate_text_document;
 pub use errors::{ValidatorError, ErrorSeverity};
-pub use script::{validate_script, ScriptValidationResult};
+pub use script::{validate_script, validate_script_safe, validate_script_with_config, validate_script_profiled, collect_script_diagnostics, apply_config, ScriptValidationResult, format_script, FormatOptions, QuoteStyle, CallGraph};
 pub use workflow::validate_workflow_patterns;
-pub use typechecker::validate_type_usage;
+pub use typechecker::{validate_type_usage, validate_type_usage_with_shims};
+pub use config::{ValidatorConfig, RuleProfile, Environment, Platform};
+pub use manifest::{ProjectManifest, ManifestComp, validate_against_manifest};
+pub use headless::{find_dialog_calls, validate_environment, DialogCall, HeadlessReport};
+pub use metrics::{ScriptMetrics, FunctionMetrics};
+pub use i18n::{Locale, MessageCatalog};
+pub use migration::{find_track_matte_migrations, migrate_track_matte, validate_track_matte_migration, TrackMatteMigration};
+pub use profile::{Profiler, PassProfile, PeakAllocator};
+pub use color::{fix_color_ranges, validate_color_ranges, ColorValue};
+pub use renderqueue::{validate_render_queue, RQItemStatus};
+pub use paths::{validate_footage_paths, PathResolver};
+pub use platform::validate_platform_paths;
+pub use localization::validate_localized_strings;
+pub use jsxbin::{is_jsxbin, jsxbin_diagnostic};
+pub use encoding::{read_script, write_script, ScriptSource, SourceEncoding};
+pub use sourcemap::SourceMap;
+pub use changes::ChangedLines;
+pub use includes::{validate_duplicate_globals, IncludeGraph, IncludedFile};
+pub use naming::{validate_naming, NameStyle, NamingConfig};
+pub use casing::validate_class_name_case;
+pub use constraints::{validate_property_constraints, Constraint};
+pub use lifecycle::{validate_removed_objects, validate_stale_references};
+pub use alternate::validate_alternate_sources;
+pub use usage::{ApiUsage, MemberUsage};
+pub use suppression::{find_suppression_issues, fix_suppressed_dialogs, validate_suppressed_dialogs, SuppressionIssue};
+pub use catches::{validate_error_handling, CheckLevel, ErrorHandlingConfig};
+pub use loops::validate_infinite_loops;
+pub use panel::validate_panel_script;
+pub use session::ValidationSession;
+pub use workspace::Workspace;
+pub use effect_support::validate_effect_support;
+pub use obsolete::{find_obsolete_effects, fix_obsolete_effects, validate_obsolete_effects, ObsoleteEffectUse};
+pub use hierarchy::validate_property_hierarchy;
+pub use capabilities::validate_layer_capabilities;
+pub use three_d::validate_3d_properties;
+pub use camera_light::validate_camera_light_options;
+pub use text_animators::validate_text_animators;
 
 /// Main entry point for validating After Effects scripts
 pub fn validate_ae_script(script: &str, file_path: &str) -> ScriptValidationResult {
@@ -0,0 +1,280 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::inference::infer_variable_types;
+use super::script::lexer::{matching_bracket, Token, TokenKind};
+use super::script::scope::analyze_scopes;
+
+/// Undo group names that say nothing about what the script did
+const GENERIC_UNDO_NAMES: &[&str] = &["undo", "script", "action", "test", "temp", "untitled", "group", "do it", "run"];
+
+/// Casing styles for identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NameStyle {
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "UPPER_SNAKE")]
+    UpperSnake,
+}
+
+impl NameStyle {
+    pub fn name(self) -> &'static str {
+        match self {
+            NameStyle::CamelCase => "camelCase",
+            NameStyle::PascalCase => "PascalCase",
+            NameStyle::SnakeCase => "snake_case",
+            NameStyle::UpperSnake => "UPPER_SNAKE",
+        }
+    }
+
+    /// Leading `_` and `$`, common markers for private members, are ignored
+    pub fn matches(self, name: &str) -> bool {
+        let name = name.trim_start_matches(['_', '$']);
+        let mut chars = name.chars();
+        let first = match chars.next() {
+            Some(first) => first,
+            None => return true,
+        };
+        let rest: Vec<char> = chars.collect();
+        match self {
+            NameStyle::CamelCase => first.is_ascii_lowercase() && rest.iter().all(|c| c.is_ascii_alphanumeric()),
+            NameStyle::PascalCase => first.is_ascii_uppercase() && rest.iter().all(|c| c.is_ascii_alphanumeric()),
+            NameStyle::SnakeCase => first.is_ascii_lowercase()
+                && rest.iter().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '_'),
+            NameStyle::UpperSnake => first.is_ascii_uppercase()
+                && rest.iter().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || *c == '_'),
+        }
+    }
+}
+
+/// Naming conventions from the `naming` section of `.auteurrc`. Every rule
+/// is off until configured.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamingConfig {
+    /// Regex that layer names given as string literals must match
+    pub layer_names: Option<String>,
+    /// Regex that comp names given as string literals must match
+    pub comp_names: Option<String>,
+    /// Style of function names
+    pub functions: Option<NameStyle>,
+    /// Style of global variables initialized to a literal and never reassigned
+    pub constants: Option<NameStyle>,
+    /// Require undo group names that describe the change
+    pub undo_groups: bool,
+}
+
+impl NamingConfig {
+    /// Checks that the configured patterns compile
+    pub fn check(&self) -> Result<(), String> {
+        for pattern in self.layer_names.iter().chain(&self.comp_names) {
+            Regex::new(pattern).map_err(|e| format!("Invalid naming pattern '{}': {}", pattern, e))?;
+        }
+        Ok(())
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.layer_names.is_some() || self.comp_names.is_some() || self.functions.is_some()
+            || self.constants.is_some() || self.undo_groups
+    }
+}
+
+/// Layer-creating methods and the argument holding the layer name
+const LAYER_NAME_ARGS: &[(&str, usize)] = &[
+    ("addSolid", 1),
+    ("addCamera", 0),
+    ("addLight", 0),
+];
+
+/// Reports names that break the conventions configured in `naming`
+pub fn validate_naming(script: &str, file_path: &str, config: &NamingConfig) -> Vec<ValidatorError> {
+    if !config.is_enabled() {
+        return Vec::new();
+    }
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let mut errors = Vec::new();
+    let mut report = |token: &Token, rule: &str, message: String, suggestion: String| {
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(token.line)
+            .column(token.column)
+            .length(token.text.chars().count())
+            .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some(suggestion))
+            .rule(rule)
+            .build();
+        errors.push(ValidatorError::Script { message, context, severity: ErrorSeverity::Warning });
+    };
+
+    let layer_names = config.layer_names.as_deref().and_then(|p| Regex::new(p).ok());
+    let comp_names = config.comp_names.as_deref().and_then(|p| Regex::new(p).ok());
+    let variable_types = infer_variable_types(script);
+
+    for (i, token) in tokens.iter().enumerate() {
+        // Names given at creation and `x.name = "..."` on inferred layers and comps
+        let mut named: Option<(&Token, bool)> = None;
+        if token.is_identifier() && i > 0 && tokens[i - 1].is_punct(".") {
+            if token.text == "addComp" {
+                named = call_argument(tokens, i, 0).map(|arg| (arg, false));
+            } else if let Some((_, index)) = LAYER_NAME_ARGS.iter().find(|(method, _)| *method == token.text) {
+                named = call_argument(tokens, i, *index).map(|arg| (arg, true));
+            } else if token.text == "name" && i >= 2 && tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+                let receiver = variable_types.get(&tokens[i - 2].text).map(String::as_str);
+                let value = tokens.get(i + 2).filter(|t| t.kind == TokenKind::String);
+                named = match (receiver, value) {
+                    (Some("CompItem"), Some(value)) => Some((value, false)),
+                    (Some("Layer"), Some(value)) => Some((value, true)),
+                    _ => None,
+                };
+            }
+        }
+        if let Some((literal, is_layer)) = named {
+            let (pattern, rule, kind) = if is_layer {
+                (&layer_names, "naming-layer-name", "Layer")
+            } else {
+                (&comp_names, "naming-comp-name", "Comp")
+            };
+            let value = unquote(&literal.text);
+            if let Some(pattern) = pattern.as_ref().filter(|p| !p.is_match(value)) {
+                report(literal, rule, format!("{} name \"{}\" does not match the naming convention", kind, value),
+                    format!("Use a name matching /{}/", pattern.as_str()));
+            }
+        }
+
+        if token.is_identifier() && i > 0 && tokens[i - 1].is_punct(".") && token.text == "beginUndoGroup" && config.undo_groups {
+            match call_argument(tokens, i, 0) {
+                None if tokens.get(i + 2).is_some_and(|t| t.is_punct(")")) => report(
+                    token, "naming-undo-group", "beginUndoGroup is called without a name".to_string(),
+                    "Name the undo group after the change, e.g. app.beginUndoGroup(\"Add lower third\")".to_string(),
+                ),
+                Some(literal) => {
+                    let name = unquote(&literal.text).trim();
+                    if name.chars().count() < 3 || GENERIC_UNDO_NAMES.contains(&name.to_lowercase().as_str()) {
+                        report(literal, "naming-undo-group", format!("Undo group name \"{}\" does not describe the change", name),
+                            "The name appears in Edit > Undo; describe what the script did, e.g. \"Add lower third\"".to_string());
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let Some(style) = config.functions {
+        for scope in analysis.scopes.iter().skip(1) {
+            let name = match &scope.name {
+                Some(name) if !style.matches(name) => name,
+                _ => continue,
+            };
+            if let Some(token) = tokens.get(scope.start + 1) {
+                report(token, "naming-function", format!("Function '{}' is not {}", name, style.name()),
+                    format!("Rename it to follow the {} convention", style.name()));
+            }
+        }
+    }
+
+    if let Some(style) = config.constants {
+        for (i, token) in tokens.iter().enumerate() {
+            if !token.is_keyword("var") || analysis.token_scopes[i] != 0 {
+                continue;
+            }
+            let name = match (tokens.get(i + 1), tokens.get(i + 2), tokens.get(i + 3), tokens.get(i + 4)) {
+                (Some(name), Some(assign), Some(value), Some(end))
+                    if name.is_identifier() && assign.is_punct("=") && is_literal(value)
+                        && (end.is_punct(";") || end.newlines_before > 0) => name,
+                _ => continue,
+            };
+            if !style.matches(&name.text) && !is_reassigned(tokens, &name.text, i + 1) {
+                report(name, "naming-constant", format!("Constant '{}' is not {}", name.text, style.name()),
+                    format!("It is never reassigned; name it in {}", style.name()));
+            }
+        }
+    }
+
+    errors
+}
+
+/// The string literal passed as argument `index` of the call whose method
+/// name is at `method`, if that argument is a single literal
+fn call_argument(tokens: &[Token], method: usize, index: usize) -> Option<&Token> {
+    let close = matching_bracket(tokens, method + 1).filter(|_| tokens[method + 1].is_punct("("))?;
+    let mut argument = 0usize;
+    let mut start = method + 2;
+    let mut j = start;
+    while j <= close {
+        if j == close || tokens[j].is_punct(",") {
+            if argument == index {
+                let literal = &tokens[start];
+                return (j == start + 1 && literal.kind == TokenKind::String).then_some(literal);
+            }
+            argument += 1;
+            start = j + 1;
+        }
+        j = matching_bracket(tokens, j).unwrap_or(j) + 1;
+    }
+    None
+}
+
+fn unquote(literal: &str) -> &str {
+    literal.get(1..literal.len().saturating_sub(1)).unwrap_or("")
+}
+
+fn is_literal(token: &Token) -> bool {
+    matches!(token.kind, TokenKind::Number | TokenKind::String) || token.is_keyword("true") || token.is_keyword("false")
+}
+
+/// Whether `name` is assigned or updated anywhere but its declaration
+fn is_reassigned(tokens: &[Token], name: &str, declaration: usize) -> bool {
+    tokens.iter().enumerate().any(|(i, token)| {
+        i != declaration && token.is_identifier() && token.text == name
+            && !(i > 0 && tokens[i - 1].is_punct("."))
+            && (tokens.get(i + 1).is_some_and(|t| {
+                t.kind == TokenKind::Punctuator && (t.text.ends_with('=') && !["==", "===", "!=", "!==", "<=", ">="].contains(&t.text.as_str())
+                    || t.text == "++" || t.text == "--")
+            }) || (i > 0 && (tokens[i - 1].is_punct("++") || tokens[i - 1].is_punct("--"))))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_conventions() {
+        let config: NamingConfig = serde_json::from_str(r#"{
+            "layer_names": "^[A-Z][A-Za-z0-9 ]+$",
+            "comp_names": "^(MAIN|PRE)_",
+            "functions": "camelCase",
+            "constants": "UPPER_SNAKE",
+            "undo_groups": true
+        }"#).unwrap();
+        let script = r#"var MAX_LAYERS = 10;
+var frameRate = 30;
+var counter = 0;
+counter++;
+function build_title(comp) {
+    app.beginUndoGroup("undo");
+    var main = app.project.items.addComp("MAIN_Title", 1920, 1080, 1, 10, 30);
+    var pre = app.project.items.addComp("scratch", 1920, 1080, 1, 10, 30);
+    var bg = main.layers.addSolid([0, 0, 0], "bg solid", 1920, 1080, 1);
+    var layer = main.layer(1);
+    layer.name = "Title Card";
+    app.endUndoGroup();
+}
+"#;
+        let errors = validate_naming(script, "a.jsx", &config);
+        let found: Vec<(&str, usize)> = errors.iter().map(|e| (e.rule_id().unwrap(), e.get_context().line.unwrap())).collect();
+        assert_eq!(found, vec![
+            ("naming-undo-group", 6),
+            ("naming-comp-name", 8),
+            ("naming-layer-name", 9),
+            ("naming-function", 5),
+            ("naming-constant", 2),
+        ]);
+        assert!(validate_naming(script, "a.jsx", &NamingConfig::default()).is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use crate::data::match_names::{find_obsolete_effect, get_effect_group, ObsoleteEffect};
+
+lazy_static! {
+    static ref ADD_EFFECT_RE: Regex = Regex::new(r#"\.addProperty\s*\(\s*(?:"([^"\n]+)"|'([^'\n]+)')\s*\)"#).unwrap();
+}
+
+/// An obsolete effect added by a script
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObsoleteEffectUse {
+    pub effect: &'static ObsoleteEffect,
+    pub line: usize,
+    pub column: usize,
+    /// Byte range of the match name, without quotes
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Finds `addProperty` calls adding an obsolete effect by match name
+pub fn find_obsolete_effects(script: &str) -> Vec<ObsoleteEffectUse> {
+    ADD_EFFECT_RE.captures_iter(script).filter_map(|cap| {
+        let name = cap.get(1).or_else(|| cap.get(2))?;
+        let effect = find_obsolete_effect(name.as_str())?;
+        let line_start = script[..name.start()].rfind('\n').map_or(0, |i| i + 1);
+        Some(ObsoleteEffectUse {
+            effect,
+            line: script[..name.start()].matches('\n').count() + 1,
+            column: script[line_start..name.start()].chars().count() + 1,
+            start: name.start(),
+            end: name.end(),
+        })
+    }).collect()
+}
+
+/// Swaps obsolete match names for their replacements where the parameters
+/// are the same. Returns the fixed script and the number of effects changed.
+pub fn fix_obsolete_effects(script: &str) -> (String, usize) {
+    let mut output = script.to_string();
+    let mut fixed = 0;
+    for found in find_obsolete_effects(script).into_iter().rev() {
+        if let Some(replacement) = found.effect.replacement.filter(|_| found.effect.compatible) {
+            output.replace_range(found.start..found.end, replacement);
+            fixed += 1;
+        }
+    }
+    (output, fixed)
+}
+
+/// Suggests the modern effect for obsolete ones: a warning for effects only
+/// listed under Effect > Obsolete, which may be dropped from later
+/// versions, and a note for superseded effects still in their menu
+pub fn validate_obsolete_effects(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    find_obsolete_effects(script).into_iter().map(|found| {
+        let effect = found.effect;
+        let suggestion = match effect.replacement {
+            Some(replacement) if effect.compatible => format!("Use {} with the same parameters: \"{}\"; `ae-validator fix` can do this", effect.advice, replacement),
+            Some(replacement) => format!("Use {} instead: \"{}\"; its parameters differ, so update the values the script sets", effect.advice, replacement),
+            None => format!("Use {} instead", effect.advice),
+        };
+        let severity = if get_effect_group(effect.match_name) == Some("Obsolete") {
+            ErrorSeverity::Warning
+        } else {
+            ErrorSeverity::Info
+        };
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(found.line)
+            .column(found.column)
+            .length(found.end - found.start)
+            .code_snippet(script.lines().nth(found.line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some(suggestion))
+            .rule("obsolete-effect")
+            .build();
+
+        ValidatorError::Script {
+            message: format!("{} (\"{}\") is obsolete", effect.display_name, effect.match_name),
+            context,
+            severity,
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obsolete_effects() {
+        let script = r#"var fx = layer.property("ADBE Effect Parade");
+fx.addProperty("ADBE Gaussian Blur");
+fx.addProperty('ADBE Fast Blur');
+fx.addProperty("ADBE Color Balance (HLS)");
+fx.addProperty("ADBE Gaussian Blur 2");
+layer.effect("ADBE Fast Blur");
+"#;
+        let errors = validate_obsolete_effects(script, "fx.jsx");
+        let found: Vec<(usize, ErrorSeverity)> = errors.iter().map(|e| (e.get_context().line.unwrap(), e.get_severity())).collect();
+        assert_eq!(found, vec![(2, ErrorSeverity::Warning), (3, ErrorSeverity::Warning), (4, ErrorSeverity::Info)]);
+        assert_eq!(errors[0].get_message(), "Gaussian Blur (Legacy) (\"ADBE Gaussian Blur\") is obsolete");
+
+        let (fixed, count) = fix_obsolete_effects(script);
+        assert_eq!(count, 1);
+        assert!(fixed.contains("fx.addProperty(\"ADBE Gaussian Blur 2\");\nfx.addProperty('ADBE Fast Blur');"));
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::HashSet;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{matching_bracket, Token};
+use super::script::scope::{analyze_scopes, ScopeAnalysis};
+
+/// Global functions that open a modal dialog
+const BLOCKING_FUNCTIONS: &[&str] = &["alert", "confirm", "prompt"];
+
+/// Methods that wait for the user or another process, with the receiver
+/// they must be called on (`None` for any receiver)
+const BLOCKING_METHODS: &[(Option<&str>, &str)] = &[
+    (None, "openDialog"),
+    (None, "saveDialog"),
+    (None, "selectDialog"),
+    (None, "importFileWithDialog"),
+    (None, "saveWithDialog"),
+    (Some("$"), "sleep"),
+    (Some("system"), "callSystem"),
+    (Some("renderQueue"), "render"),
+];
+
+/// Checks the structure of a dockable panel script, installed under
+/// `ScriptUI Panels` and run by After Effects at launch:
+///
+/// - the UI must be built onto the `Panel` After Effects passes as `this`,
+///   falling back to a `Window` when run from File > Scripts
+/// - docked panels need `layout.layout(true)` to show their controls and a
+///   resize handler calling `layout.resize()`
+/// - nothing run at load time may block, since that stalls application startup
+pub fn validate_panel_script(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let mut errors = Vec::new();
+
+    let entry = tokens.windows(2).position(|w| w[0].is_keyword("instanceof") && w[1].text == "Panel");
+    match entry {
+        None => {
+            let at = tokens.windows(2).position(|w| w[0].is_keyword("new") && w[1].text == "Window").map_or(0, |i| i + 1);
+            errors.push(panel_error(
+                script, file_path, tokens.get(at),
+                "Panel scripts must build their UI onto the Panel After Effects passes in, but the script never checks 'instanceof Panel'".to_string(),
+                "var win = (thisObj instanceof Panel) ? thisObj : new Window(\"palette\", \"Title\", undefined, { resizeable: true });",
+                "panel-entry",
+                ErrorSeverity::Error,
+            ));
+        }
+        Some(i) if !receives_this(&analysis) => {
+            errors.push(panel_error(
+                script, file_path, tokens.get(i),
+                "The panel object is never passed in: 'this' at the top level of the script is the Panel, but nothing hands it to the UI code".to_string(),
+                "Wrap the script in (function (thisObj) { ... })(this) and build the UI onto thisObj",
+                "panel-entry",
+                ErrorSeverity::Error,
+            ));
+        }
+        Some(_) => {}
+    }
+
+    if let Some(i) = entry {
+        let lays_out = tokens.windows(4).any(|w| w[0].text == "layout" && w[1].is_punct(".") && w[2].text == "layout" && w[3].is_punct("("));
+        if !lays_out {
+            errors.push(panel_error(
+                script, file_path, tokens.get(i),
+                "Controls added to a docked panel stay hidden until layout.layout(true) is called".to_string(),
+                "Call win.layout.layout(true) once the controls are added",
+                "panel-layout",
+                ErrorSeverity::Warning,
+            ));
+        }
+        match resize_handler(tokens) {
+            Some(Ok(())) => {}
+            Some(Err(handler)) => errors.push(panel_error(
+                script, file_path, tokens.get(handler),
+                "The resize handler never calls layout.resize(), so the controls keep their size when the panel is resized".to_string(),
+                "Call this.layout.resize() in the handler",
+                "panel-resize",
+                ErrorSeverity::Warning,
+            )),
+            None => errors.push(panel_error(
+                script, file_path, tokens.get(i),
+                "The panel has no onResizing handler, so its controls keep their size when the user resizes the panel".to_string(),
+                "win.onResizing = win.onResize = function () { this.layout.resize(); };",
+                "panel-resize",
+                ErrorSeverity::Warning,
+            )),
+        }
+    }
+
+    let load_scopes = load_time_scopes(&analysis);
+    for (i, token) in tokens.iter().enumerate() {
+        if load_scopes.contains(&analysis.token_scopes[i]) && is_blocking_call(tokens, i) {
+            errors.push(panel_error(
+                script, file_path, Some(token),
+                format!("'{}' runs while the panel loads and blocks After Effects from starting", token.text),
+                "Move the call into an event handler such as a button's onClick",
+                "panel-blocking-load",
+                ErrorSeverity::Error,
+            ));
+        }
+    }
+
+    errors.sort_by_key(|e| e.get_context().line);
+    errors
+}
+
+/// Whether `this` at the top level is passed to a function or checked with
+/// `instanceof`, so the Panel reaches the code building the UI
+fn receives_this(analysis: &ScopeAnalysis) -> bool {
+    let tokens = &analysis.tokens;
+    tokens.iter().enumerate().any(|(i, token)| {
+        token.is_keyword("this")
+            && analysis.token_scopes[i] == 0
+            && (tokens.get(i + 1).is_some_and(|t| t.is_keyword("instanceof"))
+                || (i > 0 && (tokens[i - 1].is_punct("(") || tokens[i - 1].is_punct(","))))
+    })
+}
+
+/// The `onResizing`/`onResize` handler: `Ok` if its body calls
+/// `layout.resize()` (or it is a named function), `Err` with the handler's
+/// index otherwise, `None` without a handler
+fn resize_handler(tokens: &[Token]) -> Option<Result<(), usize>> {
+    let mut found = None;
+    for (i, token) in tokens.iter().enumerate() {
+        let assigned = (token.text == "onResizing" || token.text == "onResize")
+            && i > 0 && tokens[i - 1].is_punct(".")
+            && tokens.get(i + 1).is_some_and(|t| t.is_punct("="));
+        if !assigned {
+            continue;
+        }
+        // `win.onResizing = win.onResize = function () { ... }`
+        let value = tokens[i + 2..].iter()
+            .position(|t| !(t.is_identifier() || t.is_punct(".") || t.is_punct("=")))
+            .map(|offset| i + 2 + offset);
+        let body = value
+            .filter(|&v| tokens[v].is_keyword("function"))
+            .and_then(|v| tokens[v..].iter().position(|t| t.is_punct("{")).map(|offset| v + offset))
+            .and_then(|open| matching_bracket(tokens, open).map(|close| &tokens[open..close]));
+        let resizes = match body {
+            Some(body) => body.windows(3).any(|w| w[0].text == "layout" && w[1].is_punct(".") && w[2].text == "resize"),
+            None => true,
+        };
+        if resizes {
+            return Some(Ok(()));
+        }
+        found = found.or(Some(Err(i)));
+    }
+    found
+}
+
+/// Scopes whose code runs while the script loads: the top level, functions
+/// invoked in place and named functions called from those. Event handlers
+/// are assigned, not called, so they are left out.
+fn load_time_scopes(analysis: &ScopeAnalysis) -> HashSet<usize> {
+    let tokens = &analysis.tokens;
+    let mut load: HashSet<usize> = HashSet::new();
+    load.insert(0);
+    for scope in 1..analysis.scopes.len() {
+        if is_invoked_in_place(tokens, analysis.scopes[scope].start) {
+            load.insert(scope);
+        }
+    }
+
+    loop {
+        let mut added = false;
+        for scope in 1..analysis.scopes.len() {
+            if load.contains(&scope) {
+                continue;
+            }
+            let name = analysis.scope_label(scope);
+            let called = tokens.iter().enumerate().any(|(i, token)| {
+                token.text == name
+                    && load.contains(&analysis.token_scopes[i])
+                    && tokens.get(i + 1).is_some_and(|t| t.is_punct("("))
+                    && !(i > 0 && (tokens[i - 1].is_keyword("function") || tokens[i - 1].is_punct(".")))
+            });
+            if called {
+                load.insert(scope);
+                added = true;
+            }
+        }
+        if !added {
+            return load;
+        }
+    }
+}
+
+/// Whether the function whose keyword is at `start` is called right where
+/// it is defined: `(function () { ... })()`, `(function () { ... }())` or
+/// `.call(this)`
+fn is_invoked_in_place(tokens: &[Token], start: usize) -> bool {
+    let close = tokens[start..].iter().position(|t| t.is_punct("{"))
+        .and_then(|offset| matching_bracket(tokens, start + offset));
+    let after = match close {
+        Some(close) => &tokens[close + 1..],
+        None => return false,
+    };
+    let after = if after.first().is_some_and(|t| t.is_punct(")")) { &after[1..] } else { after };
+    match after {
+        [open, ..] if open.is_punct("(") => true,
+        [dot, method, ..] if dot.is_punct(".") => method.text == "call" || method.text == "apply",
+        _ => false,
+    }
+}
+
+fn is_blocking_call(tokens: &[Token], i: usize) -> bool {
+    let token = &tokens[i];
+    if !token.is_identifier() || !tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+        return false;
+    }
+    let receiver = if i >= 2 && tokens[i - 1].is_punct(".") { Some(tokens[i - 2].text.as_str()) } else { None };
+    match receiver {
+        None => BLOCKING_FUNCTIONS.contains(&token.text.as_str()) && !(i > 0 && tokens[i - 1].is_keyword("function")),
+        Some(receiver) => BLOCKING_METHODS.iter().any(|(expected, method)| {
+            *method == token.text && expected.is_none_or(|expected| expected == receiver)
+        }),
+    }
+}
+
+fn panel_error(
+    script: &str,
+    file_path: &str,
+    token: Option<&Token>,
+    message: String,
+    suggestion: &str,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let (line, column, length) = token.map_or((1, 1, 0), |t| (t.line, t.column, t.text.chars().count()));
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(column)
+        .length(length)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion.to_string()))
+        .rule(rule)
+        .build();
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(script: &str) -> Vec<(usize, String)> {
+        validate_panel_script(script, "Panel.jsx").iter()
+            .map(|e| (e.get_context().line.unwrap(), e.get_context().rule.clone().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_panel_entry_structure() {
+        let canonical = r#"(function (thisObj) {
+    function buildUI(thisObj) {
+        var win = (thisObj instanceof Panel) ? thisObj : new Window("palette", "Tools", undefined, { resizeable: true });
+        var button = win.add("button", undefined, "Render");
+        button.onClick = function () {
+            if (confirm("Render now?")) app.project.renderQueue.render();
+        };
+        win.onResizing = win.onResize = function () { this.layout.resize(); };
+        win.layout.layout(true);
+        return win;
+    }
+    var panel = buildUI(thisObj);
+    if (panel instanceof Window) { panel.center(); panel.show(); }
+})(this);
+"#;
+        assert!(rules(canonical).is_empty());
+
+        let blocking = canonical.replace("var panel = buildUI(thisObj);", "alert(\"Loading\");\n    var panel = buildUI(thisObj);");
+        assert_eq!(rules(&blocking), vec![(12, "panel-blocking-load".to_string())]);
+
+        let unshaped = canonical
+            .replace("})(this);", "})();")
+            .replace("this.layout.resize();", "")
+            .replace("win.layout.layout(true);", "");
+        assert_eq!(rules(&unshaped), vec![
+            (3, "panel-entry".to_string()),
+            (3, "panel-layout".to_string()),
+            (8, "panel-resize".to_string()),
+        ]);
+
+        let window_only = "var win = new Window(\"palette\", \"Tools\");\nwin.show();\n";
+        assert_eq!(rules(window_only), vec![(1, "panel-entry".to_string())]);
+    }
+}
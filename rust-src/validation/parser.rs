@@ -1,5 +1,4 @@
 use super::context::{ValidationContext, ObjectContext};
-use serde_json::Value;
 use std::collections::HashMap;
 
 pub struct ScriptParser {
@@ -7,6 +6,12 @@ pub struct ScriptParser {
     variables: HashMap<String, ObjectContext>,
 }
 
+impl Default for ScriptParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ScriptParser {
     pub fn new() -> Self {
         ScriptParser {
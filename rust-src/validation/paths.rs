@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+
+lazy_static! {
+    /// `new File("...")` and `File("...")`, including inside `new ImportOptions(...)`.
+    /// Only whole literals are matched; concatenated paths are left alone.
+    static ref FILE_LITERAL_RE: Regex = Regex::new(r#"\b(?:new\s+)?File\s*\(\s*(?:"([^"\n]+)"|'([^'\n]+)')\s*\)"#).unwrap();
+}
+
+/// Maps the paths a script was written against onto the local filesystem
+#[derive(Debug, Clone)]
+pub struct PathResolver {
+    /// Directory relative paths are resolved from, usually the script's own
+    base_dir: PathBuf,
+    /// Path prefixes and their local replacements, longest prefix first
+    roots: Vec<(String, PathBuf)>,
+}
+
+impl PathResolver {
+    pub fn new<P: AsRef<Path>>(base_dir: P) -> Self {
+        Self {
+            base_dir: base_dir.as_ref().to_path_buf(),
+            roots: Vec::new(),
+        }
+    }
+
+    /// Resolves paths starting with `prefix` under `target` instead,
+    /// e.g. `/Volumes/Assets` to `./assets` for a bundled asset pack
+    pub fn with_root<P: AsRef<Path>>(mut self, prefix: &str, target: P) -> Self {
+        self.roots.push((prefix.trim_end_matches('/').to_string(), target.as_ref().to_path_buf()));
+        self.roots.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self
+    }
+
+    pub fn with_roots(self, roots: &BTreeMap<String, String>) -> Self {
+        roots.iter().fold(self, |resolver, (prefix, target)| resolver.with_root(prefix, target))
+    }
+
+    /// Local path for a path literal from a script
+    pub fn resolve(&self, literal: &str) -> PathBuf {
+        // ExtendScript accepts URI-encoded paths
+        let literal = literal.replace("%20", " ");
+        for (prefix, target) in &self.roots {
+            if let Some(rest) = literal.strip_prefix(prefix.as_str()) {
+                if rest.is_empty() || rest.starts_with('/') {
+                    return self.relative_to_base(&target.join(rest.trim_start_matches('/')));
+                }
+            }
+        }
+        if let Some(rest) = literal.strip_prefix("~/") {
+            if let Some(home) = std::env::var_os("HOME") {
+                return PathBuf::from(home).join(rest);
+            }
+        }
+        self.relative_to_base(Path::new(&literal))
+    }
+
+    fn relative_to_base(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.base_dir.join(path)
+        }
+    }
+}
+
+/// Warns about `File` path literals that do not exist on the local filesystem
+pub fn validate_footage_paths(script: &str, file_path: &str, resolver: &PathResolver) -> Vec<ValidatorError> {
+    let mut errors = Vec::new();
+
+    for cap in FILE_LITERAL_RE.captures_iter(script) {
+        let literal = cap.get(1).or_else(|| cap.get(2)).unwrap().as_str();
+        let resolved = resolver.resolve(literal);
+        if resolved.exists() {
+            continue;
+        }
+
+        let m = cap.get(0).unwrap();
+        let line = script[..m.start()].matches('\n').count() + 1;
+        let line_start = script[..m.start()].rfind('\n').map_or(0, |i| i + 1);
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(line)
+            .column(script[line_start..m.start()].chars().count() + 1)
+            .length(m.end() - m.start())
+            .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some(format!(
+                "Looked for {}; add a --path-root mapping if the assets live elsewhere",
+                resolved.display()
+            )))
+            .rule("missing-footage")
+            .build();
+
+        errors.push(ValidatorError::Script {
+            message: format!("File '{}' does not exist", literal),
+            context,
+            severity: ErrorSeverity::Warning,
+        });
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_missing_footage_with_root_mapping() {
+        let dir = std::env::temp_dir().join(format!("ae-validator-paths-{}", std::process::id()));
+        fs::create_dir_all(dir.join("assets")).unwrap();
+        fs::write(dir.join("assets/clip.mov"), b"").unwrap();
+        fs::write(dir.join("logo.png"), b"").unwrap();
+
+        let resolver = PathResolver::new(&dir).with_root("/Volumes/Assets/", dir.join("assets"));
+        let script = r#"var clip = new ImportOptions(File("/Volumes/Assets/clip.mov"));
+var logo = new File('logo.png');
+var missing = new File("/Volumes/Assets/missing.mov");
+var built = new File(root + "/clip.mov");
+"#;
+        let errors = validate_footage_paths(script, "pack.jsx", &resolver);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].get_message(), "File '/Volumes/Assets/missing.mov' does not exist");
+        assert_eq!(errors[0].get_context().line, Some(3));
+    }
+}
@@ -1,6 +1,5 @@
 use regex::Regex;
 use lazy_static::lazy_static;
-use std::collections::HashMap;
 
 lazy_static! {
     static ref LAYER_REF_RE: Regex = Regex::new(r"thisLayer|thisComp\.layer\(\s*['\x22]?[\w\s]+['\x22]?\s*\)").unwrap();
@@ -37,6 +36,20 @@ impl PerformanceMetrics {
         }
     }
 
+    /// Rough relative cost of evaluating the expression once per frame.
+    /// Loops and cross-layer reads dominate; plain arithmetic is nearly free.
+    pub fn estimated_cost(&self) -> u32 {
+        let weighted = self.layer_refs * 4
+            + self.property_refs * 2
+            + self.math_funcs
+            + self.time_accesses * 2
+            + self.loop_count * 10
+            + self.array_methods * 5
+            + self.nested_calls * 2
+            + self.expression_length / 100;
+        weighted as u32
+    }
+
     pub fn get_warnings(&self) -> Vec<String> {
         let mut warnings = Vec::new();
         
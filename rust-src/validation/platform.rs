@@ -0,0 +1,127 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::script::lexer::{significant_tokens, TokenKind};
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::config::{Platform, ValidatorConfig};
+
+lazy_static! {
+    /// `C:\...` or `C:/...`, with the backslash escaped in source
+    static ref DRIVE_LETTER_RE: Regex = Regex::new(r"^[A-Za-z]:(?:\\\\|/)").unwrap();
+    static ref MAC_ROOT_RE: Regex = Regex::new(r"^/(?:Users|Volumes|Applications)/").unwrap();
+}
+
+/// Flags hard-coded paths that only work on some of the configured platforms.
+///
+/// Windows paths (drive letters, backslash separators) are reported when the
+/// scripts must run on macOS and macOS volume paths when they must run on
+/// Windows; `~` and paths built from `fsName` only when both are targeted.
+pub fn validate_platform_paths(script: &str, file_path: &str, config: &ValidatorConfig) -> Vec<ValidatorError> {
+    let mut errors = Vec::new();
+    let windows = config.targets(Platform::Windows);
+    let macos = config.targets(Platform::Macos);
+
+    let tokens = significant_tokens(script);
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind == TokenKind::String && token.text.len() >= 2 {
+            let inner = &token.text[1..token.text.len() - 1];
+            let finding = if macos && DRIVE_LETTER_RE.is_match(inner) {
+                Some((
+                    format!("Hard-coded Windows path {}", token.text),
+                    "Use Folder.desktop, Folder.myDocuments or Folder.userData, or a forward-slash URI such as \"/c/Projects\"",
+                ))
+            } else if macos && inner.contains("\\\\") {
+                Some((
+                    format!("Backslash path separators in {} only work on Windows", token.text),
+                    "Use forward slashes, which ExtendScript accepts on every platform",
+                ))
+            } else if windows && macos && (inner == "~" || inner.starts_with("~/")) {
+                Some((
+                    format!("'~' in {} resolves to a different folder on each platform", token.text),
+                    "Use Folder.userData, Folder.myDocuments or Folder.desktop instead of ~",
+                ))
+            } else if windows && MAC_ROOT_RE.is_match(inner) {
+                Some((
+                    format!("Hard-coded macOS path {}", token.text),
+                    "Use Folder.desktop, Folder.myDocuments or Folder.userData instead of an absolute macOS path",
+                ))
+            } else {
+                None
+            };
+            if let Some((message, suggestion)) = finding {
+                errors.push(platform_error(script, file_path, token.line, token.column, token.text.len(), message, suggestion));
+            }
+        } else if windows && macos
+            && token.text == "fsName"
+            && i > 0 && tokens[i - 1].is_punct(".")
+            && tokens.get(i + 1).is_some_and(|t| t.is_punct("+"))
+        {
+            errors.push(platform_error(
+                script,
+                file_path,
+                token.line,
+                token.column,
+                token.text.len(),
+                "Paths built from fsName use the platform's native separators".to_string(),
+                "Build paths from .fullName, which always uses forward slashes: new File(folder.fullName + \"/name\")",
+            ));
+        }
+    }
+
+    errors
+}
+
+fn platform_error(
+    script: &str,
+    file_path: &str,
+    line: usize,
+    column: usize,
+    length: usize,
+    message: String,
+    suggestion: &str,
+) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(column)
+        .length(length)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion.to_string()))
+        .rule("platform-path")
+        .build();
+
+    ValidatorError::Script {
+        message,
+        context,
+        severity: ErrorSeverity::Warning,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"var out = new File("C:\\renders\\out.mov");
+var log = new File("~/Desktop/log.txt");
+var dir = Folder.desktop.fsName + "/exports";
+var ok = new File(Folder.desktop.fullName + "/exports");
+"#;
+
+    #[test]
+    fn test_cross_platform_paths() {
+        let errors = validate_platform_paths(SCRIPT, "export.jsx", &ValidatorConfig::new());
+        let lines: Vec<usize> = errors.iter().map(|e| e.get_context().line.unwrap()).collect();
+        assert_eq!(lines, vec![1, 2, 3]);
+        assert!(errors[0].get_message().starts_with("Hard-coded Windows path"));
+    }
+
+    #[test]
+    fn test_single_platform_disables_foreign_checks() {
+        let windows = ValidatorConfig::from_json(r#"{ "platforms": ["windows"] }"#).unwrap();
+        assert!(validate_platform_paths(SCRIPT, "export.jsx", &windows).is_empty());
+
+        let macos = ValidatorConfig::from_json(r#"{ "platforms": ["macos"] }"#).unwrap();
+        let errors = validate_platform_paths(SCRIPT, "export.jsx", &macos);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].get_context().line, Some(1));
+    }
+}
@@ -0,0 +1,188 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Time spent in one validation pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassProfile {
+    pub name: String,
+    pub duration: Duration,
+    /// Most heap memory the pass had allocated at once beyond what was live
+    /// when it started; `None` unless [`PeakAllocator`] is installed
+    pub peak_memory: Option<usize>,
+}
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator that tracks live and peak heap memory for the profiler.
+/// Binaries opt in with
+/// `#[global_allocator] static ALLOCATOR: PeakAllocator = PeakAllocator;`.
+pub struct PeakAllocator;
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn grow(size: usize) {
+    let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(allocated, Ordering::Relaxed);
+}
+
+/// Bytes live now, with the peak restarted from there; `None` when
+/// [`PeakAllocator`] is not the global allocator
+fn restart_peak() -> Option<usize> {
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(allocated, Ordering::Relaxed);
+    (allocated > 0).then_some(allocated)
+}
+
+/// Collects per-pass timings while a script is validated.
+///
+/// With the `tracing` feature every pass also runs inside a
+/// `validation_pass` span carrying the pass name, so timings show up in any
+/// `tracing` subscriber alongside the caller's own spans.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    pub passes: Vec<PassProfile>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `pass` and records how long it took under `name`, and with
+    /// [`PeakAllocator`] installed, how much memory it needed at its peak
+    pub fn time<T>(&mut self, name: &str, pass: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("validation_pass", pass = name).entered();
+        let baseline = restart_peak();
+        let start = Instant::now();
+        let output = pass();
+        let duration = start.elapsed();
+        let peak = baseline.map(|baseline| PEAK.load(Ordering::Relaxed).saturating_sub(baseline));
+        self.record_with_memory(name, duration, peak);
+        output
+    }
+
+    /// Records a pass timed elsewhere; repeated names accumulate
+    pub fn record(&mut self, name: &str, duration: Duration) {
+        self.record_with_memory(name, duration, None);
+    }
+
+    /// Like [`record`](Self::record), with the pass's peak memory in bytes;
+    /// repeated names keep the highest peak
+    pub fn record_with_memory(&mut self, name: &str, duration: Duration, peak_memory: Option<usize>) {
+        match self.passes.iter_mut().find(|p| p.name == name) {
+            Some(pass) => {
+                pass.duration += duration;
+                pass.peak_memory = pass.peak_memory.max(peak_memory);
+            }
+            None => self.passes.push(PassProfile { name: name.to_string(), duration, peak_memory }),
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.passes.iter().map(|p| p.duration).sum()
+    }
+
+    /// Passes ordered from most to least expensive
+    pub fn slowest(&self) -> Vec<&PassProfile> {
+        let mut passes: Vec<&PassProfile> = self.passes.iter().collect();
+        passes.sort_by_key(|p| std::cmp::Reverse(p.duration));
+        passes
+    }
+
+    /// Slowest-first table with each pass's share of the total
+    /// Highest peak memory of any pass, in bytes
+    pub fn peak_memory(&self) -> Option<usize> {
+        self.passes.iter().filter_map(|p| p.peak_memory).max()
+    }
+
+    /// Slowest-first table with each pass's share of the total, and its peak
+    /// memory when that was measured
+    pub fn to_table(&self) -> String {
+        let total = self.total().as_secs_f64();
+        let with_memory = self.peak_memory().is_some();
+        let kib = |bytes: Option<usize>| bytes.map_or("-".to_string(), |bytes| format!("{:.1}", bytes as f64 / 1024.0));
+        let mut output = format!("{:<28} {:>10} {:>7}", "pass", "ms", "share");
+        if with_memory {
+            output.push_str(&format!(" {:>12}", "peak KiB"));
+        }
+        output.push('\n');
+        for pass in self.slowest() {
+            let share = if total > 0.0 { pass.duration.as_secs_f64() / total * 100.0 } else { 0.0 };
+            output.push_str(&format!(
+                "{:<28} {:>10.3} {:>6.1}%",
+                pass.name,
+                pass.duration.as_secs_f64() * 1000.0,
+                share
+            ));
+            if with_memory {
+                output.push_str(&format!(" {:>12}", kib(pass.peak_memory)));
+            }
+            output.push('\n');
+        }
+        output.push_str(&format!("{:<28} {:>10.3}", "total", total * 1000.0));
+        if with_memory {
+            output.push_str(&format!(" {:>20}", kib(self.peak_memory())));
+        }
+        output.push('\n');
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_and_ranks_passes() {
+        let mut profiler = Profiler::new();
+        profiler.record("scope", Duration::from_millis(2));
+        profiler.record("syntax", Duration::from_millis(5));
+        profiler.record("scope", Duration::from_millis(4));
+        let value = profiler.time("types", || 42);
+
+        assert_eq!(value, 42);
+        assert_eq!(profiler.passes.len(), 3);
+        let names: Vec<&str> = profiler.slowest().iter().map(|p| p.name.as_str()).take(2).collect();
+        assert_eq!(names, vec!["scope", "syntax"]);
+        assert!(profiler.to_table().lines().nth(1).unwrap().starts_with("scope"));
+        assert!(!profiler.to_table().contains("peak KiB"));
+
+        profiler.record_with_memory("types", Duration::ZERO, Some(4096));
+        profiler.record_with_memory("types", Duration::ZERO, Some(2048));
+        assert_eq!(profiler.peak_memory(), Some(4096));
+        assert!(profiler.to_table().lines().last().unwrap().ends_with(" 4.0"));
+    }
+}
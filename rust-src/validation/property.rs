@@ -2,6 +2,11 @@ use super::rules::{ValidationRule, PropertyValueType};
 use serde_json::Value;
 
 pub fn validate_property_value(value: &Value, rule: &ValidationRule) -> Result<(), String> {
+    validate_value_type(value, rule)?;
+    validate_custom(value, rule)
+}
+
+fn validate_value_type(value: &Value, rule: &ValidationRule) -> Result<(), String> {
     match &rule.value_type {
         PropertyValueType::NoValue => {
             if !value.is_null() {
@@ -20,7 +25,7 @@ pub fn validate_property_value(value: &Value, rule: &ValidationRule) -> Result<(
         PropertyValueType::Shape => validate_shape(value, rule),
         PropertyValueType::TextDocument => validate_text_document(value, rule),
         PropertyValueType::ArbText => validate_arb_text(value),
-        PropertyValueType::CustomValue | PropertyValueType::Custom(_) => validate_custom(value, rule),
+        PropertyValueType::CustomValue | PropertyValueType::Custom(_) => Ok(()),
     }
 }
 
@@ -78,13 +83,16 @@ pub fn validate_three_d(value: &Value, rule: &ValidationRule) -> Result<(), Stri
     }
 }
 
-pub fn validate_color(value: &Value, rule: &ValidationRule) -> Result<(), String> {
-    validate_array_size(value, 4)?;
+pub fn validate_color(value: &Value, _rule: &ValidationRule) -> Result<(), String> {
+    // Colors are [r, g, b, a]; creation methods such as addSolid take [r, g, b]
+    if validate_array_size(value, 3).is_err() {
+        validate_array_size(value, 4)?;
+    }
     if let Value::Array(arr) = value {
         for v in arr {
             if let Value::Number(n) = v {
                 if let Some(f) = n.as_f64() {
-                    if f < 0.0 || f > 1.0 {
+                    if !(0.0..=1.0).contains(&f) {
                         return Err("Color values must be between 0 and 1".to_string());
                     }
                 } else {
@@ -100,7 +108,7 @@ pub fn validate_color(value: &Value, rule: &ValidationRule) -> Result<(), String
     }
 }
 
-pub fn validate_marker(value: &Value, rule: &ValidationRule) -> Result<(), String> {
+pub fn validate_marker(value: &Value, _rule: &ValidationRule) -> Result<(), String> {
     if let Value::Object(_) = value {
         // Basic marker validation - could be expanded based on specific requirements
         Ok(())
@@ -109,7 +117,7 @@ pub fn validate_marker(value: &Value, rule: &ValidationRule) -> Result<(), Strin
     }
 }
 
-pub fn validate_shape(value: &Value, rule: &ValidationRule) -> Result<(), String> {
+pub fn validate_shape(value: &Value, _rule: &ValidationRule) -> Result<(), String> {
     if let Value::Object(_) = value {
         // Basic shape validation - could be expanded based on specific requirements
         Ok(())
@@ -118,7 +126,7 @@ pub fn validate_shape(value: &Value, rule: &ValidationRule) -> Result<(), String
     }
 }
 
-pub fn validate_text_document(value: &Value, rule: &ValidationRule) -> Result<(), String> {
+pub fn validate_text_document(value: &Value, _rule: &ValidationRule) -> Result<(), String> {
     if let Value::Object(_) = value {
         // Basic text document validation - could be expanded based on specific requirements
         Ok(())
@@ -169,7 +177,7 @@ pub fn validate_mask_index(value: &Value) -> Result<(), String> {
 
 pub fn validate_custom(value: &Value, rule: &ValidationRule) -> Result<(), String> {
     if let Some(validator) = &rule.custom_validator {
-        validator(value)
+        validator.validate(value)
     } else {
         // If no custom validator is provided, accept any value
         Ok(())
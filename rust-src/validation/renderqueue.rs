@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{matching_bracket, significant_tokens};
+
+lazy_static! {
+    static ref QUEUE_ALIAS_RE: Regex = Regex::new(r"\b(\w+)\s*=\s*[\w.]*\brenderQueue\s*;").unwrap();
+    static ref ADD_RE: Regex = Regex::new(r"(?:\b(\w+)\s*=\s*)?(?:[\w.]*\brenderQueue|\b(\w+))\.items\.add\s*\(").unwrap();
+    static ref DUPLICATE_RE: Regex = Regex::new(r"(?:\b(\w+)\s*=\s*)?\b(\w+)\.duplicate\s*\(\s*\)").unwrap();
+    static ref RENDER_CALL_RE: Regex = Regex::new(r"(?:[\w.]*\brenderQueue|\b(\w+))\.render\s*\(\s*\)").unwrap();
+    static ref SET_RENDER_RE: Regex = Regex::new(r"\b(\w+)\.render\s*=\s*(true|false)\b").unwrap();
+    static ref SET_STATUS_RE: Regex = Regex::new(r"\b(\w+)\.status\s*=[^=]").unwrap();
+    static ref STATUS_GUARD_RE: Regex = Regex::new(r"\bif\s*\(\s*(\w+)\.status\s*===?\s*RQItemStatus\.(\w+)\s*\)\s*\{").unwrap();
+    static ref UNQUEUE_ALL_RE: Regex = Regex::new(r"(?s)\bfor\s*\([^)]*renderQueue\.numItems[^)]*\)\s*\{[^{}]*\.render\s*=\s*false[^{}]*\}").unwrap();
+}
+
+/// Status of a render queue item, mirroring `RQItemStatus`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RQItemStatus {
+    WillContinue,
+    NeedsOutput,
+    Unqueued,
+    Queued,
+    Rendering,
+    UserStopped,
+    ErrStopped,
+    Done,
+}
+
+impl RQItemStatus {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "WILL_CONTINUE" => Some(Self::WillContinue),
+            "NEEDS_OUTPUT" => Some(Self::NeedsOutput),
+            "UNQUEUED" => Some(Self::Unqueued),
+            "QUEUED" => Some(Self::Queued),
+            "RENDERING" => Some(Self::Rendering),
+            "USER_STOPPED" => Some(Self::UserStopped),
+            "ERR_STOPPED" => Some(Self::ErrStopped),
+            "DONE" => Some(Self::Done),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::WillContinue => "WILL_CONTINUE",
+            Self::NeedsOutput => "NEEDS_OUTPUT",
+            Self::Unqueued => "UNQUEUED",
+            Self::Queued => "QUEUED",
+            Self::Rendering => "RENDERING",
+            Self::UserStopped => "USER_STOPPED",
+            Self::ErrStopped => "ERR_STOPPED",
+            Self::Done => "DONE",
+        }
+    }
+
+    /// Whether `render` may be changed in this status. Finished and stopped
+    /// items must be duplicated to render again; a rendering item is locked.
+    pub fn can_set_render(self) -> bool {
+        matches!(self, Self::WillContinue | Self::NeedsOutput | Self::Unqueued | Self::Queued)
+    }
+
+    /// Status after `render` is set to `value`, or `None` if the change is illegal
+    pub fn set_render(self, value: bool) -> Option<Self> {
+        if !self.can_set_render() {
+            return None;
+        }
+        Some(if value { Self::Queued } else { Self::Unqueued })
+    }
+
+    /// Status after `renderQueue.render()` returns
+    pub fn after_render(self) -> Self {
+        match self {
+            Self::Queued | Self::WillContinue => Self::Done,
+            other => other,
+        }
+    }
+}
+
+enum Event<'a> {
+    Add(Option<&'a str>),
+    Duplicate(Option<&'a str>, &'a str),
+    RenderCall,
+    SetRender(&'a str, bool),
+    SetStatus(&'a str),
+    UnqueueAll,
+}
+
+/// Tracks render queue item statuses through the script in source order.
+///
+/// Items created with `renderQueue.items.add()` start out QUEUED and become
+/// DONE when `renderQueue.render()` runs; inside
+/// `if (item.status == RQItemStatus.X) { ... }` the item is known to be X.
+/// The queue is only known to be empty after a render or after a loop that
+/// sets `render = false` on every item, with nothing queued since.
+pub fn validate_render_queue(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let aliases: Vec<&str> = QUEUE_ALIAS_RE.captures_iter(script).map(|cap| cap.get(1).unwrap().as_str()).collect();
+    let is_queue = |receiver: Option<regex::Match>| receiver.is_none_or(|r| aliases.contains(&r.as_str()));
+
+    let mut events: Vec<(usize, usize, Event)> = Vec::new();
+    for cap in ADD_RE.captures_iter(script).filter(|cap| is_queue(cap.get(2))) {
+        let m = cap.get(0).unwrap();
+        events.push((m.start(), m.end(), Event::Add(cap.get(1).map(|v| v.as_str()))));
+    }
+    for cap in DUPLICATE_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        events.push((m.start(), m.end(), Event::Duplicate(cap.get(1).map(|v| v.as_str()), cap.get(2).unwrap().as_str())));
+    }
+    for cap in RENDER_CALL_RE.captures_iter(script).filter(|cap| is_queue(cap.get(1))) {
+        let m = cap.get(0).unwrap();
+        events.push((m.start(), m.end(), Event::RenderCall));
+    }
+    for cap in SET_RENDER_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        events.push((m.start(), m.end(), Event::SetRender(cap.get(1).unwrap().as_str(), &cap[2] == "true")));
+    }
+    for cap in SET_STATUS_RE.captures_iter(script) {
+        let m = cap.get(0).unwrap();
+        events.push((m.start(), m.end(), Event::SetStatus(cap.get(1).unwrap().as_str())));
+    }
+    for m in UNQUEUE_ALL_RE.find_iter(script) {
+        // Takes effect once the loop has finished
+        events.push((m.end(), m.end(), Event::UnqueueAll));
+    }
+    events.sort_by_key(|(start, _, _)| *start);
+
+    let tokens = significant_tokens(script);
+    let guards: Vec<(&str, RQItemStatus, usize, usize)> = STATUS_GUARD_RE.captures_iter(script)
+        .filter_map(|cap| {
+            let status = RQItemStatus::from_name(&cap[2])?;
+            let open = cap.get(0).unwrap().end() - 1;
+            // The guarded block runs to its closing `}`, or the script end
+            let end = tokens.binary_search_by_key(&open, |t| t.start).ok()
+                .and_then(|brace| matching_bracket(&tokens, brace))
+                .map_or(script.len(), |close| tokens[close].start);
+            Some((cap.get(1).unwrap().as_str(), status, open, end))
+        })
+        .collect();
+
+    let mut statuses: HashMap<&str, RQItemStatus> = HashMap::new();
+    // None while the script cannot know what the project already queued
+    let mut queue_empty: Option<bool> = None;
+    let mut errors = Vec::new();
+
+    for (start, end, event) in events {
+        match event {
+            Event::Add(var) => {
+                if let Some(var) = var {
+                    statuses.insert(var, RQItemStatus::Queued);
+                }
+                queue_empty = Some(false);
+            }
+            Event::Duplicate(var, source) => {
+                if statuses.contains_key(source) {
+                    if let Some(var) = var {
+                        statuses.insert(var, RQItemStatus::Queued);
+                    }
+                    queue_empty = Some(false);
+                }
+            }
+            Event::RenderCall => {
+                if queue_empty == Some(true) {
+                    errors.push(diagnostic(
+                        script, file_path, start, end,
+                        "renderQueue.render() is called with no queued items".to_string(),
+                        "Queue an item with renderQueue.items.add(comp) or set render = true on an existing item first",
+                        "render-queue-empty",
+                        ErrorSeverity::Warning,
+                    ));
+                }
+                for status in statuses.values_mut() {
+                    *status = status.after_render();
+                }
+                queue_empty = Some(true);
+            }
+            Event::SetRender(var, value) => {
+                let guarded = guards.iter()
+                    .rfind(|(name, _, open, close)| *name == var && *open < start && start < *close)
+                    .map(|(_, status, _, _)| *status);
+                let current = match guarded.or_else(|| statuses.get(var).copied()) {
+                    Some(status) => status,
+                    None => {
+                        if value {
+                            queue_empty = Some(false);
+                        }
+                        continue;
+                    }
+                };
+                match current.set_render(value) {
+                    Some(next) => {
+                        statuses.insert(var, next);
+                        if value {
+                            queue_empty = Some(false);
+                        }
+                    }
+                    None => errors.push(diagnostic(
+                        script, file_path, start, end,
+                        format!("'{}.render' cannot be changed while the item is {}", var, current.name()),
+                        &if current == RQItemStatus::Rendering {
+                            "Wait until rendering has finished".to_string()
+                        } else {
+                            format!("Render it again with a copy: var again = {}.duplicate(); again.render = true;", var)
+                        },
+                        "render-queue-state",
+                        ErrorSeverity::Error,
+                    )),
+                }
+            }
+            Event::SetStatus(var) => {
+                errors.push(diagnostic(
+                    script, file_path, start, end,
+                    format!("'{}.status' is read-only", var),
+                    &format!("Set {}.render = true or false to queue or unqueue the item", var),
+                    "render-queue-state",
+                    ErrorSeverity::Error,
+                ));
+            }
+            Event::UnqueueAll => {
+                for status in statuses.values_mut() {
+                    if let Some(next) = status.set_render(false) {
+                        *status = next;
+                    }
+                }
+                queue_empty = Some(true);
+            }
+        }
+    }
+
+    errors
+}
+
+#[allow(clippy::too_many_arguments)]
+fn diagnostic(
+    script: &str,
+    file_path: &str,
+    start: usize,
+    end: usize,
+    message: String,
+    suggestion: &str,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let line = script[..start].matches('\n').count() + 1;
+    let line_start = script[..start].rfind('\n').map_or(0, |i| i + 1);
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(line)
+        .column(script[line_start..start].chars().count() + 1)
+        .length(end - start)
+        .code_snippet(script.lines().nth(line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion.to_string()))
+        .rule(rule)
+        .build();
+
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(script: &str) -> Vec<String> {
+        validate_render_queue(script, "render.jsx").into_iter().map(|e| e.get_message().to_string()).collect()
+    }
+
+    #[test]
+    fn test_done_items_must_be_duplicated() {
+        let script = r#"var rq = app.project.renderQueue;
+var item = rq.items.add(comp);
+app.project.renderQueue.render();
+item.render = true;
+var again = item.duplicate();
+again.render = true;
+"#;
+        assert_eq!(messages(script), vec!["'item.render' cannot be changed while the item is DONE"]);
+    }
+
+    #[test]
+    fn test_status_guards_and_read_only_status() {
+        let script = r#"var item = app.project.renderQueue.item(1);
+if (item.status == RQItemStatus.RENDERING) {
+    item.render = false;
+}
+if (item.status === RQItemStatus.UNQUEUED) {
+    item.render = true;
+}
+item.status = RQItemStatus.QUEUED;
+"#;
+        assert_eq!(messages(script), vec![
+            "'item.render' cannot be changed while the item is RENDERING",
+            "'item.status' is read-only",
+        ]);
+    }
+
+    #[test]
+    fn test_render_with_empty_queue() {
+        let script = r#"for (var i = 1; i <= app.project.renderQueue.numItems; i++) {
+    app.project.renderQueue.item(i).render = false;
+}
+app.project.renderQueue.render();
+app.project.renderQueue.items.add(comp);
+app.project.renderQueue.render();
+app.project.renderQueue.render();
+"#;
+        let errors = validate_render_queue(script, "render.jsx");
+        let lines: Vec<usize> = errors.iter().map(|e| e.get_context().line.unwrap()).collect();
+        assert_eq!(lines, vec![4, 7]);
+        assert!(errors.iter().all(|e| e.rule_id() == Some("render-queue-empty")));
+    }
+}
@@ -1,8 +1,10 @@
-use std::collections::HashMap;
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::fmt;
+use super::custom::CustomValidator;
+use super::schema::ValueSchema;
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum PropertyValueType {
     NoValue,
     ThreeDSpatial,  // Array of three floating-point positional values
@@ -43,7 +45,7 @@ impl fmt::Debug for PropertyValueType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArraySizeRule {
     pub parameter_name: String,
     pub property_value_type: PropertyValueType,
@@ -51,7 +53,7 @@ pub struct ArraySizeRule {
     pub description: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RangeValidation {
     pub parameter_name: String,
     pub min: Option<f64>,
@@ -59,6 +61,37 @@ pub struct RangeValidation {
     pub description: String,
 }
 
+impl RangeValidation {
+    pub fn between(parameter_name: &str, min: f64, max: f64) -> Self {
+        RangeValidation {
+            parameter_name: parameter_name.to_string(),
+            min: Some(min),
+            max: Some(max),
+            description: format!("{} must be between {} and {}", parameter_name, min, max),
+        }
+    }
+
+    pub fn at_least(parameter_name: &str, min: f64) -> Self {
+        RangeValidation {
+            parameter_name: parameter_name.to_string(),
+            min: Some(min),
+            max: None,
+            description: format!("{} must be at least {}", parameter_name, min),
+        }
+    }
+
+    /// A parameter without numeric limits, keeping later ranges aligned
+    pub fn unbounded(parameter_name: &str) -> Self {
+        RangeValidation {
+            parameter_name: parameter_name.to_string(),
+            min: None,
+            max: None,
+            description: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationRule {
     pub value_type: PropertyValueType,
     pub array_size: Option<usize>,
@@ -69,41 +102,8 @@ pub struct ValidationRule {
     pub dimensions_separated: bool,
     pub is_dropdown: bool,
     pub allowed_values: Option<Vec<String>>,
-    pub custom_validator: Option<Box<dyn Fn(&Value) -> Result<(), String> + Send + Sync>>,
-}
-
-impl Clone for ValidationRule {
-    fn clone(&self) -> Self {
-        ValidationRule {
-            value_type: self.value_type.clone(),
-            array_size: self.array_size,
-            range_min: self.range_min,
-            range_max: self.range_max,
-            is_spatial: self.is_spatial,
-            can_vary_over_time: self.can_vary_over_time,
-            dimensions_separated: self.dimensions_separated,
-            is_dropdown: self.is_dropdown,
-            allowed_values: self.allowed_values.clone(),
-            custom_validator: None, // We can't clone function pointers
-        }
-    }
-}
-
-impl fmt::Debug for ValidationRule {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ValidationRule")
-            .field("value_type", &self.value_type)
-            .field("array_size", &self.array_size)
-            .field("range_min", &self.range_min)
-            .field("range_max", &self.range_max)
-            .field("is_spatial", &self.is_spatial)
-            .field("can_vary_over_time", &self.can_vary_over_time)
-            .field("dimensions_separated", &self.dimensions_separated)
-            .field("is_dropdown", &self.is_dropdown)
-            .field("allowed_values", &self.allowed_values)
-            .field("custom_validator", &"<function>")
-            .finish()
-    }
+    /// Checks beyond the type, range and allowed values
+    pub custom_validator: Option<CustomValidator>,
 }
 
 impl ValidationRule {
@@ -154,18 +154,20 @@ impl ValidationRule {
         self
     }
 
-    pub fn with_validator<F>(mut self, validator: F) -> Self
-    where
-        F: Fn(&Value) -> Result<(), String> + Send + Sync + 'static,
-    {
-        self.custom_validator = Some(Box::new(validator));
+    pub fn with_validator(mut self, validator: CustomValidator) -> Self {
+        self.custom_validator = Some(validator);
         self
     }
 
     pub fn validate(&self, value: &Value) -> Result<(), String> {
-        // Use custom validator if available
-        if let Some(ref validator) = self.custom_validator {
-            return validator(value);
+        self.validate_in(value, &Map::new())
+    }
+
+    /// Validates `value` on an object whose other known property values are
+    /// `siblings`, which custom validators may refer to
+    pub fn validate_in(&self, value: &Value, siblings: &Map<String, Value>) -> Result<(), String> {
+        if let Some(validator) = &self.custom_validator {
+            validator.validate_in(value, siblings)?;
         }
 
         // Basic type validation based on value_type
@@ -220,7 +222,7 @@ impl ValidationRule {
                         // Validate color range [0.0, 1.0]
                         for v in arr {
                             if let Some(num) = v.as_f64() {
-                                if num < 0.0 || num > 1.0 {
+                                if !(0.0..=1.0).contains(&num) {
                                     return Err(format!("Color component {} is outside range [0.0, 1.0]", num));
                                 }
                             }
@@ -238,17 +240,26 @@ impl ValidationRule {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MethodValidation {
     pub param_count: usize,
     pub param_types: Vec<PropertyValueType>,
     pub optional_params: Vec<PropertyValueType>,
     pub array_sizes: Option<Vec<ArraySizeRule>>,
     pub param_ranges: Option<Vec<RangeValidation>>,
+    /// Structure of composite arguments such as `KeyframeEase` arrays, in
+    /// parameter order; `None` entries are checked by type alone
+    pub param_schemas: Option<Vec<Option<ValueSchema>>>,
     pub property_type_requirements: Option<Vec<PropertyValueType>>,
     pub temporal_dimensions: Option<usize>,
     pub is_spatial: bool,
     pub requires_expression: bool,
+    /// Class of the returned object, e.g. `CompItem` for `ItemCollection.addComp`
+    pub return_type: Option<String>,
+    /// Further signatures of an overloaded method, such as `setValue` for
+    /// each kind of property value
+    #[serde(default)]
+    pub overloads: Vec<MethodValidation>,
 }
 
 impl MethodValidation {
@@ -259,10 +270,13 @@ impl MethodValidation {
             optional_params: Vec::new(),
             array_sizes: None,
             param_ranges: None,
+            param_schemas: None,
             property_type_requirements: None,
             temporal_dimensions: None,
             is_spatial: false,
             requires_expression: false,
+            return_type: None,
+            overloads: Vec::new(),
         }
     }
 
@@ -281,11 +295,18 @@ impl MethodValidation {
         self
     }
 
+    /// Numeric limits per parameter, in parameter order (required, then optional)
     pub fn with_param_ranges(mut self, ranges: Vec<RangeValidation>) -> Self {
         self.param_ranges = Some(ranges);
         self
     }
 
+    /// Structure of composite arguments, in parameter order
+    pub fn with_param_schemas(mut self, schemas: Vec<Option<ValueSchema>>) -> Self {
+        self.param_schemas = Some(schemas);
+        self
+    }
+
     pub fn with_spatial(mut self) -> Self {
         self.is_spatial = true;
         self
@@ -300,4 +321,87 @@ impl MethodValidation {
         self.requires_expression = true;
         self
     }
+
+    pub fn with_return_type(mut self, class_name: &str) -> Self {
+        self.return_type = Some(class_name.to_string());
+        self
+    }
+
+    /// Adds another signature the method can be called with
+    pub fn with_overload(mut self, overload: MethodValidation) -> Self {
+        self.overloads.push(overload);
+        self
+    }
+
+    /// This signature followed by its overloads
+    pub fn signatures(&self) -> impl Iterator<Item = &MethodValidation> {
+        std::iter::once(self).chain(self.overloads.iter())
+    }
+
+    /// Resolves a call with `arg_count` arguments to a signature, given the
+    /// problems `check` finds with the call against each one. The first
+    /// signature without problems is the match; when there is none, the
+    /// closest signature is returned with its problems: one accepting the
+    /// argument count, with the fewest problems.
+    pub fn resolve<T>(
+        &self,
+        arg_count: usize,
+        mut check: impl FnMut(&MethodValidation) -> Vec<T>,
+    ) -> Result<&MethodValidation, (&MethodValidation, Vec<T>)> {
+        let mut closest: Option<(&MethodValidation, Vec<T>)> = None;
+        for signature in self.signatures() {
+            let problems = check(signature);
+            if problems.is_empty() {
+                return Ok(signature);
+            }
+            let distance = |signature: &MethodValidation, problems: &[T]| (!signature.accepts_arg_count(arg_count), problems.len());
+            if closest.as_ref().is_none_or(|(best, best_problems)| distance(signature, &problems) < distance(best, best_problems)) {
+                closest = Some((signature, problems));
+            }
+        }
+        Err(closest.expect("a method has at least one signature"))
+    }
+
+    /// Whether the parameter list is modeled, so argument counts and literal
+    /// types can be checked
+    pub fn has_signature(&self) -> bool {
+        !self.param_types.is_empty() || !self.optional_params.is_empty() || self.return_type.is_some()
+    }
+
+    pub fn accepts_arg_count(&self, count: usize) -> bool {
+        count >= self.param_count && count <= self.param_count + self.optional_params.len()
+    }
+
+    /// Range of the parameter at `index`, if one is declared
+    pub fn param_range(&self, index: usize) -> Option<&RangeValidation> {
+        self.param_ranges.as_ref()?.get(index)
+    }
+
+    /// Schema of the parameter at `index`, if one is declared
+    pub fn param_schema(&self, index: usize) -> Option<&ValueSchema> {
+        self.param_schemas.as_ref()?.get(index)?.as_ref()
+    }
+
+    /// A property rule for the argument at `index` carrying its declared
+    /// range and schema, so arguments are checked like property values
+    pub fn param_rule(&self, index: usize) -> Option<ValidationRule> {
+        let mut rule = ValidationRule::simple(self.param_type(index)?.clone());
+        if let Some(range) = self.param_range(index) {
+            rule.range_min = range.min;
+            rule.range_max = range.max;
+        }
+        if let Some(schema) = self.param_schema(index) {
+            rule.custom_validator = Some(CustomValidator::Schema(schema.clone()));
+        }
+        Some(rule)
+    }
+
+    /// Expected type of the argument at `index`, required parameters first
+    pub fn param_type(&self, index: usize) -> Option<&PropertyValueType> {
+        if index < self.param_count {
+            self.param_types.get(index)
+        } else {
+            self.optional_params.get(index - self.param_count)
+        }
+    }
 } 
\ No newline at end of file
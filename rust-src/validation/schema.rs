@@ -0,0 +1,387 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use super::script::lexer::{matching_bracket, significant_tokens, Token, TokenKind};
+
+/// Key holding the class of a `new Class(...)` literal read by [`literal_value`]
+pub const CONSTRUCTED_CLASS: &str = "$class";
+/// Key holding the constructor arguments of a `new Class(...)` literal
+pub const CONSTRUCTED_ARGS: &str = "$args";
+
+/// Shape of a structured value, such as the `KeyframeEase` arrays of
+/// `setTemporalEaseAtKey` or gradient color stops, checked field by field
+/// rather than as an opaque object. `null` stands for a value that is not
+/// known, and passes every schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueSchema {
+    Any,
+    Boolean,
+    String,
+    Number {
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    /// Elements that all match `items`, `min_len` to `max_len` of them
+    Array {
+        items: Box<ValueSchema>,
+        #[serde(default)]
+        min_len: Option<usize>,
+        #[serde(default)]
+        max_len: Option<usize>,
+    },
+    /// An object with named fields. Instances of `class` built with
+    /// `new class(...)` pass their arguments to the fields in order.
+    Object {
+        #[serde(default)]
+        class: Option<String>,
+        fields: Vec<FieldSchema>,
+    },
+    /// An options object literal such as `{width: 1920, height: 1080}`,
+    /// whose keys must all be declared fields
+    Options(Vec<FieldSchema>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldSchema {
+    pub name: String,
+    pub schema: ValueSchema,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl FieldSchema {
+    pub fn new(name: &str, schema: ValueSchema) -> Self {
+        FieldSchema { name: name.to_string(), schema, optional: false }
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+}
+
+impl ValueSchema {
+    pub fn number() -> Self {
+        ValueSchema::Number { min: None, max: None }
+    }
+
+    pub fn number_in(min: f64, max: f64) -> Self {
+        ValueSchema::Number { min: Some(min), max: Some(max) }
+    }
+
+    pub fn array_of(items: ValueSchema, min_len: usize, max_len: usize) -> Self {
+        ValueSchema::Array { items: Box::new(items), min_len: Some(min_len), max_len: Some(max_len) }
+    }
+
+    /// Options with the given fields, all of them optional
+    pub fn options(fields: Vec<FieldSchema>) -> Self {
+        ValueSchema::Options(fields.into_iter().map(FieldSchema::optional).collect())
+    }
+
+    /// `new KeyframeEase(speed, influence)`, one per dimension of the
+    /// property: 1 to 3, or 1 for spatial properties
+    pub fn keyframe_eases() -> Self {
+        let ease = ValueSchema::Object {
+            class: Some("KeyframeEase".to_string()),
+            fields: vec![
+                FieldSchema::new("speed", ValueSchema::number()),
+                FieldSchema::new("influence", ValueSchema::number_in(0.1, 100.0)),
+            ],
+        };
+        Self::array_of(ease, 1, 3)
+    }
+
+    /// Gradient color stops: a location and an `[r, g, b]` or `[r, g, b, a]`
+    /// color in 0-1, and optionally the midpoint to the next stop
+    pub fn gradient_stops() -> Self {
+        let stop = ValueSchema::Object {
+            class: None,
+            fields: vec![
+                FieldSchema::new("location", ValueSchema::number_in(0.0, 1.0)),
+                FieldSchema::new("color", Self::array_of(ValueSchema::number_in(0.0, 1.0), 3, 4)),
+                FieldSchema::new("midpoint", ValueSchema::number_in(0.0, 1.0)).optional(),
+            ],
+        };
+        ValueSchema::Array { items: Box::new(stop), min_len: Some(2), max_len: None }
+    }
+
+    pub fn validate(&self, value: &Value) -> Result<(), String> {
+        self.check(value, "")
+    }
+
+    /// Checks `value`, found at `path` (`[1].influence`) in the value being validated
+    fn check(&self, value: &Value, path: &str) -> Result<(), String> {
+        if value.is_null() {
+            return Ok(());
+        }
+        let at = if path.is_empty() { "Value".to_string() } else { path.to_string() };
+        match self {
+            ValueSchema::Any => Ok(()),
+            ValueSchema::Boolean if value.is_boolean() => Ok(()),
+            ValueSchema::Boolean => Err(format!("{} must be a boolean, got {}", at, value)),
+            ValueSchema::String if value.is_string() => Ok(()),
+            ValueSchema::String => Err(format!("{} must be a string, got {}", at, value)),
+            ValueSchema::Number { min, max } => {
+                let number = value.as_f64().ok_or_else(|| format!("{} must be a number, got {}", at, describe(value)))?;
+                match (min, max) {
+                    (Some(min), Some(max)) if number < *min || number > *max => {
+                        Err(format!("{} must be between {} and {}, got {}", at, min, max, number))
+                    }
+                    (Some(min), None) if number < *min => Err(format!("{} must be at least {}, got {}", at, min, number)),
+                    (None, Some(max)) if number > *max => Err(format!("{} must be at most {}, got {}", at, max, number)),
+                    _ => Ok(()),
+                }
+            }
+            ValueSchema::Array { items, min_len, max_len } => {
+                let elements = value.as_array().ok_or_else(|| format!("{} must be an array, got {}", at, describe(value)))?;
+                if min_len.is_some_and(|min| elements.len() < min) || max_len.is_some_and(|max| elements.len() > max) {
+                    let expected = match (min_len, max_len) {
+                        (Some(min), Some(max)) if min == max => format!("{}", min),
+                        (Some(min), Some(max)) => format!("{} to {}", min, max),
+                        (Some(min), None) => format!("at least {}", min),
+                        (None, Some(max)) => format!("at most {}", max),
+                        (None, None) => unreachable!(),
+                    };
+                    return Err(format!("{} must have {} elements, got {}", at, expected, elements.len()));
+                }
+                elements.iter().enumerate().try_for_each(|(i, element)| items.check(element, &format!("{}[{}]", path, i)))
+            }
+            ValueSchema::Object { class, fields } => {
+                let object = match value.as_object() {
+                    Some(object) => object,
+                    None => return Err(format!("{} must be {}, got {}", at, object_name(class.as_deref()), describe(value))),
+                };
+                if let (Some(class), Some(built)) = (class, object.get(CONSTRUCTED_CLASS).and_then(Value::as_str)) {
+                    if class != built {
+                        return Err(format!("{} must be {}, got a {}", at, object_name(Some(class)), built));
+                    }
+                }
+                let args = object.get(CONSTRUCTED_ARGS).and_then(Value::as_array);
+                if let Some(args) = args.filter(|args| args.len() > fields.len()) {
+                    return Err(format!("{} takes {} arguments, got {}", at, fields.len(), args.len()));
+                }
+                check_fields(fields, path, |i, field| match args {
+                    Some(args) => args.get(i),
+                    None => object.get(&field.name),
+                })
+            }
+            ValueSchema::Options(fields) => {
+                let object = match value.as_object().filter(|object| !object.contains_key(CONSTRUCTED_CLASS)) {
+                    Some(object) => object,
+                    None => return Err(format!("{} must be an options object, got {}", at, describe(value))),
+                };
+                if let Some(key) = object.keys().find(|key| !fields.iter().any(|field| field.name == **key)) {
+                    let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+                    let hint = closest_name(key, &names).map_or(String::new(), |name| format!("; did you mean '{}'?", name));
+                    return Err(format!("{} has no option '{}'{}", at, key, hint));
+                }
+                check_fields(fields, path, |_, field| object.get(&field.name))
+            }
+        }
+    }
+}
+
+/// Checks each field against the value `get` finds for it
+fn check_fields<'a>(
+    fields: &[FieldSchema],
+    path: &str,
+    get: impl Fn(usize, &FieldSchema) -> Option<&'a Value>,
+) -> Result<(), String> {
+    for (i, field) in fields.iter().enumerate() {
+        let field_path = format!("{}.{}", if path.is_empty() { "value" } else { path }, field.name);
+        match get(i, field) {
+            Some(field_value) => field.schema.check(field_value, &field_path)?,
+            None if field.optional => {}
+            None => return Err(format!("{} is missing", field_path)),
+        }
+    }
+    Ok(())
+}
+
+/// The declared name a misspelt key most likely meant
+fn closest_name<'a>(key: &str, names: &[&'a str]) -> Option<&'a str> {
+    names.iter()
+        .map(|name| (strsim::levenshtein(&key.to_lowercase(), &name.to_lowercase()), *name))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, name)| (*distance, *name))
+        .map(|(_, name)| name)
+}
+
+fn object_name(class: Option<&str>) -> String {
+    match class {
+        Some(class) => format!("a {}", class),
+        None => "an object".to_string(),
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value.get(CONSTRUCTED_CLASS).and_then(Value::as_str) {
+        Some(class) => format!("a {}", class),
+        None => value.to_string(),
+    }
+}
+
+/// The value of a JavaScript literal: numbers, strings, booleans, arrays,
+/// object literals and `new Class(...)`, which becomes an object with
+/// [`CONSTRUCTED_CLASS`] and [`CONSTRUCTED_ARGS`]. Parts that are not
+/// literals, such as variables, read as `null`. `None` when the whole
+/// source is not a literal.
+pub fn literal_value(source: &str) -> Option<Value> {
+    let tokens = significant_tokens(source);
+    let mut pos = 0;
+    let value = literal(&tokens, &mut pos);
+    (pos == tokens.len() && !value.is_null()).then_some(value)
+}
+
+fn literal(tokens: &[Token], pos: &mut usize) -> Value {
+    let token = match tokens.get(*pos) {
+        Some(token) => token,
+        None => return Value::Null,
+    };
+    let start = *pos;
+    *pos += 1;
+    let value = match token.kind {
+        TokenKind::Number => number(&token.text, false),
+        TokenKind::String => Some(Value::String(token.text.get(1..token.text.len().saturating_sub(1)).unwrap_or("").to_string())),
+        _ if token.is_punct("-") => match tokens.get(*pos) {
+            Some(next) if next.kind == TokenKind::Number => {
+                *pos += 1;
+                number(&next.text, true)
+            }
+            _ => None,
+        },
+        _ if token.text == "true" || token.text == "false" => Some(Value::Bool(token.text == "true")),
+        _ if token.is_punct("[") => list(tokens, pos, "]").map(Value::Array),
+        _ if token.is_punct("{") => object(tokens, pos),
+        _ if token.is_keyword("new") => match tokens.get(*pos..*pos + 2) {
+            Some([class, open]) if class.is_identifier() && open.is_punct("(") => {
+                *pos += 2;
+                list(tokens, pos, ")").map(|args| {
+                    let mut object = Map::new();
+                    object.insert(CONSTRUCTED_CLASS.to_string(), Value::String(class.text.clone()));
+                    object.insert(CONSTRUCTED_ARGS.to_string(), Value::Array(args));
+                    Value::Object(object)
+                })
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    let ends = tokens.get(*pos).is_none_or(|t| t.is_punct(",") || t.is_punct("]") || t.is_punct(")") || t.is_punct("}"));
+    match value {
+        Some(value) if ends => value,
+        // Not a literal, or part of a larger expression: skip to the end of the element
+        _ => {
+            *pos = start;
+            skip_expression(tokens, pos);
+            Value::Null
+        }
+    }
+}
+
+/// A number literal, kept integral when it is written as an integer
+fn number(text: &str, negative: bool) -> Option<Value> {
+    let sign = if negative { -1 } else { 1 };
+    match text.parse::<i64>() {
+        Ok(integer) => Some(Value::from(sign * integer)),
+        Err(_) => text.parse::<f64>().ok().and_then(|n| serde_json::Number::from_f64(sign as f64 * n)).map(Value::Number),
+    }
+}
+
+/// Elements up to the `close` matching an already consumed opener
+fn list(tokens: &[Token], pos: &mut usize, close: &str) -> Option<Vec<Value>> {
+    let mut items = Vec::new();
+    loop {
+        if tokens.get(*pos)?.is_punct(close) {
+            *pos += 1;
+            return Some(items);
+        }
+        items.push(literal(tokens, pos));
+        if tokens.get(*pos)?.is_punct(",") {
+            *pos += 1;
+        }
+    }
+}
+
+/// `{ name: value, "other": value }` after its opening brace
+fn object(tokens: &[Token], pos: &mut usize) -> Option<Value> {
+    let mut object = Map::new();
+    loop {
+        let key = tokens.get(*pos)?;
+        if key.is_punct("}") {
+            *pos += 1;
+            return Some(Value::Object(object));
+        }
+        let name = match key.kind {
+            TokenKind::String => key.text.get(1..key.text.len().saturating_sub(1))?.to_string(),
+            _ => key.text.clone(),
+        };
+        if !tokens.get(*pos + 1)?.is_punct(":") {
+            return None;
+        }
+        *pos += 2;
+        object.insert(name, literal(tokens, pos));
+        if tokens.get(*pos)?.is_punct(",") {
+            *pos += 1;
+        }
+    }
+}
+
+/// Moves past one element: up to a `,` or closing bracket outside nested brackets
+fn skip_expression(tokens: &[Token], pos: &mut usize) {
+    while let Some(token) = tokens.get(*pos) {
+        if token.is_punct(",") || token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            return;
+        }
+        *pos = match matching_bracket(tokens, *pos) {
+            Some(close) => close + 1,
+            None if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") => tokens.len(),
+            None => *pos + 1,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_value_schemas() {
+        let eases = ValueSchema::keyframe_eases();
+        let check = |source: &str| eases.validate(&literal_value(source).unwrap());
+        assert!(check("[new KeyframeEase(0, 33.3), new KeyframeEase(-2.5, 75)]").is_ok());
+        assert!(check("[new KeyframeEase(speed, influence)]").is_ok());
+        assert_eq!(check("[new KeyframeEase(0, 33), new KeyframeEase(0, 150)]").unwrap_err(), "[1].influence must be between 0.1 and 100, got 150");
+        assert_eq!(check("[0.5, 75]").unwrap_err(), "[0] must be a KeyframeEase, got 0.5");
+        assert_eq!(check("[new Shape()]").unwrap_err(), "[0] must be a KeyframeEase, got a Shape");
+        assert_eq!(check("[new KeyframeEase(0)]").unwrap_err(), "[0].influence is missing");
+        assert_eq!(check("[]").unwrap_err(), "Value must have 1 to 3 elements, got 0");
+        assert_eq!(literal_value("ease.concat(other)"), None);
+
+        let stops = ValueSchema::gradient_stops();
+        assert!(stops.validate(&json!([
+            { "location": 0, "color": [1, 0, 0] },
+            { "location": 1, "color": [0, 0, 1, 0.5], "midpoint": 0.5 },
+        ])).is_ok());
+        assert_eq!(
+            stops.validate(&literal_value("[{location: 0, color: [1, 0, 0]}, {location: 1, color: [0, 0, 255]}]").unwrap()).unwrap_err(),
+            "[1].color[2] must be between 0 and 1, got 255"
+        );
+        assert_eq!(stops.validate(&json!([{ "color": [1, 1, 1] }, { "location": 1, "color": [0, 0, 0] }])).unwrap_err(), "[0].location is missing");
+
+        let size = ValueSchema::options(vec![
+            FieldSchema::new("width", ValueSchema::number_in(4.0, 30000.0)),
+            FieldSchema::new("height", ValueSchema::number_in(4.0, 30000.0)),
+            FieldSchema::new("name", ValueSchema::String),
+        ]);
+        let check = |source: &str| size.validate(&literal_value(source).unwrap());
+        assert!(check("{width: 1920, height: h}").is_ok());
+        assert_eq!(check("{widht: 1920}").unwrap_err(), "Value has no option 'widht'; did you mean 'width'?");
+        assert_eq!(check("{width: 1920, name: 42}").unwrap_err(), "value.name must be a string, got 42");
+        assert_eq!(check("{'height': 2}").unwrap_err(), "value.height must be between 4 and 30000, got 2");
+        assert_eq!(check("new Size(1920, 1080)").unwrap_err(), "Value must be an options object, got a Size");
+    }
+}
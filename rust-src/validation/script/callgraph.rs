@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use serde::Serialize;
+use super::inference::infer_variable_types;
+use super::lexer::Token;
+use super::scope::analyze_scopes;
+
+/// Whether a call targets a function defined in the script or the AE API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallKind {
+    User,
+    Api,
+}
+
+/// A function defined in the script
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunctionNode {
+    pub name: String,
+    pub line: usize,
+}
+
+/// All calls from one caller to one callee
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CallEdge {
+    pub caller: String,
+    pub callee: String,
+    pub kind: CallKind,
+    /// Lines of every call site
+    pub lines: Vec<usize>,
+}
+
+/// User-function call graph and AE API usage of a script
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CallGraph {
+    pub functions: Vec<FunctionNode>,
+    pub edges: Vec<CallEdge>,
+}
+
+impl CallGraph {
+    pub fn build(script: &str) -> Self {
+        let analysis = analyze_scopes(script);
+        let variable_types = infer_variable_types(script);
+        let tokens = &analysis.tokens;
+
+        let functions: Vec<FunctionNode> = (1..analysis.scopes.len())
+            .map(|scope| FunctionNode {
+                name: analysis.scope_label(scope),
+                line: analysis.scopes[scope].line,
+            })
+            .collect();
+        let user_functions: HashSet<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+
+        let mut edges: BTreeMap<(String, String, CallKind), Vec<usize>> = BTreeMap::new();
+        for (i, token) in tokens.iter().enumerate() {
+            if !token.is_identifier() || !tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+                continue;
+            }
+            let prev = i.checked_sub(1).map(|p| &tokens[p]);
+            if prev.is_some_and(|p| p.is_keyword("function")) {
+                continue;
+            }
+
+            let chain = receiver_chain(tokens, i);
+            let callee = if chain.is_empty() {
+                if !user_functions.contains(token.text.as_str()) {
+                    continue;
+                }
+                (token.text.clone(), CallKind::User)
+            } else {
+                let qualified = format!("{}.{}", chain.join("."), token.text);
+                if user_functions.contains(qualified.as_str()) {
+                    (qualified, CallKind::User)
+                } else if chain[0] == "app" {
+                    (qualified, CallKind::Api)
+                } else if let Some(class) = variable_types.get(&chain[0]) {
+                    let mut path = vec![class.clone()];
+                    path.extend(chain[1..].iter().cloned());
+                    path.push(token.text.clone());
+                    (path.join("."), CallKind::Api)
+                } else {
+                    continue;
+                }
+            };
+
+            let caller = analysis.scope_label(analysis.token_scopes[i]);
+            edges.entry((caller, callee.0, callee.1)).or_default().push(token.line);
+        }
+
+        CallGraph {
+            functions,
+            edges: edges.into_iter()
+                .map(|((caller, callee, kind), lines)| CallEdge { caller, callee, kind, lines })
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Graphviz rendering; API members are drawn as dashed ellipses
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph calls {\n    rankdir=LR;\n    node [shape=box];\n");
+        output.push_str("    \"<global>\";\n");
+        for function in &self.functions {
+            output.push_str(&format!("    {};\n", quote(&function.name)));
+        }
+        let api_members: BTreeSet<&str> = self.edges.iter()
+            .filter(|edge| edge.kind == CallKind::Api)
+            .map(|edge| edge.callee.as_str())
+            .collect();
+        for member in api_members {
+            output.push_str(&format!("    {} [shape=ellipse, style=dashed];\n", quote(member)));
+        }
+        for edge in &self.edges {
+            let label = if edge.lines.len() > 1 { format!(" [label=\"{}\"]", edge.lines.len()) } else { String::new() };
+            output.push_str(&format!("    {} -> {}{};\n", quote(&edge.caller), quote(&edge.callee), label));
+        }
+        output.push_str("}\n");
+        output
+    }
+}
+
+/// Identifiers of a dotted receiver such as `app.project.items` ending just before `index`
+fn receiver_chain(tokens: &[Token], index: usize) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut i = index;
+    while i >= 2 && tokens[i - 1].is_punct(".") && tokens[i - 2].is_identifier() {
+        chain.push(tokens[i - 2].text.clone());
+        i -= 2;
+    }
+    // Calls on computed receivers like `comp.layer(1).remove()` are not attributed
+    if i >= 1 && tokens[i - 1].is_punct(".") {
+        return vec!["?".to_string()];
+    }
+    chain.reverse();
+    chain
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"
+        function main() {
+            var comp = app.project.activeItem;
+            app.beginUndoGroup("Build");
+            buildLayers(comp);
+            buildLayers(comp);
+            app.endUndoGroup();
+        }
+        function buildLayers(comp) {
+            var layer = comp.layers.addNull();
+            layer.moveToEnd();
+            helpers.log("done");
+        }
+        helpers.log = function (msg) { $.writeln(msg); };
+        main();
+    "#;
+
+    #[test]
+    fn test_user_and_api_edges() {
+        let graph = CallGraph::build(SCRIPT);
+        let names: Vec<&str> = graph.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "buildLayers", "helpers.log"]);
+
+        let edge = |caller: &str, callee: &str| graph.edges.iter().find(|e| e.caller == caller && e.callee == callee);
+        assert_eq!(edge("main", "buildLayers").unwrap().lines.len(), 2);
+        assert_eq!(edge("main", "app.beginUndoGroup").unwrap().kind, CallKind::Api);
+        assert_eq!(edge("buildLayers", "CompItem.layers.addNull").unwrap().kind, CallKind::Api);
+        assert_eq!(edge("buildLayers", "Layer.moveToEnd").unwrap().kind, CallKind::Api);
+        assert_eq!(edge("buildLayers", "helpers.log").unwrap().kind, CallKind::User);
+        assert!(edge("<global>", "main").is_some());
+    }
+
+    #[test]
+    fn test_dot_output() {
+        let dot = CallGraph::build(SCRIPT).to_dot();
+        assert!(dot.starts_with("digraph calls {"));
+        assert!(dot.contains("\"main\" -> \"buildLayers\" [label=\"2\"];"));
+        assert!(dot.contains("\"app.endUndoGroup\" [shape=ellipse, style=dashed];"));
+    }
+}
@@ -0,0 +1,297 @@
+use super::lexer::{tokenize, Token, TokenKind};
+
+/// Quote style used for string literals when formatting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Keep each literal's original quotes
+    Preserve,
+    Single,
+    Double,
+}
+
+/// Options controlling how scripts are pretty-printed
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Number of spaces per indentation level (ignored when `use_tabs` is set)
+    pub indent_width: usize,
+    pub use_tabs: bool,
+    pub quote_style: QuoteStyle,
+    /// Maximum number of consecutive blank lines kept from the source
+    pub max_blank_lines: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            use_tabs: false,
+            quote_style: QuoteStyle::Preserve,
+            max_blank_lines: 1,
+        }
+    }
+}
+
+/// Pretty-prints an ExtendScript source.
+///
+/// Line structure and comments are kept from the original; indentation is
+/// recomputed from bracket nesting and spacing between tokens is normalized.
+pub fn format_script(source: &str, options: &FormatOptions) -> String {
+    let tokens = tokenize(source);
+    let mut formatter = Formatter::new(options);
+    for (i, token) in tokens.iter().enumerate() {
+        formatter.emit(token, i > 0);
+    }
+    formatter.finish()
+}
+
+struct Formatter<'a> {
+    options: &'a FormatOptions,
+    lines: Vec<String>,
+    current: String,
+    depth: usize,
+    /// Open `?` operators still waiting for their `:`
+    pending_ternaries: usize,
+    prev: Option<Token>,
+    prev_was_prefix_op: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(options: &'a FormatOptions) -> Self {
+        Self {
+            options,
+            lines: Vec::new(),
+            current: String::new(),
+            depth: 0,
+            pending_ternaries: 0,
+            prev: None,
+            prev_was_prefix_op: false,
+        }
+    }
+
+    fn emit(&mut self, token: &Token, has_prev: bool) {
+        let is_closer = token.is_punct("}") || token.is_punct(")") || token.is_punct("]");
+
+        if has_prev && token.newlines_before > 0 {
+            self.end_line();
+            let blank = (token.newlines_before - 1).min(self.options.max_blank_lines);
+            for _ in 0..blank {
+                self.lines.push(String::new());
+            }
+        }
+
+        if self.current.is_empty() {
+            let level = if is_closer { self.depth.saturating_sub(1) } else { self.depth };
+            self.current.push_str(&self.indent(level));
+        } else if self.needs_space(token) {
+            self.current.push(' ');
+        }
+
+        let is_prefix_op = self.is_prefix_operator(token);
+        if token.is_punct("?") {
+            self.pending_ternaries += 1;
+        } else if token.is_punct(":") && self.pending_ternaries > 0 && !is_prefix_op {
+            self.pending_ternaries -= 1;
+        }
+
+        match token.kind {
+            TokenKind::String => self.current.push_str(&requote(&token.text, self.options.quote_style)),
+            _ => self.current.push_str(&token.text),
+        }
+
+        if token.is_punct("{") || token.is_punct("(") || token.is_punct("[") {
+            self.depth += 1;
+        } else if is_closer {
+            self.depth = self.depth.saturating_sub(1);
+        }
+
+        self.prev_was_prefix_op = is_prefix_op;
+        self.prev = Some(token.clone());
+    }
+
+    fn finish(mut self) -> String {
+        self.end_line();
+        while self.lines.last().is_some_and(|l| l.is_empty()) {
+            self.lines.pop();
+        }
+        let mut output = self.lines.join("\n");
+        output.push('\n');
+        output
+    }
+
+    fn end_line(&mut self) {
+        let line = std::mem::take(&mut self.current);
+        self.lines.push(line.trim_end().to_string());
+    }
+
+    fn indent(&self, level: usize) -> String {
+        if self.options.use_tabs {
+            "\t".repeat(level)
+        } else {
+            " ".repeat(level * self.options.indent_width)
+        }
+    }
+
+    fn is_prefix_operator(&self, token: &Token) -> bool {
+        if token.kind != TokenKind::Punctuator {
+            return false;
+        }
+        if !matches!(token.text.as_str(), "+" | "-" | "!" | "~" | "++" | "--") {
+            return false;
+        }
+        match &self.prev {
+            None => true,
+            Some(prev) => match prev.kind {
+                TokenKind::Identifier | TokenKind::Number | TokenKind::String
+                | TokenKind::Template | TokenKind::Regex => false,
+                TokenKind::Keyword => !matches!(prev.text.as_str(), "this" | "true" | "false" | "null" | "undefined"),
+                TokenKind::Punctuator => !matches!(prev.text.as_str(), ")" | "]" | "}"),
+                _ => true,
+            },
+        }
+    }
+
+    fn needs_space(&self, next: &Token) -> bool {
+        let prev = match &self.prev {
+            Some(prev) => prev,
+            None => return false,
+        };
+
+        if next.is_trivia() || prev.is_trivia() {
+            return true;
+        }
+        if prev.is_punct("(") || prev.is_punct("[") {
+            return false;
+        }
+        if prev.is_punct("{") {
+            return !next.is_punct("}");
+        }
+        if next.is_punct(")") || next.is_punct("]") || next.is_punct(",") || next.is_punct(";") {
+            return false;
+        }
+        if next.is_punct("}") {
+            return true;
+        }
+        if next.is_punct(".") || prev.is_punct(".") || next.is_punct("?.") || prev.is_punct("?.") {
+            return false;
+        }
+        if self.prev_was_prefix_op {
+            // `- -a` and `+ +b` would turn into the `--` and `++` operators
+            return match prev.text.chars().last() {
+                Some(op @ ('+' | '-')) => next.text.starts_with(op),
+                _ => false,
+            };
+        }
+        if (next.is_punct("++") || next.is_punct("--")) && !self.is_prefix_operator(next) {
+            return false;
+        }
+        if next.is_punct("(") {
+            return match prev.kind {
+                TokenKind::Keyword => prev.text != "function",
+                TokenKind::Identifier | TokenKind::String => false,
+                TokenKind::Punctuator => !matches!(prev.text.as_str(), ")" | "]"),
+                _ => true,
+            };
+        }
+        if next.is_punct("[") {
+            return match prev.kind {
+                TokenKind::Identifier | TokenKind::String => false,
+                TokenKind::Punctuator => !matches!(prev.text.as_str(), ")" | "]"),
+                _ => true,
+            };
+        }
+        if next.is_punct(":") {
+            return self.pending_ternaries > 0;
+        }
+        true
+    }
+}
+
+/// Rewrites a string literal to the requested quote style when that needs no new escapes
+fn requote(literal: &str, style: QuoteStyle) -> String {
+    let target = match style {
+        QuoteStyle::Preserve => return literal.to_string(),
+        QuoteStyle::Single => '\'',
+        QuoteStyle::Double => '"',
+    };
+
+    let mut chars = literal.chars();
+    let original = match chars.next() {
+        Some(q) if q == '\'' || q == '"' => q,
+        _ => return literal.to_string(),
+    };
+    if original == target || literal.len() < 2 || !literal.ends_with(original) {
+        return literal.to_string();
+    }
+
+    let inner = &literal[1..literal.len() - 1];
+    let mut converted = String::with_capacity(literal.len());
+    converted.push(target);
+    let mut inner_chars = inner.chars();
+    while let Some(c) = inner_chars.next() {
+        if c == '\\' {
+            match inner_chars.next() {
+                Some(escaped) if escaped == original => converted.push(original),
+                Some(escaped) => {
+                    converted.push('\\');
+                    converted.push(escaped);
+                }
+                None => return literal.to_string(),
+            }
+        } else if c == target {
+            // Would need a new escape; keep the literal untouched
+            return literal.to_string();
+        } else {
+            converted.push(c);
+        }
+    }
+    converted.push(target);
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reindents_blocks() {
+        let source = "if (comp) {\nvar layer = comp.layer(1);\n}\n";
+        let formatted = format_script(source, &FormatOptions::default());
+        assert_eq!(formatted, "if (comp) {\n    var layer = comp.layer(1);\n}\n");
+    }
+
+    #[test]
+    fn test_normalizes_spacing() {
+        let source = "var x=a+b*(c-1);\nfor(var i=0;i<n;i++){x+=i;}\n";
+        let formatted = format_script(source, &FormatOptions::default());
+        assert_eq!(formatted, "var x = a + b * (c - 1);\nfor (var i = 0; i < n; i++) { x += i; }\n");
+    }
+
+    #[test]
+    fn test_keeps_space_between_repeated_prefix_operators() {
+        let source = "var a = - -b;\nvar c = + +d;\nvar e = - --f;\nvar g = -h;\n";
+        let formatted = format_script(source, &FormatOptions::default());
+        assert_eq!(formatted, source);
+        assert_eq!(format_script("var a = -  -b;\n", &FormatOptions::default()), "var a = - -b;\n");
+    }
+
+    #[test]
+    fn test_preserves_comments() {
+        let source = "// header\nvar a = 1; // trailing\n/* block */\n";
+        let formatted = format_script(source, &FormatOptions::default());
+        assert_eq!(formatted, source);
+    }
+
+    #[test]
+    fn test_quote_style() {
+        let options = FormatOptions { quote_style: QuoteStyle::Single, ..FormatOptions::default() };
+        let formatted = format_script("var a = \"x\"; var b = \"it's\";\n", &options);
+        assert_eq!(formatted, "var a = 'x'; var b = \"it's\";\n");
+    }
+
+    #[test]
+    fn test_tabs_and_object_literals() {
+        let options = FormatOptions { use_tabs: true, ..FormatOptions::default() };
+        let formatted = format_script("var o = {\na: 1,\nb: c ? 2 : 3\n};\n", &options);
+        assert_eq!(formatted, "var o = {\n\ta: 1,\n\tb: c ? 2 : 3\n};\n");
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use regex::Regex;
+use lazy_static::lazy_static;
+use super::lexer::{matching_bracket, significant_tokens, Token, TokenKind};
+
+lazy_static! {
+    static ref ASSIGNMENT_RE: Regex = Regex::new(r"(?m)(?:\bvar\s+)?\b(\w+)\s*=\s*([^;=\n][^;\n]*)").unwrap();
+    static ref NEW_RE: Regex = Regex::new(r"^new\s+(\w+)\s*\(").unwrap();
+    static ref CALL_RE: Regex = Regex::new(r"^(\w+)\s*\(").unwrap();
+    /// A `/** ... */` comment and the function or variable declared after it
+    static ref JSDOC_RE: Regex = Regex::new(r"(?s)/\*\*(.*?)\*/\s*(?:function\s+(\w+)|(?:var|let|const)\s+(\w+))?").unwrap();
+    static ref PARAM_TAG_RE: Regex = Regex::new(r"@param\s+\{[?!]?(\w+)\}\s+\[?(\w+)").unwrap();
+    static ref TYPE_TAG_RE: Regex = Regex::new(r"@type\s+\{[?!]?(\w+)\}").unwrap();
+    static ref RETURNS_TAG_RE: Regex = Regex::new(r"@returns?\s+\{[?!]?(\w+)\}").unwrap();
+    /// Inline cast: `/** @type {CompItem} */ (app.project.activeItem)`
+    static ref TYPE_CAST_RE: Regex = Regex::new(r"^/\*\*\s*@type\s+\{[?!]?(\w+)\}\s*\*/").unwrap();
+}
+
+/// Receiver patterns and the class they produce, checked in order
+const INFERENCE_RULES: &[(&str, &str)] = &[
+    (r"^app\.project$", "Project"),
+    (r"\.activeItem$", "CompItem"),
+    (r"\.items\.addComp\s*\(", "CompItem"),
+    (r"\.layers\.(?:add\w*|byName|precompose)\s*\(", "Layer"),
+    (r"\.layer\s*\(", "Layer"),
+    (r"\.layers$", "LayerCollection"),
+    (r"\.items$", "ItemCollection"),
+    (r"\.(?:property|propertyGroup|effect|mask)\s*\(", "Property"),
+    (r"\.(?:transform|opacity|position|scale|rotation|anchorPoint)$", "Property"),
+    (r"\.item\s*\(", "Item"),
+    (r"^File\.(?:openDialog|saveDialog)\s*\(", "File"),
+    (r"^Folder\.selectDialog\s*\(", "Folder"),
+];
+
+lazy_static! {
+    static ref COMPILED_RULES: Vec<(Regex, &'static str)> = INFERENCE_RULES.iter()
+        .map(|(pattern, class)| (Regex::new(pattern).unwrap(), *class))
+        .collect();
+}
+
+/// Infers the API class of variables from their assignments.
+///
+/// Later assignments override earlier ones; variables whose value cannot be
+/// classified are left out. JSDoc annotations take precedence over anything
+/// inferred, so users can settle ambiguous cases explicitly.
+pub fn infer_variable_types(script: &str) -> HashMap<String, String> {
+    let annotated = jsdoc_types(script);
+    let mut types = annotated.clone();
+
+    for cap in ASSIGNMENT_RE.captures_iter(script) {
+        let name = &cap[1];
+        let value = cap[2].trim();
+        if annotated.contains_key(name) {
+            continue;
+        }
+        if let Some(class) = infer_expression_type(value, &types) {
+            types.insert(name.to_string(), class);
+        }
+    }
+
+    types
+}
+
+/// Classes declared in JSDoc comments: `@param {CompItem} comp`, `@type {Layer}`
+/// before a `var`, and `@returns {CompItem}` before a function, recorded
+/// under `name()` so calls to the function are typed.
+///
+/// Like the rest of inference, names are not scoped: a `@param` types every
+/// variable of that name.
+pub fn jsdoc_types(script: &str) -> HashMap<String, String> {
+    let mut types = HashMap::new();
+    for cap in JSDOC_RE.captures_iter(script) {
+        let comment = &cap[1];
+        for param in PARAM_TAG_RE.captures_iter(comment) {
+            types.insert(param[2].to_string(), param[1].to_string());
+        }
+        if let (Some(function), Some(returns)) = (cap.get(2), RETURNS_TAG_RE.captures(comment)) {
+            types.insert(format!("{}()", function.as_str()), returns[1].to_string());
+        }
+        if let (Some(variable), Some(declared)) = (cap.get(3), TYPE_TAG_RE.captures(comment)) {
+            types.insert(variable.as_str().to_string(), declared[1].to_string());
+        }
+    }
+    types
+}
+
+/// Infers the class produced by an expression, given already known variable types
+pub fn infer_expression_type(expr: &str, known: &HashMap<String, String>) -> Option<String> {
+    if let Some(cap) = TYPE_CAST_RE.captures(expr.trim()) {
+        return Some(cap[1].to_string());
+    }
+    let expr = expr.trim().trim_end_matches(')').trim_start_matches('(');
+    if let Some(class) = known.get(expr) {
+        return Some(class.clone());
+    }
+    if let Some(cap) = NEW_RE.captures(expr) {
+        return Some(cap[1].to_string());
+    }
+    if let Some(class) = CALL_RE.captures(expr).and_then(|cap| known.get(&format!("{}()", &cap[1]))) {
+        return Some(class.clone());
+    }
+    // The last member of a chain decides its class: `comp.layer(1).property("Opacity")`
+    // is a Property. Rules matching at the same place keep their order.
+    COMPILED_RULES.iter()
+        .filter_map(|(pattern, class)| pattern.find(expr).map(|m| (m.start(), *class)))
+        .rev()
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, class)| class.to_string())
+}
+
+/// Property values that identify a class, so comparing against them
+/// narrows like `instanceof`: `item.typeName === "Composition"`
+const DISCRIMINANTS: &[(&str, &str, &str)] = &[
+    ("typeName", "Composition", "CompItem"),
+    ("typeName", "Folder", "FolderItem"),
+    ("typeName", "Footage", "FootageItem"),
+    ("matchName", "ADBE AV Layer", "AVLayer"),
+    ("matchName", "ADBE Text Layer", "TextLayer"),
+    ("matchName", "ADBE Vector Layer", "ShapeLayer"),
+    ("matchName", "ADBE Camera Layer", "CameraLayer"),
+    ("matchName", "ADBE Light Layer", "LightLayer"),
+];
+
+/// A variable narrowed by a type guard: inside the guarded range it holds
+/// `class`, whatever its inferred type is elsewhere
+#[derive(Debug, Clone, PartialEq)]
+pub struct Narrowing {
+    pub variable: String,
+    pub class: String,
+    /// Byte offsets of the guarded code
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Finds type guards and the code they protect:
+///
+/// - `if (x instanceof C)` and `if (x.typeName === "Composition")`, for each
+///   `else if` of a chain and conditions joined with `&&`. Conditions with
+///   `!` or `||` narrow nothing, since the branch may run when the check fails.
+/// - `guard && x.member` and `guard ? x.member : other`, narrowing the rest of
+///   the `&&` chain or the ternary's first branch.
+/// - `switch (x.typeName)` and `switch (x.matchName)`, narrowing each `case`
+///   labelled with a known value.
+pub fn type_narrowings(script: &str) -> Vec<Narrowing> {
+    let tokens = significant_tokens(script);
+    let mut narrowings = Vec::new();
+    let mut narrow = |(variable, class, _): (String, String, usize), start: usize, end: usize| {
+        narrowings.push(Narrowing { variable, class, start, end });
+    };
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_keyword("if") && tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+            let close = match matching_bracket(&tokens, i + 1) {
+                Some(close) => close,
+                None => continue,
+            };
+            let condition = &tokens[i + 2..close];
+            if condition.iter().any(|t| t.is_punct("!") || t.is_punct("||")) {
+                continue;
+            }
+            let end = match branch_end(&tokens, close + 1) {
+                Some(end) => end,
+                None => continue,
+            };
+            for j in 0..condition.len() {
+                if let Some(guard) = guard_at(condition, j) {
+                    narrow(guard, tokens[close + 1].start, tokens[end].end);
+                }
+            }
+        } else if token.is_keyword("switch") {
+            for (guard, start, end) in switch_cases(&tokens, i) {
+                narrow(guard, start, end);
+            }
+        } else if let Some(guard) = guard_at(&tokens, i) {
+            if i > 0 && tokens[i - 1].is_punct("!") {
+                continue;
+            }
+            let after = guard.2 + 1;
+            let end = match tokens.get(after) {
+                Some(t) if t.is_punct("&&") => operand_end(&tokens, after + 1, false),
+                Some(t) if t.is_punct("?") => operand_end(&tokens, after + 1, true),
+                _ => None,
+            };
+            if let Some(end) = end {
+                narrow(guard, tokens[after + 1].start, tokens[end].end);
+            }
+        }
+    }
+
+    narrowings
+}
+
+/// The class `variable` is narrowed to at byte `offset`, from the innermost
+/// guard around it
+pub fn narrowed_type<'a>(narrowings: &'a [Narrowing], variable: &str, offset: usize) -> Option<&'a str> {
+    narrowings.iter()
+        .filter(|n| n.variable == variable && n.start <= offset && offset < n.end)
+        .min_by_key(|n| n.end - n.start)
+        .map(|n| n.class.as_str())
+}
+
+/// A guard starting at token `i`: `x instanceof C` or `x.typeName == "..."`,
+/// as the variable, its class and the guard's last token
+fn guard_at(tokens: &[Token], i: usize) -> Option<(String, String, usize)> {
+    let variable = tokens.get(i).filter(|t| t.is_identifier())?;
+    if i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?.")) {
+        return None;
+    }
+    let next = tokens.get(i + 1)?;
+    if next.is_keyword("instanceof") {
+        let class = tokens.get(i + 2).filter(|t| t.is_identifier())?;
+        return Some((variable.text.clone(), class.text.clone(), i + 2));
+    }
+    let window = tokens.get(i + 1..i + 5)?;
+    if !window[0].is_punct(".") || !(window[2].is_punct("===") || window[2].is_punct("==")) {
+        return None;
+    }
+    let class = discriminated_class(&window[1].text, &window[3])?;
+    Some((variable.text.clone(), class.to_string(), i + 4))
+}
+
+/// The class a `typeName`/`matchName` string literal identifies
+fn discriminated_class(property: &str, literal: &Token) -> Option<&'static str> {
+    if literal.kind != TokenKind::String {
+        return None;
+    }
+    let value = literal.text.get(1..literal.text.len() - 1)?;
+    DISCRIMINANTS.iter()
+        .find(|(p, v, _)| *p == property && *v == value)
+        .map(|(_, _, class)| *class)
+}
+
+/// Guards from a `switch (x.typeName)` at token `i`, each with the byte
+/// range of its `case` up to the next label
+fn switch_cases(tokens: &[Token], i: usize) -> Vec<((String, String, usize), usize, usize)> {
+    let mut cases = Vec::new();
+    let subject = match tokens.get(i + 1..i + 7) {
+        Some(subject) if subject[0].is_punct("(") && subject[1].is_identifier() && subject[2].is_punct(".")
+            && subject[4].is_punct(")") && subject[5].is_punct("{") => subject,
+        _ => return cases,
+    };
+    let body_end = match matching_bracket(tokens, i + 6) {
+        Some(end) => end,
+        None => return cases,
+    };
+
+    let mut depth = 0usize;
+    let mut open: Option<(Option<&str>, usize)> = None;
+    for j in i + 7..=body_end {
+        let token = &tokens[j];
+        let label = depth == 0 && (token.is_keyword("case") || token.is_keyword("default"));
+        if label || j == body_end {
+            if let Some((Some(class), start)) = open.take() {
+                if start < j {
+                    let guard = (subject[1].text.clone(), class.to_string(), j);
+                    cases.push((guard, tokens[start].start, tokens[j - 1].end));
+                }
+            }
+        }
+        if label {
+            let colon = tokens[j..body_end].iter().position(|t| t.is_punct(":")).map(|k| j + k);
+            if let Some(colon) = colon {
+                let class = if token.is_keyword("case") && colon == j + 2 {
+                    discriminated_class(&subject[3].text, &tokens[j + 1])
+                } else {
+                    None
+                };
+                open = Some((class, colon + 1));
+            }
+        }
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            depth += 1;
+        } else if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            depth = depth.saturating_sub(1);
+        }
+    }
+    cases
+}
+
+/// Last token of the operand starting at `start`: the rest of an `&&` chain,
+/// or with `ternary` the first branch of a `?:`
+fn operand_end(tokens: &[Token], start: usize, ternary: bool) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut nested = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            depth += 1;
+            continue;
+        }
+        if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            if depth == 0 {
+                return i.checked_sub(1).filter(|&end| end >= start);
+            }
+            depth -= 1;
+            continue;
+        }
+        if depth > 0 {
+            continue;
+        }
+        let ends = if ternary && token.is_punct("?") {
+            nested += 1;
+            false
+        } else if token.is_punct(":") {
+            if nested == 0 {
+                true
+            } else {
+                nested -= 1;
+                false
+            }
+        } else {
+            token.is_punct(";") || token.is_punct(",") || (!ternary && (token.is_punct("||") || token.is_punct("?")))
+                || (i > start && token.newlines_before > 0 && !is_continuation(&tokens[i - 1]))
+        };
+        if ends {
+            return i.checked_sub(1).filter(|&end| end >= start);
+        }
+    }
+    tokens.len().checked_sub(1).filter(|&end| end >= start)
+}
+
+/// Whether an expression carries on past a line break after this token
+fn is_continuation(token: &Token) -> bool {
+    token.kind == TokenKind::Punctuator && !matches!(token.text.as_str(), ")" | "]" | "}" | "++" | "--")
+}
+
+/// Last token of the statement or block starting at `start`
+pub(crate) fn branch_end(tokens: &[Token], start: usize) -> Option<usize> {
+    if tokens.get(start)?.is_punct("{") {
+        return matching_bracket(tokens, start);
+    }
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            depth += 1;
+        } else if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            depth = depth.checked_sub(1)?;
+        } else if depth == 0 && (token.is_punct(";") || (i > start && token.newlines_before > 0)) {
+            return Some(if token.is_punct(";") { i } else { i - 1 });
+        }
+    }
+    tokens.len().checked_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infers_common_receivers() {
+        let types = infer_variable_types(r#"
+            var proj = app.project;
+            var comp = app.project.activeItem;
+            var layer = comp.layer(1);
+            var solid = comp.layers.addSolid([1, 1, 1], "BG", 100, 100, 1);
+            var pos = layer.property("Position");
+            var other = layer;
+            var count = comp.numLayers;
+            var layers = comp.layers;
+            var opacity = app.project.activeItem.layer(1).property("Opacity");
+        "#);
+        assert_eq!(types.get("proj").map(String::as_str), Some("Project"));
+        assert_eq!(types.get("comp").map(String::as_str), Some("CompItem"));
+        assert_eq!(types.get("layer").map(String::as_str), Some("Layer"));
+        assert_eq!(types.get("solid").map(String::as_str), Some("Layer"));
+        assert_eq!(types.get("pos").map(String::as_str), Some("Property"));
+        assert_eq!(types.get("other").map(String::as_str), Some("Layer"));
+        assert_eq!(types.get("count"), None);
+        assert_eq!(types.get("layers").map(String::as_str), Some("LayerCollection"));
+        assert_eq!(types.get("opacity").map(String::as_str), Some("Property"));
+    }
+
+    #[test]
+    fn test_jsdoc_annotations() {
+        let types = infer_variable_types(r#"
+            /**
+             * @param {CompItem} target
+             * @param {string} label
+             * @returns {Layer}
+             */
+            function addTitle(target, label) {}
+
+            /** @type {Layer} */
+            var picked = getSelection()[0];
+            var title = addTitle(app.project.activeItem, "Hi");
+            var comp = /** @type {CompItem} */ (app.project.item(1));
+            /** @type {Property} */
+            var layer = comp.layer(1);
+        "#);
+        assert_eq!(types.get("target").map(String::as_str), Some("CompItem"));
+        assert_eq!(types.get("picked").map(String::as_str), Some("Layer"));
+        assert_eq!(types.get("title").map(String::as_str), Some("Layer"));
+        assert_eq!(types.get("comp").map(String::as_str), Some("CompItem"));
+        assert_eq!(types.get("layer").map(String::as_str), Some("Property"));
+    }
+
+    #[test]
+    fn test_instanceof_narrowing() {
+        let script = r#"var item = app.project.item(1);
+if (item instanceof CompItem && item.numLayers > 0) {
+    item.layer(1);
+} else if (item instanceof FolderItem) {
+    item.item(1);
+}
+if (!(item instanceof CompItem)) { item.remove(); }
+item.remove();
+"#;
+        let narrowings = type_narrowings(script);
+        let at = |needle: &str| script.find(needle).unwrap();
+        assert_eq!(narrowed_type(&narrowings, "item", at("item.layer")), Some("CompItem"));
+        assert_eq!(narrowed_type(&narrowings, "item", at("item.item")), Some("FolderItem"));
+        assert_eq!(narrowed_type(&narrowings, "item", at("item.remove")), None);
+        assert_eq!(narrowed_type(&narrowings, "item", script.rfind("item.remove").unwrap()), None);
+    }
+
+    #[test]
+    fn test_discriminant_narrowing() {
+        let script = r#"var ok = item && item.typeName === "Composition" && item.numLayers > 0;
+var n = item instanceof CompItem ? item.numLayers : item.name;
+switch (layer.matchName) {
+    case "ADBE Text Layer":
+        layer.sourceText;
+        break;
+    case "ADBE Vector Layer": {
+        layer.content;
+        break;
+    }
+    case "Unknown":
+        layer.other;
+}
+layer.after;
+"#;
+        let narrowings = type_narrowings(script);
+        let at = |needle: &str| script.find(needle).unwrap();
+        assert_eq!(narrowed_type(&narrowings, "item", at("item.numLayers >")), Some("CompItem"));
+        assert_eq!(narrowed_type(&narrowings, "item", at("item.numLayers :")), Some("CompItem"));
+        assert_eq!(narrowed_type(&narrowings, "item", at("item.name")), None);
+        assert_eq!(narrowed_type(&narrowings, "layer", at("layer.sourceText")), Some("TextLayer"));
+        assert_eq!(narrowed_type(&narrowings, "layer", at("layer.content")), Some("ShapeLayer"));
+        assert_eq!(narrowed_type(&narrowings, "layer", at("layer.other")), None);
+        assert_eq!(narrowed_type(&narrowings, "layer", at("layer.after")), None);
+    }
+}
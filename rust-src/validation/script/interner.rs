@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Handle to a string stored in an [`Interner`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+/// Stores each distinct string once. Identifiers repeat heavily in generated
+/// scripts, so tokens holding symbols instead of strings keep memory close to
+/// the number of distinct names rather than the number of tokens.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, text: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(text) {
+            return *symbol;
+        }
+        let symbol = Symbol(self.strings.len() as u32);
+        let text: Arc<str> = Arc::from(text);
+        self.strings.push(text.clone());
+        self.symbols.insert(text, symbol);
+        symbol
+    }
+
+    /// The symbol of `text` if it was interned, without adding it
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.symbols.get(text).copied()
+    }
+
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+
+    /// Number of distinct strings
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Bytes of string data held, each distinct string counted once
+    pub fn text_size(&self) -> usize {
+        self.strings.iter().map(|text| text.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings_are_stored_once() {
+        let mut interner = Interner::new();
+        let layer = interner.intern("layer");
+        let comp = interner.intern("comp");
+        assert_eq!(interner.intern("layer"), layer);
+        assert_ne!(layer, comp);
+        assert_eq!(interner.resolve(comp), "comp");
+        assert_eq!(interner.get("missing"), None);
+        assert_eq!((interner.len(), interner.text_size()), (2, 9));
+    }
+}
@@ -0,0 +1,517 @@
+use super::interner::{Interner, Symbol};
+use super::lines::LineIndex;
+
+/// Kinds of tokens produced by the ExtendScript lexer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Identifier,
+    Keyword,
+    Number,
+    String,
+    Template,
+    Regex,
+    Punctuator,
+    LineComment,
+    BlockComment,
+    /// ExtendScript preprocessor directive such as `#include` or `#target`
+    Preprocessor,
+}
+
+/// A single lexical token with its position in the source
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    /// 1-based line number of the first character
+    pub line: usize,
+    /// 1-based column of the first character
+    pub column: usize,
+    /// Byte offset of the first character
+    pub start: usize,
+    /// Byte offset one past the last character
+    pub end: usize,
+    /// Number of line breaks between the previous token and this one
+    pub newlines_before: usize,
+}
+
+impl Token {
+    pub fn is_punct(&self, punct: &str) -> bool {
+        self.kind == TokenKind::Punctuator && self.text == punct
+    }
+
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Keyword && self.text == keyword
+    }
+
+    pub fn is_identifier(&self) -> bool {
+        self.kind == TokenKind::Identifier
+    }
+
+    /// Comments and preprocessor lines carry no program semantics
+    pub fn is_trivia(&self) -> bool {
+        matches!(self.kind, TokenKind::LineComment | TokenKind::BlockComment | TokenKind::Preprocessor)
+    }
+}
+
+const PUNCTUATORS: &[&str] = &[
+    ">>>=", "===", "!==", "**=", "<<=", ">>=", ">>>", "&&=", "||=", "??=", "...",
+    "=>", "==", "!=", "<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=",
+    "*=", "/=", "%=", "&=", "|=", "^=", "<<", ">>", "**",
+];
+
+pub fn is_keyword(word: &str) -> bool {
+    matches!(word,
+        "break" | "case" | "catch" | "class" | "const" | "continue" | "debugger" |
+        "default" | "delete" | "do" | "else" | "export" | "extends" | "false" |
+        "finally" | "for" | "function" | "if" | "import" | "in" | "instanceof" |
+        "let" | "new" | "null" | "return" | "super" | "switch" | "this" | "throw" |
+        "true" | "try" | "typeof" | "var" | "void" | "while" | "with" | "yield" |
+        "undefined"
+    )
+}
+
+/// Tokenizes an ExtendScript/JavaScript source, keeping comments as tokens.
+///
+/// The lexer never fails: malformed input such as unterminated strings or
+/// comments produces a token running to the end of the line or file.
+pub fn tokenize(source: &str) -> Vec<Token> {
+    Lexer::new(source).map(|lexeme| lexeme.into_token(source)).collect()
+}
+
+/// Tokenizes a source and drops comments and preprocessor lines
+pub fn significant_tokens(source: &str) -> Vec<Token> {
+    Lexer::new(source)
+        .filter(|lexeme| !lexeme.is_trivia())
+        .map(|lexeme| lexeme.into_token(source))
+        .collect()
+}
+
+/// Index of the bracket closing the `(`, `[` or `{` at `open`, counting
+/// nested brackets of every kind; `None` when `open` is not an opening
+/// bracket or it is never closed
+pub fn matching_bracket(tokens: &[Token], open: usize) -> Option<usize> {
+    bracket_close(tokens.len(), open, |i, punct| tokens[i].is_punct(punct))
+}
+
+fn bracket_close(len: usize, open: usize, is_punct: impl Fn(usize, &str) -> bool) -> Option<usize> {
+    if !["(", "[", "{"].iter().any(|opener| open < len && is_punct(open, opener)) {
+        return None;
+    }
+    let mut depth = 0usize;
+    for i in open..len {
+        if is_punct(i, "(") || is_punct(i, "[") || is_punct(i, "{") {
+            depth += 1;
+        } else if is_punct(i, ")") || is_punct(i, "]") || is_punct(i, "}") {
+            depth = depth.checked_sub(1)?;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+/// A significant token whose text is interned. Lines are looked up in
+/// [`CompactTokens::lines`] rather than stored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactToken {
+    pub kind: TokenKind,
+    pub text: Symbol,
+    /// Byte offset of the first character
+    pub start: u32,
+    /// Byte offset one past the last character
+    pub end: u32,
+}
+
+impl CompactToken {
+    pub fn is_identifier(&self) -> bool {
+        self.kind == TokenKind::Identifier
+    }
+}
+
+/// Significant tokens for multi-megabyte scripts: 16 bytes per token, each
+/// distinct text stored once, and a line index in place of per-token lines
+#[derive(Debug, Clone)]
+pub struct CompactTokens {
+    pub tokens: Vec<CompactToken>,
+    pub interner: Interner,
+    pub lines: LineIndex,
+}
+
+impl CompactTokens {
+    pub fn new(source: &str) -> Self {
+        let mut interner = Interner::new();
+        let tokens = Lexer::new(source)
+            .filter(|lexeme| !lexeme.is_trivia())
+            .map(|lexeme| CompactToken {
+                kind: lexeme.kind,
+                text: interner.intern(&source[lexeme.start..lexeme.end]),
+                start: lexeme.start as u32,
+                end: lexeme.end as u32,
+            })
+            .collect();
+        Self { tokens, interner, lines: LineIndex::new(source) }
+    }
+
+    pub fn text(&self, token: &CompactToken) -> &str {
+        self.interner.resolve(token.text)
+    }
+
+    /// Whether the token at `index` is the punctuator `punct`
+    pub fn is_punct(&self, index: usize, punct: &str) -> bool {
+        self.tokens.get(index).is_some_and(|token| {
+            token.kind == TokenKind::Punctuator && self.interner.get(punct) == Some(token.text)
+        })
+    }
+
+    /// Index of the bracket closing the one at `open`, like [`matching_bracket`]
+    pub fn matching_bracket(&self, open: usize) -> Option<usize> {
+        bracket_close(self.tokens.len(), open, |i, punct| self.is_punct(i, punct))
+    }
+
+    /// 1-based line of the token's first character
+    pub fn line(&self, token: &CompactToken) -> usize {
+        self.lines.line(token.start as usize)
+    }
+
+    /// Heap memory held by the tokens, their texts and the line index
+    pub fn heap_size(&self) -> usize {
+        self.tokens.capacity() * std::mem::size_of::<CompactToken>() + self.interner.text_size() + self.lines.heap_size()
+    }
+}
+
+/// A token's position and kind, before its text is copied out of the source
+struct Lexeme {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+    start: usize,
+    end: usize,
+    newlines_before: usize,
+}
+
+impl Lexeme {
+    fn is_trivia(&self) -> bool {
+        matches!(self.kind, TokenKind::LineComment | TokenKind::BlockComment | TokenKind::Preprocessor)
+    }
+
+    fn into_token(self, source: &str) -> Token {
+        Token {
+            kind: self.kind,
+            text: source[self.start..self.end].to_string(),
+            line: self.line,
+            column: self.column,
+            start: self.start,
+            end: self.end,
+            newlines_before: self.newlines_before,
+        }
+    }
+}
+
+/// Scans the source in place, one lexeme at a time, so memory beyond the
+/// tokens a caller keeps does not grow with the script
+struct Lexer<'a> {
+    source: &'a str,
+    /// Byte offset of the next character
+    pos: usize,
+    line: usize,
+    column: usize,
+    newlines: usize,
+    line_has_token: bool,
+    /// Kind and span of the last significant token, for telling regex
+    /// literals from division
+    previous: Option<(TokenKind, usize, usize)>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            pos: 0,
+            line: 1,
+            column: 1,
+            newlines: 0,
+            line_has_token: false,
+            previous: None,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.source[self.pos..].chars().nth(offset)
+    }
+
+    fn offset(&self) -> usize {
+        self.pos
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_to_line_end(&mut self) {
+        while let Some(c) = self.peek(0) {
+            if c == '\n' {
+                break;
+            }
+            self.bump();
+        }
+    }
+
+    fn lex_block_comment(&mut self) {
+        self.bump();
+        self.bump();
+        while let Some(c) = self.bump() {
+            if c == '*' && self.peek(0) == Some('/') {
+                self.bump();
+                return;
+            }
+        }
+    }
+
+    fn lex_string(&mut self, quote: char, multiline: bool) {
+        self.bump();
+        while let Some(c) = self.peek(0) {
+            if c == '\n' && !multiline {
+                return;
+            }
+            self.bump();
+            if c == '\\' {
+                self.bump();
+            } else if c == quote {
+                return;
+            }
+        }
+    }
+
+    fn lex_number(&mut self) {
+        while let Some(c) = self.peek(0) {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                let is_exponent = (c == 'e' || c == 'E')
+                    && matches!(self.peek(1), Some('+') | Some('-'));
+                self.bump();
+                if is_exponent {
+                    self.bump();
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn lex_regex(&mut self) {
+        self.bump();
+        let mut in_class = false;
+        while let Some(c) = self.peek(0) {
+            if c == '\n' {
+                return;
+            }
+            self.bump();
+            match c {
+                '\\'
+                    if self.peek(0) != Some('\n') => {
+                        self.bump();
+                    }
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '/' if !in_class => break,
+                _ => {}
+            }
+        }
+        while self.peek(0).is_some_and(is_ident_part) {
+            self.bump();
+        }
+    }
+
+    fn lex_punctuator(&mut self) {
+        for punct in PUNCTUATORS {
+            let matches = punct.chars().enumerate().all(|(i, pc)| self.peek(i) == Some(pc));
+            if matches {
+                for _ in 0..punct.chars().count() {
+                    self.bump();
+                }
+                return;
+            }
+        }
+        self.bump();
+    }
+
+    /// A `/` starts a regex literal unless it follows something that ends an operand
+    fn regex_allowed(&self) -> bool {
+        match self.previous {
+            None => true,
+            Some((kind, start, end)) => {
+                let text = &self.source[start..end];
+                match kind {
+                    TokenKind::Identifier | TokenKind::Number | TokenKind::String
+                    | TokenKind::Template | TokenKind::Regex => false,
+                    TokenKind::Keyword => !matches!(text, "this" | "true" | "false" | "null" | "undefined" | "super"),
+                    TokenKind::Punctuator => !matches!(text, ")" | "]" | "}" | "++" | "--"),
+                    _ => true,
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Lexeme;
+
+    fn next(&mut self) -> Option<Lexeme> {
+        while let Some(c) = self.peek(0) {
+            if c == '\n' {
+                self.newlines += 1;
+                self.line_has_token = false;
+                self.bump();
+                continue;
+            }
+            if c.is_whitespace() {
+                self.bump();
+                continue;
+            }
+
+            let start = self.offset();
+            let line = self.line;
+            let column = self.column;
+
+            let kind = if c == '/' && self.peek(1) == Some('/') {
+                self.skip_to_line_end();
+                TokenKind::LineComment
+            } else if c == '/' && self.peek(1) == Some('*') {
+                self.lex_block_comment();
+                TokenKind::BlockComment
+            } else if c == '#' && !self.line_has_token {
+                self.skip_to_line_end();
+                TokenKind::Preprocessor
+            } else if c == '"' || c == '\'' {
+                self.lex_string(c, false);
+                TokenKind::String
+            } else if c == '`' {
+                self.lex_string(c, true);
+                TokenKind::Template
+            } else if c.is_ascii_digit() || (c == '.' && self.peek(1).is_some_and(|n| n.is_ascii_digit())) {
+                self.lex_number();
+                TokenKind::Number
+            } else if is_ident_start(c) {
+                while self.peek(0).is_some_and(is_ident_part) {
+                    self.bump();
+                }
+                if is_keyword(&self.source[start..self.offset()]) {
+                    TokenKind::Keyword
+                } else {
+                    TokenKind::Identifier
+                }
+            } else if c == '/' && self.regex_allowed() {
+                self.lex_regex();
+                TokenKind::Regex
+            } else {
+                self.lex_punctuator();
+                TokenKind::Punctuator
+            };
+
+            let lexeme = Lexeme {
+                kind,
+                line,
+                column,
+                start,
+                end: self.offset(),
+                newlines_before: self.newlines,
+            };
+            if !lexeme.is_trivia() {
+                self.previous = Some((kind, lexeme.start, lexeme.end));
+            }
+            self.newlines = 0;
+            self.line_has_token = true;
+            return Some(lexeme);
+        }
+
+        None
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_ident_part(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source).into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn test_tokenize_statement() {
+        let tokens = tokenize("var comp = app.project.activeItem;");
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["var", "comp", "=", "app", ".", "project", ".", "activeItem", ";"]);
+        assert_eq!(tokens[0].kind, TokenKind::Keyword);
+        assert_eq!(tokens[1].kind, TokenKind::Identifier);
+    }
+
+    #[test]
+    fn test_comments_and_preprocessor_are_kept() {
+        let source = "#include \"lib.jsx\"\n// note\nvar a = 1; /* block */";
+        assert_eq!(kinds(source), vec![
+            TokenKind::Preprocessor,
+            TokenKind::LineComment,
+            TokenKind::Keyword,
+            TokenKind::Identifier,
+            TokenKind::Punctuator,
+            TokenKind::Number,
+            TokenKind::Punctuator,
+            TokenKind::BlockComment,
+        ]);
+    }
+
+    #[test]
+    fn test_strings_and_regex() {
+        let tokens = tokenize(r#"var s = "a \"b\" c"; var r = /x\/y/g; var d = a / b;"#);
+        assert_eq!(tokens[3].kind, TokenKind::String);
+        assert_eq!(tokens[3].text, r#""a \"b\" c""#);
+        assert_eq!(tokens[8].kind, TokenKind::Regex);
+        assert_eq!(tokens[8].text, r"/x\/y/g");
+        assert!(tokens[14].is_punct("/"));
+    }
+
+    #[test]
+    fn test_positions_and_newlines() {
+        let tokens = tokenize("a;\n\n  b;");
+        assert_eq!(tokens[2].line, 3);
+        assert_eq!(tokens[2].column, 3);
+        assert_eq!(tokens[2].newlines_before, 2);
+    }
+
+    #[test]
+    fn test_compact_tokens_share_texts() {
+        let source = "var é = layer;\n// note\nlayer.name = layer.name + \"x\";";
+        let compact = CompactTokens::new(source);
+        let tokens = significant_tokens(source);
+        assert_eq!(compact.tokens.len(), tokens.len());
+        for (token, full) in compact.tokens.iter().zip(&tokens) {
+            assert_eq!((compact.text(token), compact.line(token), token.kind), (full.text.as_str(), full.line, full.kind));
+        }
+        assert_eq!(compact.tokens[3].text, compact.tokens[5].text);
+        assert!(compact.is_punct(6, "."));
+        assert_eq!(compact.interner.len(), 9);
+    }
+
+    #[test]
+    fn test_unterminated_input_does_not_panic() {
+        tokenize("var s = \"unterminated\n/* open comment");
+        tokenize("`template");
+        tokenize("/");
+    }
+}
@@ -0,0 +1,103 @@
+use std::io::{self, Read};
+
+/// Byte offsets of line starts, four bytes per line, for turning offsets into
+/// line numbers without rescanning the script for every diagnostic
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineIndex {
+    starts: Vec<u32>,
+    len: u32,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut index = Self::empty();
+        index.extend(source.as_bytes());
+        index
+    }
+
+    /// Builds the index from `reader` in fixed-size chunks, so a generated
+    /// script never has to be held in memory as a whole
+    pub fn from_reader<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut index = Self::empty();
+        let mut buffer = [0u8; 64 * 1024];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return Ok(index),
+                Ok(read) => index.extend(&buffer[..read]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn empty() -> Self {
+        Self { starts: vec![0], len: 0 }
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        for (i, byte) in bytes.iter().enumerate() {
+            if *byte == b'\n' {
+                self.starts.push(self.len + i as u32 + 1);
+            }
+        }
+        self.len += bytes.len() as u32;
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// 1-based line containing the byte `offset`
+    pub fn line(&self, offset: usize) -> usize {
+        self.starts.partition_point(|start| *start as usize <= offset)
+    }
+
+    /// Byte offset at which the 1-based `line` starts
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.starts.get(line.checked_sub(1)?).map(|start| *start as usize)
+    }
+
+    /// 1-based line and column, counted in characters, of `offset` in the
+    /// `source` the index was built from
+    pub fn position(&self, source: &str, offset: usize) -> (usize, usize) {
+        let line = self.line(offset);
+        let start = self.starts[line - 1] as usize;
+        (line, source[start..offset].chars().count() + 1)
+    }
+
+    /// Text of the 1-based `line` without its line break
+    pub fn line_text<'a>(&self, source: &'a str, line: usize) -> &'a str {
+        let start = match self.line_start(line) {
+            Some(start) => start,
+            None => return "",
+        };
+        let end = self.line_start(line + 1).map_or(source.len(), |next| next - 1);
+        source[start..end].trim_end_matches('\r')
+    }
+
+    /// Heap memory held by the index
+    pub fn heap_size(&self) -> usize {
+        self.starts.capacity() * std::mem::size_of::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets_map_to_lines() {
+        let source = "var a = 1;\r\n\nvar é = a;";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line(0), 1);
+        assert_eq!(index.line(11), 1);
+        assert_eq!(index.line(12), 2);
+        assert_eq!(index.position(source, source.find('=').unwrap()), (1, 7));
+        assert_eq!(index.position(source, source.rfind('a').unwrap()), (3, 9));
+        assert_eq!(index.line_text(source, 1), "var a = 1;");
+        assert_eq!(index.line_text(source, 3), "var é = a;");
+        assert_eq!(index.line_text(source, 4), "");
+        assert_eq!(LineIndex::from_reader(source.as_bytes()).unwrap(), index);
+    }
+}
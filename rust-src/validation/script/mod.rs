@@ -1,10 +1,54 @@
-use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
-use super::expression::validate_expression_syntax;
+use std::panic::{self, AssertUnwindSafe};
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity, group_related};
+use super::expression::{validate_expression_syntax, validate_expression_references};
 use super::workflow::validate_workflow_patterns;
-use super::typechecker::validate_type_usage;
+use super::typechecker::validate_type_usage_with_shims;
+use super::config::{ValidatorConfig, RuleProfile};
+use super::headless::validate_environment;
+use super::migration::validate_track_matte_migration;
+use super::color::validate_color_ranges;
+use super::renderqueue::validate_render_queue;
+use super::platform::validate_platform_paths;
+use super::naming::validate_naming;
+use super::localization::validate_localized_strings;
+use super::casing::validate_class_name_case;
+use super::constraints::validate_property_constraints;
+use super::lifecycle::{validate_removed_objects, validate_stale_references};
+use super::alternate::validate_alternate_sources;
+use super::suppression::validate_suppressed_dialogs;
+use super::catches::validate_error_handling;
+use super::loops::validate_infinite_loops;
+use super::effect_support::validate_effect_support;
+use super::obsolete::validate_obsolete_effects;
+use super::hierarchy::validate_property_hierarchy;
+use super::capabilities::validate_layer_capabilities;
+use super::three_d::validate_3d_properties;
+use super::camera_light::validate_camera_light_options;
+use super::text_animators::validate_text_animators;
+use super::easing::validate_keyframe_eases;
+use super::globals::validate_global_calls;
+use super::tasks::validate_scheduled_tasks;
+use super::embedded::validate_evaluated_strings;
+use super::jsxbin::{is_jsxbin, jsxbin_diagnostic};
+use super::i18n::{Locale, MessageCatalog};
+use super::profile::Profiler;
 
 mod tool_analysis;
+pub mod lexer;
+pub mod lines;
+pub mod interner;
+pub mod formatter;
+pub mod scope;
+pub mod inference;
+pub mod callgraph;
+pub mod modules;
+pub mod syntax;
 use tool_analysis::{analyze_tool_usage, validate_tool_parameters, check_tool_dependencies};
+pub use formatter::{format_script, FormatOptions, QuoteStyle};
+pub use callgraph::{CallGraph, CallEdge, CallKind};
+pub use modules::{find_modules, ScriptModule, ModuleMember};
+use scope::{validate_context_globals, validate_implicit_globals};
+use syntax::validate_syntax;
 
 /// Represents a script validation result
 #[derive(Debug)]
@@ -14,6 +58,12 @@ pub struct ScriptValidationResult {
     pub warnings: Vec<ValidatorError>,
 }
 
+impl Default for ScriptValidationResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ScriptValidationResult {
     pub fn new() -> Self {
         Self {
@@ -31,40 +81,219 @@ impl ScriptValidationResult {
     pub fn add_warning(&mut self, warning: ValidatorError) {
         self.warnings.push(warning);
     }
+
+    /// Collapses repeated errors and warnings into one diagnostic per root cause
+    pub fn group_related(&mut self) {
+        self.errors = group_related(std::mem::take(&mut self.errors));
+        self.warnings = group_related(std::mem::take(&mut self.warnings));
+    }
+
+    /// Renders messages and suggestions from the catalog where templates exist
+    pub fn localize(&mut self, catalog: &MessageCatalog) {
+        for error in self.errors.iter_mut().chain(self.warnings.iter_mut()) {
+            catalog.localize(error);
+        }
+    }
+
+    /// Promotes warnings from rules escalated by the profile to errors
+    pub fn apply_profile(&mut self, profile: &RuleProfile) {
+        for mut warning in std::mem::take(&mut self.warnings) {
+            if warning.rule_id().is_some_and(|rule| profile.escalates(rule)) {
+                warning.set_severity(ErrorSeverity::Error);
+                self.add_error(warning);
+            } else {
+                self.warnings.push(warning);
+            }
+        }
+    }
 }
 
 /// Validates an ExtendScript/JavaScript file for common issues and tool usage patterns
 pub fn validate_script(script: &str, file_path: &str) -> ScriptValidationResult {
+    run_script_rules(script, file_path, &[], &mut Profiler::new())
+}
+
+fn run_script_rules(script: &str, file_path: &str, shims: &[String], profiler: &mut Profiler) -> ScriptValidationResult {
     let mut result = ScriptValidationResult::new();
 
+    // Compiled files are not source; the rules below would only report noise
+    if is_jsxbin(script) {
+        result.add_error(jsxbin_diagnostic(script, file_path, false));
+        return result;
+    }
+
     // Validate basic script structure
-    validate_script_structure(script, file_path, &mut result);
+    profiler.time("structure", || validate_script_structure(script, file_path, &mut result));
 
     // Validate tool usage patterns
-    validate_tool_usage_patterns(script, file_path, &mut result);
+    profiler.time("tool-usage", || validate_tool_usage_patterns(script, file_path, &mut result));
 
     // Validate expressions within the script
-    validate_script_expressions(script, file_path, &mut result);
-    
+    profiler.time("expressions", || validate_script_expressions(script, file_path, &mut result));
+
     // Validate workflow patterns
-    for error in validate_workflow_patterns(script, file_path) {
+    add_by_severity(&mut result, profiler.time("workflow", || validate_workflow_patterns(script, file_path)));
+
+    // Validate type usage
+    add_by_severity(&mut result, profiler.time("types", || validate_type_usage_with_shims(script, file_path, shims)));
+
+    // Validate variable scoping
+    add_by_severity(&mut result, profiler.time("scope", || validate_implicit_globals(script, file_path)));
+
+    // Flag expression globals in script code and script globals in expressions
+    add_by_severity(&mut result, profiler.time("context-globals", || validate_context_globals(script, file_path)));
+
+    // Flag APIs replaced in newer After Effects versions
+    add_by_severity(&mut result, profiler.time("migration", || validate_track_matte_migration(script, file_path)));
+
+    // Flag 0-255 color components where AE expects 0-1
+    add_by_severity(&mut result, profiler.time("colors", || validate_color_ranges(script, file_path)));
+
+    // Track render queue item statuses
+    add_by_severity(&mut result, profiler.time("render-queue", || validate_render_queue(script, file_path)));
+
+    // Flag English menu and effect names that break on localized installs
+    add_by_severity(&mut result, profiler.time("localization", || validate_localized_strings(script, file_path)));
+
+    // Flag constructors and enums written in the wrong case
+    add_by_severity(&mut result, profiler.time("casing", || validate_class_name_case(script, file_path)));
+
+    // Check relationships between assigned property values
+    add_by_severity(&mut result, profiler.time("constraints", || validate_property_constraints(script, file_path)));
+
+    // Flag objects used or removed again after remove()
+    add_by_severity(&mut result, profiler.time("lifecycle", || validate_removed_objects(script, file_path)));
+
+    // Flag references used after the project or their parent object changed under them
+    add_by_severity(&mut result, profiler.time("stale-references", || validate_stale_references(script, file_path)));
+
+    // Check media replacement through Essential Properties
+    add_by_severity(&mut result, profiler.time("alternate-source", || validate_alternate_sources(script, file_path)));
+
+    // Pair beginSuppressDialogs with endSuppressDialogs on every path
+    add_by_severity(&mut result, profiler.time("suppress-dialogs", || validate_suppressed_dialogs(script, file_path)));
+
+    // Flag loops that never end and freeze After Effects
+    add_by_severity(&mut result, profiler.time("infinite-loops", || validate_infinite_loops(script, file_path)));
+
+    // Check added effects against the project bit depth and their availability
+    add_by_severity(&mut result, profiler.time("effect-support", || validate_effect_support(script, file_path)));
+
+    // Suggest modern replacements for obsolete effects
+    add_by_severity(&mut result, profiler.time("obsolete-effects", || validate_obsolete_effects(script, file_path)));
+
+    // Check property match names against the groups they are looked up in
+    add_by_severity(&mut result, profiler.time("property-hierarchy", || validate_property_hierarchy(script, file_path)));
+
+    // Check layer members and property groups against the layer type
+    add_by_severity(&mut result, profiler.time("layer-capabilities", || validate_layer_capabilities(script, file_path)));
+
+    // Flag 3D-only properties on layers that are still 2D
+    add_by_severity(&mut result, profiler.time("3d-properties", || validate_3d_properties(script, file_path)));
+
+    // Check camera and light option values and the switches they depend on
+    add_by_severity(&mut result, profiler.time("camera-light-options", || validate_camera_light_options(script, file_path)));
+
+    // Check per-character 3D properties and expression selector results
+    add_by_severity(&mut result, profiler.time("text-animators", || validate_text_animators(script, file_path)));
+
+    // Check hand-written KeyframeEase values against the easing presets
+    add_by_severity(&mut result, profiler.time("keyframe-eases", || validate_keyframe_eases(script, file_path)));
+
+    // Check calls to global functions and flag calls to undefined ones
+    add_by_severity(&mut result, profiler.time("global-functions", || validate_global_calls(script, file_path)));
+
+    // Validate the code strings of scheduled tasks and the ids they are cancelled with
+    add_by_severity(&mut result, profiler.time("scheduled-tasks", || validate_scheduled_tasks(script, file_path)));
+
+    // Validate code passed to eval, string event handlers and evaluated files
+    add_by_severity(&mut result, profiler.time("evaluated-strings", || validate_evaluated_strings(script, file_path)));
+
+    result
+}
+
+fn add_by_severity(result: &mut ScriptValidationResult, errors: Vec<ValidatorError>) {
+    for error in errors {
         match error.severity() {
             ErrorSeverity::Error => result.add_error(error),
             _ => result.add_warning(error),
         }
     }
-    
-    // Validate type usage
-    for error in validate_type_usage(script, file_path) {
-        match error.severity() {
-            ErrorSeverity::Error => result.add_error(error),
-            _ => result.add_warning(error),
+}
+
+/// Like [`validate_script`], but never panics.
+///
+/// A panic inside a rule is caught and reported as a fatal "internal-error"
+/// diagnostic, so editor integrations keep running on malformed input.
+pub fn validate_script_safe(script: &str, file_path: &str) -> ScriptValidationResult {
+    match panic::catch_unwind(AssertUnwindSafe(|| validate_script(script, file_path))) {
+        Ok(result) => result,
+        Err(payload) => {
+            let reason = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .suggestion(Some("Please report this script as a validator bug".to_string()))
+                .rule("internal-error")
+                .build();
+
+            let mut result = ScriptValidationResult::new();
+            result.add_error(ValidatorError::Script {
+                message: format!("Validator failed on this script: {}", reason),
+                context,
+                severity: ErrorSeverity::Fatal,
+            });
+            result
         }
     }
+}
+
+/// Validates a script for the configured environment and applies the rule profile
+pub fn validate_script_with_config(script: &str, file_path: &str, config: &ValidatorConfig) -> Result<ScriptValidationResult, String> {
+    validate_script_profiled(script, file_path, config, &mut Profiler::new())
+}
+
+/// Like [`validate_script_with_config`], recording how long each pass takes
+pub fn validate_script_profiled(script: &str, file_path: &str, config: &ValidatorConfig, profiler: &mut Profiler) -> Result<ScriptValidationResult, String> {
+    let mut result = collect_script_diagnostics(script, file_path, config, profiler);
+    if !is_jsxbin(script) {
+        apply_config(&mut result, config)?;
+    }
+    Ok(result)
+}
 
+/// Runs every configured pass without applying the rule profile, grouping or
+/// localization, so callers can add diagnostics from their own passes before
+/// [`apply_config`]
+pub fn collect_script_diagnostics(script: &str, file_path: &str, config: &ValidatorConfig, profiler: &mut Profiler) -> ScriptValidationResult {
+    if is_jsxbin(script) {
+        let mut result = ScriptValidationResult::new();
+        add_by_severity(&mut result, vec![jsxbin_diagnostic(script, file_path, config.allow_jsxbin)]);
+        return result;
+    }
+    let mut result = run_script_rules(script, file_path, &config.shims, profiler);
+    add_by_severity(&mut result, profiler.time("environment", || validate_environment(script, file_path, config.environment)));
+    add_by_severity(&mut result, profiler.time("platform", || validate_platform_paths(script, file_path, config)));
+    add_by_severity(&mut result, profiler.time("naming", || validate_naming(script, file_path, &config.naming)));
+    add_by_severity(&mut result, profiler.time("error-handling", || validate_error_handling(script, file_path, &config.error_handling)));
     result
 }
 
+/// Applies the rule profile, then groups and localizes the diagnostics
+pub fn apply_config(result: &mut ScriptValidationResult, config: &ValidatorConfig) -> Result<(), String> {
+    if let Some(profile) = config.active_profile()? {
+        result.apply_profile(&profile);
+    }
+    if config.group_related {
+        result.group_related();
+    }
+    if config.locale != Locale::En {
+        result.localize(&MessageCatalog::for_locale(config.locale));
+    }
+    Ok(())
+}
+
 fn validate_script_structure(script: &str, file_path: &str, result: &mut ScriptValidationResult) {
     // Check for missing semicolons
     for (line_num, line) in script.lines().enumerate() {
@@ -77,6 +306,7 @@ fn validate_script_structure(script: &str, file_path: &str, result: &mut ScriptV
                 .column(line.len())
                 .code_snippet(line.to_string())
                 .suggestion(Some("Add a semicolon at the end of the statement".to_string()))
+                .rule("missing-semicolon")
                 .build();
 
             result.add_warning(ValidatorError::Script {
@@ -87,67 +317,13 @@ fn validate_script_structure(script: &str, file_path: &str, result: &mut ScriptV
         }
     }
 
-    // Check for balanced braces
-    let mut brace_count = 0;
-    let mut in_string = false;
-    let mut string_char = ' ';
-    let mut last_open_brace_line = 0;
-
-    for (line_num, line) in script.lines().enumerate() {
-        for (col, c) in line.chars().enumerate() {
-            match c {
-                '\'' | '"' => {
-                    if !in_string {
-                        in_string = true;
-                        string_char = c;
-                    } else if c == string_char {
-                        in_string = false;
-                    }
-                }
-                '{' if !in_string => {
-                    brace_count += 1;
-                    last_open_brace_line = line_num + 1;
-                }
-                '}' if !in_string => {
-                    brace_count -= 1;
-                    if brace_count < 0 {
-                        let context = ErrorContextBuilder::new()
-                            .file(file_path.to_string())
-                            .line(line_num + 1)
-                            .column(col + 1)
-                            .code_snippet(line.to_string())
-                            .build();
-
-                        result.add_error(ValidatorError::Script {
-                            message: "Unmatched closing brace".to_string(),
-                            context,
-                            severity: ErrorSeverity::Error,
-                        });
-                        brace_count = 0; // Reset to avoid cascading errors
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
-    if brace_count > 0 {
-        let context = ErrorContextBuilder::new()
-            .file(file_path.to_string())
-            .line(last_open_brace_line)
-            .column(1)
-            .suggestion(Some("Add closing brace(s) to match opening brace".to_string()))
-            .build();
-
-        result.add_error(ValidatorError::Script {
-            message: format!("Missing {} closing brace(s)", brace_count),
-            context,
-            severity: ErrorSeverity::Error,
-        });
+    // Check delimiters and literals, reporting every independent error
+    for error in validate_syntax(script, file_path) {
+        result.add_error(error);
     }
 }
 
-fn validate_tool_usage_patterns(script: &str, file_path: &str, result: &mut ScriptValidationResult) {
+fn validate_tool_usage_patterns(script: &str, _file_path: &str, result: &mut ScriptValidationResult) {
     // Analyze tool usage
     let usages = analyze_tool_usage(script);
     
@@ -181,4 +357,39 @@ fn validate_script_expressions(script: &str, file_path: &str, result: &mut Scrip
             result.add_error(validation_error);
         }
     }
-} 
\ No newline at end of file
+
+    // Check for self-referencing and circular expressions
+    for error in validate_expression_references(script, file_path) {
+        result.add_warning(error);
+    }
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_validation_on_malformed_input() {
+        let inputs = [
+            "",
+            "\"unterminated",
+            "/* open comment",
+            "function (",
+            "layer.transform.opacity.expression = \"((((((\";",
+            "}}}}{{{{ var = = ;",
+            "é\u{0}\u{FFFF}`${",
+        ];
+        for input in inputs {
+            let result = validate_script_safe(input, "fuzz.jsx");
+            assert!(result.errors.iter().all(|e| e.rule_id() != Some("internal-error")), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_safe_validation_on_valid_input() {
+        let script = "var comp = app.project.activeItem;\nif (comp instanceof CompItem) {\n    executeComprehensiveAITool(\"create_layer\", { name: \"Title\" });\n}\n";
+        let result = validate_script_safe(script, "valid.jsx");
+        let internal = result.errors.iter().filter(|e| e.rule_id() == Some("internal-error")).count();
+        assert_eq!(internal, 0, "{:?}", result.errors);
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use super::lexer::{matching_bracket, Token, TokenKind};
+use super::scope::{analyze_scopes, ScopeAnalysis};
+
+/// A member exported by a namespace object
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleMember {
+    pub name: String,
+    pub line: usize,
+    /// Parameter count when the member is a function the script defines
+    pub params: Option<usize>,
+}
+
+/// A global namespace object declared with one of the common ExtendScript
+/// module patterns
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptModule {
+    pub name: String,
+    pub line: usize,
+    pub members: BTreeMap<String, ModuleMember>,
+}
+
+impl ScriptModule {
+    fn add(&mut self, member: ModuleMember) {
+        self.members.entry(member.name.clone()).or_insert(member);
+    }
+}
+
+/// Finds namespace objects declared in the global scope and the members
+/// they export. Recognized declarations:
+///
+/// - `var MyLib = MyLib || {};` followed by `MyLib.member = ...`
+/// - `var MyLib = { member: ... };`
+/// - `var MyLib = (function () { ...; return { member: ... }; })();`
+pub fn find_modules(script: &str) -> Vec<ScriptModule> {
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let mut modules: Vec<ScriptModule> = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if analysis.token_scopes[i] != 0 || !token.is_identifier()
+            || !tokens.get(i + 1).is_some_and(|t| t.is_punct("="))
+            || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+        let name = &token.text;
+        let mut value = i + 2;
+        // `MyLib || {}` reuses the object when another file already created it
+        if tokens.get(value).is_some_and(|t| t.text == *name)
+            && tokens.get(value + 1).is_some_and(|t| t.is_punct("||")) {
+            value += 2;
+        }
+
+        let mut module = ScriptModule { name: name.clone(), line: token.line, members: BTreeMap::new() };
+        if tokens.get(value).is_some_and(|t| t.is_punct("{")) {
+            object_members(&analysis, value, &mut module);
+        } else if let Some(function) = iife_function(tokens, value) {
+            let scope = analysis.scopes.iter().position(|s| s.start == function);
+            for (r, token) in tokens.iter().enumerate().skip(function) {
+                if token.is_keyword("return") && scope == Some(analysis.token_scopes[r])
+                    && tokens.get(r + 1).is_some_and(|t| t.is_punct("{")) {
+                    object_members(&analysis, r + 1, &mut module);
+                }
+            }
+        } else {
+            continue;
+        }
+
+        match modules.iter_mut().find(|m| m.name == module.name) {
+            Some(existing) => module.members.into_values().for_each(|member| existing.add(member)),
+            None => modules.push(module),
+        }
+    }
+
+    // `MyLib.member = ...` anywhere in the script
+    for module in modules.iter_mut() {
+        for (i, token) in tokens.iter().enumerate() {
+            if token.text != module.name
+                || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?.")))
+                || !tokens.get(i + 1).is_some_and(|t| t.is_punct("."))
+                || !tokens.get(i + 3).is_some_and(|t| t.is_punct("=")) {
+                continue;
+            }
+            if let Some(member) = tokens.get(i + 2).filter(|t| t.is_identifier()) {
+                module.add(ModuleMember {
+                    name: member.text.clone(),
+                    line: member.line,
+                    params: function_params(&analysis, i + 4),
+                });
+            }
+        }
+    }
+
+    modules
+}
+
+/// Index of the `function` keyword of an immediately invoked function
+/// expression starting at `start`: `(function () {...})()`,
+/// `(function () {...}())` or `function () {...}()`
+fn iife_function(tokens: &[Token], start: usize) -> Option<usize> {
+    let wrapped = tokens.get(start)?.is_punct("(");
+    let function = if wrapped { start + 1 } else { start };
+    if !tokens.get(function)?.is_keyword("function") {
+        return None;
+    }
+    let body = function + tokens[function..].iter().position(|t| t.is_punct("{"))?;
+    let end = matching_bracket(tokens, body)?;
+    let after = &tokens[end + 1..];
+    let invoked = match after {
+        [close, open, ..] if wrapped && close.is_punct(")") && open.is_punct("(") => true,
+        [open, ..] => open.is_punct("("),
+        [] => false,
+    };
+    invoked.then_some(function)
+}
+
+/// Adds the keys of the object literal opening at `open` to `module`
+fn object_members(analysis: &ScopeAnalysis, open: usize, module: &mut ScriptModule) {
+    let tokens = &analysis.tokens;
+    let end = matching_bracket(tokens, open).unwrap_or(tokens.len());
+    let mut depth = 0usize;
+    for i in open..end {
+        let token = &tokens[i];
+        if token.is_punct("{") || token.is_punct("(") || token.is_punct("[") {
+            depth += 1;
+        } else if token.is_punct("}") || token.is_punct(")") || token.is_punct("]") {
+            depth = depth.saturating_sub(1);
+        } else if depth == 1
+            && (token.is_identifier() || token.kind == TokenKind::String)
+            && (tokens[i - 1].is_punct("{") || tokens[i - 1].is_punct(","))
+            && tokens.get(i + 1).is_some_and(|t| t.is_punct(":")) {
+            module.add(ModuleMember {
+                name: token.text.trim_matches(|c| c == '"' || c == '\'').to_string(),
+                line: token.line,
+                params: function_params(analysis, i + 2),
+            });
+        }
+    }
+}
+
+/// Parameter count of the function a member's value refers to: a function
+/// expression at `value`, or the name of a function declared in the script
+fn function_params(analysis: &ScopeAnalysis, value: usize) -> Option<usize> {
+    let tokens = &analysis.tokens;
+    let token = tokens.get(value)?;
+    let function = if token.is_keyword("function") {
+        value
+    } else if token.is_identifier() {
+        analysis.scopes.iter().skip(1).find(|s| s.name.as_deref() == Some(token.text.as_str()))?.start
+    } else {
+        return None;
+    };
+    let open = function + tokens[function..].iter().position(|t| t.is_punct("("))?;
+    Some(tokens[open + 1..].iter()
+        .take_while(|t| !t.is_punct(")"))
+        .filter(|t| t.is_identifier())
+        .count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognizes_namespace_patterns() {
+        let script = r#"
+var MyLib = MyLib || {};
+MyLib.createTitle = function (comp, text) { return comp.layers.addText(text); };
+MyLib.VERSION = "1.2";
+
+var Utils = (function () {
+    function pad(n, width) { return n; }
+    var hidden = 1;
+    return { pad: pad, clamp: function (v) { return v; } };
+})();
+
+var Config = { width: 1920, "height": 1080 };
+var comp = app.project.activeItem;
+"#;
+        let modules = find_modules(script);
+        let names: Vec<&str> = modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["MyLib", "Utils", "Config"]);
+
+        let members = |index: usize| modules[index].members.keys().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(members(0), vec!["VERSION", "createTitle"]);
+        assert_eq!(modules[0].members["createTitle"].params, Some(2));
+        assert_eq!(members(1), vec!["clamp", "pad"]);
+        assert_eq!(modules[1].members["pad"].params, Some(2));
+        assert_eq!(members(2), vec!["height", "width"]);
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::HashSet;
+use super::lexer::{significant_tokens, Token, TokenKind};
+use super::super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+
+/// Globals provided by the ExtendScript engine and After Effects
+pub const EXTENDSCRIPT_GLOBALS: &[&str] = &[
+    "app", "$", "Folder", "File", "Socket", "Window", "ScriptUI", "Math", "JSON",
+    "Array", "Object", "String", "Number", "Boolean", "Date", "RegExp", "Error",
+    "Function", "undefined", "NaN", "Infinity", "system", "XML",
+];
+
+/// Globals that only exist while an expression is evaluated
+pub const EXPRESSION_GLOBALS: &[&str] = &[
+    "thisComp", "thisLayer", "thisProperty", "thisProject",
+];
+
+/// Script globals that are not defined in the expression engine
+pub const SCRIPT_ONLY_GLOBALS: &[&str] = &[
+    "app", "$", "system", "File", "Folder", "Socket", "Window", "ScriptUI",
+];
+
+/// Where code runs, which decides the globals it can use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalContext {
+    /// A `.jsx` script run by the ExtendScript engine
+    Script,
+    /// An expression string assigned to a property
+    Expression,
+}
+
+impl GlobalContext {
+    /// Globals from the other context that are undefined here
+    pub fn foreign_globals(self) -> &'static [&'static str] {
+        match self {
+            GlobalContext::Script => EXPRESSION_GLOBALS,
+            GlobalContext::Expression => SCRIPT_ONLY_GLOBALS,
+        }
+    }
+
+    pub fn is_global(self, name: &str) -> bool {
+        match self {
+            GlobalContext::Script => EXTENDSCRIPT_GLOBALS.contains(&name),
+            GlobalContext::Expression => !SCRIPT_ONLY_GLOBALS.contains(&name),
+        }
+    }
+}
+
+/// A function body (or the global scope) discovered in a script
+#[derive(Debug, Clone)]
+pub struct FunctionScope {
+    pub name: Option<String>,
+    pub parent: Option<usize>,
+    /// Names declared with `var`/`let`/`const`, function declarations, parameters and catch bindings
+    pub declared: HashSet<String>,
+    /// Line where the scope starts
+    pub line: usize,
+    /// Index in `ScopeAnalysis::tokens` of the `function` keyword (0 for the global scope)
+    pub start: usize,
+}
+
+/// An assignment to a name that is not declared in any enclosing scope
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplicitGlobal {
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Function-level scope information for a script
+#[derive(Debug, Clone)]
+pub struct ScopeAnalysis {
+    pub tokens: Vec<Token>,
+    /// Scope 0 is always the global scope
+    pub scopes: Vec<FunctionScope>,
+    /// Innermost scope index of each token in `tokens`
+    pub token_scopes: Vec<usize>,
+}
+
+impl ScopeAnalysis {
+    /// Looks up a name in `scope` and its parents
+    pub fn is_declared(&self, name: &str, scope: usize) -> bool {
+        let mut current = Some(scope);
+        while let Some(index) = current {
+            if self.scopes[index].declared.contains(name) {
+                return true;
+            }
+            current = self.scopes[index].parent;
+        }
+        false
+    }
+
+    pub fn global_declarations(&self) -> &HashSet<String> {
+        &self.scopes[0].declared
+    }
+
+    /// Display name of a scope: the function name, the target an anonymous
+    /// function is assigned to (`obj.onClick`), or `<global>`
+    pub fn scope_label(&self, scope: usize) -> String {
+        let function = &self.scopes[scope];
+        if scope == 0 {
+            return "<global>".to_string();
+        }
+        if let Some(name) = &function.name {
+            return name.clone();
+        }
+
+        let i = function.start;
+        if i >= 2 && (self.tokens[i - 1].is_punct("=") || self.tokens[i - 1].is_punct(":"))
+            && self.tokens[i - 2].is_identifier() {
+            let mut first = i - 2;
+            while first >= 2 && self.tokens[first - 1].is_punct(".") && self.tokens[first - 2].is_identifier() {
+                first -= 2;
+            }
+            return self.tokens[first..i - 1].iter().map(|t| t.text.as_str()).collect();
+        }
+        format!("<anonymous:{}>", function.line)
+    }
+
+    /// Returns the first assignment of every implicitly created global
+    pub fn implicit_globals(&self) -> Vec<ImplicitGlobal> {
+        let mut found = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (i, token) in self.tokens.iter().enumerate() {
+            if !token.is_identifier() {
+                continue;
+            }
+            let next = match self.tokens.get(i + 1) {
+                Some(next) => next,
+                None => continue,
+            };
+            let is_assignment = next.kind == TokenKind::Punctuator && is_assignment_operator(&next.text);
+            let is_for_in = i >= 2
+                && self.tokens[i - 1].is_punct("(")
+                && self.tokens[i - 2].is_keyword("for")
+                && next.is_keyword("in");
+            if !is_assignment && !is_for_in {
+                continue;
+            }
+            if i > 0 {
+                let prev = &self.tokens[i - 1];
+                if prev.is_punct(".") || prev.is_punct("?.")
+                    || prev.is_keyword("var") || prev.is_keyword("let") || prev.is_keyword("const") {
+                    continue;
+                }
+            }
+            // `MyLib = MyLib || {}` deliberately shares a namespace between scripts
+            let is_namespace = next.is_punct("=")
+                && self.tokens.get(i + 2).is_some_and(|t| t.text == token.text)
+                && self.tokens.get(i + 3).is_some_and(|t| t.is_punct("||"));
+            if is_namespace
+                || self.is_declared(&token.text, self.token_scopes[i])
+                || EXTENDSCRIPT_GLOBALS.contains(&token.text.as_str())
+                || !seen.insert(token.text.clone()) {
+                continue;
+            }
+
+            found.push(ImplicitGlobal {
+                name: token.text.clone(),
+                line: token.line,
+                column: token.column,
+            });
+        }
+
+        found
+    }
+}
+
+/// Builds function-level scopes for a script.
+///
+/// ExtendScript follows ES3 semantics, so `var` declarations are hoisted to
+/// the enclosing function and blocks do not introduce scopes.
+pub fn analyze_scopes(script: &str) -> ScopeAnalysis {
+    let tokens = significant_tokens(script);
+    let mut scopes = vec![FunctionScope {
+        name: None,
+        parent: None,
+        declared: HashSet::new(),
+        line: 1,
+        start: 0,
+    }];
+    let mut token_scopes = Vec::with_capacity(tokens.len());
+    // (scope index, brace depth of the body's opening brace)
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+    let mut brace_depth = 0usize;
+    let mut pending_function: Option<usize> = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let current = stack.last().map_or(0, |(scope, _)| *scope);
+
+        if token.is_keyword("function") {
+            let name = tokens.get(i + 1).filter(|t| t.is_identifier()).map(|t| t.text.clone());
+            let scope_index = scopes.len();
+            let mut scope = FunctionScope {
+                name: name.clone(),
+                parent: Some(current),
+                declared: HashSet::new(),
+                line: token.line,
+                start: i,
+            };
+            if let Some(name) = name {
+                if is_declaration_position(&tokens, i) {
+                    scopes[current].declared.insert(name);
+                } else {
+                    // Named function expressions only bind their name inside the body
+                    scope.declared.insert(name);
+                }
+            }
+            let params_start = if scope.name.is_some() { i + 2 } else { i + 1 };
+            if tokens.get(params_start).is_some_and(|t| t.is_punct("(")) {
+                for param in tokens[params_start + 1..].iter().take_while(|t| !t.is_punct(")")) {
+                    if param.is_identifier() {
+                        scope.declared.insert(param.text.clone());
+                    }
+                }
+            }
+            scopes.push(scope);
+            pending_function = Some(scope_index);
+        } else if token.is_keyword("var") || token.is_keyword("let") || token.is_keyword("const") {
+            for name in declared_names(&tokens, i) {
+                scopes[current].declared.insert(name);
+            }
+        } else if token.is_keyword("catch") {
+            if tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+                if let Some(binding) = tokens.get(i + 2).filter(|t| t.is_identifier()) {
+                    scopes[current].declared.insert(binding.text.clone());
+                }
+            }
+        } else if token.is_punct("{") {
+            brace_depth += 1;
+            if let Some(scope_index) = pending_function.take() {
+                stack.push((scope_index, brace_depth));
+            }
+        } else if token.is_punct("}") {
+            if stack.len() > 1 && stack.last().is_some_and(|(_, depth)| *depth == brace_depth) {
+                stack.pop();
+            }
+            brace_depth = brace_depth.saturating_sub(1);
+        }
+
+        token_scopes.push(stack.last().map_or(0, |(scope, _)| *scope));
+    }
+
+    ScopeAnalysis { tokens, scopes, token_scopes }
+}
+
+/// Reports assignments that silently create globals in the shared ExtendScript engine
+pub fn validate_implicit_globals(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let analysis = analyze_scopes(script);
+
+    analysis.implicit_globals().into_iter().map(|global| {
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(global.line)
+            .column(global.column)
+            .length(global.name.chars().count())
+            .code_snippet(script.lines().nth(global.line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some(format!(
+                "Declare it with 'var {}' in the enclosing function, or attach it to a namespace object (e.g. MyScript.{})",
+                global.name, global.name
+            )))
+            .rule("implicit-global")
+            .arg("name", &global.name)
+            .build();
+
+        ValidatorError::Scope {
+            message: format!("Assignment to undeclared variable '{}' creates an implicit global", global.name),
+            context,
+            severity: ErrorSeverity::Warning,
+            variable: global.name,
+        }
+    }).collect()
+}
+
+/// Identifier references in `analysis` that resolve to a global of the
+/// other context: not a member access, not an object key and not declared
+/// by the code itself (in any scope, since that shadows the global)
+fn foreign_global_refs(analysis: &ScopeAnalysis, context: GlobalContext) -> Vec<&Token> {
+    let tokens = &analysis.tokens;
+    tokens.iter().enumerate()
+        .filter(|(i, token)| {
+            token.is_identifier()
+                && context.foreign_globals().contains(&token.text.as_str())
+                && !analysis.scopes.iter().any(|scope| scope.declared.contains(&token.text))
+                && !(*i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?.")))
+                && !(tokens.get(i + 1).is_some_and(|next| next.is_punct(":"))
+                    && *i > 0 && (tokens[i - 1].is_punct("{") || tokens[i - 1].is_punct(",")))
+        })
+        .map(|(_, token)| token)
+        .collect()
+}
+
+/// Reports globals used in the wrong context: expression-only globals such
+/// as `thisComp` in script code, where they are undefined, and script
+/// globals such as `app` inside expression strings, where they do not exist
+pub fn validate_context_globals(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let analysis = analyze_scopes(script);
+    let mut errors = Vec::new();
+    let snippet = |line: usize| script.lines().nth(line - 1).unwrap_or("").trim().to_string();
+
+    for token in foreign_global_refs(&analysis, GlobalContext::Script) {
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(token.line)
+            .column(token.column)
+            .length(token.text.chars().count())
+            .code_snippet(snippet(token.line))
+            .suggestion(Some(format!(
+                "'{}' only exists inside expressions; in a script, reach the comp or layer through app.project (e.g. app.project.activeItem)",
+                token.text
+            )))
+            .rule("expression-global-in-script")
+            .build();
+        errors.push(ValidatorError::Scope {
+            message: format!("'{}' is an expression global and is undefined in scripts", token.text),
+            context,
+            severity: ErrorSeverity::Error,
+            variable: token.text.clone(),
+        });
+    }
+
+    // String literals assigned with `.expression = "..."` or passed to `setExpression("...")`
+    let tokens = &analysis.tokens;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.kind != TokenKind::String || token.text.len() < 2 || i < 3 {
+            continue;
+        }
+        let assigned = tokens[i - 1].is_punct("=") && tokens[i - 2].text == "expression" && tokens[i - 3].is_punct(".");
+        let passed = tokens[i - 1].is_punct("(") && tokens[i - 2].text == "setExpression" && tokens[i - 3].is_punct(".");
+        if !assigned && !passed {
+            continue;
+        }
+
+        let expression = token.text[1..token.text.len() - 1].replace("\\\"", "\"").replace("\\'", "'");
+        let inner = analyze_scopes(&expression);
+        for global in foreign_global_refs(&inner, GlobalContext::Expression) {
+            let line = token.line + global.line - 1;
+            let column = if global.line == 1 { token.column + global.column } else { global.column };
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(line)
+                .column(column)
+                .length(global.text.chars().count())
+                .code_snippet(snippet(line))
+                .suggestion(Some(format!(
+                    "Expressions cannot reach '{}'; compute the value in the script and write it into the expression string",
+                    global.text
+                )))
+                .rule("script-global-in-expression")
+                .build();
+            errors.push(ValidatorError::Scope {
+                message: format!("'{}' is a script global and is undefined inside expressions", global.text),
+                context,
+                severity: ErrorSeverity::Warning,
+                variable: global.text.clone(),
+            });
+        }
+    }
+
+    errors.sort_by_key(|e| (e.get_context().line, e.get_context().column));
+    errors
+}
+
+fn is_assignment_operator(op: &str) -> bool {
+    matches!(op,
+        "=" | "+=" | "-=" | "*=" | "/=" | "%=" | "&=" | "|=" | "^=" |
+        "<<=" | ">>=" | ">>>=" | "**=" | "&&=" | "||=" | "??="
+    )
+}
+
+fn is_declaration_position(tokens: &[Token], index: usize) -> bool {
+    match index.checked_sub(1).and_then(|i| tokens.get(i)) {
+        None => true,
+        Some(prev) => prev.is_punct(";") || prev.is_punct("{") || prev.is_punct("}"),
+    }
+}
+
+/// Collects the names bound by a `var` statement starting at `index`
+fn declared_names(tokens: &[Token], index: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    match tokens.get(index + 1) {
+        Some(first) if first.is_identifier() => names.push(first.text.clone()),
+        _ => return names,
+    }
+
+    let mut nesting = 0usize;
+    let mut j = index + 2;
+    while let Some(token) = tokens.get(j) {
+        let prev = &tokens[j - 1];
+        // Automatic semicolon insertion between two operands on different lines
+        if nesting == 0 && token.newlines_before > 0 && ends_operand(prev) && starts_operand(token) {
+            break;
+        }
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            nesting += 1;
+        } else if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            if nesting == 0 {
+                break;
+            }
+            nesting -= 1;
+        } else if nesting == 0 && (token.is_punct(";") || token.is_keyword("in")) {
+            break;
+        } else if nesting == 0 && token.is_punct(",") {
+            if let Some(next) = tokens.get(j + 1).filter(|t| t.is_identifier()) {
+                names.push(next.text.clone());
+            }
+        }
+        j += 1;
+    }
+
+    names
+}
+
+fn ends_operand(token: &Token) -> bool {
+    matches!(token.kind, TokenKind::Identifier | TokenKind::Number | TokenKind::String | TokenKind::Regex)
+        || token.is_punct(")") || token.is_punct("]")
+}
+
+fn starts_operand(token: &Token) -> bool {
+    matches!(token.kind, TokenKind::Identifier | TokenKind::Keyword)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn implicit_names(script: &str) -> Vec<String> {
+        analyze_scopes(script).implicit_globals().into_iter().map(|g| g.name).collect()
+    }
+
+    #[test]
+    fn test_detects_assignment_without_var() {
+        assert_eq!(implicit_names("compName = \"foo\";"), vec!["compName"]);
+    }
+
+    #[test]
+    fn test_declarations_are_hoisted_per_function() {
+        let script = r#"
+            function build(comp) {
+                total = 0;
+                for (var i = 1; i <= comp.numLayers; i++) {
+                    total += i;
+                }
+                var total;
+                comp.name = "Built";
+            }
+        "#;
+        assert!(implicit_names(script).is_empty());
+    }
+
+    #[test]
+    fn test_inner_function_sees_outer_declarations() {
+        let script = r#"
+            var count = 0, label;
+            function bump() { count += 1; label = "x"; leaked = true; }
+        "#;
+        assert_eq!(implicit_names(script), vec!["leaked"]);
+    }
+
+    #[test]
+    fn test_catch_params_and_for_in() {
+        let script = "try { risky(); } catch (e) { e = null; }\nfor (key in obj) {}";
+        assert_eq!(implicit_names(script), vec!["key"]);
+    }
+
+    #[test]
+    fn test_reports_each_name_once() {
+        let errors = validate_implicit_globals("a = 1;\na = 2;\n", "test.jsx");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule_id(), Some("implicit-global"));
+        assert!(validate_implicit_globals("MyLib = MyLib || {};\n", "test.jsx").is_empty());
+    }
+
+    #[test]
+    fn test_globals_in_the_wrong_context() {
+        let script = r#"var layer = thisComp.layer(1);
+var opts = { thisLayer: 1 };
+function build(thisProperty) { return thisProperty; }
+layer.transform.opacity.expression = "app.project.activeItem ? 50 : thisComp.numLayers";
+layer.transform.rotation.setExpression("var thisApp = 1; time * 10");
+"#;
+        let errors = validate_context_globals(script, "ctx.jsx");
+        let found: Vec<(Option<&str>, usize, usize)> = errors.iter()
+            .map(|e| (e.rule_id(), e.get_context().line.unwrap(), e.get_context().column.unwrap()))
+            .collect();
+        assert_eq!(found, vec![
+            (Some("expression-global-in-script"), 1, 13),
+            (Some("script-global-in-expression"), 4, 39),
+        ]);
+    }
+}
@@ -0,0 +1,277 @@
+use super::lexer::{tokenize, Token, TokenKind};
+use super::super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+
+/// A structural problem found while checking delimiters and literals
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxIssue {
+    pub message: String,
+    /// Message catalog key, e.g. `syntax.unclosed-brace`
+    pub key: String,
+    pub line: usize,
+    pub column: usize,
+    /// Characters covered by the offending token
+    pub length: usize,
+    pub suggestion: Option<String>,
+    /// An unclosed or unmatched delimiter, rather than a bad literal
+    pub delimiter: bool,
+}
+
+/// An opening delimiter waiting for its closer
+#[derive(Debug, Clone, Copy)]
+struct Open {
+    delimiter: char,
+    line: usize,
+    column: usize,
+}
+
+/// Checks delimiters, strings and comments, recovering after each error.
+///
+/// Recovery is panic-mode: an error is reported once and the parser
+/// resynchronizes at the next point it can trust, so one typo does not hide
+/// or duplicate problems in the rest of the file:
+///
+/// - a closer that matches an opener further down the stack closes the
+///   openers above it, which are reported as unclosed;
+/// - a closer with no matching opener is reported and skipped;
+/// - a statement keyword after `;` or `}` while a `(` or `[` is open ends
+///   that parenthesized construct;
+/// - once the delimiters are known not to balance, a function declaration
+///   in column 1 while braces are open is treated as a new top-level
+///   function, closing the unterminated blocks before it. Balanced scripts
+///   are left alone, since unindented code inside a wrapper such as an IIFE
+///   also starts in column 1.
+pub fn check_structure(script: &str) -> Vec<SyntaxIssue> {
+    let tokens = tokenize(script);
+    let issues = scan(&tokens, false);
+    if issues.iter().any(|issue| issue.delimiter) {
+        scan(&tokens, true)
+    } else {
+        issues
+    }
+}
+
+/// One pass over the tokens; `recover_at_functions` enables the column-1
+/// function recovery point
+fn scan(tokens: &[Token], recover_at_functions: bool) -> Vec<SyntaxIssue> {
+    let mut issues = Vec::new();
+    let mut stack: Vec<Open> = Vec::new();
+    let mut prev: Option<&Token> = None;
+
+    for token in tokens {
+        match token.kind {
+            TokenKind::String | TokenKind::Template => check_string(token, &mut issues),
+            TokenKind::BlockComment if !token.text.ends_with("*/") || token.text.len() < 4 => {
+                issues.push(issue(token, "Unterminated block comment", "syntax.unterminated-comment", "Close the comment with '*/'"));
+            }
+            _ => {}
+        }
+        if token.is_trivia() {
+            continue;
+        }
+
+        if token.kind == TokenKind::Keyword && is_statement_start(prev) {
+            if matches!(token.text.as_str(), "function" | "var" | "if" | "for" | "while" | "return" | "switch" | "try") {
+                while let Some(open) = stack.last().copied().filter(|o| o.delimiter != '{') {
+                    stack.pop();
+                    issues.push(unclosed(open, Some(token.line)));
+                }
+            }
+            if recover_at_functions && token.text == "function" && token.column == 1 {
+                while let Some(open) = stack.pop() {
+                    issues.push(unclosed(open, Some(token.line)));
+                }
+            }
+        }
+
+        if token.kind == TokenKind::Punctuator {
+            match token.text.as_str() {
+                "(" | "[" | "{" => stack.push(Open {
+                    delimiter: token.text.chars().next().unwrap_or('('),
+                    line: token.line,
+                    column: token.column,
+                }),
+                ")" | "]" | "}" => {
+                    let opener = opener_of(&token.text);
+                    match stack.iter().rposition(|o| o.delimiter == opener) {
+                        Some(index) => {
+                            for open in stack.drain(index + 1..).rev() {
+                                issues.push(unclosed(open, Some(token.line)));
+                            }
+                            stack.pop();
+                        }
+                        None => issues.push(SyntaxIssue {
+                            delimiter: true,
+                            ..issue(
+                                token,
+                                &format!("Unmatched closing {}", delimiter_name(opener)),
+                                &format!("syntax.unmatched-{}", delimiter_name(opener)),
+                                &format!("Remove the '{}' or add the matching '{}'", token.text, opener),
+                            )
+                        }),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        prev = Some(token);
+    }
+
+    for open in stack.into_iter().rev() {
+        issues.push(unclosed(open, None));
+    }
+
+    issues.sort_by_key(|i| (i.line, i.column));
+    issues
+}
+
+/// Reports every independent structural error in the script
+pub fn validate_syntax(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    check_structure(script).into_iter().map(|issue| {
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(issue.line)
+            .column(issue.column)
+            .length(issue.length)
+            .code_snippet(script.lines().nth(issue.line - 1).unwrap_or("").trim().to_string())
+            .suggestion(issue.suggestion)
+            .rule("syntax")
+            .message_key(&issue.key)
+            .build();
+
+        ValidatorError::Script {
+            message: issue.message,
+            context,
+            severity: ErrorSeverity::Error,
+        }
+    }).collect()
+}
+
+fn check_string(token: &Token, issues: &mut Vec<SyntaxIssue>) {
+    let quote = token.text.chars().next().unwrap_or('"');
+    let mut escaped = false;
+    let mut closed = false;
+    for (i, c) in token.text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            closed = i + c.len_utf8() == token.text.len();
+        }
+    }
+    if !closed {
+        let (message, key, suggestion) = if token.kind == TokenKind::Template {
+            ("Unterminated template literal", "syntax.unterminated-template", "Close the template literal with '`'")
+        } else {
+            ("Unterminated string literal", "syntax.unterminated-string", "Close the string on the same line, or join lines with '+'")
+        };
+        issues.push(issue(token, message, key, suggestion));
+    }
+}
+
+fn is_statement_start(prev: Option<&Token>) -> bool {
+    prev.is_none_or(|p| p.is_punct(";") || p.is_punct("}"))
+}
+
+fn opener_of(closer: &str) -> char {
+    match closer {
+        ")" => '(',
+        "]" => '[',
+        _ => '{',
+    }
+}
+
+fn closer_of(opener: char) -> char {
+    match opener {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+fn delimiter_name(opener: char) -> &'static str {
+    match opener {
+        '(' => "parenthesis",
+        '[' => "bracket",
+        _ => "brace",
+    }
+}
+
+fn issue(token: &Token, message: &str, key: &str, suggestion: &str) -> SyntaxIssue {
+    SyntaxIssue {
+        message: message.to_string(),
+        key: key.to_string(),
+        line: token.line,
+        column: token.column,
+        length: token.text.lines().next().unwrap_or("").chars().count().max(1),
+        suggestion: Some(suggestion.to_string()),
+        delimiter: false,
+    }
+}
+
+fn unclosed(open: Open, before_line: Option<usize>) -> SyntaxIssue {
+    let location = match before_line {
+        Some(line) => format!("before line {}", line),
+        None => "before the end of the file".to_string(),
+    };
+    SyntaxIssue {
+        message: format!("Unclosed {} opened here", delimiter_name(open.delimiter)),
+        key: format!("syntax.unclosed-{}", delimiter_name(open.delimiter)),
+        line: open.line,
+        column: open.column,
+        length: 1,
+        suggestion: Some(format!("Add '{}' {}", closer_of(open.delimiter), location)),
+        delimiter: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(script: &str) -> Vec<(usize, String)> {
+        check_structure(script).into_iter().map(|i| (i.line, i.message)).collect()
+    }
+
+    #[test]
+    fn test_balanced_script_has_no_issues() {
+        let script = "function a(x) {\n    if (x) { return [1, 2]; }\n}\n// don't\nvar s = \"{\";\n";
+        assert!(check_structure(script).is_empty());
+    }
+
+    #[test]
+    fn test_missing_brace_does_not_swallow_later_functions() {
+        let script = "function a() {\n    if (x) {\n        go();\n}\n\nfunction b() {\n    run(;\n}\n\nfunction c() {\n}\n}\n";
+        assert_eq!(messages(script), vec![
+            (1, "Unclosed brace opened here".to_string()),
+            (7, "Unclosed parenthesis opened here".to_string()),
+            (12, "Unmatched closing brace".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_unindented_functions_in_iife_are_not_recovery_points() {
+        let script = "(function () {\nvar a = 1;\nfunction helper() {}\nfunction run() {\n    helper();\n}\n})();\n";
+        assert!(check_structure(script).is_empty());
+    }
+
+    #[test]
+    fn test_unclosed_paren_recovers_at_next_statement() {
+        let script = "var a = foo(1, 2;\nvar b = [1, 2;\nvar c = bar(3);\n";
+        assert_eq!(messages(script), vec![
+            (1, "Unclosed parenthesis opened here".to_string()),
+            (2, "Unclosed bracket opened here".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_unterminated_literals_are_reported_per_line() {
+        let script = "var a = \"open;\nvar b = 'fine';\nvar c = 'also open;\n/* never closed";
+        assert_eq!(messages(script), vec![
+            (1, "Unterminated string literal".to_string()),
+            (3, "Unterminated string literal".to_string()),
+            (4, "Unterminated block comment".to_string()),
+        ]);
+    }
+}
@@ -4,7 +4,7 @@ use regex::Regex;
 use lazy_static::lazy_static;
 
 lazy_static! {
-    static ref TOOL_CALL_RE: Regex = Regex::new(r#"executeComprehensiveAITool\s*\(\s*["']([^"']+)["']\s*,\s*({[^}]+}|\[[^\]]+\]|[^,)]+)\s*\)"#).unwrap();
+    static ref TOOL_CALL_RE: Regex = Regex::new(r#"executeComprehensiveAITool\s*\(\s*["']([^"']+)["']\s*,\s*(\{[^}]+\}|\[[^\]]+\]|[^,)]+)\s*\)"#).unwrap();
     static ref TOOL_LIST_RE: Regex = Regex::new(r#"getComprehensiveAITools\(\s*\)"#).unwrap();
     static ref CATEGORY_CHECK_RE: Regex = Regex::new(r#"detectToolCategory\s*\(\s*["']([^"']+)["']\s*\)"#).unwrap();
 }
@@ -38,6 +38,7 @@ pub fn analyze_tool_usage(script: &str) -> Vec<ToolUsage> {
     usages
 }
 
+#[allow(clippy::result_large_err)]
 pub fn validate_tool_parameters(usage: &ToolUsage) -> Result<(), ValidatorError> {
     // Try to parse parameters as JSON
     if usage.parameters.starts_with('{') {
@@ -65,6 +66,7 @@ pub fn validate_tool_parameters(usage: &ToolUsage) -> Result<(), ValidatorError>
     }
 }
 
+#[allow(clippy::result_large_err)]
 fn validate_basic_parameter_syntax(params: &str, usage: &ToolUsage) -> Result<(), ValidatorError> {
     // Check for common syntax issues in non-JSON parameters
     if params.contains("undefined") || params.contains("null") {
@@ -105,13 +107,9 @@ fn validate_basic_parameter_syntax(params: &str, usage: &ToolUsage) -> Result<()
 
 pub fn check_tool_dependencies(script: &str) -> Vec<ValidatorError> {
     let mut errors = Vec::new();
-    let mut has_tool_list_check = false;
-    
+
     // Check if script verifies tool availability
-    for cap in TOOL_LIST_RE.captures_iter(script) {
-        has_tool_list_check = true;
-        break;
-    }
+    let has_tool_list_check = TOOL_LIST_RE.is_match(script);
 
     // Find all tool usages
     let usages = analyze_tool_usage(script);
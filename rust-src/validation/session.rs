@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use super::config::ValidatorConfig;
+use super::errors::{ErrorSeverity, ValidatorError};
+use super::includes::{validate_duplicate_globals, IncludeGraph, IncludedFile};
+use super::script::scope::analyze_scopes;
+use super::script::{validate_script_with_config, ScriptValidationResult};
+
+/// Scripts that share one ExtendScript engine, validated together.
+///
+/// Scripts run in the same engine see each other's globals, and whichever
+/// defines a global last replaces it for all of them. A session holds
+/// scripts in the order they run, so an editor can validate the open file
+/// in the context of its project: globals declared by the other scripts
+/// count as defined, and definitions that collide across scripts are
+/// reported.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationSession {
+    config: ValidatorConfig,
+    scripts: Vec<IncludedFile>,
+}
+
+impl ValidationSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: ValidatorConfig) -> Self {
+        Self { config, scripts: Vec::new() }
+    }
+
+    /// Adds a script after those already in the session. A script with the
+    /// same name keeps its place and takes the new text, e.g. when an
+    /// editor buffer changes.
+    pub fn add_script(&mut self, name: &str, text: &str) {
+        self.add_file(IncludedFile { path: PathBuf::from(name), name: name.to_string(), text: text.to_string() });
+    }
+
+    /// Adds an entry script and the files it includes, in load order
+    pub fn add_include_graph(&mut self, graph: &IncludeGraph) {
+        for file in &graph.files {
+            self.add_file(file.clone());
+        }
+    }
+
+    fn add_file(&mut self, file: IncludedFile) {
+        match self.scripts.iter_mut().find(|script| script.name == file.name) {
+            Some(script) => script.text = file.text,
+            None => self.scripts.push(file),
+        }
+    }
+
+    /// Removes a script, returning whether it was in the session
+    pub fn remove_script(&mut self, name: &str) -> bool {
+        let before = self.scripts.len();
+        self.scripts.retain(|script| script.name != name);
+        self.scripts.len() != before
+    }
+
+    /// Names of the scripts, in the order they run
+    pub fn script_names(&self) -> Vec<&str> {
+        self.scripts.iter().map(|script| script.name.as_str()).collect()
+    }
+
+    /// Every global declared at the top level of a script, with the name of
+    /// the first script declaring it
+    pub fn globals(&self) -> BTreeMap<String, String> {
+        let mut globals = BTreeMap::new();
+        for script in &self.scripts {
+            for name in analyze_scopes(&script.text).global_declarations() {
+                globals.entry(name.clone()).or_insert_with(|| script.name.clone());
+            }
+        }
+        globals
+    }
+
+    /// Globals that two scripts of the session define differently
+    pub fn collisions(&self) -> Vec<ValidatorError> {
+        validate_duplicate_globals(&IncludeGraph { files: self.scripts.clone(), unresolved: Vec::new() })
+    }
+
+    /// Validates one script of the session. Assignments to globals another
+    /// script declares are not reported as implicit globals, and collisions
+    /// whose later definition is in this script are added.
+    pub fn validate(&self, name: &str) -> Result<ScriptValidationResult, String> {
+        let script = self.scripts.iter()
+            .find(|script| script.name == name)
+            .ok_or_else(|| format!("No script named '{}' in the session", name))?;
+        let shared: HashSet<String> = self.scripts.iter()
+            .filter(|other| other.name != name)
+            .flat_map(|other| analyze_scopes(&other.text).global_declarations().clone())
+            .collect();
+
+        let mut result = validate_script_with_config(&script.text, &script.name, &self.config)?;
+        let declared_elsewhere = |error: &ValidatorError| match error {
+            ValidatorError::Scope { variable, .. } => error.rule_id() == Some("implicit-global") && shared.contains(variable),
+            _ => false,
+        };
+        result.errors.retain(|error| !declared_elsewhere(error));
+        result.warnings.retain(|warning| !declared_elsewhere(warning));
+        result.is_valid = result.errors.is_empty();
+
+        for collision in self.collisions() {
+            if collision.get_context().file.as_deref() != Some(name) {
+                continue;
+            }
+            match collision.severity() {
+                ErrorSeverity::Error | ErrorSeverity::Fatal => result.add_error(collision),
+                _ => result.add_warning(collision),
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(result: &ScriptValidationResult) -> Vec<(&str, usize)> {
+        result.errors.iter().chain(&result.warnings)
+            .filter_map(|e| Some((e.rule_id()?, e.get_context().line?)))
+            .filter(|(rule, _)| *rule == "implicit-global" || *rule == "duplicate-global")
+            .collect()
+    }
+
+    #[test]
+    fn test_scripts_share_globals() {
+        let mut session = ValidationSession::new();
+        session.add_script("lib.jsx", "var Config = { fps: 30 };\nfunction log(m) { $.writeln(m); }\n");
+        session.add_script("main.jsx", "Config = { fps: 24 };\ncounter = 1;\nlog(\"start\");\nfunction log(m) { alert(m); }\n");
+
+        assert_eq!(session.globals().get("Config").map(String::as_str), Some("lib.jsx"));
+        assert_eq!(session.collisions().len(), 1);
+        let result = session.validate("main.jsx").unwrap();
+        assert_eq!(rules(&result), vec![("implicit-global", 2), ("duplicate-global", 4)]);
+
+        session.add_script("main.jsx", "Config = { fps: 24 };\nlog(\"start\");\n");
+        assert_eq!(session.script_names(), vec!["lib.jsx", "main.jsx"]);
+        assert!(rules(&session.validate("main.jsx").unwrap()).is_empty());
+
+        assert!(session.remove_script("lib.jsx"));
+        assert_eq!(rules(&session.validate("main.jsx").unwrap()), vec![("implicit-global", 1)]);
+        assert!(session.validate("missing.jsx").is_err());
+    }
+}
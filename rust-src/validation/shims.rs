@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+use std::path::Path;
+use regex::Regex;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref INCLUDE_RE: Regex = Regex::new(r#"(?m)^\s*(?:#|//@)include\s+["']([^"']+)["']"#).unwrap();
+}
+
+/// Widely used polyfills and the built-ins they add to ExtendScript.
+///
+/// Instance methods are listed by name (`map`), static members with their
+/// owner (`Object.keys`), globals by name (`JSON`).
+pub const KNOWN_SHIMS: &[(&str, &[&str])] = &[
+    ("json2", &["JSON"]),
+    ("es5-shim", &[
+        "map", "filter", "forEach", "reduce", "reduceRight", "some", "every", "trim", "bind",
+        "Array.isArray", "Object.keys", "Object.create", "Date.now",
+    ]),
+    ("extendscript-es5-shim", &[
+        "map", "filter", "forEach", "reduce", "reduceRight", "some", "every", "trim", "bind",
+        "Array.isArray", "Object.keys", "Object.create", "Date.now",
+    ]),
+    ("es6-shim", &["find", "findIndex", "includes", "Object.assign"]),
+];
+
+/// Paths named by `#include` or `//@include` lines, as written
+pub fn include_paths(script: &str) -> Vec<String> {
+    INCLUDE_RE.captures_iter(script).map(|cap| cap[1].to_string()).collect()
+}
+
+/// Names of known shims pulled in with `#include` or `//@include`
+pub fn detect_included_shims(script: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    for path in include_paths(script) {
+        if let Some(name) = shim_name(&path) {
+            if !found.contains(&name) {
+                found.push(name);
+            }
+        }
+    }
+    found
+}
+
+/// Features provided by the given shims; unknown names are ignored
+pub fn provided_features<S: AsRef<str>>(shims: &[S]) -> HashSet<&'static str> {
+    let mut features = HashSet::new();
+    for shim in shims {
+        let name = shim_name(shim.as_ref()).unwrap_or_default();
+        if let Some((_, provides)) = KNOWN_SHIMS.iter().find(|(known, _)| *known == name) {
+            features.extend(provides.iter().copied());
+        }
+    }
+    features
+}
+
+/// Maps an include path or configured name such as `lib/json2.min.js` to `json2`
+fn shim_name(path: &str) -> Option<String> {
+    let file = Path::new(path).file_name()?.to_string_lossy().to_lowercase();
+    let mut name = file.as_str();
+    for suffix in [".jsxinc", ".jsx", ".js", ".min", "-min"] {
+        name = name.strip_suffix(suffix).unwrap_or(name);
+    }
+    KNOWN_SHIMS.iter().find(|(known, _)| *known == name).map(|(known, _)| known.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_includes() {
+        let script = "#include \"lib/json2.jsx\"\n//@include 'vendor/es5-shim.min.js'\n#include \"utils.jsx\"\n";
+        assert_eq!(detect_included_shims(script), vec!["json2", "es5-shim"]);
+    }
+
+    #[test]
+    fn test_provided_features() {
+        let features = provided_features(&["json2", "es6-shim.js", "unknown"]);
+        assert!(features.contains("JSON"));
+        assert!(features.contains("Object.assign"));
+        assert!(!features.contains("map"));
+    }
+}
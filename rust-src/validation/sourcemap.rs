@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use serde::Deserialize;
+use serde_json::Value;
+use super::errors::ValidatorError;
+
+/// Marker a bundler writes on the line before each concatenated module
+const SOURCE_URL_MARKERS: &[&str] = &["//# sourceURL=", "//@ sourceURL="];
+
+/// Maps lines of a concatenated script back to the module they came from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceMap {
+    /// `(first bundle line, file, original line of that bundle line)`, sorted
+    /// by bundle line; each range runs until the next one starts
+    ranges: Vec<(usize, String, usize)>,
+}
+
+/// One module in an offset manifest: `{"file": "lib/ui.jsx", "start": 120, "lines": 48}`
+#[derive(Debug, Clone, Deserialize)]
+struct OffsetEntry {
+    file: String,
+    /// Bundle line of the module's first line
+    start: usize,
+    /// Line count; when omitted the module runs until the next one
+    #[serde(default)]
+    lines: Option<usize>,
+}
+
+impl SourceMap {
+    /// Loads a source map (version 3) or an offset manifest (a JSON array of
+    /// `{"file", "start", "lines"}` entries)
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        Self::from_json(&content)
+            .map_err(|e| format!("Invalid source map {}: {}", path.as_ref().display(), e))
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        if value.is_array() {
+            let entries: Vec<OffsetEntry> = serde_json::from_value(value).map_err(|e| e.to_string())?;
+            return Ok(Self::from_offsets(entries));
+        }
+        if value.get("version").and_then(Value::as_u64) != Some(3) {
+            return Err("expected a version 3 source map or an array of offsets".to_string());
+        }
+        let sources: Vec<String> = value.get("sources")
+            .and_then(Value::as_array)
+            .map(|sources| sources.iter().map(|s| s.as_str().unwrap_or("").to_string()).collect())
+            .unwrap_or_default();
+        let mappings = value.get("mappings").and_then(Value::as_str).ok_or("missing mappings")?;
+        Self::from_mappings(mappings, &sources)
+    }
+
+    /// Uses `//# sourceURL=<file>` lines written between concatenated
+    /// modules; the lines after each marker belong to that file. Returns
+    /// `None` when the script has no markers.
+    pub fn from_source_urls(script: &str) -> Option<Self> {
+        let ranges: Vec<(usize, String, usize)> = script.lines().enumerate()
+            .filter_map(|(index, line)| {
+                let line = line.trim();
+                SOURCE_URL_MARKERS.iter()
+                    .find_map(|marker| line.strip_prefix(marker))
+                    .map(|file| (index + 2, file.trim().to_string(), 1))
+            })
+            .collect();
+        if ranges.is_empty() {
+            None
+        } else {
+            Some(Self { ranges })
+        }
+    }
+
+    fn from_offsets(entries: Vec<OffsetEntry>) -> Self {
+        let starts: HashSet<usize> = entries.iter().map(|entry| entry.start).collect();
+        let mut ranges = Vec::new();
+        for entry in entries {
+            if let Some(end) = entry.lines.map(|lines| entry.start + lines) {
+                // Lines after the module that belong to no file (e.g. a bundler footer)
+                if !starts.contains(&end) {
+                    ranges.push((end, String::new(), 1));
+                }
+            }
+            ranges.push((entry.start, entry.file, 1));
+        }
+        ranges.sort_by_key(|(start, _, _)| *start);
+        Self { ranges }
+    }
+
+    /// Line-level mapping from the VLQ `mappings` field: each bundle line maps
+    /// to the source and line of its first segment
+    fn from_mappings(mappings: &str, sources: &[String]) -> Result<Self, String> {
+        let mut ranges = Vec::new();
+        let (mut source, mut original_line) = (0i64, 0i64);
+        for (index, line) in mappings.split(';').enumerate() {
+            let mut first = None;
+            for segment in line.split(',').filter(|s| !s.is_empty()) {
+                let fields = decode_vlq(segment)?;
+                if fields.len() >= 4 {
+                    source += fields[1];
+                    original_line += fields[2];
+                    first.get_or_insert((source, original_line));
+                }
+            }
+            if let Some((source, original_line)) = first {
+                let file = usize::try_from(source).ok().and_then(|s| sources.get(s))
+                    .ok_or_else(|| format!("mapping refers to unknown source {}", source))?;
+                ranges.push((index + 1, file.clone(), original_line as usize + 1));
+            }
+        }
+        Ok(Self { ranges })
+    }
+
+    /// Original file and line of a bundle line
+    pub fn lookup(&self, line: usize) -> Option<(&str, usize)> {
+        let index = self.ranges.partition_point(|(start, _, _)| *start <= line).checked_sub(1)?;
+        let (start, file, original) = &self.ranges[index];
+        if file.is_empty() {
+            return None;
+        }
+        Some((file.as_str(), original + (line - start)))
+    }
+
+    /// Points a diagnostic at the original file and line. The bundle line is
+    /// kept as the code snippet, since the original file is not read.
+    pub fn apply(&self, error: &mut ValidatorError, bundle: &str) {
+        let context = error.get_context_mut();
+        let line = match context.line {
+            Some(line) => line,
+            None => return,
+        };
+        if let Some((file, original_line)) = self.lookup(line) {
+            if context.code_snippet.is_none() {
+                context.code_snippet = bundle.lines().nth(line - 1).map(|text| text.trim().to_string());
+            }
+            context.file = Some(file.to_string());
+            context.line = Some(original_line);
+        }
+    }
+}
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decodes one base64 VLQ segment of a source map
+fn decode_vlq(segment: &str) -> Result<Vec<i64>, String> {
+    let mut values = Vec::new();
+    let (mut value, mut shift) = (0i64, 0u32);
+    for c in segment.bytes() {
+        let digit = BASE64.iter().position(|&b| b == c)
+            .ok_or_else(|| format!("invalid character '{}' in mappings", c as char))? as i64;
+        value += (digit & 31) << shift;
+        if digit & 32 != 0 {
+            shift += 5;
+        } else {
+            values.push(if value & 1 == 1 { -(value >> 1) } else { value >> 1 });
+            value = 0;
+            shift = 0;
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_url_markers_and_offsets() {
+        let bundle = "//# sourceURL=lib/util.jsx\nfunction pad(n) {\n    return n;\n}\n//# sourceURL=main.jsx\nvar comp = app.project.activeItem;\n";
+        let map = SourceMap::from_source_urls(bundle).unwrap();
+        assert_eq!(map.lookup(1), None);
+        assert_eq!(map.lookup(3), Some(("lib/util.jsx", 2)));
+        assert_eq!(map.lookup(6), Some(("main.jsx", 1)));
+
+        let offsets = SourceMap::from_json(r#"[{"file": "b.jsx", "start": 11, "lines": 5}, {"file": "a.jsx", "start": 1, "lines": 10}]"#).unwrap();
+        assert_eq!(offsets.lookup(10), Some(("a.jsx", 10)));
+        assert_eq!(offsets.lookup(12), Some(("b.jsx", 2)));
+        assert_eq!(offsets.lookup(16), None);
+    }
+
+    #[test]
+    fn test_v3_mappings() {
+        // Line 1 -> a.jsx:1, line 2 -> a.jsx:2, line 3 unmapped, line 4 -> b.jsx:1
+        let map = SourceMap::from_json(r#"{"version": 3, "sources": ["a.jsx", "b.jsx"], "names": [], "mappings": "AAAA;AACA;;ACDA"}"#).unwrap();
+        assert_eq!(map.lookup(2), Some(("a.jsx", 2)));
+        assert_eq!(map.lookup(4), Some(("b.jsx", 1)));
+    }
+}
@@ -0,0 +1,318 @@
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{matching_bracket, significant_tokens, Token};
+use super::script::lines::LineIndex;
+
+const BEGIN: &str = "beginSuppressDialogs";
+const END: &str = "endSuppressDialogs";
+
+const TRY_FINALLY: &str = "Wrap the code in try { ... } finally { app.endSuppressDialogs(false); } so dialogs come back on every path";
+
+/// A problem with `beginSuppressDialogs()`/`endSuppressDialogs()` pairing
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuppressionIssue {
+    pub rule: &'static str,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub severity: ErrorSeverity,
+    /// Byte range and replacement wrapping the suppressed code in try/finally
+    pub fix: Option<(usize, usize, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockKind {
+    Function,
+    Try(usize),
+    /// Body of the `finally` of the try block with this id
+    Finally(usize),
+    Other,
+}
+
+/// A `beginSuppressDialogs()` not yet ended
+struct Suppression {
+    /// Index of the method token
+    token: usize,
+    block: usize,
+    /// `return` statements reached while suppressed, with the ids of the
+    /// try blocks enclosing them
+    exits: Vec<(usize, Vec<usize>)>,
+    calls_between: bool,
+}
+
+/// Pairs `beginSuppressDialogs()` with `endSuppressDialogs()` in each
+/// function and reports what can leave After Effects silently swallowing
+/// error dialogs: a begin that is never ended, a `return` between the two
+/// that skips the end, and an unmatched end. Pairs whose end does not run in
+/// a `finally` also leak when the code between them throws.
+///
+/// Calls pair in order within a function; nested functions are analyzed on
+/// their own.
+pub fn find_suppression_issues(script: &str) -> Vec<SuppressionIssue> {
+    let tokens = significant_tokens(script);
+    let lines = LineIndex::new(script);
+    let mut issues = Vec::new();
+    // Open blocks as (kind, id); the script body is block 0
+    let mut blocks: Vec<(BlockKind, usize)> = vec![(BlockKind::Function, 0)];
+    let mut frames: Vec<Vec<Suppression>> = vec![Vec::new()];
+    let mut next_block = 1;
+    let mut function_pending = false;
+    let mut last_closed_try = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_keyword("function") {
+            function_pending = true;
+            continue;
+        }
+        if token.is_punct("{") {
+            let kind = if function_pending {
+                function_pending = false;
+                frames.push(Vec::new());
+                BlockKind::Function
+            } else if i > 0 && tokens[i - 1].is_keyword("try") {
+                BlockKind::Try(next_block)
+            } else if i > 0 && tokens[i - 1].is_keyword("finally") {
+                last_closed_try.take().map_or(BlockKind::Other, BlockKind::Finally)
+            } else {
+                BlockKind::Other
+            };
+            blocks.push((kind, next_block));
+            next_block += 1;
+            continue;
+        }
+        if token.is_punct("}") {
+            if blocks.len() > 1 {
+                match blocks.pop() {
+                    Some((BlockKind::Function, _)) => {
+                        let open = frames.pop().unwrap_or_default();
+                        report_unended(&tokens, &open, &mut issues);
+                    }
+                    Some((BlockKind::Try(id), _)) => last_closed_try = Some(id),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+
+        let frame = frames.last_mut().expect("the script body is always a frame");
+        if token.is_keyword("return") {
+            let enclosing_tries = enclosing_tries(&blocks);
+            for suppression in frame.iter_mut() {
+                suppression.exits.push((i, enclosing_tries.clone()));
+            }
+            continue;
+        }
+        if !token.is_identifier() || !tokens.get(i + 1).is_some_and(|t| t.is_punct("(")) {
+            continue;
+        }
+        let is_member = i > 0 && tokens[i - 1].is_punct(".");
+
+        if is_member && token.text == BEGIN {
+            frame.push(Suppression { token: i, block: blocks.last().map_or(0, |b| b.1), exits: Vec::new(), calls_between: false });
+        } else if is_member && token.text == END {
+            let suppression = match frame.pop() {
+                Some(suppression) => suppression,
+                None => {
+                    issues.push(issue(
+                        token, "suppress-dialogs-unmatched-end",
+                        "app.endSuppressDialogs() is called without a matching app.beginSuppressDialogs()".to_string(),
+                        ErrorSeverity::Warning,
+                    ));
+                    continue;
+                }
+            };
+            let finally_of = blocks.iter().rev()
+                .take_while(|(kind, _)| *kind != BlockKind::Function)
+                .find_map(|(kind, _)| match kind {
+                    BlockKind::Finally(try_id) => Some(*try_id),
+                    _ => None,
+                });
+            let skipped: Vec<usize> = suppression.exits.iter()
+                .filter(|(_, tries)| finally_of.is_none_or(|try_id| !tries.contains(&try_id)))
+                .map(|(exit, _)| *exit)
+                .collect();
+            for exit in &skipped {
+                issues.push(issue(
+                    &tokens[*exit], "suppress-dialogs-early-exit",
+                    format!("This return skips app.endSuppressDialogs() on line {} and leaves dialogs suppressed", token.line),
+                    ErrorSeverity::Error,
+                ));
+            }
+            if finally_of.is_some() {
+                continue;
+            }
+            if skipped.is_empty() && suppression.calls_between {
+                issues.push(issue(
+                    &tokens[suppression.token], "suppress-dialogs-unprotected",
+                    format!("An error before app.endSuppressDialogs() on line {} leaves dialogs suppressed", token.line),
+                    ErrorSeverity::Warning,
+                ));
+            }
+            if !skipped.is_empty() || suppression.calls_between {
+                let same_block = blocks.last().map_or(0, |b| b.1) == suppression.block;
+                let fix = if same_block { try_finally_fix(script, &lines, &tokens, suppression.token, i) } else { None };
+                if let Some(last) = issues.last_mut() {
+                    last.fix = fix;
+                }
+            }
+        } else {
+            for suppression in frame.iter_mut() {
+                suppression.calls_between = true;
+            }
+        }
+    }
+
+    for open in frames.iter().rev() {
+        report_unended(&tokens, open, &mut issues);
+    }
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+/// Flags suppressions still open when their function ends
+fn report_unended(tokens: &[Token], open: &[Suppression], issues: &mut Vec<SuppressionIssue>) {
+    for suppression in open {
+        issues.push(issue(
+            &tokens[suppression.token], "suppress-dialogs-leak",
+            "app.beginSuppressDialogs() is never ended; After Effects keeps swallowing error dialogs after the script".to_string(),
+            ErrorSeverity::Error,
+        ));
+    }
+}
+
+/// Ids of the try blocks enclosing the current token within its function
+fn enclosing_tries(blocks: &[(BlockKind, usize)]) -> Vec<usize> {
+    blocks.iter().rev()
+        .take_while(|(kind, _)| *kind != BlockKind::Function)
+        .filter_map(|(kind, _)| match kind {
+            BlockKind::Try(id) => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Rewrites the lines between the begin and end statements, both alone on
+/// their lines, into a try block with the end in its finally
+fn try_finally_fix(script: &str, lines: &LineIndex, tokens: &[Token], begin: usize, end: usize) -> Option<(usize, usize, String)> {
+    let (begin_line, _) = statement_line(script, lines, tokens, begin)?;
+    let (end_line, end_statement) = statement_line(script, lines, tokens, end)?;
+    let indent: String = lines.line_text(script, begin_line).chars().take_while(|c| c.is_whitespace()).collect();
+    let start = lines.line_start(begin_line + 1)?;
+    let end_start = lines.line_start(end_line)?;
+    let replace_end = end_start + lines.line_text(script, end_line).len();
+
+    let mut fixed = format!("{}try {{\n", indent);
+    for body_line in script[start..end_start].lines() {
+        if !body_line.trim().is_empty() {
+            fixed.push_str("    ");
+            fixed.push_str(body_line);
+        }
+        fixed.push('\n');
+    }
+    fixed.push_str(&format!("{i}}} finally {{\n{i}    {}\n{i}}}", end_statement, i = indent));
+    Some((start, replace_end, fixed))
+}
+
+/// Line of the `receiver.method(...);` statement whose method token is at
+/// `method`, and its text, when nothing else shares the line
+fn statement_line<'a>(script: &'a str, lines: &LineIndex, tokens: &[Token], method: usize) -> Option<(usize, &'a str)> {
+    let receiver = tokens.get(method.checked_sub(2)?).filter(|t| t.is_identifier())?;
+    let close = matching_bracket(tokens, method + 1)?;
+    let semicolon = tokens.get(close + 1).filter(|t| t.is_punct(";"))?;
+    let statement = &script[receiver.start..semicolon.end];
+    (lines.line_text(script, receiver.line).trim() == statement).then_some((receiver.line, statement))
+}
+
+fn issue(token: &Token, rule: &'static str, message: String, severity: ErrorSeverity) -> SuppressionIssue {
+    SuppressionIssue {
+        rule,
+        message,
+        line: token.line,
+        column: token.column,
+        length: token.text.chars().count(),
+        severity,
+        fix: None,
+    }
+}
+
+/// Validates dialog suppression pairing; see [`find_suppression_issues`]
+pub fn validate_suppressed_dialogs(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    find_suppression_issues(script).into_iter().map(|issue| {
+        let suggestion = match issue.rule {
+            "suppress-dialogs-leak" => "Call app.endSuppressDialogs(false) in a finally block once the quiet work is done".to_string(),
+            "suppress-dialogs-unmatched-end" => "Remove the call, or add app.beginSuppressDialogs() before the work it should silence".to_string(),
+            _ if issue.fix.is_some() => format!("{}; `ae-validator fix` can do this", TRY_FINALLY),
+            _ => TRY_FINALLY.to_string(),
+        };
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(issue.line)
+            .column(issue.column)
+            .length(issue.length)
+            .code_snippet(script.lines().nth(issue.line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some(suggestion))
+            .rule(issue.rule)
+            .build();
+        ValidatorError::Script { message: issue.message, context, severity: issue.severity }
+    }).collect()
+}
+
+/// Wraps unprotected suppressed code in try/finally and returns the new
+/// script with the number of pairs changed
+pub fn fix_suppressed_dialogs(script: &str) -> (String, usize) {
+    let mut fixes: Vec<(usize, usize, String)> = find_suppression_issues(script).into_iter().filter_map(|issue| issue.fix).collect();
+    fixes.sort_by_key(|(start, _, _)| *start);
+    fixes.dedup_by_key(|(start, _, _)| *start);
+
+    let mut output = script.to_string();
+    let mut fixed = 0;
+    let mut applied_start = usize::MAX;
+    for (start, end, replacement) in fixes.into_iter().rev() {
+        // Nested pairs overlap; the outer one is left for the next run
+        if end > applied_start {
+            continue;
+        }
+        output.replace_range(start..end, &replacement);
+        applied_start = start;
+        fixed += 1;
+    }
+    (output, fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suppression_pairing() {
+        let script = r#"app.beginSuppressDialogs();
+try {
+    app.open(file);
+} finally {
+    app.endSuppressDialogs(false);
+}
+function render(queue) {
+    app.beginSuppressDialogs();
+    if (!queue.numItems) return;
+    queue.render();
+    app.endSuppressDialogs(false);
+}
+function quiet() {
+    app.beginSuppressDialogs();
+}
+app.endSuppressDialogs(true);
+"#;
+        let issues = find_suppression_issues(script);
+        let found: Vec<(usize, &str)> = issues.iter().map(|i| (i.line, i.rule)).collect();
+        assert_eq!(found, vec![
+            (9, "suppress-dialogs-early-exit"),
+            (14, "suppress-dialogs-leak"),
+            (16, "suppress-dialogs-unmatched-end"),
+        ]);
+
+        let (fixed, count) = fix_suppressed_dialogs("function go() {\n    app.beginSuppressDialogs();\n    app.project.save();\n\n    app.endSuppressDialogs(false);\n}\n");
+        assert_eq!(count, 1);
+        assert_eq!(fixed, "function go() {\n    app.beginSuppressDialogs();\n    try {\n        app.project.save();\n\n    } finally {\n        app.endSuppressDialogs(false);\n    }\n}\n");
+        assert!(find_suppression_issues(&fixed).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::embedded::{validate_embedded_code, EmbeddedCode, EvaluatedIn};
+use super::script::lexer::{matching_bracket, Token, TokenKind};
+use super::script::scope::analyze_scopes;
+
+/// Checks `app.scheduleTask(code, delay, repeat)` and `app.cancelTask(id)`.
+///
+/// The code string of a scheduled task is validated like a script of its
+/// own, see [`validate_embedded_code`]. It runs later in the global scope,
+/// so local variables of the function that scheduled it are undefined by
+/// then, and a function passed instead of a string is converted to its
+/// source text and never called. Repeating tasks whose id is dropped cannot
+/// be cancelled, and `cancelTask` only accepts an id returned by
+/// `scheduleTask`.
+pub fn validate_scheduled_tasks(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let analysis = analyze_scopes(script);
+    let tokens = &analysis.tokens;
+    let snippet = |line: usize| script.lines().nth(line - 1).unwrap_or("").trim().to_string();
+    let mut errors = Vec::new();
+
+    let task_ids: HashSet<&str> = (0..tokens.len())
+        .filter(|&i| is_app_call(tokens, i + 2, "scheduleTask") && tokens[i + 1].is_punct("=") && tokens[i].is_identifier())
+        .map(|i| tokens[i].text.as_str())
+        .collect();
+    let assigned: HashSet<&str> = (0..tokens.len())
+        .filter(|&i| tokens[i].is_identifier() && tokens.get(i + 1).is_some_and(|t| t.is_punct("="))
+            && !(i > 0 && tokens[i - 1].is_punct(".")))
+        .map(|i| tokens[i].text.as_str())
+        .collect();
+
+    for i in 0..tokens.len() {
+        if is_app_call(tokens, i, "scheduleTask") {
+            let args = arguments(tokens, i + 4);
+            let code = match args.first() {
+                Some(code) => code,
+                None => continue,
+            };
+            let first = &tokens[code.start];
+            if code.len() == 1 && first.kind == TokenKind::String {
+                if let Some(embedded) = EmbeddedCode::from_literal(first) {
+                    let evaluated_in = EvaluatedIn::Global {
+                        scope: analysis.token_scopes[i],
+                        rule: "schedule-task-closure",
+                        code: "Scheduled code",
+                        runs: "the scheduled task runs",
+                    };
+                    errors.extend(validate_embedded_code(&embedded, &analysis, evaluated_in, script, file_path));
+                }
+            } else if first.is_keyword("function") || (code.len() == 1 && is_function_name(tokens, &first.text)) {
+                errors.push(task_error(
+                    file_path, first, snippet(first.line),
+                    "app.scheduleTask() takes a string of code; a function is converted to its source text and never called".to_string(),
+                    format!("Pass the call as a string, e.g. \"{}();\", and make the function global", if first.is_identifier() { first.text.as_str() } else { "myTask" }),
+                    "schedule-task-function",
+                    ErrorSeverity::Error,
+                ));
+            }
+
+            let repeats = args.get(2).is_some_and(|repeat| repeat.len() == 1 && tokens[repeat.start].is_keyword("true"));
+            if repeats && is_statement_start(tokens, i) {
+                errors.push(task_error(
+                    file_path, &tokens[i + 2], snippet(tokens[i].line),
+                    "This repeating task can never be cancelled because its id is discarded".to_string(),
+                    "Keep the id, e.g. var taskId = app.scheduleTask(...), and pass it to app.cancelTask() when the work is done".to_string(),
+                    "schedule-task-uncancellable",
+                    ErrorSeverity::Warning,
+                ));
+            }
+        }
+
+        if is_app_call(tokens, i, "cancelTask") {
+            let args = arguments(tokens, i + 4);
+            let id = match args.first().filter(|id| id.len() == 1) {
+                Some(id) => &tokens[id.start],
+                None => continue,
+            };
+            let message = if id.kind == TokenKind::Number {
+                format!("app.cancelTask({}) uses a hard-coded id; After Effects assigns task ids when tasks are scheduled", id.text)
+            } else if id.is_identifier() && assigned.contains(id.text.as_str()) && !task_ids.contains(id.text.as_str()) {
+                format!("'{}' is never assigned an id returned by app.scheduleTask()", id.text)
+            } else {
+                continue;
+            };
+            errors.push(task_error(
+                file_path, id, snippet(id.line), message,
+                "Pass the value app.scheduleTask() returned for the task to cancel".to_string(),
+                "cancel-task-id",
+                ErrorSeverity::Warning,
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Whether the tokens at `i` are `app.method(`
+fn is_app_call(tokens: &[Token], i: usize, method: &str) -> bool {
+    matches!(tokens.get(i..i + 4), Some([app, dot, m, open])
+        if app.text == "app" && dot.is_punct(".") && m.text == method && open.is_punct("("))
+        && !(i > 0 && tokens[i - 1].is_punct("."))
+}
+
+/// Token ranges of the top-level arguments of the call whose `(` is at `open - 1`
+fn arguments(tokens: &[Token], open: usize) -> Vec<std::ops::Range<usize>> {
+    let mut args = Vec::new();
+    let close = match matching_bracket(tokens, open - 1) {
+        Some(close) => close,
+        None => return args,
+    };
+    let mut start = open;
+    let mut j = open;
+    while j < close {
+        if tokens[j].is_punct(",") {
+            args.push(start..j);
+            start = j + 1;
+        }
+        // Nested brackets are skipped whole, commas inside them included
+        j = matching_bracket(tokens, j).unwrap_or(j) + 1;
+    }
+    if close > start {
+        args.push(start..close);
+    }
+    args
+}
+
+fn is_function_name(tokens: &[Token], name: &str) -> bool {
+    tokens.windows(2).any(|pair| pair[0].is_keyword("function") && pair[1].text == name)
+}
+
+/// Whether the expression at `i` is a whole statement, so its value is dropped
+fn is_statement_start(tokens: &[Token], i: usize) -> bool {
+    match i.checked_sub(1).map(|j| &tokens[j]) {
+        None => true,
+        Some(prev) => prev.is_punct(";") || prev.is_punct("{") || prev.is_punct("}") || prev.is_punct(")") || prev.is_keyword("else"),
+    }
+}
+
+fn task_error(
+    file_path: &str,
+    token: &Token,
+    snippet: String,
+    message: String,
+    suggestion: String,
+    rule: &str,
+    severity: ErrorSeverity,
+) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(snippet)
+        .suggestion(Some(suggestion))
+        .rule(rule)
+        .build();
+    ValidatorError::Script { message, context, severity }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduled_tasks() {
+        let script = r#"var pending = 0;
+function tick() { pending--; }
+function start(comp) {
+    var label = comp.name;
+    var taskId = app.scheduleTask("tick(); alret(label);", 500, false);
+    app.scheduleTask("pending = pending + 1;\
+    comp.name = 'x';", 1000, true);
+    app.scheduleTask(tick, 100, false);
+    app.cancelTask(taskId);
+    app.cancelTask(3);
+}
+"#;
+        let errors = validate_scheduled_tasks(script, "t.jsx");
+        let checked: Vec<&ValidatorError> = errors.iter()
+            .filter(|e| e.rule_id().map_or(false, |rule| rule == "unknown-function" || rule.contains("task")))
+            .collect();
+        let found: Vec<(Option<&str>, usize, usize)> = checked.iter()
+            .map(|e| (e.rule_id(), e.get_context().line.unwrap(), e.get_context().column.unwrap()))
+            .collect();
+        assert_eq!(found, vec![
+            (Some("unknown-function"), 5, 44),
+            (Some("schedule-task-closure"), 5, 50),
+            (Some("schedule-task-closure"), 7, 5),
+            (Some("schedule-task-uncancellable"), 6, 9),
+            (Some("schedule-task-function"), 8, 22),
+            (Some("cancel-task-id"), 10, 20),
+        ]);
+        assert_eq!(checked[1].get_message(), "'label' is local to start and undefined when the scheduled task runs");
+        assert_eq!(checked[3].get_context().code_snippet.as_deref(), Some("app.scheduleTask(\"pending = pending + 1;\\"));
+    }
+}
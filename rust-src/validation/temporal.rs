@@ -1,4 +1,4 @@
-use super::rules::{ValidationRule, PropertyValueType};
+use super::rules::PropertyValueType;
 use serde_json::Value;
 
 pub fn validate_temporal_ease(property_type: &PropertyValueType, in_ease: &[Value], out_ease: &[Value]) -> Result<(), String> {
@@ -44,7 +44,7 @@ fn validate_ease_keyframe(keyframe: &Value) -> Result<(), String> {
             match obj.get("influence") {
                 Some(Value::Number(n)) => {
                     let influence = n.as_f64().ok_or("Invalid influence format")?;
-                    if influence < 0.0 || influence > 100.0 {
+                    if !(0.0..=100.0).contains(&influence) {
                         return Err("Influence must be between 0 and 100".to_string());
                     }
                 }
@@ -1,6 +1,6 @@
 use super::rules::ValidationRule;
 
-pub fn validate_text_document(doc: &serde_json::Value, rule: &ValidationRule) -> Result<(), String> {
+pub fn validate_text_document(doc: &serde_json::Value, _rule: &ValidationRule) -> Result<(), String> {
     match doc {
         serde_json::Value::Object(obj) => {
             // Validate text color
@@ -26,7 +26,7 @@ fn validate_color_value(color: &serde_json::Value) -> Result<(), String> {
                 match component {
                     serde_json::Value::Number(n) => {
                         let value = n.as_f64().unwrap();
-                        if value < 0.0 || value > 1.0 {
+                        if !(0.0..=1.0).contains(&value) {
                             return Err("Color components must be between 0.0 and 1.0".to_string());
                         }
                     }
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{significant_tokens, Token, TokenKind};
+
+/// Animator properties that only exist with per-character 3D enabled
+const PER_CHARACTER_3D: &[&str] = &["ADBE Text Rotation X", "ADBE Text Rotation Y"];
+
+/// Components of the Amount of an expression selector: one per axis of the
+/// properties it drives
+const AMOUNT_DIMENSIONS: usize = 3;
+
+/// Where a chain of `property()` calls is in a text layer's animators
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    /// A text layer, by variable name
+    Layer(String),
+    Text(String),
+    Animators(String),
+    /// The animators are numbered in the order the script adds them
+    Animator(usize),
+    Selectors(usize),
+    Properties(usize),
+    ExpressionSelector(usize),
+    Amount(usize),
+}
+
+#[derive(Debug, Default)]
+struct Animator {
+    /// Variable holding the text layer
+    layer: String,
+    /// Match names of the properties the animator drives
+    properties: Vec<String>,
+}
+
+/// Checks text animators built by a script:
+///
+/// - X and Y Rotation only exist on animators of a layer with
+///   `threeDPerChar` enabled; new text layers start with it off
+/// - the Amount expression of an expression selector must evaluate to a
+///   number or a 3-component array, one value per axis
+///
+/// Animators are followed through `property()`/`addProperty()` chains and
+/// variables assigned from them, in source order.
+pub fn validate_text_animators(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let mut nodes: HashMap<String, Node> = HashMap::new();
+    let mut per_character: HashMap<String, bool> = HashMap::new();
+    let mut animators: Vec<Animator> = Vec::new();
+    let mut expressions: Vec<(usize, &Token)> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.is_identifier() || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+        let name = token.text.as_str();
+
+        // `var title = comp.layers.addText("Title");`; other values are
+        // followed below, from their first identifier
+        if tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+            nodes.remove(name);
+            per_character.remove(name);
+            if creates_text_layer(&tokens, i + 2) {
+                nodes.insert(name.to_string(), Node::Layer(name.to_string()));
+                per_character.insert(name.to_string(), false);
+            }
+            continue;
+        }
+
+        // `title.threeDPerChar = true;`
+        if let Some([dot, member, assign, value]) = tokens.get(i + 1..i + 5) {
+            if dot.is_punct(".") && member.text == "threeDPerChar" && assign.is_punct("=") {
+                let enabled = value.is_keyword("true") && tokens.get(i + 5).is_none_or(|t| t.is_punct(";") || t.newlines_before > 0);
+                let unconditional = !(i > 0 && (tokens[i - 1].is_punct(")") || tokens[i - 1].is_keyword("else")));
+                match (enabled, unconditional) {
+                    (true, _) => per_character.insert(name.to_string(), true),
+                    (false, true) if value.is_keyword("false") => per_character.insert(name.to_string(), false),
+                    _ => per_character.remove(name),
+                };
+                continue;
+            }
+        }
+
+        let start = nodes.get(name).cloned();
+        let (node, end) = match walk(&tokens, i, start, &per_character, &mut animators, &mut errors, script, file_path) {
+            Some(found) => found,
+            None => continue,
+        };
+
+        // `var animator = title.property(...).addProperty("ADBE Text Animator");`
+        let assigned = i >= 2
+            && tokens[i - 1].is_punct("=")
+            && tokens[i - 2].is_identifier()
+            && !(i >= 3 && tokens[i - 3].is_punct("."))
+            && tokens.get(end).is_none_or(|t| t.is_punct(";") || t.newlines_before > 0);
+        if assigned {
+            nodes.insert(tokens[i - 2].text.clone(), node.clone());
+        }
+
+        // `amount.expression = "..."`
+        if let (Node::Amount(id), Some([dot, member, assign, value])) = (&node, tokens.get(end..end + 4)) {
+            if dot.is_punct(".") && member.text == "expression" && assign.is_punct("=") && value.kind == TokenKind::String {
+                expressions.push((*id, value));
+            }
+        }
+    }
+
+    // Properties can be added to the animator after the expression is set
+    for (id, token) in expressions {
+        let components = match result_components(unquote(&token.text)) {
+            Some(components) if components != 1 && components != AMOUNT_DIMENSIONS => components,
+            _ => continue,
+        };
+        let driven: Vec<&str> = animators[id].properties.iter()
+            .map(|p| p.trim_start_matches("ADBE Text ").trim_end_matches(" 3D"))
+            .collect();
+        let drives = if driven.is_empty() { String::new() } else { format!(" driving {}", driven.join(", ")) };
+        errors.push(animator_error(
+            script, file_path, token,
+            format!("The expression selector{} returns {} values, but its Amount has {} components, one per axis", drives, components, AMOUNT_DIMENSIONS),
+            "Return a single number, or an array like [x, y, z]; selectorValue already has 3 components".to_string(),
+            "expression-selector-dimension",
+        ));
+    }
+
+    errors.sort_by_key(|e| (e.get_context().line, e.get_context().column));
+    errors
+}
+
+/// Follows the `property()`/`addProperty()` calls chained onto the
+/// identifier at `start`, registering animators and the properties they
+/// drive, and returns the node the chain ends on with the index after it
+#[allow(clippy::too_many_arguments)]
+fn walk(
+    tokens: &[Token],
+    start: usize,
+    mut node: Option<Node>,
+    per_character: &HashMap<String, bool>,
+    animators: &mut Vec<Animator>,
+    errors: &mut Vec<ValidatorError>,
+    script: &str,
+    file_path: &str,
+) -> Option<(Node, usize)> {
+    let mut j = start + 1;
+    loop {
+        let (method, arg) = match tokens.get(j..j + 5) {
+            Some([dot, method, open, arg, close])
+                if dot.is_punct(".") && (method.text == "property" || method.text == "addProperty")
+                    && open.is_punct("(") && arg.kind == TokenKind::String && close.is_punct(")") => (method.text.as_str(), arg),
+            _ => return node.map(|node| (node, j)),
+        };
+        let child = unquote(&arg.text);
+        node = match (node?, method, child) {
+            (Node::Layer(layer), _, "ADBE Text Properties" | "Text") => Some(Node::Text(layer)),
+            (Node::Text(layer), _, "ADBE Text Animators" | "Animators") => Some(Node::Animators(layer)),
+            (Node::Animators(layer), "addProperty", "ADBE Text Animator") => {
+                animators.push(Animator { layer, properties: Vec::new() });
+                Some(Node::Animator(animators.len() - 1))
+            }
+            (Node::Animator(id), _, "ADBE Text Selectors" | "Selectors") => Some(Node::Selectors(id)),
+            (Node::Animator(id), _, "ADBE Text Animator Properties" | "Properties") => Some(Node::Properties(id)),
+            (Node::Selectors(id), "addProperty", "ADBE Text Expressible Selector") => Some(Node::ExpressionSelector(id)),
+            (Node::ExpressionSelector(id), _, "ADBE Text Expressible Amount" | "Amount") => Some(Node::Amount(id)),
+            (Node::Properties(id), "addProperty", property) if property.starts_with("ADBE Text ") => {
+                let layer = &animators[id].layer;
+                if per_character.get(layer) == Some(&false) && PER_CHARACTER_3D.contains(&property) {
+                    errors.push(animator_error(
+                        script, file_path, arg,
+                        format!("\"{}\" needs per-character 3D, which is off on '{}'", property, layer),
+                        format!("Set {}.threeDPerChar = true before adding the property", layer),
+                        "per-char-3d",
+                    ));
+                }
+                animators[id].properties.push(property.to_string());
+                None
+            }
+            _ => None,
+        };
+        j += 5;
+    }
+}
+
+/// Whether the value starting at `start` is `<...>.layers.addText(...)` or
+/// another text layer constructor
+fn creates_text_layer(tokens: &[Token], start: usize) -> bool {
+    let end = tokens[start.min(tokens.len())..].iter()
+        .position(|t| !(t.is_identifier() || t.is_punct(".")))
+        .map_or(tokens.len(), |offset| start + offset);
+    match tokens.get(end.saturating_sub(3)..=end) {
+        Some([layers, dot, method, open]) if layers.text == "layers" && dot.is_punct(".") && open.is_punct("(") => {
+            matches!(method.text.as_str(), "addText" | "addBoxText" | "addVerticalText" | "addVerticalBoxText")
+        }
+        _ => false,
+    }
+}
+
+/// Components of the value an expression ends on: 1 for a number, the
+/// length of an array literal, `None` when not statically known
+fn result_components(expression: &str) -> Option<usize> {
+    let last = expression.trim().trim_end_matches(';')
+        .rsplit([';', '\n'])
+        .map(str::trim)
+        .find(|statement| !statement.is_empty())?;
+    if last.parse::<f64>().is_ok() {
+        return Some(1);
+    }
+    let inner = last.strip_prefix('[')?.strip_suffix(']')?;
+    let mut depth = 0i32;
+    let mut components = 1;
+    for c in inner.chars() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ',' if depth == 0 => components += 1,
+            _ => {}
+        }
+    }
+    Some(components)
+}
+
+fn unquote(text: &str) -> &str {
+    text.get(1..text.len().saturating_sub(1)).unwrap_or("")
+}
+
+fn animator_error(script: &str, file_path: &str, token: &Token, message: String, suggestion: String, rule: &str) -> ValidatorError {
+    let context = ErrorContextBuilder::new()
+        .file(file_path.to_string())
+        .line(token.line)
+        .column(token.column)
+        .length(token.text.chars().count())
+        .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+        .suggestion(Some(suggestion))
+        .rule(rule)
+        .build();
+    ValidatorError::Script { message, context, severity: ErrorSeverity::Error }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_animators() {
+        let script = r#"var title = comp.layers.addText("Title");
+var animator = title.property("ADBE Text Properties").property("ADBE Text Animators").addProperty("ADBE Text Animator");
+var props = animator.property("ADBE Text Animator Properties");
+props.addProperty("ADBE Text Rotation X");
+var selector = animator.property("ADBE Text Selectors").addProperty("ADBE Text Expressible Selector");
+selector.property("ADBE Text Expressible Amount").expression = "var s = selectorValue; [s[0], s[1]]";
+props.addProperty("ADBE Text Scale 3D");
+title.threeDPerChar = true;
+props.addProperty("ADBE Text Rotation Y");
+selector.property("Amount").expression = "[100, 100, 100]";
+selector.property("Amount").expression = "ease(textIndex, 0, textTotal, 0, 100)";
+"#;
+        let errors = validate_text_animators(script, "text.jsx");
+        let found: Vec<(usize, &str, &str)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.rule_id().unwrap(), e.get_message()))
+            .collect();
+        assert_eq!(found, vec![
+            (4, "per-char-3d", "\"ADBE Text Rotation X\" needs per-character 3D, which is off on 'title'"),
+            (6, "expression-selector-dimension", "The expression selector driving Rotation X, Scale, Rotation Y returns 2 values, but its Amount has 3 components, one per axis"),
+        ]);
+    }
+}
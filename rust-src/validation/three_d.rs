@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::script::lexer::{significant_tokens, Token, TokenKind};
+use crate::api::objects::layer::LayerType;
+use crate::data::match_names::canonical_match_name;
+
+/// Layer and transform attributes that only exist on 3D layers
+const THREE_D_MEMBERS: &[&str] = &[
+    "xRotation",
+    "yRotation",
+    "orientation",
+    "materialOption",
+    "castsShadows",
+    "lightTransmission",
+    "acceptsShadows",
+    "acceptsLights",
+];
+
+/// Properties and groups that only exist on 3D layers
+const THREE_D_PROPERTIES: &[&str] = &[
+    "ADBE Rotate X",
+    "ADBE Rotate Y",
+    "ADBE Orientation",
+    "ADBE Material Options Group",
+];
+
+/// Where a layer variable became provably 2D: the blocks enclosing that
+/// point, outermost first
+struct TwoD {
+    line: usize,
+    blocks: Vec<usize>,
+}
+
+/// Flags 3D-only properties used on layers that are 2D at that point.
+///
+/// A layer is provably 2D after the `layers.add*()` call creating it, other
+/// than for cameras and lights, or after `threeDLayer = false`, for code
+/// that statement always runs before: later statements of its block and
+/// the blocks nested in them. Setting `threeDLayer` to anything else,
+/// assigning the variable again or passing it to a function forgets what is
+/// known about it.
+pub fn validate_3d_properties(script: &str, file_path: &str) -> Vec<ValidatorError> {
+    let tokens = significant_tokens(script);
+    let mut two_d: HashMap<&str, TwoD> = HashMap::new();
+    let mut blocks = vec![0];
+    let mut next_block = 1;
+    let mut errors = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_punct("{") {
+            blocks.push(next_block);
+            next_block += 1;
+            continue;
+        }
+        if token.is_punct("}") {
+            if blocks.len() > 1 {
+                blocks.pop();
+            }
+            continue;
+        }
+        if !token.is_identifier() || (i > 0 && (tokens[i - 1].is_punct(".") || tokens[i - 1].is_punct("?."))) {
+            continue;
+        }
+        let name = token.text.as_str();
+
+        if tokens.get(i + 1).is_some_and(|t| t.is_punct("=")) {
+            match creates_2d_layer(&tokens, i + 2) {
+                true => two_d.insert(name, TwoD { line: token.line, blocks: blocks.clone() }),
+                false => two_d.remove(name),
+            };
+            continue;
+        }
+        if let Some(value) = three_d_switch(&tokens, i) {
+            let unconditional = !(i > 0 && (tokens[i - 1].is_punct(")") || tokens[i - 1].is_keyword("else")));
+            match value {
+                Some(false) if unconditional => two_d.insert(name, TwoD { line: token.line, blocks: blocks.clone() }),
+                _ => two_d.remove(name),
+            };
+            continue;
+        }
+        let passed = i > 0
+            && (tokens[i - 1].is_punct("(") || tokens[i - 1].is_punct(","))
+            && tokens.get(i + 1).is_some_and(|t| t.is_punct(")") || t.is_punct(","));
+        if passed {
+            two_d.remove(name);
+            continue;
+        }
+
+        let state = match two_d.get(name).filter(|state| blocks.starts_with(&state.blocks)) {
+            Some(state) => state,
+            None => continue,
+        };
+        if let Some(used) = three_d_access(&tokens, i) {
+            let property = tokens[used].text.trim_matches(|c| c == '"' || c == '\'');
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(tokens[used].line)
+                .column(tokens[used].column)
+                .length(tokens[used].text.chars().count())
+                .code_snippet(script.lines().nth(tokens[used].line - 1).unwrap_or("").trim().to_string())
+                .suggestion(Some(format!("Set {}.threeDLayer = true before using 3D properties", name)))
+                .rule("3d-only-property")
+                .build();
+            errors.push(ValidatorError::Script {
+                message: format!("'{}' only exists on 3D layers, but '{}' is 2D here (since line {})", property, name, state.line),
+                context,
+                severity: ErrorSeverity::Error,
+            });
+        }
+    }
+
+    errors
+}
+
+/// Whether the value starting at `start` is `<...>.layers.add*(...)` for a
+/// layer type that starts out 2D
+fn creates_2d_layer(tokens: &[Token], start: usize) -> bool {
+    let end = tokens[start.min(tokens.len())..].iter()
+        .position(|t| !(t.is_identifier() || t.is_punct(".")))
+        .map_or(tokens.len(), |offset| start + offset);
+    match tokens.get(end.saturating_sub(3)..=end) {
+        Some([layers, dot, method, open]) if layers.text == "layers" && dot.is_punct(".") && open.is_punct("(") => {
+            LayerType::from_constructor(&method.text)
+                .is_some_and(|layer_type| !matches!(layer_type, LayerType::Camera | LayerType::Light))
+        }
+        _ => false,
+    }
+}
+
+/// `name.threeDLayer = value`: `Some(Some(value))` for `true` or `false`,
+/// `Some(None)` for anything else
+fn three_d_switch(tokens: &[Token], i: usize) -> Option<Option<bool>> {
+    match tokens.get(i + 1..i + 5) {
+        Some([dot, member, assign, value]) if dot.is_punct(".") && member.text == "threeDLayer" && assign.is_punct("=") => {
+            let literal = match value.text.as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            };
+            let ends = tokens.get(i + 5).is_none_or(|t| t.is_punct(";") || t.newlines_before > 0);
+            Some(literal.filter(|_| ends))
+        }
+        _ => None,
+    }
+}
+
+/// The token naming a 3D-only property accessed on the layer at `i`,
+/// directly or through its transform group
+fn three_d_access(tokens: &[Token], i: usize) -> Option<usize> {
+    let mut j = i + 1;
+    let mut parent = None;
+    if is_member(tokens, j, "transform") {
+        j += 2;
+        parent = Some("ADBE Transform Group");
+    } else if let Some(group) = property_call(tokens, j) {
+        if canonical_match_name(unquote(&tokens[group].text), None) == Some("ADBE Transform Group") {
+            j += 5;
+            parent = Some("ADBE Transform Group");
+        }
+    }
+
+    if tokens.get(j).is_some_and(|t| t.is_punct(".")) && tokens.get(j + 1).is_some_and(|t| THREE_D_MEMBERS.contains(&t.text.as_str())) {
+        return Some(j + 1);
+    }
+    property_call(tokens, j).filter(|&arg| {
+        canonical_match_name(unquote(&tokens[arg].text), parent).is_some_and(|match_name| THREE_D_PROPERTIES.contains(&match_name))
+    })
+}
+
+fn is_member(tokens: &[Token], j: usize, member: &str) -> bool {
+    matches!(tokens.get(j..j + 2), Some([dot, name]) if dot.is_punct(".") && name.text == member)
+}
+
+/// `.property("name")` at `j`, as the index of the string argument
+fn property_call(tokens: &[Token], j: usize) -> Option<usize> {
+    match tokens.get(j..j + 5) {
+        Some([dot, method, open, arg, close])
+            if dot.is_punct(".") && method.text == "property" && open.is_punct("(")
+                && arg.kind == TokenKind::String && close.is_punct(")") => Some(j + 3),
+        _ => None,
+    }
+}
+
+fn unquote(text: &str) -> &str {
+    text.get(1..text.len().saturating_sub(1)).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_3d_properties_on_2d_layers() {
+        let script = r#"var title = comp.layers.addText("Title");
+title.transform.xRotation.setValue(45);
+title.property("ADBE Transform Group").property("ADBE Orientation").setValue([0, 90, 0]);
+if (tilt) { title.threeDLayer = true; }
+title.transform.yRotation.setValue(10);
+var logo = comp.layers.addSolid([1, 1, 1], "Logo", 200, 200, 1);
+logo.threeDLayer = true;
+logo.property("Material Options").property("Casts Shadows").setValue(1);
+logo.threeDLayer = false;
+logo.orientation.setValue([0, 0, 0]);
+var cam = comp.layers.addCamera("Camera", [960, 540]);
+cam.transform.orientation.setValue([0, 45, 0]);
+var shape = comp.layers.addShape();
+make3D(shape);
+shape.transform.xRotation.setValue(30);
+"#;
+        let errors = validate_3d_properties(script, "3d.jsx");
+        let found: Vec<(usize, &str)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.get_message()))
+            .collect();
+        assert_eq!(found, vec![
+            (2, "'xRotation' only exists on 3D layers, but 'title' is 2D here (since line 1)"),
+            (3, "'ADBE Orientation' only exists on 3D layers, but 'title' is 2D here (since line 1)"),
+            (10, "'orientation' only exists on 3D layers, but 'logo' is 2D here (since line 9)"),
+        ]);
+        assert_eq!(errors[0].get_context().docs_url.as_deref(), Some("https://ae-scripting.docsforadobe.dev/matchnames/layer/3dlayer/"));
+    }
+}
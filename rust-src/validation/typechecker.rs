@@ -1,12 +1,23 @@
 use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
 use super::rules::PropertyValueType;
+use super::script::lexer::{significant_tokens, Token, TokenKind};
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use super::shims::{detect_included_shims, provided_features};
 
 /// Type information for common After Effects objects and methods
 pub struct TypeChecker {
     /// Maps object types to their methods and expected parameter types
+    #[allow(dead_code)]
     type_info: HashMap<String, HashMap<String, Vec<PropertyValueType>>>,
+    /// Built-ins provided by shims declared in the configuration
+    shimmed: HashSet<&'static str>,
+}
+
+impl Default for TypeChecker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TypeChecker {
@@ -39,7 +50,13 @@ impl TypeChecker {
         comp_methods.insert("duplicate".to_string(), vec![]);
         type_info.insert("CompItem".to_string(), comp_methods);
         
-        Self { type_info }
+        Self { type_info, shimmed: HashSet::new() }
+    }
+    
+    /// Treats the built-ins provided by these shims (e.g. `json2`, `es5-shim`) as available
+    pub fn with_shims<S: AsRef<str>>(mut self, shims: &[S]) -> Self {
+        self.shimmed.extend(provided_features(shims));
+        self
     }
     
     /// Validates type usage in scripts
@@ -55,6 +72,14 @@ impl TypeChecker {
         // Check array dimension mismatches
         self.validate_array_dimensions(script, file_path, &mut errors);
         
+        // Check loose equality on known-typed values
+        self.validate_equality_operators(script, file_path, &mut errors);
+        
+        // ExtendScript implements ES3; gate newer syntax and runtime methods
+        if is_extendscript_file(file_path) {
+            self.validate_language_features(script, file_path, &mut errors);
+        }
+        
         errors
     }
     
@@ -188,7 +213,7 @@ impl TypeChecker {
                         .column(1)
                         .code_snippet(capture[0].to_string())
                         .suggestion(Some(format!("For 3D layers, use [{}]", 
-                            vec![trimmed_values[0]; 3].join(", "))))
+                            [trimmed_values[0]; 3].join(", "))))
                         .build();
                         
                     errors.push(ValidatorError::Type {
@@ -200,10 +225,251 @@ impl TypeChecker {
             }
         }
     }
+    
+    /// Flags `==`/`!=` where one operand has a statically known type
+    fn validate_equality_operators(&self, script: &str, file_path: &str, errors: &mut Vec<ValidatorError>) {
+        let tokens = significant_tokens(script);
+        
+        for (i, token) in tokens.iter().enumerate() {
+            if !(token.is_punct("==") || token.is_punct("!=")) {
+                continue;
+            }
+            
+            let left_known = i > 0 && is_known_typed_operand(&tokens[i - 1]);
+            let right_known = tokens.get(i + 1).is_some_and(is_known_typed_operand);
+            if !left_known && !right_known {
+                continue;
+            }
+            
+            let strict_operator = if token.text == "==" { "===" } else { "!==" };
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(token.line)
+                .column(token.column)
+                .length(token.text.len())
+                .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+                .suggestion(Some(format!("Use '{}' to compare without type coercion", strict_operator)))
+                .rule("loose-equality")
+                .arg("operator", &token.text)
+                .arg("strict", strict_operator)
+                .build();
+                
+            errors.push(ValidatorError::Type {
+                message: format!("Loose equality '{}' used on a value of known type", token.text),
+                context,
+                severity: ErrorSeverity::Info,
+            });
+        }
+    }
+    
+    /// Flags syntax and built-ins that ExtendScript's ES3 engine does not provide
+    fn validate_language_features(&self, script: &str, file_path: &str, errors: &mut Vec<ValidatorError>) {
+        let mut shimmed = self.shimmed.clone();
+        shimmed.extend(provided_features(&detect_included_shims(script)));
+        let has_json_polyfill = shimmed.contains("JSON")
+            || Regex::new(r"\b(?:var\s+)?JSON\s*=[^=]").unwrap().is_match(script);
+        let tokens = significant_tokens(script);
+        let tokens: Vec<&Token> = tokens.iter().collect();
+        
+        let mut report = |token: &Token, length: usize, rule: &str, message: String, suggestion: &str| {
+            let context = ErrorContextBuilder::new()
+                .file(file_path.to_string())
+                .line(token.line)
+                .column(token.column)
+                .length(length)
+                .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+                .suggestion(Some(suggestion.to_string()))
+                .rule(rule)
+                .build();
+            errors.push(ValidatorError::Type {
+                message,
+                context,
+                severity: ErrorSeverity::Error,
+            });
+        };
+        
+        for (i, token) in tokens.iter().enumerate() {
+            let prev = i.checked_sub(1).map(|p| tokens[p]);
+            let next = tokens.get(i + 1).copied();
+            
+            let syntax = match token.kind {
+                TokenKind::Keyword => ES3_SYNTAX_KEYWORDS.iter().find(|(k, _, _)| *k == token.text),
+                TokenKind::Punctuator => ES3_SYNTAX_PUNCTUATORS.iter().find(|(p, _, _)| *p == token.text),
+                TokenKind::Template => Some(&("`", "Template literals", "Use string concatenation with '+'")),
+                TokenKind::Identifier if token.text == "of"
+                    && prev.is_some_and(|p| p.is_identifier())
+                    && i >= 2 && (tokens[i - 2].is_punct("(") || tokens[i - 2].is_keyword("var")) =>
+                    Some(&("of", "for...of loops", "Use an index-based for loop")),
+                _ => None,
+            };
+            if let Some((_, feature, suggestion)) = syntax {
+                report(token, token.text.lines().next().unwrap_or("").chars().count(), "es3-syntax",
+                    format!("{} are not supported by ExtendScript (ES3)", feature), suggestion);
+                continue;
+            }
+            
+            if !token.is_identifier() {
+                continue;
+            }
+            if token.text == "JSON" && next.is_some_and(|n| n.is_punct(".")) && !has_json_polyfill {
+                report(token, 4, "es3-runtime",
+                    "JSON is not available in ExtendScript without a polyfill".to_string(),
+                    "Add #include \"json2.jsx\" (Douglas Crockford's json2.js) before using JSON");
+                continue;
+            }
+            
+            let is_call = next.is_some_and(|n| n.is_punct("("));
+            let after_dot = prev.is_some_and(|p| p.is_punct("."));
+            if !is_call || !after_dot {
+                continue;
+            }
+            let owner = if i >= 2 { tokens[i - 2].text.as_str() } else { "" };
+            let missing = ES3_MISSING_METHODS.iter().find(|(o, m, _)| {
+                *m == token.text && (o.is_empty() || *o == owner)
+            });
+            if let Some((static_owner, method, suggestion)) = missing {
+                let feature = if static_owner.is_empty() {
+                    method.to_string()
+                } else {
+                    format!("{}.{}", static_owner, method)
+                };
+                if shimmed.contains(feature.as_str()) {
+                    continue;
+                }
+                let polyfill = if static_owner.is_empty() {
+                    format!("prototype.{}", method)
+                } else {
+                    format!("{}.{}", static_owner, method)
+                };
+                if Regex::new(&format!(r"{}\s*=[^=]", regex::escape(&polyfill))).unwrap().is_match(script) {
+                    continue;
+                }
+                let name = if static_owner.is_empty() {
+                    format!(".{}()", method)
+                } else {
+                    format!("{}.{}()", static_owner, method)
+                };
+                report(token, method.len(), "es3-runtime",
+                    format!("{} is not available in ExtendScript (ES3)", name), suggestion);
+            }
+        }
+    }
+}
+
+/// Keywords introduced after ES3: (keyword, feature, suggestion)
+const ES3_SYNTAX_KEYWORDS: &[(&str, &str, &str)] = &[
+    ("let", "'let' declarations", "Use 'var'"),
+    ("const", "'const' declarations", "Use 'var' and treat the variable as read-only by convention"),
+    ("class", "Classes", "Use a constructor function and its prototype"),
+    ("import", "ES modules", "Use #include to load other scripts"),
+    ("export", "ES modules", "Use #include to load other scripts"),
+];
+
+/// Operators introduced after ES3: (punctuator, feature, suggestion)
+const ES3_SYNTAX_PUNCTUATORS: &[(&str, &str, &str)] = &[
+    ("=>", "Arrow functions", "Use a function expression: function (x) { ... }"),
+    ("...", "Spread and rest parameters", "Use Function.prototype.apply, Array.prototype.concat or the arguments object"),
+    ("**", "Exponent operators", "Use Math.pow(base, exponent)"),
+    ("?.", "Optional chaining", "Check each step explicitly: a && a.b"),
+    ("??", "Nullish coalescing", "Use a conditional: (a !== null && a !== undefined) ? a : b"),
+];
+
+/// Built-ins missing from the ES3 runtime: (static owner or "" for instance methods, method, suggestion)
+const ES3_MISSING_METHODS: &[(&str, &str, &str)] = &[
+    ("", "map", "Use a for loop, or include an Array.prototype.map polyfill"),
+    ("", "filter", "Use a for loop, or include an Array.prototype.filter polyfill"),
+    ("", "forEach", "Use a for loop, or include an Array.prototype.forEach polyfill"),
+    ("", "reduce", "Use a for loop with an accumulator variable"),
+    ("", "reduceRight", "Use a reverse for loop with an accumulator variable"),
+    ("", "some", "Use a for loop that breaks on the first match"),
+    ("", "every", "Use a for loop that breaks on the first failure"),
+    ("", "find", "Use a for loop that returns the first match"),
+    ("", "findIndex", "Use a for loop that returns the index of the first match"),
+    ("", "includes", "Use a for loop for arrays, or str.indexOf(x) !== -1 for strings"),
+    ("", "trim", "Use str.replace(/^\\s+|\\s+$/g, '')"),
+    ("", "bind", "Use a closure: var self = this; function () { self.method(); }"),
+    ("Array", "isArray", "Use 'value instanceof Array'"),
+    ("Object", "keys", "Use a for...in loop with hasOwnProperty"),
+    ("Object", "create", "Use a constructor function with a prototype"),
+    ("Object", "assign", "Copy properties with a for...in loop"),
+    ("Date", "now", "Use new Date().getTime()"),
+];
+
+/// `.jsx`, `.jsxinc` and `.jsxbin` sources run on the ExtendScript engine
+fn is_extendscript_file(file_path: &str) -> bool {
+    let lower = file_path.to_lowercase();
+    lower.ends_with(".jsx") || lower.ends_with(".jsxinc")
+}
+
+/// AE members whose values always have a fixed primitive type
+const KNOWN_TYPED_MEMBERS: &[&str] = &[
+    "typeName", "matchName", "name", "length", "numLayers", "numItems",
+    "numKeys", "numProperties", "index", "propertyIndex",
+];
+
+fn is_known_typed_operand(token: &Token) -> bool {
+    match token.kind {
+        TokenKind::String | TokenKind::Number => true,
+        TokenKind::Keyword => token.text == "true" || token.text == "false",
+        TokenKind::Identifier => KNOWN_TYPED_MEMBERS.contains(&token.text.as_str()),
+        _ => false,
+    }
 }
 
 /// Validates type usage in scripts
 pub fn validate_type_usage(script: &str, file_path: &str) -> Vec<ValidatorError> {
     let checker = TypeChecker::new();
     checker.validate_types(script, file_path)
-}
\ No newline at end of file
+}
+
+/// Validates type usage, treating built-ins from the given shims as available
+pub fn validate_type_usage_with_shims<S: AsRef<str>>(script: &str, file_path: &str, shims: &[S]) -> Vec<ValidatorError> {
+    TypeChecker::new().with_shims(shims).validate_types(script, file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(script: &str, file_path: &str) -> Vec<(usize, String)> {
+        validate_type_usage(script, file_path).into_iter()
+            .filter(|e| e.rule_id().map_or(false, |r| r.starts_with("es3-")))
+            .map(|e| (e.get_context().line.unwrap_or(0), e.get_message().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_flags_post_es3_syntax() {
+        let script = "const a = 1;\nlet b = [1, 2];\nvar f = x => x * 2;\nvar s = `v${a}`;\n";
+        let found = rules(script, "a.jsx");
+        assert_eq!(found.iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(found[2].1.starts_with("Arrow functions"));
+        assert!(rules(script, "panel.js").is_empty());
+    }
+
+    #[test]
+    fn test_flags_missing_runtime_methods() {
+        let script = "var names = items.map(function (i) { return i.name; });\nvar keys = Object.keys(obj);\nvar data = JSON.parse(text);\nvar s = name.replace(/a/g, 'b');\n";
+        let found = rules(script, "a.jsx");
+        assert_eq!(found.len(), 3);
+        assert!(found[0].1.contains(".map()"));
+        assert!(found[1].1.contains("Object.keys()"));
+        assert!(found[2].1.starts_with("JSON"));
+    }
+
+    #[test]
+    fn test_polyfills_suppress_runtime_errors() {
+        let script = "#include \"json2.jsx\"\nArray.prototype.map = function (f) { return this; };\nvar a = JSON.stringify(items.map(f));\n";
+        assert!(rules(script, "a.jsx").is_empty());
+    }
+
+    #[test]
+    fn test_configured_shims_suppress_runtime_errors() {
+        let script = "var keys = Object.keys(obj);\nvar names = items.map(f);\nvar found = items.find(f);\n";
+        let found: Vec<String> = validate_type_usage_with_shims(script, "a.jsx", &["es5-shim"]).into_iter()
+            .filter(|e| e.rule_id() == Some("es3-runtime"))
+            .map(|e| e.get_message().to_string())
+            .collect();
+        assert_eq!(found, vec![".find() is not available in ExtendScript (ES3)"]);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+use super::rules::PropertyValueType;
+use super::script::inference::infer_variable_types;
+use super::script::lexer::CompactTokens;
+use crate::api::UnifiedApi;
+
+/// How often a script uses one member of an API class
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MemberUsage {
+    pub class: String,
+    pub member: String,
+    /// `method` or `property`
+    pub kind: &'static str,
+    pub count: usize,
+    /// Line of the first use
+    pub line: usize,
+    /// Whether the model declares the member
+    pub known: bool,
+}
+
+/// The API members a script uses, by class
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ApiUsage {
+    pub members: Vec<MemberUsage>,
+    /// Member accesses whose receiver could not be resolved to a class
+    pub unresolved: usize,
+}
+
+impl ApiUsage {
+    /// Walks member chains such as `app.project.activeItem.layer(1).name`,
+    /// following property types and method return types through the model.
+    /// Chains starting at a variable use its inferred class.
+    pub fn analyze(script: &str, api: &UnifiedApi) -> Self {
+        // Generated scripts run to megabytes; compact tokens keep each
+        // identifier's text once however often it is used
+        let compact = CompactTokens::new(script);
+        let tokens = &compact.tokens;
+        let variable_types = infer_variable_types(script);
+        let mut members: BTreeMap<(String, String), MemberUsage> = BTreeMap::new();
+        let mut unresolved = 0;
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            let chain_start = token.is_identifier() && (i == 0 || !(compact.is_punct(i - 1, ".") || compact.is_punct(i - 1, "?.")));
+            if !chain_start || !compact.is_punct(i + 1, ".") {
+                i += 1;
+                continue;
+            }
+
+            let name = compact.text(token);
+            let mut class = api.canonical_name(name)
+                .or_else(|| variable_types.get(name).and_then(|class| api.canonical_name(class)))
+                .map(str::to_string);
+            let mut j = i + 1;
+            while j + 1 < tokens.len() && compact.is_punct(j, ".") && tokens[j + 1].is_identifier() {
+                let member = compact.text(&tokens[j + 1]);
+                let current = match class.take() {
+                    Some(current) => current,
+                    None => {
+                        unresolved += 1;
+                        break;
+                    }
+                };
+                let called = compact.is_punct(j + 2, "(");
+                let (kind, known, next) = if called {
+                    let returns = api.method_return_type(&current, member).and_then(|c| api.canonical_name(c));
+                    ("method", api.validate_method(&current, member), returns)
+                } else {
+                    let rule = api.object(&current).and_then(|object| object.properties.get(member));
+                    let holds = rule.and_then(|rule| match &rule.value_type {
+                        PropertyValueType::Custom(c) => api.canonical_name(c),
+                        _ => None,
+                    });
+                    ("property", rule.is_some(), holds)
+                };
+
+                members.entry((current.clone(), member.to_string()))
+                    .and_modify(|usage| usage.count += 1)
+                    .or_insert(MemberUsage {
+                        class: current,
+                        member: member.to_string(),
+                        kind,
+                        count: 1,
+                        line: compact.line(&tokens[j + 1]),
+                        known,
+                    });
+                class = next.map(str::to_string);
+
+                j += 2;
+                if called {
+                    j = match compact.matching_bracket(j) {
+                        Some(close) => close + 1,
+                        None => break,
+                    };
+                }
+            }
+            i = j.max(i + 1);
+        }
+
+        ApiUsage { members: members.into_values().collect(), unresolved }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Plain-text table, one member per line, unknown members marked
+    pub fn to_table(&self) -> String {
+        let names: Vec<String> = self.members.iter().map(|m| format!("{}.{}", m.class, m.member)).collect();
+        let width = names.iter().map(String::len).max().unwrap_or(0).max("Member".len());
+        let mut output = format!("{:<width$}  {:<8}  {:>5}  {:>5}\n", "Member", "Kind", "Uses", "Line", width = width);
+        for (usage, name) in self.members.iter().zip(&names) {
+            output.push_str(&format!(
+                "{:<width$}  {:<8}  {:>5}  {:>5}{}\n",
+                name, usage.kind, usage.count, usage.line,
+                if usage.known { "" } else { "  (unknown)" },
+                width = width
+            ));
+        }
+        output.push_str(&format!("\n{} member(s), {} unresolved access(es)\n", self.members.len(), self.unresolved));
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_chains_follow_the_model() {
+        let api = UnifiedApi::shared();
+        let script = "var comp = app.project.activeItem;\ncomp.layer(1);\ncomp.layer(2);\ncomp.frobnicate();\nfoo.bar;\n";
+        let usage = ApiUsage::analyze(script, &api);
+        let found: Vec<(&str, &str, usize, bool)> = usage.members.iter()
+            .map(|m| (m.class.as_str(), m.member.as_str(), m.count, m.known))
+            .collect();
+        assert!(found.contains(&("Application", "project", 1, true)));
+        assert!(found.contains(&("Project", "activeItem", 1, true)));
+        assert!(found.contains(&("CompItem", "layer", 2, true)));
+        assert!(found.contains(&("CompItem", "frobnicate", 1, false)));
+        assert_eq!(usage.unresolved, 1);
+    }
+}
@@ -1,4 +1,7 @@
 use super::errors::{ValidatorError, ErrorContextBuilder, ErrorSeverity};
+use super::context::ValidationContext;
+use super::headless::find_dialog_calls;
+use super::script::lexer::{matching_bracket, significant_tokens, Token};
 use regex::Regex;
 
 /// Validates After Effects workflow patterns in scripts
@@ -8,12 +11,18 @@ pub fn validate_workflow_patterns(script: &str, file_path: &str) -> Vec<Validato
     // Check for undo group patterns
     validate_undo_groups(script, file_path, &mut errors);
     
+    // Check for per-iteration undo entries in loops
+    validate_loop_undo_entries(script, file_path, &mut errors);
+    
     // Check for activeItem null checks
     validate_active_item_checks(script, file_path, &mut errors);
     
     // Check for array bounds
     validate_array_access(script, file_path, &mut errors);
     
+    // Check watch folder scripts
+    validate_watch_folder(script, file_path, &mut errors);
+    
     errors
 }
 
@@ -104,6 +113,109 @@ fn validate_undo_groups(script: &str, file_path: &str, errors: &mut Vec<Validato
     }
 }
 
+/// Methods that add one undo entry per call outside an undo group
+const MUTATING_METHODS: &[&str] = &[
+    "setValue",
+    "setValueAtTime",
+    "setValuesAtTimes",
+    "setValueAtKey",
+    "addKey",
+    "addProperty",
+];
+
+/// Flags property mutations in loops that are not inside a single undo
+/// group begun before the loop.
+///
+/// Without a group every call is its own undo entry, which makes a loop over
+/// hundreds of layers slow and floods the undo history, so this is an error.
+/// A group begun inside the loop still leaves one entry per iteration and is
+/// a warning. Each outermost loop is reported once.
+fn validate_loop_undo_entries(script: &str, file_path: &str, errors: &mut Vec<ValidatorError>) {
+    let tokens = significant_tokens(script);
+    let loops = loop_bodies(&tokens);
+    if loops.is_empty() {
+        return;
+    }
+
+    let mut open_groups: Vec<usize> = Vec::new();
+    let mut reported: Vec<usize> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let is_call = tokens.get(i + 1).is_some_and(|next| next.is_punct("("))
+            && i > 0 && tokens[i - 1].is_punct(".");
+        if !is_call {
+            continue;
+        }
+        match token.text.as_str() {
+            "beginUndoGroup" => open_groups.push(i),
+            "endUndoGroup" => {
+                open_groups.pop();
+            }
+            name if MUTATING_METHODS.contains(&name) => {
+                // Outermost loop around the call
+                let (keyword, start, _) = match loops.iter().filter(|(_, start, end)| *start < i && i < *end).min_by_key(|(_, start, _)| *start) {
+                    Some(found) => *found,
+                    None => continue,
+                };
+                if reported.contains(&keyword) || open_groups.first().is_some_and(|&begin| begin < start) {
+                    continue;
+                }
+                reported.push(keyword);
+
+                let per_iteration = open_groups.last().is_some_and(|&begin| begin > start);
+                let (message, severity) = if per_iteration {
+                    (format!("beginUndoGroup inside a loop adds one undo entry per iteration for '{}'", name), ErrorSeverity::Warning)
+                } else {
+                    (format!("'{}' inside a loop without an undo group adds one undo entry per call", name), ErrorSeverity::Error)
+                };
+                let line = tokens[keyword].line;
+                let context = ErrorContextBuilder::new()
+                    .file(file_path.to_string())
+                    .line(token.line)
+                    .column(token.column)
+                    .length(token.text.len())
+                    .code_snippet(script.lines().nth(token.line - 1).unwrap_or("").trim().to_string())
+                    .suggestion(Some(format!(
+                        "Call app.beginUndoGroup() once before the {} loop on line {} and app.endUndoGroup() after it",
+                        tokens[keyword].text, line
+                    )))
+                    .rule("loop-undo-entries")
+                    .build();
+                errors.push(ValidatorError::Script { message, context, severity });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `(keyword index, body start, body end)` token ranges of every `for`,
+/// `while` and `do` loop body, braced or single-statement
+fn loop_bodies(tokens: &[Token]) -> Vec<(usize, usize, usize)> {
+    let mut loops = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let body_start = if token.is_keyword("do") {
+            i + 1
+        } else if token.is_keyword("for") || token.is_keyword("while") {
+            match tokens.get(i + 1).filter(|t| t.is_punct("(")).and_then(|_| matching_bracket(tokens, i + 1)) {
+                Some(close) => close + 1,
+                None => continue,
+            }
+        } else {
+            continue;
+        };
+        let body_end = match tokens.get(body_start) {
+            Some(t) if t.is_punct("{") => matching_bracket(tokens, body_start),
+            // `while (...);` closing a do-while has no body
+            Some(t) if t.is_punct(";") => continue,
+            Some(_) => tokens[body_start..].iter().position(|t| t.is_punct(";")).map(|offset| body_start + offset),
+            None => None,
+        };
+        if let Some(body_end) = body_end {
+            loops.push((i, body_start, body_end));
+        }
+    }
+    loops
+}
+
 /// Validates that activeItem is checked before use
 fn validate_active_item_checks(script: &str, file_path: &str, errors: &mut Vec<ValidatorError>) {
     let active_item_use = Regex::new(r"app\.project\.activeItem\.").unwrap();
@@ -123,6 +235,7 @@ fn validate_active_item_checks(script: &str, file_path: &str, errors: &mut Vec<V
                 .line(line_num)
                 .column(1)
                 .suggestion(Some("Add: if (app.project.activeItem) { ... }".to_string()))
+                .rule("unguarded-active-item")
                 .build();
                 
             errors.push(ValidatorError::Script {
@@ -185,4 +298,132 @@ fn validate_array_access(script: &str, file_path: &str, errors: &mut Vec<Validat
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Validates scripts that start or run under watch folder rendering.
+///
+/// A watch folder render node has nobody in front of it, so a dialog stalls
+/// the queue. Dialogs are flagged when the script is known to run as a watch
+/// folder (inside `if (app.isWatchFolder)`), and in scripts that start one
+/// with `app.watchFolder()` unless guarded by `if (!app.isWatchFolder)`.
+fn validate_watch_folder(script: &str, file_path: &str, errors: &mut Vec<ValidatorError>) {
+    let watch_call_regex = Regex::new(r"\bapp\.watchFolder\s*\(\s*([^)]*)\)").unwrap();
+    let starts_watch_folder = watch_call_regex.is_match(script);
+
+    for call in find_dialog_calls(script) {
+        let mut context = ValidationContext::new();
+        for condition in &call.conditions {
+            context.enter_branch(condition);
+        }
+        let severity = match context.is_watch_folder {
+            Some(true) => ErrorSeverity::Error,
+            None if starts_watch_folder => ErrorSeverity::Warning,
+            _ => continue,
+        };
+
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(call.line)
+            .column(call.column)
+            .code_snippet(script.lines().nth(call.line - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some("Guard the call with if (!app.isWatchFolder) { ... } or log with $.writeln instead".to_string()))
+            .rule("watch-folder-dialog")
+            .build();
+
+        errors.push(ValidatorError::Script {
+            message: format!("'{}' blocks the watch folder render node waiting for input", call.name),
+            context,
+            severity,
+        });
+    }
+
+    // app.watchFolder() takes the Folder to watch, not a File inside it
+    let file_assign_regex = Regex::new(r"\b(\w+)\s*=\s*(?:new\s+)?(File|Folder)\s*(?:\(|\.\w+Dialog\s*\()").unwrap();
+    let file_literal_regex = Regex::new(r"^(?:new\s+)?File\s*(?:\(|\.\w+Dialog\s*\()").unwrap();
+    for capture in watch_call_regex.captures_iter(script) {
+        let call = capture.get(0).unwrap();
+        let argument = capture[1].trim();
+
+        let is_file = file_literal_regex.is_match(argument)
+            || file_assign_regex.captures_iter(&script[..call.start()])
+                .filter(|assign| &assign[1] == argument)
+                .last()
+                .is_some_and(|assign| &assign[2] == "File");
+        if !is_file {
+            continue;
+        }
+
+        let line_num = script[..call.start()].matches('\n').count() + 1;
+        let line_start = script[..call.start()].rfind('\n').map_or(0, |i| i + 1);
+        let context = ErrorContextBuilder::new()
+            .file(file_path.to_string())
+            .line(line_num)
+            .column(call.start() - line_start + 1)
+            .length(call.end() - call.start())
+            .code_snippet(script.lines().nth(line_num - 1).unwrap_or("").trim().to_string())
+            .suggestion(Some("Pass the folder to watch: app.watchFolder(new Folder(\"/path/to/watch\"))".to_string()))
+            .rule("watch-folder-target")
+            .build();
+
+        errors.push(ValidatorError::Script {
+            message: "app.watchFolder() expects a Folder, not a File".to_string(),
+            context,
+            severity: ErrorSeverity::Error,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_folder_dialogs_and_target() {
+        let script = r#"if (!app.isWatchFolder) {
+    alert("Starting watch folder");
+}
+if (app.isWatchFolder) {
+    confirm("Render?");
+}
+var target = new File("~/renders/job.aep");
+app.watchFolder(target);
+app.watchFolder(new Folder("~/watch"));
+"#;
+        let mut errors = Vec::new();
+        validate_watch_folder(script, "watch.jsx", &mut errors);
+        let found: Vec<(Option<&str>, usize)> = errors.iter()
+            .map(|e| (e.rule_id(), e.get_context().line.unwrap()))
+            .collect();
+        assert_eq!(found, vec![
+            (Some("watch-folder-dialog"), 5),
+            (Some("watch-folder-target"), 8),
+        ]);
+    }
+    #[test]
+    fn test_loop_mutations_need_one_undo_group() {
+        let script = r#"var comp = app.project.activeItem;
+for (var i = 1; i <= comp.numLayers; i++) {
+    comp.layer(i).transform.opacity.setValue(50);
+    comp.layer(i).transform.scale.setValue([50, 50]);
+}
+for (var j = 1; j <= comp.numLayers; j++) {
+    app.beginUndoGroup("Blur");
+    comp.layer(j).effects.addProperty("ADBE Gaussian Blur 2");
+    app.endUndoGroup();
+}
+app.beginUndoGroup("Fade");
+for (var k = 1; k <= comp.numLayers; k++) comp.layer(k).transform.opacity.setValueAtTime(0, 0);
+app.endUndoGroup();
+"#;
+        let mut errors = Vec::new();
+        validate_loop_undo_entries(script, "loops.jsx", &mut errors);
+        let found: Vec<(usize, ErrorSeverity)> = errors.iter()
+            .map(|e| (e.get_context().line.unwrap(), e.severity()))
+            .collect();
+        assert_eq!(found, vec![(3, ErrorSeverity::Error), (8, ErrorSeverity::Warning)]);
+        assert_eq!(
+            errors[0].get_context().suggestion(),
+            Some("Call app.beginUndoGroup() once before the for loop on line 2 and app.endUndoGroup() after it")
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use walkdir::WalkDir;
+use super::config::ValidatorConfig;
+use super::encoding::read_script;
+use super::script::{validate_script_with_config, ScriptValidationResult};
+use crate::hooks::HOOK_EXTENSIONS;
+
+/// Folders no front end looks for scripts in
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+/// A folder of scripts, validated the same way by every front end.
+///
+/// The CLI, an editor and the bindings go through a workspace to find the
+/// scripts under a root, resolve the `.auteurrc` cascade that applies to
+/// each one and validate them. Configs are resolved once per folder, and
+/// results are cached by content hash, so validating an unchanged file or
+/// buffer again is free.
+#[derive(Debug)]
+pub struct Workspace {
+    root: PathBuf,
+    /// Used for every script instead of the cascade, like `--config`
+    config: Option<Arc<ValidatorConfig>>,
+    configs: Mutex<HashMap<PathBuf, Arc<ValidatorConfig>>>,
+    results: Mutex<HashMap<PathBuf, (u64, Arc<ScriptValidationResult>)>>,
+}
+
+impl Workspace {
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            config: None,
+            configs: Mutex::new(HashMap::new()),
+            results: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Validates every script with `config` instead of the `.auteurrc` files
+    pub fn with_config(mut self, config: ValidatorConfig) -> Self {
+        self.config = Some(Arc::new(config));
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Scripts under the root, sorted. Hidden folders and dependency
+    /// folders are skipped.
+    pub fn scripts(&self) -> Vec<PathBuf> {
+        let mut scripts: Vec<PathBuf> = WalkDir::new(&self.root)
+            .into_iter()
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                entry.depth() == 0 || !entry.file_type().is_dir()
+                    || !(name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()))
+            })
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| HOOK_EXTENSIONS.contains(&ext.to_string_lossy().as_ref())))
+            .collect();
+        scripts.sort();
+        scripts
+    }
+
+    /// The config that applies to `script`: the workspace's own config, or
+    /// the `.auteurrc` files of its folder and the folders above
+    pub fn config_for<P: AsRef<Path>>(&self, script: P) -> Result<Arc<ValidatorConfig>, String> {
+        if let Some(config) = &self.config {
+            return Ok(config.clone());
+        }
+        let dir = match script.as_ref().parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        if let Some(config) = self.configs.lock().unwrap().get(&dir) {
+            return Ok(config.clone());
+        }
+        let (config, _) = ValidatorConfig::cascade(&dir)?;
+        let config = Arc::new(config);
+        self.configs.lock().unwrap().insert(dir, config.clone());
+        Ok(config)
+    }
+
+    /// Validates `text` as the contents of `path`, e.g. an unsaved editor
+    /// buffer. The result is reused while the text stays the same.
+    pub fn validate_text<P: AsRef<Path>>(&self, path: P, text: &str) -> Result<Arc<ScriptValidationResult>, String> {
+        let path = path.as_ref();
+        let hash = content_hash(text);
+        if let Some((cached, result)) = self.results.lock().unwrap().get(path) {
+            if *cached == hash {
+                return Ok(result.clone());
+            }
+        }
+        let config = self.config_for(path)?;
+        let name = path.to_string_lossy().replace('\\', "/");
+        let result = Arc::new(validate_script_with_config(text, &name, &config)?);
+        self.results.lock().unwrap().insert(path.to_path_buf(), (hash, result.clone()));
+        Ok(result)
+    }
+
+    /// Reads and validates the script at `path`
+    pub fn validate_file<P: AsRef<Path>>(&self, path: P) -> Result<Arc<ScriptValidationResult>, String> {
+        let path = path.as_ref();
+        let source = read_script(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        self.validate_text(path, &source.text)
+    }
+
+    /// Validates every script of [`Workspace::scripts`] on up to `threads`
+    /// threads, returning the results in the order of the scripts
+    pub fn validate_all(&self, threads: usize) -> Vec<(PathBuf, Result<Arc<ScriptValidationResult>, String>)> {
+        let scripts = self.scripts();
+        let chunk = scripts.len().div_ceil(threads.max(1)).max(1);
+        thread::scope(|scope| {
+            let workers: Vec<_> = scripts.chunks(chunk)
+                .map(|paths| scope.spawn(move || {
+                    paths.iter().map(|path| (path.clone(), self.validate_file(path))).collect::<Vec<_>>()
+                }))
+                .collect();
+            workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+        })
+    }
+
+    /// Drops the cached result for `path`, e.g. when its file is deleted
+    pub fn forget<P: AsRef<Path>>(&self, path: P) {
+        self.results.lock().unwrap().remove(path.as_ref());
+    }
+
+    /// Drops resolved configs and every cached result, for when an
+    /// `.auteurrc` file changes
+    pub fn reload_configs(&self) {
+        self.configs.lock().unwrap().clear();
+        self.results.lock().unwrap().clear();
+    }
+}
+
+fn content_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::validation::config::CONFIG_FILE_NAME;
+    use crate::validation::i18n::Locale;
+
+    #[test]
+    fn test_workspace_configs_and_cache() {
+        let root = std::env::temp_dir().join(format!("ae-validator-workspace-{}", std::process::id()));
+        fs::create_dir_all(root.join("panels")).unwrap();
+        fs::create_dir_all(root.join("node_modules/lib")).unwrap();
+        fs::write(root.join(CONFIG_FILE_NAME), r#"{ "root": true, "locale": "ja" }"#).unwrap();
+        fs::write(root.join("panels").join(CONFIG_FILE_NAME), r#"{ "allow_jsxbin": true }"#).unwrap();
+        fs::write(root.join("main.jsx"), "var comp = app.project.activeItem;\n").unwrap();
+        fs::write(root.join("panels/ui.jsx"), "alret(\"x\");\n").unwrap();
+        fs::write(root.join("panels/notes.txt"), "").unwrap();
+        fs::write(root.join("node_modules/lib/index.js"), "").unwrap();
+
+        let workspace = Workspace::new(&root);
+        assert_eq!(workspace.scripts(), vec![root.join("main.jsx"), root.join("panels/ui.jsx")]);
+        let panel = workspace.config_for(root.join("panels/ui.jsx")).unwrap();
+        assert!(panel.allow_jsxbin);
+        assert_eq!(panel.locale, Locale::Ja);
+        assert!(!workspace.config_for(root.join("main.jsx")).unwrap().allow_jsxbin);
+
+        let results = workspace.validate_all(2);
+        assert_eq!(results.len(), 2);
+        let ui = results[1].1.as_ref().unwrap();
+        assert!(ui.warnings.iter().any(|w| w.rule_id() == Some("unknown-function")));
+
+        let again = workspace.validate_file(root.join("panels/ui.jsx")).unwrap();
+        assert!(Arc::ptr_eq(ui, &again));
+        let edited = workspace.validate_text(root.join("panels/ui.jsx"), "alert(\"x\");\n").unwrap();
+        assert!(!Arc::ptr_eq(ui, &edited));
+        assert!(!edited.warnings.iter().any(|w| w.rule_id() == Some("unknown-function")));
+
+        let fixed = Workspace::new(&root).with_config(ValidatorConfig::new());
+        assert!(!fixed.config_for(root.join("panels/ui.jsx")).unwrap().allow_jsxbin);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
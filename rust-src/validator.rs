@@ -1,28 +1,128 @@
-use crate::api::UnifiedApi;
+use crate::api::{ApiObject, LayeredApi, EntryKind, MethodValidation, ValidationRule};
+use crate::api::documentation::member_docs_url;
+use crate::validation::rules::PropertyValueType;
+use crate::validation::property::validate_property_value;
+use crate::validation::schema::literal_value;
+use crate::validation::color::ColorValue;
+use crate::validation::context::ObjectContext;
+use crate::validation::script::inference::{infer_expression_type, infer_variable_types, narrowed_type, type_narrowings, Narrowing};
+use crate::validation::script::lines::LineIndex;
+use crate::validation::script::modules::find_modules;
 use crate::errors::{ValidationError, ErrorLevel};
+use crate::validation::errors::Suggestion;
+use crate::report::{legacy_diagnostic, ValidationReport};
 use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Passes that ran, with their errors and timings
+type PassResults = Vec<(&'static str, Vec<ValidationError>, Duration)>;
+
+/// One validation pass over a script
+type Pass = fn(&mut ScriptValidator, &str) -> Vec<ValidationError>;
+
+/// Flag shared with another thread, e.g. an LSP server dropping a stale
+/// request, that stops a validation run between passes
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Budget for [`ScriptValidator::validate_script_with`]
+#[derive(Debug, Clone, Default)]
+pub struct ValidateOptions {
+    /// No pass starts after this instant
+    pub deadline: Option<Instant>,
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl ValidateOptions {
+    /// Stops starting passes once `timeout` has elapsed from now
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Why the run should stop, if it should
+    fn interrupted(&self) -> Option<&'static str> {
+        if self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            Some("cancelled")
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some("timed out")
+        } else {
+            None
+        }
+    }
+}
 
 pub struct ScriptValidator {
-    api: UnifiedApi,
+    api: LayeredApi,
+}
+
+impl Default for ScriptValidator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ScriptValidator {
     pub fn new() -> Self {
         ScriptValidator {
-            api: UnifiedApi::new(),
+            api: LayeredApi::new(),
         }
     }
 
-    pub fn validate_script(&mut self, script: &str) -> Result<(), Vec<ValidationError>> {
-        let mut errors = Vec::new();
+    /// The validator's API model, for registering extension objects and members
+    pub fn api_mut(&mut self) -> &mut LayeredApi {
+        &mut self.api
+    }
 
-        if let Err(e) = self.validate_syntax(script) {
-            errors.push(e);
+    /// Registers namespace objects a script declares (`var MyLib = MyLib || {}`,
+    /// an IIFE returning an object, an object literal) so calls to their
+    /// members, e.g. from scripts that `#include` it, resolve. Names of API
+    /// classes are never shadowed.
+    pub fn register_modules(&mut self, script: &str) {
+        for module in find_modules(script) {
+            if self.api.base().canonical_name(&module.name).is_some() {
+                continue;
+            }
+            if !self.api.contains_object(&module.name) {
+                self.api.add_object(&module.name, ApiObject::new(ObjectContext::Custom(module.name.clone())));
+            }
+            for member in module.members.values() {
+                // Members may hold function references, so every member is callable
+                self.api.add_method(&module.name, &member.name, MethodValidation::new(member.params.unwrap_or(0)));
+                self.api.add_property(&module.name, &member.name, ValidationRule::simple(PropertyValueType::CustomValue));
+            }
         }
+    }
 
-        errors.extend(self.validate_api_usage(script));
-        errors.extend(self.validate_match_names(script));
-        errors.extend(self.validate_es6_features(script));
+    pub fn validate_script(&mut self, script: &str) -> Result<(), Vec<ValidationError>> {
+        let errors: Vec<ValidationError> = self.run_passes(script, &ValidateOptions::default())
+            .0
+            .into_iter()
+            .flat_map(|(_, errors, _)| errors)
+            .collect();
 
         if errors.is_empty() {
             Ok(())
@@ -31,6 +131,60 @@ impl ScriptValidator {
         }
     }
 
+    /// Validates a script and returns diagnostics split by severity, with
+    /// per-pass timings and rule hit counts. Each pass is reported as a rule.
+    pub fn validate_script_report(&mut self, script: &str) -> ValidationReport {
+        self.validate_script_with(script, &ValidateOptions::default())
+    }
+
+    /// Like [`validate_script_report`](Self::validate_script_report), but
+    /// stops before the next pass once `options` is cancelled or past its
+    /// deadline. The report then holds the finished passes' diagnostics and a
+    /// `validation-truncated` error, so partial results never pass as valid.
+    pub fn validate_script_with(&mut self, script: &str, options: &ValidateOptions) -> ValidationReport {
+        let mut report = ValidationReport::new(None);
+        let (passes, truncated) = self.run_passes(script, options);
+        for (pass, errors, duration) in passes {
+            report.record_pass(pass, duration);
+            report.extend(errors.into_iter().map(|error| legacy_diagnostic(error, None, pass)));
+        }
+        if let Some(truncated) = truncated {
+            report.push(legacy_diagnostic(truncated, None, "validation-truncated"));
+        }
+        report
+    }
+
+    fn run_passes(&mut self, script: &str, options: &ValidateOptions) -> (PassResults, Option<ValidationError>) {
+        let passes: [(&'static str, Pass); 5] = [
+            ("syntax", |validator, script| validator.validate_syntax(script).err().into_iter().collect()),
+            ("api-usage", |validator, script| validator.validate_api_usage(script)),
+            ("match-names", |validator, script| validator.validate_match_names(script)),
+            ("enum-members", |validator, script| validator.validate_enum_members(script)),
+            ("es6-features", |validator, script| validator.validate_es6_features(script)),
+        ];
+
+        // Namespaces declared further down are callable anywhere in the script
+        self.register_modules(script);
+        let mut results = Vec::new();
+        for (name, pass) in passes {
+            if let Some(reason) = options.interrupted() {
+                let truncated = ValidationError::new(
+                    &format!("Validation truncated: {} before the {} pass; {} of {} passes ran", reason, name, results.len(), passes.len()),
+                    0,
+                    0,
+                    ErrorLevel::Error,
+                    Some("Diagnostics are partial; raise the time budget or split the script".to_string()),
+                );
+                return (results, Some(truncated));
+            }
+            let start = Instant::now();
+            let errors = pass(self, script);
+            results.push((name, errors, start.elapsed()));
+        }
+
+        (results, None)
+    }
+
     fn validate_syntax(&self, script: &str) -> Result<(), ValidationError> {
         // Basic syntax validation
         if !script.contains(";") {
@@ -47,26 +201,85 @@ impl ScriptValidator {
 
     fn validate_api_usage(&mut self, script: &str) -> Vec<ValidationError> {
         let mut errors = Vec::new();
+        // Positions come from one index rather than rescanning the script per match
+        let lines = LineIndex::new(script);
+
+        let variable_types = infer_variable_types(script);
+        // Inside a type guard the checked class wins over the inferred one
+        let narrowings = type_narrowings(script);
 
         // Method call validation
         let method_call_re = Regex::new(r"(?m)(\w+)\.(\w+)\(").unwrap();
         for cap in method_call_re.captures_iter(script) {
-            let class_name = &cap[1];
+            let receiver = &cap[1];
             let method_name = &cap[2];
+            let class_name = if let Some(narrowed) = self.narrowed_class(&narrowings, receiver, cap.get(0).unwrap().start()) {
+                narrowed
+            } else if self.api.contains_object(receiver) {
+                receiver
+            } else {
+                variable_types.get(receiver).map(String::as_str).unwrap_or(receiver)
+            };
             
             if !self.api.validate_method(class_name, method_name) {
                 // Get line and column information
                 let full_match = cap.get(0).unwrap();
-                let line = script[..full_match.start()].lines().count();
-                let last_newline = script[..full_match.start()].rfind('\n').unwrap_or(0);
-                let column = full_match.start() - last_newline;
-                
+                let (line, column) = lines.position(script, full_match.start());
+                // Renames replace the method name after `receiver.`
+                let name_column = column + receiver.chars().count() + 1;
+
+                errors.push(ValidationError::new(
+                    &format!("Invalid method call: {}.{}", receiver, method_name),
+                    line,
+                    column,
+                    ErrorLevel::Error,
+                    None,
+                )
+                .with_suggestions(self.method_suggestions(receiver, class_name, method_name, line, name_column))
+                .with_docs_url(self.class_docs_url(class_name)));
+            } else if let Some(method) = self.api.method(class_name, method_name).filter(|m| m.has_signature()) {
+                let full_match = cap.get(0).unwrap();
+                if let Some(args) = split_call_args(&script[full_match.end()..]) {
+                    let (line, column) = lines.position(script, full_match.start());
+                    let docs_url = member_docs_url(self.api.canonical_name(class_name).unwrap_or(class_name), Some(method_name));
+                    errors.extend(check_call_arguments(receiver, method_name, method, &args, &variable_types, line, column)
+                        .into_iter()
+                        .map(|error| error.with_docs_url(docs_url.clone())));
+                }
+            }
+        }
+
+        // Enumerated property assignments on inferred receivers
+        let assignment_re = Regex::new(r"(?m)\b(\w+)\.(\w+)\s*=\s*([^;=\n][^;\n]*)").unwrap();
+        let enum_member_re = Regex::new(r"^[A-Z]\w*\.\w+$").unwrap();
+        for cap in assignment_re.captures_iter(script) {
+            let receiver = &cap[1];
+            let property_name = &cap[2];
+            let value = cap[3].trim();
+            let class_name = self.narrowed_class(&narrowings, receiver, cap.get(0).unwrap().start())
+                .or_else(|| variable_types.get(receiver).map(String::as_str))
+                .unwrap_or(receiver);
+            let allowed = match self.api.object(class_name)
+                .and_then(|obj| obj.properties.get(property_name))
+                .filter(|rule| rule.is_dropdown)
+                .and_then(|rule| rule.allowed_values.as_ref())
+            {
+                Some(allowed) if !allowed.is_empty() => allowed,
+                _ => continue,
+            };
+            // Only literals and enum members are known; variables could hold anything
+            let is_known = value.starts_with('"') || value.starts_with('\'') || value.parse::<f64>().is_ok()
+                || enum_member_re.is_match(value);
+            if is_known && !allowed.iter().any(|a| a == value) {
+                let full_match = cap.get(0).unwrap();
+                let (line, column) = lines.position(script, full_match.start());
+
                 errors.push(ValidationError::new(
-                    &format!("Invalid method call: {}.{}", class_name, method_name),
+                    &format!("Invalid value for {}.{}: {}", class_name, property_name, value),
                     line,
                     column,
                     ErrorLevel::Error,
-                    Some(format!("Check documentation for valid methods of {}", class_name)),
+                    Some(format!("Expected one of: {}", allowed.join(", "))),
                 ));
             }
         }
@@ -74,7 +287,9 @@ impl ScriptValidator {
         // Property access validation (simple pattern, we'll filter out method calls)
         let property_access_re = Regex::new(r"(\w+)\.(\w+)").unwrap();
         for cap in property_access_re.captures_iter(script) {
-            let class_name = &cap[1];
+            let receiver = &cap[1];
+            let class_name = self.narrowed_class(&narrowings, receiver, cap.get(0).unwrap().start())
+                .unwrap_or(receiver);
             let property_name = &cap[2];
             
             // Skip if this looks like a method call (followed by parentheses)
@@ -86,28 +301,84 @@ impl ScriptValidator {
                 }
             }
             
-            if let Err(_) = self.api.validate_property_access(class_name, property_name, None) {
+            // Enum members are checked separately
+            if self.api.is_enum(class_name) {
+                continue;
+            }
+
+            if self.api.validate_property_access(class_name, property_name, None).is_err() {
                 // Get line and column information
                 let full_match = cap.get(0).unwrap();
-                let line = script[..full_match.start()].lines().count();
-                let last_newline = script[..full_match.start()].rfind('\n').unwrap_or(0);
-                let column = full_match.start() - last_newline;
+                let (line, column) = lines.position(script, full_match.start());
                 
                 errors.push(ValidationError::new(
-                    &format!("Invalid property access: {}.{}", class_name, property_name),
+                    &format!("Invalid property access: {}.{}", receiver, property_name),
                     line,
                     column,
                     ErrorLevel::Error,
-                    Some(format!("Check documentation for valid properties of {}", class_name)),
-                ));
+                    self.api.suggest_member(class_name, property_name)
+                        .or_else(|| Some(format!("Check documentation for valid properties of {}", class_name))),
+                ).with_docs_url(self.class_docs_url(class_name)));
             }
         }
 
         errors
     }
 
+    /// The class a guard narrows `receiver` to at `offset`. It wins over
+    /// receiver aliases, so `item instanceof FolderItem` checks against
+    /// FolderItem rather than the `item` alias of Item.
+    fn narrowed_class<'a>(&self, narrowings: &'a [Narrowing], receiver: &str, offset: usize) -> Option<&'a str> {
+        narrowed_type(narrowings, receiver, offset)
+    }
+
+    /// Guide page of the class a member was looked up on
+    fn class_docs_url(&self, class_name: &str) -> Option<String> {
+        member_docs_url(self.api.canonical_name(class_name).unwrap_or(class_name), None)
+    }
+
+    /// Suggests close method names on the class, as renames of the method
+    /// at `line`/`column` ranked by edit distance, and points out classes
+    /// that do declare the method, which usually means the receiver was
+    /// mistyped
+    fn method_suggestions(&self, receiver: &str, class_name: &str, method_name: &str, line: usize, column: usize) -> Vec<Suggestion> {
+        let canonical = self.api.canonical_name(class_name).unwrap_or(class_name);
+        let mut hints: Vec<Suggestion> = self.api.search_index()
+            .suggest_member(canonical, method_name, 3)
+            .into_iter()
+            .map(|candidate| {
+                let distance = strsim::levenshtein(&method_name.to_lowercase(), &candidate.to_lowercase());
+                let longest = method_name.chars().count().max(candidate.chars().count()).max(1);
+                Suggestion::new(format!("Did you mean '{}'?", candidate))
+                    .with_confidence(1.0 - distance as f32 / longest as f32)
+                    .with_replacement(line, column, method_name.chars().count(), candidate)
+            })
+            .collect();
+
+        let owners: Vec<&str> = self.api.search_index()
+            .owners_of(method_name, EntryKind::Method)
+            .into_iter()
+            .filter(|owner| owner.starts_with(|c: char| c.is_ascii_uppercase()))
+            .filter(|owner| !owner.eq_ignore_ascii_case(class_name))
+            .collect();
+        if !owners.is_empty() {
+            let hint = if receiver != class_name {
+                format!("'{}' exists on {}, but '{}' was inferred as {}", method_name, owners.join(", "), receiver, class_name)
+            } else {
+                format!("'{}' exists on {}", method_name, owners.join(", "))
+            };
+            hints.push(Suggestion::new(hint).with_confidence(0.5));
+        }
+
+        if hints.is_empty() {
+            hints.push(Suggestion::new(format!("Check documentation for valid methods of {}", class_name)));
+        }
+        hints
+    }
+
     fn validate_match_names(&self, script: &str) -> Vec<ValidationError> {
         let mut errors = Vec::new();
+        let lines = LineIndex::new(script);
 
         // Match name validation with fuzzy matching suggestions
         
@@ -117,14 +388,14 @@ impl ScriptValidator {
             let match_name = &cap[1];
             
             // Check if it's a valid effect match name
-            if match_name.starts_with("ADBE") || match_name.starts_with("CC") || 
+            if (match_name.starts_with("ADBE") || match_name.starts_with("CC") || 
                match_name.starts_with("APC") || match_name.starts_with("VISINF") ||
                match_name.starts_with("CS") || match_name.starts_with("ISL") ||
                match_name.starts_with("SYNTHAP") || match_name.starts_with("CINEMA") ||
                match_name.starts_with("EXtractoR") || match_name.starts_with("IDentifier") ||
-               match_name.starts_with("Keylight") {
+               match_name.starts_with("Keylight"))
                 
-                if !self.api.validate_effect_match_name(match_name) && 
+                && !self.api.validate_effect_match_name(match_name) && 
                    !self.api.validate_property_match_name(match_name) &&
                    !self.api.validate_layer_match_name(match_name) {
                     
@@ -141,9 +412,7 @@ impl ScriptValidator {
                     
                     // Get line and column information
                     let full_match = cap.get(0).unwrap();
-                    let line = script[..full_match.start()].lines().count();
-                    let last_newline = script[..full_match.start()].rfind('\n').unwrap_or(0);
-                    let column = full_match.start() - last_newline;
+                    let (line, column) = lines.position(script, full_match.start());
                     
                     errors.push(ValidationError::new(
                         &error_msg,
@@ -153,7 +422,6 @@ impl ScriptValidator {
                         Some("Use a valid match name from the After Effects documentation".to_string()),
                     ));
                 }
-            }
         }
 
         // Layer type validation - look for patterns like layers.add("Layer Type")
@@ -161,8 +429,8 @@ impl ScriptValidator {
         for cap in layer_type_re.captures_iter(script) {
             let layer_type = &cap[1];
             
-            if layer_type.starts_with("ADBE") {
-                if !self.api.validate_layer_match_name(layer_type) {
+            if layer_type.starts_with("ADBE")
+                && !self.api.validate_layer_match_name(layer_type) {
                     let suggestion = self.api.suggest_layer_match_name(layer_type);
                     
                     let error_msg = if let Some(suggestion) = suggestion {
@@ -173,9 +441,7 @@ impl ScriptValidator {
                     
                     // Get line and column information
                     let full_match = cap.get(0).unwrap();
-                    let line = script[..full_match.start()].lines().count();
-                    let last_newline = script[..full_match.start()].rfind('\n').unwrap_or(0);
-                    let column = full_match.start() - last_newline;
+                    let (line, column) = lines.position(script, full_match.start());
                     
                     errors.push(ValidationError::new(
                         &error_msg,
@@ -185,7 +451,37 @@ impl ScriptValidator {
                         Some("Use a valid layer type match name".to_string()),
                     ));
                 }
+        }
+
+        errors
+    }
+
+    fn validate_enum_members(&self, script: &str) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let lines = LineIndex::new(script);
+
+        // Enum member access such as BlendingMode.MULTIPLY
+        let enum_member_re = Regex::new(r"\b([A-Z]\w+)\.(\w+)").unwrap();
+        for cap in enum_member_re.captures_iter(script) {
+            let enum_name = &cap[1];
+            let member = &cap[2];
+
+            if !self.api.is_enum(enum_name) || self.api.validate_enum_member(enum_name, member) {
+                continue;
             }
+
+            // Get line and column information
+            let full_match = cap.get(0).unwrap();
+            let (line, column) = lines.position(script, full_match.start());
+
+            errors.push(ValidationError::new(
+                &format!("Unknown enum member: {}.{}", enum_name, member),
+                line,
+                column,
+                ErrorLevel::Error,
+                self.api.suggest_member(enum_name, member)
+                    .or_else(|| Some(format!("Check documentation for members of {}", enum_name))),
+            ));
         }
 
         errors
@@ -225,9 +521,511 @@ impl ScriptValidator {
     }
 }
 
+/// Top-level arguments of a call, given the text right after its `(`;
+/// `None` when the closing parenthesis is missing
+fn split_call_args(rest: &str) -> Option<Vec<&str>> {
+    let mut args = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (i, c) in rest.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            ')' => {
+                let last = rest[start..i].trim();
+                if !last.is_empty() || !args.is_empty() {
+                    args.push(last);
+                }
+                return Some(args);
+            }
+            ',' if depth == 0 => {
+                args.push(rest[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Checks a call against the modeled signatures of a method. A call no
+/// overload accepts reports the problems of the closest one, and lists the
+/// overloads considered.
+fn check_call_arguments(
+    receiver: &str,
+    method_name: &str,
+    method: &MethodValidation,
+    args: &[&str],
+    variable_types: &HashMap<String, String>,
+    line: usize,
+    column: usize,
+) -> Vec<ValidationError> {
+    let resolved = method.resolve(args.len(), |signature| {
+        check_signature_arguments(receiver, method_name, signature, args, variable_types, line, column)
+    });
+    let mut errors = match resolved {
+        Ok(_) => return Vec::new(),
+        Err((_, errors)) => errors,
+    };
+    if method.overloads.is_empty() {
+        return errors;
+    }
+
+    if !method.signatures().any(|signature| signature.accepts_arg_count(args.len())) {
+        let mut expected: Vec<String> = Vec::new();
+        for count in method.signatures().map(arity) {
+            if !expected.contains(&count) {
+                expected.push(count);
+            }
+        }
+        errors = vec![ValidationError::new(
+            &format!("{}.{} expects {} argument(s), got {}", receiver, method_name, expected.join(" or "), args.len()),
+            line,
+            column,
+            ErrorLevel::Error,
+            None,
+        )];
+    }
+    let considered: Vec<String> = method.signatures().map(|overload| signature(method_name, overload)).collect();
+    for error in &mut errors {
+        error.suggestions.retain(|s| !s.message.starts_with("Signature: "));
+        error.suggestions.push(Suggestion::new(format!("No overload matches; considered: {}", considered.join("; "))));
+    }
+    errors
+}
+
+/// Argument counts a signature accepts, e.g. `2` or `1 to 3`
+fn arity(method: &MethodValidation) -> String {
+    let max = method.param_count + method.optional_params.len();
+    if max == method.param_count {
+        format!("{}", max)
+    } else {
+        format!("{} to {}", method.param_count, max)
+    }
+}
+
+/// Checks the argument count and literal argument types of a call against
+/// one modeled signature
+fn check_signature_arguments(
+    receiver: &str,
+    method_name: &str,
+    method: &MethodValidation,
+    args: &[&str],
+    variable_types: &HashMap<String, String>,
+    line: usize,
+    column: usize,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    if !method.accepts_arg_count(args.len()) {
+        errors.push(ValidationError::new(
+            &format!("{}.{} expects {} argument(s), got {}", receiver, method_name, arity(method), args.len()),
+            line,
+            column,
+            ErrorLevel::Error,
+            Some(format!("Signature: {}", signature(method_name, method))),
+        ));
+        return errors;
+    }
+
+    for (index, arg) in args.iter().enumerate() {
+        let expected = match method.param_type(index) {
+            Some(expected) => expected,
+            None => continue,
+        };
+        if let Some(problem) = literal_mismatch(arg, expected) {
+            errors.push(ValidationError::new(
+                &format!("Argument {} of {}.{} {}", index + 1, receiver, method_name, problem),
+                line,
+                column,
+                ErrorLevel::Error,
+                Some(format!("Signature: {}", signature(method_name, method))),
+            ));
+        } else if let Some((problem, suggestion)) = object_mismatch(arg, expected, variable_types) {
+            errors.push(ValidationError::new(
+                &format!("Argument {} of {}.{} {}", index + 1, receiver, method_name, problem),
+                line,
+                column,
+                ErrorLevel::Error,
+                Some(suggestion.unwrap_or_else(|| format!("Signature: {}", signature(method_name, method)))),
+            ));
+        } else if let Some(problem) = range_violation(arg, method, index) {
+            errors.push(ValidationError::new(
+                &format!("Argument {} of {}.{} is out of range: {}", index + 1, receiver, method_name, problem),
+                line,
+                column,
+                ErrorLevel::Error,
+                color_fix(arg).or_else(|| method.param_range(index)
+                    .filter(|range| !range.description.is_empty())
+                    .map(|range| range.description.clone())),
+            ));
+        } else if let Some(problem) = schema_violation(arg, method, index) {
+            errors.push(ValidationError::new(
+                &format!("Argument {} of {}.{} is invalid: {}", index + 1, receiver, method_name, problem),
+                line,
+                column,
+                ErrorLevel::Error,
+                Some(format!("Signature: {}", signature(method_name, method))),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Describes why a literal argument cannot have the expected type; non-literal
+/// arguments are never reported
+fn literal_mismatch(arg: &str, expected: &PropertyValueType) -> Option<String> {
+    let is_string = arg.starts_with('"') || arg.starts_with('\'');
+    let is_number = arg.parse::<f64>().is_ok();
+    let array_len = arg.strip_prefix('[').and_then(|a| a.strip_suffix(']')).map(|inner| {
+        split_call_args(&format!("{})", inner)).map_or(0, |items| items.len())
+    });
+
+    let found = if is_string {
+        "a string"
+    } else if is_number {
+        "a number"
+    } else if array_len.is_some() {
+        "an array"
+    } else {
+        return None;
+    };
+
+    let (wanted, ok) = match expected {
+        PropertyValueType::ArbText => ("a string", is_string),
+        PropertyValueType::OneD | PropertyValueType::LayerIndex | PropertyValueType::MaskIndex => ("a number", is_number),
+        PropertyValueType::TwoD | PropertyValueType::TwoDSpatial => ("a 2-element array", array_len == Some(2)),
+        PropertyValueType::ThreeD | PropertyValueType::ThreeDSpatial => ("a 3-element array", array_len == Some(3)),
+        PropertyValueType::Color => ("an [r, g, b] array", matches!(array_len, Some(3) | Some(4))),
+        _ => return None,
+    };
+
+    match (ok, array_len) {
+        (true, _) => None,
+        (false, Some(len)) => Some(format!("should be {}, got an array of {}", wanted, len)),
+        (false, None) => Some(format!("should be {}, got {}", wanted, found)),
+    }
+}
+
+/// Classes unrelated by inheritance, so an argument inferred as one of them
+/// can never stand in for another
+const DISTINCT_ARGUMENT_CLASSES: &[&str] = &["File", "Folder", "ImportOptions"];
+
+/// Describes an argument whose inferred class differs from the expected one,
+/// with a fix when there is an obvious one
+fn object_mismatch(
+    arg: &str,
+    expected: &PropertyValueType,
+    variable_types: &HashMap<String, String>,
+) -> Option<(String, Option<String>)> {
+    let wanted = match expected {
+        PropertyValueType::Custom(class) if DISTINCT_ARGUMENT_CLASSES.contains(&class.as_str()) => class.as_str(),
+        _ => return None,
+    };
+    let found = infer_expression_type(arg, variable_types)?;
+    if found == wanted || !DISTINCT_ARGUMENT_CLASSES.contains(&found.as_str()) {
+        return None;
+    }
+
+    let suggestion = match (found.as_str(), wanted) {
+        ("File", "ImportOptions") => Some(format!("Wrap the file: new ImportOptions({})", arg)),
+        ("File", "Folder") => Some(format!("Pass the containing folder: {}.parent", arg)),
+        _ => None,
+    };
+    let article = if wanted.starts_with(['A', 'E', 'I', 'O', 'U']) { "an" } else { "a" };
+    Some((format!("should be {} {} object, got a {}", article, wanted, found), suggestion))
+}
+
+/// Checks a numeric or color literal against the parameter's declared range
+/// using the same rules as property values
+fn range_violation(arg: &str, method: &MethodValidation, index: usize) -> Option<String> {
+    let rule = method.param_rule(index)?;
+    let ranged = rule.range_min.is_some() || rule.range_max.is_some();
+    if !ranged && rule.value_type != PropertyValueType::Color {
+        return None;
+    }
+    let value: Value = serde_json::from_str(arg).ok()?;
+    validate_property_value(&value, &rule).err()
+}
+
+/// Checks a literal argument field by field against the parameter's schema,
+/// e.g. each `KeyframeEase` of `setTemporalEaseAtKey`
+fn schema_violation(arg: &str, method: &MethodValidation, index: usize) -> Option<String> {
+    let schema = method.param_schema(index)?;
+    schema.validate(&literal_value(arg)?).err()
+}
+
+/// Autofix text for a color literal written with 0-255 components
+fn color_fix(arg: &str) -> Option<String> {
+    ColorValue::parse(arg)
+        .filter(ColorValue::looks_8bit)
+        .map(|color| format!("Color components are 0-1 floats; divide by 255: {}", color.from_8bit().to_literal()))
+}
+
+fn signature(method_name: &str, method: &MethodValidation) -> String {
+    let mut params: Vec<String> = (0..method.param_count)
+        .map(|i| method.param_type(i).map_or("any".to_string(), |t| format!("{:?}", t)))
+        .collect();
+    params.extend(method.optional_params.iter().map(|t| format!("[{:?}]", t)));
+    match &method.return_type {
+        Some(returns) => format!("{}({}) -> {}", method_name, params.join(", "), returns),
+        None => format!("{}({})", method_name, params.join(", ")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::typeimport::{merge_declarations, parse_declarations};
+
+    #[test]
+    fn test_unknown_method_suggestions() {
+        let mut validator = ScriptValidator::new();
+        let script = "var target = comp.layer(1);\ntarget.duplicat();\ntarget.setValue(1);\ntarget.remove();\n";
+        let errors = validator.validate_api_usage(script);
+
+        let typo = errors.iter().find(|e| e.message == "Invalid method call: target.duplicat").unwrap();
+        assert_eq!(typo.suggestion(), Some("Did you mean 'duplicate'?"));
+        let rename = typo.suggestions[0].replacement_span.as_ref().unwrap();
+        assert_eq!((rename.line, rename.column, rename.length, rename.text.as_str()), (2, 8, 8, "duplicate"));
+
+        let wrong_class = errors.iter().find(|e| e.message == "Invalid method call: target.setValue").unwrap();
+        assert_eq!(
+            wrong_class.suggestion(),
+            Some("'setValue' exists on Property, but 'target' was inferred as Layer")
+        );
+        assert_eq!(wrong_class.docs_url.as_deref(), Some("https://ae-scripting.docsforadobe.dev/layer/layer/"));
+
+        assert!(!errors.iter().any(|e| e.message == "Invalid method call: target.remove"));
+    }
+
+    #[test]
+    fn test_instanceof_guards_narrow_receivers() {
+        let mut validator = ScriptValidator::new();
+        let errors = validator.validate_api_usage(r#"var item = app.project.item(1);
+if (item instanceof CompItem) {
+    item.layer(1);
+    var count = item.numLayers;
+} else if (item instanceof FolderItem) {
+    item.item(1);
+}
+item.layer(1);
+"#);
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.iter().filter(|m| **m == "Invalid method call: item.layer").count(), 1);
+        assert!(!messages.contains(&"Invalid property access: item.numLayers"));
+        assert!(!messages.contains(&"Invalid method call: item.item"));
+    }
+
+    #[test]
+    fn test_registered_modules_resolve_member_calls() {
+        let mut validator = ScriptValidator::new();
+        validator.register_modules("var MyLib = MyLib || {};\nMyLib.createTitle = function (comp, text) {};\n");
+        let errors = validator.validate_api_usage("var comp = app.project.activeItem;\nMyLib.createTitle(comp, \"Hi\");\nMyLib.createTitel(comp);\n");
+
+        assert!(!errors.iter().any(|e| e.message == "Invalid method call: MyLib.createTitle"));
+        assert!(errors.iter().any(|e| e.message == "Invalid method call: MyLib.createTitel"));
+    }
+
+    #[test]
+    fn test_collection_add_methods_check_arity_and_types() {
+        let mut validator = ScriptValidator::new();
+        let errors = validator.validate_api_usage(r#"var comp = app.project.items.addComp("Main", 1920, 1080, 1, 10, 30);
+var bg = comp.layers.addSolid([0, 0, 0], "BG", 1920);
+var title = comp.layers.addText(42);
+var cam = comp.layers.addCamera("Cam", [960, 540]);
+var box = comp.layers.addBoxText([400, 200, 1], "Title");
+"#);
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.contains(&"layers.addSolid expects 5 to 6 argument(s), got 3"));
+        assert!(messages.contains(&"Argument 1 of layers.addText should be a string, got a number"));
+        assert!(messages.contains(&"Argument 1 of layers.addBoxText should be a 2-element array, got an array of 3"));
+        assert!(!messages.iter().any(|m| m.contains("addComp") || m.contains("addCamera")));
+    }
+
+    #[test]
+    fn test_option_objects_are_checked_by_key() {
+        let mut validator = ScriptValidator::new();
+        let declarations = parse_declarations("declare class Studio {
+  makeComp(name: string, options: { width: number; height: number; fps?: number }): CompItem
+}");
+        merge_declarations(validator.api_mut(), &declarations);
+        let errors = validator.validate_api_usage(r#"Studio.makeComp("Main", {width: 1920, height: 1080, fps: 30});
+Studio.makeComp("Thumb", {width: 320, hieght: 180});
+Studio.makeComp("Square", {width: 1080, height: "1080"});
+Studio.makeComp("Sized", size);
+"#);
+        let messages: Vec<&str> = errors.iter()
+            .map(|e| e.message.as_str())
+            .filter(|m| m.contains("is invalid"))
+            .collect();
+        assert_eq!(messages, vec![
+            "Argument 2 of Studio.makeComp is invalid: Value has no option 'hieght'; did you mean 'height'?",
+            "Argument 2 of Studio.makeComp is invalid: value.height must be a number, got \"1080\"",
+        ]);
+    }
+
+    #[test]
+    fn test_overloaded_methods_resolve_best_match() {
+        let mut validator = ScriptValidator::new();
+        let errors = validator.validate_api_usage(r#"var comp = app.project.activeItem;
+var key = comp.layers.addLight("Key", [960, 540]);
+var fill = comp.layers.addLight("Fill", [960, 540, -500]);
+var rim = comp.layers.addLight("Rim", [1, 2, 3, 4]);
+var extra = comp.layers.addLight("Extra");
+var intensity = key.property("Intensity");
+intensity.setValue(80);
+intensity.setValue([1, 0.9, 0.8, 1]);
+intensity.setValue("80%");
+intensity.setValue([1, 2, 3, 4, 5]);
+"#);
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).filter(|m| m.contains("setValue") || m.contains("addLight")).collect();
+        assert_eq!(messages, vec![
+            "Argument 2 of layers.addLight should be a 2-element array, got an array of 4",
+            "layers.addLight expects 2 argument(s), got 1",
+            "Argument 1 of intensity.setValue should be a number, got an array of 5",
+        ]);
+        let rim = errors.iter().find(|e| e.message.contains("got an array of 4")).unwrap();
+        assert_eq!(
+            rim.suggestion(),
+            Some("No overload matches; considered: addLight(ArbText, TwoD) -> LightLayer; addLight(ArbText, ThreeD) -> LightLayer")
+        );
+    }
+
+    #[test]
+    fn test_creation_arguments_respect_ae_limits() {
+        let mut validator = ScriptValidator::new();
+        let errors = validator.validate_api_usage(r#"var comp = app.project.items.addComp("Main", 2, 1080, 1, -5, 120);
+var bg = comp.layers.addSolid([1.5, 0, 0], "BG", 1920, 1080, 0);
+var ok = comp.layers.addSolid([1, 1, 1], "White", 1920, 1080, 1, 10);
+"#);
+        let messages: Vec<&str> = errors.iter()
+            .map(|e| e.message.as_str())
+            .filter(|m| m.contains("out of range"))
+            .collect();
+        assert_eq!(messages, vec![
+            "Argument 2 of items.addComp is out of range: Value 2 is less than minimum 4",
+            "Argument 5 of items.addComp is out of range: Value -5 is less than minimum 0",
+            "Argument 6 of items.addComp is out of range: Value 120 is greater than maximum 99",
+            "Argument 1 of layers.addSolid is out of range: Color values must be between 0 and 1",
+            "Argument 5 of layers.addSolid is out of range: Value 0 is less than minimum 0.01",
+        ]);
+        let width = errors.iter().find(|e| e.message.starts_with("Argument 2 of items.addComp")).unwrap();
+        assert_eq!(width.suggestion(), Some("width must be between 4 and 30000"));
+
+        let errors = validator.validate_api_usage("var red = comp.layers.addSolid([255, 0, 0], \"Red\", 100, 100, 1);");
+        let color = errors.iter().find(|e| e.message.starts_with("Argument 1 of layers.addSolid")).unwrap();
+        assert_eq!(color.suggestion(), Some("Color components are 0-1 floats; divide by 255: [1, 0, 0]"));
+    }
+
+    #[test]
+    fn test_composite_arguments_are_checked_by_field() {
+        let mut validator = ScriptValidator::new();
+        let errors = validator.validate_api_usage(r#"var prop = app.project.activeItem.layer(1).property("Opacity");
+prop.setTemporalEaseAtKey(1, [new KeyframeEase(0, 33)], [new KeyframeEase(0, 150)]);
+prop.setTemporalEaseAtKey(2, [0.5, 75], easeOut);
+prop.setSpatialTangentsAtKey(1, [0, 0], [0, 0, 0, 0]);
+"#);
+        let messages: Vec<&str> = errors.iter()
+            .map(|e| e.message.as_str())
+            .filter(|m| m.contains("is invalid"))
+            .collect();
+        assert_eq!(messages, vec![
+            "Argument 3 of prop.setTemporalEaseAtKey is invalid: [0].influence must be between 0.1 and 100, got 150",
+            "Argument 2 of prop.setTemporalEaseAtKey is invalid: [0] must be a KeyframeEase, got 0.5",
+            "Argument 3 of prop.setSpatialTangentsAtKey is invalid: Value must have 2 to 3 elements, got 4",
+        ]);
+    }
+
+    #[test]
+    fn test_guides_and_motion_graphics_templates() {
+        let mut validator = ScriptValidator::new();
+        let errors = validator.validate_api_usage(r#"var comp = app.project.activeItem;
+var guide = comp.addGuide(1, 960);
+comp.setGuide(540, guide);
+comp.addGuide(2, 100);
+comp.openInEssentialGraphics();
+var exported = comp.exportAsMotionGraphicsTemplate(true, "~/Desktop/title.mogrt");
+"#);
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.contains(&"Argument 1 of comp.addGuide is out of range: Value 2 is greater than maximum 1"));
+        assert!(!messages.iter().any(|m| m.contains("Invalid method call")));
+    }
+
+    #[test]
+    fn test_import_file_expects_import_options() {
+        let mut validator = ScriptValidator::new();
+        let errors = validator.validate_api_usage(r#"var footage = new File("~/footage/clip.mov");
+app.project.importFile(footage);
+var options = new ImportOptions(footage);
+options.importAs = ImportAsType.COMP;
+options.importAs = "FOOTAGE";
+if (options.canImportAs(ImportAsType.FOOTAGE)) {
+    app.project.importFile(options);
+}
+"#);
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert!(messages.contains(&"Argument 1 of project.importFile should be an ImportOptions object, got a File"));
+        assert!(messages.contains(&"Invalid value for ImportOptions.importAs: \"FOOTAGE\""));
+        assert!(!messages.iter().any(|m| m.contains("ImportAsType.COMP") || m.contains("canImportAs")));
+        let wrap = errors.iter().find(|e| e.message.contains("importFile")).unwrap();
+        assert_eq!(wrap.suggestion(), Some("Wrap the file: new ImportOptions(footage)"));
+    }
+
+    #[test]
+    fn test_cancelled_validation_is_truncated() {
+        let mut validator = ScriptValidator::new();
+        let script = "const x = 1;\n";
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let report = validator.validate_script_with(script, &ValidateOptions::default().with_cancellation(token));
+        assert!(report.pass_timings.is_empty());
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].get_message(), "Validation truncated: cancelled before the syntax pass; 0 of 5 passes ran");
+
+        let report = validator.validate_script_with(script, &ValidateOptions::default().with_timeout(Duration::ZERO));
+        assert_eq!(report.rule_hits.get("validation-truncated"), Some(&1));
+
+        let report = validator.validate_script_with(script, &ValidateOptions::default().with_timeout(Duration::from_secs(60)));
+        assert_eq!(report.pass_timings.len(), 5);
+        assert_eq!(report.rule_hits.get("validation-truncated"), None);
+    }
+
+    #[test]
+    fn test_validation_report() {
+        let mut validator = ScriptValidator::new();
+        let report = validator.validate_script_report("const x = 1;\nvar y = `${x}`;\n");
+
+        assert!(!report.is_valid());
+        assert_eq!(report.pass_timings.len(), 5);
+        assert_eq!(report.rule_hits.get("es6-features"), Some(&2));
+        assert_eq!(report.errors.len(), report.rule_hits.values().sum::<usize>());
+        assert!(report.summary().starts_with(&format!("{} errors, 0 warnings, 0 infos in ", report.errors.len())));
+    }
+
+    #[test]
+    fn test_unknown_enum_member_suggestion() {
+        let validator = ScriptValidator::new();
+        let errors = validator.validate_enum_members("layer.blendingMode = BlendingMode.MULTIPLI;\nlayer.quality = LayerQuality.BEST;");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].suggestion(), Some("Did you mean 'MULTIPLY'?"));
+    }
 
     #[test]
     fn test_validate_temporal_ease() {